@@ -5,11 +5,14 @@ use crate::communications::broadcast_handler::BroadcastHandle;
 use crate::communications::connection_manager::{ConnectionManager, REDIRECT_TIMEOUT_MILLIS};
 use crate::communications::node_conversation::ClientError;
 use crate::terminal::terminal_interface::TerminalWrapper;
+use crossbeam_channel::Receiver;
 use masq_lib::constants::{TIMEOUT_ERROR, UNMARSHAL_ERROR};
+use masq_lib::short_writeln;
 use masq_lib::ui_gateway::MessageBody;
 use std::fmt::{Debug, Formatter};
 use std::io;
 use std::io::{Read, Write};
+use std::str::FromStr;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ContextError {
@@ -45,6 +48,30 @@ impl From<ClientError> for ContextError {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Json,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Human
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            other => Err(format!("Unrecognized output format: '{}'", other)),
+        }
+    }
+}
+
 pub trait CommandContext {
     fn active_port(&self) -> Option<u16>;
     fn send(&mut self, message: MessageBody) -> Result<(), ContextError>;
@@ -56,15 +83,21 @@ pub trait CommandContext {
     fn stdin(&mut self) -> &mut dyn Read;
     fn stdout(&mut self) -> &mut dyn Write;
     fn stderr(&mut self) -> &mut dyn Write;
+    fn broadcast_receiver_opt(&self) -> Option<Receiver<MessageBody>>;
     fn close(&mut self);
 }
 
+type JsonAwareStreams = (Box<dyn Write>, Box<dyn Write>, Option<Box<dyn Write>>);
+
 pub struct CommandContextReal {
     connection: ConnectionManager,
     pub stdin: Box<dyn Read>,
     pub stdout: Box<dyn Write>,
     pub stderr: Box<dyn Write>,
+    pub json_output_opt: Option<Box<dyn Write>>,
+    pub output_format: OutputFormat,
     pub terminal_interface: Option<TerminalWrapper>,
+    pub broadcast_rx_opt: Option<Receiver<MessageBody>>,
 }
 
 impl Debug for CommandContextReal {
@@ -100,6 +133,12 @@ impl CommandContext for CommandContextReal {
                 Ok(_) => message,
             },
         };
+        if let (Some(json_output), Ok(payload)) = (
+            self.json_output_opt.as_mut(),
+            incoming_message.payload.as_ref(),
+        ) {
+            short_writeln!(json_output, "{}", payload);
+        }
         Ok(incoming_message)
     }
 
@@ -115,6 +154,10 @@ impl CommandContext for CommandContextReal {
         &mut self.stderr
     }
 
+    fn broadcast_receiver_opt(&self) -> Option<Receiver<MessageBody>> {
+        self.broadcast_rx_opt.clone()
+    }
+
     fn close(&mut self) {
         self.connection.close();
     }
@@ -123,22 +166,41 @@ impl CommandContext for CommandContextReal {
 impl CommandContextReal {
     pub fn new(
         daemon_ui_port: u16,
+        instance_opt: Option<String>,
+        output_format: OutputFormat,
         foreground_terminal_interface: Option<TerminalWrapper>,
         generic_broadcast_handle: Box<dyn BroadcastHandle>,
     ) -> Result<Self, ContextError> {
         let mut connection = ConnectionManager::new();
         match connection.connect(
             daemon_ui_port,
+            instance_opt,
             generic_broadcast_handle,
             REDIRECT_TIMEOUT_MILLIS,
         ) {
-            Ok(_) => Ok(Self {
-                connection,
-                stdin: Box::new(io::stdin()),
-                stdout: Box::new(io::stdout()),
-                stderr: Box::new(io::stderr()),
-                terminal_interface: foreground_terminal_interface,
-            }),
+            Ok(_) => {
+                //in JSON mode the human-readable rendering, errors included, is swallowed; the raw
+                //response payloads go to the real stdout, and errors reach the real stderr as JSON
+                //from the mode that runs the command
+                let (stdout, stderr, json_output_opt): JsonAwareStreams = match output_format {
+                    OutputFormat::Human => (Box::new(io::stdout()), Box::new(io::stderr()), None),
+                    OutputFormat::Json => (
+                        Box::new(io::sink()),
+                        Box::new(io::sink()),
+                        Some(Box::new(io::stdout())),
+                    ),
+                };
+                Ok(Self {
+                    connection,
+                    stdin: Box::new(io::stdin()),
+                    stdout,
+                    stderr,
+                    json_output_opt,
+                    output_format,
+                    terminal_interface: foreground_terminal_interface,
+                    broadcast_rx_opt: None,
+                })
+            }
             Err(e) => Err(ConnectionRefused(format!("{:?}", e))),
         }
     }
@@ -209,7 +271,14 @@ mod tests {
         let handle = server.start();
         let broadcast_handle = BroadcastHandleInactive;
 
-        let subject = CommandContextReal::new(port, None, Box::new(broadcast_handle)).unwrap();
+        let subject = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        )
+        .unwrap();
 
         assert_eq!(subject.active_port(), Some(port));
         handle.stop();
@@ -228,7 +297,14 @@ mod tests {
         let stop_handle = server.start();
         let broadcast_handle = BroadcastHandleInactive;
 
-        let mut subject = CommandContextReal::new(port, None, Box::new(broadcast_handle)).unwrap();
+        let mut subject = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        )
+        .unwrap();
         subject.stdin = Box::new(stdin);
         subject.stdout = Box::new(stdout);
         subject.stderr = Box::new(stderr);
@@ -255,13 +331,67 @@ mod tests {
         stop_handle.stop();
     }
 
+    #[test]
+    fn transact_writes_response_payload_to_json_output_in_json_mode() {
+        running_test();
+        let port = find_free_port();
+        let stdout = ByteArrayWriter::new();
+        let stdout_arc = stdout.inner_arc();
+        let json_output = ByteArrayWriter::new();
+        let json_output_arc = json_output.inner_arc();
+        let server = MockWebSocketsServer::new(port).queue_response(UiShutdownResponse {}.tmb(1));
+        let stop_handle = server.start();
+        let broadcast_handle = BroadcastHandleInactive;
+        let mut subject = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Json,
+            None,
+            Box::new(broadcast_handle),
+        )
+        .unwrap();
+        assert!(subject.json_output_opt.is_some());
+        subject.stdout = Box::new(stdout);
+        subject.json_output_opt = Some(Box::new(json_output));
+
+        subject.transact(UiShutdownRequest {}.tmb(1), 1000).unwrap();
+        write!(subject.stdout(), "This is stdout.").unwrap();
+
+        assert_eq!(subject.output_format, OutputFormat::Json);
+        assert_eq!(
+            json_output_arc.lock().unwrap().get_string(),
+            "{}\n".to_string()
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "This is stdout.".to_string()
+        );
+        stop_handle.stop();
+    }
+
+    #[test]
+    fn output_format_is_parsed_from_its_name() {
+        assert_eq!(OutputFormat::from_str("human"), Ok(OutputFormat::Human));
+        assert_eq!(OutputFormat::from_str("json"), Ok(OutputFormat::Json));
+        assert_eq!(
+            OutputFormat::from_str("xml"),
+            Err("Unrecognized output format: 'xml'".to_string())
+        );
+    }
+
     #[test]
     fn works_when_server_isnt_present() {
         running_test();
         let port = find_free_port();
         let broadcast_handle = BroadcastHandleInactive;
 
-        let result = CommandContextReal::new(port, None, Box::new(broadcast_handle));
+        let result = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        );
 
         match result {
             Err(ConnectionRefused(_)) => (),
@@ -281,7 +411,14 @@ mod tests {
         });
         let stop_handle = server.start();
         let broadcast_handle = BroadcastHandleInactive;
-        let mut subject = CommandContextReal::new(port, None, Box::new(broadcast_handle)).unwrap();
+        let mut subject = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        )
+        .unwrap();
 
         let response = subject.transact(UiSetupRequest { values: vec![] }.tmb(1), 1000);
 
@@ -296,7 +433,14 @@ mod tests {
         let server = MockWebSocketsServer::new(port).queue_string("disconnect");
         let stop_handle = server.start();
         let broadcast_handle = BroadcastHandleInactive;
-        let mut subject = CommandContextReal::new(port, None, Box::new(broadcast_handle)).unwrap();
+        let mut subject = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        )
+        .unwrap();
 
         let response = subject.transact(UiSetupRequest { values: vec![] }.tmb(1), 1000);
 
@@ -319,7 +463,13 @@ mod tests {
         let server = MockWebSocketsServer::new(port);
         let stop_handle = server.start();
         let broadcast_handle = BroadcastHandleInactive;
-        let subject_result = CommandContextReal::new(port, None, Box::new(broadcast_handle));
+        let subject_result = CommandContextReal::new(
+            port,
+            None,
+            OutputFormat::Human,
+            None,
+            Box::new(broadcast_handle),
+        );
         let mut subject = subject_result.unwrap();
         subject.stdin = Box::new(stdin);
         subject.stdout = Box::new(stdout);
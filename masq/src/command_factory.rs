@@ -1,22 +1,33 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::command_factory::CommandFactoryError::{CommandSyntax, UnrecognizedSubcommand};
+use crate::commands::backup_command::BackupCommand;
+use crate::commands::ban_command::{BanCommand, UnbanCommand};
+use crate::commands::ban_list_command::BanListCommand;
 use crate::commands::change_password_command::ChangePasswordCommand;
 use crate::commands::check_password_command::CheckPasswordCommand;
 use crate::commands::commands_common::Command;
 use crate::commands::configuration_command::ConfigurationCommand;
 use crate::commands::connection_status_command::ConnectionStatusCommand;
 use crate::commands::crash_command::CrashCommand;
+use crate::commands::dashboard_command::DashboardCommand;
 use crate::commands::descriptor_command::DescriptorCommand;
+use crate::commands::discover_wallets_command::DiscoverWalletsCommand;
+use crate::commands::financials_command::export::FinancialsExportCommand;
 use crate::commands::financials_command::FinancialsCommand;
+use crate::commands::forgive_debt_command::ForgiveDebtCommand;
 use crate::commands::generate_wallets_command::GenerateWalletsCommand;
+use crate::commands::logs_command::LogsCommand;
 use crate::commands::recover_wallets_command::RecoverWalletsCommand;
+use crate::commands::restore_command::RestoreCommand;
+use crate::commands::rotate_earning_wallet_command::RotateEarningWalletCommand;
 use crate::commands::scan_command::ScanCommand;
 use crate::commands::set_configuration_command::SetConfigurationCommand;
 use crate::commands::setup_command::SetupCommand;
 use crate::commands::shutdown_command::ShutdownCommand;
 use crate::commands::start_command::StartCommand;
 use crate::commands::wallet_addresses_command::WalletAddressesCommand;
+use crate::commands::wallet_balances_command::WalletBalancesCommand;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum CommandFactoryError {
@@ -34,6 +45,15 @@ pub struct CommandFactoryReal;
 impl CommandFactory for CommandFactoryReal {
     fn make(&self, pieces: &[String]) -> Result<Box<dyn Command>, CommandFactoryError> {
         let boxed_command: Box<dyn Command> = match pieces[0].as_str() {
+            "backup" => match BackupCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "ban" => match BanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "ban-list" => Box::new(BanListCommand::new()),
             "change-password" => match ChangePasswordCommand::new_change(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -51,19 +71,49 @@ impl CommandFactory for CommandFactoryReal {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "dashboard" => match DashboardCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "descriptor" => Box::new(DescriptorCommand::new()),
+            "discover-wallets" => match DiscoverWalletsCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "financials" if pieces.get(1).map(|piece| piece.as_str()) == Some("export") => {
+                match FinancialsExportCommand::new(pieces) {
+                    Ok(command) => Box::new(command),
+                    Err(msg) => return Err(CommandSyntax(msg)),
+                }
+            }
             "financials" => match FinancialsCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "forgive-debt" => match ForgiveDebtCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "generate-wallets" => match GenerateWalletsCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "logs" => match LogsCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "recover-wallets" => match RecoverWalletsCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "restore" => match RestoreCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
+            "rotate-earning-wallet" => match RotateEarningWalletCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "scan" => match ScanCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
@@ -80,12 +130,20 @@ impl CommandFactory for CommandFactoryReal {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
-            "shutdown" => Box::new(ShutdownCommand::new()),
+            "shutdown" => match ShutdownCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "start" => Box::new(StartCommand::new()),
+            "unban" => match UnbanCommand::new(pieces) {
+                Ok(command) => Box::new(command),
+                Err(msg) => return Err(CommandSyntax(msg)),
+            },
             "wallet-addresses" => match WalletAddressesCommand::new(pieces) {
                 Ok(command) => Box::new(command),
                 Err(msg) => return Err(CommandSyntax(msg)),
             },
+            "wallet-balances" => Box::new(WalletBalancesCommand::new()),
             unrecognized => return Err(UnrecognizedSubcommand(unrecognized.to_string())),
         };
         Ok(boxed_command)
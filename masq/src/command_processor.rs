@@ -1,10 +1,12 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::command_context::CommandContextReal;
-use crate::command_context::{CommandContext, ContextError};
+use crate::command_context::{CommandContext, ContextError, OutputFormat};
 use crate::commands::commands_common::{Command, CommandError};
 use crate::communications::broadcast_handler::BroadcastHandle;
 use crate::terminal::terminal_interface::TerminalWrapper;
+use crossbeam_channel::Receiver;
+use masq_lib::ui_gateway::MessageBody;
 use masq_lib::utils::ExpectValue;
 
 pub trait CommandProcessorFactory {
@@ -12,7 +14,10 @@ pub trait CommandProcessorFactory {
         &self,
         terminal_interface: Option<TerminalWrapper>,
         generic_broadcast_handle: Box<dyn BroadcastHandle>,
+        broadcast_rx_opt: Option<Receiver<MessageBody>>,
         ui_port: u16,
+        instance_opt: Option<String>,
+        output_format: OutputFormat,
     ) -> Result<Box<dyn CommandProcessor>, CommandError>;
 }
 
@@ -24,10 +29,22 @@ impl CommandProcessorFactory for CommandProcessorFactoryReal {
         &self,
         terminal_interface: Option<TerminalWrapper>,
         generic_broadcast_handle: Box<dyn BroadcastHandle>,
+        broadcast_rx_opt: Option<Receiver<MessageBody>>,
         ui_port: u16,
+        instance_opt: Option<String>,
+        output_format: OutputFormat,
     ) -> Result<Box<dyn CommandProcessor>, CommandError> {
-        match CommandContextReal::new(ui_port, terminal_interface, generic_broadcast_handle) {
-            Ok(context) => Ok(Box::new(CommandProcessorReal { context })),
+        match CommandContextReal::new(
+            ui_port,
+            instance_opt,
+            output_format,
+            terminal_interface,
+            generic_broadcast_handle,
+        ) {
+            Ok(mut context) => {
+                context.broadcast_rx_opt = broadcast_rx_opt;
+                Ok(Box::new(CommandProcessorReal { context }))
+            }
             Err(ContextError::ConnectionRefused(s)) => Err(CommandError::ConnectionProblem(s)),
             Err(e) => panic!("Unexpected error: {:?}", e),
         }
@@ -44,6 +61,7 @@ pub trait CommandProcessor {
     fn process(&mut self, command: Box<dyn Command>) -> Result<(), CommandError>;
     fn close(&mut self);
     fn terminal_wrapper_ref(&self) -> &TerminalWrapper;
+    fn output_format(&self) -> OutputFormat;
 }
 
 pub struct CommandProcessorReal {
@@ -70,6 +88,10 @@ impl CommandProcessor for CommandProcessorReal {
             .as_ref()
             .expectv("TerminalWrapper")
     }
+
+    fn output_format(&self) -> OutputFormat {
+        self.context.output_format
+    }
 }
 
 #[cfg(test)]
@@ -78,10 +100,10 @@ mod tests {
     use crate::command_context::CommandContext;
     use crate::commands::check_password_command::CheckPasswordCommand;
     use crate::communications::broadcast_handler::{
-        BroadcastHandleInactive, BroadcastHandler, BroadcastHandlerReal,
+        BroadcastHandleGeneric, BroadcastHandleInactive, BroadcastHandler, BroadcastHandlerReal,
     };
     use crate::test_utils::mocks::TestStreamFactory;
-    use crossbeam_channel::{bounded, Sender};
+    use crossbeam_channel::{bounded, unbounded, Sender};
     use masq_lib::messages::UiShutdownRequest;
     use masq_lib::messages::{ToMessageBody, UiCheckPasswordResponse, UiUndeliveredFireAndForget};
     use masq_lib::test_utils::mock_websockets_server::MockWebSocketsServer;
@@ -107,7 +129,14 @@ mod tests {
         let subject = CommandProcessorFactoryReal::new();
         let broadcast_handle = BroadcastHandleInactive;
 
-        let result = subject.make(None, Box::new(broadcast_handle), ui_port);
+        let result = subject.make(
+            None,
+            Box::new(broadcast_handle),
+            None,
+            ui_port,
+            None,
+            OutputFormat::Human,
+        );
 
         match result.err() {
             Some(CommandError::ConnectionProblem(_)) => (),
@@ -118,6 +147,54 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct BroadcastListeningCommand {
+        sender: Sender<Option<MessageBody>>,
+    }
+
+    impl Command for BroadcastListeningCommand {
+        fn execute<'a>(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+            let received_opt = context
+                .broadcast_receiver_opt()
+                .and_then(|rx| rx.recv_timeout(Duration::from_millis(1000)).ok());
+            self.sender.send(received_opt).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn factory_hands_broadcast_receiver_over_to_the_context() {
+        running_test();
+        let ui_port = find_free_port();
+        let server = MockWebSocketsServer::new(ui_port);
+        let stop_handle = server.start();
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        let broadcast = UiUndeliveredFireAndForget {
+            opcode: "whatever".to_string(),
+        }
+        .tmb(0);
+        broadcast_tx.send(broadcast.clone()).unwrap();
+        let (result_tx, result_rx) = bounded(1);
+        let subject = CommandProcessorFactoryReal::new();
+        let mut processor = subject
+            .make(
+                None,
+                Box::new(BroadcastHandleGeneric::new(broadcast_tx)),
+                Some(broadcast_rx),
+                ui_port,
+                None,
+                OutputFormat::Human,
+            )
+            .unwrap();
+
+        processor
+            .process(Box::new(BroadcastListeningCommand { sender: result_tx }))
+            .unwrap();
+
+        assert_eq!(result_rx.try_recv().unwrap(), Some(broadcast));
+        stop_handle.stop();
+    }
+
     #[test]
     fn process_locks_writing_and_prevents_interferences_from_unexpected_broadcast_messages() {
         running_test(); //don't remove
@@ -152,7 +229,14 @@ mod tests {
         let p_f = CommandProcessorFactoryReal::new();
         let stop_handle = server.start();
         let mut processor = p_f
-            .make(Some(terminal_interface), generic_broadcast_handle, ui_port)
+            .make(
+                Some(terminal_interface),
+                generic_broadcast_handle,
+                None,
+                ui_port,
+                None,
+                OutputFormat::Human,
+            )
             .unwrap();
         processor
             .process(Box::new(CheckPasswordCommand {
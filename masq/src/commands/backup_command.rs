@@ -0,0 +1,292 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiBackupRequest, UiBackupResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+use std::fs;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BackupCommand {
+    file: String,
+    db_password: String,
+}
+
+const BACKUP_SUBCOMMAND_ABOUT: &str =
+    "Writes an encrypted archive of the Node's configuration, wallets, payables, receivables, \
+     pending payables, bans and past neighbors to a file, so that they can be restored on other \
+     hardware. Only valid if Node is already running.";
+const FILE_ARG_HELP: &str = "Path of the file to write the archive to.";
+const DB_PASSWORD_ARG_HELP: &str =
+    "The current database password. The archive is encrypted with it, and it will be needed \
+     again to restore the archive.";
+
+pub fn backup_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("backup")
+        .about(BACKUP_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("file")
+                .help(FILE_ARG_HELP)
+                .index(1)
+                .value_name("FILE")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("db-password")
+                .help(DB_PASSWORD_ARG_HELP)
+                .long("db-password")
+                .value_name("DB-PASSWORD")
+                .required(true)
+                .case_insensitive(false)
+                .takes_value(true),
+        )
+}
+
+impl Command for BackupCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBackupRequest {
+            db_password: self.db_password.clone(),
+        };
+        let output: Result<UiBackupResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                if let Err(e) = fs::write(&self.file, &response.archive) {
+                    let error = CommandError::Other(format!(
+                        "Could not write archive to {}: {}",
+                        self.file, e
+                    ));
+                    short_writeln!(context.stderr(), "Backup failed: {:?}", error);
+                    return Err(error);
+                }
+                short_writeln!(
+                    context.stdout(),
+                    "Node state backed up to {} (database schema version {}).",
+                    self.file,
+                    response.schema_version
+                );
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Backup failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl BackupCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match backup_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            file: matches
+                .value_of("file")
+                .expect("file parameter is not properly required")
+                .to_string(),
+            db_password: matches
+                .value_of("db-password")
+                .expect("db-password parameter is not properly required")
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::BAD_PASSWORD_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            BACKUP_SUBCOMMAND_ABOUT,
+            "Writes an encrypted archive of the Node's configuration, wallets, payables, receivables, \
+             pending payables, bans and past neighbors to a file, so that they can be restored on other \
+             hardware. Only valid if Node is already running."
+        );
+        assert_eq!(FILE_ARG_HELP, "Path of the file to write the archive to.");
+        assert_eq!(
+            DB_PASSWORD_ARG_HELP,
+            "The current database password. The archive is encrypted with it, and it will be needed \
+             again to restore the archive."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory() {
+        let home_dir =
+            ensure_node_home_directory_exists("backup_command", "testing_command_factory");
+        let file = home_dir.join("node.backup");
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiBackupResponse {
+            archive: "0123abcd".to_string(),
+            schema_version: 12,
+        }
+        .tmb(0)));
+        let subject = factory
+            .make(&[
+                "backup".to_string(),
+                file.to_str().unwrap().to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+            ])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn backup_command_requires_a_password() {
+        let result = BackupCommand::new(&["backup".to_string(), "node.backup".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn backup_command_happy_path() {
+        let home_dir =
+            ensure_node_home_directory_exists("backup_command", "backup_command_happy_path");
+        let file = home_dir.join("node.backup");
+        let file_name = file.to_str().unwrap().to_string();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBackupResponse {
+                archive: "0123abcd".to_string(),
+                schema_version: 12,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BackupCommand::new(&[
+            "backup".to_string(),
+            file_name.clone(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBackupRequest {
+                    db_password: "password".to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(fs::read_to_string(&file).unwrap(), "0123abcd".to_string());
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Node state backed up to {} (database schema version 12).\n",
+                file_name
+            )
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn backup_command_reports_unwritable_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "backup_command",
+            "backup_command_reports_unwritable_file",
+        );
+        let file_name = home_dir
+            .join("nonexistent")
+            .join("node.backup")
+            .to_str()
+            .unwrap()
+            .to_string();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiBackupResponse {
+            archive: "0123abcd".to_string(),
+            schema_version: 12,
+        }
+        .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BackupCommand::new(&[
+            "backup".to_string(),
+            file_name.clone(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        match result {
+            Err(CommandError::Other(msg)) => assert!(
+                msg.starts_with(&format!("Could not write archive to {}: ", file_name)),
+                "{}",
+                msg
+            ),
+            x => panic!("Expected CommandError::Other, got {:?}", x),
+        }
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert!(stderr_arc
+            .lock()
+            .unwrap()
+            .get_string()
+            .starts_with("Backup failed: Other("));
+    }
+
+    #[test]
+    fn backup_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(BAD_PASSWORD_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BackupCommand::new(&[
+            "backup".to_string(),
+            "node.backup".to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                BAD_PASSWORD_ERROR,
+                "Booga".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Backup failed: Payload({}, \"Booga\")\n",
+                BAD_PASSWORD_ERROR
+            )
+        );
+    }
+}
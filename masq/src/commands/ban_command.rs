@@ -0,0 +1,378 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiBanRequest, UiBanResponse, UiUnbanRequest, UiUnbanResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BanCommand {
+    wallet: String,
+    note_opt: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnbanCommand {
+    wallet: String,
+}
+
+const BAN_SUBCOMMAND_ABOUT: &str =
+    "Bans a wallet, so that the Node refuses to serve it until it is unbanned. \
+     Only valid if Node is already running.";
+const UNBAN_SUBCOMMAND_ABOUT: &str =
+    "Lifts the ban on a wallet. A wallet banned for delinquency will be banned again at the next \
+     receivable scan if it still hasn't paid. Only valid if Node is already running.";
+const WALLET_ARG_HELP: &str = "Address of the wallet, starting with 0x.";
+const NOTE_ARG_HELP: &str = "Free-form note recording why the wallet is banned.";
+
+pub fn ban_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("ban")
+        .about(BAN_SUBCOMMAND_ABOUT)
+        .arg(wallet_arg())
+        .arg(
+            Arg::with_name("note")
+                .help(NOTE_ARG_HELP)
+                .long("note")
+                .value_name("NOTE")
+                .required(false)
+                .takes_value(true),
+        )
+}
+
+pub fn unban_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("unban")
+        .about(UNBAN_SUBCOMMAND_ABOUT)
+        .arg(wallet_arg())
+}
+
+fn wallet_arg() -> Arg<'static, 'static> {
+    Arg::with_name("wallet")
+        .help(WALLET_ARG_HELP)
+        .index(1)
+        .value_name("WALLET")
+        .required(true)
+}
+
+impl Command for BanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiBanRequest {
+            wallet: self.wallet.clone(),
+            note_opt: self.note_opt.clone(),
+        };
+        let output: Result<UiBanResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) if response.newly_banned => {
+                short_writeln!(context.stdout(), "Wallet {} is now banned.", self.wallet);
+                Ok(())
+            }
+            Ok(_) => {
+                short_writeln!(
+                    context.stdout(),
+                    "Wallet {} was already banned.",
+                    self.wallet
+                );
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Ban failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl BanCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match ban_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            wallet: matches
+                .value_of("wallet")
+                .expect("wallet parameter is not properly required")
+                .to_string(),
+            note_opt: matches.value_of("note").map(|note| note.to_string()),
+        })
+    }
+}
+
+impl Command for UnbanCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiUnbanRequest {
+            wallet: self.wallet.clone(),
+        };
+        let output: Result<UiUnbanResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) if response.was_banned => {
+                short_writeln!(
+                    context.stdout(),
+                    "Wallet {} is no longer banned.",
+                    self.wallet
+                );
+                Ok(())
+            }
+            Ok(_) => {
+                short_writeln!(context.stdout(), "Wallet {} was not banned.", self.wallet);
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Unban failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl UnbanCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match unban_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            wallet: matches
+                .value_of("wallet")
+                .expect("wallet parameter is not properly required")
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::BAN_LIST_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    const WALLET: &str = "0x1111111111111111111111111111111111111111";
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            BAN_SUBCOMMAND_ABOUT,
+            "Bans a wallet, so that the Node refuses to serve it until it is unbanned. \
+             Only valid if Node is already running."
+        );
+        assert_eq!(
+            UNBAN_SUBCOMMAND_ABOUT,
+            "Lifts the ban on a wallet. A wallet banned for delinquency will be banned again at the next \
+             receivable scan if it still hasn't paid. Only valid if Node is already running."
+        );
+        assert_eq!(WALLET_ARG_HELP, "Address of the wallet, starting with 0x.");
+        assert_eq!(
+            NOTE_ARG_HELP,
+            "Free-form note recording why the wallet is banned."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory_with_ban() {
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiBanResponse { newly_banned: true }.tmb(0)));
+        let subject = factory
+            .make(&["ban".to_string(), WALLET.to_string()])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn testing_command_factory_with_unban() {
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiUnbanResponse { was_banned: true }.tmb(0)));
+        let subject = factory
+            .make(&["unban".to_string(), WALLET.to_string()])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn ban_command_requires_a_wallet() {
+        let result = BanCommand::new(&["ban".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn ban_command_happy_path_with_note() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBanResponse { newly_banned: true }.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BanCommand::new(&[
+            "ban".to_string(),
+            WALLET.to_string(),
+            "--note".to_string(),
+            "Spams us".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBanRequest {
+                    wallet: WALLET.to_string(),
+                    note_opt: Some("Spams us".to_string()),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} is now banned.\n", WALLET)
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn ban_command_reports_wallet_already_banned() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBanResponse {
+                newly_banned: false,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = BanCommand::new(&["ban".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiBanRequest {
+                    wallet: WALLET.to_string(),
+                    note_opt: None,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} was already banned.\n", WALLET)
+        );
+    }
+
+    #[test]
+    fn ban_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(BAN_LIST_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BanCommand::new(&["ban".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(BAN_LIST_ERROR, "Booga".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!("Ban failed: Payload({}, \"Booga\")\n", BAN_LIST_ERROR)
+        );
+    }
+
+    #[test]
+    fn unban_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiUnbanResponse { was_banned: true }.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = UnbanCommand::new(&["unban".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiUnbanRequest {
+                    wallet: WALLET.to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} is no longer banned.\n", WALLET)
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn unban_command_reports_wallet_not_banned() {
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiUnbanResponse { was_banned: false }.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = UnbanCommand::new(&["unban".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("Wallet {} was not banned.\n", WALLET)
+        );
+    }
+
+    #[test]
+    fn unban_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(BAN_LIST_ERROR, "Booga".to_string()),
+        ));
+        let stderr_arc = context.stderr_arc();
+        let subject = UnbanCommand::new(&["unban".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(BAN_LIST_ERROR, "Booga".to_string()))
+        );
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!("Unban failed: Payload({}, \"Booga\")\n", BAN_LIST_ERROR)
+        );
+    }
+}
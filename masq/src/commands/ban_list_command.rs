@@ -0,0 +1,212 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{UiBanListRequest, UiBanListResponse, UiBannedWallet};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+use std::io::Write;
+use time::macros::format_description;
+use time::OffsetDateTime;
+
+#[derive(Debug)]
+pub struct BanListCommand {}
+
+const BAN_LIST_SUBCOMMAND_ABOUT: &str =
+    "Displays the wallets the Node refuses to serve, with the reason, time and any note recorded \
+     for each ban. Only valid if Node is already running.";
+
+pub fn ban_list_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("ban-list").about(BAN_LIST_SUBCOMMAND_ABOUT)
+}
+
+impl Command for BanListCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let output: Result<UiBanListResponse, CommandError> = transaction(
+            UiBanListRequest {},
+            context,
+            STANDARD_COMMAND_TIMEOUT_MILLIS,
+        );
+        match output {
+            Ok(response) => {
+                Self::write_ban_list(&response.banned, context.stdout());
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Ban list retrieval failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Default for BanListCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BanListCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    fn write_ban_list(banned: &[UiBannedWallet], stdout: &mut dyn Write) {
+        if banned.is_empty() {
+            short_writeln!(stdout, "No wallets are banned.");
+            return;
+        }
+        short_writeln!(
+            stdout,
+            "{:<42}  {:<11}  {:<19}  Note",
+            "Wallet",
+            "Reason",
+            "Banned at (UTC)"
+        );
+        banned.iter().for_each(|banned_wallet| {
+            let line = format!(
+                "{:<42}  {:<11}  {:<19}  {}",
+                banned_wallet.wallet,
+                banned_wallet.reason,
+                banned_wallet
+                    .timestamp_opt
+                    .map(Self::utc_time)
+                    .unwrap_or_else(|| "unknown".to_string()),
+                banned_wallet.note_opt.as_deref().unwrap_or("")
+            );
+            short_writeln!(stdout, "{}", line.trim_end())
+        })
+    }
+
+    pub fn utc_time(timestamp: u64) -> String {
+        i64::try_from(timestamp)
+            .ok()
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+            .and_then(|time| {
+                time.format(format_description!(
+                    "[year]-[month]-[day] [hour]:[minute]:[second]"
+                ))
+                .ok()
+            })
+            .unwrap_or_else(|| timestamp.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::BAN_LIST_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            BAN_LIST_SUBCOMMAND_ABOUT,
+            "Displays the wallets the Node refuses to serve, with the reason, time and any note recorded \
+             for each ban. Only valid if Node is already running."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiBanListResponse { banned: vec![] }.tmb(0)));
+        let subject = factory.make(&["ban-list".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn ban_list_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiBanListResponse {
+                banned: vec![
+                    UiBannedWallet {
+                        wallet: "0x1111111111111111111111111111111111111111".to_string(),
+                        reason: "delinquency".to_string(),
+                        note_opt: None,
+                        timestamp_opt: None,
+                    },
+                    UiBannedWallet {
+                        wallet: "0x2222222222222222222222222222222222222222".to_string(),
+                        reason: "manual".to_string(),
+                        note_opt: Some("Spams us".to_string()),
+                        timestamp_opt: Some(1_650_000_000),
+                    },
+                ],
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BanListCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(UiBanListRequest {}.tmb(0), STANDARD_COMMAND_TIMEOUT_MILLIS)]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Wallet                                      Reason       Banned at (UTC)      Note\n\
+             0x1111111111111111111111111111111111111111  delinquency  unknown\n\
+             0x2222222222222222222222222222222222222222  manual       2022-04-15 05:20:00  Spams us\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn ban_list_command_reports_empty_list() {
+        let mut context = CommandContextMock::new()
+            .transact_result(Ok(UiBanListResponse { banned: vec![] }.tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = BanListCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "No wallets are banned.\n"
+        );
+    }
+
+    #[test]
+    fn ban_list_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(BAN_LIST_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = BanListCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(BAN_LIST_ERROR, "Booga".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Ban list retrieval failed: Payload({}, \"Booga\")\n",
+                BAN_LIST_ERROR
+            )
+        );
+    }
+}
@@ -8,14 +8,25 @@ use masq_lib::intentionally_blank;
 use masq_lib::messages::{FromMessageBody, ToMessageBody, UiMessageError};
 use masq_lib::short_writeln;
 use masq_lib::ui_gateway::MessageBody;
+use serde_json::json;
 use std::any::Any;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 pub const STANDARD_COMMAND_TIMEOUT_MILLIS: u64 = 1000;
 pub const STANDARD_COLUMN_WIDTH: usize = 33;
 
+//exit codes of non-interactive masq; scripts rely on them, so don't renumber
+pub const EXIT_CODE_OTHER_ERROR: u8 = 1;
+pub const EXIT_CODE_COMMAND_SYNTAX: u8 = 2;
+pub const EXIT_CODE_CONNECTION_PROBLEM: u8 = 3;
+pub const EXIT_CODE_TRANSMISSION_PROBLEM: u8 = 4;
+pub const EXIT_CODE_UNEXPECTED_RESPONSE: u8 = 5;
+pub const EXIT_CODE_PAYLOAD_ERROR: u8 = 6;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CommandError {
     ConnectionProblem(String),
@@ -50,6 +61,48 @@ impl Display for CommandError {
     }
 }
 
+impl CommandError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            ConnectionProblem(_) => EXIT_CODE_CONNECTION_PROBLEM,
+            Transmission(_) | Reception(_) => EXIT_CODE_TRANSMISSION_PROBLEM,
+            UnexpectedResponse(_) => EXIT_CODE_UNEXPECTED_RESPONSE,
+            Payload(_, _) => EXIT_CODE_PAYLOAD_ERROR,
+            Other(_) => EXIT_CODE_OTHER_ERROR,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        match self {
+            ConnectionProblem(s) => json_error("connectionProblem", None, s),
+            Transmission(s) => json_error("transmission", None, s),
+            Reception(s) => json_error("reception", None, s),
+            UnexpectedResponse(e) => {
+                let (kind, body) = match e {
+                    UiMessageError::UnexpectedMessage(body) => ("unexpectedMessage", body),
+                    UiMessageError::PayloadError(body) => ("payloadError", body),
+                    UiMessageError::DeserializationError(_, body) => ("deserializationError", body),
+                };
+                let code_opt = body.payload.as_ref().err().map(|(code, _)| *code);
+                json_error(kind, code_opt, &e.to_string())
+            }
+            Payload(code, s) => json_error("payload", Some(*code), s),
+            Other(s) => json_error("other", None, s),
+        }
+    }
+}
+
+pub fn json_error(kind: &str, code_opt: Option<u64>, message: &str) -> String {
+    let mut error = json!({
+        "kind": kind,
+        "message": message,
+    });
+    if let Some(code) = code_opt {
+        error["code"] = json!(code);
+    }
+    json!({ "error": error }).to_string()
+}
+
 pub trait Command: Debug {
     fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError>;
 
@@ -107,6 +160,11 @@ impl From<ContextError> for CommandError {
     }
 }
 
+// Long-running commands poll the flag to leave their loop cleanly instead of dying on the signal
+pub(in crate::commands) fn install_ctrlc_handler(stop_flag: Arc<AtomicBool>) -> Result<(), String> {
+    ctrlc::set_handler(move || stop_flag.store(true, Ordering::Relaxed)).map_err(|e| e.to_string())
+}
+
 pub(in crate::commands) fn dump_parameter_line(stream: &mut dyn Write, name: &str, value: &str) {
     short_writeln!(
         stream,
@@ -262,6 +320,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn command_error_exit_codes_are_stable() {
+        assert_eq!(EXIT_CODE_OTHER_ERROR, 1);
+        assert_eq!(EXIT_CODE_COMMAND_SYNTAX, 2);
+        assert_eq!(EXIT_CODE_CONNECTION_PROBLEM, 3);
+        assert_eq!(EXIT_CODE_TRANSMISSION_PROBLEM, 4);
+        assert_eq!(EXIT_CODE_UNEXPECTED_RESPONSE, 5);
+        assert_eq!(EXIT_CODE_PAYLOAD_ERROR, 6);
+        let message_body = MessageBody {
+            opcode: "opcode".to_string(),
+            path: MessagePath::FireAndForget,
+            payload: Ok("{}".to_string()),
+        };
+        let exit_codes = vec![
+            ConnectionProblem("string".to_string()),
+            Transmission("string".to_string()),
+            Reception("string".to_string()),
+            UnexpectedResponse(UiMessageError::UnexpectedMessage(message_body)),
+            Payload(1234, "string".to_string()),
+            Other("string".to_string()),
+        ]
+        .iter()
+        .map(|error| error.exit_code())
+        .collect::<Vec<u8>>();
+
+        assert_eq!(exit_codes, vec![3, 4, 4, 5, 6, 1]);
+    }
+
+    #[test]
+    fn command_error_converts_to_json_properly() {
+        assert_eq!(
+            ConnectionProblem("string".to_string()).to_json(),
+            r#"{"error":{"kind":"connectionProblem","message":"string"}}"#.to_string()
+        );
+        assert_eq!(
+            Reception("string".to_string()).to_json(),
+            r#"{"error":{"kind":"reception","message":"string"}}"#.to_string()
+        );
+        assert_eq!(
+            Payload(1234, "string".to_string()).to_json(),
+            r#"{"error":{"code":1234,"kind":"payload","message":"string"}}"#.to_string()
+        );
+        let message_body = MessageBody {
+            opcode: "opcode".to_string(),
+            path: MessagePath::FireAndForget,
+            payload: Err((1234, "booga".to_string())),
+        };
+        assert_eq!(
+            UnexpectedResponse(UiMessageError::PayloadError(message_body)).to_json(),
+            r#"{"error":{"code":1234,"kind":"payloadError","message":"Daemon or Node complained about your command with opcode 'opcode'. Error code 1234: booga"}}"#
+                .to_string()
+        );
+        assert_eq!(
+            Other("string".to_string()).to_json(),
+            r#"{"error":{"kind":"other","message":"string"}}"#.to_string()
+        );
+    }
+
     #[test]
     #[should_panic(expected = "Couldn't redirect to Node: \"message\"")]
     fn context_error_converter_sad() {
@@ -0,0 +1,664 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::CommandError::{Other, Payload};
+use crate::commands::commands_common::{
+    install_ctrlc_handler, transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use masq_lib::constants::NODE_NOT_RUNNING_ERROR;
+use masq_lib::implement_as_any;
+use masq_lib::messages::{
+    FromMessageBody, ToMessageBody, UiConnectionChangeBroadcast, UiConnectionStage,
+    UiFinancialStatistics, UiFinancialsRequest, UiFinancialsResponse, UiLogBroadcast, UiNeighbor,
+    UiNeighborhoodStatusRequest, UiNeighborhoodStatusResponse, UiNodeCrashedBroadcast,
+    UiSetupBroadcast, UiStreamStatusRequest, UiStreamStatusResponse,
+};
+use masq_lib::short_writeln;
+use masq_lib::ui_gateway::MessageBody;
+#[cfg(test)]
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 5;
+const BROADCAST_CHECK_INTERVAL_MILLIS: u64 = 100;
+const LOG_PANE_CAPACITY: usize = 10;
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+const HIDE_CURSOR: &str = "\x1B[?25l";
+const SHOW_CURSOR: &str = "\x1B[?25h";
+
+const DASHBOARD_SUBCOMMAND_ABOUT: &str =
+    "Shows a full-screen view of the Node that refreshes in place: connection stage, neighbors, \
+     client streams, recent log lines and balances. Press Ctrl-C to leave it. Only available \
+     from the shell, as 'masq dashboard'.";
+const INTERVAL_ARG_HELP: &str =
+    "Number of seconds between two polls of the Node for neighborhood, stream and financial \
+     status. Broadcasts are shown as soon as they arrive regardless. Defaults to 5.";
+const INTERACTIVE_MODE_ERROR: &str =
+    "The dashboard takes over the whole screen; leave the interactive mode and run it as \
+     'masq dashboard' instead";
+
+pub fn dashboard_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("dashboard")
+        .about(DASHBOARD_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("interval")
+                .help(INTERVAL_ARG_HELP)
+                .long("interval")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .required(false)
+                .validator(validate_interval),
+        )
+}
+
+fn validate_interval(interval: String) -> Result<(), String> {
+    match interval.parse::<u64>() {
+        Ok(secs) if secs > 0 => Ok(()),
+        _ => Err(interval),
+    }
+}
+
+#[derive(Debug)]
+pub struct DashboardCommand {
+    interval: Duration,
+    stop_flag: Arc<AtomicBool>,
+    interrupt_handler_installer: fn(Arc<AtomicBool>) -> Result<(), String>,
+}
+
+impl Command for DashboardCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let broadcast_rx = match context.broadcast_receiver_opt() {
+            Some(rx) => rx,
+            None => {
+                short_writeln!(context.stderr(), "{}", INTERACTIVE_MODE_ERROR);
+                return Err(Other(INTERACTIVE_MODE_ERROR.to_string()));
+            }
+        };
+        if let Err(e) = (self.interrupt_handler_installer)(self.stop_flag.clone()) {
+            return Err(Other(format!("Can't intercept Ctrl-C: {}", e)));
+        }
+        write!(context.stdout(), "{}", HIDE_CURSOR).expect("write! failed");
+        let result = self.run(context, &broadcast_rx);
+        write!(context.stdout(), "{}", SHOW_CURSOR).expect("write! failed");
+        context.stdout().flush().expect("flush failed");
+        result
+    }
+
+    implement_as_any!();
+}
+
+impl DashboardCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match dashboard_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let interval_secs = matches
+            .value_of("interval")
+            .map(|secs| secs.parse::<u64>().expect("validator is failing"))
+            .unwrap_or(DEFAULT_REFRESH_INTERVAL_SECS);
+        Ok(Self {
+            interval: Duration::from_secs(interval_secs),
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            interrupt_handler_installer: install_ctrlc_handler,
+        })
+    }
+
+    fn run(
+        &self,
+        context: &mut dyn CommandContext,
+        broadcast_rx: &Receiver<MessageBody>,
+    ) -> Result<(), CommandError> {
+        let mut state = DashboardState::default();
+        loop {
+            Self::poll(context, &mut state)?;
+            Self::redraw(context, &state);
+            let deadline = Instant::now() + self.interval;
+            while Instant::now() < deadline {
+                let mut changed = false;
+                while let Ok(message_body) = broadcast_rx.try_recv() {
+                    changed |= state.apply_broadcast(message_body);
+                }
+                if changed {
+                    Self::redraw(context, &state);
+                }
+                if self.stop_flag.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+                match broadcast_rx.recv_timeout(Self::next_check(deadline)) {
+                    Ok(message_body) => {
+                        if state.apply_broadcast(message_body) {
+                            Self::redraw(context, &state)
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return Err(CommandError::ConnectionProblem(
+                            "Broadcasts from the Node or Daemon stopped coming".to_string(),
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_check(deadline: Instant) -> Duration {
+        deadline
+            .saturating_duration_since(Instant::now())
+            .min(Duration::from_millis(BROADCAST_CHECK_INTERVAL_MILLIS))
+    }
+
+    fn poll(
+        context: &mut dyn CommandContext,
+        state: &mut DashboardState,
+    ) -> Result<(), CommandError> {
+        state.node_running = true;
+        match Self::poll_one::<_, UiNeighborhoodStatusResponse>(
+            UiNeighborhoodStatusRequest {},
+            context,
+            state,
+        )? {
+            Some(response) => {
+                state.stage_opt = Some(response.stage);
+                state.neighbors = response.neighbors;
+                state.known_nodes_opt = Some(response.known_nodes);
+            }
+            None => return Ok(()),
+        }
+        state.streams_opt =
+            Self::poll_one::<_, UiStreamStatusResponse>(UiStreamStatusRequest {}, context, state)?;
+        let financials_request = UiFinancialsRequest {
+            stats_required: true,
+            top_records_opt: None,
+            custom_queries_opt: None,
+        };
+        state.stats_opt =
+            Self::poll_one::<_, UiFinancialsResponse>(financials_request, context, state)?
+                .and_then(|response| response.stats_opt);
+        Ok(())
+    }
+
+    //a Node that isn't running is something to show on the screen, not a reason to quit
+    fn poll_one<I, O>(
+        request: I,
+        context: &mut dyn CommandContext,
+        state: &mut DashboardState,
+    ) -> Result<Option<O>, CommandError>
+    where
+        I: ToMessageBody,
+        O: FromMessageBody,
+    {
+        match transaction::<I, O>(request, context, STANDARD_COMMAND_TIMEOUT_MILLIS) {
+            Ok(response) => Ok(Some(response)),
+            Err(Payload(code, _)) if code == NODE_NOT_RUNNING_ERROR => {
+                state.node_running = false;
+                Ok(None)
+            }
+            Err(Payload(code, message)) => {
+                state.push_log(format!("Poll failed: {} ({})", message, code));
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn redraw(context: &mut dyn CommandContext, state: &DashboardState) {
+        write!(context.stdout(), "{}{}", CLEAR_SCREEN, state.render()).expect("write! failed");
+        context.stdout().flush().expect("flush failed");
+    }
+}
+
+#[derive(Debug, Default)]
+struct DashboardState {
+    node_running: bool,
+    stage_opt: Option<UiConnectionStage>,
+    neighbors: Vec<UiNeighbor>,
+    known_nodes_opt: Option<u64>,
+    streams_opt: Option<UiStreamStatusResponse>,
+    stats_opt: Option<UiFinancialStatistics>,
+    logs: VecDeque<String>,
+}
+
+impl DashboardState {
+    fn apply_broadcast(&mut self, message_body: MessageBody) -> bool {
+        if let Ok((body, _)) = UiConnectionChangeBroadcast::fmb(message_body.clone()) {
+            self.stage_opt = Some(body.stage);
+        } else if let Ok((body, _)) = UiLogBroadcast::fmb(message_body.clone()) {
+            self.push_log(format!("{:?}: {}", body.log_level, body.msg));
+        } else if let Ok((body, _)) = UiNodeCrashedBroadcast::fmb(message_body.clone()) {
+            self.node_running = false;
+            self.stage_opt = None;
+            self.push_log(format!(
+                "Node (PID {}) crashed: {:?}",
+                body.process_id, body.crash_reason
+            ));
+        } else if let Ok((body, _)) = UiSetupBroadcast::fmb(message_body) {
+            self.push_log(format!(
+                "Daemon setup changed ({} values, {} errors)",
+                body.values.len(),
+                body.errors.len()
+            ));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn push_log(&mut self, line: String) {
+        if self.logs.len() == LOG_PANE_CAPACITY {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(line);
+    }
+
+    fn render(&self) -> String {
+        let mut screen = String::new();
+        screen.push_str("MASQ Node dashboard (Ctrl-C to quit)\n\n");
+        screen.push_str(&self.render_connection_pane());
+        screen.push_str(&self.render_neighbors_pane());
+        screen.push_str(&self.render_streams_pane());
+        screen.push_str(&self.render_balances_pane());
+        screen.push_str(&self.render_log_pane());
+        screen
+    }
+
+    fn render_connection_pane(&self) -> String {
+        let stage = match (&self.stage_opt, self.node_running) {
+            (_, false) => "Node is not running".to_string(),
+            (None, true) => "Unknown".to_string(),
+            (Some(stage), true) => format!("{:?}", stage),
+        };
+        format!("== Connection ==\n{}\n\n", stage)
+    }
+
+    fn render_neighbors_pane(&self) -> String {
+        let mut pane = match self.known_nodes_opt {
+            Some(known_nodes) => format!(
+                "== Neighbors ({}; {} Nodes known) ==\n",
+                self.neighbors.len(),
+                known_nodes
+            ),
+            None => "== Neighbors ==\n".to_string(),
+        };
+        if self.neighbors.is_empty() {
+            pane.push_str("None\n");
+        }
+        self.neighbors.iter().for_each(|neighbor| {
+            pane.push_str(&format!(
+                "{} {:<22} {}\n",
+                neighbor.public_key,
+                neighbor.node_addr_opt.as_deref().unwrap_or("-"),
+                if neighbor.full_neighbor {
+                    "full"
+                } else {
+                    "half"
+                }
+            ))
+        });
+        pane.push('\n');
+        pane
+    }
+
+    fn render_streams_pane(&self) -> String {
+        let streams = match &self.streams_opt {
+            Some(streams) => format!(
+                "Active: {}   Tunneled: {}",
+                streams.active_streams, streams.tunneled_streams
+            ),
+            None => "Unknown".to_string(),
+        };
+        format!("== Client streams ==\n{}\n\n", streams)
+    }
+
+    fn render_balances_pane(&self) -> String {
+        let balances = match &self.stats_opt {
+            Some(stats) => format!(
+                "Payable: {} gwei unpaid, {} gwei paid\nReceivable: {} gwei unpaid, {} gwei paid",
+                stats.total_unpaid_and_pending_payable_gwei,
+                stats.total_paid_payable_gwei,
+                stats.total_unpaid_receivable_gwei,
+                stats.total_paid_receivable_gwei
+            ),
+            None => "Unknown".to_string(),
+        };
+        format!("== Balances ==\n{}\n\n", balances)
+    }
+
+    fn render_log_pane(&self) -> String {
+        let mut pane = "== Recent log ==\n".to_string();
+        self.logs.iter().for_each(|line| {
+            pane.push_str(line);
+            pane.push('\n');
+        });
+        pane
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use crossbeam_channel::unbounded;
+    use masq_lib::messages::{CrashReason, SerializableLogLevel};
+    use std::sync::Mutex;
+
+    fn make_subject(stop_immediately: bool) -> DashboardCommand {
+        DashboardCommand {
+            interval: Duration::from_millis(10),
+            stop_flag: Arc::new(AtomicBool::new(stop_immediately)),
+            interrupt_handler_installer: |_| Ok(()),
+        }
+    }
+
+    fn make_stats() -> UiFinancialStatistics {
+        UiFinancialStatistics {
+            total_unpaid_and_pending_payable_gwei: 1000,
+            total_paid_payable_gwei: 2000,
+            total_unpaid_receivable_gwei: 3000,
+            total_paid_receivable_gwei: 4000,
+        }
+    }
+
+    fn make_neighbor(key: &str, full: bool) -> UiNeighbor {
+        UiNeighbor {
+            public_key: key.to_string(),
+            node_addr_opt: Some("1.2.3.4:5678".to_string()),
+            full_neighbor: full,
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DEFAULT_REFRESH_INTERVAL_SECS, 5);
+        assert_eq!(BROADCAST_CHECK_INTERVAL_MILLIS, 100);
+        assert_eq!(LOG_PANE_CAPACITY, 10);
+        assert_eq!(CLEAR_SCREEN, "\x1B[2J\x1B[H");
+        assert_eq!(HIDE_CURSOR, "\x1B[?25l");
+        assert_eq!(SHOW_CURSOR, "\x1B[?25h");
+        assert_eq!(
+            INTERACTIVE_MODE_ERROR,
+            "The dashboard takes over the whole screen; leave the interactive mode and run it as \
+             'masq dashboard' instead"
+        );
+    }
+
+    #[test]
+    fn command_factory_makes_dashboard_command_with_interval() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory
+            .make(&[
+                "dashboard".to_string(),
+                "--interval".to_string(),
+                "12".to_string(),
+            ])
+            .unwrap();
+
+        let command = result.as_any().downcast_ref::<DashboardCommand>().unwrap();
+        assert_eq!(command.interval, Duration::from_secs(12));
+        assert!(!command.stop_flag.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn interval_defaults_to_five_seconds() {
+        let result = DashboardCommand::new(&["dashboard".to_string()]).unwrap();
+
+        assert_eq!(result.interval, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn zero_interval_is_rejected() {
+        let result = DashboardCommand::new(&[
+            "dashboard".to_string(),
+            "--interval".to_string(),
+            "0".to_string(),
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dashboard_refuses_to_run_without_broadcasts() {
+        let mut context = CommandContextMock::new();
+        let stderr_arc = context.stderr_arc();
+        let subject = make_subject(true);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(Other(INTERACTIVE_MODE_ERROR.to_string())));
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!("{}\n", INTERACTIVE_MODE_ERROR)
+        );
+    }
+
+    #[test]
+    fn dashboard_polls_the_node_and_renders_the_panes() {
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        broadcast_tx
+            .send(
+                UiLogBroadcast {
+                    msg: "Something happened".to_string(),
+                    log_level: SerializableLogLevel::Warn,
+                }
+                .tmb(0),
+            )
+            .unwrap();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiNeighborhoodStatusResponse {
+                stage: UiConnectionStage::ConnectedToNeighbor,
+                neighbors: vec![make_neighbor("AAAA", true)],
+                known_nodes: 3,
+            }
+            .tmb(0)))
+            .transact_result(Ok(UiStreamStatusResponse {
+                active_streams: 4,
+                tunneled_streams: 1,
+            }
+            .tmb(0)))
+            .transact_result(Ok(UiFinancialsResponse {
+                stats_opt: Some(make_stats()),
+                query_results_opt: None,
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = make_subject(true);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![
+                (
+                    UiNeighborhoodStatusRequest {}.tmb(0),
+                    STANDARD_COMMAND_TIMEOUT_MILLIS
+                ),
+                (
+                    UiStreamStatusRequest {}.tmb(0),
+                    STANDARD_COMMAND_TIMEOUT_MILLIS
+                ),
+                (
+                    UiFinancialsRequest {
+                        stats_required: true,
+                        top_records_opt: None,
+                        custom_queries_opt: None,
+                    }
+                    .tmb(0),
+                    STANDARD_COMMAND_TIMEOUT_MILLIS
+                ),
+            ]
+        );
+        let stdout = stdout_arc.lock().unwrap().get_string();
+        assert!(stdout.starts_with(HIDE_CURSOR), "{}", stdout);
+        assert!(stdout.ends_with(SHOW_CURSOR), "{}", stdout);
+        let last_screen = stdout
+            .trim_end_matches(SHOW_CURSOR)
+            .rsplit(CLEAR_SCREEN)
+            .next()
+            .unwrap();
+        assert_eq!(
+            last_screen,
+            "MASQ Node dashboard (Ctrl-C to quit)\n\n\
+             == Connection ==\nConnectedToNeighbor\n\n\
+             == Neighbors (1; 3 Nodes known) ==\nAAAA 1.2.3.4:5678           full\n\n\
+             == Client streams ==\nActive: 4   Tunneled: 1\n\n\
+             == Balances ==\nPayable: 1000 gwei unpaid, 2000 gwei paid\n\
+             Receivable: 3000 gwei unpaid, 4000 gwei paid\n\n\
+             == Recent log ==\nWarn: Something happened\n"
+        );
+    }
+
+    #[test]
+    fn dashboard_keeps_going_when_the_node_is_not_running() {
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_params(&transact_params_arc)
+            .transact_result(Err(ContextError::PayloadError(
+                NODE_NOT_RUNNING_ERROR,
+                "irrelevant".to_string(),
+            )));
+        let stdout_arc = context.stdout_arc();
+        let subject = make_subject(true);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(transact_params_arc.lock().unwrap().len(), 1);
+        let stdout = stdout_arc.lock().unwrap().get_string();
+        assert!(
+            stdout.contains("== Connection ==\nNode is not running\n"),
+            "{}",
+            stdout
+        );
+    }
+
+    #[test]
+    fn dashboard_gives_up_when_the_connection_is_lost() {
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_result(Err(ContextError::ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let subject = make_subject(false);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::ConnectionProblem("Booga".to_string()))
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!("{}{}", HIDE_CURSOR, SHOW_CURSOR)
+        );
+    }
+
+    #[test]
+    fn dashboard_reports_failure_to_intercept_ctrl_c() {
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let mut context = CommandContextMock::new().broadcast_receiver(broadcast_rx);
+        let mut subject = make_subject(true);
+        subject.interrupt_handler_installer = |_| Err("Booga".to_string());
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Other("Can't intercept Ctrl-C: Booga".to_string()))
+        );
+    }
+
+    #[test]
+    fn broadcasts_update_the_state() {
+        let mut subject = DashboardState::default();
+
+        let results = vec![
+            subject.apply_broadcast(
+                UiConnectionChangeBroadcast {
+                    stage: UiConnectionStage::RouteFound,
+                }
+                .tmb(0),
+            ),
+            subject.apply_broadcast(
+                UiLogBroadcast {
+                    msg: "Hello".to_string(),
+                    log_level: SerializableLogLevel::Info,
+                }
+                .tmb(0),
+            ),
+            subject.apply_broadcast(UiStreamStatusRequest {}.tmb(0)),
+        ];
+
+        assert_eq!(results, vec![true, true, false]);
+        assert_eq!(subject.stage_opt, Some(UiConnectionStage::RouteFound));
+        assert_eq!(subject.logs, vec!["Info: Hello".to_string()]);
+
+        let result = subject.apply_broadcast(
+            UiNodeCrashedBroadcast {
+                instance: "default".to_string(),
+                process_id: 1234,
+                crash_reason: CrashReason::NoInformation,
+            }
+            .tmb(0),
+        );
+
+        assert!(result);
+        assert_eq!(subject.stage_opt, None);
+        assert!(!subject.node_running);
+        assert_eq!(
+            subject.logs.back(),
+            Some(&"Node (PID 1234) crashed: NoInformation".to_string())
+        );
+    }
+
+    #[test]
+    fn log_pane_keeps_only_the_most_recent_lines() {
+        let mut subject = DashboardState::default();
+
+        (0..(LOG_PANE_CAPACITY + 2)).for_each(|index| subject.push_log(format!("line {}", index)));
+
+        assert_eq!(subject.logs.len(), LOG_PANE_CAPACITY);
+        assert_eq!(subject.logs.front(), Some(&"line 2".to_string()));
+        assert_eq!(
+            subject.logs.back(),
+            Some(&format!("line {}", LOG_PANE_CAPACITY + 1))
+        );
+    }
+
+    #[test]
+    fn empty_state_renders_placeholders() {
+        let subject = DashboardState {
+            node_running: true,
+            neighbors: vec![make_neighbor("BBBB", false)],
+            ..DashboardState::default()
+        };
+
+        let result = subject.render();
+
+        assert_eq!(
+            result,
+            "MASQ Node dashboard (Ctrl-C to quit)\n\n\
+             == Connection ==\nUnknown\n\n\
+             == Neighbors ==\nBBBB 1.2.3.4:5678           half\n\n\
+             == Client streams ==\nUnknown\n\n\
+             == Balances ==\nUnknown\n\n\
+             == Recent log ==\n"
+        );
+    }
+}
@@ -0,0 +1,436 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{transaction, Command, CommandError};
+use crate::commands::recover_wallets_command::{
+    LANGUAGE_ARG_DEFAULT_VALUE, LANGUAGE_ARG_POSSIBLE_VALUES,
+};
+use clap::{App, Arg, SubCommand};
+use itertools::Itertools;
+use masq_lib::messages::{UiDiscoverWalletsRequest, UiDiscoverWalletsResponse, UiRecoverSeedSpec};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+
+// Every scanned derivation path costs several round trips to the blockchain service
+pub const DISCOVER_WALLETS_COMMAND_TIMEOUT_MILLIS: u64 = 120000;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DiscoverWalletsCommand {
+    mnemonic_phrase: Vec<String>,
+    language: String,
+    passphrase_opt: Option<String>,
+    account_count_opt: Option<u32>,
+    index_count_opt: Option<u32>,
+}
+
+const DISCOVER_WALLETS_ABOUT: &str =
+    "Scans the standard derivation paths of a mnemonic phrase for wallets that hold gas or MASQ, \
+     have sent transactions or have received MASQ, so that you can find the paths to give to \
+     recover-wallets. Progress is reported as the scan goes. Only valid if Node is already running.";
+const MNEMONIC_PHRASE_ARG_HELP: &str =
+    "The mnemonic phrase from which the wallets were derived. Surround with double quotes.";
+const PASSPHRASE_ARG_HELP: &str =
+    "The additional word, if any, that was placed at the end of the mnemonic phrase.";
+const LANGUAGE_ARG_HELP: &str = "The language in which the mnemonic phrase is written.";
+const ACCOUNTS_ARG_HELP: &str =
+    "Number of accounts to scan, as in m/44'/60'/<account>'/0/<index>. Defaults to 1.";
+const INDEXES_ARG_HELP: &str =
+    "Number of indexes to scan in each account, as in m/44'/60'/<account>'/0/<index>. Defaults to 20.";
+
+pub fn discover_wallets_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("discover-wallets")
+        .about(DISCOVER_WALLETS_ABOUT)
+        .arg(
+            Arg::with_name("mnemonic-phrase")
+                .help(MNEMONIC_PHRASE_ARG_HELP)
+                .long("mnemonic-phrase")
+                .value_name("MNEMONIC-PHRASE")
+                .required(true)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("passphrase")
+                .help(PASSPHRASE_ARG_HELP)
+                .long("passphrase")
+                .value_name("PASSPHRASE")
+                .required(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("language")
+                .help(LANGUAGE_ARG_HELP)
+                .long("language")
+                .value_name("LANGUAGE")
+                .required(false)
+                .default_value(LANGUAGE_ARG_DEFAULT_VALUE)
+                .takes_value(true)
+                .possible_values(&LANGUAGE_ARG_POSSIBLE_VALUES),
+        )
+        .arg(
+            Arg::with_name("accounts")
+                .help(ACCOUNTS_ARG_HELP)
+                .long("accounts")
+                .value_name("ACCOUNTS")
+                .required(false)
+                .takes_value(true)
+                .validator(validate_count),
+        )
+        .arg(
+            Arg::with_name("indexes")
+                .help(INDEXES_ARG_HELP)
+                .long("indexes")
+                .value_name("INDEXES")
+                .required(false)
+                .takes_value(true)
+                .validator(validate_count),
+        )
+}
+
+fn validate_count(count: String) -> Result<(), String> {
+    match count.parse::<u32>() {
+        Ok(value) if value > 0 => Ok(()),
+        _ => Err(count),
+    }
+}
+
+impl Command for DiscoverWalletsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiDiscoverWalletsRequest {
+            seed_spec: UiRecoverSeedSpec {
+                mnemonic_phrase: self.mnemonic_phrase.clone(),
+                mnemonic_phrase_language_opt: Some(self.language.clone()),
+                mnemonic_passphrase_opt: self.passphrase_opt.clone(),
+            },
+            account_count_opt: self.account_count_opt,
+            index_count_opt: self.index_count_opt,
+        };
+        let output: Result<UiDiscoverWalletsResponse, CommandError> =
+            transaction(input, context, DISCOVER_WALLETS_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                Self::write_candidates(context, &response);
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Wallet discovery failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl DiscoverWalletsCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match discover_wallets_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let count_of = |name: &str| {
+            matches.value_of(name).map(|count| {
+                count
+                    .parse::<u32>()
+                    .unwrap_or_else(|_| panic!("{} validator is not working", name))
+            })
+        };
+        Ok(Self {
+            mnemonic_phrase: matches
+                .value_of("mnemonic-phrase")
+                .expect("mnemonic-phrase is not properly required")
+                .split(' ')
+                .map(|word| word.to_string())
+                .collect_vec(),
+            language: matches
+                .value_of("language")
+                .expect("language is not properly defaulted by clap")
+                .to_string(),
+            passphrase_opt: matches.value_of("passphrase").map(|mp| mp.to_string()),
+            account_count_opt: count_of("accounts"),
+            index_count_opt: count_of("indexes"),
+        })
+    }
+
+    fn write_candidates(context: &mut dyn CommandContext, response: &UiDiscoverWalletsResponse) {
+        if response.candidates.is_empty() {
+            short_writeln!(
+                context.stdout(),
+                "None of the {} derivation paths scanned leads to a wallet with any activity.",
+                response.scanned_path_count
+            );
+            return;
+        }
+        short_writeln!(
+            context.stdout(),
+            "{} of the {} derivation paths scanned lead to wallets with activity:\n",
+            response.candidates.len(),
+            response.scanned_path_count
+        );
+        short_writeln!(
+            context.stdout(),
+            "{:<20} {:<42} {:>20} {:>20} {:>12} {:>14}",
+            "Derivation path",
+            "Address",
+            "Gas balance (gwei)",
+            "MASQ balance (gwei)",
+            "Transactions",
+            "MASQ transfers"
+        );
+        response.candidates.iter().for_each(|candidate| {
+            short_writeln!(
+                context.stdout(),
+                "{:<20} {:<42} {:>20} {:>20} {:>12} {:>14}",
+                candidate.derivation_path,
+                candidate.address,
+                candidate.gas_balance_gwei,
+                candidate.masq_balance_gwei,
+                candidate.transaction_count,
+                candidate.received_transfer_count
+            )
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::WALLET_DISCOVERY_ERROR;
+    use masq_lib::messages::{ToMessageBody, UiWalletCandidate};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DISCOVER_WALLETS_COMMAND_TIMEOUT_MILLIS, 120000);
+        assert_eq!(
+            DISCOVER_WALLETS_ABOUT,
+            "Scans the standard derivation paths of a mnemonic phrase for wallets that hold gas or MASQ, \
+             have sent transactions or have received MASQ, so that you can find the paths to give to \
+             recover-wallets. Progress is reported as the scan goes. Only valid if Node is already running."
+        );
+        assert_eq!(
+            MNEMONIC_PHRASE_ARG_HELP,
+            "The mnemonic phrase from which the wallets were derived. Surround with double quotes."
+        );
+        assert_eq!(
+            PASSPHRASE_ARG_HELP,
+            "The additional word, if any, that was placed at the end of the mnemonic phrase."
+        );
+        assert_eq!(
+            LANGUAGE_ARG_HELP,
+            "The language in which the mnemonic phrase is written."
+        );
+        assert_eq!(
+            ACCOUNTS_ARG_HELP,
+            "Number of accounts to scan, as in m/44'/60'/<account>'/0/<index>. Defaults to 1."
+        );
+        assert_eq!(
+            INDEXES_ARG_HELP,
+            "Number of indexes to scan in each account, as in m/44'/60'/<account>'/0/<index>. Defaults to 20."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory() {
+        let factory = CommandFactoryReal::new();
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiDiscoverWalletsResponse {
+                scanned_path_count: 20,
+                candidates: vec![],
+            }
+            .tmb(0)));
+        let subject = factory
+            .make(&[
+                "discover-wallets".to_string(),
+                "--mnemonic-phrase".to_string(),
+                "word1 word2".to_string(),
+            ])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn validate_count_works() {
+        assert!(validate_count("abc".to_string()).is_err());
+        assert!(validate_count("-1".to_string()).is_err());
+        assert!(validate_count("0".to_string()).is_err());
+        assert!(validate_count("5".to_string()).is_ok());
+    }
+
+    #[test]
+    fn discover_wallets_command_parses_its_arguments() {
+        let result = DiscoverWalletsCommand::new(&[
+            "discover-wallets".to_string(),
+            "--mnemonic-phrase".to_string(),
+            "word1 word2".to_string(),
+            "--passphrase".to_string(),
+            "booga".to_string(),
+            "--language".to_string(),
+            "Spanish".to_string(),
+            "--accounts".to_string(),
+            "3".to_string(),
+            "--indexes".to_string(),
+            "5".to_string(),
+        ]);
+
+        assert_eq!(
+            result,
+            Ok(DiscoverWalletsCommand {
+                mnemonic_phrase: vec!["word1".to_string(), "word2".to_string()],
+                language: "Spanish".to_string(),
+                passphrase_opt: Some("booga".to_string()),
+                account_count_opt: Some(3),
+                index_count_opt: Some(5),
+            })
+        );
+    }
+
+    #[test]
+    fn discover_wallets_command_leaves_range_to_node_by_default() {
+        let result = DiscoverWalletsCommand::new(&[
+            "discover-wallets".to_string(),
+            "--mnemonic-phrase".to_string(),
+            "word1 word2".to_string(),
+        ]);
+
+        assert_eq!(
+            result,
+            Ok(DiscoverWalletsCommand {
+                mnemonic_phrase: vec!["word1".to_string(), "word2".to_string()],
+                language: "English".to_string(),
+                passphrase_opt: None,
+                account_count_opt: None,
+                index_count_opt: None,
+            })
+        );
+    }
+
+    #[test]
+    fn discover_wallets_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiDiscoverWalletsResponse {
+                scanned_path_count: 40,
+                candidates: vec![
+                    UiWalletCandidate {
+                        derivation_path: "m/44'/60'/0'/0/0".to_string(),
+                        address: "0x1111111111111111111111111111111111111111".to_string(),
+                        gas_balance_gwei: 1_234,
+                        masq_balance_gwei: 0,
+                        transaction_count: 17,
+                        received_transfer_count: 2,
+                    },
+                    UiWalletCandidate {
+                        derivation_path: "m/44'/60'/1'/0/3".to_string(),
+                        address: "0x2222222222222222222222222222222222222222".to_string(),
+                        gas_balance_gwei: 0,
+                        masq_balance_gwei: 5_000_000_000,
+                        transaction_count: 0,
+                        received_transfer_count: 1,
+                    },
+                ],
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = DiscoverWalletsCommand::new(&[
+            "discover-wallets".to_string(),
+            "--mnemonic-phrase".to_string(),
+            "word1 word2".to_string(),
+            "--accounts".to_string(),
+            "2".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiDiscoverWalletsRequest {
+                    seed_spec: UiRecoverSeedSpec {
+                        mnemonic_phrase: vec!["word1".to_string(), "word2".to_string()],
+                        mnemonic_phrase_language_opt: Some("English".to_string()),
+                        mnemonic_passphrase_opt: None,
+                    },
+                    account_count_opt: Some(2),
+                    index_count_opt: None,
+                }
+                .tmb(0),
+                DISCOVER_WALLETS_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "2 of the 40 derivation paths scanned lead to wallets with activity:\n\n\
+             Derivation path      Address                                      Gas balance (gwei)  MASQ balance (gwei) Transactions MASQ transfers\n\
+             m/44'/60'/0'/0/0     0x1111111111111111111111111111111111111111                 1234                    0           17              2\n\
+             m/44'/60'/1'/0/3     0x2222222222222222222222222222222222222222                    0           5000000000            0              1\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn discover_wallets_command_reports_when_nothing_is_found() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiDiscoverWalletsResponse {
+                scanned_path_count: 20,
+                candidates: vec![],
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = DiscoverWalletsCommand::new(&[
+            "discover-wallets".to_string(),
+            "--mnemonic-phrase".to_string(),
+            "word1 word2".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "None of the 20 derivation paths scanned leads to a wallet with any activity.\n"
+        );
+    }
+
+    #[test]
+    fn discover_wallets_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(WALLET_DISCOVERY_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = DiscoverWalletsCommand::new(&[
+            "discover-wallets".to_string(),
+            "--mnemonic-phrase".to_string(),
+            "word1 word2".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                WALLET_DISCOVERY_ERROR,
+                "Booga".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Wallet discovery failed: Payload({}, \"Booga\")\n",
+                WALLET_DISCOVERY_ERROR
+            )
+        );
+    }
+}
@@ -1,10 +1,12 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::commands::financials_command::parsing_and_value_dressing::restricted::{
-    parse_masq_range_to_gwei, parse_time_params,
+    parse_masq_range_to_gwei, parse_moment, parse_time_params,
+};
+use clap::{App, AppSettings, Arg, ArgGroup, SubCommand};
+use masq_lib::shared_schema::common_validators::{
+    validate_ethereum_address, validate_non_zero_u16,
 };
-use clap::{App, Arg, ArgGroup, SubCommand};
-use masq_lib::shared_schema::common_validators::validate_non_zero_u16;
 use num::CheckedMul;
 use std::fmt::{Debug, Display};
 use std::num::ParseIntError;
@@ -24,10 +26,27 @@ const NO_STATS_ARG_HELP: &str = "Disables statistics that display by default, co
 const GWEI_HELP: &str =
     "Orders money values rendering in gwei of MASQ instead of whole MASQs as the default.";
 const ORDERED_HELP: &str = "Determines in what ordering the top records will be returned. This option works only with the '--top' argument.";
+const EXPORT_SUBCOMMAND_ABOUT: &str = "Writes the payable and receivable accounts of this Node, together with the individual transactions in its ledger, \
+ to the standard output as CSV or JSON for bookkeeping. Only valid if Node is already running.";
+const FORMAT_HELP: &str = "The format of the export. CSV puts accounts and transactions into a single table with a fixed order of columns.";
+const UNITS_HELP: &str =
+    "The units in which amounts of money are written. Amounts are exact to the wei in every unit.";
+const FROM_HELP: &str = "Exports only accounts last active, and transactions recorded, at or after this moment. It can be a date written as \
+ YYYY-MM-DD, standing for its midnight in UTC, or a Unix timestamp in seconds. Defaults to the beginning of time.";
+const TO_HELP: &str = "Exports only accounts last active, and transactions recorded, at or before this moment. It can be a date written as \
+ YYYY-MM-DD, standing for its midnight in UTC, or a Unix timestamp in seconds. Defaults to the present.";
+const HISTORY_HELP: &str = "Displays the ledger of individual service charges and payments, oldest first, instead of the account balances. \
+ It can be narrowed down by the '--wallet' and '--age' arguments.";
+const WALLET_HELP: &str = "Restricts the ledger history to entries of a single wallet. This option works only with the '--history' argument.";
+const AGE_HELP: &str = "Restricts the ledger history to entries whose age in seconds lies within the range, written as <MIN-AGE>-<MAX-AGE>. \
+ This option works only with the '--history' argument.";
 
 pub fn financials_subcommand() -> App<'static, 'static> {
     SubCommand::with_name("financials")
         .about(FINANCIALS_SUBCOMMAND_ABOUT)
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .setting(AppSettings::DisableHelpSubcommand)
+        .subcommand(export_subcommand())
         .arg(
             Arg::with_name("top")
                 .help(TOP_ARG_HELP)
@@ -92,6 +111,40 @@ pub fn financials_subcommand() -> App<'static, 'static> {
                 .possible_values(&["balance", "age"])
                 .required(false),
         )
+        .arg(
+            Arg::with_name("history")
+                .help(HISTORY_HELP)
+                .value_name("HISTORY")
+                .long("history")
+                .case_insensitive(false)
+                .takes_value(false)
+                .required(false)
+                .conflicts_with_all(&["top", "payable", "receivable", "no-stats", "ordered"]),
+        )
+        .arg(
+            Arg::with_name("wallet")
+                .help(WALLET_HELP)
+                .value_name("WALLET")
+                .long("wallet")
+                .short("w")
+                .case_insensitive(false)
+                .takes_value(true)
+                .required(false)
+                .requires("history")
+                .validator(validate_ethereum_address),
+        )
+        .arg(
+            Arg::with_name("age")
+                .help(AGE_HELP)
+                .value_name("AGE-RANGE")
+                .long("age")
+                .short("a")
+                .case_insensitive(false)
+                .takes_value(true)
+                .required(false)
+                .requires("history")
+                .validator(validate_age_range),
+        )
         .groups(&[
             ArgGroup::with_name("at_least_one_query")
                 .args(&["receivable", "payable", "top"])
@@ -112,6 +165,59 @@ pub fn financials_subcommand() -> App<'static, 'static> {
         ])
 }
 
+fn export_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("export")
+        .about(EXPORT_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("format")
+                .help(FORMAT_HELP)
+                .value_name("FORMAT")
+                .long("format")
+                .short("f")
+                .case_insensitive(true)
+                .takes_value(true)
+                .possible_values(&["csv", "json"])
+                .default_value("csv")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("units")
+                .help(UNITS_HELP)
+                .value_name("UNITS")
+                .long("units")
+                .short("u")
+                .case_insensitive(true)
+                .takes_value(true)
+                .possible_values(&["wei", "gwei", "masq"])
+                .default_value("masq")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("from")
+                .help(FROM_HELP)
+                .value_name("FROM")
+                .long("from")
+                .case_insensitive(false)
+                .takes_value(true)
+                .required(false)
+                .validator(validate_moment),
+        )
+        .arg(
+            Arg::with_name("to")
+                .help(TO_HELP)
+                .value_name("TO")
+                .long("to")
+                .case_insensitive(false)
+                .takes_value(true)
+                .required(false)
+                .validator(validate_moment),
+        )
+}
+
+fn validate_moment(moment: String) -> Result<(), String> {
+    parse_moment(&moment).map(|_| ())
+}
+
 fn validate_two_ranges<N>(two_ranges: String) -> Result<(), String>
 where
     N: FromStr<Err = ParseIntError>
@@ -156,6 +262,19 @@ where
     }
 }
 
+fn validate_age_range(age_range: String) -> Result<(), String> {
+    let (min_age_str, max_age_str) = match age_range.split('-').collect::<Vec<&str>>()[..] {
+        [min_age_str, max_age_str] => (min_age_str, max_age_str),
+        _ => return Err(format!("Age range '{}' is formatted wrong", age_range)),
+    };
+    let (min_age, max_age) = parse_time_params(min_age_str, max_age_str)?;
+    if min_age >= max_age {
+        Err(format!("Age range '{}' must be low to high", age_range))
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,6 +302,22 @@ mod tests {
             "Orders money values rendering in gwei of MASQ instead of whole MASQs as the default."
         );
         assert_eq!(ORDERED_HELP, "Determines in what ordering the top records will be returned. This option works only with the '--top' argument.");
+        assert_eq!(EXPORT_SUBCOMMAND_ABOUT, "Writes the payable and receivable accounts of this Node, together with the individual transactions in its ledger, \
+            to the standard output as CSV or JSON for bookkeeping. Only valid if Node is already running.");
+        assert_eq!(FORMAT_HELP, "The format of the export. CSV puts accounts and transactions into a single table with a fixed order of columns.");
+        assert_eq!(
+            UNITS_HELP,
+            "The units in which amounts of money are written. Amounts are exact to the wei in every unit."
+        );
+        assert_eq!(FROM_HELP, "Exports only accounts last active, and transactions recorded, at or after this moment. It can be a date written as \
+            YYYY-MM-DD, standing for its midnight in UTC, or a Unix timestamp in seconds. Defaults to the beginning of time.");
+        assert_eq!(TO_HELP, "Exports only accounts last active, and transactions recorded, at or before this moment. It can be a date written as \
+            YYYY-MM-DD, standing for its midnight in UTC, or a Unix timestamp in seconds. Defaults to the present.");
+        assert_eq!(HISTORY_HELP, "Displays the ledger of individual service charges and payments, oldest first, instead of the account balances. \
+            It can be narrowed down by the '--wallet' and '--age' arguments.");
+        assert_eq!(WALLET_HELP, "Restricts the ledger history to entries of a single wallet. This option works only with the '--history' argument.");
+        assert_eq!(AGE_HELP, "Restricts the ledger history to entries whose age in seconds lies within the range, written as <MIN-AGE>-<MAX-AGE>. \
+            This option works only with the '--history' argument.");
     }
 
     #[test]
@@ -310,4 +445,41 @@ mod tests {
             Err("Balance range '7878.0-a lot' in improper format".to_string())
         )
     }
+
+    #[test]
+    fn validate_age_range_accepts_range_from_low_to_high() {
+        let result = validate_age_range("0-3600".to_string());
+
+        assert_eq!(result, Ok(()))
+    }
+
+    #[test]
+    fn validate_age_range_with_misused_range_delimiter() {
+        let result = validate_age_range("10+3600".to_string());
+
+        assert_eq!(
+            result,
+            Err("Age range '10+3600' is formatted wrong".to_string())
+        )
+    }
+
+    #[test]
+    fn validate_age_range_second_value_smaller_than_the_first() {
+        let result = validate_age_range("3600-10".to_string());
+
+        assert_eq!(
+            result,
+            Err("Age range '3600-10' must be low to high".to_string())
+        )
+    }
+
+    #[test]
+    fn validate_age_range_non_numeric_value() {
+        let result = validate_age_range("10-forever".to_string());
+
+        assert_eq!(
+            result,
+            Err("Non numeric value 'forever', it must be a valid integer".to_string())
+        )
+    }
 }
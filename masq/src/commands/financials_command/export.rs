@@ -0,0 +1,559 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use crate::commands::financials_command::args_validation::financials_subcommand;
+use crate::commands::financials_command::parsing_and_value_dressing::restricted::{
+    dress_wei_in_units, parse_moment,
+};
+use crate::commands::financials_command::pretty_print_utils::restricted::utc_time_from_timestamp;
+use masq_lib::messages::{
+    UiExportedAccount, UiExportedTransaction, UiFinancialsExportRequest, UiFinancialsExportResponse,
+};
+use masq_lib::short_writeln;
+use serde_json::{json, Value};
+use std::io::Write;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExportUnits {
+    Wei,
+    Gwei,
+    Masq,
+}
+
+impl ExportUnits {
+    fn name(&self) -> &'static str {
+        match self {
+            ExportUnits::Wei => "wei",
+            ExportUnits::Gwei => "gwei",
+            ExportUnits::Masq => "masq",
+        }
+    }
+
+    fn decimal_places(&self) -> u32 {
+        match self {
+            ExportUnits::Wei => 0,
+            ExportUnits::Gwei => 9,
+            ExportUnits::Masq => 18,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FinancialsExportCommand {
+    format: ExportFormat,
+    units: ExportUnits,
+    from_timestamp_opt: Option<u64>,
+    to_timestamp_opt: Option<u64>,
+}
+
+impl Command for FinancialsExportCommand {
+    // The Node hands out the ledger a page at a time; the accounts arrive with the first page
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let mut input = UiFinancialsExportRequest {
+            from_timestamp_opt: self.from_timestamp_opt,
+            to_timestamp_opt: self.to_timestamp_opt,
+            offset_opt: None,
+            limit_opt: None,
+        };
+        let mut export_opt: Option<UiFinancialsExportResponse> = None;
+        loop {
+            let output: Result<UiFinancialsExportResponse, CommandError> =
+                transaction(input.clone(), context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+            match output {
+                Ok(response) => {
+                    let next_offset_opt = response.next_offset_opt;
+                    match export_opt.as_mut() {
+                        None => export_opt = Some(response),
+                        Some(export) => export.transactions.extend(response.transactions),
+                    }
+                    match next_offset_opt {
+                        Some(next_offset) => input.offset_opt = Some(next_offset),
+                        None => break,
+                    }
+                }
+                Err(e) => {
+                    short_writeln!(context.stderr(), "Financials export failed: {:?}", e);
+                    return Err(e);
+                }
+            }
+        }
+        let export = export_opt.expect("export without a single page");
+        match self.format {
+            ExportFormat::Csv => self.write_csv(context.stdout(), &export),
+            ExportFormat::Json => self.write_json(context.stdout(), &export),
+        }
+        Ok(())
+    }
+}
+
+impl FinancialsExportCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match financials_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(e.to_string()),
+        };
+        let matches = match matches.subcommand_matches("export") {
+            Some(matches) => matches,
+            None => return Err("The 'export' subcommand is missing".to_string()),
+        };
+        let format = match matches
+            .value_of("format")
+            .expect("format is defaulted")
+            .to_lowercase()
+            .as_str()
+        {
+            "json" => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        };
+        let units = match matches
+            .value_of("units")
+            .expect("units are defaulted")
+            .to_lowercase()
+            .as_str()
+        {
+            "wei" => ExportUnits::Wei,
+            "gwei" => ExportUnits::Gwei,
+            _ => ExportUnits::Masq,
+        };
+        let parse = |name: &str| {
+            matches
+                .value_of(name)
+                .map(|moment| parse_moment(moment).expect("blew up after validation"))
+        };
+        let from_timestamp_opt = parse("from");
+        let to_timestamp_opt = parse("to");
+        if let (Some(from), Some(to)) = (from_timestamp_opt, to_timestamp_opt) {
+            if from > to {
+                return Err(format!(
+                    "The moment given by --from ({}) must not come after the one given by --to ({})",
+                    from, to
+                ));
+            }
+        }
+        Ok(Self {
+            format,
+            units,
+            from_timestamp_opt,
+            to_timestamp_opt,
+        })
+    }
+
+    fn write_csv(&self, stdout: &mut dyn Write, response: &UiFinancialsExportResponse) {
+        short_writeln!(
+            stdout,
+            "record,wallet,timestamp,time_utc,kind,amount_{},transaction_hash,nonce,gas_price_gwei,block_number,outcome",
+            self.units.name()
+        );
+        let accounts = [
+            ("payable", &response.payables),
+            ("receivable", &response.receivables),
+        ];
+        accounts.iter().for_each(|(record, accounts)| {
+            accounts.iter().for_each(|account| {
+                short_writeln!(
+                    stdout,
+                    "{},{},{},{},,{},{},,,,",
+                    record,
+                    account.wallet,
+                    account.last_activity_timestamp,
+                    utc_time_from_timestamp(account.last_activity_timestamp),
+                    self.amount(&account.balance_wei),
+                    or_empty(&account.pending_payable_hash_opt)
+                )
+            })
+        });
+        response.transactions.iter().for_each(|transaction| {
+            short_writeln!(
+                stdout,
+                "transaction,{},{},{},{},{},{},{},{},{},{}",
+                transaction.wallet,
+                transaction.timestamp,
+                utc_time_from_timestamp(transaction.timestamp),
+                transaction.kind,
+                self.amount(&transaction.amount_wei),
+                or_empty(&transaction.transaction_hash_opt),
+                or_empty(&transaction.nonce_opt),
+                or_empty(&transaction.gas_price_gwei_opt),
+                or_empty(&transaction.block_number_opt),
+                or_empty(&transaction.outcome_opt)
+            )
+        });
+    }
+
+    fn write_json(&self, stdout: &mut dyn Write, response: &UiFinancialsExportResponse) {
+        let account_to_json = |account: &UiExportedAccount| {
+            json!({
+                "wallet": account.wallet,
+                "balance": self.amount(&account.balance_wei),
+                "lastActivityTimestamp": account.last_activity_timestamp,
+                "pendingPayableHash": account.pending_payable_hash_opt,
+            })
+        };
+        let transaction_to_json = |transaction: &UiExportedTransaction| {
+            json!({
+                "timestamp": transaction.timestamp,
+                "wallet": transaction.wallet,
+                "kind": transaction.kind,
+                "amount": self.amount(&transaction.amount_wei),
+                "transactionHash": transaction.transaction_hash_opt,
+                "nonce": transaction.nonce_opt,
+                "gasPriceGwei": transaction.gas_price_gwei_opt,
+                "blockNumber": transaction.block_number_opt,
+                "outcome": transaction.outcome_opt,
+            })
+        };
+        let export = json!({
+            "units": self.units.name(),
+            "payables": response.payables.iter().map(account_to_json).collect::<Vec<Value>>(),
+            "receivables": response.receivables.iter().map(account_to_json).collect::<Vec<Value>>(),
+            "transactions": response.transactions.iter().map(transaction_to_json).collect::<Vec<Value>>(),
+        });
+        short_writeln!(
+            stdout,
+            "{}",
+            serde_json::to_string_pretty(&export).expect("JSON serialization failed")
+        );
+    }
+
+    // Amounts stay strings even in JSON, where a number could lose digits
+    fn amount(&self, wei: &str) -> String {
+        match wei.parse::<i128>() {
+            Ok(wei) => dress_wei_in_units(wei, self.units.decimal_places()),
+            Err(_) => wei.to_string(),
+        }
+    }
+}
+
+fn or_empty<T: ToString>(value_opt: &Option<T>) -> String {
+    match value_opt {
+        Some(value) => value.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError::ConnectionDropped;
+    use crate::command_factory::{CommandFactory, CommandFactoryError, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::messages::{FromMessageBody, ToMessageBody};
+    use masq_lib::utils::slice_of_strs_to_vec_of_strings;
+    use std::sync::{Arc, Mutex};
+
+    fn make_export_response() -> UiFinancialsExportResponse {
+        let hash = "0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638";
+        UiFinancialsExportResponse {
+            payables: vec![UiExportedAccount {
+                wallet: "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string(),
+                balance_wei: "1000000000000000001".to_string(),
+                last_activity_timestamp: 1_666_000_000,
+                pending_payable_hash_opt: Some(hash.to_string()),
+            }],
+            receivables: vec![UiExportedAccount {
+                wallet: "0xaa22968a5263f165F014d3F21A443f10a116EDe0".to_string(),
+                balance_wei: "-2500000000".to_string(),
+                last_activity_timestamp: 1_665_000_000,
+                pending_payable_hash_opt: None,
+            }],
+            transactions: vec![UiExportedTransaction {
+                timestamp: 1_666_000_120,
+                wallet: "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string(),
+                kind: "payment_sent".to_string(),
+                amount_wei: "644000000000000000".to_string(),
+                transaction_hash_opt: Some(hash.to_string()),
+                nonce_opt: Some(12),
+                gas_price_gwei_opt: Some(100),
+                block_number_opt: None,
+                outcome_opt: Some("submitted".to_string()),
+            }],
+            next_offset_opt: None,
+        }
+    }
+
+    #[test]
+    fn command_factory_makes_export_command_with_defaults() {
+        let factory = CommandFactoryReal::new();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiFinancialsExportResponse {
+                payables: vec![],
+                receivables: vec![],
+                transactions: vec![],
+                next_offset_opt: None,
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = factory
+            .make(&slice_of_strs_to_vec_of_strings(&["financials", "export"]))
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiFinancialsExportRequest {
+                    from_timestamp_opt: None,
+                    to_timestamp_opt: None,
+                    offset_opt: None,
+                    limit_opt: None,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "record,wallet,timestamp,time_utc,kind,amount_masq,transaction_hash,nonce,gas_price_gwei,block_number,outcome\n"
+        );
+    }
+
+    #[test]
+    fn export_command_parses_its_arguments() {
+        let result = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+            "--format",
+            "JSON",
+            "--units",
+            "gwei",
+            "--from",
+            "2022-01-01",
+            "--to",
+            "1672531200",
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            FinancialsExportCommand {
+                format: ExportFormat::Json,
+                units: ExportUnits::Gwei,
+                from_timestamp_opt: Some(1_640_995_200),
+                to_timestamp_opt: Some(1_672_531_200),
+            }
+        )
+    }
+
+    #[test]
+    fn export_command_refuses_time_window_running_backwards() {
+        let result = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+            "--from",
+            "2023-01-01",
+            "--to",
+            "2022-01-01",
+        ]));
+
+        assert_eq!(
+            result,
+            Err("The moment given by --from (1672531200) must not come after the one given by --to (1640995200)".to_string())
+        )
+    }
+
+    #[test]
+    fn export_command_refuses_unintelligible_moment() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+            "--from",
+            "last-tuesday",
+        ]));
+
+        let err = match result {
+            Err(CommandFactoryError::CommandSyntax(err)) => err,
+            x => panic!("we expected CommandSyntax error but got: {:?}", x),
+        };
+        assert!(
+            err.contains(
+                "'last-tuesday' is neither a date written as YYYY-MM-DD nor a Unix timestamp"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn export_subcommand_must_come_right_after_financials() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "--gwei",
+            "export",
+        ]));
+
+        match result {
+            Err(CommandFactoryError::CommandSyntax(_)) => (),
+            x => panic!("we expected CommandSyntax error but got: {:?}", x),
+        }
+    }
+
+    #[test]
+    fn export_as_csv_in_masq() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(make_export_response().tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "record,wallet,timestamp,time_utc,kind,amount_masq,transaction_hash,nonce,gas_price_gwei,block_number,outcome\n\
+            payable,0xA884A2F1A5Ec6C2e499644666a5E6af97B966888,1666000000,2022-10-17 09:46:40,,1.000000000000000001,0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638,,,,\n\
+            receivable,0xaa22968a5263f165F014d3F21A443f10a116EDe0,1665000000,2022-10-05 20:00:00,,-0.0000000025,,,,,\n\
+            transaction,0xA884A2F1A5Ec6C2e499644666a5E6af97B966888,1666000120,2022-10-17 09:48:40,payment_sent,0.644,0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638,12,100,,submitted\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn export_as_json_in_wei() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(make_export_response().tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+            "--format",
+            "json",
+            "--units",
+            "wei",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let stdout = stdout_arc.lock().unwrap().get_string();
+        let exported: Value = serde_json::from_str(&stdout).unwrap();
+        assert_eq!(
+            exported,
+            json!({
+                "units": "wei",
+                "payables": [{
+                    "wallet": "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888",
+                    "balance": "1000000000000000001",
+                    "lastActivityTimestamp": 1666000000,
+                    "pendingPayableHash": "0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638"
+                }],
+                "receivables": [{
+                    "wallet": "0xaa22968a5263f165F014d3F21A443f10a116EDe0",
+                    "balance": "-2500000000",
+                    "lastActivityTimestamp": 1665000000,
+                    "pendingPayableHash": null
+                }],
+                "transactions": [{
+                    "timestamp": 1666000120,
+                    "wallet": "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888",
+                    "kind": "payment_sent",
+                    "amount": "644000000000000000",
+                    "transactionHash": "0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638",
+                    "nonce": 12,
+                    "gasPriceGwei": 100,
+                    "blockNumber": null,
+                    "outcome": "submitted"
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn export_gathers_the_ledger_page_by_page() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let first_page = UiFinancialsExportResponse {
+            next_offset_opt: Some(1),
+            ..make_export_response()
+        };
+        let mut second_transaction = make_export_response().transactions.remove(0);
+        second_transaction.timestamp = 1_666_000_180;
+        second_transaction.outcome_opt = Some("confirmed".to_string());
+        let second_page = UiFinancialsExportResponse {
+            payables: vec![],
+            receivables: vec![],
+            transactions: vec![second_transaction],
+            next_offset_opt: None,
+        };
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(first_page.tmb(0)))
+            .transact_result(Ok(second_page.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+            "--units",
+            "wei",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        let offsets = transact_params
+            .iter()
+            .map(|(body, _)| {
+                UiFinancialsExportRequest::fmb(body.clone())
+                    .unwrap()
+                    .0
+                    .offset_opt
+            })
+            .collect::<Vec<Option<u64>>>();
+        assert_eq!(offsets, vec![None, Some(1)]);
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "record,wallet,timestamp,time_utc,kind,amount_wei,transaction_hash,nonce,gas_price_gwei,block_number,outcome\n\
+            payable,0xA884A2F1A5Ec6C2e499644666a5E6af97B966888,1666000000,2022-10-17 09:46:40,,1000000000000000001,0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638,,,,\n\
+            receivable,0xaa22968a5263f165F014d3F21A443f10a116EDe0,1665000000,2022-10-05 20:00:00,,-2500000000,,,,,\n\
+            transaction,0xA884A2F1A5Ec6C2e499644666a5E6af97B966888,1666000120,2022-10-17 09:48:40,payment_sent,644000000000000000,0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638,12,100,,submitted\n\
+            transaction,0xA884A2F1A5Ec6C2e499644666a5E6af97B966888,1666000180,2022-10-17 09:49:40,payment_sent,644000000000000000,0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638,12,100,,confirmed\n"
+        );
+    }
+
+    #[test]
+    fn export_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = FinancialsExportCommand::new(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "export",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Financials export failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
+}
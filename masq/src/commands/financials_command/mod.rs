@@ -2,6 +2,7 @@
 
 pub mod args_validation;
 pub mod data_structures;
+pub mod export;
 pub mod parsing_and_value_dressing;
 pub mod pretty_print_utils;
 #[cfg(test)]
@@ -20,14 +21,15 @@ use crate::commands::financials_command::parsing_and_value_dressing::restricted:
 };
 use crate::commands::financials_command::pretty_print_utils::restricted::process_gwei_into_requested_format;
 use crate::commands::financials_command::pretty_print_utils::restricted::{
-    financial_status_totals_title, main_title_for_tops_opt, no_records_found, prepare_metadata,
-    render_accounts_generic, subtitle_for_tops, title_for_custom_query,
-    triple_or_single_blank_line, StringValuesFormattableAccount,
+    financial_status_totals_title, ledger_history_title, main_title_for_tops_opt, no_records_found,
+    prepare_ledger_headings, prepare_metadata, render_accounts_generic, subtitle_for_tops,
+    title_for_custom_query, triple_or_single_blank_line, StringValuesFormattableAccount,
 };
 use clap::ArgMatches;
 use masq_lib::messages::{
     CustomQueries, QueryResults, RangeQuery, TopRecordsConfig, UiFinancialStatistics,
-    UiFinancialsRequest, UiFinancialsResponse,
+    UiFinancialsRequest, UiFinancialsResponse, UiLedgerEntry, UiLedgerHistoryRequest,
+    UiLedgerHistoryResponse,
 };
 use masq_lib::short_writeln;
 use masq_lib::utils::ExpectValue;
@@ -43,10 +45,14 @@ pub struct FinancialsCommand {
     gwei_precision: bool,
     top_records_opt: Option<TopRecordsConfig>,
     custom_queries_opt: Option<CustomQueryInput>,
+    history_opt: Option<UiLedgerHistoryRequest>,
 }
 
 impl Command for FinancialsCommand {
     fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        if let Some(history_request) = self.history_opt.as_ref() {
+            return self.execute_history_request(history_request.clone(), context);
+        }
         let input = UiFinancialsRequest {
             stats_required: self.stats_required,
             top_records_opt: self.top_records_opt,
@@ -87,14 +93,54 @@ impl FinancialsCommand {
         let top_records_opt = Self::parse_top_records_args(&matches);
         let gwei_precision = matches.is_present("gwei");
         let custom_queries_opt = Self::parse_custom_query_args(&matches);
+        let history_opt = Self::parse_history_args(&matches);
         Ok(Self {
             stats_required,
             top_records_opt,
             custom_queries_opt,
             gwei_precision,
+            history_opt,
         })
     }
 
+    // The Node hands out the ledger a page at a time; they're gathered here into one table
+    fn execute_history_request(
+        &self,
+        mut request: UiLedgerHistoryRequest,
+        context: &mut dyn CommandContext,
+    ) -> Result<(), CommandError> {
+        let mut entries = vec![];
+        loop {
+            let output: Result<UiLedgerHistoryResponse, CommandError> =
+                transaction(request.clone(), context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+            match output {
+                Ok(response) => {
+                    entries.extend(response.entries);
+                    match response.next_offset_opt {
+                        Some(next_offset) => request.offset_opt = Some(next_offset),
+                        None => break,
+                    }
+                }
+                Err(e) => {
+                    short_writeln!(context.stderr(), "Ledger history retrieval failed: {:?}", e);
+                    return Err(e);
+                }
+            }
+        }
+        self.process_ledger_history(context.stdout(), entries);
+        Ok(())
+    }
+
+    fn process_ledger_history(&self, stdout: &mut dyn Write, entries: Vec<UiLedgerEntry>) {
+        let headings = prepare_ledger_headings(self.gwei_precision);
+        ledger_history_title(stdout);
+        if entries.is_empty() {
+            no_records_found(stdout, headings.words.as_slice())
+        } else {
+            render_accounts_generic(stdout, entries, &headings)
+        }
+    }
+
     fn process_command_response(
         &self,
         response: UiFinancialsResponse,
@@ -218,6 +264,28 @@ impl FinancialsCommand {
         })
     }
 
+    fn parse_history_args(matches: &ArgMatches) -> Option<UiLedgerHistoryRequest> {
+        if !matches.is_present("history") {
+            return None;
+        }
+        let (min_age_s_opt, max_age_s_opt) = match matches.value_of("age") {
+            Some(age_range) => {
+                let (min_age_str, max_age_str) = split_time_range(age_range);
+                let (min_age, max_age) =
+                    parse_time_params(min_age_str, max_age_str).expect("blew up after validation");
+                (Some(min_age), Some(max_age))
+            }
+            None => (None, None),
+        };
+        Some(UiLedgerHistoryRequest {
+            wallet_opt: matches.value_of("wallet").map(|wallet| wallet.to_string()),
+            min_age_s_opt,
+            max_age_s_opt,
+            offset_opt: None,
+            limit_opt: None,
+        })
+    }
+
     fn parse_custom_query_args(matches: &ArgMatches) -> Option<CustomQueryInput> {
         fn decompose_optional_inputs<N>(
             composed_parameters_opt: Option<RangeQueryInput<N>>,
@@ -862,7 +930,8 @@ mod tests {
                         "05000", "0010000", "040", "050"
                     ]))
                 }),
-                gwei_precision: false
+                gwei_precision: false,
+                history_opt: None
             }
         );
     }
@@ -897,7 +966,8 @@ mod tests {
                         "5000", "10000", "-050", "-040"
                     ]))
                 }),
-                gwei_precision: false
+                gwei_precision: false,
+                history_opt: None
             }
         );
     }
@@ -1870,4 +1940,258 @@ mod tests {
             "Financials retrieval failed: ConnectionProblem(\"Booga\")\n"
         );
     }
+
+    #[test]
+    fn history_cannot_be_combined_with_queries_of_balances() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "--history",
+            "--top",
+            "10",
+        ]));
+
+        let err = match result {
+            Err(CommandFactoryError::CommandSyntax(err)) => err,
+            x => panic!("we expected CommandSyntax error but got: {:?}", x),
+        };
+        assert!(err.contains("cannot be used with"), "{}", err);
+        assert!(err.contains("--history"), "{}", err);
+    }
+
+    #[test]
+    fn wallet_and_age_are_accepted_only_with_history() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory.make(&slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "--wallet",
+            "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888",
+            "--age",
+            "0-3600",
+        ]));
+
+        let err = match result {
+            Err(CommandFactoryError::CommandSyntax(err)) => err,
+            x => panic!("we expected CommandSyntax error but got: {:?}", x),
+        };
+        assert!(err.contains("--history"), "{}", err);
+    }
+
+    #[test]
+    fn parse_history_args_without_restrictions() {
+        let args = slice_of_strs_to_vec_of_strings(&["financials", "--history"]);
+
+        let result = FinancialsCommand::new(&args).unwrap();
+
+        assert_eq!(
+            result.history_opt,
+            Some(UiLedgerHistoryRequest {
+                wallet_opt: None,
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                offset_opt: None,
+                limit_opt: None,
+            })
+        );
+    }
+
+    #[test]
+    fn financials_command_history_with_wallet_and_age_range() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let hash = "0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638";
+        let expected_response = UiLedgerHistoryResponse {
+            entries: vec![
+                UiLedgerEntry {
+                    timestamp: 1_666_000_000,
+                    wallet: "0xaa22968a5263f165F014d3F21A443f10a116EDe0".to_string(),
+                    kind: "service_provided".to_string(),
+                    amount_wei: "550".to_string(),
+                    transaction_hash_opt: None,
+                    nonce_opt: None,
+                    gas_price_gwei_opt: None,
+                    block_number_opt: None,
+                    outcome_opt: None,
+                },
+                UiLedgerEntry {
+                    timestamp: 1_666_000_120,
+                    wallet: "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string(),
+                    kind: "payment_sent".to_string(),
+                    amount_wei: "644000000000000000".to_string(),
+                    transaction_hash_opt: Some(hash.to_string()),
+                    nonce_opt: Some(12),
+                    gas_price_gwei_opt: Some(100),
+                    block_number_opt: None,
+                    outcome_opt: Some("submitted".to_string()),
+                },
+                UiLedgerEntry {
+                    timestamp: 1_666_000_360,
+                    wallet: "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string(),
+                    kind: "payment_sent".to_string(),
+                    amount_wei: "644000000000000000".to_string(),
+                    transaction_hash_opt: Some(hash.to_string()),
+                    nonce_opt: Some(12),
+                    gas_price_gwei_opt: Some(100),
+                    block_number_opt: Some(15_000_000),
+                    outcome_opt: Some("confirmed".to_string()),
+                },
+            ],
+            next_offset_opt: None,
+        };
+        let args = slice_of_strs_to_vec_of_strings(&[
+            "financials",
+            "--history",
+            "--wallet",
+            "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888",
+            "--age",
+            "0-86400",
+        ]);
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(expected_response.tmb(31)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = FinancialsCommand::new(&args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiLedgerHistoryRequest {
+                    wallet_opt: Some("0xA884A2F1A5Ec6C2e499644666a5E6af97B966888".to_string()),
+                    min_age_s_opt: Some(0),
+                    max_age_s_opt: Some(86400),
+                    offset_opt: None,
+                    limit_opt: None,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "\n\
+                Ledger history\n\
+                \n\
+                #   Wallet                                       Time [UTC]            Kind               Amount [MASQ]   Tx hash                                                              Nonce   Gas price [gwei]   Block      Outcome  \n\
+                1   0xaa22968a5263f165F014d3F21A443f10a116EDe0   2022-10-17 09:46:40   service_provided   < 0.01          None                                                                 None    None               None       None     \n\
+                2   0xA884A2F1A5Ec6C2e499644666a5E6af97B966888   2022-10-17 09:48:40   payment_sent       0.64            0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638   12      100                None       submitted\n\
+                3   0xA884A2F1A5Ec6C2e499644666a5E6af97B966888   2022-10-17 09:52:40   payment_sent       0.64            0x3648c8b8c7e067ac30b80b6936159326d564dd13b7ae465b26647154ada2c638   12      100                15000000   confirmed\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn financials_command_history_with_no_records_found() {
+        let expected_response = UiLedgerHistoryResponse {
+            entries: vec![],
+            next_offset_opt: None,
+        };
+        let args = slice_of_strs_to_vec_of_strings(&["financials", "--history", "--gwei"]);
+        let mut context = CommandContextMock::new().transact_result(Ok(expected_response.tmb(31)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = FinancialsCommand::new(&args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "\n\
+                Ledger history\n\
+                \n\
+                #   Wallet                                       Time [UTC]   Kind   Amount [gwei]   Tx hash   Nonce   Gas price [gwei]   Block   Outcome\n\
+                \n\
+                No records found\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn financials_command_history_gathers_all_pages() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let entry = |wallet: &str, amount_wei: &str| UiLedgerEntry {
+            timestamp: 1_666_000_000,
+            wallet: wallet.to_string(),
+            kind: "service_provided".to_string(),
+            amount_wei: amount_wei.to_string(),
+            transaction_hash_opt: None,
+            nonce_opt: None,
+            gas_price_gwei_opt: None,
+            block_number_opt: None,
+            outcome_opt: None,
+        };
+        let first_page = UiLedgerHistoryResponse {
+            entries: vec![entry("0xaa22968a5263f165F014d3F21A443f10a116EDe0", "550")],
+            next_offset_opt: Some(1),
+        };
+        let second_page = UiLedgerHistoryResponse {
+            entries: vec![entry(
+                "0xA884A2F1A5Ec6C2e499644666a5E6af97B966888",
+                "1000000000",
+            )],
+            next_offset_opt: None,
+        };
+        let args = slice_of_strs_to_vec_of_strings(&["financials", "--history", "--gwei"]);
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(first_page.tmb(31)))
+            .transact_result(Ok(second_page.tmb(32)));
+        let stdout_arc = context.stdout_arc();
+        let subject = FinancialsCommand::new(&args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let request = |offset_opt: Option<u64>| UiLedgerHistoryRequest {
+            wallet_opt: None,
+            min_age_s_opt: None,
+            max_age_s_opt: None,
+            offset_opt,
+            limit_opt: None,
+        };
+        assert_eq!(
+            *transact_params_arc.lock().unwrap(),
+            vec![
+                (request(None).tmb(0), STANDARD_COMMAND_TIMEOUT_MILLIS),
+                (request(Some(1)).tmb(0), STANDARD_COMMAND_TIMEOUT_MILLIS)
+            ]
+        );
+        let stdout = stdout_arc.lock().unwrap().get_string();
+        assert!(
+            stdout.contains("1   0xaa22968a5263f165F014d3F21A443f10a116EDe0"),
+            "{}",
+            stdout
+        );
+        assert!(stdout.contains("0.00000055"), "{}", stdout);
+        assert!(
+            stdout.contains("2   0xA884A2F1A5Ec6C2e499644666a5E6af97B966888"),
+            "{}",
+            stdout
+        );
+    }
+
+    #[test]
+    fn financials_command_history_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ConnectionDropped("Booga".to_string())));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let args = slice_of_strs_to_vec_of_strings(&["financials", "--history"]);
+        let subject = FinancialsCommand::new(&args).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Err(ConnectionProblem("Booga".to_string())));
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            "Ledger history retrieval failed: ConnectionProblem(\"Booga\")\n"
+        );
+    }
 }
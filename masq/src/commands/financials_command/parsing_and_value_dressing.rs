@@ -12,6 +12,7 @@ pub(in crate::commands::financials_command) mod restricted {
     use std::num::{IntErrorKind, ParseIntError};
     use std::str::FromStr;
     use thousands::Separable;
+    use time::{Date, Month};
 
     pub fn convert_masq_from_gwei_and_dress_well(balance_gwei: i64) -> String {
         const MASK_FOR_NON_SIGNIFICANT_DIGITS: i64 = 10_000_000;
@@ -288,6 +289,53 @@ pub(in crate::commands::financials_command) mod restricted {
         }
     }
 
+    pub fn dress_wei_in_units(wei: i128, decimal_places: u32) -> String {
+        let divisor = 10_i128.pow(decimal_places);
+        let sign = if wei < 0 { "-" } else { "" };
+        let integer_part = (wei / divisor).abs();
+        let fractional_part = (wei % divisor).abs();
+        if fractional_part == 0 {
+            format!("{}{}", sign, integer_part)
+        } else {
+            let fractional_digits = format!(
+                "{:0>width$}",
+                fractional_part,
+                width = decimal_places as usize
+            );
+            format!(
+                "{}{}.{}",
+                sign,
+                integer_part,
+                fractional_digits.trim_end_matches('0')
+            )
+        }
+    }
+
+    pub fn parse_moment(moment: &str) -> Result<u64, String> {
+        fn parse_date(moment: &str) -> Option<i64> {
+            let mut parts = moment.split('-');
+            let year = parts.next()?.parse::<i32>().ok()?;
+            let month = Month::try_from(parts.next()?.parse::<u8>().ok()?).ok()?;
+            let day = parts.next()?.parse::<u8>().ok()?;
+            if parts.next().is_some() {
+                return None;
+            }
+            let date = Date::from_calendar_date(year, month, day).ok()?;
+            Some(date.midnight().assume_utc().unix_timestamp())
+        }
+        if let Ok(timestamp) = moment.parse::<u64>() {
+            return Ok(timestamp);
+        }
+        match parse_date(moment) {
+            Some(timestamp) if timestamp >= 0 => Ok(timestamp as u64),
+            Some(_) => Err(format!("Date '{}' lies before 1970-01-01", moment)),
+            None => Err(format!(
+                "'{}' is neither a date written as YYYY-MM-DD nor a Unix timestamp",
+                moment
+            )),
+        }
+    }
+
     fn check_right_dot_usage(num: &str, dot_idx: usize) -> Result<(), String> {
         if dot_idx == (num.len() - 1) {
             Err(format!(
@@ -305,8 +353,8 @@ pub(in crate::commands::financials_command) mod restricted {
 #[cfg(test)]
 mod tests {
     use crate::commands::financials_command::parsing_and_value_dressing::restricted::{
-        convert_masq_from_gwei_and_dress_well, extract_individual_masq_values,
-        neaten_users_writing_if_possible, parse_integer_within_limits,
+        convert_masq_from_gwei_and_dress_well, dress_wei_in_units, extract_individual_masq_values,
+        neaten_users_writing_if_possible, parse_integer_within_limits, parse_moment,
         process_optionally_fractional_number,
     };
     use crate::commands::financials_command::test_utils::transpose_inputs_to_nested_tuples;
@@ -516,4 +564,53 @@ mod tests {
             Err("Misused decimal number dot delimiter at '45.056.000'".to_string())
         )
     }
+
+    #[test]
+    fn dress_wei_in_units_keeps_every_digit() {
+        assert_eq!(
+            dress_wei_in_units(1_000_000_000_000_000_001, 18),
+            "1.000000000000000001"
+        );
+        assert_eq!(dress_wei_in_units(2_500_000_000, 9), "2.5");
+        assert_eq!(dress_wei_in_units(-987_654_321, 9), "-0.987654321");
+        assert_eq!(dress_wei_in_units(4_000_000_000, 9), "4");
+        assert_eq!(dress_wei_in_units(-45, 0), "-45");
+        assert_eq!(dress_wei_in_units(0, 18), "0");
+    }
+
+    #[test]
+    fn parse_moment_accepts_unix_timestamps_and_dates() {
+        assert_eq!(parse_moment("1666000000"), Ok(1_666_000_000));
+        assert_eq!(parse_moment("2022-10-17"), Ok(1_665_964_800));
+        assert_eq!(parse_moment("1970-01-01"), Ok(0));
+    }
+
+    #[test]
+    fn parse_moment_rejects_what_it_cannot_understand() {
+        assert_eq!(
+            parse_moment("2022-02-30"),
+            Err(
+                "'2022-02-30' is neither a date written as YYYY-MM-DD nor a Unix timestamp"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_moment("yesterday"),
+            Err(
+                "'yesterday' is neither a date written as YYYY-MM-DD nor a Unix timestamp"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_moment("2022-10-17-12"),
+            Err(
+                "'2022-10-17-12' is neither a date written as YYYY-MM-DD nor a Unix timestamp"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            parse_moment("1969-12-31"),
+            Err("Date '1969-12-31' lies before 1970-01-01".to_string())
+        );
+    }
 }
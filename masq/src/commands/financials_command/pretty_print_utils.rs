@@ -8,12 +8,14 @@ pub(in crate::commands::financials_command) mod restricted {
         convert_masq_from_gwei_and_dress_well, neaten_users_writing_if_possible,
     };
     use crate::commands::financials_command::FinancialsCommand;
-    use masq_lib::constants::WALLET_ADDRESS_LENGTH;
-    use masq_lib::messages::{UiPayableAccount, UiReceivableAccount};
+    use masq_lib::constants::{WALLET_ADDRESS_LENGTH, WEIS_IN_GWEI};
+    use masq_lib::messages::{UiLedgerEntry, UiPayableAccount, UiReceivableAccount};
     use masq_lib::short_writeln;
     use std::fmt::{Debug, Display};
     use std::io::Write;
     use thousands::Separable;
+    use time::macros::format_description;
+    use time::OffsetDateTime;
 
     pub trait StringValuesFormattableAccount {
         fn convert_to_strings(&self, ordinal_num: usize, is_gwei: bool) -> Vec<String>;
@@ -46,6 +48,29 @@ pub(in crate::commands::financials_command) mod restricted {
         }
     }
 
+    impl StringValuesFormattableAccount for UiLedgerEntry {
+        fn convert_to_strings(&self, ordinal_num: usize, is_gwei: bool) -> Vec<String> {
+            fn or_none<T: ToString>(value_opt: &Option<T>) -> String {
+                match value_opt {
+                    Some(value) => value.to_string(),
+                    None => "None".to_string(),
+                }
+            }
+            vec![
+                ordinal_num.to_string(),
+                self.wallet.to_string(),
+                utc_time_from_timestamp(self.timestamp),
+                self.kind.to_string(),
+                process_wei_into_requested_format(&self.amount_wei, is_gwei),
+                or_none(&self.transaction_hash_opt),
+                or_none(&self.nonce_opt),
+                or_none(&self.gas_price_gwei_opt),
+                or_none(&self.block_number_opt),
+                or_none(&self.outcome_opt),
+            ]
+        }
+    }
+
     pub fn financial_status_totals_title(stdout: &mut dyn Write, is_gwei: bool) {
         short_writeln!(
             stdout,
@@ -120,6 +145,32 @@ pub(in crate::commands::financials_command) mod restricted {
         }
     }
 
+    // Ledger amounts are exact to the wei, so in gwei they keep whatever fraction they have
+    pub fn process_wei_into_requested_format(wei: &str, should_stay_gwei: bool) -> String {
+        let wei = match wei.parse::<u128>() {
+            Ok(wei) => wei,
+            Err(_) => return wei.to_string(),
+        };
+        let gwei = wei / WEIS_IN_GWEI as u128;
+        let wei_remainder = wei % WEIS_IN_GWEI as u128;
+        if !should_stay_gwei {
+            convert_masq_from_gwei_and_dress_well(i64::try_from(gwei).unwrap_or(i64::MAX))
+        } else if wei_remainder == 0 {
+            gwei.separate_with_commas()
+        } else {
+            let fraction = format!("{:09}", wei_remainder);
+            format!(
+                "{}.{}",
+                gwei.separate_with_commas(),
+                fraction.trim_end_matches('0')
+            )
+        }
+    }
+
+    pub fn ledger_history_title(stdout: &mut dyn Write) {
+        short_writeln!(stdout, "\nLedger history\n")
+    }
+
     pub fn triple_or_single_blank_line(stdout: &mut dyn Write, leading_dump: bool) {
         if leading_dump {
             short_writeln!(stdout)
@@ -149,6 +200,41 @@ pub(in crate::commands::financials_command) mod restricted {
         )
     }
 
+    pub fn prepare_ledger_headings(is_gwei: bool) -> HeadingsHolder {
+        let amount = format!("Amount {}", gwei_or_masq_units(is_gwei));
+        HeadingsHolder {
+            words: vec![
+                "#",
+                "Wallet",
+                "Time [UTC]",
+                "Kind",
+                &amount,
+                "Tx hash",
+                "Nonce",
+                "Gas price [gwei]",
+                "Block",
+                "Outcome",
+            ]
+            .into_iter()
+            .map(|word| word.to_string())
+            .collect(),
+            is_gwei,
+        }
+    }
+
+    pub fn utc_time_from_timestamp(timestamp: u64) -> String {
+        i64::try_from(timestamp)
+            .ok()
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+            .and_then(|time| {
+                time.format(format_description!(
+                    "[year]-[month]-[day] [hour]:[minute]:[second]"
+                ))
+                .ok()
+            })
+            .unwrap_or_else(|| timestamp.to_string())
+    }
+
     fn gwei_or_masq_balance(is_gwei: bool) -> String {
         format!("Balance {}", gwei_or_masq_units(is_gwei))
     }
@@ -249,7 +335,7 @@ pub(in crate::commands::financials_command) mod restricted {
 #[cfg(test)]
 mod tests {
     use crate::commands::financials_command::pretty_print_utils::restricted::{
-        figure_out_max_widths, StringValuesFormattableAccount,
+        figure_out_max_widths, process_wei_into_requested_format, StringValuesFormattableAccount,
     };
 
     #[derive(Clone)]
@@ -310,4 +396,23 @@ mod tests {
         //the second number is always 42 as the length of wallet address
         assert_eq!(result, vec![3, 42, 5, 10])
     }
+
+    #[test]
+    fn process_wei_into_requested_format_keeps_sub_gwei_amounts() {
+        assert_eq!(process_wei_into_requested_format("550", true), "0.00000055");
+        assert_eq!(
+            process_wei_into_requested_format("1234000000001", true),
+            "1,234.000000001"
+        );
+        assert_eq!(
+            process_wei_into_requested_format("1234000000000", true),
+            "1,234"
+        );
+        assert_eq!(process_wei_into_requested_format("550", false), "< 0.01");
+        assert_eq!(
+            process_wei_into_requested_format("644000000000000000", false),
+            "0.64"
+        );
+        assert_eq!(process_wei_into_requested_format("booga", true), "booga");
+    }
 }
@@ -0,0 +1,269 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiForgiveDebtRequest, UiForgiveDebtResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ForgiveDebtCommand {
+    wallet: String,
+    write_off: bool,
+}
+
+const FORGIVE_DEBT_SUBCOMMAND_ABOUT: &str =
+    "Forgives the debt of a delinquent wallet by restarting its aging as if it had just paid, \
+     withdraws any delinquency warning against it, and lifts any ban for delinquency. Only valid \
+     if Node is already running.";
+const WALLET_ARG_HELP: &str = "Address of the debtor's wallet, starting with 0x.";
+const WRITE_OFF_ARG_HELP: &str =
+    "Instead of restarting its aging, deletes the debt altogether and records the loss in the ledger.";
+
+pub fn forgive_debt_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("forgive-debt")
+        .about(FORGIVE_DEBT_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("wallet")
+                .help(WALLET_ARG_HELP)
+                .index(1)
+                .value_name("WALLET")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("write-off")
+                .help(WRITE_OFF_ARG_HELP)
+                .long("write-off")
+                .required(false)
+                .takes_value(false),
+        )
+}
+
+impl Command for ForgiveDebtCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiForgiveDebtRequest {
+            wallet: self.wallet.clone(),
+            write_off: self.write_off,
+        };
+        let output: Result<UiForgiveDebtResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                short_writeln!(
+                    context.stdout(),
+                    "Debt of {} wei owed by wallet {} has been {}.",
+                    response.balance_wei,
+                    self.wallet,
+                    if self.write_off {
+                        "written off"
+                    } else {
+                        "forgiven"
+                    }
+                );
+                if response.unbanned {
+                    short_writeln!(
+                        context.stdout(),
+                        "Wallet {} is no longer banned.",
+                        self.wallet
+                    );
+                }
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Debt forgiveness failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl ForgiveDebtCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match forgive_debt_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            wallet: matches
+                .value_of("wallet")
+                .expect("wallet parameter is not properly required")
+                .to_string(),
+            write_off: matches.is_present("write-off"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::DEBT_FORGIVENESS_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    const WALLET: &str = "0x1111111111111111111111111111111111111111";
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            FORGIVE_DEBT_SUBCOMMAND_ABOUT,
+            "Forgives the debt of a delinquent wallet by restarting its aging as if it had just paid, \
+             withdraws any delinquency warning against it, and lifts any ban for delinquency. Only valid \
+             if Node is already running."
+        );
+        assert_eq!(
+            WALLET_ARG_HELP,
+            "Address of the debtor's wallet, starting with 0x."
+        );
+        assert_eq!(
+            WRITE_OFF_ARG_HELP,
+            "Instead of restarting its aging, deletes the debt altogether and records the loss in the ledger."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory() {
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiForgiveDebtResponse {
+            balance_wei: "1000".to_string(),
+            unbanned: false,
+        }
+        .tmb(0)));
+        let subject = factory
+            .make(&["forgive-debt".to_string(), WALLET.to_string()])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn forgive_debt_command_requires_a_wallet() {
+        let result = ForgiveDebtCommand::new(&["forgive-debt".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn forgive_debt_command_happy_path_with_unban() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiForgiveDebtResponse {
+                balance_wei: "123456789".to_string(),
+                unbanned: true,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject =
+            ForgiveDebtCommand::new(&["forgive-debt".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiForgiveDebtRequest {
+                    wallet: WALLET.to_string(),
+                    write_off: false,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Debt of 123456789 wei owed by wallet {} has been forgiven.\n\
+                 Wallet {} is no longer banned.\n",
+                WALLET, WALLET
+            )
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn forgive_debt_command_happy_path_with_write_off() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiForgiveDebtResponse {
+                balance_wei: "123456789".to_string(),
+                unbanned: false,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = ForgiveDebtCommand::new(&[
+            "forgive-debt".to_string(),
+            WALLET.to_string(),
+            "--write-off".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiForgiveDebtRequest {
+                    wallet: WALLET.to_string(),
+                    write_off: true,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Debt of 123456789 wei owed by wallet {} has been written off.\n",
+                WALLET
+            )
+        );
+    }
+
+    #[test]
+    fn forgive_debt_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(DEBT_FORGIVENESS_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject =
+            ForgiveDebtCommand::new(&["forgive-debt".to_string(), WALLET.to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                DEBT_FORGIVENESS_ERROR,
+                "Booga".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Debt forgiveness failed: Payload({}, \"Booga\")\n",
+                DEBT_FORGIVENESS_ERROR
+            )
+        );
+    }
+}
@@ -0,0 +1,487 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::CommandError::Other;
+use crate::commands::commands_common::{
+    install_ctrlc_handler, transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use crate::terminal::terminal_interface::TerminalWrapper;
+use clap::{App, Arg, SubCommand};
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use masq_lib::implement_as_any;
+use masq_lib::messages::{
+    FromMessageBody, UiLogLevel, UiLogStreamBroadcast, UiLogsRequest, UiLogsResponse,
+};
+use masq_lib::short_writeln;
+use masq_lib::ui_gateway::MessageBody;
+#[cfg(test)]
+use std::any::Any;
+use std::fmt::Debug;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+const DEFAULT_TAIL_LINES: u32 = 20;
+const BROADCAST_CHECK_INTERVAL_MILLIS: u64 = 100;
+
+const LOGS_SUBCOMMAND_ABOUT: &str =
+    "Shows lines from the Node's log, filtered by level and module in the Node itself. Without \
+     --follow, shows the last lines of the log file; with --follow, keeps showing new lines as \
+     they're logged.";
+const LEVEL_ARG_HELP: &str =
+    "Least severe level to show: error, warn, info, debug or trace. Independent of the level \
+     the Node writes to its log file. Defaults to info.";
+const MODULE_ARG_HELP: &str =
+    "Shows only lines logged by modules whose names start with this, like 'Neighborhood' or \
+     'Accountant'; case doesn't matter. May be given more than once. Defaults to all modules.";
+const TAIL_ARG_HELP: &str =
+    "Number of lines to show from the end of the log file before anything else. Defaults to 20 \
+     without --follow and to 0 with it.";
+const FOLLOW_ARG_HELP: &str =
+    "Keeps showing new log lines as the Node logs them. From the shell, press Ctrl-C to stop; \
+     at the interactive prompt, lines appear among your commands until you run 'logs' again \
+     without --follow.";
+
+pub fn logs_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("logs")
+        .about(LOGS_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("level")
+                .help(LEVEL_ARG_HELP)
+                .long("level")
+                .value_name("LEVEL")
+                .takes_value(true)
+                .required(false)
+                .possible_values(&["error", "warn", "info", "debug", "trace"])
+                .case_insensitive(true),
+        )
+        .arg(
+            Arg::with_name("module")
+                .help(MODULE_ARG_HELP)
+                .long("module")
+                .value_name("MODULE")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("tail")
+                .help(TAIL_ARG_HELP)
+                .long("tail")
+                .value_name("LINES")
+                .takes_value(true)
+                .required(false)
+                .validator(validate_tail),
+        )
+        .arg(
+            Arg::with_name("follow")
+                .help(FOLLOW_ARG_HELP)
+                .long("follow")
+                .takes_value(false)
+                .required(false),
+        )
+}
+
+fn validate_tail(tail: String) -> Result<(), String> {
+    match tail.parse::<u32>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(tail),
+    }
+}
+
+#[derive(Debug)]
+pub struct LogsCommand {
+    min_level: UiLogLevel,
+    modules: Vec<String>,
+    tail_lines_opt: Option<u32>,
+    follow: bool,
+    stop_flag: Arc<AtomicBool>,
+    interrupt_handler_installer: fn(Arc<AtomicBool>) -> Result<(), String>,
+}
+
+impl Command for LogsCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let broadcast_rx_opt = if self.follow {
+            context.broadcast_receiver_opt()
+        } else {
+            None
+        };
+        if broadcast_rx_opt.is_some() {
+            if let Err(e) = (self.interrupt_handler_installer)(self.stop_flag.clone()) {
+                return Err(Other(format!("Can't intercept Ctrl-C: {}", e)));
+            }
+        }
+        let input = UiLogsRequest {
+            min_level: self.min_level,
+            modules: self.modules.clone(),
+            tail_lines_opt: self.tail_lines_opt,
+            follow: self.follow,
+        };
+        let response: UiLogsResponse =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS)?;
+        response
+            .lines
+            .iter()
+            .for_each(|line| short_writeln!(context.stdout(), "{}", line));
+        match (self.follow, broadcast_rx_opt) {
+            (false, _) => Ok(()),
+            (true, Some(broadcast_rx)) => self.follow(context, &broadcast_rx),
+            (true, None) => {
+                short_writeln!(
+                    context.stdout(),
+                    "New log lines will be shown as they arrive; run 'logs' without --follow to \
+                     stop them."
+                );
+                Ok(())
+            }
+        }
+    }
+
+    implement_as_any!();
+}
+
+impl LogsCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match logs_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let min_level = match matches.value_of("level") {
+            Some(level) => UiLogLevel::from_str(level).expect("clap schema is failing"),
+            None => UiLogLevel::Info,
+        };
+        let modules = matches
+            .values_of("module")
+            .map(|modules| modules.map(|module| module.to_string()).collect())
+            .unwrap_or_default();
+        let follow = matches.is_present("follow");
+        let tail_lines_opt = match matches.value_of("tail") {
+            Some(tail) => Some(tail.parse::<u32>().expect("validator is failing")),
+            None if follow => None,
+            None => Some(DEFAULT_TAIL_LINES),
+        };
+        Ok(Self {
+            min_level,
+            modules,
+            tail_lines_opt,
+            follow,
+            stop_flag: Arc::new(AtomicBool::new(false)),
+            interrupt_handler_installer: install_ctrlc_handler,
+        })
+    }
+
+    pub fn handle_broadcast(
+        body: UiLogStreamBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(stdout, "{}", Self::format_line(&body));
+        stdout.flush().expect("flush failed");
+    }
+
+    fn follow(
+        &self,
+        context: &mut dyn CommandContext,
+        broadcast_rx: &Receiver<MessageBody>,
+    ) -> Result<(), CommandError> {
+        while !self.stop_flag.load(Ordering::Relaxed) {
+            match broadcast_rx.recv_timeout(Duration::from_millis(BROADCAST_CHECK_INTERVAL_MILLIS))
+            {
+                Ok(message_body) => {
+                    if let Ok((body, _)) = UiLogStreamBroadcast::fmb(message_body) {
+                        short_writeln!(context.stdout(), "{}", Self::format_line(&body));
+                        context.stdout().flush().expect("flush failed");
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => (),
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(CommandError::ConnectionProblem(
+                        "Broadcasts from the Node or Daemon stopped coming".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    //same shape as the lines in the log file, minus the thread
+    fn format_line(body: &UiLogStreamBroadcast) -> String {
+        let level = format!("{:?}", body.level).to_uppercase();
+        format!(
+            "{} {}: {}: {}",
+            body.timestamp, level, body.module, body.msg
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::commands::commands_common::CommandError::ConnectionProblem;
+    use crate::test_utils::mocks::{CommandContextMock, TerminalPassiveMock};
+    use crossbeam_channel::unbounded;
+    use masq_lib::constants::LOG_FILE_READ_ERROR;
+    use masq_lib::messages::{ToMessageBody, UiStreamStatusRequest};
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use std::sync::Mutex;
+
+    fn make_subject(follow: bool, stop_immediately: bool) -> LogsCommand {
+        LogsCommand {
+            min_level: UiLogLevel::Debug,
+            modules: vec!["Neighborhood".to_string()],
+            tail_lines_opt: Some(2),
+            follow,
+            stop_flag: Arc::new(AtomicBool::new(stop_immediately)),
+            interrupt_handler_installer: |_| Ok(()),
+        }
+    }
+
+    fn make_log_stream_broadcast(msg: &str) -> UiLogStreamBroadcast {
+        UiLogStreamBroadcast {
+            timestamp: "2026-10-19 10:00:00.123".to_string(),
+            level: UiLogLevel::Warn,
+            module: "Neighborhood".to_string(),
+            msg: msg.to_string(),
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DEFAULT_TAIL_LINES, 20);
+        assert_eq!(BROADCAST_CHECK_INTERVAL_MILLIS, 100);
+    }
+
+    #[test]
+    fn command_factory_makes_logs_command_with_all_arguments() {
+        let factory = CommandFactoryReal::new();
+
+        let result = factory
+            .make(&[
+                "logs".to_string(),
+                "--level".to_string(),
+                "TRACE".to_string(),
+                "--module".to_string(),
+                "Neighborhood".to_string(),
+                "--module".to_string(),
+                "proxy".to_string(),
+                "--tail".to_string(),
+                "50".to_string(),
+                "--follow".to_string(),
+            ])
+            .unwrap();
+
+        let command = result.as_any().downcast_ref::<LogsCommand>().unwrap();
+        assert_eq!(command.min_level, UiLogLevel::Trace);
+        assert_eq!(
+            command.modules,
+            vec!["Neighborhood".to_string(), "proxy".to_string()]
+        );
+        assert_eq!(command.tail_lines_opt, Some(50));
+        assert!(command.follow);
+        assert!(!command.stop_flag.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn defaults_without_follow() {
+        let result = LogsCommand::new(&["logs".to_string()]).unwrap();
+
+        assert_eq!(result.min_level, UiLogLevel::Info);
+        assert_eq!(result.modules, Vec::<String>::new());
+        assert_eq!(result.tail_lines_opt, Some(DEFAULT_TAIL_LINES));
+        assert!(!result.follow);
+    }
+
+    #[test]
+    fn follow_needs_no_tail_by_default() {
+        let result = LogsCommand::new(&["logs".to_string(), "--follow".to_string()]).unwrap();
+
+        assert_eq!(result.tail_lines_opt, None);
+        assert!(result.follow);
+    }
+
+    #[test]
+    fn bad_arguments_are_rejected() {
+        let bad_level = LogsCommand::new(&[
+            "logs".to_string(),
+            "--level".to_string(),
+            "loud".to_string(),
+        ]);
+        let bad_tail =
+            LogsCommand::new(&["logs".to_string(), "--tail".to_string(), "-3".to_string()]);
+
+        assert!(bad_level.is_err());
+        assert!(bad_tail.is_err());
+    }
+
+    #[test]
+    fn logs_command_prints_the_tail() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiLogsResponse {
+                lines: vec!["first line".to_string(), "second line".to_string()],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = make_subject(false, false);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiLogsRequest {
+                    min_level: UiLogLevel::Debug,
+                    modules: vec!["Neighborhood".to_string()],
+                    tail_lines_opt: Some(2),
+                    follow: false,
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "first line\nsecond line\n".to_string()
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn logs_command_reports_log_file_trouble() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(LOG_FILE_READ_ERROR, "No such file".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let subject = make_subject(false, false);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                LOG_FILE_READ_ERROR,
+                "No such file".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn logs_command_follows_at_the_interactive_prompt_by_leaving_lines_to_the_broadcast_handler() {
+        let mut context = CommandContextMock::new().transact_result(Ok(UiLogsResponse {
+            lines: vec!["old line".to_string()],
+        }
+        .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let mut subject = make_subject(true, false);
+        subject.interrupt_handler_installer = |_| panic!("Ctrl-C belongs to the prompt");
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "old line\nNew log lines will be shown as they arrive; run 'logs' without --follow \
+             to stop them.\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn logs_command_follows_from_the_shell_until_stopped() {
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        let subject = make_subject(true, false);
+        let stop_flag = subject.stop_flag.clone();
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_result(Ok(UiLogsResponse { lines: vec![] }.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        broadcast_tx
+            .send(make_log_stream_broadcast("Neighbor went away").tmb(0))
+            .unwrap();
+        broadcast_tx.send(UiStreamStatusRequest {}.tmb(0)).unwrap();
+        broadcast_tx
+            .send(make_log_stream_broadcast("Neighbor came back").tmb(0))
+            .unwrap();
+        let stopper = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            stop_flag.store(true, Ordering::Relaxed);
+        });
+
+        let result = subject.execute(&mut context);
+
+        stopper.join().unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "2026-10-19 10:00:00.123 WARN: Neighborhood: Neighbor went away\n\
+             2026-10-19 10:00:00.123 WARN: Neighborhood: Neighbor came back\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn logs_command_gives_up_following_when_the_connection_is_lost() {
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        drop(broadcast_tx);
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_result(Ok(UiLogsResponse { lines: vec![] }.tmb(0)));
+        let subject = make_subject(true, false);
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(ConnectionProblem(
+                "Broadcasts from the Node or Daemon stopped coming".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn logs_command_reports_failure_to_intercept_ctrl_c() {
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .broadcast_receiver(broadcast_rx)
+            .transact_params(&transact_params_arc);
+        let mut subject = make_subject(true, true);
+        subject.interrupt_handler_installer = |_| Err("Booga".to_string());
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(Other("Can't intercept Ctrl-C: Booga".to_string()))
+        );
+        assert!(transact_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn handle_broadcast_prints_the_line() {
+        let mut stdout = ByteArrayWriter::new();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        LogsCommand::handle_broadcast(
+            make_log_stream_broadcast("Something happened"),
+            &mut stdout,
+            &term_interface,
+        );
+
+        assert_eq!(
+            stdout.get_string(),
+            "2026-10-19 10:00:00.123 WARN: Neighborhood: Something happened\n".to_string()
+        );
+    }
+}
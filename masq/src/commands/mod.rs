@@ -1,18 +1,28 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod backup_command;
+pub mod ban_command;
+pub mod ban_list_command;
 pub mod change_password_command;
 pub mod check_password_command;
 pub mod commands_common;
 pub mod configuration_command;
 pub mod connection_status_command;
 pub mod crash_command;
+pub mod dashboard_command;
 pub mod descriptor_command;
+pub mod discover_wallets_command;
 pub mod financials_command;
+pub mod forgive_debt_command;
 pub mod generate_wallets_command;
+pub mod logs_command;
 pub mod recover_wallets_command;
+pub mod restore_command;
+pub mod rotate_earning_wallet_command;
 pub mod scan_command;
 pub mod set_configuration_command;
 pub mod setup_command;
 pub mod shutdown_command;
 pub mod start_command;
 pub mod wallet_addresses_command;
+pub mod wallet_balances_command;
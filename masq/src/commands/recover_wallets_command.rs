@@ -145,7 +145,7 @@ const EARNING_PATH_ARG_HELP: &str =
      quotes will cause problems.";
 const EARNING_ADDRESS_ARG_HELP: &str =
     "The address of the earning wallet. Represent it as '0x' followed by 40 hexadecimal digits.";
-const LANGUAGE_ARG_POSSIBLE_VALUES: [&str; 8] = [
+pub const LANGUAGE_ARG_POSSIBLE_VALUES: [&str; 8] = [
     "English",
     "Chinese",
     "Traditional Chinese",
@@ -155,7 +155,7 @@ const LANGUAGE_ARG_POSSIBLE_VALUES: [&str; 8] = [
     "Korean",
     "Spanish",
 ];
-const LANGUAGE_ARG_DEFAULT_VALUE: &str = "English";
+pub const LANGUAGE_ARG_DEFAULT_VALUE: &str = "English";
 
 pub fn recover_wallets_subcommand() -> App<'static, 'static> {
     SubCommand::with_name("recover-wallets")
@@ -0,0 +1,330 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::constants::CURRENT_SCHEMA_VERSION;
+use masq_lib::messages::{UiRestoreRequest, UiRestoreResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+use std::fs;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RestoreCommand {
+    file: String,
+    db_password: String,
+}
+
+const RESTORE_SUBCOMMAND_ABOUT: &str =
+    "Replaces the Node's configuration, wallets, payables, receivables, pending payables, bans, \
+     delinquency warnings and past neighbors with those in an archive written by the backup \
+     command. Archives from older versions of Node are migrated on the way in. Node shuts down \
+     afterwards and must be started again. Only valid if Node is already running.";
+const FILE_ARG_HELP: &str = "Path of the archive file to restore from.";
+const DB_PASSWORD_ARG_HELP: &str =
+    "The current database password. It must also be the one that was in effect when the \
+     archive was made.";
+
+pub fn restore_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("restore")
+        .about(RESTORE_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("file")
+                .help(FILE_ARG_HELP)
+                .index(1)
+                .value_name("FILE")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("db-password")
+                .help(DB_PASSWORD_ARG_HELP)
+                .long("db-password")
+                .value_name("DB-PASSWORD")
+                .required(true)
+                .case_insensitive(false)
+                .takes_value(true),
+        )
+}
+
+impl Command for RestoreCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let archive = match fs::read_to_string(&self.file) {
+            Ok(archive) => archive,
+            Err(e) => {
+                let error = CommandError::Other(format!(
+                    "Could not read archive from {}: {}",
+                    self.file, e
+                ));
+                short_writeln!(context.stderr(), "Restore failed: {:?}", error);
+                return Err(error);
+            }
+        };
+        let input = UiRestoreRequest {
+            db_password: self.db_password.clone(),
+            archive,
+        };
+        let output: Result<UiRestoreResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                if response.archive_schema_version < CURRENT_SCHEMA_VERSION {
+                    short_writeln!(
+                        context.stdout(),
+                        "Archive was migrated from database schema version {} to {}.",
+                        response.archive_schema_version,
+                        CURRENT_SCHEMA_VERSION
+                    );
+                }
+                short_writeln!(
+                    context.stdout(),
+                    "Node state restored from {}. The Node is shutting down; start it again to put it into effect.",
+                    self.file
+                );
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Restore failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl RestoreCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match restore_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            file: matches
+                .value_of("file")
+                .expect("file parameter is not properly required")
+                .to_string(),
+            db_password: matches
+                .value_of("db-password")
+                .expect("db-password parameter is not properly required")
+                .to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::RESTORE_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    fn make_archive_file(test_name: &str) -> PathBuf {
+        let home_dir = ensure_node_home_directory_exists("restore_command", test_name);
+        let file = home_dir.join("node.backup");
+        fs::write(&file, "0123abcd").unwrap();
+        file
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            RESTORE_SUBCOMMAND_ABOUT,
+            "Replaces the Node's configuration, wallets, payables, receivables, pending payables, bans, \
+             delinquency warnings and past neighbors with those in an archive written by the backup \
+             command. Archives from older versions of Node are migrated on the way in. Node shuts down \
+             afterwards and must be started again. Only valid if Node is already running."
+        );
+        assert_eq!(FILE_ARG_HELP, "Path of the archive file to restore from.");
+        assert_eq!(
+            DB_PASSWORD_ARG_HELP,
+            "The current database password. It must also be the one that was in effect when the \
+             archive was made."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory() {
+        let file = make_archive_file("testing_command_factory");
+        let factory = CommandFactoryReal::new();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiRestoreResponse {
+            archive_schema_version: CURRENT_SCHEMA_VERSION,
+        }
+        .tmb(0)));
+        let subject = factory
+            .make(&[
+                "restore".to_string(),
+                file.to_str().unwrap().to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+            ])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn restore_command_requires_a_password() {
+        let result = RestoreCommand::new(&["restore".to_string(), "node.backup".to_string()]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn restore_command_happy_path() {
+        let file = make_archive_file("restore_command_happy_path");
+        let file_name = file.to_str().unwrap().to_string();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiRestoreResponse {
+                archive_schema_version: CURRENT_SCHEMA_VERSION,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = RestoreCommand::new(&[
+            "restore".to_string(),
+            file_name.clone(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiRestoreRequest {
+                    db_password: "password".to_string(),
+                    archive: "0123abcd".to_string(),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Node state restored from {}. The Node is shutting down; start it again to put it into effect.\n",
+                file_name
+            )
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn restore_command_mentions_migration_of_older_archive() {
+        let file = make_archive_file("restore_command_mentions_migration_of_older_archive");
+        let file_name = file.to_str().unwrap().to_string();
+        let mut context = CommandContextMock::new().transact_result(Ok(UiRestoreResponse {
+            archive_schema_version: CURRENT_SCHEMA_VERSION - 1,
+        }
+        .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = RestoreCommand::new(&[
+            "restore".to_string(),
+            file_name.clone(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Archive was migrated from database schema version {} to {}.\n\
+                 Node state restored from {}. The Node is shutting down; start it again to put it into effect.\n",
+                CURRENT_SCHEMA_VERSION - 1,
+                CURRENT_SCHEMA_VERSION,
+                file_name
+            )
+        );
+    }
+
+    #[test]
+    fn restore_command_reports_unreadable_file() {
+        let home_dir = ensure_node_home_directory_exists(
+            "restore_command",
+            "restore_command_reports_unreadable_file",
+        );
+        let file_name = home_dir
+            .join("nonexistent.backup")
+            .to_str()
+            .unwrap()
+            .to_string();
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new().transact_params(&transact_params_arc);
+        let stderr_arc = context.stderr_arc();
+        let subject = RestoreCommand::new(&[
+            "restore".to_string(),
+            file_name.clone(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        match result {
+            Err(CommandError::Other(msg)) => assert!(
+                msg.starts_with(&format!("Could not read archive from {}: ", file_name)),
+                "{}",
+                msg
+            ),
+            x => panic!("Expected CommandError::Other, got {:?}", x),
+        }
+        assert!(transact_params_arc.lock().unwrap().is_empty());
+        assert!(stderr_arc
+            .lock()
+            .unwrap()
+            .get_string()
+            .starts_with("Restore failed: Other("));
+    }
+
+    #[test]
+    fn restore_command_sad_path() {
+        let file = make_archive_file("restore_command_sad_path");
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(RESTORE_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = RestoreCommand::new(&[
+            "restore".to_string(),
+            file.to_str().unwrap().to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(RESTORE_ERROR, "Booga".to_string()))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!("Restore failed: Payload({}, \"Booga\")\n", RESTORE_ERROR)
+        );
+    }
+}
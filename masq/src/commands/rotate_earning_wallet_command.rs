@@ -0,0 +1,305 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::ban_list_command::BanListCommand;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, Arg, SubCommand};
+use masq_lib::messages::{UiRotateEarningWalletRequest, UiRotateEarningWalletResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RotateEarningWalletCommand {
+    db_password: String,
+    new_earning_wallet: String,
+    watch_period_sec_opt: Option<u64>,
+}
+
+const ROTATE_EARNING_WALLET_SUBCOMMAND_ABOUT: &str =
+    "Replaces the earning wallet with a new one and gossips the change to the network. The old \
+     earning wallet keeps being watched for late payments for a while. Only valid if Node is \
+     already running.";
+const DB_PASSWORD_ARG_HELP: &str = "The current database password.";
+const NEW_EARNING_WALLET_ARG_HELP: &str = "Address of the new earning wallet, starting with 0x.";
+const WATCH_PERIOD_ARG_HELP: &str =
+    "Number of seconds to keep watching the old earning wallet for late payments. Defaults to \
+     30 days.";
+
+pub fn rotate_earning_wallet_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("rotate-earning-wallet")
+        .about(ROTATE_EARNING_WALLET_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("db-password")
+                .help(DB_PASSWORD_ARG_HELP)
+                .long("db-password")
+                .value_name("DB-PASSWORD")
+                .required(true)
+                .case_insensitive(false)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("new-earning-wallet")
+                .help(NEW_EARNING_WALLET_ARG_HELP)
+                .index(1)
+                .value_name("NEW-EARNING-WALLET")
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("watch-period")
+                .help(WATCH_PERIOD_ARG_HELP)
+                .long("watch-period")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .required(false)
+                .validator(validate_watch_period),
+        )
+}
+
+fn validate_watch_period(watch_period: String) -> Result<(), String> {
+    match watch_period.parse::<u64>() {
+        Ok(_) => Ok(()),
+        _ => Err(watch_period),
+    }
+}
+
+impl Command for RotateEarningWalletCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiRotateEarningWalletRequest {
+            db_password: self.db_password.clone(),
+            new_earning_wallet: self.new_earning_wallet.clone(),
+            watch_period_sec_opt: self.watch_period_sec_opt,
+        };
+        let output: Result<UiRotateEarningWalletResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                short_writeln!(
+                    context.stdout(),
+                    "Earning wallet changed from {} to {}.",
+                    response.old_earning_wallet,
+                    self.new_earning_wallet
+                );
+                short_writeln!(
+                    context.stdout(),
+                    "Payments to {} will still be recognized until {} UTC.",
+                    response.old_earning_wallet,
+                    BanListCommand::utc_time(response.watch_until)
+                );
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Earning wallet rotation failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl RotateEarningWalletCommand {
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match rotate_earning_wallet_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        Ok(Self {
+            db_password: matches
+                .value_of("db-password")
+                .expect("db-password parameter is not properly required")
+                .to_string(),
+            new_earning_wallet: matches
+                .value_of("new-earning-wallet")
+                .expect("new-earning-wallet parameter is not properly required")
+                .to_string(),
+            watch_period_sec_opt: matches.value_of("watch-period").map(|watch_period| {
+                watch_period
+                    .parse::<u64>()
+                    .expect("watch-period validator is not working")
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::EARNING_WALLET_ROTATION_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    const OLD_WALLET: &str = "0x1111111111111111111111111111111111111111";
+    const NEW_WALLET: &str = "0x2222222222222222222222222222222222222222";
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            ROTATE_EARNING_WALLET_SUBCOMMAND_ABOUT,
+            "Replaces the earning wallet with a new one and gossips the change to the network. The old \
+             earning wallet keeps being watched for late payments for a while. Only valid if Node is \
+             already running."
+        );
+        assert_eq!(DB_PASSWORD_ARG_HELP, "The current database password.");
+        assert_eq!(
+            NEW_EARNING_WALLET_ARG_HELP,
+            "Address of the new earning wallet, starting with 0x."
+        );
+        assert_eq!(
+            WATCH_PERIOD_ARG_HELP,
+            "Number of seconds to keep watching the old earning wallet for late payments. Defaults to \
+             30 days."
+        );
+    }
+
+    #[test]
+    fn testing_command_factory() {
+        let factory = CommandFactoryReal::new();
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiRotateEarningWalletResponse {
+                old_earning_wallet: OLD_WALLET.to_string(),
+                watch_until: 0,
+            }
+            .tmb(0)));
+        let subject = factory
+            .make(&[
+                "rotate-earning-wallet".to_string(),
+                "--db-password".to_string(),
+                "password".to_string(),
+                NEW_WALLET.to_string(),
+            ])
+            .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn validate_watch_period_works() {
+        assert!(validate_watch_period("abc".to_string()).is_err());
+        assert!(validate_watch_period("-1".to_string()).is_err());
+        assert!(validate_watch_period("86400".to_string()).is_ok());
+    }
+
+    #[test]
+    fn rotate_earning_wallet_command_requires_a_wallet() {
+        let result = RotateEarningWalletCommand::new(&[
+            "rotate-earning-wallet".to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+        ]);
+
+        let msg = result.unwrap_err();
+        assert!(
+            msg.contains("The following required arguments were not provided"),
+            "{}",
+            msg
+        );
+    }
+
+    #[test]
+    fn rotate_earning_wallet_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiRotateEarningWalletResponse {
+                old_earning_wallet: OLD_WALLET.to_string(),
+                watch_until: 1_700_000_000,
+            }
+            .tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = RotateEarningWalletCommand::new(&[
+            "rotate-earning-wallet".to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+            NEW_WALLET.to_string(),
+            "--watch-period".to_string(),
+            "86400".to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiRotateEarningWalletRequest {
+                    db_password: "password".to_string(),
+                    new_earning_wallet: NEW_WALLET.to_string(),
+                    watch_period_sec_opt: Some(86400),
+                }
+                .tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            format!(
+                "Earning wallet changed from {} to {}.\n\
+                 Payments to {} will still be recognized until 2023-11-14 22:13:20 UTC.\n",
+                OLD_WALLET, NEW_WALLET, OLD_WALLET
+            )
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn rotate_earning_wallet_command_leaves_watch_period_to_node_by_default() {
+        let subject = RotateEarningWalletCommand::new(&[
+            "rotate-earning-wallet".to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+            NEW_WALLET.to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            subject,
+            RotateEarningWalletCommand {
+                db_password: "password".to_string(),
+                new_earning_wallet: NEW_WALLET.to_string(),
+                watch_period_sec_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rotate_earning_wallet_command_sad_path() {
+        let mut context = CommandContextMock::new().transact_result(Err(
+            ContextError::PayloadError(EARNING_WALLET_ROTATION_ERROR, "Booga".to_string()),
+        ));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = RotateEarningWalletCommand::new(&[
+            "rotate-earning-wallet".to_string(),
+            "--db-password".to_string(),
+            "password".to_string(),
+            NEW_WALLET.to_string(),
+        ])
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                EARNING_WALLET_ROTATION_ERROR,
+                "Booga".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Earning wallet rotation failed: Payload({}, \"Booga\")\n",
+                EARNING_WALLET_ROTATION_ERROR
+            )
+        );
+    }
+}
@@ -3,11 +3,14 @@
 use crate::command_context::CommandContext;
 use crate::commands::commands_common::{transaction, Command, CommandError};
 use crate::terminal::terminal_interface::TerminalWrapper;
-use clap::{value_t, App, SubCommand};
+use clap::{value_t, App, Arg, SubCommand};
 use masq_lib::constants::SETUP_ERROR;
 use masq_lib::implement_as_any;
 use masq_lib::messages::{
-    UiSetupBroadcast, UiSetupInner, UiSetupRequest, UiSetupRequestValue, UiSetupResponse,
+    UiDiffSetupProfileRequest, UiDiffSetupProfileResponse, UiLoadSetupProfileRequest,
+    UiLoadSetupProfileResponse, UiSaveSetupProfileRequest, UiSaveSetupProfileResponse,
+    UiSetupBroadcast, UiSetupInner, UiSetupProfilesRequest, UiSetupProfilesResponse,
+    UiSetupRequest, UiSetupRequestValue, UiSetupResponse,
 };
 use masq_lib::shared_schema::shared_app;
 use masq_lib::short_writeln;
@@ -21,18 +24,65 @@ pub const SETUP_COMMAND_TIMEOUT_MILLIS: u64 = 30000;
 
 const SETUP_COMMAND_ABOUT: &str =
     "Establishes (if Node is not already running) and displays startup parameters for MASQNode.";
+const SAVE_ARG_HELP: &str =
+    "Saves the values established with the setup command, except for passwords and private keys, \
+     as a setup profile with this name. The Daemon keeps profiles next to the data directories.";
+const LOAD_ARG_HELP: &str =
+    "Replaces the values established with the setup command by those of the setup profile with \
+     this name. The result is validated just like an ordinary setup.";
+const DIFF_ARG_HELP: &str =
+    "Shows how the values of the setup profile with this name differ from the current setup.";
+const LIST_PROFILES_ARG_HELP: &str = "Lists the names of the saved setup profiles.";
+const NOT_SET: &str = "(not set)";
+const PROFILE_ARGS: [&str; 4] = ["save", "load", "diff", "list-profiles"];
 
 pub fn setup_subcommand() -> App<'static, 'static> {
     shared_app(SubCommand::with_name("setup").about(SETUP_COMMAND_ABOUT))
+        .arg(profile_arg("save", SAVE_ARG_HELP))
+        .arg(profile_arg("load", LOAD_ARG_HELP))
+        .arg(profile_arg("diff", DIFF_ARG_HELP))
+        .arg(
+            Arg::with_name("list-profiles")
+                .help(LIST_PROFILES_ARG_HELP)
+                .long("list-profiles")
+                .takes_value(false)
+                .conflicts_with_all(&["save", "load", "diff"]),
+        )
+}
+
+fn profile_arg(name: &'static str, help: &'static str) -> Arg<'static, 'static> {
+    Arg::with_name(name)
+        .help(help)
+        .long(name)
+        .value_name("PROFILE")
+        .takes_value(true)
+        .required(false)
+        .conflicts_with_all(match name {
+            "save" => &["load", "diff"],
+            "load" => &["save", "diff"],
+            _ => &["save", "load"],
+        })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SetupProfileAction {
+    Save(String),
+    Load(String),
+    Diff(String),
+    List,
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct SetupCommand {
     pub values: Vec<UiSetupRequestValue>,
+    pub profile_action_opt: Option<SetupProfileAction>,
 }
 
 impl Command for SetupCommand {
     fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        if let Some(action) = &self.profile_action_opt {
+            return Self::execute_profile_action(action, context);
+        }
         let out_message = UiSetupRequest {
             values: self.values.clone(),
         };
@@ -59,10 +109,22 @@ impl SetupCommand {
             Ok(matches) => matches,
             Err(e) => return Err(format!("{}", e)),
         };
+        let profile_action_opt = if let Some(name) = matches.value_of("save") {
+            Some(SetupProfileAction::Save(name.to_string()))
+        } else if let Some(name) = matches.value_of("load") {
+            Some(SetupProfileAction::Load(name.to_string()))
+        } else if let Some(name) = matches.value_of("diff") {
+            Some(SetupProfileAction::Diff(name.to_string()))
+        } else if matches.is_present("list-profiles") {
+            Some(SetupProfileAction::List)
+        } else {
+            None
+        };
         let mut values = pieces
             .iter()
             .filter(|piece| (*piece).starts_with("--"))
             .map(|piece| piece[2..].to_string())
+            .filter(|key| !PROFILE_ARGS.contains(&key.as_str()))
             .map(|key| {
                 if Self::has_value(pieces, &key) {
                     let value = value_t!(matches, &key, String).expect("Value disappeared!");
@@ -72,12 +134,95 @@ impl SetupCommand {
                 }
             })
             .collect::<Vec<UiSetupRequestValue>>();
+        if profile_action_opt.is_some() && !values.is_empty() {
+            return Err(
+                "Setup values can't be combined with --save, --load, --diff or --list-profiles"
+                    .to_string(),
+            );
+        }
         values.sort_by(|a, b| {
             a.name
                 .partial_cmp(&b.name)
                 .expect("String comparison failed")
         });
-        Ok(Self { values })
+        Ok(Self {
+            values,
+            profile_action_opt,
+        })
+    }
+
+    fn execute_profile_action(
+        action: &SetupProfileAction,
+        context: &mut dyn CommandContext,
+    ) -> Result<(), CommandError> {
+        match action {
+            SetupProfileAction::Save(name) => {
+                let response: UiSaveSetupProfileResponse = transaction(
+                    UiSaveSetupProfileRequest { name: name.clone() },
+                    context,
+                    SETUP_COMMAND_TIMEOUT_MILLIS,
+                )?;
+                let stdout = context.stdout();
+                short_writeln!(stdout, "Setup saved as profile '{}':\n", name);
+                short_writeln!(stdout, "{:29} {}", "NAME", "VALUE");
+                response
+                    .values
+                    .into_iter()
+                    .for_each(|value| short_writeln!(stdout, "{:29} {}", value.name, value.value));
+                short_writeln!(stdout);
+            }
+            SetupProfileAction::Load(name) => {
+                let response: UiLoadSetupProfileResponse = transaction(
+                    UiLoadSetupProfileRequest { name: name.clone() },
+                    context,
+                    SETUP_COMMAND_TIMEOUT_MILLIS,
+                )?;
+                Self::dump_setup(UiSetupInner::from(response), context.stdout());
+            }
+            SetupProfileAction::Diff(name) => {
+                let response: UiDiffSetupProfileResponse = transaction(
+                    UiDiffSetupProfileRequest { name: name.clone() },
+                    context,
+                    SETUP_COMMAND_TIMEOUT_MILLIS,
+                )?;
+                let stdout = context.stdout();
+                if response.differences.is_empty() {
+                    short_writeln!(stdout, "The setup matches profile '{}'.", name);
+                    return Ok(());
+                }
+                short_writeln!(stdout, "{:29} {:64} {}", "NAME", "PROFILE", "CURRENT");
+                response.differences.into_iter().for_each(|difference| {
+                    short_writeln!(
+                        stdout,
+                        "{:29} {:64} {}",
+                        difference.name,
+                        difference
+                            .profile_value_opt
+                            .unwrap_or_else(|| NOT_SET.to_string()),
+                        difference
+                            .current_value_opt
+                            .unwrap_or_else(|| NOT_SET.to_string())
+                    )
+                });
+                short_writeln!(stdout);
+            }
+            SetupProfileAction::List => {
+                let response: UiSetupProfilesResponse = transaction(
+                    UiSetupProfilesRequest {},
+                    context,
+                    SETUP_COMMAND_TIMEOUT_MILLIS,
+                )?;
+                let stdout = context.stdout();
+                if response.profiles.is_empty() {
+                    short_writeln!(stdout, "No setup profiles have been saved.");
+                }
+                response
+                    .profiles
+                    .into_iter()
+                    .for_each(|name| short_writeln!(stdout, "{}", name));
+            }
+        }
+        Ok(())
     }
 
     pub fn handle_broadcast(
@@ -136,13 +281,17 @@ impl SetupCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::command_context::ContextError;
     use crate::command_factory::{CommandFactory, CommandFactoryReal};
     use crate::communications::broadcast_handler::StreamFactory;
     use crate::test_utils::mocks::{CommandContextMock, TerminalPassiveMock, TestStreamFactory};
-    use masq_lib::constants::ETH_ROPSTEN_FULL_IDENTIFIER;
+    use masq_lib::constants::{ETH_ROPSTEN_FULL_IDENTIFIER, SETUP_PROFILE_ERROR};
     use masq_lib::messages::ToMessageBody;
     use masq_lib::messages::UiSetupResponseValueStatus::{Configured, Default, Set};
-    use masq_lib::messages::{UiSetupRequest, UiSetupResponse, UiSetupResponseValue};
+    use masq_lib::messages::{
+        UiSetupProfileDifference, UiSetupProfileValue, UiSetupRequest, UiSetupResponse,
+        UiSetupResponseValue,
+    };
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use std::sync::{Arc, Mutex};
 
@@ -308,6 +457,276 @@ NOTE: no changes were made to the setup because the Node is currently running.\n
         assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
     }
 
+    fn make_pieces(pieces: Vec<&str>) -> Vec<String> {
+        pieces.into_iter().map(|piece| piece.to_string()).collect()
+    }
+
+    #[test]
+    fn setup_command_recognizes_profile_actions() {
+        let results = vec![
+            vec!["setup", "--save", "dev-standard"],
+            vec!["setup", "--load", "dev-standard"],
+            vec!["setup", "--diff", "dev-standard"],
+            vec!["setup", "--list-profiles"],
+        ]
+        .into_iter()
+        .map(|pieces| SetupCommand::new(&make_pieces(pieces)).unwrap())
+        .map(|command| command.profile_action_opt)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                Some(SetupProfileAction::Save("dev-standard".to_string())),
+                Some(SetupProfileAction::Load("dev-standard".to_string())),
+                Some(SetupProfileAction::Diff("dev-standard".to_string())),
+                Some(SetupProfileAction::List),
+            ]
+        );
+    }
+
+    #[test]
+    fn setup_command_refuses_profile_actions_mixed_with_values() {
+        let result = SetupCommand::new(&make_pieces(vec![
+            "setup",
+            "--load",
+            "dev-standard",
+            "--log-level",
+            "debug",
+        ]));
+
+        assert_eq!(
+            result,
+            Err(
+                "Setup values can't be combined with --save, --load, --diff or --list-profiles"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn setup_command_refuses_two_profile_actions() {
+        let msg = SetupCommand::new(&make_pieces(vec![
+            "setup",
+            "--save",
+            "dev-standard",
+            "--load",
+            "polygon-consume-only",
+        ]))
+        .err()
+        .unwrap();
+
+        assert!(msg.contains("cannot be used with"), "{}", msg);
+    }
+
+    #[test]
+    fn setup_command_saves_profile() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiSaveSetupProfileResponse {
+                values: vec![
+                    UiSetupProfileValue::new("chain", "polygon-mainnet"),
+                    UiSetupProfileValue::new("neighborhood-mode", "consume-only"),
+                ],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(&make_pieces(vec![
+            "setup",
+            "--save",
+            "polygon-consume-only",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiSaveSetupProfileRequest {
+                    name: "polygon-consume-only".to_string()
+                }
+                .tmb(0),
+                SETUP_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Setup saved as profile 'polygon-consume-only':\n\
+\n\
+NAME                          VALUE\n\
+chain                         polygon-mainnet\n\
+neighborhood-mode             consume-only\n\
+\n"
+        );
+    }
+
+    #[test]
+    fn setup_command_loads_profile() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiLoadSetupProfileResponse {
+                running: false,
+                values: vec![
+                    UiSetupResponseValue::new("neighborhood-mode", "consume-only", Set),
+                    UiSetupResponseValue::new("chain", "polygon-mainnet", Set),
+                ],
+                errors: vec![("neighbors".to_string(), "Required".to_string())],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(&make_pieces(vec![
+            "setup",
+            "--load",
+            "polygon-consume-only",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiLoadSetupProfileRequest {
+                    name: "polygon-consume-only".to_string()
+                }
+                .tmb(0),
+                SETUP_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq! (stdout_arc.lock().unwrap().get_string(),
+"NAME                          VALUE                                                            STATUS\n\
+chain                         polygon-mainnet                                                  Set\n\
+neighborhood-mode             consume-only                                                     Set\n\
+\n\
+ERRORS:
+neighbors                     Required\n\
+\n");
+    }
+
+    #[test]
+    fn setup_command_diffs_profile() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiDiffSetupProfileResponse {
+                differences: vec![
+                    UiSetupProfileDifference {
+                        name: "ip".to_string(),
+                        profile_value_opt: None,
+                        current_value_opt: Some("1.2.3.4".to_string()),
+                    },
+                    UiSetupProfileDifference {
+                        name: "neighborhood-mode".to_string(),
+                        profile_value_opt: Some("consume-only".to_string()),
+                        current_value_opt: Some("zero-hop".to_string()),
+                    },
+                ],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(&make_pieces(vec![
+            "setup",
+            "--diff",
+            "polygon-consume-only",
+        ]))
+        .unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq! (stdout_arc.lock().unwrap().get_string(),
+"NAME                          PROFILE                                                          CURRENT\n\
+ip                            (not set)                                                        1.2.3.4\n\
+neighborhood-mode             consume-only                                                     zero-hop\n\
+\n");
+    }
+
+    #[test]
+    fn setup_command_diff_says_so_when_nothing_differs() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(UiDiffSetupProfileResponse {
+                differences: vec![],
+            }
+            .tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject =
+            SetupCommand::new(&make_pieces(vec!["setup", "--diff", "dev-standard"])).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "The setup matches profile 'dev-standard'.\n"
+        );
+    }
+
+    #[test]
+    fn setup_command_lists_profiles() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiSetupProfilesResponse {
+                profiles: vec![
+                    "dev-standard".to_string(),
+                    "polygon-consume-only".to_string(),
+                ],
+            }
+            .tmb(0)))
+            .transact_result(Ok(UiSetupProfilesResponse { profiles: vec![] }.tmb(0)));
+        let stdout_arc = context.stdout_arc();
+        let subject = SetupCommand::new(&make_pieces(vec!["setup", "--list-profiles"])).unwrap();
+
+        let first_result = subject.execute(&mut context);
+        let second_result = subject.execute(&mut context);
+
+        assert_eq!(first_result, Ok(()));
+        assert_eq!(second_result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![
+                (
+                    UiSetupProfilesRequest {}.tmb(0),
+                    SETUP_COMMAND_TIMEOUT_MILLIS
+                ),
+                (
+                    UiSetupProfilesRequest {}.tmb(0),
+                    SETUP_COMMAND_TIMEOUT_MILLIS
+                )
+            ]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "dev-standard\npolygon-consume-only\nNo setup profiles have been saved.\n"
+        );
+    }
+
+    #[test]
+    fn setup_command_passes_profile_errors_on() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ContextError::PayloadError(
+                SETUP_PROFILE_ERROR,
+                "There is no setup profile named 'booga'".to_string(),
+            )));
+        let subject = SetupCommand::new(&make_pieces(vec!["setup", "--load", "booga"])).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                SETUP_PROFILE_ERROR,
+                "There is no setup profile named 'booga'".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn handle_broadcast_works() {
         let message = UiSetupBroadcast {
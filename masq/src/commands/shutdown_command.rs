@@ -5,8 +5,9 @@ use crate::commands::commands_common::CommandError::{
     ConnectionProblem, Other, Payload, Transmission,
 };
 use crate::commands::commands_common::{transaction, Command, CommandError};
-use clap::{App, SubCommand};
+use clap::{App, Arg, SubCommand};
 use masq_lib::constants::NODE_NOT_RUNNING_ERROR;
+use masq_lib::messages::{UiGracefulShutdownRequest, UiGracefulShutdownResponse};
 use masq_lib::messages::{UiShutdownRequest, UiShutdownResponse};
 use masq_lib::short_writeln;
 use masq_lib::utils::localhost;
@@ -19,9 +20,12 @@ use std::time::{Duration, Instant};
 const DEFAULT_SHUTDOWN_ATTEMPT_INTERVAL: u64 = 250; // milliseconds
 const DEFAULT_SHUTDOWN_ATTEMPT_LIMIT: u64 = 4;
 const SHUTDOWN_COMMAND_TIMEOUT_MILLIS: u64 = 60000;
+const DEFAULT_DRAIN_TIMEOUT_SEC: u64 = 30;
+const GRACEFUL_SHUTDOWN_MARGIN_MILLIS: u64 = 60000; // farewell Gossip and final payable scan
 
 #[derive(Debug)]
 pub struct ShutdownCommand {
+    graceful_opt: Option<UiGracefulShutdownRequest>,
     shutdown_awaiter: Box<dyn ShutdownAwaiter>,
     attempt_interval: u64,
     attempt_limit: u64,
@@ -29,16 +33,68 @@ pub struct ShutdownCommand {
 
 const SHUTDOWN_SUBCOMMAND_ABOUT: &str =
     "Shuts down the running MASQNode. Only valid if Node is already running.";
+const GRACEFUL_ARG_HELP: &str =
+    "Stops accepting new client streams, lets the open ones finish, tells the neighbors \
+     goodbye, and only then exits. Without it, the Node stops at once.";
+const DRAIN_TIMEOUT_ARG_HELP: &str =
+    "Number of seconds a graceful shutdown waits for open client streams to finish. \
+     Defaults to 30.";
+const FINAL_SCAN_ARG_HELP: &str =
+    "Makes a graceful shutdown pay the debts that are due before exiting.";
 
 pub fn shutdown_subcommand() -> App<'static, 'static> {
-    SubCommand::with_name("shutdown").about(SHUTDOWN_SUBCOMMAND_ABOUT)
+    SubCommand::with_name("shutdown")
+        .about(SHUTDOWN_SUBCOMMAND_ABOUT)
+        .arg(
+            Arg::with_name("graceful")
+                .help(GRACEFUL_ARG_HELP)
+                .long("graceful")
+                .required(false)
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("drain-timeout")
+                .help(DRAIN_TIMEOUT_ARG_HELP)
+                .long("drain-timeout")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .required(false)
+                .requires("graceful")
+                .validator(validate_drain_timeout),
+        )
+        .arg(
+            Arg::with_name("final-scan")
+                .help(FINAL_SCAN_ARG_HELP)
+                .long("final-scan")
+                .required(false)
+                .takes_value(false)
+                .requires("graceful"),
+        )
+}
+
+fn validate_drain_timeout(drain_timeout: String) -> Result<(), String> {
+    match drain_timeout.parse::<u64>() {
+        Ok(_) => Ok(()),
+        _ => Err(drain_timeout),
+    }
 }
 
 impl Command for ShutdownCommand {
     fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
-        let input = UiShutdownRequest {};
-        let output: Result<UiShutdownResponse, CommandError> =
-            transaction(input, context, SHUTDOWN_COMMAND_TIMEOUT_MILLIS);
+        let output = match &self.graceful_opt {
+            None => transaction::<UiShutdownRequest, UiShutdownResponse>(
+                UiShutdownRequest {},
+                context,
+                SHUTDOWN_COMMAND_TIMEOUT_MILLIS,
+            )
+            .map(|_| ()),
+            Some(request) => transaction::<UiGracefulShutdownRequest, UiGracefulShutdownResponse>(
+                request.clone(),
+                context,
+                SHUTDOWN_COMMAND_TIMEOUT_MILLIS,
+            )
+            .map(|_| ()),
+        };
         match output {
             Ok(_) => (),
             Err(ConnectionProblem(_)) => {
@@ -67,6 +123,12 @@ impl Command for ShutdownCommand {
                 panic!("Unexpected error: please report to us: {}", unknown_error)
             }
         }
+        if self.graceful_opt.is_some() {
+            short_writeln!(
+                context.stdout(),
+                "MASQNode was instructed to shut down gracefully; waiting for it to finish"
+            );
+        }
         match context.active_port() {
             None => {
                 short_writeln!(
@@ -76,11 +138,10 @@ impl Command for ShutdownCommand {
                 Ok(())
             }
             Some(active_port) => {
-                if self.shutdown_awaiter.wait(
-                    active_port,
-                    self.attempt_interval,
-                    self.attempt_limit,
-                ) {
+                if self
+                    .shutdown_awaiter
+                    .wait(active_port, self.attempt_interval, self.wait_limit())
+                {
                     short_writeln!(
                         context.stdout(),
                         "MASQNode was instructed to shut down and has stopped answering"
@@ -101,6 +162,7 @@ impl Command for ShutdownCommand {
 impl Default for ShutdownCommand {
     fn default() -> Self {
         Self {
+            graceful_opt: None,
             shutdown_awaiter: Box::new(ShutdownAwaiterReal {}),
             attempt_interval: DEFAULT_SHUTDOWN_ATTEMPT_INTERVAL,
             attempt_limit: DEFAULT_SHUTDOWN_ATTEMPT_LIMIT,
@@ -109,8 +171,41 @@ impl Default for ShutdownCommand {
 }
 
 impl ShutdownCommand {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(pieces: &[String]) -> Result<Self, String> {
+        let matches = match shutdown_subcommand().get_matches_from_safe(pieces) {
+            Ok(matches) => matches,
+            Err(e) => return Err(format!("{}", e)),
+        };
+        let graceful_opt = if matches.is_present("graceful") {
+            Some(UiGracefulShutdownRequest {
+                drain_timeout_sec: matches
+                    .value_of("drain-timeout")
+                    .map(|drain_timeout| {
+                        drain_timeout
+                            .parse::<u64>()
+                            .expect("drain-timeout validator is not working")
+                    })
+                    .unwrap_or(DEFAULT_DRAIN_TIMEOUT_SEC),
+                final_scan: matches.is_present("final-scan"),
+            })
+        } else {
+            None
+        };
+        Ok(Self {
+            graceful_opt,
+            ..Self::default()
+        })
+    }
+
+    fn wait_limit(&self) -> u64 {
+        match &self.graceful_opt {
+            None => self.attempt_limit,
+            Some(request) => {
+                self.attempt_limit
+                    + request.drain_timeout_sec * 1000
+                    + GRACEFUL_SHUTDOWN_MARGIN_MILLIS
+            }
+        }
     }
 }
 
@@ -158,10 +253,26 @@ mod tests {
         assert_eq!(DEFAULT_SHUTDOWN_ATTEMPT_INTERVAL, 250);
         assert_eq!(DEFAULT_SHUTDOWN_ATTEMPT_LIMIT, 4);
         assert_eq!(SHUTDOWN_COMMAND_TIMEOUT_MILLIS, 60000);
+        assert_eq!(DEFAULT_DRAIN_TIMEOUT_SEC, 30);
+        assert_eq!(GRACEFUL_SHUTDOWN_MARGIN_MILLIS, 60000);
         assert_eq!(
             SHUTDOWN_SUBCOMMAND_ABOUT,
             "Shuts down the running MASQNode. Only valid if Node is already running."
         );
+        assert_eq!(
+            GRACEFUL_ARG_HELP,
+            "Stops accepting new client streams, lets the open ones finish, tells the neighbors \
+             goodbye, and only then exits. Without it, the Node stops at once."
+        );
+        assert_eq!(
+            DRAIN_TIMEOUT_ARG_HELP,
+            "Number of seconds a graceful shutdown waits for open client streams to finish. \
+             Defaults to 30."
+        );
+        assert_eq!(
+            FINAL_SCAN_ARG_HELP,
+            "Makes a graceful shutdown pay the debts that are due before exiting."
+        );
     }
 
     #[derive(Debug)]
@@ -201,7 +312,7 @@ mod tests {
 
     #[test]
     fn shutdown_command_defaults_parameters() {
-        let subject = ShutdownCommand::new();
+        let subject = ShutdownCommand::default();
 
         assert_eq!(subject.attempt_interval, DEFAULT_SHUTDOWN_ATTEMPT_INTERVAL);
         assert_eq!(subject.attempt_limit, DEFAULT_SHUTDOWN_ATTEMPT_LIMIT);
@@ -219,6 +330,125 @@ mod tests {
         assert_eq!(result, Ok(()));
     }
 
+    #[test]
+    fn shutdown_command_without_flags_is_hard() {
+        let subject = ShutdownCommand::new(&["shutdown".to_string()]).unwrap();
+
+        assert_eq!(subject.graceful_opt, None);
+    }
+
+    #[test]
+    fn shutdown_command_with_graceful_flag_uses_defaults() {
+        let subject =
+            ShutdownCommand::new(&["shutdown".to_string(), "--graceful".to_string()]).unwrap();
+
+        assert_eq!(
+            subject.graceful_opt,
+            Some(UiGracefulShutdownRequest {
+                drain_timeout_sec: DEFAULT_DRAIN_TIMEOUT_SEC,
+                final_scan: false,
+            })
+        );
+    }
+
+    #[test]
+    fn shutdown_command_with_all_graceful_parameters() {
+        let subject = ShutdownCommand::new(&[
+            "shutdown".to_string(),
+            "--graceful".to_string(),
+            "--drain-timeout".to_string(),
+            "45".to_string(),
+            "--final-scan".to_string(),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            subject.graceful_opt,
+            Some(UiGracefulShutdownRequest {
+                drain_timeout_sec: 45,
+                final_scan: true,
+            })
+        );
+    }
+
+    #[test]
+    fn shutdown_command_refuses_graceful_parameters_without_graceful_flag() {
+        let result = ShutdownCommand::new(&["shutdown".to_string(), "--final-scan".to_string()]);
+
+        let msg = result.err().unwrap();
+        assert!(
+            msg.contains("--graceful"),
+            "Expected mention of --graceful in '{}'",
+            msg
+        );
+    }
+
+    #[test]
+    fn shutdown_command_refuses_bad_drain_timeout() {
+        let result = ShutdownCommand::new(&[
+            "shutdown".to_string(),
+            "--graceful".to_string(),
+            "--drain-timeout".to_string(),
+            "forever".to_string(),
+        ]);
+
+        let msg = result.err().unwrap();
+        assert!(msg.contains("forever"), "Expected 'forever' in '{}'", msg);
+    }
+
+    #[test]
+    fn graceful_shutdown_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let port = find_free_port();
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(UiGracefulShutdownResponse {}.tmb(0)))
+            .active_port_result(Some(port));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let wait_params_arc = Arc::new(Mutex::new(vec![]));
+        let shutdown_awaiter = ShutdownAwaiterMock::new()
+            .wait_params(&wait_params_arc)
+            .wait_result(true);
+        let mut subject = ShutdownCommand::new(&[
+            "shutdown".to_string(),
+            "--graceful".to_string(),
+            "--drain-timeout".to_string(),
+            "5".to_string(),
+        ])
+        .unwrap();
+        subject.shutdown_awaiter = Box::new(shutdown_awaiter);
+        subject.attempt_interval = 10;
+        subject.attempt_limit = 3;
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiGracefulShutdownRequest {
+                    drain_timeout_sec: 5,
+                    final_scan: false,
+                }
+                .tmb(0),
+                SHUTDOWN_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "MASQNode was instructed to shut down gracefully; waiting for it to finish\n\
+             MASQNode was instructed to shut down and has stopped answering\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+        let wait_params = wait_params_arc.lock().unwrap();
+        assert_eq!(
+            *wait_params,
+            vec![(port, 10, 3 + 5000 + GRACEFUL_SHUTDOWN_MARGIN_MILLIS)]
+        )
+    }
+
     #[test]
     fn shutdown_command_doesnt_work_if_node_is_not_running() {
         let mut context = CommandContextMock::new().transact_result(Err(
@@ -226,7 +456,7 @@ mod tests {
         ));
         let stdout_arc = context.stdout_arc();
         let stderr_arc = context.stderr_arc();
-        let subject = ShutdownCommand::new();
+        let subject = ShutdownCommand::default();
 
         let result = subject.execute(&mut context);
 
@@ -254,7 +484,7 @@ mod tests {
         let stderr_arc = context.stderr_arc();
         let wait_params_arc = Arc::new(Mutex::new(vec![]));
         let shutdown_awaiter = ShutdownAwaiterMock::new().wait_params(&wait_params_arc);
-        let mut subject = ShutdownCommand::new();
+        let mut subject = ShutdownCommand::default();
         subject.shutdown_awaiter = Box::new(shutdown_awaiter);
         subject.attempt_interval = 10;
         subject.attempt_limit = 3;
@@ -285,7 +515,7 @@ mod tests {
         let stderr_arc = context.stderr_arc();
         let wait_params_arc = Arc::new(Mutex::new(vec![]));
         let shutdown_awaiter = ShutdownAwaiterMock::new().wait_params(&wait_params_arc);
-        let mut subject = ShutdownCommand::new();
+        let mut subject = ShutdownCommand::default();
         subject.shutdown_awaiter = Box::new(shutdown_awaiter);
         subject.attempt_interval = 10;
         subject.attempt_limit = 3;
@@ -321,7 +551,7 @@ mod tests {
         let shutdown_awaiter = ShutdownAwaiterMock::new()
             .wait_params(&wait_params_arc)
             .wait_result(true);
-        let mut subject = ShutdownCommand::new();
+        let mut subject = ShutdownCommand::default();
         subject.shutdown_awaiter = Box::new(shutdown_awaiter);
         subject.attempt_interval = 10;
         subject.attempt_limit = 3;
@@ -357,7 +587,7 @@ mod tests {
         let shutdown_awaiter = ShutdownAwaiterMock::new()
             .wait_params(&wait_params_arc)
             .wait_result(true);
-        let mut subject = ShutdownCommand::new();
+        let mut subject = ShutdownCommand::default();
         subject.shutdown_awaiter = Box::new(shutdown_awaiter);
         subject.attempt_interval = 10;
         subject.attempt_limit = 3;
@@ -394,7 +624,7 @@ mod tests {
         let shutdown_awaiter = ShutdownAwaiterMock::new()
             .wait_params(&wait_params_arc)
             .wait_result(false);
-        let mut subject = ShutdownCommand::new();
+        let mut subject = ShutdownCommand::default();
         subject.shutdown_awaiter = Box::new(shutdown_awaiter);
         subject.attempt_interval = 10;
         subject.attempt_limit = 3;
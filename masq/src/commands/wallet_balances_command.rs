@@ -0,0 +1,188 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::CommandContext;
+use crate::commands::commands_common::{
+    transaction, Command, CommandError, STANDARD_COMMAND_TIMEOUT_MILLIS,
+};
+use clap::{App, SubCommand};
+use masq_lib::messages::{UiWalletBalancesRequest, UiWalletBalancesResponse};
+use masq_lib::short_writeln;
+use std::fmt::Debug;
+use thousands::Separable;
+
+#[derive(Debug)]
+pub struct WalletBalancesCommand {}
+
+const WALLET_BALANCES_SUBCOMMAND_ABOUT: &str =
+    "Displays the gas-currency and MASQ balances of the consuming wallet, along with the \
+     thresholds below which the Node stops consuming services. Only valid if Node is already running.";
+
+pub fn wallet_balances_subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("wallet-balances").about(WALLET_BALANCES_SUBCOMMAND_ABOUT)
+}
+
+impl Command for WalletBalancesCommand {
+    fn execute(&self, context: &mut dyn CommandContext) -> Result<(), CommandError> {
+        let input = UiWalletBalancesRequest {};
+        let output: Result<UiWalletBalancesResponse, CommandError> =
+            transaction(input, context, STANDARD_COMMAND_TIMEOUT_MILLIS);
+        match output {
+            Ok(response) => {
+                short_writeln!(
+                    context.stdout(),
+                    "Consuming wallet: {}\n\
+                     Gas balance:      {} gwei (threshold {} gwei)\n\
+                     MASQ balance:     {} gwei (threshold {} gwei)",
+                    response.consuming_wallet_address,
+                    response.gas_balance_gwei.separate_with_commas(),
+                    response.gas_threshold_gwei.separate_with_commas(),
+                    response.masq_balance_gwei.separate_with_commas(),
+                    response.masq_threshold_gwei.separate_with_commas()
+                );
+                if response.consuming_services_suspended {
+                    short_writeln!(
+                        context.stdout(),
+                        "Consuming services are suspended until the wallet is replenished."
+                    )
+                }
+                Ok(())
+            }
+            Err(e) => {
+                short_writeln!(context.stderr(), "Balance retrieval failed: {:?}", e);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl Default for WalletBalancesCommand {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalletBalancesCommand {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_context::ContextError;
+    use crate::command_factory::{CommandFactory, CommandFactoryReal};
+    use crate::test_utils::mocks::CommandContextMock;
+    use masq_lib::constants::WALLET_BALANCES_ERROR;
+    use masq_lib::messages::ToMessageBody;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            WALLET_BALANCES_SUBCOMMAND_ABOUT,
+            "Displays the gas-currency and MASQ balances of the consuming wallet, along with the \
+             thresholds below which the Node stops consuming services. Only valid if Node is already running."
+        );
+    }
+
+    fn make_response(consuming_services_suspended: bool) -> UiWalletBalancesResponse {
+        UiWalletBalancesResponse {
+            consuming_wallet_address: "0x0123456789012345678901234567890123456789".to_string(),
+            gas_balance_gwei: 12_345_678,
+            masq_balance_gwei: 987_654_321_000,
+            gas_threshold_gwei: 10_000_000,
+            masq_threshold_gwei: 1_000_000_000,
+            consuming_services_suspended,
+        }
+    }
+
+    #[test]
+    fn testing_command_factory_here() {
+        let factory = CommandFactoryReal::new();
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(make_response(false).tmb(0)));
+        let subject = factory.make(&["wallet-balances".to_string()]).unwrap();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn wallet_balances_command_happy_path() {
+        let transact_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut context = CommandContextMock::new()
+            .transact_params(&transact_params_arc)
+            .transact_result(Ok(make_response(false).tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        let transact_params = transact_params_arc.lock().unwrap();
+        assert_eq!(
+            *transact_params,
+            vec![(
+                UiWalletBalancesRequest {}.tmb(0),
+                STANDARD_COMMAND_TIMEOUT_MILLIS
+            )]
+        );
+        assert_eq!(
+            stdout_arc.lock().unwrap().get_string(),
+            "Consuming wallet: 0x0123456789012345678901234567890123456789\n\
+             Gas balance:      12,345,678 gwei (threshold 10,000,000 gwei)\n\
+             MASQ balance:     987,654,321,000 gwei (threshold 1,000,000,000 gwei)\n"
+        );
+        assert_eq!(stderr_arc.lock().unwrap().get_string(), String::new());
+    }
+
+    #[test]
+    fn wallet_balances_command_reports_suspension() {
+        let mut context =
+            CommandContextMock::new().transact_result(Ok(make_response(true).tmb(42)));
+        let stdout_arc = context.stdout_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(result, Ok(()));
+        assert!(stdout_arc
+            .lock()
+            .unwrap()
+            .get_string()
+            .ends_with("Consuming services are suspended until the wallet is replenished.\n"));
+    }
+
+    #[test]
+    fn wallet_balances_command_sad_path() {
+        let mut context =
+            CommandContextMock::new().transact_result(Err(ContextError::PayloadError(
+                WALLET_BALANCES_ERROR,
+                "No consuming wallet is configured".to_string(),
+            )));
+        let stdout_arc = context.stdout_arc();
+        let stderr_arc = context.stderr_arc();
+        let subject = WalletBalancesCommand::new();
+
+        let result = subject.execute(&mut context);
+
+        assert_eq!(
+            result,
+            Err(CommandError::Payload(
+                WALLET_BALANCES_ERROR,
+                "No consuming wallet is configured".to_string()
+            ))
+        );
+        assert_eq!(stdout_arc.lock().unwrap().get_string(), String::new());
+        assert_eq!(
+            stderr_arc.lock().unwrap().get_string(),
+            format!(
+                "Balance retrieval failed: Payload({}, \"No consuming wallet is configured\")\n",
+                WALLET_BALANCES_ERROR
+            )
+        );
+    }
+}
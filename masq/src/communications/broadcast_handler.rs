@@ -1,13 +1,16 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::commands::change_password_command::ChangePasswordCommand;
+use crate::commands::logs_command::LogsCommand;
 use crate::commands::setup_command::SetupCommand;
 use crate::notifications::crashed_notification::CrashNotifier;
 use crate::terminal::terminal_interface::TerminalWrapper;
 use crossbeam_channel::{unbounded, RecvError, Sender};
 use masq_lib::messages::{
-    FromMessageBody, UiConnectionChangeBroadcast, UiLogBroadcast, UiNewPasswordBroadcast,
-    UiNodeCrashedBroadcast, UiSetupBroadcast, UiUndeliveredFireAndForget,
+    FromMessageBody, UiBanListChangedBroadcast, UiConnectionChangeBroadcast, UiLogBroadcast,
+    UiLogStreamBroadcast, UiNewPasswordBroadcast, UiNodeCrashedBroadcast, UiNodeRestartedBroadcast,
+    UiReceivablesCatchUpBroadcast, UiSetupBroadcast, UiShutdownProgressBroadcast,
+    UiUndeliveredFireAndForget, UiWalletBalanceBroadcast, UiWalletDiscoveryProgressBroadcast,
 };
 use masq_lib::ui_gateway::MessageBody;
 use masq_lib::utils::ExpectValue;
@@ -16,7 +19,13 @@ use std::fmt::Debug;
 use std::io::Write;
 use std::thread;
 
+use crate::notifications::ban_list_changed_notification::BanListChangedNotification;
 use crate::notifications::connection_change_notification::ConnectionChangeNotification;
+use crate::notifications::receivables_catch_up_notification::ReceivablesCatchUpNotification;
+use crate::notifications::restarted_notification::RestartedNotification;
+use crate::notifications::shutdown_progress_notification::ShutdownProgressNotification;
+use crate::notifications::wallet_balance_notification::WalletBalanceNotification;
+use crate::notifications::wallet_discovery_progress_notification::WalletDiscoveryProgressNotification;
 #[cfg(test)]
 use std::any::Any;
 
@@ -37,12 +46,19 @@ pub struct BroadcastHandleGeneric {
     message_tx: Sender<MessageBody>,
 }
 
+impl BroadcastHandleGeneric {
+    pub fn new(message_tx: Sender<MessageBody>) -> Self {
+        Self { message_tx }
+    }
+}
+
 impl BroadcastHandle for BroadcastHandleGeneric {
     fn send(&self, message_body: MessageBody) {
         self.message_tx
             .send(message_body)
             .expect("Message send failed")
     }
+    implement_as_any!();
 }
 
 pub trait BroadcastHandler {
@@ -93,10 +109,14 @@ impl BroadcastHandlerReal {
             Ok(message_body) => {
                 if let Ok((body, _)) = UiLogBroadcast::fmb(message_body.clone()) {
                     handle_ui_log_broadcast(body, stdout, terminal_interface)
+                } else if let Ok((body, _)) = UiLogStreamBroadcast::fmb(message_body.clone()) {
+                    LogsCommand::handle_broadcast(body, stdout, terminal_interface);
                 } else if let Ok((body, _)) = UiSetupBroadcast::fmb(message_body.clone()) {
                     SetupCommand::handle_broadcast(body, stdout, terminal_interface);
                 } else if let Ok((body, _)) = UiNodeCrashedBroadcast::fmb(message_body.clone()) {
                     CrashNotifier::handle_broadcast(body, stdout, terminal_interface);
+                } else if let Ok((body, _)) = UiNodeRestartedBroadcast::fmb(message_body.clone()) {
+                    RestartedNotification::handle_broadcast(body, stdout, terminal_interface);
                 } else if let Ok((body, _)) = UiNewPasswordBroadcast::fmb(message_body.clone()) {
                     ChangePasswordCommand::handle_broadcast(body, stdout, terminal_interface);
                 } else if let Ok((body, _)) = UiUndeliveredFireAndForget::fmb(message_body.clone())
@@ -113,6 +133,33 @@ impl BroadcastHandlerReal {
                         stdout,
                         terminal_interface,
                     );
+                } else if let Ok((body, _)) =
+                    UiReceivablesCatchUpBroadcast::fmb(message_body.clone())
+                {
+                    ReceivablesCatchUpNotification::handle_broadcast(
+                        body,
+                        stdout,
+                        terminal_interface,
+                    );
+                } else if let Ok((body, _)) = UiWalletBalanceBroadcast::fmb(message_body.clone()) {
+                    WalletBalanceNotification::handle_broadcast(body, stdout, terminal_interface);
+                } else if let Ok((body, _)) = UiBanListChangedBroadcast::fmb(message_body.clone()) {
+                    BanListChangedNotification::handle_broadcast(body, stdout, terminal_interface);
+                } else if let Ok((body, _)) = UiShutdownProgressBroadcast::fmb(message_body.clone())
+                {
+                    ShutdownProgressNotification::handle_broadcast(
+                        body,
+                        stdout,
+                        terminal_interface,
+                    );
+                } else if let Ok((body, _)) =
+                    UiWalletDiscoveryProgressBroadcast::fmb(message_body.clone())
+                {
+                    WalletDiscoveryProgressNotification::handle_broadcast(
+                        body,
+                        stdout,
+                        terminal_interface,
+                    );
                 } else {
                     handle_unrecognized_broadcast(message_body, stderr, terminal_interface)
                 }
@@ -193,11 +240,12 @@ mod tests {
         TerminalPassiveMock, TestStreamFactory,
     };
     use crossbeam_channel::{bounded, unbounded, Receiver};
+    use masq_lib::messages::UiSetupResponseValueStatus;
     use masq_lib::messages::{
         CrashReason, SerializableLogLevel, ToMessageBody, UiConnectionChangeBroadcast,
-        UiConnectionStage, UiLogBroadcast, UiNodeCrashedBroadcast,
+        UiConnectionStage, UiLogBroadcast, UiLogLevel, UiNodeCrashedBroadcast,
     };
-    use masq_lib::messages::{UiSetupBroadcast, UiSetupResponseValue, UiSetupResponseValueStatus};
+    use masq_lib::messages::{ShutdownStage, UiSetupBroadcast, UiSetupResponseValue};
     use masq_lib::ui_gateway::MessagePath;
     use std::sync::Arc;
     use std::time::Duration;
@@ -262,6 +310,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn broadcast_of_log_stream_triggers_correct_handler() {
+        let (factory, handle) = TestStreamFactory::new();
+        let subject = BroadcastHandlerReal::new(Some(TerminalWrapper::new(Arc::new(
+            TerminalPassiveMock::new(),
+        ))))
+        .start(Box::new(factory));
+        let message = UiLogStreamBroadcast {
+            timestamp: "2026-10-19 10:00:00.123".to_string(),
+            level: UiLogLevel::Debug,
+            module: "Accountant".to_string(),
+            msg: "Scanning for payables".to_string(),
+        }
+        .tmb(0);
+
+        subject.send(message);
+
+        let stdout = handle.stdout_so_far();
+        assert_eq!(
+            stdout,
+            "2026-10-19 10:00:00.123 DEBUG: Accountant: Scanning for payables\n",
+        );
+        assert_eq!(
+            handle.stderr_so_far(),
+            "".to_string(),
+            "stderr: '{}'",
+            stdout
+        );
+    }
+
     #[test]
     fn broadcast_of_crashed_triggers_correct_handler() {
         let (factory, handle) = TestStreamFactory::new();
@@ -270,6 +348,7 @@ mod tests {
         ))))
         .start(Box::new(factory));
         let message = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 1234,
             crash_reason: CrashReason::Unrecognized("Unknown crash reason".to_string()),
         }
@@ -280,7 +359,7 @@ mod tests {
         let stdout = handle.stdout_so_far();
         assert_eq!(
             stdout,
-            "\nThe Node running as process 1234 terminated:\n------\nUnknown crash reason\n\
+            "\nThe Node instance 'default' running as process 1234 terminated:\n------\nUnknown crash reason\n\
             ------\nThe Daemon is once more accepting setup changes.\n\n"
                 .to_string()
         );
@@ -376,6 +455,176 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ui_receivables_catch_up_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiReceivablesCatchUpBroadcast {
+            scanned_through_block: 1000,
+            target_block: 2000,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nCatching up on payments to your earning wallet: scanned through block 1000 of 2000.\n\n"
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn ui_ban_list_changed_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiBanListChangedBroadcast {
+            banned: vec!["0x1111111111111111111111111111111111111111".to_string()],
+            unbanned: vec![],
+            warned: vec![],
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nWallet 0x1111111111111111111111111111111111111111 has been banned for delinquency.\n\n"
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn ui_shutdown_progress_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiShutdownProgressBroadcast {
+            stage: ShutdownStage::Settling,
+            message: "Running a final payable scan".to_string(),
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nGraceful shutdown (settling): Running a final payable scan\n\n".to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn ui_wallet_discovery_progress_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiWalletDiscoveryProgressBroadcast {
+            scanned_path_count: 20,
+            total_path_count: 40,
+            candidates: vec![],
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nWallet discovery: 20 of 40 derivation paths scanned\n\n".to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn ui_node_restarted_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiNodeRestartedBroadcast {
+            instance: "default".to_string(),
+            crashed_process_id: 1234,
+            new_process_id: 2345,
+            redirect_ui_port: 7777,
+            attempt: 1,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nThe Daemon restarted the Node instance 'default' that was running as process 1234 (restart 1). \
+             It is now running as process 2345, and UIs can reach it on port 7777.\n\n"
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
+    #[test]
+    fn ui_wallet_balance_broadcast_is_handled_properly() {
+        let (factory, handle) = TestStreamFactory::new();
+        let (mut stdout, mut stderr) = factory.make();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let message_body = UiWalletBalanceBroadcast {
+            gas_balance_gwei: 10,
+            masq_balance_gwei: 20,
+            gas_threshold_gwei: 30,
+            masq_threshold_gwei: 40,
+            consuming_services_suspended: true,
+        }
+        .tmb(0);
+
+        let result = BroadcastHandlerReal::handle_message_body(
+            Ok(message_body),
+            &mut stdout,
+            &mut stderr,
+            &terminal_interface,
+        );
+
+        assert_eq!(result, true);
+        assert_eq!(
+            handle.stdout_so_far(),
+            "\nYour consuming wallet is low on funds: 10 gwei of gas (threshold 30), 20 gwei of MASQ \
+            (threshold 40). Consuming services are suspended until you replenish it.\n\n"
+                .to_string()
+        );
+        assert_eq!(handle.stderr_so_far(), "".to_string());
+    }
+
     #[test]
     fn unexpected_broadcasts_are_ineffectual_but_dont_kill_the_handler() {
         let (factory, handle) = TestStreamFactory::new();
@@ -489,12 +738,13 @@ neighborhood-mode             standard
     #[test]
     fn crash_notifier_handle_broadcast_has_a_synchronizer_correctly_implemented() {
         let crash_notifier_body = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 100,
             crash_reason: CrashReason::NoInformation,
         };
 
         let broadcast_output = "\
-The Node running as process 100 terminated.
+The Node instance 'default' running as process 100 terminated.
 The Daemon is once more accepting setup changes.
 
 ";
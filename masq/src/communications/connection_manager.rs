@@ -7,8 +7,9 @@ use crate::communications::client_listener_thread::{ClientListener, ClientListen
 use crate::communications::node_conversation::{NodeConversation, NodeConversationTermination};
 use crossbeam_channel::{unbounded, RecvTimeoutError};
 use crossbeam_channel::{Receiver, RecvError, Sender};
+use masq_lib::constants::DEFAULT_INSTANCE_NAME;
 use masq_lib::messages::{CrashReason, FromMessageBody, ToMessageBody, UiNodeCrashedBroadcast};
-use masq_lib::messages::{UiRedirect, NODE_UI_PROTOCOL};
+use masq_lib::messages::{UiRedirect, UiSelectInstance, NODE_UI_PROTOCOL};
 use masq_lib::ui_gateway::{MessageBody, MessagePath};
 use masq_lib::ui_traffic_converter::UiTrafficConverter;
 use masq_lib::utils::localhost;
@@ -84,12 +85,14 @@ impl ConnectionManager {
     pub fn connect(
         &mut self,
         port: u16,
+        instance_opt: Option<String>,
         generic_broadcast_handle: Box<dyn BroadcastHandle>,
         timeout_millis: u64,
     ) -> Result<(), ClientListenerError> {
         let (demand_tx, demand_rx) = unbounded();
         let (listener_to_manager_tx, listener_to_manager_rx) = unbounded();
-        let talker_half = make_client_listener(port, listener_to_manager_tx, timeout_millis)?;
+        let mut talker_half = make_client_listener(port, listener_to_manager_tx, timeout_millis)?;
+        select_instance(&mut talker_half, port, port, &instance_opt)?;
         let (conversation_return_tx, conversation_return_rx) = unbounded();
         let (redirect_order_tx, redirect_order_rx) = unbounded();
         let (redirect_response_tx, redirect_response_rx) = unbounded();
@@ -104,6 +107,7 @@ impl ConnectionManager {
             active_port: Some(port),
             daemon_port: port,
             node_port: None,
+            instance_opt,
             conversations: HashMap::new(),
             conversations_waiting: HashSet::new(),
             next_context_id: 1,
@@ -176,6 +180,29 @@ fn make_client_listener(
     Ok(talker_half)
 }
 
+// The Daemon knows which Node instance a UI is addressing only by its client ID, which is new for
+// every connection; so the selection has to be repeated every time we connect to the Daemon.
+// A Node has no instances to select among, so nothing is sent to any port but the Daemon's.
+fn select_instance(
+    talker_half: &mut Writer<TcpStream>,
+    port: u16,
+    daemon_port: u16,
+    instance_opt: &Option<String>,
+) -> Result<(), ClientListenerError> {
+    match instance_opt {
+        Some(name) if port == daemon_port => talker_half
+            .sender
+            .send_message(
+                &mut talker_half.stream,
+                &OwnedMessage::Text(UiTrafficConverter::new_marshal(
+                    UiSelectInstance { name: name.clone() }.tmb(0),
+                )),
+            )
+            .map_err(|e| ClientListenerError::Broken(format!("{:?}", e))),
+        _ => Ok(()),
+    }
+}
+
 //hack a time-out around connection attempt to the Node or Daemon. Leaks a thread if the attempt times out
 fn connect_insecure_timeout(
     mut builder: ClientBuilder<'static>,
@@ -193,6 +220,7 @@ struct CmsInner {
     active_port: Option<u16>,
     daemon_port: u16,
     node_port: Option<u16>,
+    instance_opt: Option<String>,
     conversations: HashMap<u64, Sender<Result<MessageBody, NodeConversationTermination>>>,
     conversations_waiting: HashSet<u64>,
     next_context_id: u64,
@@ -449,12 +477,17 @@ impl ConnectionManagerThread {
         }
         let (listener_to_manager_tx, listener_to_manager_rx) = unbounded();
         inner.listener_to_manager_rx = listener_to_manager_rx;
-        match make_client_listener(
-            inner.active_port.expect("Active port disappeared!"),
-            listener_to_manager_tx,
-            FALLBACK_TIMEOUT_MILLIS,
-        ) {
-            Ok(talker_half) => inner.talker_half = talker_half,
+        let active_port = inner.active_port.expect("Active port disappeared!");
+        match make_client_listener(active_port, listener_to_manager_tx, FALLBACK_TIMEOUT_MILLIS) {
+            Ok(mut talker_half) => {
+                let _ = select_instance(
+                    &mut talker_half,
+                    active_port,
+                    inner.daemon_port,
+                    &inner.instance_opt,
+                );
+                inner.talker_half = talker_half
+            }
             Err(e) => panic!("ClientListenerThread could not be restarted: {:?}", e),
         };
         inner = Self::disappoint_waiting_conversations(inner, NodeConversationTermination::Fatal);
@@ -490,6 +523,10 @@ impl ConnectionManagerThread {
 
     fn send_daemon_crashed(inner: &CmsInner) {
         let crash_msg = UiNodeCrashedBroadcast {
+            instance: inner
+                .instance_opt
+                .clone()
+                .unwrap_or_else(|| DEFAULT_INSTANCE_NAME.to_string()),
             process_id: 0,
             crash_reason: CrashReason::DaemonCrashed,
         };
@@ -611,7 +648,7 @@ mod tests {
         thread::sleep(Duration::from_millis(500)); // let the server get started
         let mut subject = ConnectionManager::new();
         subject
-            .connect(port, Box::new(BroadcastHandleMock::new()), 1000)
+            .connect(port, None, Box::new(BroadcastHandleMock::new()), 1000)
             .unwrap();
         (subject, stop_handle)
     }
@@ -858,6 +895,7 @@ mod tests {
         let mut broadcast_handle_send_params = broadcast_handle_send_params_arc.lock().unwrap();
         let message_body: MessageBody = (*broadcast_handle_send_params).remove(0);
         let crash_broadcast = UiNodeCrashedBroadcast::fmb(message_body).unwrap().0;
+        assert_eq!(crash_broadcast.instance, DEFAULT_INSTANCE_NAME.to_string());
         assert_eq!(crash_broadcast.crash_reason, CrashReason::DaemonCrashed);
     }
 
@@ -909,6 +947,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn connect_selects_instance_at_the_daemon() {
+        let port = find_free_port();
+        let server = MockWebSocketsServer::new(port);
+        let stop_handle = server.start();
+        let mut subject = ConnectionManager::new();
+
+        subject
+            .connect(
+                port,
+                Some("second".to_string()),
+                Box::new(BroadcastHandleMock::new()),
+                1000,
+            )
+            .unwrap();
+
+        thread::sleep(Duration::from_millis(200));
+        let outgoing_messages = stop_handle.stop();
+        assert_eq!(
+            outgoing_messages,
+            vec![Ok(UiSelectInstance {
+                name: "second".to_string()
+            }
+            .tmb(0))]
+        );
+    }
+
+    #[test]
+    fn fallback_from_node_selects_instance_at_the_daemon_again() {
+        let daemon_port = find_free_port();
+        let daemon = MockWebSocketsServer::new(daemon_port);
+        let stop_handle = daemon.start();
+        let node_port = find_free_port();
+        let mut inner = make_inner();
+        inner.active_port = Some(node_port);
+        inner.daemon_port = daemon_port;
+        inner.node_port = Some(node_port);
+        inner.instance_opt = Some("second".to_string());
+
+        let inner = ConnectionManagerThread::fallback(inner, NodeConversationTermination::Fatal);
+
+        assert_eq!(inner.active_port, Some(daemon_port));
+        thread::sleep(Duration::from_millis(200));
+        let outgoing_messages = stop_handle.stop();
+        assert_eq!(
+            outgoing_messages,
+            vec![Ok(UiSelectInstance {
+                name: "second".to_string()
+            }
+            .tmb(0))]
+        );
+    }
+
+    #[test]
+    fn select_instance_sends_nothing_to_a_port_that_is_not_the_daemons() {
+        let node_port = find_free_port();
+        let node = MockWebSocketsServer::new(node_port);
+        let stop_handle = node.start();
+        let client = make_client(node_port);
+        let (_, mut talker_half) = client.split().unwrap();
+        let daemon_port = find_free_port();
+
+        let result = select_instance(
+            &mut talker_half,
+            node_port,
+            daemon_port,
+            &Some("second".to_string()),
+        );
+
+        assert_eq!(result, Ok(()));
+        thread::sleep(Duration::from_millis(200));
+        let outgoing_messages = stop_handle.stop();
+        assert_eq!(outgoing_messages, vec![]);
+    }
+
+    #[test]
+    fn handle_redirect_order_does_not_select_instance_at_the_node() {
+        let node_port = find_free_port();
+        let node = MockWebSocketsServer::new(node_port);
+        let stop_handle = node.start();
+        let (redirect_response_tx, redirect_response_rx) = unbounded();
+        let mut inner = make_inner();
+        inner.daemon_port = find_free_port();
+        inner.instance_opt = Some("second".to_string());
+        inner.redirect_response_tx = redirect_response_tx;
+
+        let inner = ConnectionManagerThread::handle_redirect_order(
+            inner,
+            Ok(RedirectOrder::new(node_port, 0, 1000)),
+        );
+
+        assert_eq!(redirect_response_rx.try_recv().unwrap(), Ok(()));
+        assert_eq!(inner.active_port, Some(node_port));
+        thread::sleep(Duration::from_millis(200));
+        let outgoing_messages = stop_handle.stop();
+        assert_eq!(outgoing_messages, vec![]);
+    }
+
     #[test]
     fn doesnt_fall_back_from_daemon() {
         let unoccupied_port = find_free_port();
@@ -1201,7 +1337,7 @@ mod tests {
         let broadcast_handler = BroadcastHandleMock::new().send_params(&send_params_arc);
         let mut subject = ConnectionManager::new();
         subject
-            .connect(daemon_port, Box::new(broadcast_handler), 1000)
+            .connect(daemon_port, None, Box::new(broadcast_handler), 1000)
             .unwrap();
         let conversation = subject.start_conversation();
 
@@ -1454,7 +1590,7 @@ mod tests {
         let mut subject = ConnectionManager::new();
         thread::sleep(Duration::from_millis(500)); // let the server get started
         subject
-            .connect(port, Box::new(BroadcastHandleMock::new()), 1000)
+            .connect(port, None, Box::new(BroadcastHandleMock::new()), 1000)
             .unwrap();
         let conversation1 = subject.start_conversation();
         let conversation2 = subject.start_conversation();
@@ -1481,6 +1617,7 @@ mod tests {
             active_port: Some(0),
             daemon_port: 0,
             node_port: None,
+            instance_opt: None,
             conversations: HashMap::new(),
             conversations_waiting: HashSet::new(),
             next_context_id: 0,
@@ -68,6 +68,13 @@ fn handle_args(
                 return event;
             }
         }
+        [help, command] if help == "help" => {
+            return handle_help_for_command(
+                command,
+                streams,
+                command_processor.terminal_wrapper_ref(),
+            )
+        }
         _ => (),
     }
     let _ = handle_command_common(command_factory, command_processor, args, streams.stderr);
@@ -110,6 +117,25 @@ fn handle_help_or_version(
     InteractiveEvent::Continue
 }
 
+fn handle_help_for_command(
+    command: &str,
+    streams: &mut StdStreams<'_>,
+    terminal_interface: &TerminalWrapper,
+) -> InteractiveEvent {
+    let _lock = terminal_interface.lock();
+    match app().get_matches_from_safe(vec!["masq", command, "--help"]) {
+        Err(e) if e.kind == clap::ErrorKind::HelpDisplayed => {
+            short_writeln!(streams.stdout, "{}\n", e.message)
+        }
+        _ => short_writeln!(
+            streams.stderr,
+            "Unrecognized command: '{}'; type 'help' to list the commands",
+            command
+        ),
+    }
+    InteractiveEvent::Continue
+}
+
 fn pass_args_or_print_messages(
     streams: &mut StdStreams<'_>,
     read_line_result: TerminalEvent,
@@ -158,8 +184,8 @@ fn print_protected(
 mod tests {
     use crate::command_factory::CommandFactoryError;
     use crate::interactive_mode::{
-        go_interactive, handle_args, handle_help_or_version, handle_terminal_event,
-        pass_args_or_print_messages, InteractiveEvent,
+        go_interactive, handle_args, handle_help_for_command, handle_help_or_version,
+        handle_terminal_event, pass_args_or_print_messages, InteractiveEvent,
     };
     use crate::terminal::line_reader::TerminalEvent;
     use crate::terminal::line_reader::TerminalEvent::{Break, Continue, Error};
@@ -237,6 +263,44 @@ mod tests {
         assert_eq!(stream_holder.stderr.get_string(), "Booga!\n".to_string());
     }
 
+    #[test]
+    fn help_with_a_command_shows_the_help_of_that_command() {
+        let mut stream_holder = FakeStreamHolder::new();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalActiveMock::new()));
+
+        let result = handle_help_for_command(
+            "set-configuration",
+            &mut stream_holder.streams(),
+            &terminal_interface,
+        );
+
+        assert_eq!(result, InteractiveEvent::Continue);
+        let stdout = stream_holder.stdout.get_string();
+        assert!(
+            stdout.contains("Sets Node configuration parameters"),
+            "{}",
+            stdout
+        );
+        assert!(stdout.contains("--min-hops <MIN-HOPS>"), "{}", stdout);
+        assert_eq!(stream_holder.stderr.get_string(), "");
+    }
+
+    #[test]
+    fn help_with_an_unknown_command_complains() {
+        let mut stream_holder = FakeStreamHolder::new();
+        let terminal_interface = TerminalWrapper::new(Arc::new(TerminalActiveMock::new()));
+
+        let result =
+            handle_help_for_command("booga", &mut stream_holder.streams(), &terminal_interface);
+
+        assert_eq!(result, InteractiveEvent::Continue);
+        assert_eq!(stream_holder.stdout.get_string(), "");
+        assert_eq!(
+            stream_holder.stderr.get_string(),
+            "Unrecognized command: 'booga'; type 'help' to list the commands\n"
+        );
+    }
+
     #[test]
     fn handle_args_process_empty_args_short_circuit() {
         let args = &[];
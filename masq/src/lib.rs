@@ -10,6 +10,7 @@ pub mod non_interactive_clap;
 pub mod non_interactive_mode;
 mod notifications;
 mod schema;
+pub mod script_mode;
 pub mod terminal;
 
 #[macro_use]
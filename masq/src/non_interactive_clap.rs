@@ -1,5 +1,6 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::command_context::OutputFormat;
 use crate::schema::app;
 use clap::{value_t, ArgMatches};
 
@@ -18,17 +19,33 @@ impl NIClapFactory for NIClapFactoryReal {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitialArgs {
+    pub ui_port: u16,
+    pub instance_opt: Option<String>,
+    pub output_format: OutputFormat,
+    pub script_opt: Option<String>,
+    pub keep_going: bool,
+}
+
 pub trait NonInteractiveClap {
-    fn non_interactive_initial_clap_operations(&self, args: &[String]) -> u16;
+    fn non_interactive_initial_clap_operations(&self, args: &[String]) -> InitialArgs;
 }
 
 pub struct NonInteractiveClapReal;
 
 //partly tested by integration tests
 impl NonInteractiveClap for NonInteractiveClapReal {
-    fn non_interactive_initial_clap_operations(&self, args: &[String]) -> u16 {
+    fn non_interactive_initial_clap_operations(&self, args: &[String]) -> InitialArgs {
         let matches = handle_help_or_version_if_required(args);
-        value_t!(matches, "ui-port", u16).expect("ui-port is not properly defaulted")
+        InitialArgs {
+            ui_port: value_t!(matches, "ui-port", u16).expect("ui-port is not properly defaulted"),
+            instance_opt: matches.value_of("instance").map(|name| name.to_string()),
+            output_format: value_t!(matches, "output", OutputFormat)
+                .expect("output is not properly defaulted"),
+            script_opt: matches.value_of("script").map(|path| path.to_string()),
+            keep_going: matches.is_present("keep-going"),
+        }
     }
 }
 
@@ -41,27 +58,102 @@ mod tests {
     use super::*;
     use masq_lib::constants::DEFAULT_UI_PORT;
 
+    fn make_args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|str| str.to_string()).collect()
+    }
+
+    fn default_initial_args() -> InitialArgs {
+        InitialArgs {
+            ui_port: DEFAULT_UI_PORT,
+            instance_opt: None,
+            output_format: OutputFormat::Human,
+            script_opt: None,
+            keep_going: false,
+        }
+    }
+
     #[test]
     fn non_interactive_clap_real_produces_default_value_for_ui_port() {
-        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(
-            &vec!["masq", "setup", "--chain"]
-                .iter()
-                .map(|str| str.to_string())
-                .collect::<Vec<String>>(),
-        );
-
-        assert_eq!(result, DEFAULT_UI_PORT)
+        let result = NonInteractiveClapReal
+            .non_interactive_initial_clap_operations(&make_args(&["masq", "setup", "--chain"]));
+
+        assert_eq!(result, default_initial_args())
     }
 
     #[test]
     fn non_interactive_clap_real_accept_custom_value_for_ui_port() {
-        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(
-            &vec!["masq", "--ui-port", "10000", "setup", "--log-level", "off"]
-                .iter()
-                .map(|str| str.to_string())
-                .collect::<Vec<String>>(),
-        );
-
-        assert_eq!(result, 10000)
+        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(&make_args(&[
+            "masq",
+            "--ui-port",
+            "10000",
+            "setup",
+            "--log-level",
+            "off",
+        ]));
+
+        assert_eq!(
+            result,
+            InitialArgs {
+                ui_port: 10000,
+                ..default_initial_args()
+            }
+        )
+    }
+
+    #[test]
+    fn non_interactive_clap_real_accepts_instance_name() {
+        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(&make_args(&[
+            "masq",
+            "--instance",
+            "second",
+            "setup",
+            "--log-level",
+            "off",
+        ]));
+
+        assert_eq!(
+            result,
+            InitialArgs {
+                instance_opt: Some("second".to_string()),
+                ..default_initial_args()
+            }
+        )
+    }
+
+    #[test]
+    fn non_interactive_clap_real_accepts_json_output_format() {
+        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(&make_args(&[
+            "masq",
+            "--output",
+            "json",
+            "financials",
+        ]));
+
+        assert_eq!(
+            result,
+            InitialArgs {
+                output_format: OutputFormat::Json,
+                ..default_initial_args()
+            }
+        )
+    }
+
+    #[test]
+    fn non_interactive_clap_real_accepts_script_and_keep_going() {
+        let result = NonInteractiveClapReal.non_interactive_initial_clap_operations(&make_args(&[
+            "masq",
+            "--script",
+            "provision.masq",
+            "--keep-going",
+        ]));
+
+        assert_eq!(
+            result,
+            InitialArgs {
+                script_opt: Some("provision.masq".to_string()),
+                keep_going: true,
+                ..default_initial_args()
+            }
+        )
     }
 }
@@ -1,20 +1,27 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::command_context::OutputFormat;
 use crate::command_factory::CommandFactoryError::{CommandSyntax, UnrecognizedSubcommand};
 use crate::command_factory::{CommandFactory, CommandFactoryReal};
 use crate::command_processor::{
     CommandProcessor, CommandProcessorFactory, CommandProcessorFactoryReal,
 };
+use crate::commands::commands_common::{json_error, EXIT_CODE_COMMAND_SYNTAX};
 use crate::communications::broadcast_handler::{
-    BroadcastHandle, BroadcastHandleInactive, BroadcastHandler, BroadcastHandlerReal,
-    StreamFactory, StreamFactoryReal,
+    BroadcastHandle, BroadcastHandleGeneric, BroadcastHandler, BroadcastHandlerReal, StreamFactory,
+    StreamFactoryReal,
 };
 use crate::interactive_mode::go_interactive;
 use crate::non_interactive_clap::{NIClapFactory, NIClapFactoryReal};
+use crate::script_mode::ScriptRunner;
 use crate::terminal::terminal_interface::TerminalWrapper;
+use crossbeam_channel::{unbounded, Receiver};
+use masq_lib::blockchains::custom_chains::load_custom_chains_from_environment;
 use masq_lib::command::{Command, StdStreams};
 use masq_lib::short_writeln;
-use std::io::Write;
+use masq_lib::ui_gateway::MessageBody;
+use std::fs::File;
+use std::io::{Read, Write};
 use std::ops::Not;
 
 pub struct Main {
@@ -58,9 +65,35 @@ impl Main {
             .not()
     }
 
-    fn populate_non_interactive_dependencies() -> (Box<dyn BroadcastHandle>, Option<TerminalWrapper>)
+    fn populate_non_interactive_dependencies() -> (Box<dyn BroadcastHandle>, Receiver<MessageBody>)
     {
-        (Box::new(BroadcastHandleInactive), None)
+        //broadcasts are queued for commands that want them, like the dashboard; others ignore them
+        let (message_tx, message_rx) = unbounded();
+        (
+            Box::new(BroadcastHandleGeneric::new(message_tx)),
+            message_rx,
+        )
+    }
+
+    fn populate_script_dependencies(keep_going: bool) -> (Box<dyn BroadcastHandle>, ScriptRunner) {
+        let (message_tx, message_rx) = unbounded();
+        (
+            Box::new(BroadcastHandleGeneric::new(message_tx)),
+            ScriptRunner::new(keep_going, message_rx),
+        )
+    }
+
+    fn read_script(path: &str, stdin: &mut dyn Read) -> Result<String, String> {
+        let mut script = String::new();
+        let result = if path == "-" {
+            stdin.read_to_string(&mut script).map(|_| ())
+        } else {
+            File::open(path).and_then(|mut file| file.read_to_string(&mut script).map(|_| ()))
+        };
+        match result {
+            Ok(()) => Ok(script),
+            Err(e) => Err(format!("Can't read script '{}': {}", path, e)),
+        }
     }
 
     fn populate_interactive_dependencies(
@@ -81,14 +114,42 @@ impl Main {
 
 impl Command<u8> for Main {
     fn go(&mut self, streams: &mut StdStreams<'_>, args: &[String]) -> u8 {
-        let ui_port = self
+        if let Err(msg) = load_custom_chains_from_environment() {
+            short_writeln!(streams.stderr, "{}", msg);
+            return bool_into_numeric_code(false);
+        }
+        let initial_args = self
             .non_interactive_clap_factory
             .make()
             .non_interactive_initial_clap_operations(args);
+        let output_format = initial_args.output_format;
+        let script_opt = match &initial_args.script_opt {
+            Some(path) => match Self::read_script(path, streams.stdin) {
+                Ok(script) => Some(script),
+                Err(msg) => {
+                    short_writeln!(streams.stderr, "{}", msg);
+                    return bool_into_numeric_code(false);
+                }
+            },
+            None => None,
+        };
         let subcommand_opt = Self::extract_subcommand(args);
-        let (generic_broadcast_handle, terminal_interface) = match subcommand_opt {
-            Some(_) => Self::populate_non_interactive_dependencies(),
-            None => match Self::populate_interactive_dependencies(StreamFactoryReal) {
+        let mut script_runner_opt = None;
+        let mut broadcast_rx_opt = None;
+        let (generic_broadcast_handle, terminal_interface) = match (&script_opt, &subcommand_opt) {
+            (Some(_), _) => {
+                let (broadcast_handle, script_runner) =
+                    Self::populate_script_dependencies(initial_args.keep_going);
+                script_runner_opt = Some(script_runner);
+                (broadcast_handle, None)
+            }
+            (None, Some(_)) => {
+                let (broadcast_handle, broadcast_rx) =
+                    Self::populate_non_interactive_dependencies();
+                broadcast_rx_opt = Some(broadcast_rx);
+                (broadcast_handle, None)
+            }
+            (None, None) => match Self::populate_interactive_dependencies(StreamFactoryReal) {
                 Ok(tuple) => tuple,
                 Err(error) => {
                     short_writeln!(streams.stderr, "Pre-configuration error: {}", error);
@@ -99,30 +160,46 @@ impl Command<u8> for Main {
         let mut command_processor = match self.processor_factory.make(
             terminal_interface,
             generic_broadcast_handle,
-            ui_port,
+            broadcast_rx_opt,
+            initial_args.ui_port,
+            initial_args.instance_opt,
+            output_format,
         ) {
             Ok(processor) => processor,
             Err(error) => {
-                short_writeln!(
+                match output_format {
+                    OutputFormat::Human => short_writeln!(
                         streams.stderr,
                         "Can't connect to Daemon or Node ({:?}). Probably this means the Daemon isn't running.",
                         error
-                    );
-                return bool_into_numeric_code(false);
+                    ),
+                    OutputFormat::Json => short_writeln!(streams.stderr, "{}", error.to_json()),
+                }
+                return error.exit_code();
             }
         };
 
-        let result = match subcommand_opt {
-            Some(command_parts) => handle_command_common(
+        let exit_code = match (script_opt.zip(script_runner_opt), subcommand_opt) {
+            (Some((script, script_runner)), _) => script_runner.run(
+                &script,
+                &*self.command_factory,
+                &mut *command_processor,
+                streams.stderr,
+            ),
+            (None, Some(command_parts)) => handle_command_common(
                 &*self.command_factory,
                 &mut *command_processor,
                 &command_parts,
                 streams.stderr,
             ),
-            None => go_interactive(&*self.command_factory, &mut *command_processor, streams),
+            (None, None) => bool_into_numeric_code(go_interactive(
+                &*self.command_factory,
+                &mut *command_processor,
+                streams,
+            )),
         };
         command_processor.close();
-        bool_into_numeric_code(result)
+        exit_code
     }
 }
 
@@ -139,23 +216,38 @@ pub fn handle_command_common(
     processor: &mut dyn CommandProcessor,
     command_parts: &[String],
     stderr: &mut dyn Write,
-) -> bool {
+) -> u8 {
+    let output_format = processor.output_format();
     let command = match command_factory.make(command_parts) {
         Ok(c) => c,
         Err(UnrecognizedSubcommand(msg)) => {
-            short_writeln!(stderr, "Unrecognized command: '{}'", msg);
-            return false;
+            match output_format {
+                OutputFormat::Human => short_writeln!(stderr, "Unrecognized command: '{}'", msg),
+                OutputFormat::Json => {
+                    short_writeln!(stderr, "{}", json_error("unrecognizedCommand", None, &msg))
+                }
+            }
+            return EXIT_CODE_COMMAND_SYNTAX;
         }
         Err(CommandSyntax(msg)) => {
-            short_writeln!(stderr, "{}", msg);
-            return false;
+            match output_format {
+                OutputFormat::Human => short_writeln!(stderr, "{}", msg),
+                OutputFormat::Json => {
+                    short_writeln!(stderr, "{}", json_error("commandSyntax", None, &msg))
+                }
+            }
+            return EXIT_CODE_COMMAND_SYNTAX;
         }
     };
-    if let Err(e) = processor.process(command) {
-        short_writeln!(stderr, "{}", e);
-        false
-    } else {
-        true
+    match processor.process(command) {
+        Ok(()) => 0,
+        Err(e) => {
+            match output_format {
+                OutputFormat::Human => short_writeln!(stderr, "{}", e),
+                OutputFormat::Json => short_writeln!(stderr, "{}", e.to_json()),
+            }
+            e.exit_code()
+        }
     }
 }
 
@@ -166,7 +258,10 @@ mod tests {
     use crate::command_context::ContextError::Other;
     use crate::commands::commands_common;
     use crate::commands::commands_common::CommandError;
-    use crate::commands::commands_common::CommandError::Transmission;
+    use crate::commands::commands_common::CommandError::{Payload, Transmission};
+    use crate::commands::commands_common::{
+        EXIT_CODE_CONNECTION_PROBLEM, EXIT_CODE_PAYLOAD_ERROR, EXIT_CODE_TRANSMISSION_PROBLEM,
+    };
     use crate::commands::setup_command::SetupCommand;
     use crate::terminal::line_reader::TerminalEvent;
     use crate::test_utils::mocks::{
@@ -175,7 +270,7 @@ mod tests {
     };
     use masq_lib::intentionally_blank;
     use masq_lib::messages::{ToMessageBody, UiNewPasswordBroadcast, UiShutdownRequest};
-    use masq_lib::test_utils::fake_stream_holder::FakeStreamHolder;
+    use masq_lib::test_utils::fake_stream_holder::{ByteArrayReader, FakeStreamHolder};
     use std::any::Any;
     use std::sync::{Arc, Mutex};
 
@@ -233,13 +328,23 @@ mod tests {
             ]
         );
         let mut p_make_params = p_make_params_arc.lock().unwrap();
-        let (terminal_interface, broadcast_handle, ui_port) = p_make_params.pop().unwrap();
+        let (
+            terminal_interface,
+            broadcast_handle,
+            broadcast_rx_opt,
+            ui_port,
+            instance_opt,
+            output_format,
+        ) = p_make_params.pop().unwrap();
         assert_eq!(ui_port, 5333);
+        assert_eq!(instance_opt, None);
+        assert_eq!(output_format, OutputFormat::Human);
         assert!(terminal_interface.is_none());
         assert!(broadcast_handle
             .as_any()
-            .downcast_ref::<BroadcastHandleInactive>()
+            .downcast_ref::<BroadcastHandleGeneric>()
             .is_some());
+        assert!(broadcast_rx_opt.is_some());
 
         let mut process_params = process_params_arc.lock().unwrap();
         let command = process_params.remove(0);
@@ -290,7 +395,7 @@ mod tests {
             &["command".to_string(), "subcommand".to_string()],
         );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_COMMAND_SYNTAX);
         let c_make_params = c_make_params_arc.lock().unwrap();
         assert_eq!(*c_make_params, vec![vec!["subcommand".to_string()],]);
         assert_eq!(
@@ -322,7 +427,7 @@ mod tests {
             &["command".to_string(), "subcommand".to_string()],
         );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_COMMAND_SYNTAX);
         let c_make_params = c_make_params_arc.lock().unwrap();
         assert_eq!(*c_make_params, vec![vec!["subcommand".to_string()],]);
         assert_eq!(stream_holder.stdout.get_string(), "".to_string());
@@ -351,7 +456,7 @@ mod tests {
             &["command".to_string(), "subcommand".to_string()],
         );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_TRANSMISSION_PROBLEM);
         assert_eq!(stream_holder.stdout.get_string(), "".to_string());
         assert_eq!(
             stream_holder.stderr.get_string(),
@@ -359,6 +464,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn go_reports_errors_as_json_in_json_mode() {
+        let command = MockCommand::new(UiShutdownRequest {}.tmb(1)).execute_result(Ok(())); // irrelevant
+        let command_factory = CommandFactoryMock::new()
+            .make_result(Ok(Box::new(command)))
+            .make_result(Err(CommandSyntax("booga".to_string())));
+        let processor = CommandProcessorMock::new()
+            .output_format_result(OutputFormat::Json)
+            .process_result(Err(Payload(1234, "Booga!".to_string())));
+        let mut processor: Box<dyn CommandProcessor> = Box::new(processor);
+        let mut stream_holder = FakeStreamHolder::new();
+
+        let payload_result = handle_command_common(
+            &command_factory,
+            &mut *processor,
+            &["financials".to_string()],
+            &mut stream_holder.stderr,
+        );
+        let syntax_result = handle_command_common(
+            &command_factory,
+            &mut *processor,
+            &["financials".to_string(), "--booga".to_string()],
+            &mut stream_holder.stderr,
+        );
+
+        assert_eq!(payload_result, EXIT_CODE_PAYLOAD_ERROR);
+        assert_eq!(syntax_result, EXIT_CODE_COMMAND_SYNTAX);
+        assert_eq!(
+            stream_holder.stderr.get_string(),
+            "{\"error\":{\"code\":1234,\"kind\":\"payload\",\"message\":\"Booga!\"}}\n\
+             {\"error\":{\"kind\":\"commandSyntax\",\"message\":\"booga\"}}\n"
+                .to_string()
+        );
+    }
+
     #[test]
     fn go_works_when_daemon_is_not_running() {
         let processor_factory = CommandProcessorFactoryMock::new()
@@ -375,7 +515,7 @@ mod tests {
             &["command".to_string(), "subcommand".to_string()],
         );
 
-        assert_eq!(result, 1);
+        assert_eq!(result, EXIT_CODE_CONNECTION_PROBLEM);
         assert_eq!(stream_holder.stdout.get_string(), "".to_string());
         assert_eq!(
             stream_holder.stderr.get_string(),
@@ -385,6 +525,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn go_reports_absent_daemon_as_json_in_json_mode() {
+        let p_make_params_arc = Arc::new(Mutex::new(vec![]));
+        let processor_factory = CommandProcessorFactoryMock::new()
+            .make_params(&p_make_params_arc)
+            .make_result(Err(CommandError::ConnectionProblem("booga".to_string())));
+        let mut subject = Main {
+            non_interactive_clap_factory: Box::new(NIClapFactoryReal),
+            command_factory: Box::new(CommandFactoryMock::new()),
+            processor_factory: Box::new(processor_factory),
+        };
+        let mut stream_holder = FakeStreamHolder::new();
+
+        let result = subject.go(
+            &mut stream_holder.streams(),
+            &["masq", "--output", "json", "financials"]
+                .iter()
+                .map(|str| str.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        assert_eq!(result, EXIT_CODE_CONNECTION_PROBLEM);
+        let p_make_params = p_make_params_arc.lock().unwrap();
+        assert_eq!(p_make_params[0].5, OutputFormat::Json);
+        assert_eq!(stream_holder.stdout.get_string(), "".to_string());
+        assert_eq!(
+            stream_holder.stderr.get_string(),
+            "{\"error\":{\"kind\":\"connectionProblem\",\"message\":\"booga\"}}\n".to_string()
+        );
+    }
+
     #[test]
     fn populate_interactive_dependencies_produces_all_needed_to_block_printing_from_another_thread_when_the_lock_is_acquired(
     ) {
@@ -446,13 +617,23 @@ mod tests {
         let c_make_params = c_make_params_arc.lock().unwrap();
         assert_eq!(*c_make_params, vec![vec!["setup".to_string(),],]);
         let mut p_make_params = p_make_params_arc.lock().unwrap();
-        let (terminal_interface, broadcast_handle, ui_port) = p_make_params.pop().unwrap();
+        let (
+            terminal_interface,
+            broadcast_handle,
+            broadcast_rx_opt,
+            ui_port,
+            instance_opt,
+            output_format,
+        ) = p_make_params.pop().unwrap();
         assert_eq!(ui_port, 10000);
+        assert_eq!(instance_opt, None);
+        assert_eq!(output_format, OutputFormat::Human);
         assert!(terminal_interface.is_none());
         assert!(broadcast_handle
             .as_any()
-            .downcast_ref::<BroadcastHandleInactive>()
+            .downcast_ref::<BroadcastHandleGeneric>()
             .is_some());
+        assert!(broadcast_rx_opt.is_some());
         let mut process_params = process_params_arc.lock().unwrap();
         assert_eq!(
             *(*process_params)
@@ -461,10 +642,91 @@ mod tests {
                 .as_any()
                 .downcast_ref::<SetupCommand>()
                 .unwrap(),
-            SetupCommand { values: vec![] }
+            SetupCommand {
+                values: vec![],
+                profile_action_opt: None
+            }
         )
     }
 
+    #[test]
+    fn go_runs_script_from_stdin() {
+        let c_make_params_arc = Arc::new(Mutex::new(vec![]));
+        let command_factory = CommandFactoryMock::new()
+            .make_params(&c_make_params_arc)
+            .make_result(Ok(Box::new(SetupCommand::new(&[]).unwrap())))
+            .make_result(Ok(Box::new(SetupCommand::new(&[]).unwrap())));
+        let close_params_arc = Arc::new(Mutex::new(vec![]));
+        let processor = CommandProcessorMock::new()
+            .close_params(&close_params_arc)
+            .process_result(Ok(()))
+            .process_result(Err(Payload(1234, "Booga!".to_string())));
+        let p_make_params_arc = Arc::new(Mutex::new(vec![]));
+        let processor_factory = CommandProcessorFactoryMock::new()
+            .make_params(&p_make_params_arc)
+            .make_result(Ok(Box::new(processor)));
+        let mut subject = Main {
+            non_interactive_clap_factory: Box::new(NIClapFactoryReal),
+            command_factory: Box::new(command_factory),
+            processor_factory: Box::new(processor_factory),
+        };
+        let mut stream_holder = FakeStreamHolder::new();
+        stream_holder.stdin = ByteArrayReader::new(b"setup\n# and now\nstart\nshutdown\n");
+
+        let result = subject.go(
+            &mut stream_holder.streams(),
+            &["masq", "--script", "-"]
+                .iter()
+                .map(|str| str.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        assert_eq!(result, EXIT_CODE_PAYLOAD_ERROR);
+        let c_make_params = c_make_params_arc.lock().unwrap();
+        assert_eq!(
+            *c_make_params,
+            vec![vec!["setup".to_string()], vec!["start".to_string()]]
+        );
+        let p_make_params = p_make_params_arc.lock().unwrap();
+        assert!(p_make_params[0].0.is_none());
+        assert_eq!(
+            stream_holder.stderr.get_string(),
+            "Booga! (Code 0000_0000_0000_04D2)\nScript line 3 failed: start\n".to_string()
+        );
+        assert_eq!(close_params_arc.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn go_complains_about_unreadable_script() {
+        let p_make_params_arc = Arc::new(Mutex::new(vec![]));
+        let processor_factory = CommandProcessorFactoryMock::new().make_params(&p_make_params_arc);
+        let mut subject = Main {
+            non_interactive_clap_factory: Box::new(NIClapFactoryReal),
+            command_factory: Box::new(CommandFactoryMock::new()),
+            processor_factory: Box::new(processor_factory),
+        };
+        let mut stream_holder = FakeStreamHolder::new();
+
+        let result = subject.go(
+            &mut stream_holder.streams(),
+            &["masq", "--script", "/nonexistent/booga.masq"]
+                .iter()
+                .map(|str| str.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        assert_eq!(result, 1);
+        assert!(
+            stream_holder
+                .stderr
+                .get_string()
+                .starts_with("Can't read script '/nonexistent/booga.masq': "),
+            "{}",
+            stream_holder.stderr.get_string()
+        );
+        assert!(p_make_params_arc.lock().unwrap().is_empty());
+    }
+
     #[test]
     fn extract_subcommands_can_process_interactive_mode_request() {
         let args = vec!["masq".to_string()];
@@ -0,0 +1,83 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::commands::ban_list_command::BanListCommand;
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiBanListChangedBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct BanListChangedNotification {}
+
+impl BanListChangedNotification {
+    pub fn handle_broadcast(
+        response: UiBanListChangedBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(stdout);
+        response.banned.iter().for_each(|wallet| {
+            short_writeln!(stdout, "Wallet {} has been banned for delinquency.", wallet)
+        });
+        response.unbanned.iter().for_each(|wallet| {
+            short_writeln!(
+                stdout,
+                "Wallet {} has paid its debt and is no longer banned.",
+                wallet
+            )
+        });
+        response.warned.iter().for_each(|debtor| {
+            short_writeln!(
+                stdout,
+                "Wallet {} is delinquent with a balance of {} wei, and will be banned at {} UTC unless it pays.",
+                debtor.wallet,
+                debtor.balance_wei,
+                BanListCommand::utc_time(debtor.ban_timestamp)
+            )
+        });
+        short_writeln!(stdout);
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::messages::UiWarnedDebtor;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    #[test]
+    fn broadcasts_ban_list_changes() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiBanListChangedBroadcast {
+            banned: vec![
+                "0x1111111111111111111111111111111111111111".to_string(),
+                "0x2222222222222222222222222222222222222222".to_string(),
+            ],
+            unbanned: vec!["0x3333333333333333333333333333333333333333".to_string()],
+            warned: vec![UiWarnedDebtor {
+                wallet: "0x4444444444444444444444444444444444444444".to_string(),
+                balance_wei: "123456789000000000".to_string(),
+                ban_timestamp: 1_650_000_000,
+            }],
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        BanListChangedNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\n\
+             Wallet 0x1111111111111111111111111111111111111111 has been banned for delinquency.\n\
+             Wallet 0x2222222222222222222222222222222222222222 has been banned for delinquency.\n\
+             Wallet 0x3333333333333333333333333333333333333333 has paid its debt and is no longer banned.\n\
+             Wallet 0x4444444444444444444444444444444444444444 is delinquent with a balance of 123456789000000000 wei, \
+             and will be banned at 2022-04-15 05:20:00 UTC unless it pays.\n\
+             \n"
+        );
+    }
+}
@@ -30,7 +30,8 @@ impl CrashNotifier {
         }
         short_writeln!(
             stdout,
-            "\nThe Node running as process {} terminated{}\nThe Daemon is once more accepting setup changes.\n",
+            "\nThe Node instance '{}' running as process {} terminated{}\nThe Daemon is once more accepting setup changes.\n",
+            response.instance,
             response.process_id,
             Self::dress_message (response.crash_reason)
         );
@@ -43,6 +44,9 @@ impl CrashNotifier {
                 format!("the Daemon couldn't wait on the child process: {}", msg)
             }
             CrashReason::NoInformation => panic!("Should never get here"),
+            CrashReason::ConfigurationError(msg) => {
+                format!("the Node rejected its configuration: {}", msg)
+            }
             CrashReason::Unrecognized(msg) => msg,
             CrashReason::DaemonCrashed => panic!("Should never get here"),
         }
@@ -73,6 +77,7 @@ mod tests {
         let mut stdout = ByteArrayWriter::new();
         let stderr = ByteArrayWriter::new();
         let msg = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 12345,
             crash_reason: CrashReason::ChildWaitFailure("Couldn't wait".to_string()),
         };
@@ -80,16 +85,33 @@ mod tests {
 
         CrashNotifier::handle_broadcast(msg, &mut stdout, &term_interface);
 
-        assert_eq! (stdout.get_string(), "\nThe Node running as process 12345 terminated:\n------\nthe Daemon couldn't wait on the child process: Couldn't wait\n------\nThe Daemon is once more accepting setup changes.\n\n".to_string());
+        assert_eq! (stdout.get_string(), "\nThe Node instance 'default' running as process 12345 terminated:\n------\nthe Daemon couldn't wait on the child process: Couldn't wait\n------\nThe Daemon is once more accepting setup changes.\n\n".to_string());
         assert_eq!(stderr.get_string(), "".to_string());
     }
 
+    #[test]
+    pub fn handles_configuration_error() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
+            process_id: 12345,
+            crash_reason: CrashReason::ConfigurationError("chain - booga".to_string()),
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        CrashNotifier::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq! (stdout.get_string(), "\nThe Node instance 'default' running as process 12345 terminated:\n------\nthe Node rejected its configuration: chain - booga\n------\nThe Daemon is once more accepting setup changes.\n\n".to_string());
+    }
+
     #[test]
     pub fn handles_unknown_failure() {
         running_test();
         let mut stdout = ByteArrayWriter::new();
         let stderr = ByteArrayWriter::new();
         let msg = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 12345,
             crash_reason: CrashReason::Unrecognized("Just...failed!\n\n".to_string()),
         };
@@ -97,7 +119,7 @@ mod tests {
 
         CrashNotifier::handle_broadcast(msg, &mut stdout, &term_interface);
 
-        assert_eq! (stdout.get_string(), "\nThe Node running as process 12345 terminated:\n------\nJust...failed!\n------\nThe Daemon is once more accepting setup changes.\n\n".to_string());
+        assert_eq! (stdout.get_string(), "\nThe Node instance 'default' running as process 12345 terminated:\n------\nJust...failed!\n------\nThe Daemon is once more accepting setup changes.\n\n".to_string());
         assert_eq!(stderr.get_string(), "".to_string());
     }
 
@@ -107,6 +129,7 @@ mod tests {
         let mut stdout = ByteArrayWriter::new();
         let stderr = ByteArrayWriter::new();
         let msg = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 12345,
             crash_reason: CrashReason::NoInformation,
         };
@@ -114,7 +137,7 @@ mod tests {
 
         CrashNotifier::handle_broadcast(msg, &mut stdout, &term_interface);
 
-        assert_eq! (stdout.get_string(), "\nThe Node running as process 12345 terminated.\nThe Daemon is once more accepting setup changes.\n\n".to_string());
+        assert_eq! (stdout.get_string(), "\nThe Node instance 'default' running as process 12345 terminated.\nThe Daemon is once more accepting setup changes.\n\n".to_string());
         assert_eq!(stderr.get_string(), "".to_string());
     }
 
@@ -124,6 +147,7 @@ mod tests {
         running_test();
         let mut stdout = ByteArrayWriter::new();
         let msg = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 12345,
             crash_reason: CrashReason::DaemonCrashed,
         };
@@ -1,4 +1,10 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+pub mod ban_list_changed_notification;
 pub mod connection_change_notification;
 pub mod crashed_notification;
+pub mod receivables_catch_up_notification;
+pub mod restarted_notification;
+pub mod shutdown_progress_notification;
+pub mod wallet_balance_notification;
+pub mod wallet_discovery_progress_notification;
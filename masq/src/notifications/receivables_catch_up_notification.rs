@@ -0,0 +1,52 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiReceivablesCatchUpBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct ReceivablesCatchUpNotification {}
+
+impl ReceivablesCatchUpNotification {
+    pub fn handle_broadcast(
+        response: UiReceivablesCatchUpBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nCatching up on payments to your earning wallet: scanned through block {} of {}.\n",
+            response.scanned_through_block,
+            response.target_block
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    #[test]
+    fn broadcasts_catch_up_progress() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiReceivablesCatchUpBroadcast {
+            scanned_through_block: 123_456,
+            target_block: 234_567,
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        ReceivablesCatchUpNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nCatching up on payments to your earning wallet: scanned through block 123456 of 234567.\n\n"
+        );
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiNodeRestartedBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct RestartedNotification {}
+
+impl RestartedNotification {
+    pub fn handle_broadcast(
+        response: UiNodeRestartedBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nThe Daemon restarted the Node instance '{}' that was running as process {} (restart {}). \
+             It is now running as process {}, and UIs can reach it on port {}.\n",
+            response.instance,
+            response.crashed_process_id,
+            response.attempt,
+            response.new_process_id,
+            response.redirect_ui_port
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    #[test]
+    fn broadcasts_node_restart() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let msg = UiNodeRestartedBroadcast {
+            instance: "second".to_string(),
+            crashed_process_id: 12345,
+            new_process_id: 23456,
+            redirect_ui_port: 7777,
+            attempt: 2,
+        };
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        RestartedNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nThe Daemon restarted the Node instance 'second' that was running as process 12345 (restart 2). \
+             It is now running as process 23456, and UIs can reach it on port 7777.\n\n"
+        );
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::{ShutdownStage, UiShutdownProgressBroadcast};
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct ShutdownProgressNotification {}
+
+impl ShutdownProgressNotification {
+    pub fn handle_broadcast(
+        response: UiShutdownProgressBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let stage = match response.stage {
+            ShutdownStage::Draining => "draining",
+            ShutdownStage::Farewell => "farewell",
+            ShutdownStage::Settling => "settling",
+            ShutdownStage::Exiting => "exiting",
+        };
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nGraceful shutdown ({}): {}\n",
+            stage,
+            response.message
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    #[test]
+    fn broadcasts_shutdown_progress() {
+        running_test();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+        let expectations = vec![
+            (ShutdownStage::Draining, "draining"),
+            (ShutdownStage::Farewell, "farewell"),
+            (ShutdownStage::Settling, "settling"),
+            (ShutdownStage::Exiting, "exiting"),
+        ];
+
+        expectations.into_iter().for_each(|(stage, label)| {
+            let mut stdout = ByteArrayWriter::new();
+            let msg = UiShutdownProgressBroadcast {
+                stage,
+                message: "Things are happening".to_string(),
+            };
+
+            ShutdownProgressNotification::handle_broadcast(msg, &mut stdout, &term_interface);
+
+            assert_eq!(
+                stdout.get_string(),
+                format!("\nGraceful shutdown ({}): Things are happening\n\n", label)
+            );
+        });
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use masq_lib::messages::UiWalletBalanceBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct WalletBalanceNotification {}
+
+impl WalletBalanceNotification {
+    // The Node broadcasts balances every few minutes; only the ones worth worrying about are shown
+    pub fn handle_broadcast(
+        response: UiWalletBalanceBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        if !response.consuming_services_suspended {
+            return;
+        }
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nYour consuming wallet is low on funds: {} gwei of gas (threshold {}), {} gwei of MASQ \
+            (threshold {}). Consuming services are suspended until you replenish it.\n",
+            response.gas_balance_gwei,
+            response.gas_threshold_gwei,
+            response.masq_balance_gwei,
+            response.masq_threshold_gwei
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    fn make_broadcast(consuming_services_suspended: bool) -> UiWalletBalanceBroadcast {
+        UiWalletBalanceBroadcast {
+            gas_balance_gwei: 123,
+            masq_balance_gwei: 456,
+            gas_threshold_gwei: 1000,
+            masq_threshold_gwei: 2000,
+            consuming_services_suspended,
+        }
+    }
+
+    #[test]
+    fn broadcasts_low_funds_warning() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        WalletBalanceNotification::handle_broadcast(
+            make_broadcast(true),
+            &mut stdout,
+            &term_interface,
+        );
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nYour consuming wallet is low on funds: 123 gwei of gas (threshold 1000), 456 gwei of MASQ \
+            (threshold 2000). Consuming services are suspended until you replenish it.\n\n"
+        );
+    }
+
+    #[test]
+    fn stays_quiet_about_healthy_balances() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        WalletBalanceNotification::handle_broadcast(
+            make_broadcast(false),
+            &mut stdout,
+            &term_interface,
+        );
+
+        assert_eq!(stdout.get_string(), "");
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::terminal::terminal_interface::TerminalWrapper;
+use itertools::Itertools;
+use masq_lib::messages::UiWalletDiscoveryProgressBroadcast;
+use masq_lib::short_writeln;
+use std::io::Write;
+
+pub struct WalletDiscoveryProgressNotification {}
+
+impl WalletDiscoveryProgressNotification {
+    pub fn handle_broadcast(
+        response: UiWalletDiscoveryProgressBroadcast,
+        stdout: &mut dyn Write,
+        term_interface: &TerminalWrapper,
+    ) {
+        let found = if response.candidates.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "; activity found at {}",
+                response
+                    .candidates
+                    .iter()
+                    .map(|candidate| format!(
+                        "{} ({})",
+                        candidate.derivation_path, candidate.address
+                    ))
+                    .join(", ")
+            )
+        };
+        let _lock = term_interface.lock();
+        short_writeln!(
+            stdout,
+            "\nWallet discovery: {} of {} derivation paths scanned{}\n",
+            response.scanned_path_count,
+            response.total_path_count,
+            found
+        );
+        stdout.flush().expect("flush failed");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mocks::TerminalPassiveMock;
+    use masq_lib::messages::UiWalletCandidate;
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use masq_lib::utils::running_test;
+    use std::sync::Arc;
+
+    fn make_candidate(derivation_path: &str, address: &str) -> UiWalletCandidate {
+        UiWalletCandidate {
+            derivation_path: derivation_path.to_string(),
+            address: address.to_string(),
+            gas_balance_gwei: 0,
+            masq_balance_gwei: 0,
+            transaction_count: 0,
+            received_transfer_count: 1,
+        }
+    }
+
+    #[test]
+    fn broadcasts_progress_without_candidates() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        WalletDiscoveryProgressNotification::handle_broadcast(
+            UiWalletDiscoveryProgressBroadcast {
+                scanned_path_count: 20,
+                total_path_count: 60,
+                candidates: vec![],
+            },
+            &mut stdout,
+            &term_interface,
+        );
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nWallet discovery: 20 of 60 derivation paths scanned\n\n"
+        );
+    }
+
+    #[test]
+    fn broadcasts_progress_with_candidates() {
+        running_test();
+        let mut stdout = ByteArrayWriter::new();
+        let term_interface = TerminalWrapper::new(Arc::new(TerminalPassiveMock::new()));
+
+        WalletDiscoveryProgressNotification::handle_broadcast(
+            UiWalletDiscoveryProgressBroadcast {
+                scanned_path_count: 40,
+                total_path_count: 60,
+                candidates: vec![
+                    make_candidate("m/44'/60'/0'/0/21", "0x1111"),
+                    make_candidate("m/44'/60'/0'/0/37", "0x2222"),
+                ],
+            },
+            &mut stdout,
+            &term_interface,
+        );
+
+        assert_eq!(
+            stdout.get_string(),
+            "\nWallet discovery: 40 of 60 derivation paths scanned; activity found at \
+            m/44'/60'/0'/0/21 (0x1111), m/44'/60'/0'/0/37 (0x2222)\n\n"
+        );
+    }
+}
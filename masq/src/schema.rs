@@ -1,5 +1,8 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::commands::backup_command::backup_subcommand;
+use crate::commands::ban_command::{ban_subcommand, unban_subcommand};
+use crate::commands::ban_list_command::ban_list_subcommand;
 use crate::commands::change_password_command::{
     change_password_subcommand, set_password_subcommand,
 };
@@ -7,16 +10,23 @@ use crate::commands::check_password_command::check_password_subcommand;
 use crate::commands::configuration_command::configuration_subcommand;
 use crate::commands::connection_status_command::connection_status_subcommand;
 use crate::commands::crash_command::crash_subcommand;
+use crate::commands::dashboard_command::dashboard_subcommand;
 use crate::commands::descriptor_command::descriptor_subcommand;
+use crate::commands::discover_wallets_command::discover_wallets_subcommand;
 use crate::commands::financials_command::args_validation::financials_subcommand;
+use crate::commands::forgive_debt_command::forgive_debt_subcommand;
 use crate::commands::generate_wallets_command::generate_wallets_subcommand;
+use crate::commands::logs_command::logs_subcommand;
 use crate::commands::recover_wallets_command::recover_wallets_subcommand;
+use crate::commands::restore_command::restore_subcommand;
+use crate::commands::rotate_earning_wallet_command::rotate_earning_wallet_subcommand;
 use crate::commands::scan_command::scan_subcommand;
 use crate::commands::set_configuration_command::set_configuration_subcommand;
 use crate::commands::setup_command::setup_subcommand;
 use crate::commands::shutdown_command::shutdown_subcommand;
 use crate::commands::start_command::start_subcommand;
 use crate::commands::wallet_addresses_command::wallet_addresses_subcommand;
+use crate::commands::wallet_balances_command::wallet_balances_subcommand;
 use clap::{App, AppSettings, Arg};
 use lazy_static::lazy_static;
 use masq_lib::constants::{DEFAULT_UI_PORT, HIGHEST_USABLE_PORT, LOWEST_USABLE_INSECURE_PORT};
@@ -30,6 +40,27 @@ lazy_static! {
     static ref DEFAULT_UI_PORT_STRING: String = format!("{}", DEFAULT_UI_PORT);
 }
 
+const INSTANCE_HELP: &str =
+    "The name of the Node instance to address, if the Daemon is managing more than one. Each \
+     instance has its own setup and data directory. If you leave this out, you'll address the \
+     instance named 'default'.";
+
+const OUTPUT_HELP: &str =
+    "How masq should print what the Node or Daemon tells it. 'human' produces the usual tables \
+     and messages; 'json' prints the raw payload of every response as one line of JSON on stdout \
+     and reports errors as JSON objects on stderr, which is handier for scripts and monitoring.";
+
+const SCRIPT_HELP: &str =
+    "Path to a file of masq commands to run one after another, one command per line; use '-' to \
+     read the commands from standard input. Blank lines and lines starting with '#' are ignored, \
+     $NAME and ${NAME} are replaced with the values of environment variables, and a line such as \
+     'wait-for connectionChange RouteFound --timeout 120' pauses until the Daemon or Node sends a \
+     matching broadcast. The script stops at the first command that fails.";
+
+const KEEP_GOING_HELP: &str =
+    "With --script, carry on with the rest of the script after a command fails instead of stopping. \
+     masq still exits with the code of the first failure.";
+
 const APP_NAME: &str = "masq";
 const APP_VERSION: &str = "1.0.0";
 const APP_AUTHOR: &str = "MASQ";
@@ -61,22 +92,63 @@ pub fn app() -> App<'static, 'static> {
                 .validator(validate_ui_port)
                 .help(UI_PORT_HELP.as_str()),
         )
+        .arg(
+            Arg::with_name("instance")
+                .long("instance")
+                .value_name("INSTANCE")
+                .takes_value(true)
+                .validator(validate_instance_name)
+                .help(INSTANCE_HELP),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .value_name("FORMAT")
+                .takes_value(true)
+                .possible_values(&["human", "json"])
+                .default_value("human")
+                .help(OUTPUT_HELP),
+        )
+        .arg(
+            Arg::with_name("script")
+                .long("script")
+                .value_name("FILE")
+                .takes_value(true)
+                .help(SCRIPT_HELP),
+        )
+        .arg(
+            Arg::with_name("keep-going")
+                .long("keep-going")
+                .requires("script")
+                .help(KEEP_GOING_HELP),
+        )
+        .subcommand(backup_subcommand())
+        .subcommand(ban_subcommand())
+        .subcommand(ban_list_subcommand())
         .subcommand(change_password_subcommand())
         .subcommand(check_password_subcommand())
         .subcommand(crash_subcommand())
         .subcommand(configuration_subcommand())
         .subcommand(connection_status_subcommand())
+        .subcommand(dashboard_subcommand())
         .subcommand(descriptor_subcommand())
+        .subcommand(discover_wallets_subcommand())
         .subcommand(financials_subcommand())
+        .subcommand(forgive_debt_subcommand())
         .subcommand(generate_wallets_subcommand())
+        .subcommand(logs_subcommand())
         .subcommand(recover_wallets_subcommand())
+        .subcommand(restore_subcommand())
+        .subcommand(rotate_earning_wallet_subcommand())
         .subcommand(scan_subcommand())
         .subcommand(set_configuration_subcommand())
         .subcommand(set_password_subcommand())
         .subcommand(setup_subcommand())
         .subcommand(shutdown_subcommand())
         .subcommand(start_subcommand())
+        .subcommand(unban_subcommand())
         .subcommand(wallet_addresses_subcommand())
+        .subcommand(wallet_balances_subcommand())
 }
 
 fn validate_ui_port(port: String) -> Result<(), String> {
@@ -87,6 +159,21 @@ fn validate_ui_port(port: String) -> Result<(), String> {
     }
 }
 
+fn validate_instance_name(name: String) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Instance name '{}' must consist of letters, digits, '-' and '_' only",
+            name
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,5 +199,46 @@ mod tests {
             DEFAULT_UI_PORT_STRING.to_string(),
             format!("{}", DEFAULT_UI_PORT)
         );
+        assert_eq!(
+            INSTANCE_HELP,
+            "The name of the Node instance to address, if the Daemon is managing more than one. Each \
+             instance has its own setup and data directory. If you leave this out, you'll address the \
+             instance named 'default'."
+        );
+        assert_eq!(
+            OUTPUT_HELP,
+            "How masq should print what the Node or Daemon tells it. 'human' produces the usual tables \
+             and messages; 'json' prints the raw payload of every response as one line of JSON on stdout \
+             and reports errors as JSON objects on stderr, which is handier for scripts and monitoring."
+        );
+        assert_eq!(
+            SCRIPT_HELP,
+            "Path to a file of masq commands to run one after another, one command per line; use '-' to \
+             read the commands from standard input. Blank lines and lines starting with '#' are ignored, \
+             $NAME and ${NAME} are replaced with the values of environment variables, and a line such as \
+             'wait-for connectionChange RouteFound --timeout 120' pauses until the Daemon or Node sends a \
+             matching broadcast. The script stops at the first command that fails."
+        );
+        assert_eq!(
+            KEEP_GOING_HELP,
+            "With --script, carry on with the rest of the script after a command fails instead of stopping. \
+             masq still exits with the code of the first failure."
+        );
+    }
+
+    #[test]
+    fn validate_instance_name_accepts_and_rejects_properly() {
+        assert_eq!(validate_instance_name("second_Node-2".to_string()), Ok(()));
+        assert_eq!(
+            validate_instance_name("".to_string()),
+            Err("Instance name '' must consist of letters, digits, '-' and '_' only".to_string())
+        );
+        assert_eq!(
+            validate_instance_name("../booga".to_string()),
+            Err(
+                "Instance name '../booga' must consist of letters, digits, '-' and '_' only"
+                    .to_string()
+            )
+        );
     }
 }
@@ -0,0 +1,595 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::command_context::OutputFormat;
+use crate::command_factory::CommandFactory;
+use crate::command_processor::CommandProcessor;
+use crate::commands::commands_common::CommandError::{ConnectionProblem, Reception};
+use crate::commands::commands_common::{json_error, CommandError, EXIT_CODE_COMMAND_SYNTAX};
+use crate::non_interactive_mode::handle_command_common;
+use crate::terminal::line_reader::split_quoted_line;
+use crossbeam_channel::{Receiver, RecvTimeoutError};
+use masq_lib::short_writeln;
+use masq_lib::ui_gateway::MessageBody;
+use serde_json::Value;
+use std::io::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::time::{Duration, Instant};
+
+pub const WAIT_FOR_DIRECTIVE: &str = "wait-for";
+pub const DEFAULT_WAIT_FOR_TIMEOUT_SECS: u64 = 60;
+
+const WAIT_FOR_USAGE: &str = "Usage: wait-for <opcode> [<value>] [--timeout <seconds>]";
+
+pub struct ScriptRunner {
+    keep_going: bool,
+    broadcast_rx: Receiver<MessageBody>,
+    variable_lookup: Box<dyn Fn(&str) -> Option<String>>,
+}
+
+impl ScriptRunner {
+    pub fn new(keep_going: bool, broadcast_rx: Receiver<MessageBody>) -> Self {
+        Self {
+            keep_going,
+            broadcast_rx,
+            variable_lookup: Box::new(|name| std::env::var(name).ok()),
+        }
+    }
+
+    pub fn run(
+        &self,
+        script: &str,
+        command_factory: &dyn CommandFactory,
+        command_processor: &mut dyn CommandProcessor,
+        stderr: &mut dyn Write,
+    ) -> u8 {
+        let mut first_failure_opt = None;
+        for (index, line) in script.lines().enumerate() {
+            let exit_code = self.run_line(line, command_factory, command_processor, stderr);
+            if exit_code == 0 {
+                continue;
+            }
+            if command_processor.output_format() == OutputFormat::Human {
+                short_writeln!(stderr, "Script line {} failed: {}", index + 1, line.trim());
+            }
+            first_failure_opt.get_or_insert(exit_code);
+            if !self.keep_going {
+                break;
+            }
+        }
+        first_failure_opt.unwrap_or(0)
+    }
+
+    fn run_line(
+        &self,
+        line: &str,
+        command_factory: &dyn CommandFactory,
+        command_processor: &mut dyn CommandProcessor,
+        stderr: &mut dyn Write,
+    ) -> u8 {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return 0;
+        }
+        let output_format = command_processor.output_format();
+        let args = match substitute_variables(line, &*self.variable_lookup) {
+            Ok(substituted) => split_quoted_line(substituted),
+            Err(msg) => {
+                report_syntax_error(&msg, output_format, stderr);
+                return EXIT_CODE_COMMAND_SYNTAX;
+            }
+        };
+        match args.split_first() {
+            None => 0,
+            Some((directive, parameters)) if directive == WAIT_FOR_DIRECTIVE => {
+                match WaitFor::parse(parameters) {
+                    Err(msg) => {
+                        report_syntax_error(&msg, output_format, stderr);
+                        EXIT_CODE_COMMAND_SYNTAX
+                    }
+                    Ok(wait_for) => match self.wait_for(&wait_for) {
+                        Ok(()) => 0,
+                        Err(e) => {
+                            match output_format {
+                                OutputFormat::Human => short_writeln!(stderr, "{}", e),
+                                OutputFormat::Json => short_writeln!(stderr, "{}", e.to_json()),
+                            }
+                            e.exit_code()
+                        }
+                    },
+                }
+            }
+            Some(_) => handle_command_common(command_factory, command_processor, &args, stderr),
+        }
+    }
+
+    //broadcasts pile up in the channel while the commands run, so one that arrived before
+    //the wait started is still found; everything up to the match is consumed
+    fn wait_for(&self, wait_for: &WaitFor) -> Result<(), CommandError> {
+        let deadline = Instant::now() + wait_for.timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match self.broadcast_rx.recv_timeout(remaining) {
+                Ok(message_body) if wait_for.matches(&message_body) => return Ok(()),
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(Reception(format!(
+                        "No '{}' broadcast{} arrived within {} seconds",
+                        wait_for.opcode,
+                        match &wait_for.value_opt {
+                            Some(value) => format!(" with '{}'", value),
+                            None => String::new(),
+                        },
+                        wait_for.timeout.as_secs()
+                    )))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ConnectionProblem(
+                        "Lost the connection while waiting for a broadcast".to_string(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct WaitFor {
+    opcode: String,
+    value_opt: Option<String>,
+    timeout: Duration,
+}
+
+impl WaitFor {
+    fn parse(parameters: &[String]) -> Result<Self, String> {
+        let (positionals, timeout) = match parameters.iter().position(|p| p == "--timeout") {
+            None => (parameters, DEFAULT_WAIT_FOR_TIMEOUT_SECS),
+            Some(index) if index + 2 == parameters.len() => {
+                match parameters[index + 1].parse::<u64>() {
+                    Ok(secs) => (&parameters[..index], secs),
+                    Err(_) => {
+                        return Err(format!(
+                            "'{}' is not a number of seconds. {}",
+                            parameters[index + 1],
+                            WAIT_FOR_USAGE
+                        ))
+                    }
+                }
+            }
+            Some(_) => return Err(WAIT_FOR_USAGE.to_string()),
+        };
+        match positionals {
+            [opcode] => Ok(Self {
+                opcode: opcode.clone(),
+                value_opt: None,
+                timeout: Duration::from_secs(timeout),
+            }),
+            [opcode, value] => Ok(Self {
+                opcode: opcode.clone(),
+                value_opt: Some(value.clone()),
+                timeout: Duration::from_secs(timeout),
+            }),
+            _ => Err(WAIT_FOR_USAGE.to_string()),
+        }
+    }
+
+    fn matches(&self, message_body: &MessageBody) -> bool {
+        if message_body.opcode != self.opcode {
+            return false;
+        }
+        match (&self.value_opt, &message_body.payload) {
+            (None, _) => true,
+            (Some(value), Ok(json)) => serde_json::from_str::<Value>(json)
+                .map(|payload| Self::contains_value(&payload, value))
+                .unwrap_or(false),
+            (Some(_), Err(_)) => false,
+        }
+    }
+
+    fn contains_value(json: &Value, wanted: &str) -> bool {
+        match json {
+            Value::String(string) => string == wanted,
+            Value::Array(values) => values
+                .iter()
+                .any(|value| Self::contains_value(value, wanted)),
+            Value::Object(map) => map
+                .values()
+                .any(|value| Self::contains_value(value, wanted)),
+            Value::Null => false,
+            other => other.to_string() == wanted,
+        }
+    }
+}
+
+//$NAME and ${NAME} are replaced with the variable's value; $$ stands for a plain '$'
+fn substitute_variables(
+    line: &str,
+    variable_lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<String, String> {
+    let mut result = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+        let name = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                result.push('$');
+                continue;
+            }
+            Some('{') => {
+                chars.next();
+                let mut name = String::new();
+                let mut terminated = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        terminated = true;
+                        break;
+                    }
+                    name.push(c);
+                }
+                if !terminated || name.is_empty() {
+                    return Err(format!("Unterminated or empty '${{' in line: {}", line));
+                }
+                name
+            }
+            Some(c) if c.is_ascii_alphanumeric() || *c == '_' => take_name(&mut chars),
+            _ => {
+                result.push('$');
+                continue;
+            }
+        };
+        match variable_lookup(&name) {
+            Some(value) => result.push_str(&value),
+            None => return Err(format!("Environment variable '{}' is not set", name)),
+        }
+    }
+    Ok(result)
+}
+
+fn take_name(chars: &mut Peekable<Chars>) -> String {
+    let mut name = String::new();
+    while let Some(c) = chars.peek() {
+        if !(c.is_ascii_alphanumeric() || *c == '_') {
+            break;
+        }
+        name.push(*c);
+        chars.next();
+    }
+    name
+}
+
+fn report_syntax_error(msg: &str, output_format: OutputFormat, stderr: &mut dyn Write) {
+    match output_format {
+        OutputFormat::Human => short_writeln!(stderr, "{}", msg),
+        OutputFormat::Json => short_writeln!(stderr, "{}", json_error("scriptSyntax", None, msg)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_factory::CommandFactoryError::CommandSyntax;
+    use crate::commands::commands_common::CommandError::{Payload, Transmission};
+    use crate::commands::commands_common::{
+        Command, EXIT_CODE_CONNECTION_PROBLEM, EXIT_CODE_PAYLOAD_ERROR,
+        EXIT_CODE_TRANSMISSION_PROBLEM,
+    };
+    use crate::test_utils::mocks::{CommandFactoryMock, CommandProcessorMock, MockCommand};
+    use crossbeam_channel::unbounded;
+    use masq_lib::messages::{
+        ToMessageBody, UiConnectionChangeBroadcast, UiConnectionStage, UiShutdownRequest,
+    };
+    use masq_lib::test_utils::fake_stream_holder::ByteArrayWriter;
+    use std::sync::{Arc, Mutex};
+
+    fn make_command() -> Box<dyn Command> {
+        Box::new(MockCommand::new(UiShutdownRequest {}.tmb(1)))
+    }
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "DATA_DIR" => Some("/home/booga/masq".to_string()),
+            "PASSWORD" => Some("secret".to_string()),
+            _ => None,
+        }
+    }
+
+    fn connection_change(stage: UiConnectionStage) -> MessageBody {
+        UiConnectionChangeBroadcast { stage }.tmb(0)
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(WAIT_FOR_DIRECTIVE, "wait-for");
+        assert_eq!(DEFAULT_WAIT_FOR_TIMEOUT_SECS, 60);
+    }
+
+    #[test]
+    fn variables_are_substituted_from_the_environment() {
+        let result = substitute_variables(
+            "setup --data-directory ${DATA_DIR}/node --db-password $PASSWORD costs $$5 $ each",
+            &lookup,
+        );
+
+        assert_eq!(
+            result,
+            Ok(
+                "setup --data-directory /home/booga/masq/node --db-password secret costs $5 $ each"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn variable_substitution_complains_about_missing_and_malformed_variables() {
+        assert_eq!(
+            substitute_variables("set-password --new $BOOGA", &lookup),
+            Err("Environment variable 'BOOGA' is not set".to_string())
+        );
+        assert_eq!(
+            substitute_variables("setup --data-directory ${DATA_DIR", &lookup),
+            Err(
+                "Unterminated or empty '${' in line: setup --data-directory ${DATA_DIR".to_string()
+            )
+        );
+        assert_eq!(
+            substitute_variables("setup ${}", &lookup),
+            Err("Unterminated or empty '${' in line: setup ${}".to_string())
+        );
+    }
+
+    #[test]
+    fn wait_for_is_parsed_properly() {
+        let to_strings =
+            |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+
+        assert_eq!(
+            WaitFor::parse(&to_strings(&["connectionChange", "RouteFound"])),
+            Ok(WaitFor {
+                opcode: "connectionChange".to_string(),
+                value_opt: Some("RouteFound".to_string()),
+                timeout: Duration::from_secs(DEFAULT_WAIT_FOR_TIMEOUT_SECS),
+            })
+        );
+        assert_eq!(
+            WaitFor::parse(&to_strings(&["setup", "--timeout", "5"])),
+            Ok(WaitFor {
+                opcode: "setup".to_string(),
+                value_opt: None,
+                timeout: Duration::from_secs(5),
+            })
+        );
+        assert_eq!(
+            WaitFor::parse(&to_strings(&["setup", "--timeout", "soon"])),
+            Err(format!(
+                "'soon' is not a number of seconds. {}",
+                WAIT_FOR_USAGE
+            ))
+        );
+        assert_eq!(
+            WaitFor::parse(&to_strings(&["setup", "--timeout"])),
+            Err(WAIT_FOR_USAGE.to_string())
+        );
+        assert_eq!(WaitFor::parse(&[]), Err(WAIT_FOR_USAGE.to_string()));
+    }
+
+    #[test]
+    fn wait_for_matches_opcode_and_any_value_in_the_payload() {
+        let subject = WaitFor {
+            opcode: "connectionChange".to_string(),
+            value_opt: Some("RouteFound".to_string()),
+            timeout: Duration::from_secs(1),
+        };
+
+        assert!(subject.matches(&connection_change(UiConnectionStage::RouteFound)));
+        assert!(!subject.matches(&connection_change(UiConnectionStage::ConnectedToNeighbor)));
+        assert!(!subject.matches(&UiShutdownRequest {}.tmb(0)));
+    }
+
+    #[test]
+    fn run_executes_commands_and_skips_blank_lines_and_comments() {
+        let make_params_arc = Arc::new(Mutex::new(vec![]));
+        let command_factory = CommandFactoryMock::new()
+            .make_params(&make_params_arc)
+            .make_result(Ok(make_command()))
+            .make_result(Ok(make_command()));
+        let process_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut processor = CommandProcessorMock::new()
+            .process_params(&process_params_arc)
+            .process_result(Ok(()))
+            .process_result(Ok(()));
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let mut subject = ScriptRunner::new(false, broadcast_rx);
+        subject.variable_lookup = Box::new(lookup);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "# provisioning\n\n  setup --data-directory \"$DATA_DIR\"\n   \nstart\n",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, 0);
+        assert_eq!(
+            *make_params_arc.lock().unwrap(),
+            vec![
+                vec![
+                    "setup".to_string(),
+                    "--data-directory".to_string(),
+                    "/home/booga/masq".to_string()
+                ],
+                vec!["start".to_string()]
+            ]
+        );
+        assert_eq!(process_params_arc.lock().unwrap().len(), 2);
+        assert_eq!(stderr.get_string(), "".to_string());
+    }
+
+    #[test]
+    fn run_stops_at_the_first_failure() {
+        let make_params_arc = Arc::new(Mutex::new(vec![]));
+        let command_factory = CommandFactoryMock::new()
+            .make_params(&make_params_arc)
+            .make_result(Ok(make_command()));
+        let mut processor =
+            CommandProcessorMock::new().process_result(Err(Payload(1234, "Booga!".to_string())));
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let subject = ScriptRunner::new(false, broadcast_rx);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "start\nshutdown\n",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, EXIT_CODE_PAYLOAD_ERROR);
+        assert_eq!(
+            *make_params_arc.lock().unwrap(),
+            vec![vec!["start".to_string()]]
+        );
+        assert_eq!(
+            stderr.get_string(),
+            "Booga! (Code 0000_0000_0000_04D2)\nScript line 1 failed: start\n".to_string()
+        );
+    }
+
+    #[test]
+    fn run_keeps_going_when_told_to_and_reports_the_first_failure() {
+        let make_params_arc = Arc::new(Mutex::new(vec![]));
+        let command_factory = CommandFactoryMock::new()
+            .make_params(&make_params_arc)
+            .make_result(Err(CommandSyntax("booga".to_string())))
+            .make_result(Ok(make_command()))
+            .make_result(Ok(make_command()));
+        let mut processor = CommandProcessorMock::new()
+            .process_result(Err(Transmission("Booga!".to_string())))
+            .process_result(Ok(()));
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let subject = ScriptRunner::new(true, broadcast_rx);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "setup --booga\nstart\nshutdown",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, EXIT_CODE_COMMAND_SYNTAX);
+        assert_eq!(make_params_arc.lock().unwrap().len(), 3);
+        assert_eq!(
+            stderr.get_string(),
+            "booga\nScript line 1 failed: setup --booga\n\
+             Transmission problem: Booga!\nScript line 2 failed: start\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn run_waits_for_a_matching_broadcast_before_continuing() {
+        let make_params_arc = Arc::new(Mutex::new(vec![]));
+        let command_factory = CommandFactoryMock::new()
+            .make_params(&make_params_arc)
+            .make_result(Ok(make_command()))
+            .make_result(Ok(make_command()));
+        let mut processor = CommandProcessorMock::new()
+            .process_result(Ok(()))
+            .process_result(Ok(()));
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        broadcast_tx
+            .send(connection_change(UiConnectionStage::ConnectedToNeighbor))
+            .unwrap();
+        broadcast_tx
+            .send(connection_change(UiConnectionStage::RouteFound))
+            .unwrap();
+        let subject = ScriptRunner::new(false, broadcast_rx);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "start\nwait-for connectionChange RouteFound --timeout 1\nconnection-status",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, 0);
+        assert_eq!(make_params_arc.lock().unwrap().len(), 2);
+        assert_eq!(stderr.get_string(), "".to_string());
+    }
+
+    #[test]
+    fn run_gives_up_waiting_after_the_timeout() {
+        let command_factory = CommandFactoryMock::new();
+        let mut processor = CommandProcessorMock::new();
+        let (broadcast_tx, broadcast_rx) = unbounded();
+        broadcast_tx
+            .send(connection_change(UiConnectionStage::ConnectedToNeighbor))
+            .unwrap();
+        let subject = ScriptRunner::new(false, broadcast_rx);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "wait-for connectionChange RouteFound --timeout 0",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, EXIT_CODE_TRANSMISSION_PROBLEM);
+        assert_eq!(
+            stderr.get_string(),
+            "Reception problem: No 'connectionChange' broadcast with 'RouteFound' arrived within 0 seconds\n\
+             Script line 1 failed: wait-for connectionChange RouteFound --timeout 0\n"
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn run_reports_a_lost_connection_while_waiting() {
+        let command_factory = CommandFactoryMock::new();
+        let mut processor = CommandProcessorMock::new();
+        let (broadcast_tx, broadcast_rx) = unbounded::<MessageBody>();
+        drop(broadcast_tx);
+        let subject = ScriptRunner::new(false, broadcast_rx);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "wait-for setup",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, EXIT_CODE_CONNECTION_PROBLEM);
+    }
+
+    #[test]
+    fn run_reports_script_errors_as_json_in_json_mode() {
+        let command_factory = CommandFactoryMock::new();
+        let mut processor = CommandProcessorMock::new().output_format_result(OutputFormat::Json);
+        let (_broadcast_tx, broadcast_rx) = unbounded();
+        let mut subject = ScriptRunner::new(false, broadcast_rx);
+        subject.variable_lookup = Box::new(lookup);
+        let mut stderr = ByteArrayWriter::new();
+
+        let result = subject.run(
+            "start $BOOGA",
+            &command_factory,
+            &mut processor,
+            &mut stderr,
+        );
+
+        assert_eq!(result, EXIT_CODE_COMMAND_SYNTAX);
+        assert_eq!(
+            stderr.get_string(),
+            "{\"error\":{\"kind\":\"scriptSyntax\",\"message\":\"Environment variable 'BOOGA' is not set\"}}\n"
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use clap::App;
+use linefeed::{Completer, Completion, Prompter, Terminal};
+
+//words handled by the interactive loop itself rather than by a clap subcommand
+const INTERACTIVE_ONLY_COMMANDS: &[&str] = &["exit", "help", "version"];
+
+struct OptionCandidates {
+    long: String,
+    takes_value: bool,
+    possible_values: Vec<String>,
+}
+
+struct CommandCandidates {
+    name: String,
+    options: Vec<OptionCandidates>,
+}
+
+//the candidates are read out of the clap definitions once, so a new subcommand or parameter
+//is offered for completion as soon as it is added to the schema
+pub struct MasqCompleter {
+    commands: Vec<CommandCandidates>,
+}
+
+impl<Term: Terminal> Completer<Term> for MasqCompleter {
+    fn complete(
+        &self,
+        word: &str,
+        prompter: &Prompter<Term>,
+        start: usize,
+        _end: usize,
+    ) -> Option<Vec<Completion>> {
+        let preceding = &prompter.buffer()[..start];
+        Some(
+            self.candidates(preceding, word)
+                .into_iter()
+                .map(Completion::simple)
+                .collect(),
+        )
+    }
+}
+
+impl MasqCompleter {
+    pub fn new(app: &App) -> Self {
+        let mut commands: Vec<CommandCandidates> = app
+            .p
+            .subcommands
+            .iter()
+            .map(|subcommand| CommandCandidates {
+                name: subcommand.p.meta.name.clone(),
+                options: Self::options_of(subcommand),
+            })
+            .collect();
+        commands.extend(
+            INTERACTIVE_ONLY_COMMANDS
+                .iter()
+                .map(|name| CommandCandidates {
+                    name: name.to_string(),
+                    options: vec![],
+                }),
+        );
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        Self { commands }
+    }
+
+    pub fn candidates(&self, preceding: &str, word: &str) -> Vec<String> {
+        let words_before: Vec<&str> = preceding.split_whitespace().collect();
+        let command = match words_before.first() {
+            None => return self.command_names_starting_with(word),
+            Some(command) => *command,
+        };
+        if command == "help" {
+            return if words_before.len() == 1 {
+                self.command_names_starting_with(word)
+            } else {
+                vec![]
+            };
+        }
+        let options = match self.commands.iter().find(|c| c.name == command) {
+            Some(command_candidates) => &command_candidates.options,
+            None => return vec![],
+        };
+        let previous_option_opt = words_before
+            .last()
+            .and_then(|previous| previous.strip_prefix("--"))
+            .and_then(|long| options.iter().find(|option| option.long == long));
+        match previous_option_opt {
+            Some(option) if option.takes_value && !word.starts_with('-') => {
+                Self::starting_with(option.possible_values.iter(), word)
+            }
+            _ if word.is_empty() || word.starts_with('-') => {
+                let unused_options = options
+                    .iter()
+                    .map(|option| format!("--{}", option.long))
+                    .filter(|long| !words_before.contains(&long.as_str()))
+                    .collect::<Vec<String>>();
+                Self::starting_with(unused_options.iter(), word)
+            }
+            _ => vec![],
+        }
+    }
+
+    fn options_of(subcommand: &App) -> Vec<OptionCandidates> {
+        let mut options: Vec<OptionCandidates> = subcommand
+            .p
+            .opts
+            .iter()
+            .filter_map(|opt| {
+                opt.s.long.map(|long| OptionCandidates {
+                    long: long.to_string(),
+                    takes_value: true,
+                    possible_values: opt
+                        .v
+                        .possible_vals
+                        .as_ref()
+                        .map(|values| values.iter().map(|value| value.to_string()).collect())
+                        .unwrap_or_default(),
+                })
+            })
+            .chain(subcommand.p.flags.iter().filter_map(|flag| {
+                flag.s.long.map(|long| OptionCandidates {
+                    long: long.to_string(),
+                    takes_value: false,
+                    possible_values: vec![],
+                })
+            }))
+            .filter(|option| option.long != "help" && option.long != "version")
+            .collect();
+        options.sort_by(|a, b| a.long.cmp(&b.long));
+        options
+    }
+
+    fn command_names_starting_with(&self, word: &str) -> Vec<String> {
+        Self::starting_with(self.commands.iter().map(|c| &c.name), word)
+    }
+
+    fn starting_with<'a>(candidates: impl Iterator<Item = &'a String>, word: &str) -> Vec<String> {
+        candidates
+            .filter(|candidate| candidate.starts_with(word))
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::app;
+    use clap::{Arg, SubCommand};
+
+    fn make_subject() -> MasqCompleter {
+        let app = App::new("test")
+            .subcommand(
+                SubCommand::with_name("setup")
+                    .arg(
+                        Arg::with_name("chain")
+                            .long("chain")
+                            .takes_value(true)
+                            .possible_values(&["eth-mainnet", "polygon-mainnet"]),
+                    )
+                    .arg(
+                        Arg::with_name("neighborhood-mode")
+                            .long("neighborhood-mode")
+                            .takes_value(true)
+                            .possible_values(&["standard", "zero-hop"]),
+                    )
+                    .arg(
+                        Arg::with_name("data-directory")
+                            .long("data-directory")
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(SubCommand::with_name("shutdown"))
+            .subcommand(
+                SubCommand::with_name("scan").arg(Arg::with_name("payables").long("payables")),
+            );
+        MasqCompleter::new(&app)
+    }
+
+    #[test]
+    fn command_names_are_completed_at_the_start_of_the_line() {
+        let subject = make_subject();
+
+        assert_eq!(
+            subject.candidates("", "s"),
+            vec![
+                "scan".to_string(),
+                "setup".to_string(),
+                "shutdown".to_string()
+            ]
+        );
+        assert_eq!(subject.candidates("  ", "ex"), vec!["exit".to_string()]);
+    }
+
+    #[test]
+    fn help_is_followed_by_command_names() {
+        let subject = make_subject();
+
+        assert_eq!(subject.candidates("help ", "se"), vec!["setup".to_string()]);
+        assert_eq!(subject.candidates("help setup ", ""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unused_parameters_of_the_command_are_completed() {
+        let subject = make_subject();
+
+        assert_eq!(
+            subject.candidates("setup --chain eth-mainnet ", ""),
+            vec![
+                "--data-directory".to_string(),
+                "--neighborhood-mode".to_string()
+            ]
+        );
+        assert_eq!(
+            subject.candidates("setup ", "--n"),
+            vec!["--neighborhood-mode".to_string()]
+        );
+        assert_eq!(
+            subject.candidates("scan ", "--"),
+            vec!["--payables".to_string()]
+        );
+        assert_eq!(
+            subject.candidates("scan --payables ", ""),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn possible_values_are_completed_after_their_parameter() {
+        let subject = make_subject();
+
+        assert_eq!(
+            subject.candidates("setup --chain ", "pol"),
+            vec!["polygon-mainnet".to_string()]
+        );
+        assert_eq!(
+            subject.candidates("setup --neighborhood-mode ", ""),
+            vec!["standard".to_string(), "zero-hop".to_string()]
+        );
+    }
+
+    #[test]
+    fn free_values_and_unknown_commands_get_no_candidates() {
+        let subject = make_subject();
+
+        assert_eq!(
+            subject.candidates("setup --data-directory ", ""),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            subject.candidates("setup --data-directory ", "/ho"),
+            Vec::<String>::new()
+        );
+        assert_eq!(subject.candidates("booga ", "--"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn real_schema_offers_chain_names_and_setup_parameters() {
+        let subject = MasqCompleter::new(&app());
+
+        assert!(subject
+            .candidates("", "")
+            .contains(&"set-configuration".to_string()));
+        assert!(subject
+            .candidates("setup ", "--")
+            .contains(&"--neighborhood-mode".to_string()));
+        assert!(subject
+            .candidates("setup --chain ", "")
+            .contains(&"polygon-mainnet".to_string()));
+        assert!(subject
+            .candidates("set-configuration --log-level ", "")
+            .contains(&"debug".to_string()));
+    }
+}
@@ -1,13 +1,18 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::schema::app;
 use crate::terminal::secondary_infrastructure::{InterfaceWrapper, MasqTerminal, WriterLock};
+use clap::App;
+use lazy_static::lazy_static;
 use linefeed::{ReadResult, Signal};
 use masq_lib::command::StdStreams;
 use masq_lib::constants::MASQ_PROMPT;
 use masq_lib::short_writeln;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt::Debug;
 use std::io::Write;
+use std::path::PathBuf;
 
 //most of the events depend on the default linefeed signal handlers which ignore them unless you explicitly set the opposite
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -19,8 +24,59 @@ pub enum TerminalEvent {
     EoF,
 }
 
+//the history outlives the session in a file, so lines that carry passwords, mnemonic phrases or keys
+//stay out of it altogether: any line with a secret flag, or one handing a command its secret
+//positionally. The secret parameters are read out of the clap definitions, so a new one is kept
+//out as soon as it is added to the schema
+const SECRET_NAME_FRAGMENTS: [&str; 3] = ["password", "key", "phrase"];
+
+lazy_static! {
+    static ref SECRET_ARGS: SecretArgs = SecretArgs::new(&app());
+}
+
+struct SecretArgs {
+    flags: HashSet<String>,
+    positional_commands: HashSet<String>,
+}
+
+impl SecretArgs {
+    fn new(app: &App) -> Self {
+        let is_secret = |name: &str| {
+            SECRET_NAME_FRAGMENTS
+                .iter()
+                .any(|fragment| name.contains(fragment))
+        };
+        let flags = app
+            .p
+            .subcommands
+            .iter()
+            .flat_map(|subcommand| subcommand.p.opts.iter())
+            .filter(|opt| is_secret(opt.b.name))
+            .filter_map(|opt| opt.s.long.map(|long| format!("--{}", long)))
+            .collect();
+        let positional_commands = app
+            .p
+            .subcommands
+            .iter()
+            .filter(|subcommand| {
+                subcommand
+                    .p
+                    .positionals
+                    .values()
+                    .any(|positional| is_secret(positional.b.name))
+            })
+            .map(|subcommand| subcommand.p.meta.name.clone())
+            .collect();
+        Self {
+            flags,
+            positional_commands,
+        }
+    }
+}
+
 pub struct TerminalReal {
     interface: Box<dyn InterfaceWrapper>,
+    history_file_opt: Option<PathBuf>,
 }
 
 impl MasqTerminal for TerminalReal {
@@ -80,16 +136,35 @@ impl MasqTerminal for TerminalReal {
 }
 
 impl TerminalReal {
-    pub fn new(interface: Box<dyn InterfaceWrapper>) -> Self {
-        Self { interface }
+    pub fn new(interface: Box<dyn InterfaceWrapper>, history_file_opt: Option<PathBuf>) -> Self {
+        Self {
+            interface,
+            history_file_opt,
+        }
     }
 
     fn process_command_line(&self, line: String) -> TerminalEvent {
-        self.add_history(line.clone());
-        let args = split_quoted_line(line);
+        let args = split_quoted_line(line.clone());
+        if !Self::carries_secrets(&args) {
+            self.add_history(line);
+        }
         TerminalEvent::CommandLine(args)
     }
 
+    fn carries_secrets(args: &[String]) -> bool {
+        let is_positional_secret = match args.split_first() {
+            Some((command, rest)) => {
+                SECRET_ARGS.positional_commands.contains(command) && !rest.is_empty()
+            }
+            None => false,
+        };
+        is_positional_secret
+            || args.iter().any(|arg| {
+                let flag = arg.split('=').next().expect("split always yields a piece");
+                SECRET_ARGS.flags.contains(flag)
+            })
+    }
+
     fn make_prompt_vanish(&self) {
         self.interface
             .set_prompt("")
@@ -100,7 +175,11 @@ impl TerminalReal {
     }
 
     fn add_history(&self, line: String) {
-        self.interface.add_history(line)
+        self.interface.add_history(line);
+        if let Some(history_file) = self.history_file_opt.as_ref() {
+            //a history that can't be saved mustn't disturb the user's work; this session keeps it anyway
+            let _ = self.interface.save_history(history_file);
+        }
     }
 
     fn dispatch_error_msg<E: Error>(error: E) -> TerminalEvent {
@@ -108,7 +187,7 @@ impl TerminalReal {
     }
 }
 
-fn split_quoted_line(input: String) -> Vec<String> {
+pub(crate) fn split_quoted_line(input: String) -> Vec<String> {
     let mut active_single = false;
     let mut active_double = false;
     let mut pieces: Vec<String> = vec![];
@@ -147,9 +226,12 @@ mod tests {
 
     #[test]
     fn read_line_works_when_signal_interrupted_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Break))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Break))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -158,9 +240,12 @@ mod tests {
 
     #[test]
     fn read_line_works_when_signal_break_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Interrupt))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Interrupt))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -170,11 +255,14 @@ mod tests {
     #[test]
     fn read_line_works_when_a_valid_string_comes_from_the_command_line() {
         let add_history_unique_params_arc = Arc::new(Mutex::new(vec![]));
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new()
-                .read_line_result(Ok(ReadResult::Input("setup --ip 4.4.4.4".to_string())))
-                .add_history_unique_params(&add_history_unique_params_arc),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new()
+                    .read_line_result(Ok(ReadResult::Input("setup --ip 4.4.4.4".to_string())))
+                    .add_history_unique_params(&add_history_unique_params_arc),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -194,11 +282,145 @@ mod tests {
         )
     }
 
+    #[test]
+    fn command_line_is_saved_into_the_history_file_if_there_is_one() {
+        let add_history_unique_params_arc = Arc::new(Mutex::new(vec![]));
+        let save_history_params_arc = Arc::new(Mutex::new(vec![]));
+        let history_file = PathBuf::from("/home/booga/.local/share/MASQ/masq_history");
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new()
+                    .read_line_result(Ok(ReadResult::Input("descriptor".to_string())))
+                    .read_line_result(Ok(ReadResult::Input("shutdown".to_string())))
+                    .add_history_unique_params(&add_history_unique_params_arc)
+                    .save_history_params(&save_history_params_arc)
+                    .save_history_result(Ok(()))
+                    .save_history_result(Err(std::io::Error::from(ErrorKind::PermissionDenied))),
+            ),
+            Some(history_file.clone()),
+        );
+
+        let first_result = subject.read_line();
+        let second_result = subject.read_line();
+
+        assert_eq!(
+            first_result,
+            TerminalEvent::CommandLine(vec!["descriptor".to_string()])
+        );
+        assert_eq!(
+            second_result,
+            TerminalEvent::CommandLine(vec!["shutdown".to_string()])
+        );
+        let add_history_unique_params = add_history_unique_params_arc.lock().unwrap();
+        assert_eq!(
+            *add_history_unique_params,
+            vec!["descriptor".to_string(), "shutdown".to_string()]
+        );
+        let save_history_params = save_history_params_arc.lock().unwrap();
+        assert_eq!(
+            *save_history_params,
+            vec![history_file.clone(), history_file]
+        );
+    }
+
+    #[test]
+    fn secret_args_are_read_out_of_the_schema() {
+        let flags = [
+            "--db-password",
+            "--mnemonic-phrase",
+            "--passphrase",
+            "--consuming-key",
+            "--consuming-private-key",
+        ];
+        flags
+            .iter()
+            .for_each(|flag| assert!(SECRET_ARGS.flags.contains(*flag), "{}", flag));
+        assert!(!SECRET_ARGS.flags.contains("--chain"));
+        let positional_commands = [
+            "set-password",
+            "change-password",
+            "check-password",
+            "configuration",
+            "wallet-addresses",
+        ];
+        positional_commands.iter().for_each(|command| {
+            assert!(
+                SECRET_ARGS.positional_commands.contains(*command),
+                "{}",
+                command
+            )
+        });
+        assert_eq!(
+            TerminalReal::carries_secrets(&["configuration".to_string()]),
+            false
+        );
+    }
+
+    #[test]
+    fn command_lines_carrying_secrets_stay_out_of_the_history() {
+        let add_history_unique_params_arc = Arc::new(Mutex::new(vec![]));
+        let save_history_params_arc = Arc::new(Mutex::new(vec![]));
+        let secret_lines = vec![
+            "set-password booga",
+            "change-password booga wooga",
+            "check-password booga",
+            "setup --chain polygon-mainnet --db-password booga",
+            "setup --db-password=booga",
+            "recover-wallets --db-password booga --mnemonic-phrase \"one two three\"",
+            "discover-wallets --mnemonic-phrase \"one two three\" --passphrase wooga",
+            "recover-wallets --db-password booga --consuming-key 0123456789abcdef",
+            "setup --consuming-private-key 0123456789abcdef",
+            "setup --chain polygon-mainnet --consuming-private-key=0123456789abcdef",
+            "configuration booga",
+            "wallet-addresses booga",
+        ];
+        let interface = secret_lines.iter().fold(
+            InterfaceRawMock::new()
+                .add_history_unique_params(&add_history_unique_params_arc)
+                .save_history_params(&save_history_params_arc)
+                .save_history_result(Ok(())),
+            |interface, line| interface.read_line_result(Ok(ReadResult::Input(line.to_string()))),
+        );
+        let subject = TerminalReal::new(
+            Box::new(interface.read_line_result(Ok(ReadResult::Input(
+                "setup --chain polygon-mainnet".to_string(),
+            )))),
+            Some(PathBuf::from("/home/booga/.local/share/MASQ/masq_history")),
+        );
+
+        secret_lines.iter().for_each(|line| {
+            assert_eq!(
+                subject.read_line(),
+                TerminalEvent::CommandLine(split_quoted_line(line.to_string()))
+            )
+        });
+        let last_result = subject.read_line();
+
+        assert_eq!(
+            last_result,
+            TerminalEvent::CommandLine(vec![
+                "setup".to_string(),
+                "--chain".to_string(),
+                "polygon-mainnet".to_string()
+            ])
+        );
+        let add_history_unique_params = add_history_unique_params_arc.lock().unwrap();
+        assert_eq!(
+            *add_history_unique_params,
+            vec!["setup --chain polygon-mainnet".to_string()]
+        );
+        let save_history_params = save_history_params_arc.lock().unwrap();
+        assert_eq!(save_history_params.len(), 1);
+    }
+
     #[test]
     fn read_line_works_when_signal_quit_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Quit))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Quit))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -207,9 +429,12 @@ mod tests {
 
     #[test]
     fn read_line_works_when_signal_suspend_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Suspend))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Suspend))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -218,9 +443,12 @@ mod tests {
 
     #[test]
     fn read_line_works_when_signal_continue_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Continue))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Continue))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -229,9 +457,12 @@ mod tests {
 
     #[test]
     fn read_line_works_when_signal_resize_is_hit() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Resize))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new().read_line_result(Ok(ReadResult::Signal(Signal::Resize))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -240,10 +471,13 @@ mod tests {
 
     #[test]
     fn read_line_receives_an_error_and_sends_it_forward() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new()
-                .read_line_result(Err(std::io::Error::from(ErrorKind::InvalidInput))),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(
+                InterfaceRawMock::new()
+                    .read_line_result(Err(std::io::Error::from(ErrorKind::InvalidInput))),
+            ),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -257,9 +491,10 @@ mod tests {
 
     #[test]
     fn read_line_responds_well_to_end_of_file() {
-        let subject = TerminalReal::new(Box::new(
-            InterfaceRawMock::new().read_line_result(Ok(ReadResult::Eof)),
-        ));
+        let subject = TerminalReal::new(
+            Box::new(InterfaceRawMock::new().read_line_result(Ok(ReadResult::Eof))),
+            None,
+        );
 
         let result = subject.read_line();
 
@@ -278,7 +513,7 @@ mod tests {
             .set_prompt_result(Ok(()))
             .get_buffer_result("my once opened writing".to_string())
             .lock_writer_append_result(Ok(Box::new(WriterInactive {})));
-        let subject = TerminalReal::new(Box::new(terminal));
+        let subject = TerminalReal::new(Box::new(terminal), None);
         let mut streams_holder = FakeStreamHolder::default();
         let mut streams = streams_holder.streams();
 
@@ -302,7 +537,7 @@ mod tests {
             .set_buffer_result(Ok(()))
             .get_buffer_result("my once opened writing".to_string())
             .lock_writer_append_result(Ok(Box::new(WriterInactive {})));
-        let subject = TerminalReal::new(Box::new(terminal));
+        let subject = TerminalReal::new(Box::new(terminal), None);
         let mut streams_holder = FakeStreamHolder::default();
         let mut streams = streams_holder.streams();
 
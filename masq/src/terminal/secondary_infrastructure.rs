@@ -1,8 +1,11 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::terminal::completion::MasqCompleter;
 use crate::terminal::line_reader::TerminalEvent;
 use linefeed::{Interface, ReadResult, Signal, Writer};
 use masq_lib::command::StdStreams;
+use std::path::Path;
+use std::sync::Arc;
 
 #[cfg(test)]
 mod test_cfg {
@@ -56,6 +59,9 @@ pub trait InterfaceWrapper: Send + Sync {
     fn set_buffer(&self, text: &str) -> std::io::Result<()>;
     fn set_prompt(&self, prompt: &str) -> std::io::Result<()>;
     fn set_report_signal(&self, signal: Signal, set: bool);
+    fn set_completer(&self, completer: Arc<MasqCompleter>);
+    fn load_history(&self, path: &Path) -> std::io::Result<()>;
+    fn save_history(&self, path: &Path) -> std::io::Result<()>;
 }
 
 impl<U: linefeed::Terminal> InterfaceWrapper for Interface<U> {
@@ -91,6 +97,38 @@ impl<U: linefeed::Terminal> InterfaceWrapper for Interface<U> {
     fn set_report_signal(&self, signal: Signal, set: bool) {
         self.set_report_signal(signal, set)
     }
+
+    fn set_completer(&self, completer: Arc<MasqCompleter>) {
+        self.set_completer(completer);
+    }
+
+    fn load_history(&self, path: &Path) -> std::io::Result<()> {
+        self.load_history(path)
+    }
+
+    fn save_history(&self, path: &Path) -> std::io::Result<()> {
+        restrict_to_owner(path)?;
+        self.save_history(path)
+    }
+}
+
+//linefeed creates the history file with default permissions, which would let other users read the commands;
+//so we create it beforehand, or tighten it if an older version left it readable, and linefeed keeps the mode
+#[cfg(not(target_os = "windows"))]
+fn restrict_to_owner(path: &Path) -> std::io::Result<()> {
+    use std::fs::{OpenOptions, Permissions};
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(0o600)
+        .open(path)?;
+    std::fs::set_permissions(path, Permissions::from_mode(0o600))
+}
+
+#[cfg(target_os = "windows")]
+fn restrict_to_owner(_path: &Path) -> std::io::Result<()> {
+    Ok(())
 }
 
 pub trait ChainedConstructors {
@@ -108,3 +146,45 @@ pub trait ChainedConstructors {
 }
 
 impl<T: linefeed::Terminal> ChainedConstructors for T {}
+
+#[cfg(all(test, not(target_os = "windows")))]
+mod tests {
+    use super::*;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn restrict_to_owner_creates_a_missing_history_file_readable_only_by_its_owner() {
+        let home_dir = ensure_node_home_directory_exists(
+            "secondary_infrastructure",
+            "restrict_to_owner_creates_a_missing_history_file_readable_only_by_its_owner",
+        );
+        let history_file = home_dir.join("masq_history");
+
+        restrict_to_owner(&history_file).unwrap();
+
+        let metadata = std::fs::metadata(&history_file).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(metadata.len(), 0);
+    }
+
+    #[test]
+    fn restrict_to_owner_tightens_an_existing_history_file_and_keeps_its_content() {
+        let home_dir = ensure_node_home_directory_exists(
+            "secondary_infrastructure",
+            "restrict_to_owner_tightens_an_existing_history_file_and_keeps_its_content",
+        );
+        let history_file = home_dir.join("masq_history");
+        std::fs::write(&history_file, "descriptor\n").unwrap();
+        std::fs::set_permissions(&history_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        restrict_to_owner(&history_file).unwrap();
+
+        let metadata = std::fs::metadata(&history_file).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(
+            std::fs::read_to_string(&history_file).unwrap(),
+            "descriptor\n"
+        );
+    }
+}
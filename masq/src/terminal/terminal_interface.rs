@@ -1,5 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::schema::app;
+use crate::terminal::completion::MasqCompleter;
 use crate::terminal::line_reader::{TerminalEvent, TerminalReal};
 use crate::terminal::secondary_infrastructure::{
     ChainedConstructors, InterfaceWrapper, MasqTerminal, WriterLock,
@@ -7,6 +9,7 @@ use crate::terminal::secondary_infrastructure::{
 use linefeed::{Interface, Signal};
 use masq_lib::command::StdStreams;
 use masq_lib::constants::MASQ_PROMPT;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 #[cfg(not(test))]
@@ -69,12 +72,16 @@ impl TerminalWrapper {
             )))
         } else {
             //we have no positive test aimed at this (only negative and as an integration test)
-            Self::configure_interface_generic(Box::new(prod_cfg::DefaultTerminal::new))
+            Self::configure_interface_generic(
+                Box::new(prod_cfg::DefaultTerminal::new),
+                history_file_path(),
+            )
         }
     }
 
     fn configure_interface_generic<F, TerminalType>(
         terminal_creator_of_certain_type: Box<F>,
+        history_file_opt: Option<PathBuf>,
     ) -> Result<Self, String>
     where
         F: FnOnce() -> std::io::Result<TerminalType>,
@@ -83,16 +90,27 @@ impl TerminalWrapper {
         Ok(Self::new(Arc::new(interface_configurator(
             terminal_creator_of_certain_type,
             Box::new(Interface::with_term),
+            history_file_opt,
         )?)))
     }
 }
 
+//the history lives next to the Nodes' data directories, shared by all chains;
+//None if the platform has no such place or it can't be created
+#[cfg(not(test))]
+fn history_file_path() -> Option<PathBuf> {
+    let masq_dir = dirs::data_local_dir()?.join("MASQ");
+    std::fs::create_dir_all(&masq_dir).ok()?;
+    Some(masq_dir.join("masq_history"))
+}
+
 //so to say skeleton which accepts injections of closures where I can exactly say how these mocked, injected
 //constructors shall behave and what it shall produce
 
 fn interface_configurator<Term, Itf, TermConstructor, ItfConstructor>(
     construct_typed_terminal: Box<TermConstructor>,
     construct_interface: Box<ItfConstructor>,
+    history_file_opt: Option<PathBuf>,
 ) -> Result<TerminalReal, String>
 where
     TermConstructor: FnOnce() -> std::io::Result<Term>,
@@ -108,7 +126,12 @@ where
 
     set_all_settable_parameters(interface.as_mut())?;
 
-    Ok(TerminalReal::new(interface))
+    if let Some(history_file) = history_file_opt.as_ref() {
+        //no history has been saved yet on the very first run
+        let _ = interface.load_history(history_file);
+    }
+
+    Ok(TerminalReal::new(interface, history_file_opt))
 }
 
 fn set_all_settable_parameters<I>(interface: &mut I) -> Result<(), String>
@@ -122,8 +145,7 @@ where
     //according to linefeed's docs we await no failure here
     interface.set_report_signal(Signal::Interrupt, true);
 
-    //here we can add another parameter to be configured,
-    //such as "completer" (see linefeed library)
+    interface.set_completer(Arc::new(MasqCompleter::new(&app())));
 
     Ok(())
 }
@@ -131,7 +153,10 @@ where
 #[cfg(test)]
 impl TerminalWrapper {
     pub fn configure_interface() -> Result<Self, String> {
-        Self::configure_interface_generic(Box::new(Self::result_wrapper_for_in_memory_terminal))
+        Self::configure_interface_generic(
+            Box::new(Self::result_wrapper_for_in_memory_terminal),
+            None,
+        )
     }
 
     pub fn result_wrapper_for_in_memory_terminal() -> std::io::Result<test_cfg::MemoryTerminal> {
@@ -244,6 +269,7 @@ mod tests {
         let result = interface_configurator(
             Box::new(constructor_of_default_terminal_resulting_in_immediate_error),
             Box::new(Interface::with_term),
+            None,
         );
 
         let err_message = if let Err(e) = result {
@@ -272,8 +298,11 @@ mod tests {
         let terminal_type =
             move || -> std::io::Result<test_cfg::MemoryTerminal> { Ok(term_mock_clone) };
 
-        let result =
-            interface_configurator(Box::new(terminal_type), Box::new(Interface::with_term));
+        let result = interface_configurator(
+            Box::new(terminal_type),
+            Box::new(Interface::with_term),
+            None,
+        );
 
         assert!(result.is_ok())
     }
@@ -283,6 +312,7 @@ mod tests {
         let result = interface_configurator(
             Box::new(TerminalWrapper::result_wrapper_for_in_memory_terminal),
             Box::new(constructor_of_interface_raw_resulting_in_early_error),
+            None,
         );
 
         let err_message = if let Err(e) = result {
@@ -317,6 +347,7 @@ mod tests {
                     .set_prompt_params(&set_prompt_params_arc)
                     .set_prompt_result(Err(Error::from_raw_os_error(10))))
             }),
+            None,
         );
 
         let err_message = if let Err(e) = result {
@@ -343,10 +374,39 @@ mod tests {
                     .set_report_signal_params(&set_report_signal_arc)
                     .set_prompt_result(Ok(())))
             }),
+            None,
         );
 
         assert!(result.is_ok());
         let set_report_signal = set_report_signal_arc.lock().unwrap();
         assert_eq!(*set_report_signal, vec![(Signal::Interrupt, true)])
     }
+
+    #[test]
+    fn configure_interface_sets_completer_and_loads_history() {
+        let set_completer_params_arc = Arc::new(Mutex::new(vec![]));
+        let load_history_params_arc = Arc::new(Mutex::new(vec![]));
+        let history_file = PathBuf::from("/home/booga/.local/share/MASQ/masq_history");
+
+        let result = interface_configurator(
+            Box::new(TerminalWrapper::result_wrapper_for_in_memory_terminal),
+            Box::new(|_name, _terminal| {
+                Ok(InterfaceRawMock::new()
+                    .set_prompt_result(Ok(()))
+                    .set_completer_params(&set_completer_params_arc)
+                    .load_history_params(&load_history_params_arc)
+                    .load_history_result(Err(Error::from(std::io::ErrorKind::NotFound))))
+            }),
+            Some(history_file.clone()),
+        );
+
+        assert!(result.is_ok());
+        let set_completer_params = set_completer_params_arc.lock().unwrap();
+        assert_eq!(
+            set_completer_params[0].candidates("", "set-c"),
+            vec!["set-configuration".to_string()]
+        );
+        let load_history_params = load_history_params_arc.lock().unwrap();
+        assert_eq!(*load_history_params, vec![history_file])
+    }
 }
@@ -1,12 +1,13 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
-use crate::command_context::{CommandContext, ContextError};
+use crate::command_context::{CommandContext, ContextError, OutputFormat};
 use crate::command_factory::{CommandFactory, CommandFactoryError};
 use crate::command_processor::{CommandProcessor, CommandProcessorFactory};
 use crate::commands::commands_common::CommandError::Transmission;
 use crate::commands::commands_common::{Command, CommandError};
 use crate::communications::broadcast_handler::{BroadcastHandle, StreamFactory};
-use crate::non_interactive_clap::{NIClapFactory, NonInteractiveClap};
+use crate::non_interactive_clap::{InitialArgs, NIClapFactory, NonInteractiveClap};
+use crate::terminal::completion::MasqCompleter;
 use crate::terminal::line_reader::TerminalEvent;
 use crate::terminal::secondary_infrastructure::{InterfaceWrapper, MasqTerminal, WriterLock};
 use crate::terminal::terminal_interface::TerminalWrapper;
@@ -20,6 +21,7 @@ use masq_lib::ui_gateway::MessageBody;
 use std::cell::RefCell;
 use std::fmt::Arguments;
 use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{io, thread};
@@ -66,6 +68,7 @@ pub struct CommandContextMock {
     stdout_arc: Arc<Mutex<ByteArrayWriterInner>>,
     stderr: Box<dyn Write>,
     stderr_arc: Arc<Mutex<ByteArrayWriterInner>>,
+    broadcast_rx_opt: Option<Receiver<MessageBody>>,
 }
 
 impl CommandContext for CommandContextMock {
@@ -102,6 +105,10 @@ impl CommandContext for CommandContextMock {
         &mut self.stderr
     }
 
+    fn broadcast_receiver_opt(&self) -> Option<Receiver<MessageBody>> {
+        self.broadcast_rx_opt.clone()
+    }
+
     fn close(&mut self) {
         unimplemented!()
     }
@@ -123,6 +130,7 @@ impl Default for CommandContextMock {
             stdout_arc,
             stderr: Box::new(stderr),
             stderr_arc,
+            broadcast_rx_opt: None,
         }
     }
 }
@@ -157,6 +165,11 @@ impl CommandContextMock {
         self
     }
 
+    pub fn broadcast_receiver(mut self, broadcast_rx: Receiver<MessageBody>) -> Self {
+        self.broadcast_rx_opt = Some(broadcast_rx);
+        self
+    }
+
     pub fn stdout_arc(&self) -> Arc<Mutex<ByteArrayWriterInner>> {
         self.stdout_arc.clone()
     }
@@ -172,6 +185,7 @@ pub struct CommandProcessorMock {
     process_results: RefCell<Vec<Result<(), CommandError>>>,
     close_params: Arc<Mutex<Vec<()>>>,
     terminal_interface: Option<TerminalWrapper>,
+    output_format: OutputFormat,
 }
 
 impl CommandProcessor for CommandProcessorMock {
@@ -187,6 +201,10 @@ impl CommandProcessor for CommandProcessorMock {
     fn terminal_wrapper_ref(&self) -> &TerminalWrapper {
         self.terminal_interface.as_ref().unwrap()
     }
+
+    fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
 }
 
 impl CommandProcessorMock {
@@ -202,6 +220,11 @@ impl CommandProcessorMock {
         self
     }
 
+    pub fn output_format_result(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
     pub fn process_params(mut self, params: &Arc<Mutex<Vec<Box<dyn Command>>>>) -> Self {
         self.process_params = params.clone();
         self
@@ -218,9 +241,18 @@ impl CommandProcessorMock {
     }
 }
 
+pub type ProcessorFactoryMakeParams = (
+    Option<TerminalWrapper>,
+    Box<dyn BroadcastHandle>,
+    Option<Receiver<MessageBody>>,
+    u16,
+    Option<String>,
+    OutputFormat,
+);
+
 #[derive(Default)]
 pub struct CommandProcessorFactoryMock {
-    make_params: Arc<Mutex<Vec<(Option<TerminalWrapper>, Box<dyn BroadcastHandle>, u16)>>>,
+    make_params: Arc<Mutex<Vec<ProcessorFactoryMakeParams>>>,
     make_results: RefCell<Vec<Result<Box<dyn CommandProcessor>, CommandError>>>,
 }
 
@@ -229,12 +261,18 @@ impl CommandProcessorFactory for CommandProcessorFactoryMock {
         &self,
         terminal_interface: Option<TerminalWrapper>,
         generic_broadcast_handle: Box<dyn BroadcastHandle>,
+        broadcast_rx_opt: Option<Receiver<MessageBody>>,
         ui_port: u16,
+        instance_opt: Option<String>,
+        output_format: OutputFormat,
     ) -> Result<Box<dyn CommandProcessor>, CommandError> {
         self.make_params.lock().unwrap().push((
             terminal_interface,
             generic_broadcast_handle,
+            broadcast_rx_opt,
             ui_port,
+            instance_opt,
+            output_format,
         ));
         self.make_results.borrow_mut().remove(0)
     }
@@ -245,10 +283,7 @@ impl CommandProcessorFactoryMock {
         Self::default()
     }
 
-    pub fn make_params(
-        mut self,
-        params: &Arc<Mutex<Vec<(Option<TerminalWrapper>, Box<dyn BroadcastHandle>, u16)>>>,
-    ) -> Self {
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<ProcessorFactoryMakeParams>>>) -> Self {
         self.make_params = params.clone();
         self
     }
@@ -270,8 +305,14 @@ impl NIClapFactory for NIClapFactoryMock {
 pub struct NonInteractiveClapMock;
 
 impl NonInteractiveClap for NonInteractiveClapMock {
-    fn non_interactive_initial_clap_operations(&self, _args: &[String]) -> u16 {
-        DEFAULT_UI_PORT
+    fn non_interactive_initial_clap_operations(&self, _args: &[String]) -> InitialArgs {
+        InitialArgs {
+            ui_port: DEFAULT_UI_PORT,
+            instance_opt: None,
+            output_format: OutputFormat::Human,
+            script_opt: None,
+            keep_going: false,
+        }
     }
 }
 
@@ -605,6 +646,11 @@ pub struct InterfaceRawMock {
     set_buffer_params: Arc<Mutex<Vec<String>>>,
     set_buffer_results: Arc<Mutex<Vec<std::io::Result<()>>>>,
     lock_writer_append_results: Arc<Mutex<Vec<std::io::Result<Box<WriterInactive>>>>>, //for testing the outer result not the structure when ok
+    set_completer_params: Arc<Mutex<Vec<Arc<MasqCompleter>>>>,
+    load_history_params: Arc<Mutex<Vec<PathBuf>>>,
+    load_history_results: Arc<Mutex<Vec<std::io::Result<()>>>>,
+    save_history_params: Arc<Mutex<Vec<PathBuf>>>,
+    save_history_results: Arc<Mutex<Vec<std::io::Result<()>>>>,
 }
 
 impl InterfaceWrapper for InterfaceRawMock {
@@ -653,6 +699,26 @@ impl InterfaceWrapper for InterfaceRawMock {
             .unwrap()
             .push((signal, set))
     }
+
+    fn set_completer(&self, completer: Arc<MasqCompleter>) {
+        self.set_completer_params.lock().unwrap().push(completer)
+    }
+
+    fn load_history(&self, path: &Path) -> io::Result<()> {
+        self.load_history_params
+            .lock()
+            .unwrap()
+            .push(path.to_path_buf());
+        self.load_history_results.lock().unwrap().remove(0)
+    }
+
+    fn save_history(&self, path: &Path) -> io::Result<()> {
+        self.save_history_params
+            .lock()
+            .unwrap()
+            .push(path.to_path_buf());
+        self.save_history_results.lock().unwrap().remove(0)
+    }
 }
 
 impl InterfaceRawMock {
@@ -667,6 +733,11 @@ impl InterfaceRawMock {
             set_buffer_params: Arc::new(Mutex::new(vec![])),
             set_buffer_results: Arc::new(Mutex::new(vec![])),
             lock_writer_append_results: Arc::new(Mutex::new(vec![])),
+            set_completer_params: Arc::new(Mutex::new(vec![])),
+            load_history_params: Arc::new(Mutex::new(vec![])),
+            load_history_results: Arc::new(Mutex::new(vec![])),
+            save_history_params: Arc::new(Mutex::new(vec![])),
+            save_history_results: Arc::new(Mutex::new(vec![])),
         }
     }
     pub fn read_line_result(self, result: std::io::Result<ReadResult>) -> Self {
@@ -707,4 +778,29 @@ impl InterfaceRawMock {
         self.lock_writer_append_results.lock().unwrap().push(result);
         self
     }
+
+    pub fn set_completer_params(mut self, params: &Arc<Mutex<Vec<Arc<MasqCompleter>>>>) -> Self {
+        self.set_completer_params = params.clone();
+        self
+    }
+
+    pub fn load_history_params(mut self, params: &Arc<Mutex<Vec<PathBuf>>>) -> Self {
+        self.load_history_params = params.clone();
+        self
+    }
+
+    pub fn load_history_result(self, result: std::io::Result<()>) -> Self {
+        self.load_history_results.lock().unwrap().push(result);
+        self
+    }
+
+    pub fn save_history_params(mut self, params: &Arc<Mutex<Vec<PathBuf>>>) -> Self {
+        self.save_history_params = params.clone();
+        self
+    }
+
+    pub fn save_history_result(self, result: std::io::Result<()>) -> Self {
+        self.save_history_results.lock().unwrap().push(result);
+        self
+    }
 }
@@ -23,7 +23,7 @@ fn masq_without_daemon_integration() {
         "we got: {}",
         stderr
     );
-    assert_eq!(exit_code.unwrap(), 1);
+    assert_eq!(exit_code.unwrap(), 3);
 }
 
 #[test]
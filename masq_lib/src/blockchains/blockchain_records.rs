@@ -19,6 +19,9 @@ pub const CHAINS: [BlockchainRecord; 5] = [
         literal_identifier: POLYGON_MAINNET_FULL_IDENTIFIER,
         contract: POLYGON_MAINNET_CONTRACT_ADDRESS,
         contract_creation_block: POLYGON_MAINNET_CONTRACT_CREATION_BLOCK,
+        confirmation_depth: 128,
+        gas_limit_base: 70_000,
+        disperse_contract_opt: Some(DISPERSE_CONTRACT_ADDRESS),
     },
     BlockchainRecord {
         self_id: Chain::EthMainnet,
@@ -27,6 +30,9 @@ pub const CHAINS: [BlockchainRecord; 5] = [
         literal_identifier: ETH_MAINNET_FULL_IDENTIFIER,
         contract: ETH_MAINNET_CONTRACT_ADDRESS,
         contract_creation_block: ETH_MAINNET_CONTRACT_CREATION_BLOCK,
+        confirmation_depth: 12,
+        gas_limit_base: 55_000,
+        disperse_contract_opt: Some(DISPERSE_CONTRACT_ADDRESS),
     },
     BlockchainRecord {
         self_id: Chain::PolyMumbai,
@@ -35,6 +41,9 @@ pub const CHAINS: [BlockchainRecord; 5] = [
         literal_identifier: POLYGON_MUMBAI_FULL_IDENTIFIER,
         contract: MUMBAI_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: MUMBAI_TESTNET_CONTRACT_CREATION_BLOCK,
+        confirmation_depth: 64,
+        gas_limit_base: 70_000,
+        disperse_contract_opt: None,
     },
     BlockchainRecord {
         self_id: Chain::EthRopsten,
@@ -43,6 +52,9 @@ pub const CHAINS: [BlockchainRecord; 5] = [
         literal_identifier: ETH_ROPSTEN_FULL_IDENTIFIER,
         contract: ROPSTEN_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: ROPSTEN_TESTNET_CONTRACT_CREATION_BLOCK,
+        confirmation_depth: 12,
+        gas_limit_base: 55_000,
+        disperse_contract_opt: None,
     },
     BlockchainRecord {
         self_id: Chain::Dev,
@@ -51,6 +63,9 @@ pub const CHAINS: [BlockchainRecord; 5] = [
         literal_identifier: DEV_CHAIN_FULL_IDENTIFIER,
         contract: MULTINODE_TESTNET_CONTRACT_ADDRESS,
         contract_creation_block: MULTINODE_TESTNET_CONTRACT_CREATION_BLOCK,
+        confirmation_depth: 0,
+        gas_limit_base: 55_000,
+        disperse_contract_opt: None,
     },
 ];
 
@@ -62,8 +77,21 @@ pub struct BlockchainRecord {
     pub literal_identifier: &'static str,
     pub contract: Address,
     pub contract_creation_block: u64,
+    // number of blocks behind the chain head a log must be before it's treated as final
+    pub confirmation_depth: u64,
+    // the gas limit of a payment transaction before the per-byte cost of its data is added
+    pub gas_limit_base: u64,
+    // a contract able to pay several recipients in one transaction, where the chain has one
+    pub disperse_contract_opt: Option<Address>,
 }
 
+// Disperse.app, deployed at the same address on both mainnets
+#[allow(clippy::mixed_case_hex_literals)]
+const DISPERSE_CONTRACT_ADDRESS: Address = H160([
+    0xD1, 0x52, 0xf5, 0x49, 0x54, 0x50, 0x93, 0x34, 0x7A, 0x16, 0x2D, 0xce, 0x21, 0x0e, 0x72, 0x93,
+    0xf1, 0x45, 0x21, 0x50,
+]);
+
 // SHRD (Ropsten)
 const ROPSTEN_TESTNET_CONTRACT_ADDRESS: Address = H160([
     0x38, 0x4d, 0xec, 0x25, 0xe0, 0x3f, 0x94, 0x93, 0x17, 0x67, 0xce, 0x4c, 0x35, 0x56, 0x16, 0x84,
@@ -172,6 +200,9 @@ mod tests {
                 literal_identifier: "eth-mainnet",
                 contract: ETH_MAINNET_CONTRACT_ADDRESS,
                 contract_creation_block: ETH_MAINNET_CONTRACT_CREATION_BLOCK,
+                confirmation_depth: 12,
+                gas_limit_base: 55_000,
+                disperse_contract_opt: Some(DISPERSE_CONTRACT_ADDRESS),
                 chain_family: ChainFamily::Eth
             }
         )
@@ -189,6 +220,9 @@ mod tests {
                 literal_identifier: "dev",
                 contract: MULTINODE_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: 0,
+                confirmation_depth: 0,
+                gas_limit_base: 55_000,
+                disperse_contract_opt: None,
                 chain_family: ChainFamily::Dev
             }
         )
@@ -206,6 +240,9 @@ mod tests {
                 literal_identifier: "eth-ropsten",
                 contract: ROPSTEN_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: ROPSTEN_TESTNET_CONTRACT_CREATION_BLOCK,
+                confirmation_depth: 12,
+                gas_limit_base: 55_000,
+                disperse_contract_opt: None,
                 chain_family: ChainFamily::Eth
             }
         )
@@ -223,6 +260,9 @@ mod tests {
                 literal_identifier: "polygon-mainnet",
                 contract: POLYGON_MAINNET_CONTRACT_ADDRESS,
                 contract_creation_block: POLYGON_MAINNET_CONTRACT_CREATION_BLOCK,
+                confirmation_depth: 128,
+                gas_limit_base: 70_000,
+                disperse_contract_opt: Some(DISPERSE_CONTRACT_ADDRESS),
                 chain_family: ChainFamily::Polygon
             }
         )
@@ -240,6 +280,9 @@ mod tests {
                 literal_identifier: "polygon-mumbai",
                 contract: MUMBAI_TESTNET_CONTRACT_ADDRESS,
                 contract_creation_block: MUMBAI_TESTNET_CONTRACT_CREATION_BLOCK,
+                confirmation_depth: 64,
+                gas_limit_base: 70_000,
+                disperse_contract_opt: None,
                 chain_family: ChainFamily::Polygon
             }
         )
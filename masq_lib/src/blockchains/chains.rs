@@ -1,6 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::blockchains::blockchain_records::{BlockchainRecord, CHAINS};
+use crate::blockchains::custom_chains::{custom_chain_records, CUSTOM_CHAINS_FILE_ENV_VAR};
 use crate::constants::{
     DEFAULT_CHAIN, DEV_CHAIN_FULL_IDENTIFIER, ETH_MAINNET_FULL_IDENTIFIER,
     ETH_ROPSTEN_FULL_IDENTIFIER, POLYGON_MAINNET_FULL_IDENTIFIER, POLYGON_MUMBAI_FULL_IDENTIFIER,
@@ -14,6 +15,8 @@ pub enum Chain {
     PolyMainnet,
     PolyMumbai,
     Dev,
+    // defined by the operator at runtime; identified by its numeric chain id
+    Custom(u64),
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
@@ -41,6 +44,8 @@ impl From<&str> for Chain {
             Chain::EthRopsten
         } else if str == DEV_CHAIN_FULL_IDENTIFIER {
             Chain::Dev
+        } else if let Some(chain) = custom_chain_from_identifier_opt(str) {
+            chain
         } else {
             panic!("Clap let in a wrong value for chain: '{}'; if this happens we need to track down the slit", str)
         }
@@ -52,6 +57,11 @@ impl Chain {
         CHAINS
             .iter()
             .find(|b| &b.self_id == self)
+            .or_else(|| {
+                custom_chain_records()
+                    .into_iter()
+                    .find(|b| &b.self_id == self)
+            })
             .unwrap_or_else(|| panic!("BlockchainRecord for '{:?}' doesn't exist", self))
         //untested panic - but works as an expect()
     }
@@ -70,6 +80,25 @@ impl Chain {
 pub fn chain_from_chain_identifier_opt(identifier: &str) -> Option<Chain> {
     return_record_opt_standard_impl(&|b: &&BlockchainRecord| b.literal_identifier == identifier)
         .map(|record| record.self_id)
+        .or_else(|| custom_chain_from_identifier_opt(identifier))
+}
+
+// For chain names that didn't pass through clap, such as the one stored in the database; a custom
+// chain is only known if the file defining it was loaded at startup
+pub fn chain_from_chain_identifier(identifier: &str) -> Result<Chain, String> {
+    chain_from_chain_identifier_opt(identifier).ok_or_else(|| {
+        format!(
+            "Chain '{}' is unknown; if it's a custom chain, {} must name the file that defines it",
+            identifier, CUSTOM_CHAINS_FILE_ENV_VAR
+        )
+    })
+}
+
+fn custom_chain_from_identifier_opt(identifier: &str) -> Option<Chain> {
+    custom_chain_records()
+        .into_iter()
+        .find(|record| record.literal_identifier == identifier)
+        .map(|record| record.self_id)
 }
 
 fn return_record_opt_standard_impl(
@@ -136,6 +165,26 @@ mod tests {
         let _ = Chain::from("olala");
     }
 
+    #[test]
+    fn chain_from_chain_identifier_finds_official_chains() {
+        CHAINS.iter().for_each(|record| {
+            assert_eq!(
+                chain_from_chain_identifier(record.literal_identifier),
+                Ok(record.self_id)
+            )
+        });
+    }
+
+    #[test]
+    fn chain_from_chain_identifier_reports_unknown_chains_without_panicking() {
+        let result = chain_from_chain_identifier("unregistered-net");
+
+        assert_eq!(
+            result,
+            Err("Chain 'unregistered-net' is unknown; if it's a custom chain, MASQ_CHAINS_FILE must name the file that defines it".to_string())
+        )
+    }
+
     fn make_defaulted_blockchain_record<'a>() -> BlockchainRecord {
         BlockchainRecord {
             num_chain_id: 0,
@@ -143,6 +192,9 @@ mod tests {
             literal_identifier: "",
             contract: Default::default(),
             contract_creation_block: 0,
+            confirmation_depth: 0,
+            gas_limit_base: 0,
+            disperse_contract_opt: None,
             chain_family: ChainFamily::Polygon,
         }
     }
@@ -0,0 +1,429 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchains::blockchain_records::{BlockchainRecord, CHAINS};
+use crate::blockchains::chains::{Chain, ChainFamily};
+use ethereum_types::Address;
+#[cfg(not(test))]
+use lazy_static::lazy_static;
+use serde_derive::Deserialize;
+#[cfg(test)]
+use std::cell::RefCell;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+#[cfg(not(test))]
+use std::sync::RwLock;
+
+// Names the TOML file with chain definitions; Node, Daemon and masq all have to see the same file
+pub const CUSTOM_CHAINS_FILE_ENV_VAR: &str = "MASQ_CHAINS_FILE";
+
+// Records are leaked on purpose: they live as long as the process, like the ones in CHAINS
+#[cfg(not(test))]
+lazy_static! {
+    static ref CUSTOM_CHAINS: RwLock<Vec<&'static BlockchainRecord>> = RwLock::new(vec![]);
+}
+
+// Each test thread gets its own registry so that chains registered by one test can't leak into another
+#[cfg(test)]
+thread_local! {
+    static CUSTOM_CHAINS: RefCell<Vec<&'static BlockchainRecord>> = RefCell::new(vec![]);
+}
+
+#[cfg(not(test))]
+fn with_registry<R>(f: impl FnOnce(&mut Vec<&'static BlockchainRecord>) -> R) -> R {
+    f(&mut CUSTOM_CHAINS.write().expect("Custom chains poisoned"))
+}
+
+#[cfg(test)]
+fn with_registry<R>(f: impl FnOnce(&mut Vec<&'static BlockchainRecord>) -> R) -> R {
+    CUSTOM_CHAINS.with(|registry| f(&mut registry.borrow_mut()))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CustomChainDefinition {
+    pub name: String,
+    pub chain_id: u64,
+    pub contract: String,
+    pub contract_creation_block: u64,
+    pub family: String,
+    pub gas_limit_base: Option<u64>,
+    pub confirmation_depth: Option<u64>,
+    pub disperse_contract: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CustomChainsFile {
+    #[serde(default)]
+    chain: Vec<CustomChainDefinition>,
+}
+
+pub fn load_custom_chains_from_environment() -> Result<(), String> {
+    match std::env::var(CUSTOM_CHAINS_FILE_ENV_VAR) {
+        Ok(path) if !path.is_empty() => load_custom_chains_file(Path::new(&path)),
+        _ => Ok(()),
+    }
+}
+
+pub fn load_custom_chains_file(path: &Path) -> Result<(), String> {
+    let contents = fs::read_to_string(path).map_err(|e| {
+        format!(
+            "Couldn't read chain definitions from {}: {}",
+            path.display(),
+            e
+        )
+    })?;
+    let definitions = parse_custom_chains(&contents)
+        .map_err(|e| format!("Chain definitions in {} are invalid: {}", path.display(), e))?;
+    register_custom_chains(definitions)
+        .map_err(|e| format!("Chain definitions in {} are invalid: {}", path.display(), e))
+}
+
+pub fn parse_custom_chains(toml_contents: &str) -> Result<Vec<CustomChainDefinition>, String> {
+    toml::de::from_str::<CustomChainsFile>(toml_contents)
+        .map(|file| file.chain)
+        .map_err(|e| e.to_string())
+}
+
+pub fn register_custom_chains(definitions: Vec<CustomChainDefinition>) -> Result<(), String> {
+    with_registry(|registered| register_into(registered, definitions))
+}
+
+fn register_into(
+    registered: &mut Vec<&'static BlockchainRecord>,
+    definitions: Vec<CustomChainDefinition>,
+) -> Result<(), String> {
+    let mut accepted: Vec<BlockchainRecord> = vec![];
+    for definition in definitions {
+        let record = make_record(&definition)?;
+        if registered.iter().any(|existing| **existing == record) {
+            continue;
+        }
+        let mut known = CHAINS
+            .iter()
+            .chain(registered.iter().copied())
+            .chain(accepted.iter());
+        if let Some(clash) = known.find(|existing| {
+            existing.literal_identifier == record.literal_identifier
+                || existing.num_chain_id == record.num_chain_id
+        }) {
+            return Err(format!(
+                "Chain '{}' (id {}) clashes with the already known chain '{}' (id {})",
+                record.literal_identifier,
+                record.num_chain_id,
+                clash.literal_identifier,
+                clash.num_chain_id
+            ));
+        }
+        accepted.push(record)
+    }
+    accepted
+        .into_iter()
+        .for_each(|record| registered.push(Box::leak(Box::new(record))));
+    Ok(())
+}
+
+pub fn custom_chain_records() -> Vec<&'static BlockchainRecord> {
+    with_registry(|registered| registered.clone())
+}
+
+fn make_record(definition: &CustomChainDefinition) -> Result<BlockchainRecord, String> {
+    validate_name(&definition.name)?;
+    let chain_family = parse_family(&definition.family).ok_or_else(|| {
+        format!(
+            "Chain '{}' has unknown family '{}'; use 'eth', 'polygon' or 'dev'",
+            definition.name, definition.family
+        )
+    })?;
+    let contract = parse_address(&definition.contract).ok_or_else(|| {
+        format!(
+            "Chain '{}' has an invalid contract address '{}'",
+            definition.name, definition.contract
+        )
+    })?;
+    let disperse_contract_opt = match &definition.disperse_contract {
+        Some(address) => Some(parse_address(address).ok_or_else(|| {
+            format!(
+                "Chain '{}' has an invalid disperse contract address '{}'",
+                definition.name, address
+            )
+        })?),
+        None => None,
+    };
+    Ok(BlockchainRecord {
+        self_id: Chain::Custom(definition.chain_id),
+        num_chain_id: definition.chain_id,
+        chain_family,
+        literal_identifier: Box::leak(definition.name.clone().into_boxed_str()),
+        contract,
+        contract_creation_block: definition.contract_creation_block,
+        confirmation_depth: definition.confirmation_depth.unwrap_or(12),
+        gas_limit_base: definition
+            .gas_limit_base
+            .unwrap_or_else(|| default_gas_limit_base(chain_family)),
+        disperse_contract_opt,
+    })
+}
+
+fn parse_address(address: &str) -> Option<Address> {
+    Address::from_str(address.trim_start_matches("0x")).ok()
+}
+
+// The name ends up in data-directory paths and in masq:// descriptors, so it's kept tame
+fn validate_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "Chain name '{}' must consist only of lowercase letters, digits and dashes",
+            name
+        ))
+    }
+}
+
+fn parse_family(family: &str) -> Option<ChainFamily> {
+    match family {
+        "eth" => Some(ChainFamily::Eth),
+        "polygon" => Some(ChainFamily::Polygon),
+        "dev" => Some(ChainFamily::Dev),
+        _ => None,
+    }
+}
+
+fn default_gas_limit_base(family: ChainFamily) -> u64 {
+    match family {
+        ChainFamily::Polygon => 70_000,
+        ChainFamily::Eth => 55_000,
+        ChainFamily::Dev => 55_000,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchains::chains::chain_from_chain_identifier_opt;
+    use crate::test_utils::utils::ensure_node_home_directory_exists;
+    use std::io::Write;
+
+    fn make_definition(name: &str, chain_id: u64) -> CustomChainDefinition {
+        CustomChainDefinition {
+            name: name.to_string(),
+            chain_id,
+            contract: "0x59882e4a8f5d24643d4dda422922a870f1b3e664".to_string(),
+            contract_creation_block: 1234,
+            family: "polygon".to_string(),
+            gas_limit_base: None,
+            confirmation_depth: Some(5),
+            disperse_contract: None,
+        }
+    }
+
+    #[test]
+    fn parse_custom_chains_reads_toml_tables() {
+        let contents = r#"
+            [[chain]]
+            name = "private-net"
+            chain-id = 31337
+            contract = "0x59882e4a8f5d24643d4dda422922a870f1b3e664"
+            contract-creation-block = 1234
+            family = "polygon"
+            confirmation-depth = 5
+
+            [[chain]]
+            name = "other-net"
+            chain-id = 31338
+            contract = "0x59882e4a8f5d24643d4dda422922a870f1b3e664"
+            contract-creation-block = 0
+            family = "eth"
+            gas-limit-base = 60000
+            disperse-contract = "0xd152f549545093347a162dce210e7293f1452150"
+        "#;
+
+        let result = parse_custom_chains(contents).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                make_definition("private-net", 31337),
+                CustomChainDefinition {
+                    name: "other-net".to_string(),
+                    chain_id: 31338,
+                    contract: "0x59882e4a8f5d24643d4dda422922a870f1b3e664".to_string(),
+                    contract_creation_block: 0,
+                    family: "eth".to_string(),
+                    gas_limit_base: Some(60000),
+                    confirmation_depth: None,
+                    disperse_contract: Some(
+                        "0xd152f549545093347a162dce210e7293f1452150".to_string()
+                    ),
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn registered_chain_behaves_like_a_built_in_one() {
+        register_custom_chains(vec![make_definition("registered-net", 7_000_001)]).unwrap();
+
+        let chain = Chain::from("registered-net");
+        assert_eq!(chain, Chain::Custom(7_000_001));
+        assert_eq!(
+            chain_from_chain_identifier_opt("registered-net"),
+            Some(chain)
+        );
+        let record = chain.rec();
+        assert_eq!(record.literal_identifier, "registered-net");
+        assert_eq!(record.num_chain_id, 7_000_001);
+        assert_eq!(record.chain_family, ChainFamily::Polygon);
+        assert_eq!(record.contract_creation_block, 1234);
+        assert_eq!(record.confirmation_depth, 5);
+        assert_eq!(record.gas_limit_base, 70_000);
+        assert_eq!(record.disperse_contract_opt, None);
+        assert_eq!(chain.is_mainnet(), false);
+    }
+
+    #[test]
+    fn registered_chain_can_name_a_disperse_contract() {
+        let definition = CustomChainDefinition {
+            disperse_contract: Some("0xd152f549545093347a162dce210e7293f1452150".to_string()),
+            ..make_definition("disperse-net", 7_000_009)
+        };
+
+        register_custom_chains(vec![definition]).unwrap();
+
+        assert_eq!(
+            Chain::Custom(7_000_009).rec().disperse_contract_opt,
+            Some(Address::from_str("d152f549545093347a162dce210e7293f1452150").unwrap())
+        )
+    }
+
+    #[test]
+    fn registering_the_same_definition_twice_is_harmless() {
+        let definition = make_definition("twice-net", 7_000_002);
+        register_custom_chains(vec![definition.clone()]).unwrap();
+
+        let result = register_custom_chains(vec![definition]);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            custom_chain_records()
+                .iter()
+                .filter(|record| record.literal_identifier == "twice-net")
+                .count(),
+            1
+        )
+    }
+
+    #[test]
+    fn chain_clashing_with_a_built_in_chain_is_rejected() {
+        let result = register_custom_chains(vec![make_definition("my-mainnet", 137)]);
+
+        assert_eq!(
+            result,
+            Err("Chain 'my-mainnet' (id 137) clashes with the already known chain 'polygon-mainnet' (id 137)".to_string())
+        )
+    }
+
+    #[test]
+    fn nothing_is_registered_if_one_definition_is_bad() {
+        let mut bad = make_definition("bad-net", 7_000_004);
+        bad.family = "bitcoin".to_string();
+
+        let result = register_custom_chains(vec![make_definition("good-net", 7_000_003), bad]);
+
+        assert_eq!(
+            result,
+            Err(
+                "Chain 'bad-net' has unknown family 'bitcoin'; use 'eth', 'polygon' or 'dev'"
+                    .to_string()
+            )
+        );
+        assert_eq!(chain_from_chain_identifier_opt("good-net"), None)
+    }
+
+    #[test]
+    fn bad_names_and_contracts_are_rejected() {
+        let bad_contract = CustomChainDefinition {
+            contract: "0xbooga".to_string(),
+            ..make_definition("contract-net", 7_000_005)
+        };
+        let bad_disperse_contract = CustomChainDefinition {
+            disperse_contract: Some("0xwooga".to_string()),
+            ..make_definition("disperse-net", 7_000_010)
+        };
+
+        assert_eq!(
+            register_custom_chains(vec![make_definition("Bad:Name", 7_000_006)]),
+            Err(
+                "Chain name 'Bad:Name' must consist only of lowercase letters, digits and dashes"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            register_custom_chains(vec![bad_contract]),
+            Err("Chain 'contract-net' has an invalid contract address '0xbooga'".to_string())
+        );
+        assert_eq!(
+            register_custom_chains(vec![bad_disperse_contract]),
+            Err(
+                "Chain 'disperse-net' has an invalid disperse contract address '0xwooga'"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn load_custom_chains_file_registers_what_it_finds() {
+        let home_dir = ensure_node_home_directory_exists(
+            "custom_chains",
+            "load_custom_chains_file_registers_what_it_finds",
+        );
+        let path = home_dir.join("chains.toml");
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(
+            b"[[chain]]\nname = \"file-net\"\nchain-id = 7000007\n\
+              contract = \"0x59882e4a8f5d24643d4dda422922a870f1b3e664\"\n\
+              contract-creation-block = 0\nfamily = \"dev\"\n",
+        )
+        .unwrap();
+
+        let result = load_custom_chains_file(&path);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            chain_from_chain_identifier_opt("file-net"),
+            Some(Chain::Custom(7_000_007))
+        );
+        assert_eq!(Chain::Custom(7_000_007).rec().gas_limit_base, 55_000);
+    }
+
+    #[test]
+    fn chains_registered_by_one_test_thread_are_invisible_to_another() {
+        register_custom_chains(vec![make_definition("thread-net", 7_000_008)]).unwrap();
+
+        let seen_elsewhere = std::thread::spawn(|| chain_from_chain_identifier_opt("thread-net"))
+            .join()
+            .unwrap();
+
+        assert_eq!(seen_elsewhere, None);
+        assert_eq!(
+            chain_from_chain_identifier_opt("thread-net"),
+            Some(Chain::Custom(7_000_008))
+        )
+    }
+
+    #[test]
+    fn load_custom_chains_file_complains_about_a_missing_file() {
+        let path = Path::new("/nonexistent/chains.toml");
+
+        let result = load_custom_chains_file(path);
+
+        assert!(result
+            .unwrap_err()
+            .starts_with("Couldn't read chain definitions from /nonexistent/chains.toml: "))
+    }
+}
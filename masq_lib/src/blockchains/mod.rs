@@ -2,3 +2,4 @@
 
 pub mod blockchain_records;
 pub mod chains;
+pub mod custom_chains;
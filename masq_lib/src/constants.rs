@@ -5,7 +5,7 @@ use crate::data_version::DataVersion;
 use const_format::concatcp;
 
 pub const DEFAULT_CHAIN: Chain = Chain::PolyMainnet;
-pub const CURRENT_SCHEMA_VERSION: usize = 7;
+pub const CURRENT_SCHEMA_VERSION: usize = 15;
 
 pub const HIGHEST_RANDOM_CLANDESTINE_PORT: u16 = 9999;
 pub const HTTP_PORT: u16 = 80;
@@ -17,8 +17,10 @@ pub const DEFAULT_UI_PORT: u16 = 5333;
 pub const MASQ_URL_PREFIX: &str = "masq://";
 pub const CURRENT_LOGFILE_NAME: &str = "MASQNode_rCURRENT.log";
 pub const MASQ_PROMPT: &str = "masq> ";
+pub const DEFAULT_INSTANCE_NAME: &str = "default";
 
 pub const DEFAULT_GAS_PRICE: u64 = 1; //TODO ?? Really
+pub const DEFAULT_EARNING_WALLET_WATCH_PERIOD_SEC: u64 = 2_592_000;
 
 pub const WALLET_ADDRESS_LENGTH: usize = 42;
 pub const MASQ_TOTAL_SUPPLY: u64 = 37_500_000;
@@ -37,6 +39,7 @@ pub const MUMBAI_TESTNET_CONTRACT_CREATION_BLOCK: u64 = 24_638_838;
 // please add it to the test: check_limits_of_data_versions_const()
 pub const CLIENT_REQUEST_PAYLOAD_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const CLIENT_RESPONSE_PAYLOAD_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
+pub const DELINQUENCY_NOTICE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const DNS_RESOLVER_FAILURE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const GOSSIP_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
 pub const GOSSIP_FAILURE_CURRENT_VERSION: DataVersion = DataVersion { major: 0, minor: 1 };
@@ -61,6 +64,11 @@ pub const UNRECOGNIZED_PARAMETER: u64 = CONFIGURATOR_PREFIX | 11;
 pub const NON_PARSABLE_VALUE: u64 = CONFIGURATOR_PREFIX | 12;
 pub const MISSING_DATA: u64 = CONFIGURATOR_PREFIX | 13;
 pub const UNKNOWN_ERROR: u64 = CONFIGURATOR_PREFIX | 14;
+pub const EARNING_WALLET_ROTATION_ERROR: u64 = CONFIGURATOR_PREFIX | 15;
+pub const BACKUP_ERROR: u64 = CONFIGURATOR_PREFIX | 16;
+pub const RESTORE_ERROR: u64 = CONFIGURATOR_PREFIX | 17;
+pub const ILLEGAL_CONFIGURATION_CHANGE: u64 = CONFIGURATOR_PREFIX | 18;
+pub const LOG_FILE_READ_ERROR: u64 = CONFIGURATOR_PREFIX | 19;
 
 //moved from masq_lib/messages
 pub const UI_NODE_COMMUNICATION_PREFIX: u64 = 0x8000_0000_0000_0000;
@@ -71,12 +79,24 @@ pub const UNMARSHAL_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 4;
 pub const SETUP_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 5;
 pub const TIMEOUT_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 6;
 pub const SCAN_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 7;
+pub const NODE_INSTANCE_CONFLICT_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 8;
+pub const SETUP_PROFILE_ERROR: u64 = UI_NODE_COMMUNICATION_PREFIX | 9;
 
 //accountant
 pub const ACCOUNTANT_PREFIX: u64 = 0x0040_0000_0000_0000;
 pub const REQUEST_WITH_NO_VALUES: u64 = ACCOUNTANT_PREFIX | 1;
 pub const REQUEST_WITH_MUTUALLY_EXCLUSIVE_PARAMS: u64 = ACCOUNTANT_PREFIX | 2;
 pub const VALUE_EXCEEDS_ALLOWED_LIMIT: u64 = ACCOUNTANT_PREFIX | 3;
+pub const LEDGER_HISTORY_ERROR: u64 = ACCOUNTANT_PREFIX | 4;
+pub const FINANCIALS_EXPORT_ERROR: u64 = ACCOUNTANT_PREFIX | 5;
+pub const SERVICE_USAGE_ERROR: u64 = ACCOUNTANT_PREFIX | 6;
+pub const BAN_LIST_ERROR: u64 = ACCOUNTANT_PREFIX | 7;
+pub const DEBT_FORGIVENESS_ERROR: u64 = ACCOUNTANT_PREFIX | 8;
+
+//blockchain bridge
+pub const BLOCKCHAIN_BRIDGE_PREFIX: u64 = 0x0020_0000_0000_0000;
+pub const WALLET_BALANCES_ERROR: u64 = BLOCKCHAIN_BRIDGE_PREFIX | 1;
+pub const WALLET_DISCOVERY_ERROR: u64 = BLOCKCHAIN_BRIDGE_PREFIX | 2;
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
@@ -113,7 +133,9 @@ mod tests {
         assert_eq!(MASQ_URL_PREFIX, "masq://");
         assert_eq!(CURRENT_LOGFILE_NAME, "MASQNode_rCURRENT.log");
         assert_eq!(MASQ_PROMPT, "masq> ");
+        assert_eq!(DEFAULT_INSTANCE_NAME, "default");
         assert_eq!(DEFAULT_GAS_PRICE, 1);
+        assert_eq!(DEFAULT_EARNING_WALLET_WATCH_PERIOD_SEC, 2_592_000);
         assert_eq!(WALLET_ADDRESS_LENGTH, 42);
         assert_eq!(MASQ_TOTAL_SUPPLY, 37_500_000);
         assert_eq!(WEIS_IN_GWEI, 1_000_000_000);
@@ -140,6 +162,11 @@ mod tests {
         assert_eq!(NON_PARSABLE_VALUE, CONFIGURATOR_PREFIX | 12);
         assert_eq!(MISSING_DATA, CONFIGURATOR_PREFIX | 13);
         assert_eq!(UNKNOWN_ERROR, CONFIGURATOR_PREFIX | 14);
+        assert_eq!(EARNING_WALLET_ROTATION_ERROR, CONFIGURATOR_PREFIX | 15);
+        assert_eq!(BACKUP_ERROR, CONFIGURATOR_PREFIX | 16);
+        assert_eq!(RESTORE_ERROR, CONFIGURATOR_PREFIX | 17);
+        assert_eq!(ILLEGAL_CONFIGURATION_CHANGE, CONFIGURATOR_PREFIX | 18);
+        assert_eq!(LOG_FILE_READ_ERROR, CONFIGURATOR_PREFIX | 19);
         assert_eq!(UI_NODE_COMMUNICATION_PREFIX, 0x8000_0000_0000_0000);
         assert_eq!(NODE_LAUNCH_ERROR, UI_NODE_COMMUNICATION_PREFIX | 1);
         assert_eq!(NODE_NOT_RUNNING_ERROR, UI_NODE_COMMUNICATION_PREFIX | 2);
@@ -148,6 +175,11 @@ mod tests {
         assert_eq!(SETUP_ERROR, UI_NODE_COMMUNICATION_PREFIX | 5);
         assert_eq!(TIMEOUT_ERROR, UI_NODE_COMMUNICATION_PREFIX | 6);
         assert_eq!(SCAN_ERROR, UI_NODE_COMMUNICATION_PREFIX | 7);
+        assert_eq!(
+            NODE_INSTANCE_CONFLICT_ERROR,
+            UI_NODE_COMMUNICATION_PREFIX | 8
+        );
+        assert_eq!(SETUP_PROFILE_ERROR, UI_NODE_COMMUNICATION_PREFIX | 9);
         assert_eq!(ACCOUNTANT_PREFIX, 0x0040_0000_0000_0000);
         assert_eq!(REQUEST_WITH_NO_VALUES, ACCOUNTANT_PREFIX | 1);
         assert_eq!(
@@ -155,6 +187,14 @@ mod tests {
             ACCOUNTANT_PREFIX | 2
         );
         assert_eq!(VALUE_EXCEEDS_ALLOWED_LIMIT, ACCOUNTANT_PREFIX | 3);
+        assert_eq!(LEDGER_HISTORY_ERROR, ACCOUNTANT_PREFIX | 4);
+        assert_eq!(FINANCIALS_EXPORT_ERROR, ACCOUNTANT_PREFIX | 5);
+        assert_eq!(SERVICE_USAGE_ERROR, ACCOUNTANT_PREFIX | 6);
+        assert_eq!(BAN_LIST_ERROR, ACCOUNTANT_PREFIX | 7);
+        assert_eq!(DEBT_FORGIVENESS_ERROR, ACCOUNTANT_PREFIX | 8);
+        assert_eq!(BLOCKCHAIN_BRIDGE_PREFIX, 0x0020_0000_0000_0000);
+        assert_eq!(WALLET_BALANCES_ERROR, BLOCKCHAIN_BRIDGE_PREFIX | 1);
+        assert_eq!(WALLET_DISCOVERY_ERROR, BLOCKCHAIN_BRIDGE_PREFIX | 2);
         assert_eq!(CENTRAL_DELIMITER, '@');
         assert_eq!(CHAIN_IDENTIFIER_DELIMITER, ':');
         assert_eq!(MAINNET, "mainnet");
@@ -174,6 +214,10 @@ mod tests {
             CLIENT_RESPONSE_PAYLOAD_CURRENT_VERSION,
             DataVersion { major: 0, minor: 1 }
         );
+        assert_eq!(
+            DELINQUENCY_NOTICE_CURRENT_VERSION,
+            DataVersion { major: 0, minor: 1 }
+        );
         assert_eq!(
             DNS_RESOLVER_FAILURE_CURRENT_VERSION,
             DataVersion { major: 0, minor: 1 }
@@ -194,6 +238,7 @@ mod tests {
         [
             CLIENT_REQUEST_PAYLOAD_CURRENT_VERSION,
             CLIENT_RESPONSE_PAYLOAD_CURRENT_VERSION,
+            DELINQUENCY_NOTICE_CURRENT_VERSION,
             DNS_RESOLVER_FAILURE_CURRENT_VERSION,
             GOSSIP_CURRENT_VERSION,
             GOSSIP_FAILURE_CURRENT_VERSION,
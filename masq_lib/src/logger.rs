@@ -7,9 +7,9 @@ use crate::constants::{
     NODE_RECORD_INNER_CURRENT_VERSION,
 };
 use crate::data_version::DataVersion;
-use crate::messages::SerializableLogLevel;
+use crate::messages::{SerializableLogLevel, UiLogLevel};
 #[cfg(not(feature = "log_recipient_test"))]
-use crate::messages::{ToMessageBody, UiLogBroadcast};
+use crate::messages::{ToMessageBody, UiLogBroadcast, UiLogStreamBroadcast};
 #[cfg(feature = "log_recipient_test")]
 use crate::test_utils::utils::MutexIncrementInset;
 #[cfg(not(feature = "log_recipient_test"))]
@@ -23,7 +23,8 @@ use log::Level;
 use log::Metadata;
 #[allow(unused_imports)]
 use log::Record;
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
 use std::{io, thread};
 use time::format_description::parse;
 use time::OffsetDateTime;
@@ -39,8 +40,12 @@ pub const TIME_FORMATTING_STRING: &str =
 
 lazy_static! {
     pub static ref LOG_RECIPIENT_OPT: Mutex<Option<Recipient<NodeToUiMessage>>> = Mutex::new(None);
+    static ref LOG_STREAM_SUBSCRIPTIONS: Mutex<Vec<LogStreamSubscription>> = Mutex::new(vec![]);
 }
 
+// Spares every log call the mutex while nobody is subscribed, which is nearly always
+static LOG_STREAM_SUBSCRIBED: AtomicBool = AtomicBool::new(false);
+
 #[cfg(not(feature = "log_recipient_test"))]
 pub fn prepare_log_recipient(recipient: Recipient<NodeToUiMessage>) {
     if LOG_RECIPIENT_OPT
@@ -53,6 +58,64 @@ pub fn prepare_log_recipient(recipient: Recipient<NodeToUiMessage>) {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogStreamSubscription {
+    pub client_id: u64,
+    pub min_level: Level,
+    pub modules: Vec<String>,
+}
+
+impl LogStreamSubscription {
+    // A module filter matches any logger whose name starts with it, ignoring case
+    pub fn accepts(&self, level: Level, module: &str) -> bool {
+        let module = module.to_lowercase();
+        level <= self.min_level
+            && (self.modules.is_empty()
+                || self
+                    .modules
+                    .iter()
+                    .any(|prefix| module.starts_with(&prefix.to_lowercase())))
+    }
+}
+
+// A client has at most one subscription; subscribing again replaces the old one
+pub fn subscribe_to_log_stream(subscription: LogStreamSubscription) {
+    let mut subscriptions = lock_log_stream_subscriptions();
+    subscriptions.retain(|existing| existing.client_id != subscription.client_id);
+    subscriptions.push(subscription);
+    LOG_STREAM_SUBSCRIBED.store(true, Ordering::Relaxed);
+}
+
+pub fn unsubscribe_from_log_stream(client_id: u64) {
+    let mut subscriptions = lock_log_stream_subscriptions();
+    subscriptions.retain(|existing| existing.client_id != client_id);
+    LOG_STREAM_SUBSCRIBED.store(!subscriptions.is_empty(), Ordering::Relaxed);
+}
+
+pub fn log_stream_subscription(client_id: u64) -> Option<LogStreamSubscription> {
+    lock_log_stream_subscriptions()
+        .iter()
+        .find(|subscription| subscription.client_id == client_id)
+        .cloned()
+}
+
+fn log_stream_subscribers(level: Level, module: &str) -> Vec<u64> {
+    if !LOG_STREAM_SUBSCRIBED.load(Ordering::Relaxed) {
+        return vec![];
+    }
+    lock_log_stream_subscriptions()
+        .iter()
+        .filter(|subscription| subscription.accepts(level, module))
+        .map(|subscription| subscription.client_id)
+        .collect()
+}
+
+fn lock_log_stream_subscriptions<'a>() -> MutexGuard<'a, Vec<LogStreamSubscription>> {
+    LOG_STREAM_SUBSCRIPTIONS
+        .lock()
+        .expect("log stream subscriptions poisoned")
+}
+
 #[derive(Clone)]
 pub struct Logger {
     name: String,
@@ -182,6 +245,20 @@ impl Logger {
     }
 
     fn generic_log<F>(&self, level: Level, log_function: F)
+    where
+        F: FnOnce() -> String,
+    {
+        let subscribers = log_stream_subscribers(level, &self.name);
+        if subscribers.is_empty() {
+            self.log_and_transmit(level, log_function)
+        } else {
+            let msg = log_function();
+            Self::stream(&self.name, level, &msg, subscribers);
+            self.log_and_transmit(level, || msg)
+        }
+    }
+
+    fn log_and_transmit<F>(&self, level: Level, log_function: F)
     where
         F: FnOnce() -> String,
     {
@@ -249,6 +326,36 @@ impl Logger {
             recipient.try_send(actix_msg).expect("UiGateway is dead")
         }
     }
+
+    #[cfg(not(feature = "log_recipient_test"))]
+    fn stream(module: &str, level: Level, msg: &str, subscribers: Vec<u64>) {
+        if let Some(recipient) = LOG_RECIPIENT_OPT
+            .lock()
+            .expect("log recipient mutex poisoned")
+            .as_ref()
+        {
+            let timestamp = OffsetDateTime::now_utc()
+                .format(
+                    &parse(TIME_FORMATTING_STRING).expect("Unable to parse the formatting type."),
+                )
+                .expect("Unable to format date and time.");
+            subscribers.into_iter().for_each(|client_id| {
+                let actix_msg = NodeToUiMessage {
+                    target: MessageTarget::ClientId(client_id),
+                    body: UiLogStreamBroadcast {
+                        timestamp: timestamp.clone(),
+                        level: level.into(),
+                        module: module.to_string(),
+                        msg: msg.to_string(),
+                    }
+                    .tmb(0),
+                };
+                // a subscriber asking for Trace can outpace the UiGateway; it loses lines then,
+                // but the Node doesn't go down over it
+                let _ = recipient.try_send(actix_msg);
+            })
+        }
+    }
 }
 
 #[cfg(feature = "no_test_share")]
@@ -258,6 +365,30 @@ impl Logger {
     }
 }
 
+impl From<Level> for UiLogLevel {
+    fn from(native_level: Level) -> Self {
+        match native_level {
+            Level::Error => UiLogLevel::Error,
+            Level::Warn => UiLogLevel::Warn,
+            Level::Info => UiLogLevel::Info,
+            Level::Debug => UiLogLevel::Debug,
+            Level::Trace => UiLogLevel::Trace,
+        }
+    }
+}
+
+impl From<UiLogLevel> for Level {
+    fn from(ui_level: UiLogLevel) -> Self {
+        match ui_level {
+            UiLogLevel::Error => Level::Error,
+            UiLogLevel::Warn => Level::Warn,
+            UiLogLevel::Info => Level::Info,
+            UiLogLevel::Debug => Level::Debug,
+            UiLogLevel::Trace => Level::Trace,
+        }
+    }
+}
+
 impl From<Level> for SerializableLogLevel {
     fn from(native_level: Level) -> Self {
         match native_level {
@@ -305,6 +436,8 @@ lazy_static! {
 #[cfg(feature = "log_recipient_test")]
 impl Logger {
     pub fn transmit(_msg: String, _log_level: SerializableLogLevel) {}
+
+    fn stream(_module: &str, _level: Level, _msg: &str, _subscribers: Vec<u64>) {}
 }
 
 #[cfg(feature = "log_recipient_test")]
@@ -336,7 +469,7 @@ mod tests {
         DNS_RESOLVER_FAILURE_CURRENT_VERSION, GOSSIP_CURRENT_VERSION,
         GOSSIP_FAILURE_CURRENT_VERSION, NODE_RECORD_INNER_CURRENT_VERSION,
     };
-    use crate::messages::{ToMessageBody, UiLogBroadcast};
+    use crate::messages::{FromMessageBody, ToMessageBody, UiLogBroadcast, UiLogStreamBroadcast};
     use crate::test_utils::logging::init_test_logging;
     use crate::test_utils::logging::TestLogHandler;
     use crate::ui_gateway::{MessageBody, MessagePath, MessageTarget};
@@ -740,6 +873,126 @@ mod tests {
         TestLogHandler::new().exists_log_containing("WARN: test: This is a warn log.");
     }
 
+    #[test]
+    fn conversion_between_ui_log_level_and_native_level_is_enabled() {
+        let levels = vec![
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ];
+
+        let result: Vec<UiLogLevel> = levels.iter().map(|level| (*level).into()).collect();
+
+        assert_eq!(
+            result,
+            vec![
+                UiLogLevel::Error,
+                UiLogLevel::Warn,
+                UiLogLevel::Info,
+                UiLogLevel::Debug,
+                UiLogLevel::Trace
+            ]
+        );
+        let back: Vec<Level> = result.into_iter().map(Level::from).collect();
+        assert_eq!(back, levels);
+    }
+
+    #[test]
+    fn log_stream_subscription_filters_by_level_and_module() {
+        let subject = LogStreamSubscription {
+            client_id: 0,
+            min_level: Level::Debug,
+            modules: vec!["proxy".to_string(), "Neighborhood".to_string()],
+        };
+
+        assert!(subject.accepts(Level::Debug, "ProxyServer"));
+        assert!(subject.accepts(Level::Error, "ProxyClient"));
+        assert!(subject.accepts(Level::Info, "neighborhood"));
+        assert!(!subject.accepts(Level::Trace, "ProxyServer"));
+        assert!(!subject.accepts(Level::Error, "Accountant"));
+        let unfiltered = LogStreamSubscription {
+            modules: vec![],
+            ..subject
+        };
+        assert!(unfiltered.accepts(Level::Warn, "Accountant"));
+    }
+
+    #[test]
+    fn subscribing_again_replaces_the_earlier_subscription() {
+        let client_id = 0x1057_0001;
+        let first = LogStreamSubscription {
+            client_id,
+            min_level: Level::Warn,
+            modules: vec![],
+        };
+        let second = LogStreamSubscription {
+            client_id,
+            min_level: Level::Trace,
+            modules: vec!["Accountant".to_string()],
+        };
+
+        subscribe_to_log_stream(first);
+        subscribe_to_log_stream(second.clone());
+
+        assert_eq!(log_stream_subscription(client_id), Some(second));
+        assert_eq!(
+            lock_log_stream_subscriptions()
+                .iter()
+                .filter(|subscription| subscription.client_id == client_id)
+                .count(),
+            1
+        );
+        unsubscribe_from_log_stream(client_id);
+        assert_eq!(log_stream_subscription(client_id), None);
+    }
+
+    #[test]
+    fn generic_log_streams_to_subscribers_even_below_the_file_level() {
+        init_test_logging();
+        let _guard = TEST_LOG_RECIPIENT_GUARD.lock().unwrap();
+        let client_id = 0x1057_0002;
+        let mut logger = Logger::new("log_stream_test");
+        logger.set_level_for_test(Level::Info);
+        let system = System::new("generic_log_streams_to_subscribers_even_below_the_file_level");
+        let ui_gateway_recording_arc = Arc::new(Mutex::new(vec![]));
+        let ui_gateway = TestUiGateway::new(1, &ui_gateway_recording_arc);
+        let recipient = ui_gateway.start().recipient();
+        {
+            LOG_RECIPIENT_OPT.lock().unwrap().replace(recipient);
+        }
+        subscribe_to_log_stream(LogStreamSubscription {
+            client_id,
+            min_level: Level::Debug,
+            modules: vec!["log_stream".to_string()],
+        });
+
+        logger.trace(|| "This is a trace log.".to_string());
+        logger.debug(|| "This is a debug log.".to_string());
+
+        system.run(); //shut down after receiving the expected count of messages
+        unsubscribe_from_log_stream(client_id);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        assert_eq!(
+            ui_gateway_recording[0].target,
+            MessageTarget::ClientId(client_id)
+        );
+        let (broadcast, _) =
+            UiLogStreamBroadcast::fmb(ui_gateway_recording[0].body.clone()).unwrap();
+        assert_eq!(broadcast.level, UiLogLevel::Debug);
+        assert_eq!(broadcast.module, "log_stream_test".to_string());
+        assert_eq!(broadcast.msg, "This is a debug log.".to_string());
+        let timestamp_regex = Regex::new(r"^\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{3}$").unwrap();
+        assert!(
+            timestamp_regex.is_match(&broadcast.timestamp),
+            "{}",
+            broadcast.timestamp
+        );
+        TestLogHandler::new().exists_no_log_containing("This is a debug log.");
+    }
+
     #[test]
     fn log_file_heading_print_right_format() {
         let heading_result = Logger::log_file_heading();
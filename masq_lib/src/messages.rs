@@ -377,6 +377,16 @@ impl From<UiSetupBroadcast> for UiSetupInner {
     }
 }
 
+impl From<UiLoadSetupProfileResponse> for UiSetupInner {
+    fn from(input: UiLoadSetupProfileResponse) -> Self {
+        Self {
+            running: input.running,
+            values: input.values,
+            errors: input.errors,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct UiStartOrder {}
 conversation_message!(UiStartOrder, "start");
@@ -394,12 +404,14 @@ conversation_message!(UiStartResponse, "start");
 pub enum CrashReason {
     ChildWaitFailure(String),
     NoInformation,
+    ConfigurationError(String),
     Unrecognized(String),
     DaemonCrashed,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiNodeCrashedBroadcast {
+    pub instance: String,
     #[serde(rename = "processId")]
     pub process_id: u32,
     #[serde(rename = "crashReason")]
@@ -407,6 +419,19 @@ pub struct UiNodeCrashedBroadcast {
 }
 fire_and_forget_message!(UiNodeCrashedBroadcast, "crashed");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiNodeRestartedBroadcast {
+    pub instance: String,
+    #[serde(rename = "crashedProcessId")]
+    pub crashed_process_id: u32,
+    #[serde(rename = "newProcessId")]
+    pub new_process_id: u32,
+    #[serde(rename = "redirectUiPort")]
+    pub redirect_ui_port: u16,
+    pub attempt: u64,
+}
+fire_and_forget_message!(UiNodeRestartedBroadcast, "restarted");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct UiRedirect {
     pub port: u16,
@@ -417,6 +442,99 @@ pub struct UiRedirect {
 }
 fire_and_forget_message!(UiRedirect, "redirect");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSelectInstance {
+    pub name: String,
+}
+fire_and_forget_message!(UiSelectInstance, "selectInstance");
+
+// sent on a UI's behalf by the WebSocketSupervisor when that UI closes its connection
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiClientDisconnected {}
+fire_and_forget_message!(UiClientDisconnected, "clientDisconnected");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSetupProfileValue {
+    pub name: String,
+    pub value: String,
+}
+
+impl UiSetupProfileValue {
+    pub fn new(name: &str, value: &str) -> UiSetupProfileValue {
+        UiSetupProfileValue {
+            name: name.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSaveSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiSaveSetupProfileRequest, "saveSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSaveSetupProfileResponse {
+    pub values: Vec<UiSetupProfileValue>,
+}
+conversation_message!(UiSaveSetupProfileResponse, "saveSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiLoadSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiLoadSetupProfileRequest, "loadSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiLoadSetupProfileResponse {
+    pub running: bool,
+    pub values: Vec<UiSetupResponseValue>,
+    pub errors: Vec<(String, String)>,
+}
+conversation_message!(UiLoadSetupProfileResponse, "loadSetupProfile");
+
+impl From<UiSetupResponse> for UiLoadSetupProfileResponse {
+    fn from(input: UiSetupResponse) -> Self {
+        Self {
+            running: input.running,
+            values: input.values,
+            errors: input.errors,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSetupProfilesRequest {}
+conversation_message!(UiSetupProfilesRequest, "setupProfiles");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSetupProfilesResponse {
+    pub profiles: Vec<String>,
+}
+conversation_message!(UiSetupProfilesResponse, "setupProfiles");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDiffSetupProfileRequest {
+    pub name: String,
+}
+conversation_message!(UiDiffSetupProfileRequest, "diffSetupProfile");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiSetupProfileDifference {
+    pub name: String,
+    #[serde(rename = "profileValueOpt")]
+    pub profile_value_opt: Option<String>,
+    #[serde(rename = "currentValueOpt")]
+    pub current_value_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDiffSetupProfileResponse {
+    pub differences: Vec<UiSetupProfileDifference>,
+}
+conversation_message!(UiDiffSetupProfileResponse, "diffSetupProfile");
+
 ///////////////////////////////////////////////////////////////////
 // These messages are sent to or by both the Daemon and the Node
 ///////////////////////////////////////////////////////////////////
@@ -433,6 +551,21 @@ fire_and_forget_message!(UiUnmarshalError, "unmarshalError");
 // These messages are sent to or by the Node only
 ///////////////////////////////////////////////////////////////////
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBackupRequest {
+    #[serde(rename = "dbPassword")]
+    pub db_password: String,
+}
+conversation_message!(UiBackupRequest, "backup");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiBackupResponse {
+    pub archive: String,
+    #[serde(rename = "schemaVersion")]
+    pub schema_version: usize,
+}
+conversation_message!(UiBackupResponse, "backup");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiChangePasswordRequest {
     #[serde(rename = "oldPasswordOpt")]
@@ -582,6 +715,53 @@ pub struct UiDescriptorResponse {
 }
 conversation_message!(UiDescriptorResponse, "descriptor");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDiscoverWalletsRequest {
+    #[serde(rename = "seedSpec")]
+    pub seed_spec: UiRecoverSeedSpec,
+    #[serde(rename = "accountCountOpt")]
+    pub account_count_opt: Option<u32>,
+    #[serde(rename = "indexCountOpt")]
+    pub index_count_opt: Option<u32>,
+}
+conversation_message!(UiDiscoverWalletsRequest, "discoverWallets");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWalletCandidate {
+    #[serde(rename = "derivationPath")]
+    pub derivation_path: String,
+    pub address: String,
+    #[serde(rename = "gasBalanceGwei")]
+    pub gas_balance_gwei: u64,
+    #[serde(rename = "masqBalanceGwei")]
+    pub masq_balance_gwei: u64,
+    #[serde(rename = "transactionCount")]
+    pub transaction_count: u64,
+    #[serde(rename = "receivedTransferCount")]
+    pub received_transfer_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiDiscoverWalletsResponse {
+    #[serde(rename = "scannedPathCount")]
+    pub scanned_path_count: u32,
+    pub candidates: Vec<UiWalletCandidate>,
+}
+conversation_message!(UiDiscoverWalletsResponse, "discoverWallets");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWalletDiscoveryProgressBroadcast {
+    #[serde(rename = "scannedPathCount")]
+    pub scanned_path_count: u32,
+    #[serde(rename = "totalPathCount")]
+    pub total_path_count: u32,
+    pub candidates: Vec<UiWalletCandidate>,
+}
+fire_and_forget_message!(
+    UiWalletDiscoveryProgressBroadcast,
+    "walletDiscoveryProgress"
+);
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiFinancialsRequest {
     #[serde(rename = "statsRequired")]
@@ -687,6 +867,199 @@ pub struct UiReceivableAccount {
     pub balance_gwei: i64,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiLedgerHistoryRequest {
+    #[serde(rename = "walletOpt")]
+    pub wallet_opt: Option<String>,
+    #[serde(rename = "minAgeSOpt")]
+    pub min_age_s_opt: Option<u64>,
+    #[serde(rename = "maxAgeSOpt")]
+    pub max_age_s_opt: Option<u64>,
+    #[serde(rename = "offsetOpt")]
+    pub offset_opt: Option<u64>,
+    #[serde(rename = "limitOpt")]
+    pub limit_opt: Option<u64>,
+}
+conversation_message!(UiLedgerHistoryRequest, "ledgerHistory");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiLedgerEntry {
+    pub timestamp: u64,
+    pub wallet: String,
+    pub kind: String,
+    #[serde(rename = "amountWei")]
+    pub amount_wei: String,
+    #[serde(rename = "transactionHashOpt")]
+    pub transaction_hash_opt: Option<String>,
+    #[serde(rename = "nonceOpt")]
+    pub nonce_opt: Option<u64>,
+    #[serde(rename = "gasPriceGweiOpt")]
+    pub gas_price_gwei_opt: Option<u64>,
+    #[serde(rename = "blockNumberOpt")]
+    pub block_number_opt: Option<u64>,
+    #[serde(rename = "outcomeOpt")]
+    pub outcome_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiLedgerHistoryResponse {
+    pub entries: Vec<UiLedgerEntry>,
+    #[serde(rename = "nextOffsetOpt")]
+    pub next_offset_opt: Option<u64>,
+}
+conversation_message!(UiLedgerHistoryResponse, "ledgerHistory");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiFinancialsExportRequest {
+    #[serde(rename = "fromTimestampOpt")]
+    pub from_timestamp_opt: Option<u64>,
+    #[serde(rename = "toTimestampOpt")]
+    pub to_timestamp_opt: Option<u64>,
+    #[serde(rename = "offsetOpt")]
+    pub offset_opt: Option<u64>,
+    #[serde(rename = "limitOpt")]
+    pub limit_opt: Option<u64>,
+}
+conversation_message!(UiFinancialsExportRequest, "financialsExport");
+
+// Amounts in wei travel as decimal strings: they can outgrow any JSON-safe integer
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiExportedAccount {
+    pub wallet: String,
+    #[serde(rename = "balanceWei")]
+    pub balance_wei: String,
+    #[serde(rename = "lastActivityTimestamp")]
+    pub last_activity_timestamp: u64,
+    #[serde(rename = "pendingPayableHashOpt")]
+    pub pending_payable_hash_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiExportedTransaction {
+    pub timestamp: u64,
+    pub wallet: String,
+    pub kind: String,
+    #[serde(rename = "amountWei")]
+    pub amount_wei: String,
+    #[serde(rename = "transactionHashOpt")]
+    pub transaction_hash_opt: Option<String>,
+    #[serde(rename = "nonceOpt")]
+    pub nonce_opt: Option<u64>,
+    #[serde(rename = "gasPriceGweiOpt")]
+    pub gas_price_gwei_opt: Option<u64>,
+    #[serde(rename = "blockNumberOpt")]
+    pub block_number_opt: Option<u64>,
+    #[serde(rename = "outcomeOpt")]
+    pub outcome_opt: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiFinancialsExportResponse {
+    pub payables: Vec<UiExportedAccount>,
+    pub receivables: Vec<UiExportedAccount>,
+    pub transactions: Vec<UiExportedTransaction>,
+    #[serde(rename = "nextOffsetOpt")]
+    pub next_offset_opt: Option<u64>,
+}
+conversation_message!(UiFinancialsExportResponse, "financialsExport");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiServiceUsageRequest {
+    #[serde(rename = "walletOpt")]
+    pub wallet_opt: Option<String>,
+    #[serde(rename = "minAgeSOpt")]
+    pub min_age_s_opt: Option<u64>,
+    #[serde(rename = "maxAgeSOpt")]
+    pub max_age_s_opt: Option<u64>,
+    #[serde(rename = "bucketSizeSOpt")]
+    pub bucket_size_s_opt: Option<u64>,
+}
+conversation_message!(UiServiceUsageRequest, "serviceUsage");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiServiceUsage {
+    #[serde(rename = "bucketStart")]
+    pub bucket_start: u64,
+    pub wallet: String,
+    pub service: String,
+    pub direction: String,
+    #[serde(rename = "serviceCount")]
+    pub service_count: u64,
+    pub bytes: u64,
+    #[serde(rename = "chargeWei")]
+    pub charge_wei: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiServiceUsageResponse {
+    pub usage: Vec<UiServiceUsage>,
+}
+conversation_message!(UiServiceUsageResponse, "serviceUsage");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBanListRequest {}
+conversation_message!(UiBanListRequest, "banList");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBannedWallet {
+    pub wallet: String,
+    pub reason: String,
+    #[serde(rename = "noteOpt")]
+    pub note_opt: Option<String>,
+    #[serde(rename = "timestampOpt")]
+    pub timestamp_opt: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBanListResponse {
+    pub banned: Vec<UiBannedWallet>,
+}
+conversation_message!(UiBanListResponse, "banList");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBanRequest {
+    pub wallet: String,
+    #[serde(rename = "noteOpt")]
+    pub note_opt: Option<String>,
+}
+conversation_message!(UiBanRequest, "ban");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBanResponse {
+    #[serde(rename = "newlyBanned")]
+    pub newly_banned: bool,
+}
+conversation_message!(UiBanResponse, "ban");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiUnbanRequest {
+    pub wallet: String,
+}
+conversation_message!(UiUnbanRequest, "unban");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiUnbanResponse {
+    #[serde(rename = "wasBanned")]
+    pub was_banned: bool,
+}
+conversation_message!(UiUnbanResponse, "unban");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiForgiveDebtRequest {
+    pub wallet: String,
+    #[serde(rename = "writeOff")]
+    pub write_off: bool,
+}
+conversation_message!(UiForgiveDebtRequest, "forgiveDebt");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiForgiveDebtResponse {
+    #[serde(rename = "balanceWei")]
+    pub balance_wei: String,
+    pub unbanned: bool,
+}
+conversation_message!(UiForgiveDebtResponse, "forgiveDebt");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiGenerateSeedSpec {
     #[serde(rename = "mnemonicPhraseSizeOpt")]
@@ -740,10 +1113,110 @@ pub enum SerializableLogLevel {
     Info,
 }
 
+// Unlike SerializableLogLevel, covers the levels that never make it into a logBroadcast
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UiLogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for UiLogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(UiLogLevel::Error),
+            "warn" => Ok(UiLogLevel::Warn),
+            "info" => Ok(UiLogLevel::Info),
+            "debug" => Ok(UiLogLevel::Debug),
+            "trace" => Ok(UiLogLevel::Trace),
+            _ => Err(format!("Unrecognized log level: '{}'", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiLogsRequest {
+    #[serde(rename = "minLevel")]
+    pub min_level: UiLogLevel,
+    pub modules: Vec<String>,
+    #[serde(rename = "tailLinesOpt")]
+    pub tail_lines_opt: Option<u32>,
+    pub follow: bool,
+}
+conversation_message!(UiLogsRequest, "logs");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiLogsResponse {
+    pub lines: Vec<String>,
+}
+conversation_message!(UiLogsResponse, "logs");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiLogStreamBroadcast {
+    pub timestamp: String,
+    pub level: UiLogLevel,
+    pub module: String,
+    pub msg: String,
+}
+fire_and_forget_message!(UiLogStreamBroadcast, "logStream");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiNeighborhoodStatusRequest {}
+conversation_message!(UiNeighborhoodStatusRequest, "neighborhoodStatus");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiNeighbor {
+    #[serde(rename = "publicKey")]
+    pub public_key: String,
+    #[serde(rename = "nodeAddrOpt")]
+    pub node_addr_opt: Option<String>,
+    #[serde(rename = "fullNeighbor")]
+    pub full_neighbor: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiNeighborhoodStatusResponse {
+    pub stage: UiConnectionStage,
+    pub neighbors: Vec<UiNeighbor>,
+    #[serde(rename = "knownNodes")]
+    pub known_nodes: u64,
+}
+conversation_message!(UiNeighborhoodStatusResponse, "neighborhoodStatus");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiNewPasswordBroadcast {}
 fire_and_forget_message!(UiNewPasswordBroadcast, "newPassword");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiReceivablesCatchUpBroadcast {
+    #[serde(rename = "scannedThroughBlock")]
+    pub scanned_through_block: u64,
+    #[serde(rename = "targetBlock")]
+    pub target_block: u64,
+}
+fire_and_forget_message!(UiReceivablesCatchUpBroadcast, "receivablesCatchUp");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiWarnedDebtor {
+    pub wallet: String,
+    #[serde(rename = "balanceWei")]
+    pub balance_wei: String,
+    #[serde(rename = "banTimestamp")]
+    pub ban_timestamp: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiBanListChangedBroadcast {
+    pub banned: Vec<String>,
+    pub unbanned: Vec<String>,
+    pub warned: Vec<UiWarnedDebtor>,
+}
+fire_and_forget_message!(UiBanListChangedBroadcast, "banListChanged");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiRecoverSeedSpec {
     #[serde(rename = "mnemonicPhrase")]
@@ -775,6 +1248,41 @@ conversation_message!(UiRecoverWalletsRequest, "recoverWallets");
 pub struct UiRecoverWalletsResponse {}
 conversation_message!(UiRecoverWalletsResponse, "recoverWallets");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRestoreRequest {
+    #[serde(rename = "dbPassword")]
+    pub db_password: String,
+    pub archive: String,
+}
+conversation_message!(UiRestoreRequest, "restore");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRestoreResponse {
+    #[serde(rename = "archiveSchemaVersion")]
+    pub archive_schema_version: usize,
+}
+conversation_message!(UiRestoreResponse, "restore");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRotateEarningWalletRequest {
+    #[serde(rename = "dbPassword")]
+    pub db_password: String,
+    #[serde(rename = "newEarningWallet")]
+    pub new_earning_wallet: String,
+    #[serde(rename = "watchPeriodSecOpt")]
+    pub watch_period_sec_opt: Option<u64>,
+}
+conversation_message!(UiRotateEarningWalletRequest, "rotateEarningWallet");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiRotateEarningWalletResponse {
+    #[serde(rename = "oldEarningWallet")]
+    pub old_earning_wallet: String,
+    #[serde(rename = "watchUntil")]
+    pub watch_until: u64,
+}
+conversation_message!(UiRotateEarningWalletResponse, "rotateEarningWallet");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ScanType {
     Payables,
@@ -818,6 +1326,34 @@ pub struct UiSetConfigurationResponse {}
 
 conversation_message!(UiSetConfigurationResponse, "setConfiguration");
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiGracefulShutdownRequest {
+    #[serde(rename = "drainTimeoutSec")]
+    pub drain_timeout_sec: u64,
+    #[serde(rename = "finalScan")]
+    pub final_scan: bool,
+}
+conversation_message!(UiGracefulShutdownRequest, "gracefulShutdown");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiGracefulShutdownResponse {}
+conversation_message!(UiGracefulShutdownResponse, "gracefulShutdown");
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ShutdownStage {
+    Draining,
+    Farewell,
+    Settling,
+    Exiting,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct UiShutdownProgressBroadcast {
+    pub stage: ShutdownStage,
+    pub message: String,
+}
+fire_and_forget_message!(UiShutdownProgressBroadcast, "shutdownProgress");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct UiShutdownRequest {}
 conversation_message!(UiShutdownRequest, "shutdown");
@@ -826,6 +1362,19 @@ conversation_message!(UiShutdownRequest, "shutdown");
 pub struct UiShutdownResponse {}
 conversation_message!(UiShutdownResponse, "shutdown");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiStreamStatusRequest {}
+conversation_message!(UiStreamStatusRequest, "streamStatus");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiStreamStatusResponse {
+    #[serde(rename = "activeStreams")]
+    pub active_streams: u64,
+    #[serde(rename = "tunneledStreams")]
+    pub tunneled_streams: u64,
+}
+conversation_message!(UiStreamStatusResponse, "streamStatus");
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct UiWalletAddressesRequest {
     #[serde(rename = "dbPassword")]
@@ -843,6 +1392,42 @@ pub struct UiWalletAddressesResponse {
 }
 conversation_message!(UiWalletAddressesResponse, "walletAddresses");
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiWalletBalanceBroadcast {
+    #[serde(rename = "gasBalanceGwei")]
+    pub gas_balance_gwei: u64,
+    #[serde(rename = "masqBalanceGwei")]
+    pub masq_balance_gwei: u64,
+    #[serde(rename = "gasThresholdGwei")]
+    pub gas_threshold_gwei: u64,
+    #[serde(rename = "masqThresholdGwei")]
+    pub masq_threshold_gwei: u64,
+    #[serde(rename = "consumingServicesSuspended")]
+    pub consuming_services_suspended: bool,
+}
+fire_and_forget_message!(UiWalletBalanceBroadcast, "walletBalance");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiWalletBalancesRequest {}
+conversation_message!(UiWalletBalancesRequest, "walletBalances");
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UiWalletBalancesResponse {
+    #[serde(rename = "consumingWalletAddress")]
+    pub consuming_wallet_address: String,
+    #[serde(rename = "gasBalanceGwei")]
+    pub gas_balance_gwei: u64,
+    #[serde(rename = "masqBalanceGwei")]
+    pub masq_balance_gwei: u64,
+    #[serde(rename = "gasThresholdGwei")]
+    pub gas_threshold_gwei: u64,
+    #[serde(rename = "masqThresholdGwei")]
+    pub masq_threshold_gwei: u64,
+    #[serde(rename = "consumingServicesSuspended")]
+    pub consuming_services_suspended: bool,
+}
+conversation_message!(UiWalletBalancesResponse, "walletBalances");
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1189,6 +1774,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ui_log_level_from_str_happy_path() {
+        let result: Vec<UiLogLevel> = vec!["error", "WARN", "Info", "dEBUG", "trace"]
+            .into_iter()
+            .map(|s| UiLogLevel::from_str(s).unwrap())
+            .collect();
+
+        assert_eq!(
+            result,
+            vec![
+                UiLogLevel::Error,
+                UiLogLevel::Warn,
+                UiLogLevel::Info,
+                UiLogLevel::Debug,
+                UiLogLevel::Trace,
+            ]
+        );
+        assert!(UiLogLevel::Warn < UiLogLevel::Debug);
+    }
+
+    #[test]
+    fn ui_log_level_from_str_error() {
+        let result = UiLogLevel::from_str("verbose");
+
+        assert_eq!(result, Err("Unrecognized log level: 'verbose'".to_string()));
+    }
+
     #[test]
     fn top_records_ordering_from_str() {
         assert_eq!(
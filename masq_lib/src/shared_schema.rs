@@ -1,3 +1,4 @@
+use crate::blockchains::custom_chains::custom_chain_records;
 use crate::constants::{
     DEFAULT_GAS_PRICE, DEFAULT_UI_PORT, DEV_CHAIN_FULL_IDENTIFIER, ETH_MAINNET_FULL_IDENTIFIER,
     ETH_ROPSTEN_FULL_IDENTIFIER, HIGHEST_USABLE_PORT, LOWEST_USABLE_INSECURE_PORT,
@@ -11,10 +12,12 @@ pub const BLOCKCHAIN_SERVICE_HELP: &str =
     "The Ethereum client you wish to use to provide Blockchain \
      exit services from your MASQ Node (e.g. http://localhost:8545, \
      https://ropsten.infura.io/v3/YOUR-PROJECT-ID, https://mainnet.infura.io/v3/YOUR-PROJECT-ID), \
-     https://polygon-mainnet.infura.io/v3/YOUR-PROJECT-ID";
+     https://polygon-mainnet.infura.io/v3/YOUR-PROJECT-ID. With --chain dev, the value 'embedded' \
+     makes the Node run a small local stand-in blockchain of its own.";
 pub const CHAIN_HELP: &str =
     "The blockchain network MASQ Node will configure itself to use. You must ensure the \
-    Ethereum client specified by --blockchain-service-url communicates with the same blockchain network.";
+    Ethereum client specified by --blockchain-service-url communicates with the same blockchain network. \
+    Additional chains can be defined in a TOML file named by the MASQ_CHAINS_FILE environment variable.";
 pub const CONFIG_FILE_HELP: &str =
     "Optional TOML file containing configuration that doesn't often change. Should contain only \
      scalar items, string or numeric, whose names are exactly the same as the command-line parameters \
@@ -22,6 +25,11 @@ pub const CONFIG_FILE_HELP: &str =
      look for your config file starting in the --data-directory. If you specify an absolute path, \
      --data-directory will be ignored when searching for the config file. A few parameters \
      (such as --config-file, --generate-wallet, and --recover-wallet) must not be specified in a config file.";
+pub const CONFIRMATION_DEPTH_HELP: &str =
+    "How many blocks must be mined on top of a payment to you before the Node credits it. Defaults to \
+     a value chosen for the chain in use. Each scan for payments to you also takes another look at that many \
+     blocks behind the last one scanned, and reverses any payment it credited there that a deeper \
+     reorganization has since dropped from the blockchain.";
 pub const CONSUMING_PRIVATE_KEY_HELP: &str = "The private key for the Ethereum wallet from which you wish to pay \
      other Nodes for routing and exit services. Mostly this is used for testing; be careful using it for real \
      traffic, because this value is very sensitive: anyone who sees it can use it to drain your consuming wallet. \
@@ -37,6 +45,13 @@ pub const DB_PASSWORD_HELP: &str =
      mnemonic seed (if applicable) and your list of previous neighbors. If you don't provide this \
      password, none of the encrypted data in your database will be used. This is a secret;\
      providing it on the command line or in a config file may be insecure";
+pub const DELINQUENCY_GRACE_PERIOD_HELP: &str =
+    "How many seconds a debtor Node whose debt to you has become delinquent is warned before its wallet is \
+     banned. A debtor that pays enough during the grace period is never banned. The default is 0: delinquent \
+     debtors are banned as soon as they're found.";
+pub const DELINQUENCY_NOTICES_HELP: &str =
+    "If 'on', whenever the Node warns a delinquent debtor it also sends a notice of the debt and the coming \
+     ban to the debtor Node, if it can be found in the Neighborhood by its wallet. Defaults to 'off'.";
 pub const DNS_SERVERS_HELP: &str =
     "IP addresses of DNS Servers for host name look-up while providing exit \
      services for other MASQ Nodes (e.g. 1.0.0.1,1.1.1.1,8.8.8.8,9.9.9.9, etc.)";
@@ -45,6 +60,17 @@ pub const EARNING_WALLET_HELP: &str =
      (case-insensitive). If you already have a derivation-path earning wallet, don't supply this. \
      If you have supplied an earning wallet address before, either don't supply it again or be \
      careful to supply exactly the same one you supplied before.";
+pub const EXTERNAL_SIGNER_ADDRESS_HELP: &str =
+    "The address of the consuming wallet whose private key is held by the external signer named by \
+     --external-signer-url. Addresses must begin with 0x followed by 40 hexadecimal digits (case-insensitive). \
+     Required if --external-signer-url is supplied.";
+pub const EXTERNAL_SIGNER_URL_HELP: &str =
+    "URL of a Clef-style external signer, reachable over HTTP JSON-RPC, to which the Node delegates the \
+     signing of payments from its consuming wallet, so that the consuming wallet's private key can be kept \
+     in your own key-management setup rather than in the Node's database. If you supply this, you must \
+     also supply --external-signer-address, and you must not supply --consuming-private-key. Routes through \
+     other Nodes must be paid for with proofs signed by the consuming wallet itself, so this can only be used \
+     with --neighborhood-mode zero-hop.";
 pub const IP_ADDRESS_HELP: &str = "The public IP address of your MASQ Node: that is, the IPv4 \
      address at which other Nodes can contact yours. If you're running your Node behind \
      a router, this will be the IP address of the router. If this IP address starts with 192.168 or 10.0, \
@@ -56,6 +82,12 @@ pub const LOG_LEVEL_HELP: &str =
      generates a lot of log traffic. This will both consume your disk space and degrade your Node's performance. \
      You should probably not specify a level higher than the default unless you have security concerns about \
      persistent logs being kept on your computer: if your Node crashes, it's good to know why.";
+pub const LOW_BALANCE_THRESHOLDS_HELP: &str =
+    "The consuming-wallet balances, in gwei, below which the Node warns you and stops buying routing and \
+     exit services, so that it never runs up debts it can't pay. Specify the gas-currency threshold and the \
+     MASQ-token threshold separated by a vertical bar, like 10000000|1000000000. The Node checks the balances \
+     every few minutes; once both are back above their thresholds, consuming services resume. If you don't \
+     specify thresholds, the balances aren't monitored.";
 pub const NEIGHBORS_HELP: &str = "One or more Node descriptors for running Nodes in the MASQ \
      One or more Node descriptors for active Nodes in the MASQ Network to which you'd like your Node to connect \
      on startup. A Node descriptor looks similar to one of these:\n\n\
@@ -246,7 +278,7 @@ pub fn chain_arg<'a>() -> Arg<'a, 'a> {
         .value_name("CHAIN")
         .min_values(0)
         .max_values(1)
-        .possible_values(official_chain_names())
+        .possible_values(&chain_names())
         .help(CHAIN_HELP)
 }
 
@@ -260,6 +292,18 @@ pub fn official_chain_names() -> &'static [&'static str] {
     ]
 }
 
+pub fn chain_names() -> Vec<&'static str> {
+    official_chain_names()
+        .iter()
+        .copied()
+        .chain(
+            custom_chain_records()
+                .into_iter()
+                .map(|record| record.literal_identifier),
+        )
+        .collect()
+}
+
 pub fn db_password_arg(help: &str) -> Arg {
     Arg::with_name("db-password")
         .long("db-password")
@@ -347,6 +391,15 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
             .help(&CLANDESTINE_PORT_HELP),
     )
     .arg(config_file_arg())
+    .arg(
+        Arg::with_name("confirmation-depth")
+            .long("confirmation-depth")
+            .value_name("BLOCKS")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_u64)
+            .help(CONFIRMATION_DEPTH_HELP),
+    )
     .arg(
         Arg::with_name("consuming-private-key")
             .long("consuming-private-key")
@@ -368,6 +421,24 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
     )
     .arg(data_directory_arg())
     .arg(db_password_arg(DB_PASSWORD_HELP))
+    .arg(
+        Arg::with_name("delinquency-grace-period")
+            .long("delinquency-grace-period")
+            .value_name("SECONDS")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_u64)
+            .help(DELINQUENCY_GRACE_PERIOD_HELP),
+    )
+    .arg(
+        Arg::with_name("delinquency-notices")
+            .long("delinquency-notices")
+            .value_name("DELINQUENCY-NOTICES")
+            .min_values(0)
+            .max_values(1)
+            .possible_values(&["on", "off"])
+            .help(DELINQUENCY_NOTICES_HELP),
+    )
     .arg(
         Arg::with_name("dns-servers")
             .long("dns-servers")
@@ -381,6 +452,23 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
         EARNING_WALLET_HELP,
         common_validators::validate_ethereum_address,
     ))
+    .arg(
+        Arg::with_name("external-signer-address")
+            .long("external-signer-address")
+            .value_name("EXTERNAL-SIGNER-ADDRESS")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_ethereum_address)
+            .help(EXTERNAL_SIGNER_ADDRESS_HELP),
+    )
+    .arg(
+        Arg::with_name("external-signer-url")
+            .long("external-signer-url")
+            .value_name("URL")
+            .min_values(0)
+            .max_values(1)
+            .help(EXTERNAL_SIGNER_URL_HELP),
+    )
     .arg(
         Arg::with_name("fake-public-key")
             .long("fake-public-key")
@@ -417,6 +505,15 @@ pub fn shared_app(head: App<'static, 'static>) -> App<'static, 'static> {
             .case_insensitive(true)
             .help(LOG_LEVEL_HELP),
     )
+    .arg(
+        Arg::with_name("low-balance-thresholds")
+            .long("low-balance-thresholds")
+            .value_name("GAS-GWEI|MASQ-GWEI")
+            .min_values(0)
+            .max_values(1)
+            .validator(common_validators::validate_separate_u64_values)
+            .help(LOW_BALANCE_THRESHOLDS_HELP),
+    )
     .arg(
         Arg::with_name("mapping-protocol")
             .long("mapping-protocol")
@@ -602,6 +699,13 @@ pub mod common_validators {
         }
     }
 
+    pub fn validate_u64(str: String) -> Result<(), String> {
+        match str::parse::<u64>(&str) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(str),
+        }
+    }
+
     pub fn validate_separate_u64_values(values_with_delimiters: String) -> Result<(), String> {
         values_with_delimiters.split('|').try_for_each(|segment| {
             segment
@@ -669,6 +773,7 @@ mod tests {
 
     use super::*;
     use crate::blockchains::chains::Chain;
+    use crate::blockchains::custom_chains::{register_custom_chains, CustomChainDefinition};
     use crate::shared_schema::common_validators::validate_non_zero_u16;
     use crate::shared_schema::{common_validators, official_chain_names};
 
@@ -679,12 +784,14 @@ mod tests {
             "The Ethereum client you wish to use to provide Blockchain \
              exit services from your MASQ Node (e.g. http://localhost:8545, \
              https://ropsten.infura.io/v3/YOUR-PROJECT-ID, https://mainnet.infura.io/v3/YOUR-PROJECT-ID), \
-             https://polygon-mainnet.infura.io/v3/YOUR-PROJECT-ID"
+             https://polygon-mainnet.infura.io/v3/YOUR-PROJECT-ID. With --chain dev, the value 'embedded' \
+             makes the Node run a small local stand-in blockchain of its own."
         );
         assert_eq!(
             CHAIN_HELP,
             "The blockchain network MASQ Node will configure itself to use. You must ensure the \
-             Ethereum client specified by --blockchain-service-url communicates with the same blockchain network."
+             Ethereum client specified by --blockchain-service-url communicates with the same blockchain network. \
+             Additional chains can be defined in a TOML file named by the MASQ_CHAINS_FILE environment variable."
         );
         assert_eq!(
             CONFIG_FILE_HELP,
@@ -695,6 +802,13 @@ mod tests {
              --data-directory will be ignored when searching for the config file. A few parameters \
              (such as --config-file, --generate-wallet, and --recover-wallet) must not be specified in a config file."
         );
+        assert_eq!(
+            CONFIRMATION_DEPTH_HELP,
+            "How many blocks must be mined on top of a payment to you before the Node credits it. Defaults to \
+             a value chosen for the chain in use. Each scan for payments to you also takes another look at that many \
+             blocks behind the last one scanned, and reverses any payment it credited there that a deeper \
+             reorganization has since dropped from the blockchain."
+        );
         assert_eq!(
             CONSUMING_PRIVATE_KEY_HELP,
             "The private key for the Ethereum wallet from which you wish to pay \
@@ -717,6 +831,17 @@ mod tests {
              password, none of the encrypted data in your database will be used. This is a secret;\
              providing it on the command line or in a config file may be insecure"
         );
+        assert_eq!(
+            DELINQUENCY_GRACE_PERIOD_HELP,
+            "How many seconds a debtor Node whose debt to you has become delinquent is warned before its wallet is \
+             banned. A debtor that pays enough during the grace period is never banned. The default is 0: delinquent \
+             debtors are banned as soon as they're found."
+        );
+        assert_eq!(
+            DELINQUENCY_NOTICES_HELP,
+            "If 'on', whenever the Node warns a delinquent debtor it also sends a notice of the debt and the coming \
+             ban to the debtor Node, if it can be found in the Neighborhood by its wallet. Defaults to 'off'."
+        );
         assert_eq!(
             DNS_SERVERS_HELP,
             "IP addresses of DNS Servers for host name look-up while providing exit \
@@ -729,6 +854,21 @@ mod tests {
              If you have supplied an earning wallet address before, either don't supply it again or be \
              careful to supply exactly the same one you supplied before."
         );
+        assert_eq!(
+            EXTERNAL_SIGNER_ADDRESS_HELP,
+            "The address of the consuming wallet whose private key is held by the external signer named by \
+             --external-signer-url. Addresses must begin with 0x followed by 40 hexadecimal digits (case-insensitive). \
+             Required if --external-signer-url is supplied."
+        );
+        assert_eq!(
+            EXTERNAL_SIGNER_URL_HELP,
+            "URL of a Clef-style external signer, reachable over HTTP JSON-RPC, to which the Node delegates the \
+             signing of payments from its consuming wallet, so that the consuming wallet's private key can be kept \
+             in your own key-management setup rather than in the Node's database. If you supply this, you must \
+             also supply --external-signer-address, and you must not supply --consuming-private-key. Routes through \
+             other Nodes must be paid for with proofs signed by the consuming wallet itself, so this can only be used \
+             with --neighborhood-mode zero-hop."
+        );
         assert_eq!(
             IP_ADDRESS_HELP,
             "The public IP address of your MASQ Node: that is, the IPv4 \
@@ -744,6 +884,14 @@ mod tests {
              generates a lot of log traffic. This will both consume your disk space and degrade your Node's performance. \
              You should probably not specify a level higher than the default unless you have security concerns about \
              persistent logs being kept on your computer: if your Node crashes, it's good to know why.");
+        assert_eq!(
+            LOW_BALANCE_THRESHOLDS_HELP,
+            "The consuming-wallet balances, in gwei, below which the Node warns you and stops buying routing and \
+             exit services, so that it never runs up debts it can't pay. Specify the gas-currency threshold and the \
+             MASQ-token threshold separated by a vertical bar, like 10000000|1000000000. The Node checks the balances \
+             every few minutes; once both are back above their thresholds, consuming services resume. If you don't \
+             specify thresholds, the balances aren't monitored."
+        );
         assert_eq!(
             NEIGHBORS_HELP,
             "One or more Node descriptors for running Nodes in the MASQ \
@@ -1133,6 +1281,27 @@ mod tests {
         assert_eq!(result, Err("garbage".to_string()))
     }
 
+    #[test]
+    fn validate_u64_happy_path() {
+        let result = common_validators::validate_u64("0".to_string());
+
+        assert_eq!(result, Ok(()))
+    }
+
+    #[test]
+    fn validate_u64_sad_path_with_negative() {
+        let result = common_validators::validate_u64("-1".to_string());
+
+        assert_eq!(result, Err("-1".to_string()))
+    }
+
+    #[test]
+    fn validate_u64_sad_path_just_junk() {
+        let result = common_validators::validate_u64("garbage".to_string());
+
+        assert_eq!(result, Err("garbage".to_string()))
+    }
+
     #[test]
     fn official_chain_names_are_reliable() {
         let mut iterator = official_chain_names().iter();
@@ -1143,4 +1312,25 @@ mod tests {
         assert_eq!(Chain::from(*iterator.next().unwrap()), Chain::Dev);
         assert_eq!(iterator.next(), None)
     }
+
+    #[test]
+    fn chain_arg_accepts_registered_custom_chains() {
+        register_custom_chains(vec![CustomChainDefinition {
+            name: "schema-net".to_string(),
+            chain_id: 7_100_001,
+            contract: "0x59882e4a8f5d24643d4dda422922a870f1b3e664".to_string(),
+            contract_creation_block: 0,
+            family: "eth".to_string(),
+            gas_limit_base: None,
+            confirmation_depth: None,
+            disperse_contract: None,
+        }])
+        .unwrap();
+        let app = App::new("test").arg(chain_arg());
+
+        let result = app.get_matches_from_safe(vec!["test", "--chain", "schema-net"]);
+
+        assert_eq!(result.unwrap().value_of("chain"), Some("schema-net"));
+        assert!(chain_names().starts_with(official_chain_names()));
+    }
 }
@@ -460,6 +460,7 @@ mod tests {
         let conversation_number_two_response = UiCheckPasswordResponse { matches: true };
         let broadcast_number_one = UiConfigurationChangedBroadcast {}.tmb(0);
         let broadcast_number_two = UiNodeCrashedBroadcast {
+            instance: "default".to_string(),
             process_id: 0,
             crash_reason: CrashReason::NoInformation,
         }
@@ -1,13 +1,16 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 use crate::accountant::database_access_objects::dao_utils::{
-    DaoFactoryReal, VigilantRusqliteFlatten,
+    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten,
 };
 use crate::database::connection_wrapper::ConnectionWrapper;
 use crate::sub_lib::wallet::Wallet;
 use lazy_static::lazy_static;
-use rusqlite::{Error, ErrorCode, ToSql};
-use std::collections::HashSet;
+use masq_lib::utils::ExpectValue;
+use rusqlite::{Error, ErrorCode, Row, ToSql};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
 use std::sync::RwLock;
+use std::time::SystemTime;
 
 lazy_static! {
     pub static ref BAN_CACHE: BannedCache = BannedCache::default();
@@ -64,10 +67,58 @@ impl BannedCacheLoader for BannedCacheLoaderReal {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BanReason {
+    Delinquency,
+    Manual,
+}
+
+impl BanReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Delinquency => "delinquency",
+            Self::Manual => "manual",
+        }
+    }
+}
+
+impl FromStr for BanReason {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "delinquency" => Ok(Self::Delinquency),
+            "manual" => Ok(Self::Manual),
+            x => Err(format!("Unrecognized ban reason: '{}'", x)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BannedWallet {
+    pub wallet: Wallet,
+    pub reason: BanReason,
+    pub note_opt: Option<String>,
+    // Bans from before the time was recorded don't have one
+    pub timestamp_opt: Option<SystemTime>,
+}
+
 pub trait BannedDao: Send {
-    fn ban_list(&self) -> Vec<Wallet>;
-    fn ban(&self, wallet: &Wallet);
-    fn unban(&self, wallet: &Wallet);
+    fn ban_list(&self) -> Vec<BannedWallet>;
+    // Both report whether the ban list actually changed
+    fn ban(
+        &self,
+        wallet: &Wallet,
+        reason: BanReason,
+        note_opt: Option<&str>,
+        timestamp: SystemTime,
+    ) -> bool;
+    fn unban(&self, wallet: &Wallet) -> bool;
+    // Delinquent debtors still within their grace period, with the time each was warned
+    fn warnings(&self) -> HashMap<Wallet, SystemTime>;
+    // Both report whether the warning list actually changed
+    fn warn(&self, wallet: &Wallet, timestamp: SystemTime) -> bool;
+    fn unwarn(&self, wallet: &Wallet) -> bool;
 }
 
 pub trait BannedDaoFactory {
@@ -88,35 +139,62 @@ impl BannedDaoReal {
     pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
         Self { conn }
     }
+
+    fn banned_wallet_from_row(row: &Row) -> rusqlite::Result<BannedWallet> {
+        let wallet: Wallet = row.get(0).expectv("wallet");
+        let reason: String = row.get(1).expectv("reason");
+        let note_opt: Option<String> = row.get(2).expectv("note");
+        let timestamp_opt: Option<i64> = row.get(3).expectv("timestamp");
+        Ok(BannedWallet {
+            wallet,
+            reason: BanReason::from_str(&reason).expect("Ban list corrupt"),
+            note_opt,
+            timestamp_opt: timestamp_opt.map(from_time_t),
+        })
+    }
 }
 
 impl BannedDao for BannedDaoReal {
-    fn ban_list(&self) -> Vec<Wallet> {
+    fn ban_list(&self) -> Vec<BannedWallet> {
         let mut stmt = self
             .conn
-            .prepare("select wallet_address from banned")
+            .prepare(
+                "select wallet_address, reason, note, timestamp from banned order by wallet_address",
+            )
             .expect("Failed to prepare a statement");
-        stmt.query_map([], |row| row.get(0))
+        stmt.query_map([], Self::banned_wallet_from_row)
             .expect("Couldn't retrieve delinquency-ban list: database corrupt")
             .vigilant_flatten()
             .collect()
     }
 
-    fn ban(&self, wallet: &Wallet) {
+    fn ban(
+        &self,
+        wallet: &Wallet,
+        reason: BanReason,
+        note_opt: Option<&str>,
+        timestamp: SystemTime,
+    ) -> bool {
         if BAN_CACHE.is_banned(wallet) {
-            return;
+            return false;
         }
 
         let mut stmt = self
             .conn
-            .prepare("insert into banned (wallet_address) values (?)")
+            .prepare(
+                "insert into banned (wallet_address, reason, note, timestamp) values (?, ?, ?, ?)",
+            )
             .expect("Failed to prepare a statement");
-        let params: &[&dyn ToSql] = &[&wallet];
+        let params: &[&dyn ToSql] = &[&wallet, &reason.as_str(), &note_opt, &to_time_t(timestamp)];
         match stmt.execute(params) {
-            Ok(_) => BAN_CACHE.insert(wallet.clone()),
+            Ok(_) => {
+                BAN_CACHE.insert(wallet.clone());
+                true
+            }
             Err(e) => match e {
                 Error::SqliteFailure(e, _) if e.code == ErrorCode::ConstraintViolation => {
-                    BAN_CACHE.insert(wallet.clone())
+                    BAN_CACHE.insert(wallet.clone());
+                    false
                 }
                 _ => panic!(
                     "Could not initiate delinquency ban for {} because of database corruption: {}",
@@ -126,9 +204,9 @@ impl BannedDao for BannedDaoReal {
         }
     }
 
-    fn unban(&self, wallet: &Wallet) {
+    fn unban(&self, wallet: &Wallet) -> bool {
         if !BAN_CACHE.is_banned(wallet) {
-            return;
+            return false;
         }
 
         let mut stmt = self
@@ -137,13 +215,64 @@ impl BannedDao for BannedDaoReal {
             .expect("Failed to prepare a statement");
         let params: &[&dyn ToSql] = &[&wallet];
         match stmt.execute(params) {
-            Ok(_) => BAN_CACHE.remove(wallet),
+            Ok(_) => {
+                BAN_CACHE.remove(wallet);
+                true
+            }
             Err(e) => panic!(
                 "Could not terminate delinquency ban for {} because of database corruption: {}",
                 wallet, e
             ),
         }
     }
+
+    fn warnings(&self) -> HashMap<Wallet, SystemTime> {
+        let mut stmt = self
+            .conn
+            .prepare("select wallet_address, timestamp from delinquency_warning")
+            .expect("Failed to prepare a statement");
+        stmt.query_map([], |row| {
+            let wallet: Wallet = row.get(0).expectv("wallet");
+            let timestamp: i64 = row.get(1).expectv("timestamp");
+            Ok((wallet, from_time_t(timestamp)))
+        })
+        .expect("Couldn't retrieve delinquency warnings: database corrupt")
+        .vigilant_flatten()
+        .collect()
+    }
+
+    fn warn(&self, wallet: &Wallet, timestamp: SystemTime) -> bool {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "insert into delinquency_warning (wallet_address, timestamp) values (?, ?) \
+                 on conflict (wallet_address) do nothing",
+            )
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&wallet, &to_time_t(timestamp)];
+        match stmt.execute(params) {
+            Ok(rows) => rows > 0,
+            Err(e) => panic!(
+                "Could not warn {} of delinquency because of database corruption: {}",
+                wallet, e
+            ),
+        }
+    }
+
+    fn unwarn(&self, wallet: &Wallet) -> bool {
+        let mut stmt = self
+            .conn
+            .prepare("delete from delinquency_warning where wallet_address = ?")
+            .expect("Failed to prepare a statement");
+        let params: &[&dyn ToSql] = &[&wallet];
+        match stmt.execute(params) {
+            Ok(rows) => rows > 0,
+            Err(e) => panic!(
+                "Could not withdraw delinquency warning for {} because of database corruption: {}",
+                wallet, e
+            ),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -171,7 +300,14 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("donalddrumph"));
+        let result = subject.ban(
+            &make_wallet("donalddrumph"),
+            BanReason::Delinquency,
+            None,
+            SystemTime::now(),
+        );
+
+        assert_eq!(result, true);
 
         let conn = db_initializer
             .initialize(&home_dir, DbInitializationConfig::test_default())
@@ -199,11 +335,136 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("no_duplicate_wallets"));
-        subject.ban(&make_wallet("no_duplicate_wallets"));
+        let first_result = subject.ban(
+            &make_wallet("no_duplicate_wallets"),
+            BanReason::Delinquency,
+            None,
+            from_time_t(1_000),
+        );
+        let second_result = subject.ban(
+            &make_wallet("no_duplicate_wallets"),
+            BanReason::Manual,
+            Some("again"),
+            from_time_t(2_000),
+        );
 
+        assert_eq!(first_result, true);
+        assert_eq!(second_result, false);
         let ban_list = subject.ban_list();
-        assert_eq!(vec![make_wallet("no_duplicate_wallets")], ban_list);
+        assert_eq!(
+            ban_list,
+            vec![BannedWallet {
+                wallet: make_wallet("no_duplicate_wallets"),
+                reason: BanReason::Delinquency,
+                note_opt: None,
+                timestamp_opt: Some(from_time_t(1_000)),
+            }]
+        );
+    }
+
+    #[test]
+    fn ban_list_reports_reasons_notes_and_timestamps() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "banned_dao",
+            "ban_list_reports_reasons_notes_and_timestamps",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let legacy_wallet = make_wallet("legacy_ban");
+        let params: &[&dyn ToSql] = &[&legacy_wallet];
+        conn.prepare("insert into banned (wallet_address) values (?)")
+            .unwrap()
+            .execute(params)
+            .unwrap();
+        let subject = BannedDaoReal::new(conn);
+        subject.ban(
+            &make_wallet("manual_ban"),
+            BanReason::Manual,
+            Some("spammer"),
+            from_time_t(3_000),
+        );
+        subject.ban(
+            &make_wallet("delinquency_ban"),
+            BanReason::Delinquency,
+            None,
+            from_time_t(4_000),
+        );
+
+        let result = subject.ban_list();
+
+        let mut expected = vec![
+            BannedWallet {
+                wallet: legacy_wallet,
+                reason: BanReason::Delinquency,
+                note_opt: None,
+                timestamp_opt: None,
+            },
+            BannedWallet {
+                wallet: make_wallet("manual_ban"),
+                reason: BanReason::Manual,
+                note_opt: Some("spammer".to_string()),
+                timestamp_opt: Some(from_time_t(3_000)),
+            },
+            BannedWallet {
+                wallet: make_wallet("delinquency_ban"),
+                reason: BanReason::Delinquency,
+                note_opt: None,
+                timestamp_opt: Some(from_time_t(4_000)),
+            },
+        ];
+        expected.sort_by_key(|banned| banned.wallet.to_string());
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn warnings_can_be_given_listed_and_withdrawn() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "banned_dao",
+            "warnings_can_be_given_listed_and_withdrawn",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = BannedDaoReal::new(conn);
+        let first_wallet = make_wallet("first_debtor");
+        let second_wallet = make_wallet("second_debtor");
+
+        let first_warning = subject.warn(&first_wallet, from_time_t(1_000));
+        let second_warning = subject.warn(&second_wallet, from_time_t(2_000));
+        let repeated_warning = subject.warn(&first_wallet, from_time_t(3_000));
+        let warnings_before = subject.warnings();
+        let withdrawal = subject.unwarn(&first_wallet);
+        let repeated_withdrawal = subject.unwarn(&first_wallet);
+        let warnings_after = subject.warnings();
+
+        assert_eq!(first_warning, true);
+        assert_eq!(second_warning, true);
+        assert_eq!(repeated_warning, false);
+        assert_eq!(
+            warnings_before,
+            HashMap::from([
+                (first_wallet, from_time_t(1_000)),
+                (second_wallet.clone(), from_time_t(2_000)),
+            ])
+        );
+        assert_eq!(withdrawal, true);
+        assert_eq!(repeated_withdrawal, false);
+        assert_eq!(
+            warnings_after,
+            HashMap::from([(second_wallet, from_time_t(2_000))])
+        );
+    }
+
+    #[test]
+    fn ban_reasons_survive_a_round_trip_through_strings() {
+        [BanReason::Delinquency, BanReason::Manual]
+            .iter()
+            .for_each(|reason| assert_eq!(BanReason::from_str(reason.as_str()), Ok(*reason)));
+        assert_eq!(
+            BanReason::from_str("booga"),
+            Err("Unrecognized ban reason: 'booga'".to_string())
+        );
     }
 
     #[test]
@@ -218,7 +479,12 @@ mod tests {
             BannedDaoReal::new(conn)
         };
 
-        subject.ban(&make_wallet("forgot_to_init"));
+        subject.ban(
+            &make_wallet("forgot_to_init"),
+            BanReason::Delinquency,
+            None,
+            SystemTime::now(),
+        );
     }
 
     #[test]
@@ -240,7 +506,9 @@ mod tests {
         BAN_CACHE.insert(wallet.clone());
         let subject = BannedDaoReal::new(conn);
 
-        subject.unban(wallet);
+        let result = subject.unban(wallet);
+
+        assert_eq!(result, true);
 
         let conn = db_initializer
             .initialize(&home_dir, DbInitializationConfig::test_default())
@@ -264,9 +532,9 @@ mod tests {
             .unwrap();
         let subject = BannedDaoReal::new(conn);
 
-        subject.unban(&make_wallet("hey_im_not_banned"));
+        let result = subject.unban(&make_wallet("hey_im_not_banned"));
 
-        // No panic: test passes
+        assert_eq!(result, false);
     }
 
     #[test]
@@ -310,7 +578,12 @@ mod tests {
         let subject = BannedDaoReal::new(conn);
 
         let ban_me_baby = make_wallet("BAN_ME_BABY");
-        subject.ban(&ban_me_baby.clone());
+        subject.ban(
+            &ban_me_baby.clone(),
+            BanReason::Delinquency,
+            None,
+            SystemTime::now(),
+        );
 
         assert!(BAN_CACHE.is_banned(&ban_me_baby))
     }
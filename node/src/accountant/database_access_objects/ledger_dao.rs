@@ -0,0 +1,662 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::big_int_processing::big_int_divider::BigIntDivider;
+use crate::accountant::checked_conversion;
+use crate::accountant::database_access_objects::dao_utils::{
+    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten,
+};
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::utils::ExpectValue;
+use rusqlite::{Connection, OptionalExtension, Row, ToSql};
+use std::str::FromStr;
+use std::time::SystemTime;
+use web3::types::H256;
+
+// Service charges between this Node and one wallet are summed into a single entry per bucket of this
+// many seconds, aligned to the Unix epoch; otherwise every CORES package would add a row
+pub const LEDGER_SERVICE_BUCKET_S: u64 = 600;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LedgerEntryKind {
+    ServiceProvided,
+    ServiceConsumed,
+    PaymentSent,
+    PaymentReceived,
+    DebtWrittenOff,
+}
+
+impl LedgerEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ServiceProvided => "service_provided",
+            Self::ServiceConsumed => "service_consumed",
+            Self::PaymentSent => "payment_sent",
+            Self::PaymentReceived => "payment_received",
+            Self::DebtWrittenOff => "debt_written_off",
+        }
+    }
+}
+
+impl FromStr for LedgerEntryKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "service_provided" => Ok(Self::ServiceProvided),
+            "service_consumed" => Ok(Self::ServiceConsumed),
+            "payment_sent" => Ok(Self::PaymentSent),
+            "payment_received" => Ok(Self::PaymentReceived),
+            "debt_written_off" => Ok(Self::DebtWrittenOff),
+            x => Err(format!("Unrecognized ledger entry kind: '{}'", x)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PaymentOutcome {
+    Submitted,
+    Failed,
+    Confirmed,
+    Reverted,
+}
+
+impl PaymentOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submitted => "submitted",
+            Self::Failed => "failed",
+            Self::Confirmed => "confirmed",
+            Self::Reverted => "reverted",
+        }
+    }
+}
+
+impl FromStr for PaymentOutcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "submitted" => Ok(Self::Submitted),
+            "failed" => Ok(Self::Failed),
+            "confirmed" => Ok(Self::Confirmed),
+            "reverted" => Ok(Self::Reverted),
+            x => Err(format!("Unrecognized payment outcome: '{}'", x)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LedgerEntry {
+    pub timestamp: SystemTime,
+    pub wallet: Wallet,
+    pub kind: LedgerEntryKind,
+    pub amount_wei: u128,
+    pub transaction_hash_opt: Option<H256>,
+    pub nonce_opt: Option<u64>,
+    pub gas_price_gwei_opt: Option<u64>,
+    pub block_number_opt: Option<u64>,
+    pub outcome_opt: Option<PaymentOutcome>,
+}
+
+impl LedgerEntry {
+    pub fn new(
+        timestamp: SystemTime,
+        wallet: Wallet,
+        kind: LedgerEntryKind,
+        amount_wei: u128,
+    ) -> Self {
+        Self {
+            timestamp,
+            wallet,
+            kind,
+            amount_wei,
+            transaction_hash_opt: None,
+            nonce_opt: None,
+            gas_price_gwei_opt: None,
+            block_number_opt: None,
+            outcome_opt: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LedgerDaoError {
+    InsertionFailed(String),
+    PaymentNotFound(H256),
+}
+
+// Payments are append-only: a later outcome of a payment is a new entry, never an update.
+// Service charges accumulate in the entry for their bucket until the bucket is over.
+pub trait LedgerDao {
+    fn record(&mut self, entries: &[LedgerEntry]) -> Result<(), LedgerDaoError>;
+    fn record_payment_outcome(
+        &self,
+        hash: H256,
+        outcome: PaymentOutcome,
+        block_number_opt: Option<u64>,
+        timestamp: SystemTime,
+    ) -> Result<(), LedgerDaoError>;
+    fn history(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<LedgerEntry>;
+}
+
+pub trait LedgerDaoFactory {
+    fn make(&self) -> Box<dyn LedgerDao>;
+}
+
+impl LedgerDaoFactory for DaoFactoryReal {
+    fn make(&self) -> Box<dyn LedgerDao> {
+        Box::new(LedgerDaoReal::new(self.make_connection()))
+    }
+}
+
+pub struct LedgerDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl LedgerDao for LedgerDaoReal {
+    fn record(&mut self, entries: &[LedgerEntry]) -> Result<(), LedgerDaoError> {
+        let xactn = self
+            .conn
+            .transaction()
+            .map_err(|e| LedgerDaoError::InsertionFailed(e.to_string()))?;
+        entries.iter().try_for_each(|entry| match entry.kind {
+            LedgerEntryKind::ServiceProvided | LedgerEntryKind::ServiceConsumed => {
+                Self::accumulate_service_charge(&xactn, entry)
+            }
+            _ => Self::insert_entry(&xactn, entry, to_time_t(entry.timestamp)),
+        })?;
+        xactn
+            .commit()
+            .map_err(|e| LedgerDaoError::InsertionFailed(e.to_string()))
+    }
+
+    fn record_payment_outcome(
+        &self,
+        hash: H256,
+        outcome: PaymentOutcome,
+        block_number_opt: Option<u64>,
+        timestamp: SystemTime,
+    ) -> Result<(), LedgerDaoError> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "insert into ledger (timestamp, wallet_address, kind, amount_high_b, amount_low_b, \
+                 transaction_hash, nonce, gas_price_gwei, block_number, outcome) \
+                 select ?, wallet_address, kind, amount_high_b, amount_low_b, transaction_hash, \
+                 nonce, gas_price_gwei, ?, ? from ledger where transaction_hash = ? and kind = ? \
+                 order by rowid limit 1",
+            )
+            .expect("Internal error");
+        let time_t = to_time_t(timestamp);
+        let block_number_opt = block_number_opt.map(checked_conversion::<u64, i64>);
+        let hash_str = format!("{:?}", hash);
+        let params: &[&dyn ToSql] = &[
+            &time_t,
+            &block_number_opt,
+            &outcome.as_str(),
+            &hash_str,
+            &LedgerEntryKind::PaymentSent.as_str(),
+        ];
+        match stmt.execute(params) {
+            Ok(0) => Err(LedgerDaoError::PaymentNotFound(hash)),
+            Ok(_) => Ok(()),
+            Err(e) => Err(LedgerDaoError::InsertionFailed(e.to_string())),
+        }
+    }
+
+    fn history(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<LedgerEntry> {
+        let wallet_clause = match wallet_opt {
+            Some(_) => " and wallet_address = ?",
+            None => "",
+        };
+        let sql = format!(
+            "select timestamp, wallet_address, kind, amount_high_b, amount_low_b, transaction_hash, \
+             nonce, gas_price_gwei, block_number, outcome from ledger \
+             where timestamp >= ? and timestamp <= ?{} order by timestamp, rowid limit ? offset ?",
+            wallet_clause
+        );
+        let from_t = to_time_t(from);
+        let to_t = to_time_t(to);
+        let offset = offset.min(i64::MAX as u64) as i64;
+        let limit = limit.min(i64::MAX as u64) as i64;
+        let mut params: Vec<&dyn ToSql> = vec![&from_t, &to_t];
+        if let Some(wallet) = wallet_opt {
+            params.push(wallet)
+        }
+        params.push(&limit);
+        params.push(&offset);
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
+        stmt.query_map(params.as_slice(), Self::entry_from_row)
+            .expect("Couldn't retrieve the ledger: database corrupt")
+            .vigilant_flatten()
+            .collect()
+    }
+}
+
+impl LedgerDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn insert_entry(
+        conn: &Connection,
+        entry: &LedgerEntry,
+        timestamp: i64,
+    ) -> Result<(), LedgerDaoError> {
+        let (high_bytes, low_bytes) =
+            BigIntDivider::deconstruct(checked_conversion::<u128, i128>(entry.amount_wei));
+        let hash_opt = entry.transaction_hash_opt.map(|hash| format!("{:?}", hash));
+        let nonce_opt = entry.nonce_opt.map(checked_conversion::<u64, i64>);
+        let gas_price_opt = entry.gas_price_gwei_opt.map(checked_conversion::<u64, i64>);
+        let block_number_opt = entry.block_number_opt.map(checked_conversion::<u64, i64>);
+        let outcome_opt = entry.outcome_opt.map(|outcome| outcome.as_str());
+        let params: &[&dyn ToSql] = &[
+            &timestamp,
+            &entry.wallet,
+            &entry.kind.as_str(),
+            &high_bytes,
+            &low_bytes,
+            &hash_opt,
+            &nonce_opt,
+            &gas_price_opt,
+            &block_number_opt,
+            &outcome_opt,
+        ];
+        match conn.execute(
+            "insert into ledger (timestamp, wallet_address, kind, amount_high_b, amount_low_b, \
+             transaction_hash, nonce, gas_price_gwei, block_number, outcome) \
+             values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params,
+        ) {
+            Ok(_) => Ok(()),
+            Err(e) => Err(LedgerDaoError::InsertionFailed(e.to_string())),
+        }
+    }
+
+    fn accumulate_service_charge(
+        conn: &Connection,
+        entry: &LedgerEntry,
+    ) -> Result<(), LedgerDaoError> {
+        let bucket_start = Self::bucket_start(to_time_t(entry.timestamp));
+        let key: [&dyn ToSql; 3] = [&entry.wallet, &entry.kind.as_str(), &bucket_start];
+        let existing_opt = conn
+            .prepare(
+                "select rowid, amount_high_b, amount_low_b from ledger \
+                 where wallet_address = ? and kind = ? and timestamp = ?",
+            )
+            .expect("Internal error")
+            .query_row(key.as_slice(), |row| {
+                let rowid: i64 = row.get(0).expectv("rowid");
+                let high_bytes: i64 = row.get(1).expectv("amount high bytes");
+                let low_bytes: i64 = row.get(2).expectv("amount low bytes");
+                Ok((rowid, BigIntDivider::reconstitute(high_bytes, low_bytes)))
+            })
+            .optional()
+            .map_err(|e| LedgerDaoError::InsertionFailed(e.to_string()))?;
+        match existing_opt {
+            None => Self::insert_entry(conn, entry, bucket_start),
+            Some((rowid, amount_wei)) => {
+                let (high_bytes, low_bytes) =
+                    BigIntDivider::deconstruct(amount_wei.saturating_add(checked_conversion::<
+                        u128,
+                        i128,
+                    >(
+                        entry.amount_wei
+                    )));
+                let params: &[&dyn ToSql] = &[&high_bytes, &low_bytes, &rowid];
+                match conn.execute(
+                    "update ledger set amount_high_b = ?, amount_low_b = ? where rowid = ?",
+                    params,
+                ) {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(LedgerDaoError::InsertionFailed(e.to_string())),
+                }
+            }
+        }
+    }
+
+    fn bucket_start(time_t: i64) -> i64 {
+        let bucket_s = LEDGER_SERVICE_BUCKET_S as i64;
+        time_t - time_t.rem_euclid(bucket_s)
+    }
+
+    fn entry_from_row(row: &Row) -> rusqlite::Result<LedgerEntry> {
+        let timestamp: i64 = row.get(0).expectv("timestamp");
+        let wallet: Wallet = row.get(1).expectv("wallet");
+        let kind: String = row.get(2).expectv("kind");
+        let high_bytes: i64 = row.get(3).expectv("amount high bytes");
+        let low_bytes: i64 = row.get(4).expectv("amount low bytes");
+        let hash_opt: Option<String> = row.get(5).expectv("transaction hash");
+        let nonce_opt: Option<i64> = row.get(6).expectv("nonce");
+        let gas_price_opt: Option<i64> = row.get(7).expectv("gas price");
+        let block_number_opt: Option<i64> = row.get(8).expectv("block number");
+        let outcome_opt: Option<String> = row.get(9).expectv("outcome");
+        Ok(LedgerEntry {
+            timestamp: from_time_t(timestamp),
+            wallet,
+            kind: LedgerEntryKind::from_str(&kind).expect("Ledger corrupt"),
+            amount_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                high_bytes, low_bytes,
+            )),
+            transaction_hash_opt: hash_opt.map(|hash| {
+                H256::from_str(&hash[2..]).unwrap_or_else(|e| {
+                    panic!(
+                        "Invalid hash format (\"{}\": {:?}) - database corrupt",
+                        hash, e
+                    )
+                })
+            }),
+            nonce_opt: nonce_opt.map(checked_conversion::<i64, u64>),
+            gas_price_gwei_opt: gas_price_opt.map(checked_conversion::<i64, u64>),
+            block_number_opt: block_number_opt.map(checked_conversion::<i64, u64>),
+            outcome_opt: outcome_opt
+                .map(|outcome| PaymentOutcome::from_str(&outcome).expect("Ledger corrupt")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::test_utils::make_tx_hash;
+    use crate::database::connection_wrapper::ConnectionWrapperReal;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::{Connection, OpenFlags};
+
+    fn make_subject(test_name: &str) -> LedgerDaoReal {
+        let home_dir = ensure_node_home_directory_exists("ledger_dao", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        LedgerDaoReal::new(conn)
+    }
+
+    fn make_sent_payment(timestamp: i64, wallet: &Wallet, hash: H256) -> LedgerEntry {
+        LedgerEntry {
+            transaction_hash_opt: Some(hash),
+            nonce_opt: Some(5),
+            gas_price_gwei_opt: Some(80),
+            outcome_opt: Some(PaymentOutcome::Submitted),
+            ..LedgerEntry::new(
+                from_time_t(timestamp),
+                wallet.clone(),
+                LedgerEntryKind::PaymentSent,
+                u128::MAX >> 2,
+            )
+        }
+    }
+
+    #[test]
+    fn kinds_and_outcomes_survive_a_round_trip_through_strings() {
+        [
+            LedgerEntryKind::ServiceProvided,
+            LedgerEntryKind::ServiceConsumed,
+            LedgerEntryKind::PaymentSent,
+            LedgerEntryKind::PaymentReceived,
+            LedgerEntryKind::DebtWrittenOff,
+        ]
+        .iter()
+        .for_each(|kind| assert_eq!(LedgerEntryKind::from_str(kind.as_str()), Ok(*kind)));
+        [
+            PaymentOutcome::Submitted,
+            PaymentOutcome::Failed,
+            PaymentOutcome::Confirmed,
+            PaymentOutcome::Reverted,
+        ]
+        .iter()
+        .for_each(|outcome| assert_eq!(PaymentOutcome::from_str(outcome.as_str()), Ok(*outcome)));
+        assert_eq!(
+            LedgerEntryKind::from_str("booga"),
+            Err("Unrecognized ledger entry kind: 'booga'".to_string())
+        );
+        assert_eq!(
+            PaymentOutcome::from_str("booga"),
+            Err("Unrecognized payment outcome: 'booga'".to_string())
+        );
+    }
+
+    #[test]
+    fn recorded_entries_come_back_in_history() {
+        let mut subject = make_subject("recorded_entries_come_back_in_history");
+        let wallet = make_wallet("creditor");
+        let service = LedgerEntry::new(
+            from_time_t(1_000),
+            wallet.clone(),
+            LedgerEntryKind::ServiceConsumed,
+            12_345,
+        );
+        let payment = make_sent_payment(2_000, &wallet, make_tx_hash(789));
+
+        subject.record(&[service.clone(), payment.clone()]).unwrap();
+
+        let result = subject.history(None, from_time_t(0), from_time_t(10_000), 0, 100);
+        assert_eq!(
+            result,
+            vec![
+                LedgerEntry {
+                    timestamp: from_time_t(600),
+                    ..service
+                },
+                payment
+            ]
+        );
+    }
+
+    #[test]
+    fn service_charges_are_summed_per_wallet_kind_and_bucket() {
+        let mut subject = make_subject("service_charges_are_summed_per_wallet_kind_and_bucket");
+        let wallet_a = make_wallet("wallet_a");
+        let wallet_b = make_wallet("wallet_b");
+        let charge = |timestamp: i64, wallet: &Wallet, kind: LedgerEntryKind, amount: u128| {
+            LedgerEntry::new(from_time_t(timestamp), wallet.clone(), kind, amount)
+        };
+        subject
+            .record(&[
+                charge(601, &wallet_a, LedgerEntryKind::ServiceProvided, 1),
+                charge(650, &wallet_b, LedgerEntryKind::ServiceProvided, 10),
+                charge(700, &wallet_a, LedgerEntryKind::ServiceConsumed, 100),
+            ])
+            .unwrap();
+
+        subject
+            .record(&[
+                charge(1_199, &wallet_a, LedgerEntryKind::ServiceProvided, 1_000),
+                charge(1_200, &wallet_a, LedgerEntryKind::ServiceProvided, 10_000),
+            ])
+            .unwrap();
+
+        let result = subject.history(None, from_time_t(0), from_time_t(10_000), 0, 100);
+        assert_eq!(
+            result,
+            vec![
+                charge(600, &wallet_a, LedgerEntryKind::ServiceProvided, 1_001),
+                charge(600, &wallet_b, LedgerEntryKind::ServiceProvided, 10),
+                charge(600, &wallet_a, LedgerEntryKind::ServiceConsumed, 100),
+                charge(1_200, &wallet_a, LedgerEntryKind::ServiceProvided, 10_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn history_is_filtered_by_wallet_and_time_range() {
+        let mut subject = make_subject("history_is_filtered_by_wallet_and_time_range");
+        let wallet_a = make_wallet("wallet_a");
+        let wallet_b = make_wallet("wallet_b");
+        let entry = |timestamp: i64, wallet: &Wallet| {
+            LedgerEntry::new(
+                from_time_t(timestamp),
+                wallet.clone(),
+                LedgerEntryKind::PaymentReceived,
+                timestamp as u128,
+            )
+        };
+        subject
+            .record(&[
+                entry(100, &wallet_a),
+                entry(200, &wallet_b),
+                entry(300, &wallet_a),
+                entry(400, &wallet_a),
+            ])
+            .unwrap();
+
+        let by_time = subject.history(None, from_time_t(200), from_time_t(300), 0, 100);
+        let by_wallet_and_time = subject.history(
+            Some(&wallet_a),
+            from_time_t(150),
+            from_time_t(1_000),
+            0,
+            100,
+        );
+
+        assert_eq!(by_time, vec![entry(200, &wallet_b), entry(300, &wallet_a)]);
+        assert_eq!(
+            by_wallet_and_time,
+            vec![entry(300, &wallet_a), entry(400, &wallet_a)]
+        );
+    }
+
+    #[test]
+    fn history_comes_in_pages() {
+        let mut subject = make_subject("history_comes_in_pages");
+        let wallet = make_wallet("debtor");
+        let entry = |timestamp: i64| {
+            LedgerEntry::new(
+                from_time_t(timestamp),
+                wallet.clone(),
+                LedgerEntryKind::PaymentReceived,
+                timestamp as u128,
+            )
+        };
+        subject
+            .record(&[entry(100), entry(200), entry(300), entry(400), entry(500)])
+            .unwrap();
+
+        let first_page = subject.history(None, from_time_t(0), from_time_t(1_000), 0, 2);
+        let second_page = subject.history(None, from_time_t(0), from_time_t(1_000), 2, 2);
+        let last_page = subject.history(None, from_time_t(0), from_time_t(1_000), 4, u64::MAX);
+
+        assert_eq!(first_page, vec![entry(100), entry(200)]);
+        assert_eq!(second_page, vec![entry(300), entry(400)]);
+        assert_eq!(last_page, vec![entry(500)]);
+    }
+
+    #[test]
+    fn record_payment_outcome_appends_a_copy_of_the_sent_payment() {
+        let mut subject = make_subject("record_payment_outcome_appends_a_copy_of_the_sent_payment");
+        let wallet = make_wallet("creditor");
+        let hash = make_tx_hash(456);
+        let payment = make_sent_payment(2_000, &wallet, hash);
+        subject.record(&[payment.clone()]).unwrap();
+
+        let result = subject.record_payment_outcome(
+            hash,
+            PaymentOutcome::Confirmed,
+            Some(1_234_567),
+            from_time_t(2_500),
+        );
+
+        assert_eq!(result, Ok(()));
+        let history = subject.history(Some(&wallet), from_time_t(0), from_time_t(10_000), 0, 100);
+        assert_eq!(
+            history,
+            vec![
+                payment.clone(),
+                LedgerEntry {
+                    timestamp: from_time_t(2_500),
+                    block_number_opt: Some(1_234_567),
+                    outcome_opt: Some(PaymentOutcome::Confirmed),
+                    ..payment
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn record_payment_outcome_complains_about_an_unknown_payment() {
+        let subject = make_subject("record_payment_outcome_complains_about_an_unknown_payment");
+        let hash = make_tx_hash(123);
+
+        let result =
+            subject.record_payment_outcome(hash, PaymentOutcome::Failed, None, from_time_t(1));
+
+        assert_eq!(result, Err(LedgerDaoError::PaymentNotFound(hash)));
+    }
+
+    #[test]
+    fn record_reports_insertion_failure() {
+        let home_dir =
+            ensure_node_home_directory_exists("ledger_dao", "record_reports_insertion_failure");
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let mut subject = LedgerDaoReal::new(Box::new(ConnectionWrapperReal::new(conn_read_only)));
+
+        let result = subject.record(&[make_sent_payment(1, &make_wallet("abc"), make_tx_hash(1))]);
+
+        assert_eq!(
+            result,
+            Err(LedgerDaoError::InsertionFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+
+    #[test]
+    fn record_writes_all_entries_or_none() {
+        let home_dir =
+            ensure_node_home_directory_exists("ledger_dao", "record_writes_all_entries_or_none");
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let refused_wallet = make_wallet("refused");
+        conn.prepare(&format!(
+            "create trigger refuse_wallet before insert on ledger \
+             when new.wallet_address = '{}' begin select raise(abort, 'refused'); end",
+            refused_wallet
+        ))
+        .unwrap()
+        .execute([])
+        .unwrap();
+        let mut subject = LedgerDaoReal::new(conn);
+
+        let result = subject.record(&[
+            make_sent_payment(1, &make_wallet("accepted"), make_tx_hash(1)),
+            make_sent_payment(2, &refused_wallet, make_tx_hash(2)),
+        ]);
+
+        assert_eq!(
+            result,
+            Err(LedgerDaoError::InsertionFailed("refused".to_string()))
+        );
+        assert_eq!(
+            subject.history(None, from_time_t(0), from_time_t(10_000), 0, 100),
+            vec![]
+        )
+    }
+}
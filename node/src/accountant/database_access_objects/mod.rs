@@ -2,6 +2,8 @@
 
 pub mod banned_dao;
 pub mod dao_utils;
+pub mod ledger_dao;
 pub mod payable_dao;
 pub mod pending_payable_dao;
 pub mod receivable_dao;
+pub mod service_usage_dao;
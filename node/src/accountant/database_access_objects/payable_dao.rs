@@ -28,7 +28,7 @@ use itertools::Either::Left;
 use masq_lib::utils::ExpectValue;
 #[cfg(test)]
 use rusqlite::OptionalExtension;
-use rusqlite::{Error, Row};
+use rusqlite::{named_params, Error, Row};
 use std::fmt::Debug;
 use std::str::FromStr;
 use std::time::SystemTime;
@@ -85,6 +85,8 @@ pub trait PayableDao: Debug + Send {
 
     fn custom_query(&self, custom_query: CustomQuery<u64>) -> Option<Vec<PayableAccount>>;
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<PayableAccount>;
+
     fn total(&self) -> u128;
 
     #[cfg(test)]
@@ -242,6 +244,25 @@ impl PayableDao for PayableDaoReal {
         )
     }
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<PayableAccount> {
+        let sql = "select wallet_address, balance_high_b, balance_low_b, last_paid_timestamp, pending_payable_rowid, \
+            pending_payable.transaction_hash from payable left join pending_payable on \
+            pending_payable.rowid = payable.pending_payable_rowid \
+            where last_paid_timestamp >= :from_timestamp and last_paid_timestamp <= :to_timestamp \
+            order by last_paid_timestamp asc, wallet_address asc";
+        let mut stmt = self.conn.prepare(sql).expect("Internal error");
+        stmt.query_map(
+            named_params! {
+                ":from_timestamp": to_time_t(from),
+                ":to_timestamp": to_time_t(to),
+            },
+            Self::create_payable_account,
+        )
+        .expect("Database is corrupt")
+        .vigilant_flatten()
+        .collect()
+    }
+
     fn total(&self) -> u128 {
         let value_completer = |row_number: usize, row: &Row| {
             let high_bytes = row.get::<usize, i64>(0).expectv("high bytes");
@@ -1579,6 +1600,71 @@ mod tests {
         )
     }
 
+    #[test]
+    fn accounts_active_between_ignores_amounts_but_respects_the_time_window() {
+        let main_setup = |conn: &dyn ConnectionWrapper, insert: InsertPayableHelperFn| {
+            insert(
+                conn,
+                "0x1111111111111111111111111111111111111111",
+                400_005_601, //smaller than 1 gwei
+                1_600_000_500,
+                None,
+            );
+            insert(
+                conn,
+                "0x2222222222222222222222222222222222222222",
+                30_000_300_000,
+                1_600_000_000,
+                Some(1),
+            );
+            insert(
+                conn,
+                "0x3333333333333333333333333333333333333333",
+                1,
+                1_599_999_999, //too early
+                None,
+            );
+            insert(
+                conn,
+                "0x4444444444444444444444444444444444444444",
+                2,
+                1_600_001_001, //too late
+                None,
+            );
+        };
+        let subject = custom_query_test_body_for_payable(
+            "accounts_active_between_ignores_amounts_but_respects_the_time_window",
+            main_setup,
+        );
+
+        let result =
+            subject.accounts_active_between(from_time_t(1_600_000_000), from_time_t(1_600_001_000));
+
+        assert_eq!(
+            result,
+            vec![
+                PayableAccount {
+                    wallet: Wallet::new("0x2222222222222222222222222222222222222222"),
+                    balance_wei: 30_000_300_000,
+                    last_paid_timestamp: from_time_t(1_600_000_000),
+                    pending_payable_opt: Some(PendingPayableId::new(
+                        1,
+                        H256::from_str(
+                            "abc4546cce78230a2312e12f3acb78747340456fe5237896666100143abcd223"
+                        )
+                        .unwrap()
+                    ))
+                },
+                PayableAccount {
+                    wallet: Wallet::new("0x1111111111111111111111111111111111111111"),
+                    balance_wei: 400_005_601,
+                    last_paid_timestamp: from_time_t(1_600_000_500),
+                    pending_payable_opt: None
+                }
+            ]
+        )
+    }
+
     #[test]
     fn total_works() {
         let home_dir = ensure_node_home_directory_exists("payable_dao", "total_works");
@@ -49,26 +49,31 @@ impl PendingPayableDao for PendingPayableDaoReal<'_> {
         }
 
         let sql = format!(
-            "select transaction_hash, rowid from pending_payable where transaction_hash in ({})",
+            "select transaction_hash, rowid from pending_payable where transaction_hash in ({}) \
+             order by rowid",
             comma_joined_stringifiable(hashes, |hash| format!("'{:?}'", hash))
         );
-        let all_found_records = self
+        // Payables paid together through a disperse contract each have a fingerprint of their own
+        // under the same hash; a repeated hash takes those fingerprints in the order of insertion
+        let mut rowids_by_hash = self
             .conn
             .prepare(&sql)
             .expect("Internal error")
             .query_map([], hash_and_rowid_in_single_row)
             .expect("map query failed")
             .vigilant_flatten()
-            .collect::<HashMap<H256, u64>>();
-        let hashes_of_missing_rowids = hashes
+            .into_group_map()
+            .into_iter()
+            .map(|(hash, rowids)| (hash, rowids.into_iter()))
+            .collect::<HashMap<H256, _>>();
+        hashes
             .iter()
-            .filter(|hash| !all_found_records.keys().contains(hash));
-        all_found_records
-            .iter()
-            .sorted()
-            .map(|(hash, rowid)| (Some(*rowid), *hash))
-            .rev()
-            .chain(hashes_of_missing_rowids.map(|hash| (None, *hash)))
+            .map(|hash| {
+                let rowid_opt = rowids_by_hash
+                    .get_mut(hash)
+                    .and_then(|rowids| rowids.next());
+                (rowid_opt, *hash)
+            })
             .collect()
     }
 
@@ -391,6 +396,47 @@ mod tests {
         assert_eq!(result, vec![(Some(1), hash_1), (Some(2), hash_2)])
     }
 
+    #[test]
+    fn fingerprints_rowids_hands_out_fingerprints_sharing_a_hash_one_by_one() {
+        let home_dir = ensure_node_home_directory_exists(
+            "pending_payable_dao",
+            "fingerprints_rowids_hands_out_fingerprints_sharing_a_hash_one_by_one",
+        );
+        let wrapped_conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let subject = PendingPayableDaoReal::new(wrapped_conn);
+        let timestamp = from_time_t(195_000_000);
+        let shared_hash = make_tx_hash(0xd15e);
+        let lone_hash = make_tx_hash(0x1234);
+        let missing_hash = make_tx_hash(0x5678);
+        subject
+            .insert_new_fingerprints(
+                &[(shared_hash, 1111), (lone_hash, 2222), (shared_hash, 3333)],
+                timestamp,
+            )
+            .unwrap();
+
+        let result = subject.fingerprints_rowids(&[
+            shared_hash,
+            lone_hash,
+            shared_hash,
+            missing_hash,
+            shared_hash,
+        ]);
+
+        assert_eq!(
+            result,
+            vec![
+                (Some(1), shared_hash),
+                (Some(2), lone_hash),
+                (Some(3), shared_hash),
+                (None, missing_hash),
+                (None, shared_hash)
+            ]
+        )
+    }
+
     #[test]
     fn fingerprints_rowids_when_nonexistent_record() {
         let home_dir = ensure_node_home_directory_exists(
@@ -30,10 +30,12 @@ use masq_lib::logger::Logger;
 use masq_lib::utils::{plus, ExpectValue};
 use rusqlite::OptionalExtension;
 use rusqlite::Row;
-use rusqlite::{named_params, Error};
+use rusqlite::{named_params, Error, ToSql};
 #[cfg(test)]
 use std::any::Any;
+use std::str::FromStr;
 use std::time::SystemTime;
+use web3::types::H256;
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReceivableDaoError {
@@ -71,6 +73,20 @@ pub trait ReceivableDao: Send {
 
     fn more_money_received(&mut self, now: SystemTime, transactions: Vec<BlockchainTransaction>);
 
+    fn reverse_received_payments(&mut self, transactions: Vec<BlockchainTransaction>);
+
+    // Credited payments are remembered by their transaction hashes so that a second look at the
+    // same blocks can tell which of them a blockchain reorganization has dropped since; only those
+    // sent to the given recipients are returned, because the second look covers no others
+    fn received_payments_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        recipients: &[Wallet],
+    ) -> Vec<BlockchainTransaction>;
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError>;
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -79,8 +95,20 @@ pub trait ReceivableDao: Send {
 
     fn paid_delinquencies(&self, payment_thresholds: &PaymentThresholds) -> Vec<ReceivableAccount>;
 
+    // Operator overrides for a delinquent debt; both return the account as it stood before the
+    // change, or None if the wallet owes nothing
+    fn restart_aging(
+        &self,
+        wallet: &Wallet,
+        now: SystemTime,
+    ) -> Result<Option<ReceivableAccount>, ReceivableDaoError>;
+
+    fn write_off(&self, wallet: &Wallet) -> Result<Option<ReceivableAccount>, ReceivableDaoError>;
+
     fn custom_query(&self, custom_query: CustomQuery<i64>) -> Option<Vec<ReceivableAccount>>;
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<ReceivableAccount>;
+
     fn total(&self) -> i128;
 
     //test-only-like method but because of share with multi-node tests #[cfg(test)] is disallowed
@@ -149,6 +177,48 @@ impl ReceivableDao for ReceivableDaoReal {
             .unwrap_or_else(|e| self.more_money_received_pretty_error_log(&payments, e))
     }
 
+    fn reverse_received_payments(&mut self, payments: Vec<BlockchainTransaction>) {
+        self.try_multi_reverse_payment(&payments)
+            .unwrap_or_else(|e| self.reverse_received_payments_error_log(&payments, e))
+    }
+
+    fn received_payments_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        recipients: &[Wallet],
+    ) -> Vec<BlockchainTransaction> {
+        if recipients.is_empty() {
+            return vec![];
+        }
+        let from_block = checked_conversion::<u64, i64>(from_block);
+        let to_block = checked_conversion::<u64, i64>(to_block);
+        let params = vec![&from_block as &dyn ToSql, &to_block as &dyn ToSql]
+            .into_iter()
+            .chain(recipients.iter().map(|recipient| recipient as &dyn ToSql))
+            .collect::<Vec<&dyn ToSql>>();
+        let sql = format!(
+            "select transaction_hash, block_number, wallet_address, amount_high_b, amount_low_b, \
+            recipient_address from received_payment where block_number >= ? and block_number <= ? \
+            and recipient_address in ({}) order by block_number asc",
+            vec!["?"; recipients.len()].join(", ")
+        );
+        self.conn
+            .prepare(&sql)
+            .expect("Couldn't prepare statement")
+            .query_map(params.as_slice(), Self::create_received_payment)
+            .expect("Couldn't retrieve received payments: database corruption")
+            .vigilant_flatten()
+            .collect()
+    }
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError> {
+        self.conn
+            .prepare("delete from received_payment where block_number < ?")?
+            .execute(&[&checked_conversion::<u64, i64>(block_number)])?;
+        Ok(())
+    }
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -195,7 +265,8 @@ impl ReceivableDao for ReceivableDaoReal {
             select r.wallet_address, r.balance_high_b, r.balance_low_b, r.last_received_timestamp
             from receivable r inner join banned b on r.wallet_address = b.wallet_address
             where
-                (r.balance_high_b < :unban_balance_high_b) or ((balance_high_b = :unban_balance_high_b) and (balance_low_b <= :unban_balance_low_b))
+                b.reason = 'delinquency'
+                and ((r.balance_high_b < :unban_balance_high_b) or ((balance_high_b = :unban_balance_high_b) and (balance_low_b <= :unban_balance_low_b)))
         "
         );
         let mut stmt = self.conn.prepare(sql).expect("Couldn't prepare statement");
@@ -238,6 +309,24 @@ impl ReceivableDao for ReceivableDaoReal {
         )
     }
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<ReceivableAccount> {
+        let sql = "select wallet_address, balance_high_b, balance_low_b, last_received_timestamp \
+            from receivable \
+            where last_received_timestamp >= :from_timestamp and last_received_timestamp <= :to_timestamp \
+            order by last_received_timestamp asc, wallet_address asc";
+        let mut stmt = self.conn.prepare(sql).expect("Couldn't prepare statement");
+        stmt.query_map(
+            named_params! {
+                ":from_timestamp": to_time_t(from),
+                ":to_timestamp": to_time_t(to),
+            },
+            Self::create_receivable_account,
+        )
+        .expect("Couldn't retrieve receivable accounts: database corruption")
+        .vigilant_flatten()
+        .collect()
+    }
+
     fn total(&self) -> i128 {
         let value_creation = |_: usize, row: &Row| {
             Ok(BigIntDivider::reconstitute(
@@ -253,6 +342,33 @@ impl ReceivableDao for ReceivableDaoReal {
         )
     }
 
+    fn restart_aging(
+        &self,
+        wallet: &Wallet,
+        now: SystemTime,
+    ) -> Result<Option<ReceivableAccount>, ReceivableDaoError> {
+        let account_opt = self.account_status(wallet);
+        if account_opt.is_some() {
+            let params: &[&dyn ToSql] = &[&to_time_t(now), wallet];
+            self.conn
+                .prepare(
+                    "update receivable set last_received_timestamp = ? where wallet_address = ?",
+                )?
+                .execute(params)?;
+        }
+        Ok(account_opt)
+    }
+
+    fn write_off(&self, wallet: &Wallet) -> Result<Option<ReceivableAccount>, ReceivableDaoError> {
+        let account_opt = self.account_status(wallet);
+        if account_opt.is_some() {
+            self.conn
+                .prepare("delete from receivable where wallet_address = ?")?
+                .execute(&[wallet])?;
+        }
+        Ok(account_opt)
+    }
+
     fn account_status(&self, wallet: &Wallet) -> Option<ReceivableAccount> {
         let mut stmt = self
             .conn
@@ -310,7 +426,68 @@ impl ReceivableDaoReal {
                             )])
                             .build(),
                     ),
-                )?
+                )?;
+                let (amount_high_b, amount_low_b) = BigIntDivider::deconstruct(
+                    checked_conversion::<u128, i128>(transaction.wei_amount),
+                );
+                let params: &[&dyn ToSql] = &[
+                    &format!("{:?}", transaction.transaction_hash),
+                    &checked_conversion::<u64, i64>(transaction.block_number),
+                    &transaction.from,
+                    &amount_high_b,
+                    &amount_low_b,
+                    &transaction.to,
+                ];
+                xactn
+                    .prepare(
+                        "insert into received_payment (transaction_hash, block_number, wallet_address, \
+                        amount_high_b, amount_low_b, recipient_address) values (?, ?, ?, ?, ?, ?)",
+                    )?
+                    .execute(params)?;
+            }
+        }
+        match xactn.commit() {
+            // Error response is untested here, because without a mockable Transaction, it's untestable.
+            Err(e) => Err(ReceivableDaoError::RusqliteError(format!("{:?}", e))),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn try_multi_reverse_payment(
+        &mut self,
+        payments: &[BlockchainTransaction],
+    ) -> Result<(), ReceivableDaoError> {
+        let xactn = self.conn.transaction()?;
+        {
+            for transaction in payments {
+                // the last received timestamp stays untouched; we cannot tell what it was before
+                // the removed payment arrived
+                let main_sql =
+                    "update receivable set balance_high_b = balance_high_b + :balance_high_b, \
+                 balance_low_b = balance_low_b + :balance_low_b where wallet_address = :wallet";
+                let overflow_update_clause = "update receivable set balance_high_b = :balance_high_b, balance_low_b = :balance_low_b \
+                where wallet_address = :wallet";
+
+                self.big_int_db_processor.execute(
+                    Either::Right(&xactn),
+                    BigIntSqlConfig::new(
+                        main_sql,
+                        overflow_update_clause,
+                        SQLParamsBuilder::default()
+                            .key(WalletAddress(&transaction.from))
+                            .wei_change(Addition("balance", transaction.wei_amount))
+                            .build(),
+                    ),
+                )?;
+                let params: &[&dyn ToSql] = &[
+                    &format!("{:?}", transaction.transaction_hash),
+                    &transaction.from,
+                ];
+                xactn
+                    .prepare(
+                        "delete from received_payment where transaction_hash = ? and wallet_address = ?",
+                    )?
+                    .execute(params)?;
             }
         }
         match xactn.commit() {
@@ -345,6 +522,30 @@ impl ReceivableDaoReal {
         }
     }
 
+    fn create_received_payment(row: &Row) -> rusqlite::Result<BlockchainTransaction> {
+        let transaction_hash: String = row.get(0)?;
+        let block_number: i64 = row.get(1)?;
+        let from: Wallet = row.get(2)?;
+        let amount_high_b: i64 = row.get(3)?;
+        let amount_low_b: i64 = row.get(4)?;
+        let to: Wallet = row.get(5)?;
+        Ok(BlockchainTransaction {
+            block_number: checked_conversion::<i64, u64>(block_number),
+            transaction_hash: H256::from_str(&transaction_hash[2..]).unwrap_or_else(|e| {
+                panic!(
+                    "Invalid hash format in received_payment ({}): {:?} - database corrupt",
+                    transaction_hash, e
+                )
+            }),
+            from,
+            to,
+            wei_amount: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                amount_high_b,
+                amount_low_b,
+            )),
+        })
+    }
+
     fn stm_assembler_of_receivable_cq(feeder: AssemblerFeeder) -> String {
         format!(
             "select
@@ -402,6 +603,26 @@ impl ReceivableDaoReal {
             finalize_report(aggregated)
         );
     }
+
+    fn reverse_received_payments_error_log(
+        &self,
+        payments: &[BlockchainTransaction],
+        error: ReceivableDaoError,
+    ) {
+        error!(
+            self.logger,
+            "Reversal of removed payments failed, rolling back: {:?}\n{}",
+            error,
+            payments
+                .iter()
+                .map(|bc_tx| format!(
+                    "{:10} {:42} {:18}",
+                    bc_tx.block_number, bc_tx.from, bc_tx.wei_amount
+                ))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
 }
 
 impl TableNameDAO for ReceivableDaoReal {
@@ -421,6 +642,7 @@ mod tests {
         assert_account_creation_fn_fails_on_finding_wrong_columns_and_value_types,
         make_receivable_account,
     };
+    use crate::blockchain::test_utils::make_tx_hash;
     use crate::database::db_initializer::{DbInitializationConfig, DbInitializer};
     use crate::database::db_initializer::{DbInitializerReal, ExternalData};
     use crate::db_config::persistent_configuration::PersistentConfigError;
@@ -496,7 +718,9 @@ mod tests {
         );
         let payments = vec![BlockchainTransaction {
             block_number: 42u64,
+            transaction_hash: make_tx_hash(1),
             from: make_wallet("some_address"),
+            to: make_wallet("earning_wallet"),
             wei_amount: u128::MAX,
         }];
 
@@ -521,7 +745,9 @@ mod tests {
 
         let payments = vec![BlockchainTransaction {
             block_number: 42u64,
+            transaction_hash: make_tx_hash(2),
             from: make_wallet("some_address"),
+            to: make_wallet("earning_wallet"),
             wei_amount: 18446744073709551615,
         }];
 
@@ -698,17 +924,21 @@ mod tests {
         let transactions = vec![
             BlockchainTransaction {
                 from: debtor1.clone(),
+                to: make_wallet("earning_wallet"),
                 wei_amount: first_newly_received,
                 block_number: 35_u64,
+                transaction_hash: make_tx_hash(3),
             },
             BlockchainTransaction {
                 from: debtor2.clone(),
+                to: make_wallet("earning_wallet"),
                 wei_amount: second_newly_received,
                 block_number: 57_u64,
+                transaction_hash: make_tx_hash(4),
             },
         ];
 
-        subject.more_money_received(payment_time, transactions);
+        subject.more_money_received(payment_time, transactions.clone());
 
         let status1 = subject.account_status(&debtor1).unwrap();
         assert_eq!(status1.wallet, debtor1);
@@ -724,6 +954,10 @@ mod tests {
             to_time_t(status2.last_received_timestamp),
             to_time_t(payment_time)
         );
+        assert_eq!(
+            subject.received_payments_in_range(0, 100, &[make_wallet("earning_wallet")]),
+            transactions
+        );
     }
 
     #[test]
@@ -740,8 +974,10 @@ mod tests {
         );
         let transactions = vec![BlockchainTransaction {
             from: debtor.clone(),
+            to: make_wallet("earning_wallet"),
             wei_amount: 2300_u128,
             block_number: 33_u64,
+            transaction_hash: make_tx_hash(5),
         }];
 
         subject.more_money_received(SystemTime::now(), transactions);
@@ -750,6 +986,247 @@ mod tests {
         assert!(status.is_none());
     }
 
+    #[test]
+    fn reverse_received_payments_restores_balances_and_keeps_timestamps() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "reverse_received_payments_restores_balances_and_keeps_timestamps",
+        );
+        let debtor1 = make_wallet("debtor1");
+        let debtor2 = make_wallet("debtor2");
+        let payment_time = from_time_t(1_500_000_000);
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor1, 10_000)
+            .unwrap();
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor2, 20_000)
+            .unwrap();
+        let transactions = vec![
+            BlockchainTransaction {
+                from: debtor1.clone(),
+                to: make_wallet("earning_wallet"),
+                wei_amount: 4_000,
+                block_number: 35_u64,
+                transaction_hash: make_tx_hash(6),
+            },
+            BlockchainTransaction {
+                from: debtor2.clone(),
+                to: make_wallet("earning_wallet"),
+                wei_amount: 20_000,
+                block_number: 57_u64,
+                transaction_hash: make_tx_hash(7),
+            },
+        ];
+        subject.more_money_received(payment_time, transactions.clone());
+
+        subject.reverse_received_payments(transactions);
+
+        let status1 = subject.account_status(&debtor1).unwrap();
+        assert_eq!(status1.balance_wei, 10_000);
+        assert_eq!(status1.last_received_timestamp, payment_time);
+        let status2 = subject.account_status(&debtor2).unwrap();
+        assert_eq!(status2.balance_wei, 20_000);
+        assert_eq!(status2.last_received_timestamp, payment_time);
+        assert_eq!(
+            subject.received_payments_in_range(0, 100, &[make_wallet("earning_wallet")]),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn received_payments_are_remembered_until_forgotten() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "received_payments_are_remembered_until_forgotten",
+        );
+        let debtor = make_wallet("debtor");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, 10_000)
+            .unwrap();
+        let payment = |block_number: u64, base: u32| BlockchainTransaction {
+            block_number,
+            transaction_hash: make_tx_hash(base),
+            from: debtor.clone(),
+            to: make_wallet("earning_wallet"),
+            wei_amount: u128::from(base),
+        };
+        subject.more_money_received(
+            SystemTime::now(),
+            vec![payment(10, 1), payment(20, 2), payment(30, 3)],
+        );
+
+        let in_range_before =
+            subject.received_payments_in_range(20, 30, &[make_wallet("earning_wallet")]);
+        let forget_result = subject.forget_received_payments_before(20);
+        let all_after =
+            subject.received_payments_in_range(0, u64::MAX >> 1, &[make_wallet("earning_wallet")]);
+
+        assert_eq!(in_range_before, vec![payment(20, 2), payment(30, 3)]);
+        assert_eq!(forget_result, Ok(()));
+        assert_eq!(all_after, vec![payment(20, 2), payment(30, 3)]);
+    }
+
+    #[test]
+    fn received_payments_in_range_leaves_out_payments_to_wallets_no_longer_watched() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "received_payments_in_range_leaves_out_payments_to_wallets_no_longer_watched",
+        );
+        let debtor = make_wallet("debtor");
+        let earning_wallet = make_wallet("earning_wallet");
+        let retired_earning_wallet = make_wallet("retired_earning_wallet");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(SystemTime::UNIX_EPOCH, &debtor, 10_000)
+            .unwrap();
+        let payment = |to: &Wallet, base: u32| BlockchainTransaction {
+            block_number: u64::from(base),
+            transaction_hash: make_tx_hash(base),
+            from: debtor.clone(),
+            to: to.clone(),
+            wei_amount: u128::from(base),
+        };
+        subject.more_money_received(
+            SystemTime::now(),
+            vec![
+                payment(&earning_wallet, 10),
+                payment(&retired_earning_wallet, 11),
+            ],
+        );
+
+        let watched = subject.received_payments_in_range(0, 100, &[earning_wallet.clone()]);
+        let unwatched = subject.received_payments_in_range(0, 100, &[]);
+
+        assert_eq!(watched, vec![payment(&earning_wallet, 10)]);
+        assert_eq!(unwatched, vec![]);
+    }
+
+    #[test]
+    fn restart_aging_resets_the_timestamp_but_keeps_the_balance() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "restart_aging_resets_the_timestamp_but_keeps_the_balance",
+        );
+        let debtor = make_wallet("debtor");
+        let now = from_time_t(1_600_000_000);
+        let subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(from_time_t(1_500_000_000), &debtor, 10_000)
+            .unwrap();
+
+        let result = subject.restart_aging(&debtor, now);
+
+        assert_eq!(
+            result,
+            Ok(Some(ReceivableAccount {
+                wallet: debtor.clone(),
+                balance_wei: 10_000,
+                last_received_timestamp: from_time_t(1_500_000_000),
+            }))
+        );
+        let status = subject.account_status(&debtor).unwrap();
+        assert_eq!(status.balance_wei, 10_000);
+        assert_eq!(status.last_received_timestamp, now);
+    }
+
+    #[test]
+    fn write_off_deletes_the_account() {
+        let home_dir =
+            ensure_node_home_directory_exists("receivable_dao", "write_off_deletes_the_account");
+        let debtor = make_wallet("debtor");
+        let bystander = make_wallet("bystander");
+        let subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        subject
+            .more_money_receivable(from_time_t(1_500_000_000), &debtor, 10_000)
+            .unwrap();
+        subject
+            .more_money_receivable(from_time_t(1_500_000_000), &bystander, 20_000)
+            .unwrap();
+
+        let result = subject.write_off(&debtor);
+
+        assert_eq!(
+            result,
+            Ok(Some(ReceivableAccount {
+                wallet: debtor.clone(),
+                balance_wei: 10_000,
+                last_received_timestamp: from_time_t(1_500_000_000),
+            }))
+        );
+        assert_eq!(subject.account_status(&debtor), None);
+        assert_eq!(
+            subject.account_status(&bystander).unwrap().balance_wei,
+            20_000
+        );
+    }
+
+    #[test]
+    fn restart_aging_and_write_off_leave_unknown_addresses_alone() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "restart_aging_and_write_off_leave_unknown_addresses_alone",
+        );
+        let debtor = make_wallet("unknown_wallet");
+        let subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+
+        let restart_result = subject.restart_aging(&debtor, SystemTime::now());
+        let write_off_result = subject.write_off(&debtor);
+
+        assert_eq!(restart_result, Ok(None));
+        assert_eq!(write_off_result, Ok(None));
+        assert!(subject.account_status(&debtor).is_none());
+    }
+
+    #[test]
+    fn reverse_received_payments_ignores_unknown_addresses() {
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "reverse_received_payments_ignores_unknown_addresses",
+        );
+        let debtor = make_wallet("unknown_wallet");
+        let mut subject = ReceivableDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+
+        subject.reverse_received_payments(vec![BlockchainTransaction {
+            from: debtor.clone(),
+            to: make_wallet("earning_wallet"),
+            wei_amount: 2300_u128,
+            block_number: 33_u64,
+            transaction_hash: make_tx_hash(8),
+        }]);
+
+        assert!(subject.account_status(&debtor).is_none());
+    }
+
     #[test]
     fn more_money_received_logs_when_try_multi_insert_payment_fails() {
         init_test_logging();
@@ -778,17 +1255,23 @@ mod tests {
         let payments = vec![
             BlockchainTransaction {
                 block_number: 1234567890,
+                transaction_hash: make_tx_hash(9),
                 from: Wallet::new("0xAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+                to: make_wallet("earning_wallet"),
                 wei_amount: 123456789123456789,
             },
             BlockchainTransaction {
                 block_number: 2345678901,
+                transaction_hash: make_tx_hash(10),
                 from: Wallet::new("0xBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB"),
+                to: make_wallet("earning_wallet"),
                 wei_amount: 234567891234567891,
             },
             BlockchainTransaction {
                 block_number: 3456789012,
+                transaction_hash: make_tx_hash(11),
                 from: Wallet::new("0xCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCCC"),
+                to: make_wallet("earning_wallet"),
                 wei_amount: 345678912345678912,
             },
         ];
@@ -1119,6 +1602,42 @@ mod tests {
         assert_eq!(result.len(), 1);
     }
 
+    #[test]
+    fn paid_delinquencies_leaves_out_manual_bans() {
+        let payment_thresholds = PaymentThresholds {
+            maturity_threshold_sec: 0,
+            payment_grace_period_sec: 0,
+            permanent_debt_allowed_gwei: 0,
+            debt_threshold_gwei: 0,
+            threshold_interval_sec: 0,
+            unban_below_gwei: 50,
+        };
+        let mut paid_delinquent = make_receivable_account(1234, true);
+        paid_delinquent.balance_wei = gwei_to_wei(25);
+        let mut manually_banned = make_receivable_account(2345, true);
+        manually_banned.balance_wei = gwei_to_wei(25);
+        let home_dir = ensure_node_home_directory_exists(
+            "receivable_dao",
+            "paid_delinquencies_leaves_out_manual_bans",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        add_receivable_account(&conn, &paid_delinquent);
+        add_receivable_account(&conn, &manually_banned);
+        add_banned_account(&conn, &paid_delinquent);
+        let params: &[&dyn ToSql] = &[&manually_banned.wallet];
+        conn.prepare("insert into banned (wallet_address, reason) values (?, 'manual')")
+            .unwrap()
+            .execute(params)
+            .unwrap();
+        let subject = ReceivableDaoReal::new(conn);
+
+        let result = subject.paid_delinquencies(&payment_thresholds);
+
+        assert_eq!(result, vec![paid_delinquent]);
+    }
+
     #[test]
     fn custom_query_handles_empty_table_in_top_records_mode() {
         let main_test_setup = |_conn: &dyn ConnectionWrapper, _insert: InsertReceivableHelperFn| {};
@@ -1430,6 +1949,59 @@ mod tests {
         )
     }
 
+    #[test]
+    fn accounts_active_between_ignores_amounts_but_respects_the_time_window() {
+        let main_setup = |conn: &dyn ConnectionWrapper, insert: InsertReceivableHelperFn| {
+            insert(
+                conn,
+                "0x1111111111111111111111111111111111111111",
+                999_999_999, //smaller than 1 gwei
+                1_600_000_500,
+            );
+            insert(
+                conn,
+                "0x2222222222222222222222222222222222222222",
+                -1, //smaller than -1 gwei
+                1_600_000_000,
+            );
+            insert(
+                conn,
+                "0x3333333333333333333333333333333333333333",
+                30_000_300_000,
+                1_599_999_999, //too early
+            );
+            insert(
+                conn,
+                "0x4444444444444444444444444444444444444444",
+                -2_000_300_000,
+                1_600_001_001, //too late
+            );
+        };
+        let subject = custom_query_test_body_for_receivable(
+            "accounts_active_between_ignores_amounts_but_respects_the_time_window",
+            main_setup,
+        );
+
+        let result =
+            subject.accounts_active_between(from_time_t(1_600_000_000), from_time_t(1_600_001_000));
+
+        assert_eq!(
+            result,
+            vec![
+                ReceivableAccount {
+                    wallet: Wallet::new("0x2222222222222222222222222222222222222222"),
+                    balance_wei: -1,
+                    last_received_timestamp: from_time_t(1_600_000_000),
+                },
+                ReceivableAccount {
+                    wallet: Wallet::new("0x1111111111111111111111111111111111111111"),
+                    balance_wei: 999_999_999,
+                    last_received_timestamp: from_time_t(1_600_000_500),
+                }
+            ]
+        )
+    }
+
     #[test]
     fn total_works() {
         let home_dir = ensure_node_home_directory_exists("receivable_dao", "total_works");
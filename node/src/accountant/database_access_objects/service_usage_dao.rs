@@ -0,0 +1,481 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::accountant::big_int_processing::big_int_divider::BigIntDivider;
+use crate::accountant::checked_conversion;
+use crate::accountant::database_access_objects::dao_utils::{
+    from_time_t, to_time_t, DaoFactoryReal, VigilantRusqliteFlatten,
+};
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::sub_lib::wallet::Wallet;
+use masq_lib::utils::ExpectValue;
+use rusqlite::{OptionalExtension, Row, ToSql};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+// Usage is accumulated in buckets of this many seconds, aligned to the Unix epoch
+pub const SERVICE_USAGE_BUCKET_S: u64 = 3600;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum ServiceType {
+    Routing,
+    Exit,
+}
+
+impl ServiceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Routing => "routing",
+            Self::Exit => "exit",
+        }
+    }
+}
+
+impl FromStr for ServiceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "routing" => Ok(Self::Routing),
+            "exit" => Ok(Self::Exit),
+            x => Err(format!("Unrecognized service type: '{}'", x)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum ServiceDirection {
+    Provided,
+    Consumed,
+}
+
+impl ServiceDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Provided => "provided",
+            Self::Consumed => "consumed",
+        }
+    }
+}
+
+impl FromStr for ServiceDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "provided" => Ok(Self::Provided),
+            "consumed" => Ok(Self::Consumed),
+            x => Err(format!("Unrecognized service direction: '{}'", x)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ServiceUsage {
+    pub timestamp: SystemTime,
+    pub wallet: Wallet,
+    pub service: ServiceType,
+    pub direction: ServiceDirection,
+    pub bytes: u64,
+    pub charge_wei: u128,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ServiceUsageBucket {
+    pub bucket_start: SystemTime,
+    pub wallet: Wallet,
+    pub service: ServiceType,
+    pub direction: ServiceDirection,
+    pub service_count: u64,
+    pub bytes: u64,
+    pub charge_wei: u128,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ServiceUsageDaoError {
+    RecordingFailed(String),
+}
+
+pub trait ServiceUsageDao {
+    fn record(&self, usage: &ServiceUsage) -> Result<(), ServiceUsageDaoError>;
+    fn usage(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> Vec<ServiceUsageBucket>;
+}
+
+pub trait ServiceUsageDaoFactory {
+    fn make(&self) -> Box<dyn ServiceUsageDao>;
+}
+
+impl ServiceUsageDaoFactory for DaoFactoryReal {
+    fn make(&self) -> Box<dyn ServiceUsageDao> {
+        Box::new(ServiceUsageDaoReal::new(self.make_connection()))
+    }
+}
+
+pub struct ServiceUsageDaoReal {
+    conn: Box<dyn ConnectionWrapper>,
+}
+
+impl ServiceUsageDao for ServiceUsageDaoReal {
+    fn record(&self, usage: &ServiceUsage) -> Result<(), ServiceUsageDaoError> {
+        let bucket_start = Self::bucket_start(to_time_t(usage.timestamp));
+        let key: [&dyn ToSql; 4] = [
+            &usage.wallet,
+            &bucket_start,
+            &usage.service.as_str(),
+            &usage.direction.as_str(),
+        ];
+        // Only the Accountant writes here, so reading the totals before replacing them is safe
+        let (service_count, bytes, charge_wei) = self
+            .conn
+            .prepare(
+                "select service_count, bytes, charge_high_b, charge_low_b from service_usage \
+                 where wallet_address = ? and bucket_start = ? and service = ? and direction = ?",
+            )
+            .expect("Internal error")
+            .query_row(key.as_slice(), |row| {
+                let service_count: i64 = row.get(0).expectv("service count");
+                let bytes: i64 = row.get(1).expectv("bytes");
+                let high_bytes: i64 = row.get(2).expectv("charge high bytes");
+                let low_bytes: i64 = row.get(3).expectv("charge low bytes");
+                Ok((
+                    service_count,
+                    bytes,
+                    BigIntDivider::reconstitute(high_bytes, low_bytes),
+                ))
+            })
+            .optional()
+            .map_err(|e| ServiceUsageDaoError::RecordingFailed(e.to_string()))?
+            .unwrap_or((0, 0, 0));
+        let service_count = service_count.saturating_add(1);
+        let bytes = bytes.saturating_add(checked_conversion::<u64, i64>(usage.bytes));
+        let (high_bytes, low_bytes) = BigIntDivider::deconstruct(
+            charge_wei + checked_conversion::<u128, i128>(usage.charge_wei),
+        );
+        let params: &[&dyn ToSql] = &[
+            key[0],
+            key[1],
+            key[2],
+            key[3],
+            &service_count,
+            &bytes,
+            &high_bytes,
+            &low_bytes,
+        ];
+        match self.conn.prepare(
+            "insert or replace into service_usage (wallet_address, bucket_start, service, direction, \
+             service_count, bytes, charge_high_b, charge_low_b) values (?, ?, ?, ?, ?, ?, ?, ?)",
+        ) {
+            Ok(mut stmt) => match stmt.execute(params) {
+                Ok(_) => Ok(()),
+                Err(e) => Err(ServiceUsageDaoError::RecordingFailed(e.to_string())),
+            },
+            Err(e) => Err(ServiceUsageDaoError::RecordingFailed(e.to_string())),
+        }
+    }
+
+    fn usage(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> Vec<ServiceUsageBucket> {
+        let wallet_clause = match wallet_opt {
+            Some(_) => " and wallet_address = ?",
+            None => "",
+        };
+        let sql = format!(
+            "select bucket_start, wallet_address, service, direction, service_count, bytes, \
+             charge_high_b, charge_low_b from service_usage \
+             where bucket_start >= ? and bucket_start <= ?{} \
+             order by bucket_start, wallet_address, service, direction",
+            wallet_clause
+        );
+        let from_t = Self::bucket_start(to_time_t(from));
+        let to_t = to_time_t(to);
+        let mut params: Vec<&dyn ToSql> = vec![&from_t, &to_t];
+        if let Some(wallet) = wallet_opt {
+            params.push(wallet)
+        }
+        let mut stmt = self.conn.prepare(&sql).expect("Internal error");
+        stmt.query_map(params.as_slice(), Self::bucket_from_row)
+            .expect("Couldn't retrieve service usage: database corrupt")
+            .vigilant_flatten()
+            .collect()
+    }
+}
+
+impl ServiceUsageDaoReal {
+    pub fn new(conn: Box<dyn ConnectionWrapper>) -> Self {
+        Self { conn }
+    }
+
+    fn bucket_start(time_t: i64) -> i64 {
+        time_t - time_t.rem_euclid(SERVICE_USAGE_BUCKET_S as i64)
+    }
+
+    fn bucket_from_row(row: &Row) -> rusqlite::Result<ServiceUsageBucket> {
+        let bucket_start: i64 = row.get(0).expectv("bucket start");
+        let wallet: Wallet = row.get(1).expectv("wallet");
+        let service: String = row.get(2).expectv("service");
+        let direction: String = row.get(3).expectv("direction");
+        let service_count: i64 = row.get(4).expectv("service count");
+        let bytes: i64 = row.get(5).expectv("bytes");
+        let high_bytes: i64 = row.get(6).expectv("charge high bytes");
+        let low_bytes: i64 = row.get(7).expectv("charge low bytes");
+        Ok(ServiceUsageBucket {
+            bucket_start: from_time_t(bucket_start),
+            wallet,
+            service: ServiceType::from_str(&service).expect("Service usage corrupt"),
+            direction: ServiceDirection::from_str(&direction).expect("Service usage corrupt"),
+            service_count: checked_conversion::<i64, u64>(service_count),
+            bytes: checked_conversion::<i64, u64>(bytes),
+            charge_wei: checked_conversion::<i128, u128>(BigIntDivider::reconstitute(
+                high_bytes, low_bytes,
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::connection_wrapper::ConnectionWrapperReal;
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::{Connection, OpenFlags};
+
+    fn make_subject(test_name: &str) -> ServiceUsageDaoReal {
+        let home_dir = ensure_node_home_directory_exists("service_usage_dao", test_name);
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        ServiceUsageDaoReal::new(conn)
+    }
+
+    fn make_usage(
+        timestamp: i64,
+        wallet: &Wallet,
+        service: ServiceType,
+        direction: ServiceDirection,
+        bytes: u64,
+        charge_wei: u128,
+    ) -> ServiceUsage {
+        ServiceUsage {
+            timestamp: from_time_t(timestamp),
+            wallet: wallet.clone(),
+            service,
+            direction,
+            bytes,
+            charge_wei,
+        }
+    }
+
+    #[test]
+    fn service_types_and_directions_survive_a_round_trip_through_strings() {
+        [ServiceType::Routing, ServiceType::Exit]
+            .iter()
+            .for_each(|service| assert_eq!(ServiceType::from_str(service.as_str()), Ok(*service)));
+        [ServiceDirection::Provided, ServiceDirection::Consumed]
+            .iter()
+            .for_each(|direction| {
+                assert_eq!(
+                    ServiceDirection::from_str(direction.as_str()),
+                    Ok(*direction)
+                )
+            });
+        assert_eq!(
+            ServiceType::from_str("booga"),
+            Err("Unrecognized service type: 'booga'".to_string())
+        );
+        assert_eq!(
+            ServiceDirection::from_str("booga"),
+            Err("Unrecognized service direction: 'booga'".to_string())
+        );
+    }
+
+    #[test]
+    fn usage_within_one_bucket_is_accumulated() {
+        let subject = make_subject("usage_within_one_bucket_is_accumulated");
+        let wallet = make_wallet("debtor");
+        let charge = (i64::MAX as u128) + 10;
+
+        [
+            make_usage(
+                7_200,
+                &wallet,
+                ServiceType::Exit,
+                ServiceDirection::Provided,
+                1_000,
+                charge,
+            ),
+            make_usage(
+                7_300,
+                &wallet,
+                ServiceType::Exit,
+                ServiceDirection::Provided,
+                500,
+                charge,
+            ),
+            make_usage(
+                10_799,
+                &wallet,
+                ServiceType::Exit,
+                ServiceDirection::Provided,
+                24,
+                1,
+            ),
+        ]
+        .iter()
+        .for_each(|usage| subject.record(usage).unwrap());
+
+        let result = subject.usage(None, from_time_t(0), from_time_t(100_000));
+        assert_eq!(
+            result,
+            vec![ServiceUsageBucket {
+                bucket_start: from_time_t(7_200),
+                wallet,
+                service: ServiceType::Exit,
+                direction: ServiceDirection::Provided,
+                service_count: 3,
+                bytes: 1_524,
+                charge_wei: charge * 2 + 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn usage_is_kept_apart_by_bucket_wallet_service_and_direction() {
+        let subject = make_subject("usage_is_kept_apart_by_bucket_wallet_service_and_direction");
+        let wallet_a = make_wallet("wallet_a");
+        let wallet_b = make_wallet("wallet_b");
+        let usages = vec![
+            make_usage(
+                100,
+                &wallet_a,
+                ServiceType::Routing,
+                ServiceDirection::Provided,
+                10,
+                11,
+            ),
+            make_usage(
+                200,
+                &wallet_a,
+                ServiceType::Routing,
+                ServiceDirection::Consumed,
+                20,
+                21,
+            ),
+            make_usage(
+                300,
+                &wallet_a,
+                ServiceType::Exit,
+                ServiceDirection::Provided,
+                30,
+                31,
+            ),
+            make_usage(
+                400,
+                &wallet_b,
+                ServiceType::Routing,
+                ServiceDirection::Provided,
+                40,
+                41,
+            ),
+            make_usage(
+                3_700,
+                &wallet_a,
+                ServiceType::Routing,
+                ServiceDirection::Provided,
+                50,
+                51,
+            ),
+        ];
+
+        usages
+            .iter()
+            .for_each(|usage| subject.record(usage).unwrap());
+
+        let bucket = |usage: &ServiceUsage, bucket_start: i64| ServiceUsageBucket {
+            bucket_start: from_time_t(bucket_start),
+            wallet: usage.wallet.clone(),
+            service: usage.service,
+            direction: usage.direction,
+            service_count: 1,
+            bytes: usage.bytes,
+            charge_wei: usage.charge_wei,
+        };
+        let mut expected_first_hour = vec![
+            bucket(&usages[0], 0),
+            bucket(&usages[1], 0),
+            bucket(&usages[2], 0),
+            bucket(&usages[3], 0),
+        ];
+        expected_first_hour.sort_by(|a, b| {
+            (
+                a.wallet.to_string(),
+                a.service.as_str(),
+                a.direction.as_str(),
+            )
+                .cmp(&(
+                    b.wallet.to_string(),
+                    b.service.as_str(),
+                    b.direction.as_str(),
+                ))
+        });
+        let mut expected = expected_first_hour;
+        expected.push(bucket(&usages[4], 3_600));
+        assert_eq!(
+            subject.usage(None, from_time_t(0), from_time_t(100_000)),
+            expected
+        );
+        assert_eq!(
+            subject.usage(Some(&wallet_a), from_time_t(3_650), from_time_t(100_000)),
+            vec![bucket(&usages[4], 3_600)]
+        );
+        assert_eq!(
+            subject.usage(Some(&wallet_b), from_time_t(0), from_time_t(3_599)),
+            vec![bucket(&usages[3], 0)]
+        );
+    }
+
+    #[test]
+    fn record_reports_failure() {
+        let home_dir =
+            ensure_node_home_directory_exists("service_usage_dao", "record_reports_failure");
+        {
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap();
+        }
+        let conn_read_only = Connection::open_with_flags(
+            home_dir.join(DATABASE_FILE),
+            OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )
+        .unwrap();
+        let subject =
+            ServiceUsageDaoReal::new(Box::new(ConnectionWrapperReal::new(conn_read_only)));
+
+        let result = subject.record(&make_usage(
+            1,
+            &make_wallet("abc"),
+            ServiceType::Routing,
+            ServiceDirection::Consumed,
+            1,
+            1,
+        ));
+
+        assert_eq!(
+            result,
+            Err(ServiceUsageDaoError::RecordingFailed(
+                "attempt to write a readonly database".to_string()
+            ))
+        )
+    }
+}
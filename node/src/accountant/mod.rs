@@ -9,17 +9,29 @@ pub mod scanners;
 pub mod test_utils;
 
 use core::fmt::Debug;
-use masq_lib::constants::{SCAN_ERROR, WEIS_IN_GWEI};
+use masq_lib::constants::{
+    BAN_LIST_ERROR, DEBT_FORGIVENESS_ERROR, FINANCIALS_EXPORT_ERROR, LEDGER_HISTORY_ERROR,
+    SCAN_ERROR, SERVICE_USAGE_ERROR, WEIS_IN_GWEI,
+};
 use std::cell::{Ref, RefCell};
 
 use masq_lib::messages::{
-    QueryResults, ScanType, UiFinancialStatistics, UiPayableAccount, UiReceivableAccount,
-    UiScanRequest,
+    QueryResults, ScanType, UiBanListChangedBroadcast, UiBanListRequest, UiBanListResponse,
+    UiBanRequest, UiBanResponse, UiBannedWallet, UiExportedAccount, UiExportedTransaction,
+    UiFinancialStatistics, UiFinancialsExportRequest, UiFinancialsExportResponse,
+    UiForgiveDebtRequest, UiForgiveDebtResponse, UiLedgerEntry, UiLedgerHistoryRequest,
+    UiLedgerHistoryResponse, UiPayableAccount, UiReceivableAccount, UiScanRequest, UiServiceUsage,
+    UiServiceUsageRequest, UiServiceUsageResponse, UiUnbanRequest, UiUnbanResponse, UiWarnedDebtor,
 };
 use masq_lib::ui_gateway::{MessageBody, MessagePath};
 
+use crate::accountant::database_access_objects::banned_dao::{BanReason, BannedDao};
 use crate::accountant::database_access_objects::dao_utils::{
-    remap_payable_accounts, remap_receivable_accounts, CustomQuery, DaoFactoryReal,
+    from_time_t, remap_payable_accounts, remap_receivable_accounts, to_time_t, CustomQuery,
+    DaoFactoryReal,
+};
+use crate::accountant::database_access_objects::ledger_dao::{
+    LedgerDao, LedgerDaoError, LedgerEntry, LedgerEntryKind, PaymentOutcome,
 };
 use crate::accountant::database_access_objects::payable_dao::{
     PayableAccount, PayableDao, PayableDaoError,
@@ -28,10 +40,13 @@ use crate::accountant::database_access_objects::pending_payable_dao::PendingPaya
 use crate::accountant::database_access_objects::receivable_dao::{
     ReceivableDao, ReceivableDaoError,
 };
+use crate::accountant::database_access_objects::service_usage_dao::{
+    ServiceDirection, ServiceType, ServiceUsage, ServiceUsageDao, SERVICE_USAGE_BUCKET_S,
+};
 use crate::accountant::financials::visibility_restricted_module::{
     check_query_is_within_tech_limits, financials_entry_check,
 };
-use crate::accountant::scanners::{ScanTimings, Scanners};
+use crate::accountant::scanners::{BeginScanError, ScanTimings, Scanners};
 use crate::blockchain::blockchain_bridge::{
     PendingPayableFingerprint, PendingPayableFingerprintSeeds, RetrieveTransactions,
 };
@@ -41,15 +56,25 @@ use crate::blockchain::blockchain_interface::{
 use crate::bootstrapper::BootstrapperConfig;
 use crate::database::db_initializer::DbInitializationConfig;
 use crate::sub_lib::accountant::AccountantSubs;
+use crate::sub_lib::accountant::BanListChanges;
 use crate::sub_lib::accountant::DaoFactories;
+use crate::sub_lib::accountant::DelinquencyPolicy;
+use crate::sub_lib::accountant::EarningWallets;
 use crate::sub_lib::accountant::FinancialStatistics;
+use crate::sub_lib::accountant::PaymentThresholds;
 use crate::sub_lib::accountant::ReportExitServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportServicesConsumedMessage;
+use crate::sub_lib::accountant::SettleBeforeShutdown;
 use crate::sub_lib::accountant::{MessageIdGenerator, MessageIdGeneratorReal};
 use crate::sub_lib::blockchain_bridge::{
     ConsumingWalletBalances, ReportAccountsPayable, RequestBalancesToPayPayables,
 };
+use crate::sub_lib::configurator::{
+    ConfigurationChange, ConfigurationChangeMessage, NewEarningWalletMessage,
+};
+use crate::sub_lib::neighborhood::NoticeToDebtor;
+use crate::sub_lib::neighborhood::ShutdownStageComplete;
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
 use crate::sub_lib::utils::{handle_ui_crash_request, NODE_MAILBOX_CAPACITY};
 use crate::sub_lib::wallet::Wallet;
@@ -65,33 +90,44 @@ use masq_lib::crash_point::CrashPoint;
 use masq_lib::logger::Logger;
 use masq_lib::messages::UiFinancialsResponse;
 use masq_lib::messages::{FromMessageBody, ToMessageBody, UiFinancialsRequest};
-use masq_lib::ui_gateway::MessageTarget::ClientId;
+use masq_lib::ui_gateway::MessageTarget::{AllClients, ClientId};
 use masq_lib::ui_gateway::{NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::utils::ExpectValue;
 use std::any::type_name;
+use std::collections::BTreeMap;
 #[cfg(test)]
 use std::default::Default;
 use std::fmt::Display;
 use std::ops::{Div, Mul};
 use std::path::Path;
 use std::rc::Rc;
-use std::time::SystemTime;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use web3::types::{TransactionReceipt, H256};
 
 pub const CRASH_KEY: &str = "ACCOUNTANT";
 pub const DEFAULT_PENDING_TOO_LONG_SEC: u64 = 21_600; //6 hours
 
+// Largest number of ledger entries sent to a UI in one message; also the page size if the UI names none
+pub const LEDGER_PAGE_SIZE_LIMIT: u64 = 1_000;
+
 pub struct Accountant {
     suppress_initial_scans: bool,
     consuming_wallet: Option<Wallet>,
-    earning_wallet: Rc<Wallet>,
+    earning_wallets: Rc<RefCell<EarningWallets>>,
+    payment_thresholds: Rc<RefCell<PaymentThresholds>>,
     payable_dao: Box<dyn PayableDao>,
     receivable_dao: Box<dyn ReceivableDao>,
     pending_payable_dao: Box<dyn PendingPayableDao>,
+    banned_dao: Box<dyn BannedDao>,
+    ledger_dao: Box<dyn LedgerDao>,
+    service_usage_dao: Box<dyn ServiceUsageDao>,
     crashable: bool,
     scanners: Scanners,
     scan_timings: ScanTimings,
     financial_statistics: Rc<RefCell<FinancialStatistics>>,
+    ban_list_changes: Rc<RefCell<BanListChanges>>,
+    delinquency_policy: DelinquencyPolicy,
     report_accounts_payable_sub_opt: Option<Recipient<ReportAccountsPayable>>,
     request_balances_to_pay_payables_sub_opt: Option<Recipient<RequestBalancesToPayPayables>>,
     retrieve_transactions_sub_opt: Option<Recipient<RetrieveTransactions>>,
@@ -99,6 +135,9 @@ pub struct Accountant {
     report_inbound_payments_sub_opt: Option<Recipient<ReceivedPayments>>,
     report_sent_payables_sub_opt: Option<Recipient<SentPayables>>,
     ui_message_sub_opt: Option<Recipient<NodeToUiMessage>>,
+    notice_to_debtor_sub_opt: Option<Recipient<NoticeToDebtor>>,
+    shutdown_stage_complete_sub_opt: Option<Recipient<ShutdownStageComplete>>,
+    settling_before_shutdown: bool,
     message_id_generator: Box<dyn MessageIdGenerator>,
     logger: Logger,
 }
@@ -124,9 +163,30 @@ pub struct ReceivedPayments {
     // a problem? Do we want to correct the timestamp? Discuss.
     pub timestamp: SystemTime,
     pub payments: Vec<BlockchainTransaction>,
+    // a second look at blocks behind the start block, which earlier scans have already credited
+    pub rechecked_payments_opt: Option<RecheckedPayments>,
+    // credited payments missing from that second look; the Accountant fills this in
+    pub removed_payments: Vec<BlockchainTransaction>,
+    // present while the scan is still paging through older blocks; the scan goes on afterwards
+    pub catch_up_progress_opt: Option<ReceivablesCatchUpProgress>,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ReceivablesCatchUpProgress {
+    pub scanned_through_block: u64,
+    pub target_block: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecheckedPayments {
+    pub from_block: u64,
+    pub to_block: u64,
+    // the wallets the recheck asked about; payments to any other wallet weren't looked for again
+    pub recipients: Vec<Wallet>,
+    pub payments: Vec<BlockchainTransaction>,
+}
+
 #[derive(Debug, Message, PartialEq)]
 pub struct SentPayables {
     pub payment_procedure_result: Result<Vec<ProcessedPayableFallible>, PayableTransactionError>,
@@ -201,7 +261,11 @@ impl Handler<StartMessage> for Accountant {
 impl Handler<ReceivedPayments> for Accountant {
     type Result = ();
 
-    fn handle(&mut self, msg: ReceivedPayments, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, mut msg: ReceivedPayments, _ctx: &mut Self::Context) -> Self::Result {
+        if let Some(rechecked_payments) = msg.rechecked_payments_opt.take() {
+            msg.removed_payments = self.find_payments_lost_in_reorganization(rechecked_payments);
+        }
+        self.record_received_payments_in_ledger(&msg);
         if let Some(node_to_ui_msg) = self.scanners.receivable.finish_scan(msg, &self.logger) {
             self.ui_message_sub_opt
                 .as_ref()
@@ -243,6 +307,15 @@ impl Handler<SentPayables> for Accountant {
                 .try_send(node_to_ui_msg)
                 .expect("UIGateway is dead");
         }
+        self.report_debts_settled_if_settling()
+    }
+}
+
+impl Handler<SettleBeforeShutdown> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, _msg: SettleBeforeShutdown, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_settle_before_shutdown()
     }
 }
 
@@ -287,6 +360,7 @@ impl Handler<ScanError> for Accountant {
         match scan_error.scan_type {
             ScanType::Payables => {
                 self.scanners.payable.mark_as_ended(&self.logger);
+                self.report_debts_settled_if_settling();
             }
             ScanType::PendingPayables => {
                 self.scanners.pending_payable.mark_as_ended(&self.logger);
@@ -356,6 +430,26 @@ impl Handler<ReportServicesConsumedMessage> for Accountant {
     }
 }
 
+impl Handler<NewEarningWalletMessage> for Accountant {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEarningWalletMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_new_earning_wallet(msg)
+    }
+}
+
+impl Handler<ConfigurationChangeMessage> for Accountant {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ConfigurationChangeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_configuration_change(msg.change)
+    }
+}
+
 pub trait SkeletonOptHolder {
     fn skeleton_opt(&self) -> Option<ResponseSkeleton>;
 }
@@ -382,6 +476,7 @@ impl Handler<ReportTransactionReceipts> for Accountant {
     type Result = ();
 
     fn handle(&mut self, msg: ReportTransactionReceipts, _ctx: &mut Self::Context) -> Self::Result {
+        self.record_payment_outcomes_in_ledger(&msg);
         if let Some(node_to_ui_msg) = self.scanners.pending_payable.finish_scan(msg, &self.logger) {
             self.ui_message_sub_opt
                 .as_ref()
@@ -410,6 +505,20 @@ impl Handler<NodeFromUiMessage> for Accountant {
         let client_id = msg.client_id;
         if let Ok((request, context_id)) = UiFinancialsRequest::fmb(msg.body.clone()) {
             self.handle_financials(&request, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiLedgerHistoryRequest::fmb(msg.body.clone()) {
+            self.handle_ledger_history(&request, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiFinancialsExportRequest::fmb(msg.body.clone()) {
+            self.handle_financials_export(&request, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiServiceUsageRequest::fmb(msg.body.clone()) {
+            self.handle_service_usage(&request, client_id, context_id)
+        } else if let Ok((_, context_id)) = UiBanListRequest::fmb(msg.body.clone()) {
+            self.handle_ban_list(client_id, context_id)
+        } else if let Ok((request, context_id)) = UiBanRequest::fmb(msg.body.clone()) {
+            self.handle_ban(&request, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiUnbanRequest::fmb(msg.body.clone()) {
+            self.handle_unban(&request, client_id, context_id)
+        } else if let Ok((request, context_id)) = UiForgiveDebtRequest::fmb(msg.body.clone()) {
+            self.handle_forgive_debt(&request, client_id, context_id)
         } else if let Ok((body, context_id)) = UiScanRequest::fmb(msg.body.clone()) {
             self.handle_externally_triggered_scan(
                 ctx,
@@ -427,32 +536,49 @@ impl Handler<NodeFromUiMessage> for Accountant {
 
 impl Accountant {
     pub fn new(config: BootstrapperConfig, dao_factories: DaoFactories) -> Accountant {
-        let payment_thresholds = config.payment_thresholds_opt.expectv("Payment thresholds");
+        let payment_thresholds = Rc::new(RefCell::new(
+            config.payment_thresholds_opt.expectv("Payment thresholds"),
+        ));
         let scan_intervals = config.scan_intervals_opt.expectv("Scan Intervals");
-        let earning_wallet = Rc::new(config.earning_wallet);
+        let earning_wallets = Rc::new(RefCell::new(EarningWallets::new(
+            config.earning_wallet,
+            config.retired_earning_wallets,
+        )));
         let financial_statistics = Rc::new(RefCell::new(FinancialStatistics::default()));
         let payable_dao = dao_factories.payable_dao_factory.make();
         let pending_payable_dao = dao_factories.pending_payable_dao_factory.make();
         let receivable_dao = dao_factories.receivable_dao_factory.make();
+        let banned_dao = dao_factories.banned_dao_factory.make();
+        let ledger_dao = dao_factories.ledger_dao_factory.make();
+        let service_usage_dao = dao_factories.service_usage_dao_factory.make();
+        let ban_list_changes = Rc::new(RefCell::new(BanListChanges::default()));
         let scanners = Scanners::new(
             dao_factories,
-            Rc::new(payment_thresholds),
-            Rc::clone(&earning_wallet),
+            Rc::clone(&payment_thresholds),
+            Rc::clone(&earning_wallets),
             config.when_pending_too_long_sec,
             Rc::clone(&financial_statistics),
+            Rc::clone(&ban_list_changes),
+            config.delinquency_policy,
         );
 
         Accountant {
             suppress_initial_scans: config.suppress_initial_scans,
             consuming_wallet: config.consuming_wallet_opt.clone(),
-            earning_wallet: Rc::clone(&earning_wallet),
+            earning_wallets,
+            payment_thresholds,
             payable_dao,
             receivable_dao,
             pending_payable_dao,
+            banned_dao,
+            ledger_dao,
+            service_usage_dao,
             scanners,
             crashable: config.crash_point == CrashPoint::Message,
             scan_timings: ScanTimings::new(scan_intervals),
             financial_statistics: Rc::clone(&financial_statistics),
+            ban_list_changes,
+            delinquency_policy: config.delinquency_policy,
             report_accounts_payable_sub_opt: None,
             request_balances_to_pay_payables_sub_opt: None,
             report_sent_payables_sub_opt: None,
@@ -460,6 +586,9 @@ impl Accountant {
             report_inbound_payments_sub_opt: None,
             request_transaction_receipts_subs_opt: None,
             ui_message_sub_opt: None,
+            notice_to_debtor_sub_opt: None,
+            shutdown_stage_complete_sub_opt: None,
+            settling_before_shutdown: false,
             message_id_generator: Box::new(MessageIdGeneratorReal::default()),
             logger: Logger::new("Accountant"),
         }
@@ -482,6 +611,9 @@ impl Accountant {
             report_sent_payments: recipient!(addr, SentPayables),
             scan_errors: recipient!(addr, ScanError),
             ui_message_sub: recipient!(addr, NodeFromUiMessage),
+            new_earning_wallet_sub: recipient!(addr, NewEarningWalletMessage),
+            settle_before_shutdown_sub: recipient!(addr, SettleBeforeShutdown),
+            configuration_change_sub: recipient!(addr, ConfigurationChangeMessage),
         }
     }
 
@@ -490,7 +622,8 @@ impl Accountant {
     }
 
     fn record_service_provided(
-        &self,
+        &mut self,
+        service: ServiceType,
         service_rate: u64,
         byte_rate: u64,
         timestamp: SystemTime,
@@ -503,7 +636,22 @@ impl Accountant {
             match self.receivable_dao
                 .as_ref()
                 .more_money_receivable(timestamp,wallet, total_charge) {
-                Ok(_) => (),
+                Ok(_) => {
+                    self.record_in_ledger(&[LedgerEntry::new(
+                        timestamp,
+                        wallet.clone(),
+                        LedgerEntryKind::ServiceProvided,
+                        total_charge,
+                    )]);
+                    self.record_service_usage(ServiceUsage {
+                        timestamp,
+                        wallet: wallet.clone(),
+                        service,
+                        direction: ServiceDirection::Provided,
+                        bytes: payload_size as u64,
+                        charge_wei: total_charge,
+                    })
+                }
                 Err(ReceivableDaoError::SignConversion(_)) => error! (
                     self.logger,
                     "Overflow error recording service provided for {}: service rate {}, byte rate {}, payload size {}. Skipping",
@@ -524,7 +672,8 @@ impl Accountant {
     }
 
     fn record_service_consumed(
-        &self,
+        &mut self,
+        service: ServiceType,
         service_rate: u64,
         byte_rate: u64,
         timestamp: SystemTime,
@@ -537,7 +686,22 @@ impl Accountant {
             match self.payable_dao
                 .as_ref()
                 .more_money_payable(timestamp, wallet,total_charge){
-                Ok(_) => (),
+                Ok(_) => {
+                    self.record_in_ledger(&[LedgerEntry::new(
+                        timestamp,
+                        wallet.clone(),
+                        LedgerEntryKind::ServiceConsumed,
+                        total_charge,
+                    )]);
+                    self.record_service_usage(ServiceUsage {
+                        timestamp,
+                        wallet: wallet.clone(),
+                        service,
+                        direction: ServiceDirection::Consumed,
+                        bytes: payload_size as u64,
+                        charge_wei: total_charge,
+                    })
+                }
                 Err(PayableDaoError::SignConversion(_)) => error! (
                     self.logger,
                     "Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate {}, payload size {}. Skipping",
@@ -558,10 +722,168 @@ impl Accountant {
         }
     }
 
+    fn record_in_ledger(&mut self, entries: &[LedgerEntry]) {
+        if let Err(e) = self.ledger_dao.record(entries) {
+            warning!(
+                self.logger,
+                "Failed to record {} entries in the ledger: {:?}",
+                entries.len(),
+                e
+            )
+        }
+    }
+
+    fn record_service_usage(&self, usage: ServiceUsage) {
+        if let Err(e) = self.service_usage_dao.record(&usage) {
+            warning!(
+                self.logger,
+                "Failed to record {} {} service usage for {}: {:?}",
+                usage.direction.as_str(),
+                usage.service.as_str(),
+                usage.wallet,
+                e
+            )
+        }
+    }
+
+    fn find_payments_lost_in_reorganization(
+        &self,
+        rechecked_payments: RecheckedPayments,
+    ) -> Vec<BlockchainTransaction> {
+        let credited_payments = self.receivable_dao.received_payments_in_range(
+            rechecked_payments.from_block,
+            rechecked_payments.to_block,
+            &rechecked_payments.recipients,
+        );
+        if let Err(e) = self
+            .receivable_dao
+            .forget_received_payments_before(rechecked_payments.from_block)
+        {
+            warning!(
+                self.logger,
+                "Failed to forget received payments before block {}: {:?}",
+                rechecked_payments.from_block,
+                e
+            )
+        }
+        let still_on_chain = |credited: &BlockchainTransaction| {
+            rechecked_payments.payments.iter().any(|found| {
+                found.transaction_hash == credited.transaction_hash
+                    && found.from == credited.from
+                    && found.to == credited.to
+            })
+        };
+        credited_payments
+            .into_iter()
+            .filter(|credited| !still_on_chain(credited))
+            .collect()
+    }
+
+    fn record_received_payments_in_ledger(&mut self, msg: &ReceivedPayments) {
+        let entry = |payment: &BlockchainTransaction, outcome: PaymentOutcome| LedgerEntry {
+            transaction_hash_opt: Some(payment.transaction_hash),
+            block_number_opt: Some(payment.block_number),
+            outcome_opt: Some(outcome),
+            ..LedgerEntry::new(
+                msg.timestamp,
+                payment.from.clone(),
+                LedgerEntryKind::PaymentReceived,
+                payment.wei_amount,
+            )
+        };
+        let entries = msg
+            .payments
+            .iter()
+            .map(|payment| entry(payment, PaymentOutcome::Confirmed))
+            .chain(
+                msg.removed_payments
+                    .iter()
+                    .map(|payment| entry(payment, PaymentOutcome::Reverted)),
+            )
+            .collect::<Vec<LedgerEntry>>();
+        if !entries.is_empty() {
+            self.record_in_ledger(&entries)
+        }
+    }
+
+    fn record_payment_outcomes_in_ledger(&self, msg: &ReportTransactionReceipts) {
+        let now = SystemTime::now();
+        msg.fingerprints_with_receipts
+            .iter()
+            .filter_map(|(receipt_opt, fingerprint)| {
+                let receipt = receipt_opt.as_ref()?;
+                let outcome = match receipt.status.map(|status| status.as_u64()) {
+                    Some(1) => PaymentOutcome::Confirmed,
+                    Some(0) => PaymentOutcome::Failed,
+                    _ => return None,
+                };
+                let block_number_opt = receipt.block_number.map(|number| number.as_u64());
+                Some((fingerprint.hash, outcome, block_number_opt))
+            })
+            .for_each(|(hash, outcome, block_number_opt)| {
+                match self
+                    .ledger_dao
+                    .record_payment_outcome(hash, outcome, block_number_opt, now)
+                {
+                    Ok(()) => (),
+                    Err(LedgerDaoError::PaymentNotFound(_)) => debug!(
+                        self.logger,
+                        "Payment {:?} was sent before the ledger knew about it; its outcome is not recorded",
+                        hash
+                    ),
+                    Err(e) => warning!(
+                        self.logger,
+                        "Failed to record the outcome of payment {:?} in the ledger: {:?}",
+                        hash,
+                        e
+                    ),
+                }
+            })
+    }
+
+    fn handle_new_earning_wallet(&mut self, msg: NewEarningWalletMessage) {
+        info!(
+            self.logger,
+            "Earning wallet rotated from {} to {}", msg.retired.wallet, msg.new_earning_wallet
+        );
+        self.earning_wallets
+            .borrow_mut()
+            .rotate(msg.new_earning_wallet, msg.retired);
+    }
+
+    fn handle_configuration_change(&mut self, change: ConfigurationChange) {
+        match change {
+            ConfigurationChange::PaymentThresholds(payment_thresholds) => {
+                *self.payment_thresholds.borrow_mut() = payment_thresholds;
+                info!(
+                    self.logger,
+                    "Payment thresholds changed to {}", payment_thresholds
+                );
+            }
+            ConfigurationChange::ScanIntervals(scan_intervals) => {
+                self.scan_timings.pending_payable.interval =
+                    scan_intervals.pending_payable_scan_interval;
+                self.scan_timings.payable.interval = scan_intervals.payable_scan_interval;
+                self.scan_timings.receivable.interval = scan_intervals.receivable_scan_interval;
+                info!(
+                    self.logger,
+                    "Scan intervals changed to {}; they apply from the next scheduled scan",
+                    scan_intervals
+                );
+            }
+            _ => (),
+        }
+    }
+
     fn our_wallet(&self, wallet: &Wallet) -> bool {
         match &self.consuming_wallet {
             Some(ref consuming) if consuming.address() == wallet.address() => true,
-            _ => wallet.address() == self.earning_wallet.address(),
+            _ => self
+                .earning_wallets
+                .borrow()
+                .watched()
+                .iter()
+                .any(|earning_wallet| earning_wallet.address() == wallet.address()),
         }
     }
 
@@ -579,6 +901,9 @@ impl Accountant {
         );
         self.report_sent_payables_sub_opt = Some(msg.peer_actors.accountant.report_sent_payments);
         self.ui_message_sub_opt = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
+        self.notice_to_debtor_sub_opt = Some(msg.peer_actors.neighborhood.notice_to_debtor_sub);
+        self.shutdown_stage_complete_sub_opt =
+            Some(msg.peer_actors.neighborhood.shutdown_stage_complete_sub);
         self.request_transaction_receipts_subs_opt = Some(
             msg.peer_actors
                 .blockchain_bridge
@@ -596,6 +921,7 @@ impl Accountant {
             "Charging routing of {} bytes to wallet {}", msg.payload_size, msg.paying_wallet
         );
         self.record_service_provided(
+            ServiceType::Routing,
             msg.service_rate,
             msg.byte_rate,
             msg.timestamp,
@@ -617,6 +943,7 @@ impl Accountant {
             msg.byte_rate
         );
         self.record_service_provided(
+            ServiceType::Exit,
             msg.service_rate,
             msg.byte_rate,
             msg.timestamp,
@@ -643,6 +970,7 @@ impl Accountant {
             msg.exit.payload_size
         );
         self.record_service_consumed(
+            ServiceType::Exit,
             msg.exit.service_rate,
             msg.exit.byte_rate,
             msg.timestamp,
@@ -658,6 +986,7 @@ impl Accountant {
                 msg.routing_payload_size
             );
             self.record_service_consumed(
+                ServiceType::Routing,
                 routing_service.service_rate,
                 routing_service.byte_rate,
                 msg.timestamp,
@@ -679,6 +1008,457 @@ impl Accountant {
             .expect("UiGateway is dead");
     }
 
+    fn handle_ledger_history(&self, msg: &UiLedgerHistoryRequest, client_id: u64, context_id: u64) {
+        let body = self.compute_ledger_history(msg, context_id);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
+
+    fn compute_ledger_history(&self, msg: &UiLedgerHistoryRequest, context_id: u64) -> MessageBody {
+        let error_body = |message: String| MessageBody {
+            opcode: "ledgerHistory".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((LEDGER_HISTORY_ERROR, message)),
+        };
+        let wallet_opt = match msg
+            .wallet_opt
+            .as_ref()
+            .map(|wallet| Wallet::from_str(wallet))
+        {
+            None => None,
+            Some(Ok(wallet)) => Some(wallet),
+            Some(Err(e)) => return error_body(format!("Wallet address is invalid: {:?}", e)),
+        };
+        let min_age_s = msg.min_age_s_opt.unwrap_or(0);
+        let max_age_s = msg.max_age_s_opt.unwrap_or(u64::MAX);
+        if min_age_s > max_age_s {
+            return error_body(format!(
+                "Age range must be low to high, not {}-{}",
+                min_age_s, max_age_s
+            ));
+        }
+        let offset = msg.offset_opt.unwrap_or(0);
+        let limit = msg.limit_opt.unwrap_or(LEDGER_PAGE_SIZE_LIMIT);
+        if limit == 0 || limit > LEDGER_PAGE_SIZE_LIMIT {
+            return error_body(format!(
+                "Page size must be between 1 and {}, not {}",
+                LEDGER_PAGE_SIZE_LIMIT, limit
+            ));
+        }
+        let now = SystemTime::now();
+        let age_to_time = |age_s: u64| {
+            now.checked_sub(Duration::from_secs(age_s))
+                .filter(|time| *time >= UNIX_EPOCH)
+                .unwrap_or(UNIX_EPOCH)
+        };
+        // One entry more than asked for tells whether there's another page
+        let mut ledger_entries = self.ledger_dao.history(
+            wallet_opt.as_ref(),
+            age_to_time(max_age_s),
+            age_to_time(min_age_s),
+            offset,
+            limit + 1,
+        );
+        let next_offset_opt = if ledger_entries.len() as u64 > limit {
+            ledger_entries.truncate(limit as usize);
+            Some(offset.saturating_add(limit))
+        } else {
+            None
+        };
+        let entries = ledger_entries
+            .into_iter()
+            .map(|entry| UiLedgerEntry {
+                timestamp: to_time_t(entry.timestamp) as u64,
+                wallet: entry.wallet.to_string(),
+                kind: entry.kind.as_str().to_string(),
+                amount_wei: entry.amount_wei.to_string(),
+                transaction_hash_opt: entry.transaction_hash_opt.map(|hash| format!("{:?}", hash)),
+                nonce_opt: entry.nonce_opt,
+                gas_price_gwei_opt: entry.gas_price_gwei_opt,
+                block_number_opt: entry.block_number_opt,
+                outcome_opt: entry
+                    .outcome_opt
+                    .map(|outcome| outcome.as_str().to_string()),
+            })
+            .collect();
+        UiLedgerHistoryResponse {
+            entries,
+            next_offset_opt,
+        }
+        .tmb(context_id)
+    }
+
+    fn handle_financials_export(
+        &self,
+        msg: &UiFinancialsExportRequest,
+        client_id: u64,
+        context_id: u64,
+    ) {
+        let body = self.compute_financials_export(msg, context_id);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
+
+    fn compute_financials_export(
+        &self,
+        msg: &UiFinancialsExportRequest,
+        context_id: u64,
+    ) -> MessageBody {
+        let error_body = |message: String| MessageBody {
+            opcode: "financialsExport".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((FINANCIALS_EXPORT_ERROR, message)),
+        };
+        let from_timestamp = msg.from_timestamp_opt.unwrap_or(0).min(i64::MAX as u64);
+        let to_timestamp = msg
+            .to_timestamp_opt
+            .unwrap_or_else(|| to_time_t(SystemTime::now()) as u64)
+            .min(i64::MAX as u64);
+        if from_timestamp > to_timestamp {
+            return error_body(format!(
+                "Time window must run from earlier to later, not {}-{}",
+                from_timestamp, to_timestamp
+            ));
+        }
+        let offset = msg.offset_opt.unwrap_or(0);
+        let limit = msg.limit_opt.unwrap_or(LEDGER_PAGE_SIZE_LIMIT);
+        if limit == 0 || limit > LEDGER_PAGE_SIZE_LIMIT {
+            return error_body(format!(
+                "Page size must be between 1 and {}, not {}",
+                LEDGER_PAGE_SIZE_LIMIT, limit
+            ));
+        }
+        let from = from_time_t(from_timestamp as i64);
+        let to = from_time_t(to_timestamp as i64);
+        // Accounts come with the first page only; later pages carry just the rest of the ledger
+        let (payables, receivables) = if offset == 0 {
+            self.exported_accounts(from, to)
+        } else {
+            (vec![], vec![])
+        };
+        // One entry more than asked for tells whether there's another page
+        let mut ledger_entries = self.ledger_dao.history(None, from, to, offset, limit + 1);
+        let next_offset_opt = if ledger_entries.len() as u64 > limit {
+            ledger_entries.truncate(limit as usize);
+            Some(offset.saturating_add(limit))
+        } else {
+            None
+        };
+        let transactions = ledger_entries
+            .into_iter()
+            .map(|entry| UiExportedTransaction {
+                timestamp: to_time_t(entry.timestamp) as u64,
+                wallet: entry.wallet.to_string(),
+                kind: entry.kind.as_str().to_string(),
+                amount_wei: entry.amount_wei.to_string(),
+                transaction_hash_opt: entry.transaction_hash_opt.map(|hash| format!("{:?}", hash)),
+                nonce_opt: entry.nonce_opt,
+                gas_price_gwei_opt: entry.gas_price_gwei_opt,
+                block_number_opt: entry.block_number_opt,
+                outcome_opt: entry
+                    .outcome_opt
+                    .map(|outcome| outcome.as_str().to_string()),
+            })
+            .collect();
+        UiFinancialsExportResponse {
+            payables,
+            receivables,
+            transactions,
+            next_offset_opt,
+        }
+        .tmb(context_id)
+    }
+
+    fn exported_accounts(
+        &self,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> (Vec<UiExportedAccount>, Vec<UiExportedAccount>) {
+        let payables = self
+            .payable_dao
+            .accounts_active_between(from, to)
+            .into_iter()
+            .map(|account| UiExportedAccount {
+                wallet: account.wallet.to_string(),
+                balance_wei: account.balance_wei.to_string(),
+                last_activity_timestamp: to_time_t(account.last_paid_timestamp) as u64,
+                pending_payable_hash_opt: account
+                    .pending_payable_opt
+                    .map(|id| format!("{:?}", id.hash)),
+            })
+            .collect();
+        let receivables = self
+            .receivable_dao
+            .accounts_active_between(from, to)
+            .into_iter()
+            .map(|account| UiExportedAccount {
+                wallet: account.wallet.to_string(),
+                balance_wei: account.balance_wei.to_string(),
+                last_activity_timestamp: to_time_t(account.last_received_timestamp) as u64,
+                pending_payable_hash_opt: None,
+            })
+            .collect();
+        (payables, receivables)
+    }
+
+    fn handle_service_usage(&self, msg: &UiServiceUsageRequest, client_id: u64, context_id: u64) {
+        let body = self.compute_service_usage(msg, context_id);
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
+
+    fn compute_service_usage(&self, msg: &UiServiceUsageRequest, context_id: u64) -> MessageBody {
+        let error_body = |message: String| MessageBody {
+            opcode: "serviceUsage".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((SERVICE_USAGE_ERROR, message)),
+        };
+        let wallet_opt = match msg
+            .wallet_opt
+            .as_ref()
+            .map(|wallet| Wallet::from_str(wallet))
+        {
+            None => None,
+            Some(Ok(wallet)) => Some(wallet),
+            Some(Err(e)) => return error_body(format!("Wallet address is invalid: {:?}", e)),
+        };
+        let min_age_s = msg.min_age_s_opt.unwrap_or(0);
+        let max_age_s = msg.max_age_s_opt.unwrap_or(u64::MAX);
+        if min_age_s > max_age_s {
+            return error_body(format!(
+                "Age range must be low to high, not {}-{}",
+                min_age_s, max_age_s
+            ));
+        }
+        let bucket_size_s = msg.bucket_size_s_opt.unwrap_or(SERVICE_USAGE_BUCKET_S);
+        if bucket_size_s == 0 || bucket_size_s % SERVICE_USAGE_BUCKET_S != 0 {
+            return error_body(format!(
+                "Bucket size must be a positive multiple of {} seconds, not {}",
+                SERVICE_USAGE_BUCKET_S, bucket_size_s
+            ));
+        }
+        let now = SystemTime::now();
+        let age_to_time = |age_s: u64| {
+            now.checked_sub(Duration::from_secs(age_s))
+                .filter(|time| *time >= UNIX_EPOCH)
+                .unwrap_or(UNIX_EPOCH)
+        };
+        // Usage is stored in the finest buckets; coarser ones are folded together here
+        let mut buckets: BTreeMap<(u64, String, ServiceType, ServiceDirection), (u64, u64, u128)> =
+            BTreeMap::new();
+        self.service_usage_dao
+            .usage(
+                wallet_opt.as_ref(),
+                age_to_time(max_age_s),
+                age_to_time(min_age_s),
+            )
+            .into_iter()
+            .for_each(|bucket| {
+                let start = to_time_t(bucket.bucket_start) as u64;
+                let key = (
+                    start - start % bucket_size_s,
+                    bucket.wallet.to_string(),
+                    bucket.service,
+                    bucket.direction,
+                );
+                let totals = buckets.entry(key).or_insert((0, 0, 0));
+                totals.0 = totals.0.saturating_add(bucket.service_count);
+                totals.1 = totals.1.saturating_add(bucket.bytes);
+                totals.2 = totals.2.saturating_add(bucket.charge_wei);
+            });
+        let usage = buckets
+            .into_iter()
+            .map(
+                |(
+                    (bucket_start, wallet, service, direction),
+                    (service_count, bytes, charge_wei),
+                )| {
+                    UiServiceUsage {
+                        bucket_start,
+                        wallet,
+                        service: service.as_str().to_string(),
+                        direction: direction.as_str().to_string(),
+                        service_count,
+                        bytes,
+                        charge_wei: charge_wei.to_string(),
+                    }
+                },
+            )
+            .collect();
+        UiServiceUsageResponse { usage }.tmb(context_id)
+    }
+
+    fn handle_ban_list(&self, client_id: u64, context_id: u64) {
+        let body = self.compute_ban_list(context_id);
+        self.send_to_client(client_id, body)
+    }
+
+    fn compute_ban_list(&self, context_id: u64) -> MessageBody {
+        let banned = self
+            .banned_dao
+            .ban_list()
+            .into_iter()
+            .map(|banned_wallet| UiBannedWallet {
+                wallet: banned_wallet.wallet.to_string(),
+                reason: banned_wallet.reason.as_str().to_string(),
+                note_opt: banned_wallet.note_opt,
+                timestamp_opt: banned_wallet
+                    .timestamp_opt
+                    .map(|timestamp| to_time_t(timestamp) as u64),
+            })
+            .collect();
+        UiBanListResponse { banned }.tmb(context_id)
+    }
+
+    fn handle_ban(&self, msg: &UiBanRequest, client_id: u64, context_id: u64) {
+        let body = self.compute_ban(msg, context_id);
+        self.send_to_client(client_id, body)
+    }
+
+    fn compute_ban(&self, msg: &UiBanRequest, context_id: u64) -> MessageBody {
+        let wallet = match Self::ban_list_wallet(&msg.wallet, "ban", context_id) {
+            Ok(wallet) => wallet,
+            Err(body) => return body,
+        };
+        if self.our_wallet(&wallet) {
+            return MessageBody {
+                opcode: "ban".to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((
+                    BAN_LIST_ERROR,
+                    format!("Wallet {} belongs to this Node and can't be banned", wallet),
+                )),
+            };
+        }
+        let newly_banned = self.banned_dao.ban(
+            &wallet,
+            BanReason::Manual,
+            msg.note_opt.as_deref(),
+            SystemTime::now(),
+        );
+        if newly_banned {
+            info!(self.logger, "Wallet {} banned by request from UI", wallet)
+        }
+        UiBanResponse { newly_banned }.tmb(context_id)
+    }
+
+    fn handle_unban(&self, msg: &UiUnbanRequest, client_id: u64, context_id: u64) {
+        let body = self.compute_unban(msg, context_id);
+        self.send_to_client(client_id, body)
+    }
+
+    fn compute_unban(&self, msg: &UiUnbanRequest, context_id: u64) -> MessageBody {
+        let wallet = match Self::ban_list_wallet(&msg.wallet, "unban", context_id) {
+            Ok(wallet) => wallet,
+            Err(body) => return body,
+        };
+        let was_banned = self.banned_dao.unban(&wallet);
+        if was_banned {
+            info!(self.logger, "Wallet {} unbanned by request from UI", wallet)
+        }
+        UiUnbanResponse { was_banned }.tmb(context_id)
+    }
+
+    fn handle_forgive_debt(&mut self, msg: &UiForgiveDebtRequest, client_id: u64, context_id: u64) {
+        let body = self.compute_forgive_debt(msg, context_id);
+        self.send_to_client(client_id, body)
+    }
+
+    fn compute_forgive_debt(&mut self, msg: &UiForgiveDebtRequest, context_id: u64) -> MessageBody {
+        let error_body = |message: String| MessageBody {
+            opcode: "forgiveDebt".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((DEBT_FORGIVENESS_ERROR, message)),
+        };
+        let wallet = match Wallet::from_str(&msg.wallet) {
+            Ok(wallet) => wallet,
+            Err(e) => return error_body(format!("Wallet address is invalid: {:?}", e)),
+        };
+        let now = SystemTime::now();
+        let result = if msg.write_off {
+            self.receivable_dao.write_off(&wallet)
+        } else {
+            self.receivable_dao.restart_aging(&wallet, now)
+        };
+        let account = match result {
+            Ok(Some(account)) => account,
+            Ok(None) => return error_body(format!("Wallet {} owes us nothing", wallet)),
+            Err(e) => return error_body(format!("Couldn't forgive the debt: {:?}", e)),
+        };
+        if msg.write_off && account.balance_wei > 0 {
+            self.record_in_ledger(&[LedgerEntry::new(
+                now,
+                wallet.clone(),
+                LedgerEntryKind::DebtWrittenOff,
+                account.balance_wei as u128,
+            )]);
+        }
+        self.banned_dao.unwarn(&wallet);
+        let delinquency_banned = self
+            .banned_dao
+            .ban_list()
+            .iter()
+            .any(|banned| banned.wallet == wallet && banned.reason == BanReason::Delinquency);
+        let unbanned = delinquency_banned && self.banned_dao.unban(&wallet);
+        info!(
+            self.logger,
+            "Debt of {} wei owed by wallet {} {} by request from UI{}",
+            account.balance_wei,
+            wallet,
+            if msg.write_off {
+                "written off"
+            } else {
+                "forgiven"
+            },
+            if unbanned { "; wallet unbanned" } else { "" }
+        );
+        UiForgiveDebtResponse {
+            balance_wei: account.balance_wei.to_string(),
+            unbanned,
+        }
+        .tmb(context_id)
+    }
+
+    fn ban_list_wallet(wallet: &str, opcode: &str, context_id: u64) -> Result<Wallet, MessageBody> {
+        Wallet::from_str(wallet).map_err(|e| MessageBody {
+            opcode: opcode.to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((
+                BAN_LIST_ERROR,
+                format!("Wallet address is invalid: {:?}", e),
+            )),
+        })
+    }
+
+    fn send_to_client(&self, client_id: u64, body: MessageBody) {
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway not bound")
+            .try_send(NodeToUiMessage {
+                target: ClientId(client_id),
+                body,
+            })
+            .expect("UiGateway is dead");
+    }
+
     fn compute_financials(&self, msg: &UiFinancialsRequest, context_id: u64) -> MessageBody {
         if let Err(message_body) = financials_entry_check(msg, context_id) {
             return message_body;
@@ -819,6 +1599,43 @@ impl Accountant {
         }
     }
 
+    fn handle_settle_before_shutdown(&mut self) {
+        info!(
+            self.logger,
+            "Running a final payable scan before shutting down"
+        );
+        self.settling_before_shutdown = true;
+        match self
+            .scanners
+            .payable
+            .begin_scan(SystemTime::now(), None, &self.logger)
+        {
+            Ok(scan_message) => self
+                .request_balances_to_pay_payables_sub_opt
+                .as_ref()
+                .expect("BlockchainBridge is unbound")
+                .try_send(scan_message)
+                .expect("BlockchainBridge is dead"),
+            // the scan already in flight will end with SentPayables or ScanError
+            Err(BeginScanError::ScanAlreadyRunning(_)) => (),
+            Err(e) => {
+                e.handle_error(&self.logger, ScanType::Payables, true);
+                self.report_debts_settled_if_settling()
+            }
+        }
+    }
+
+    fn report_debts_settled_if_settling(&mut self) {
+        if self.settling_before_shutdown {
+            self.settling_before_shutdown = false;
+            self.shutdown_stage_complete_sub_opt
+                .as_ref()
+                .expect("Neighborhood is unbound")
+                .try_send(ShutdownStageComplete::DebtsSettled)
+                .expect("Neighborhood is dead")
+        }
+    }
+
     fn handle_request_of_scan_for_pending_payable(
         &mut self,
         response_skeleton_opt: Option<ResponseSkeleton>,
@@ -863,6 +1680,53 @@ impl Accountant {
                 response_skeleton_opt.is_some(),
             ),
         };
+        self.broadcast_ban_list_changes()
+    }
+
+    fn broadcast_ban_list_changes(&self) {
+        let changes = self.ban_list_changes.take();
+        if changes.banned.is_empty() && changes.unbanned.is_empty() && changes.warned.is_empty() {
+            return;
+        }
+        if self.delinquency_policy.notify_debtors {
+            let notice_to_debtor_sub = self
+                .notice_to_debtor_sub_opt
+                .as_ref()
+                .expect("Neighborhood is not bound");
+            changes.warned.iter().for_each(|debtor| {
+                notice_to_debtor_sub
+                    .try_send(NoticeToDebtor {
+                        wallet: debtor.wallet.clone(),
+                        balance_wei: debtor.balance_wei,
+                        ban_deadline: debtor.ban_deadline,
+                    })
+                    .expect("Neighborhood is dead")
+            });
+        }
+        let to_strings =
+            |wallets: Vec<Wallet>| wallets.iter().map(|wallet| wallet.to_string()).collect();
+        let warned = changes
+            .warned
+            .into_iter()
+            .map(|debtor| UiWarnedDebtor {
+                wallet: debtor.wallet.to_string(),
+                balance_wei: debtor.balance_wei.to_string(),
+                ban_timestamp: to_time_t(debtor.ban_deadline) as u64,
+            })
+            .collect();
+        self.ui_message_sub_opt
+            .as_ref()
+            .expect("UIGateway is not bound")
+            .try_send(NodeToUiMessage {
+                target: AllClients,
+                body: UiBanListChangedBroadcast {
+                    banned: to_strings(changes.banned),
+                    unbanned: to_strings(changes.unbanned),
+                    warned,
+                }
+                .tmb(0),
+            })
+            .expect("UIGateway is dead");
     }
 
     fn handle_externally_triggered_scan(
@@ -1003,6 +1867,7 @@ pub mod check_sqlite_fns {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::accountant::database_access_objects::banned_dao::BannedWallet;
     use crate::accountant::database_access_objects::dao_utils::from_time_t;
     use crate::accountant::database_access_objects::dao_utils::{to_time_t, CustomQuery};
     use crate::accountant::database_access_objects::payable_dao::{
@@ -1010,15 +1875,20 @@ mod tests {
     };
     use crate::accountant::database_access_objects::pending_payable_dao::PendingPayableDaoError;
     use crate::accountant::database_access_objects::receivable_dao::ReceivableAccount;
-    use crate::accountant::scanners::{BeginScanError, NullScanner, ScannerMock};
+    use crate::accountant::database_access_objects::service_usage_dao::{
+        ServiceUsageBucket, ServiceUsageDaoError,
+    };
+    use crate::accountant::scanners::{BeginScanError, NullScanner, PayableScanner, ScannerMock};
     use crate::accountant::test_utils::DaoWithDestination::{
         ForAccountantBody, ForPayableScanner, ForPendingPayableScanner, ForReceivableScanner,
     };
     use crate::accountant::test_utils::{
         bc_from_earning_wallet, bc_from_wallets, make_payable_account, make_payables,
-        BannedDaoFactoryMock, MessageIdGeneratorMock, PayableDaoFactoryMock, PayableDaoMock,
-        PendingPayableDaoFactoryMock, PendingPayableDaoMock, ReceivableDaoFactoryMock,
-        ReceivableDaoMock,
+        make_pending_payable_fingerprint, make_receivable_account, BannedDaoFactoryMock,
+        LedgerDaoFactoryMock, LedgerDaoMock, MessageIdGeneratorMock, PayableDaoFactoryMock,
+        PayableDaoMock, PendingPayableDaoFactoryMock, PendingPayableDaoMock,
+        ReceivableDaoFactoryMock, ReceivableDaoMock, ServiceUsageDaoFactoryMock,
+        ServiceUsageDaoMock,
     };
     use crate::accountant::test_utils::{AccountantBuilder, BannedDaoMock};
     use crate::accountant::Accountant;
@@ -1028,9 +1898,10 @@ mod tests {
     use crate::blockchain::test_utils::{make_tx_hash, BlockchainInterfaceMock};
     use crate::match_every_type_id;
     use crate::sub_lib::accountant::{
-        ExitServiceConsumed, PaymentThresholds, RoutingServiceConsumed, ScanIntervals,
-        DEFAULT_PAYMENT_THRESHOLDS,
+        ExitServiceConsumed, PaymentThresholds, RetiredEarningWallet, RoutingServiceConsumed,
+        ScanIntervals, DEFAULT_PAYMENT_THRESHOLDS,
     };
+    use crate::sub_lib::neighborhood::Hops;
     use crate::sub_lib::utils::NotifyLaterHandleReal;
     use crate::test_utils::persistent_configuration_mock::PersistentConfigurationMock;
     use crate::test_utils::recorder::make_recorder;
@@ -1096,6 +1967,8 @@ mod tests {
         let pending_payable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
         let receivable_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
         let banned_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let ledger_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
+        let service_usage_dao_factory_params_arc = Arc::new(Mutex::new(vec![]));
         let payable_dao_factory = PayableDaoFactoryMock::new()
             .make_params(&payable_dao_factory_params_arc)
             .make_result(PayableDaoMock::new()) // For Accountant
@@ -1112,7 +1985,14 @@ mod tests {
             .make_result(ReceivableDaoMock::new()); // For Receivable Scanner
         let banned_dao_factory = BannedDaoFactoryMock::new()
             .make_params(&banned_dao_factory_params_arc)
+            .make_result(BannedDaoMock::new()) // For Accountant
             .make_result(BannedDaoMock::new()); // For Receivable Scanner
+        let ledger_dao_factory = LedgerDaoFactoryMock::new()
+            .make_params(&ledger_dao_factory_params_arc)
+            .make_result(LedgerDaoMock::new()); // For Accountant
+        let service_usage_dao_factory = ServiceUsageDaoFactoryMock::new()
+            .make_params(&service_usage_dao_factory_params_arc)
+            .make_result(ServiceUsageDaoMock::new()); // For Accountant
 
         let _ = Accountant::new(
             config,
@@ -1121,6 +2001,8 @@ mod tests {
                 pending_payable_dao_factory: Box::new(pending_payable_dao_factory),
                 receivable_dao_factory: Box::new(receivable_dao_factory),
                 banned_dao_factory: Box::new(banned_dao_factory),
+                ledger_dao_factory: Box::new(ledger_dao_factory),
+                service_usage_dao_factory: Box::new(service_usage_dao_factory),
             },
         );
 
@@ -1136,7 +2018,12 @@ mod tests {
             *receivable_dao_factory_params_arc.lock().unwrap(),
             vec![(), ()]
         );
-        assert_eq!(*banned_dao_factory_params_arc.lock().unwrap(), vec![()]);
+        assert_eq!(*banned_dao_factory_params_arc.lock().unwrap(), vec![(), ()]);
+        assert_eq!(*ledger_dao_factory_params_arc.lock().unwrap(), vec![()]);
+        assert_eq!(
+            *service_usage_dao_factory_params_arc.lock().unwrap(),
+            vec![()]
+        );
     }
 
     #[test]
@@ -1159,8 +2046,15 @@ mod tests {
                 .make_result(ReceivableDaoMock::new()) // For Accountant
                 .make_result(ReceivableDaoMock::new()), // For Scanner
         );
-        let banned_dao_factory =
-            Box::new(BannedDaoFactoryMock::new().make_result(BannedDaoMock::new()));
+        let banned_dao_factory = Box::new(
+            BannedDaoFactoryMock::new()
+                .make_result(BannedDaoMock::new()) // For Accountant
+                .make_result(BannedDaoMock::new()), // For Receivable Scanner
+        );
+        let ledger_dao_factory =
+            Box::new(LedgerDaoFactoryMock::new().make_result(LedgerDaoMock::new()));
+        let service_usage_dao_factory =
+            Box::new(ServiceUsageDaoFactoryMock::new().make_result(ServiceUsageDaoMock::new()));
 
         let result = Accountant::new(
             bootstrapper_config,
@@ -1169,6 +2063,8 @@ mod tests {
                 pending_payable_dao_factory,
                 receivable_dao_factory,
                 banned_dao_factory,
+                ledger_dao_factory,
+                service_usage_dao_factory,
             },
         );
 
@@ -1239,7 +2135,7 @@ mod tests {
         assert_eq!(
             blockchain_bridge_recording.get_record::<RetrieveTransactions>(0),
             &RetrieveTransactions {
-                recipient: make_wallet("earning_wallet"),
+                recipients: vec![make_wallet("earning_wallet")],
                 response_skeleton_opt: Some(ResponseSkeleton {
                     client_id: 1234,
                     context_id: 4321,
@@ -1268,6 +2164,9 @@ mod tests {
         let received_payments = ReceivedPayments {
             timestamp: SystemTime::now(),
             payments: vec![],
+            rechecked_payments_opt: None,
+            removed_payments: vec![],
+            catch_up_progress_opt: None,
             response_skeleton_opt: Some(ResponseSkeleton {
                 client_id: 1234,
                 context_id: 4321,
@@ -1378,6 +2277,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn settle_before_shutdown_with_nothing_to_pay_reports_debts_settled_at_once() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        subject.scanners.payable =
+            Box::new(ScannerMock::new().begin_scan_result(Err(BeginScanError::NothingToProcess)));
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .neighborhood(neighborhood)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr.try_send(SettleBeforeShutdown {}).unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::DebtsSettled
+        );
+        assert!(blockchain_bridge_recording_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn settle_before_shutdown_begins_payable_scan_and_waits_for_its_end() {
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let mut subject = AccountantBuilder::default().build();
+        let scan_message = RequestBalancesToPayPayables {
+            accounts: vec![make_payable_account(123)],
+            response_skeleton_opt: None,
+        };
+        subject.scanners.payable =
+            Box::new(ScannerMock::new().begin_scan_result(Ok(scan_message.clone())));
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder()
+            .neighborhood(neighborhood)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr.try_send(SettleBeforeShutdown {}).unwrap();
+
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(|actor: &mut Accountant| {
+                    assert!(actor.settling_before_shutdown)
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recording = blockchain_bridge_recording_arc.lock().unwrap();
+        assert_eq!(
+            blockchain_bridge_recording.get_record::<RequestBalancesToPayPayables>(0),
+            &scan_message
+        );
+        assert!(neighborhood_recording_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn sent_payables_while_settling_before_shutdown_reports_debts_settled() {
+        let config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .fingerprints_rowids_result(vec![(Some(1), make_tx_hash(123))]);
+        let payable_dao = PayableDaoMock::default().mark_pending_payables_rowids_result(Ok(()));
+        let mut subject = AccountantBuilder::default()
+            .pending_payable_daos(vec![ForPayableScanner(pending_payable_dao)])
+            .payable_daos(vec![ForPayableScanner(payable_dao)])
+            .bootstrapper_config(config)
+            .build();
+        subject.settling_before_shutdown = true;
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let sent_payable = SentPayables {
+            payment_procedure_result: Ok(vec![Correct(PendingPayable {
+                recipient_wallet: make_wallet("blah"),
+                hash: make_tx_hash(123),
+            })]),
+            response_skeleton_opt: None,
+        };
+
+        subject_addr.try_send(sent_payable).unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::DebtsSettled
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
+    #[test]
+    fn payable_scan_error_while_settling_before_shutdown_reports_debts_settled() {
+        let mut subject = AccountantBuilder::default().build();
+        subject.scanners.payable.mark_as_started(SystemTime::now());
+        subject.settling_before_shutdown = true;
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("test");
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ScanError {
+                scan_type: ScanType::Payables,
+                response_skeleton_opt: None,
+                msg: "Booga".to_string(),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::DebtsSettled
+        );
+    }
+
     #[test]
     fn received_balances_and_qualified_payables_considered_feasible_payments_thus_all_forwarded_to_blockchain_bridge(
     ) {
@@ -1689,10 +2718,148 @@ mod tests {
         assert_eq!(
             retrieve_transactions_msg,
             &RetrieveTransactions {
-                recipient: earning_wallet.clone(),
+                recipients: vec![earning_wallet.clone()],
+                response_skeleton_opt: None,
+            }
+        );
+    }
+
+    #[test]
+    fn accountant_keeps_scanning_the_retired_earning_wallet_after_rotation() {
+        init_test_logging();
+        let (blockchain_bridge, _, blockchain_bridge_recording_arc) = make_recorder();
+        let old_earning_wallet = make_wallet("old_earning_wallet");
+        let new_earning_wallet = make_wallet("new_earning_wallet");
+        let system =
+            System::new("accountant_keeps_scanning_the_retired_earning_wallet_after_rotation");
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(old_earning_wallet.clone()))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        subject.scanners.pending_payable = Box::new(NullScanner::new());
+        subject.scanners.payable = Box::new(NullScanner::new());
+        let accountant_addr = subject.start();
+        let accountant_subs = Accountant::make_subs_from(&accountant_addr);
+        let peer_actors = peer_actors_builder()
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        send_bind_message!(accountant_subs, peer_actors);
+
+        accountant_subs
+            .new_earning_wallet_sub
+            .try_send(NewEarningWalletMessage {
+                new_earning_wallet: new_earning_wallet.clone(),
+                retired: RetiredEarningWallet {
+                    wallet: old_earning_wallet.clone(),
+                    watch_until: SystemTime::now()
+                        .checked_add(Duration::from_secs(3600))
+                        .unwrap(),
+                },
+            })
+            .unwrap();
+        send_start_message!(accountant_subs);
+
+        System::current().stop();
+        system.run();
+        let blockchain_bridge_recorder = blockchain_bridge_recording_arc.lock().unwrap();
+        let retrieve_transactions_msg =
+            blockchain_bridge_recorder.get_record::<RetrieveTransactions>(0);
+        assert_eq!(
+            retrieve_transactions_msg,
+            &RetrieveTransactions {
+                recipients: vec![new_earning_wallet.clone(), old_earning_wallet.clone()],
                 response_skeleton_opt: None,
             }
         );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: Accountant: Earning wallet rotated from {} to {}",
+            old_earning_wallet, new_earning_wallet
+        ));
+    }
+
+    #[test]
+    fn payment_thresholds_change_reaches_the_scanners() {
+        init_test_logging();
+        let test_name = "payment_thresholds_change_reaches_the_scanners";
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .build();
+        subject.logger = Logger::new(test_name);
+        let payment_thresholds = PaymentThresholds {
+            debt_threshold_gwei: 1_111_111,
+            ..*DEFAULT_PAYMENT_THRESHOLDS
+        };
+
+        subject.handle_configuration_change(ConfigurationChange::PaymentThresholds(
+            payment_thresholds,
+        ));
+
+        let payable_scanner = subject
+            .scanners
+            .payable
+            .as_any()
+            .downcast_ref::<PayableScanner>()
+            .unwrap();
+        assert_eq!(
+            *payable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
+        );
+        assert_eq!(*subject.payment_thresholds.borrow(), payment_thresholds);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Payment thresholds changed to {}",
+            payment_thresholds
+        ));
+    }
+
+    #[test]
+    fn scan_intervals_change_applies_to_the_next_scheduled_scans() {
+        init_test_logging();
+        let test_name = "scan_intervals_change_applies_to_the_next_scheduled_scans";
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .build();
+        subject.logger = Logger::new(test_name);
+        let scan_intervals = ScanIntervals {
+            pending_payable_scan_interval: Duration::from_secs(111),
+            payable_scan_interval: Duration::from_secs(222),
+            receivable_scan_interval: Duration::from_secs(333),
+        };
+
+        subject.handle_configuration_change(ConfigurationChange::ScanIntervals(scan_intervals));
+
+        assert_eq!(
+            subject.scan_timings.pending_payable.interval,
+            Duration::from_secs(111)
+        );
+        assert_eq!(
+            subject.scan_timings.payable.interval,
+            Duration::from_secs(222)
+        );
+        assert_eq!(
+            subject.scan_timings.receivable.interval,
+            Duration::from_secs(333)
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Scan intervals changed to 111|222|333; they apply from the next scheduled scan"
+        ));
+    }
+
+    #[test]
+    fn configuration_changes_owned_by_other_actors_are_ignored() {
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .build();
+        let payment_thresholds_before = *subject.payment_thresholds.borrow();
+
+        subject.handle_configuration_change(ConfigurationChange::MinHops(Hops::SixHops));
+
+        assert_eq!(
+            *subject.payment_thresholds.borrow(),
+            payment_thresholds_before
+        );
     }
 
     #[test]
@@ -1701,12 +2868,16 @@ mod tests {
         let earning_wallet = make_wallet("earner3000");
         let expected_receivable_1 = BlockchainTransaction {
             block_number: 7,
+            transaction_hash: make_tx_hash(1),
             from: make_wallet("wallet0"),
+            to: make_wallet("earning_wallet"),
             wei_amount: 456,
         };
         let expected_receivable_2 = BlockchainTransaction {
             block_number: 13,
+            transaction_hash: make_tx_hash(2),
             from: make_wallet("wallet1"),
+            to: make_wallet("earning_wallet"),
             wei_amount: 10000,
         };
         let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
@@ -1725,6 +2896,9 @@ mod tests {
             .try_send(ReceivedPayments {
                 timestamp: now,
                 payments: vec![expected_receivable_1.clone(), expected_receivable_2.clone()],
+                rechecked_payments_opt: None,
+                removed_payments: vec![],
+                catch_up_progress_opt: None,
                 response_skeleton_opt: None,
             })
             .expect("unexpected actix error");
@@ -1738,6 +2912,87 @@ mod tests {
         )
     }
 
+    #[test]
+    fn accountant_reverses_credited_payments_missing_from_the_rechecked_blocks() {
+        let now = SystemTime::now();
+        let debtor = make_wallet("debtor");
+        let payment = |block_number: u64, base: u32| BlockchainTransaction {
+            block_number,
+            transaction_hash: make_tx_hash(base),
+            from: debtor.clone(),
+            to: make_wallet("earning_wallet"),
+            wei_amount: u128::from(base) * 1000,
+        };
+        let received_payments_in_range_params_arc = Arc::new(Mutex::new(vec![]));
+        let forget_received_payments_before_params_arc = Arc::new(Mutex::new(vec![]));
+        let reverse_received_payments_params_arc = Arc::new(Mutex::new(vec![]));
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let accountant_receivable_dao = ReceivableDaoMock::new()
+            .received_payments_in_range_parameters(&received_payments_in_range_params_arc)
+            .received_payments_in_range_result(vec![payment(90, 1), payment(95, 2)])
+            .forget_received_payments_before_parameters(&forget_received_payments_before_params_arc)
+            .forget_received_payments_before_result(Ok(()));
+        let scanner_receivable_dao = ReceivableDaoMock::new()
+            .reverse_received_payments_parameters(&reverse_received_payments_params_arc);
+        let ledger_dao = LedgerDaoMock::new()
+            .record_params(&record_params_arc)
+            .record_result(Ok(()));
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .receivable_daos(vec![
+                ForAccountantBody(accountant_receivable_dao),
+                ForReceivableScanner(scanner_receivable_dao),
+            ])
+            .ledger_dao(ledger_dao)
+            .build();
+        let system =
+            System::new("accountant_reverses_credited_payments_missing_from_the_rechecked_blocks");
+        let subject_addr = subject.start();
+
+        subject_addr
+            .try_send(ReceivedPayments {
+                timestamp: now,
+                payments: vec![],
+                // the payment in block 95 is still there, albeit moved to block 96 by the
+                // reorganization; the one in block 90 is gone
+                rechecked_payments_opt: Some(RecheckedPayments {
+                    from_block: 88,
+                    to_block: 99,
+                    recipients: vec![make_wallet("earning_wallet")],
+                    payments: vec![payment(96, 2)],
+                }),
+                removed_payments: vec![],
+                catch_up_progress_opt: None,
+                response_skeleton_opt: None,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        assert_eq!(
+            *received_payments_in_range_params_arc.lock().unwrap(),
+            vec![(88, 99, vec![make_wallet("earning_wallet")])]
+        );
+        assert_eq!(
+            *forget_received_payments_before_params_arc.lock().unwrap(),
+            vec![88]
+        );
+        assert_eq!(
+            *reverse_received_payments_params_arc.lock().unwrap(),
+            vec![vec![payment(90, 1)]]
+        );
+        let record_params = record_params_arc.lock().unwrap();
+        assert_eq!(
+            *record_params,
+            vec![vec![LedgerEntry {
+                transaction_hash_opt: Some(make_tx_hash(1)),
+                block_number_opt: Some(90),
+                outcome_opt: Some(PaymentOutcome::Reverted),
+                ..LedgerEntry::new(now, debtor.clone(), LedgerEntryKind::PaymentReceived, 1000)
+            }]]
+        );
+    }
+
     #[test]
     fn accountant_scans_after_startup() {
         init_test_logging();
@@ -1815,7 +3070,7 @@ mod tests {
             .begin_scan_params(&begin_scan_params_arc)
             .begin_scan_result(Err(BeginScanError::NothingToProcess))
             .begin_scan_result(Ok(RetrieveTransactions {
-                recipient: make_wallet("some_recipient"),
+                recipients: vec![make_wallet("some_recipient")],
                 response_skeleton_opt: None,
             }))
             .stop_the_system();
@@ -2878,11 +4133,18 @@ mod tests {
                 "we cannot help ourselves; this is baaad".to_string(),
             ),
         ));
-        let subject = AccountantBuilder::default()
+        let mut subject = AccountantBuilder::default()
             .receivable_daos(vec![ForAccountantBody(receivable_dao)])
             .build();
 
-        let _ = subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        let _ = subject.record_service_provided(
+            ServiceType::Routing,
+            i64::MAX as u64,
+            1,
+            SystemTime::now(),
+            2,
+            &wallet,
+        );
     }
 
     #[test]
@@ -2891,11 +4153,18 @@ mod tests {
         let wallet = make_wallet("booga");
         let receivable_dao = ReceivableDaoMock::new()
             .more_money_receivable_result(Err(ReceivableDaoError::SignConversion(1234)));
-        let subject = AccountantBuilder::default()
+        let mut subject = AccountantBuilder::default()
             .receivable_daos(vec![ForAccountantBody(receivable_dao)])
             .build();
 
-        subject.record_service_provided(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        subject.record_service_provided(
+            ServiceType::Routing,
+            i64::MAX as u64,
+            1,
+            SystemTime::now(),
+            2,
+            &wallet,
+        );
 
         TestLogHandler::new().exists_log_containing(&format!(
             "ERROR: Accountant: Overflow error recording service provided for {}: service rate {}, byte rate 1, payload size 2. Skipping",
@@ -2910,12 +4179,19 @@ mod tests {
         let wallet = make_wallet("booga");
         let payable_dao = PayableDaoMock::new()
             .more_money_payable_result(Err(PayableDaoError::SignConversion(1234)));
-        let subject = AccountantBuilder::default()
+        let mut subject = AccountantBuilder::default()
             .payable_daos(vec![ForAccountantBody(payable_dao)])
             .build();
         let service_rate = i64::MAX as u64;
 
-        subject.record_service_consumed(service_rate, 1, SystemTime::now(), 2, &wallet);
+        subject.record_service_consumed(
+            ServiceType::Exit,
+            service_rate,
+            1,
+            SystemTime::now(),
+            2,
+            &wallet,
+        );
 
         TestLogHandler::new().exists_log_containing(&format!(
             "ERROR: Accountant: Overflow error recording consumed services from {}: total charge {}, service rate {}, byte rate 1, payload size 2. Skipping",
@@ -2936,11 +4212,18 @@ mod tests {
         let payable_dao = PayableDaoMock::new().more_money_payable_result(Err(
             PayableDaoError::RusqliteError("we cannot help ourselves; this is baaad".to_string()),
         ));
-        let subject = AccountantBuilder::default()
+        let mut subject = AccountantBuilder::default()
             .payable_daos(vec![ForAccountantBody(payable_dao)])
             .build();
 
-        let _ = subject.record_service_consumed(i64::MAX as u64, 1, SystemTime::now(), 2, &wallet);
+        let _ = subject.record_service_consumed(
+            ServiceType::Exit,
+            i64::MAX as u64,
+            1,
+            SystemTime::now(),
+            2,
+            &wallet,
+        );
     }
 
     #[test]
@@ -3565,6 +4848,1785 @@ mod tests {
         )
     }
 
+    #[test]
+    fn ledger_history_request_produces_ledger_history_response() {
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("creditor");
+        let hash = make_tx_hash(789);
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![LedgerEntry {
+                transaction_hash_opt: Some(hash),
+                nonce_opt: Some(4),
+                gas_price_gwei_opt: Some(80),
+                block_number_opt: None,
+                outcome_opt: Some(PaymentOutcome::Submitted),
+                ..LedgerEntry::new(
+                    from_time_t(1_000_000),
+                    wallet.clone(),
+                    LedgerEntryKind::PaymentSent,
+                    4_567_000_000_123,
+                )
+            }]);
+        let system = System::new("ledger_history_request_produces_ledger_history_response");
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .ledger_dao(ledger_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let before = SystemTime::now();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiLedgerHistoryRequest {
+                    wallet_opt: Some(wallet.to_string()),
+                    min_age_s_opt: Some(100),
+                    max_age_s_opt: Some(5000),
+                    offset_opt: Some(20),
+                    limit_opt: Some(10),
+                }
+                .tmb(2222),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let after = SystemTime::now();
+        let history_params = history_params_arc.lock().unwrap();
+        let (wallet_opt, from, to, offset, limit) = &history_params[0];
+        assert_eq!(wallet_opt, &Some(wallet.clone()));
+        assert_eq!((*offset, *limit), (20, 11));
+        assert!(
+            *from >= before - Duration::from_secs(5000)
+                && *from <= after - Duration::from_secs(5000)
+        );
+        assert!(
+            *to >= before - Duration::from_secs(100) && *to <= after - Duration::from_secs(100)
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        let (body, context_id) = UiLedgerHistoryResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 2222);
+        assert_eq!(
+            body,
+            UiLedgerHistoryResponse {
+                entries: vec![UiLedgerEntry {
+                    timestamp: 1_000_000,
+                    wallet: wallet.to_string(),
+                    kind: "payment_sent".to_string(),
+                    amount_wei: "4567000000123".to_string(),
+                    transaction_hash_opt: Some(format!("{:?}", hash)),
+                    nonce_opt: Some(4),
+                    gas_price_gwei_opt: Some(80),
+                    block_number_opt: None,
+                    outcome_opt: Some("submitted".to_string()),
+                }],
+                next_offset_opt: None
+            }
+        )
+    }
+
+    #[test]
+    fn ledger_history_says_where_the_next_page_starts_if_there_is_one() {
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("debtor");
+        let entry = |timestamp: i64| {
+            LedgerEntry::new(
+                from_time_t(timestamp),
+                wallet.clone(),
+                LedgerEntryKind::PaymentReceived,
+                123,
+            )
+        };
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![entry(1_000), entry(2_000), entry(3_000)]);
+        let subject = AccountantBuilder::default().ledger_dao(ledger_dao).build();
+
+        let result = subject.compute_ledger_history(
+            &UiLedgerHistoryRequest {
+                wallet_opt: None,
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                offset_opt: Some(4),
+                limit_opt: Some(2),
+            },
+            4321,
+        );
+
+        let (body, _) = UiLedgerHistoryResponse::fmb(result).unwrap();
+        assert_eq!(
+            body.entries
+                .iter()
+                .map(|entry| entry.timestamp)
+                .collect::<Vec<u64>>(),
+            vec![1_000, 2_000]
+        );
+        assert_eq!(body.next_offset_opt, Some(6));
+        let history_params = history_params_arc.lock().unwrap();
+        let (_, _, _, offset, limit) = &history_params[0];
+        assert_eq!((*offset, *limit), (4, 3));
+    }
+
+    #[test]
+    fn ledger_history_without_limits_covers_all_time() {
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![]);
+        let subject = AccountantBuilder::default().ledger_dao(ledger_dao).build();
+        let before = SystemTime::now();
+
+        let result = subject.compute_ledger_history(
+            &UiLedgerHistoryRequest {
+                wallet_opt: None,
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                offset_opt: None,
+                limit_opt: None,
+            },
+            4321,
+        );
+
+        let after = SystemTime::now();
+        assert_eq!(
+            result,
+            UiLedgerHistoryResponse {
+                entries: vec![],
+                next_offset_opt: None
+            }
+            .tmb(4321)
+        );
+        let history_params = history_params_arc.lock().unwrap();
+        let (wallet_opt, from, to, offset, limit) = &history_params[0];
+        assert_eq!(wallet_opt, &None);
+        assert_eq!((*offset, *limit), (0, LEDGER_PAGE_SIZE_LIMIT + 1));
+        assert_eq!(*from, UNIX_EPOCH);
+        assert!(*to >= before && *to <= after);
+    }
+
+    #[test]
+    fn ledger_history_request_with_bad_parameters_is_refused() {
+        let subject = AccountantBuilder::default().build();
+
+        let bad_wallet = subject.compute_ledger_history(
+            &UiLedgerHistoryRequest {
+                wallet_opt: Some("booga".to_string()),
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                offset_opt: None,
+                limit_opt: None,
+            },
+            1111,
+        );
+        let bad_range = subject.compute_ledger_history(
+            &UiLedgerHistoryRequest {
+                wallet_opt: None,
+                min_age_s_opt: Some(500),
+                max_age_s_opt: Some(400),
+                offset_opt: None,
+                limit_opt: None,
+            },
+            2222,
+        );
+        let bad_page_size = subject.compute_ledger_history(
+            &UiLedgerHistoryRequest {
+                wallet_opt: None,
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                offset_opt: None,
+                limit_opt: Some(LEDGER_PAGE_SIZE_LIMIT + 1),
+            },
+            3333,
+        );
+
+        assert_eq!(bad_wallet.opcode, "ledgerHistory");
+        assert_eq!(bad_wallet.path, MessagePath::Conversation(1111));
+        let (code, message) = bad_wallet.payload.unwrap_err();
+        assert_eq!(code, LEDGER_HISTORY_ERROR);
+        assert!(
+            message.starts_with("Wallet address is invalid: "),
+            "{}",
+            message
+        );
+        assert_eq!(
+            bad_range,
+            MessageBody {
+                opcode: "ledgerHistory".to_string(),
+                path: MessagePath::Conversation(2222),
+                payload: Err((
+                    LEDGER_HISTORY_ERROR,
+                    "Age range must be low to high, not 500-400".to_string()
+                ))
+            }
+        );
+        assert_eq!(
+            bad_page_size.payload,
+            Err((
+                LEDGER_HISTORY_ERROR,
+                "Page size must be between 1 and 1000, not 1001".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn service_usage_request_produces_service_usage_response() {
+        let usage_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("debtor");
+        let bucket = |bucket_start: i64, service, direction, charge_wei| ServiceUsageBucket {
+            bucket_start: from_time_t(bucket_start),
+            wallet: wallet.clone(),
+            service,
+            direction,
+            service_count: 2,
+            bytes: 1_000,
+            charge_wei,
+        };
+        let service_usage_dao = ServiceUsageDaoMock::new()
+            .usage_params(&usage_params_arc)
+            .usage_result(vec![
+                bucket(
+                    3_600,
+                    ServiceType::Routing,
+                    ServiceDirection::Provided,
+                    u64::MAX as u128 + 1,
+                ),
+                bucket(3_600, ServiceType::Exit, ServiceDirection::Provided, 500),
+                bucket(
+                    7_200,
+                    ServiceType::Routing,
+                    ServiceDirection::Provided,
+                    u64::MAX as u128,
+                ),
+                bucket(90_000, ServiceType::Routing, ServiceDirection::Provided, 7),
+            ]);
+        let system = System::new("service_usage_request_produces_service_usage_response");
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .service_usage_dao(service_usage_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let before = SystemTime::now();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiServiceUsageRequest {
+                    wallet_opt: Some(wallet.to_string()),
+                    min_age_s_opt: Some(100),
+                    max_age_s_opt: Some(5000),
+                    bucket_size_s_opt: Some(86_400),
+                }
+                .tmb(2222),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let after = SystemTime::now();
+        let usage_params = usage_params_arc.lock().unwrap();
+        let (wallet_opt, from, to) = &usage_params[0];
+        assert_eq!(wallet_opt, &Some(wallet.clone()));
+        assert!(
+            *from >= before - Duration::from_secs(5000)
+                && *from <= after - Duration::from_secs(5000)
+        );
+        assert!(
+            *to >= before - Duration::from_secs(100) && *to <= after - Duration::from_secs(100)
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        let (body, context_id) = UiServiceUsageResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 2222);
+        let ui_usage =
+            |bucket_start, service: &str, service_count, bytes, charge_wei: &str| UiServiceUsage {
+                bucket_start,
+                wallet: wallet.to_string(),
+                service: service.to_string(),
+                direction: "provided".to_string(),
+                service_count,
+                bytes,
+                charge_wei: charge_wei.to_string(),
+            };
+        assert_eq!(
+            body,
+            UiServiceUsageResponse {
+                usage: vec![
+                    ui_usage(0, "routing", 4, 2_000, "36893488147419103231"),
+                    ui_usage(0, "exit", 2, 1_000, "500"),
+                    ui_usage(86_400, "routing", 2, 1_000, "7"),
+                ]
+            }
+        )
+    }
+
+    #[test]
+    fn service_usage_without_limits_covers_all_time_in_hourly_buckets() {
+        let usage_params_arc = Arc::new(Mutex::new(vec![]));
+        let service_usage_dao = ServiceUsageDaoMock::new()
+            .usage_params(&usage_params_arc)
+            .usage_result(vec![ServiceUsageBucket {
+                bucket_start: from_time_t(7_200),
+                wallet: make_wallet("creditor"),
+                service: ServiceType::Exit,
+                direction: ServiceDirection::Consumed,
+                service_count: 1,
+                bytes: 100,
+                charge_wei: 1_234,
+            }]);
+        let subject = AccountantBuilder::default()
+            .service_usage_dao(service_usage_dao)
+            .build();
+        let before = SystemTime::now();
+
+        let result = subject.compute_service_usage(
+            &UiServiceUsageRequest {
+                wallet_opt: None,
+                min_age_s_opt: None,
+                max_age_s_opt: None,
+                bucket_size_s_opt: None,
+            },
+            4321,
+        );
+
+        let after = SystemTime::now();
+        assert_eq!(
+            result,
+            UiServiceUsageResponse {
+                usage: vec![UiServiceUsage {
+                    bucket_start: 7_200,
+                    wallet: make_wallet("creditor").to_string(),
+                    service: "exit".to_string(),
+                    direction: "consumed".to_string(),
+                    service_count: 1,
+                    bytes: 100,
+                    charge_wei: "1234".to_string(),
+                }]
+            }
+            .tmb(4321)
+        );
+        let usage_params = usage_params_arc.lock().unwrap();
+        let (wallet_opt, from, to) = &usage_params[0];
+        assert_eq!(wallet_opt, &None);
+        assert_eq!(*from, UNIX_EPOCH);
+        assert!(*to >= before && *to <= after);
+    }
+
+    #[test]
+    fn service_usage_request_with_bad_parameters_is_refused() {
+        let subject = AccountantBuilder::default().build();
+        let request = UiServiceUsageRequest {
+            wallet_opt: None,
+            min_age_s_opt: None,
+            max_age_s_opt: None,
+            bucket_size_s_opt: None,
+        };
+
+        let bad_wallet = subject.compute_service_usage(
+            &UiServiceUsageRequest {
+                wallet_opt: Some("booga".to_string()),
+                ..request.clone()
+            },
+            1111,
+        );
+        let bad_range = subject.compute_service_usage(
+            &UiServiceUsageRequest {
+                min_age_s_opt: Some(500),
+                max_age_s_opt: Some(400),
+                ..request.clone()
+            },
+            2222,
+        );
+        let zero_bucket = subject.compute_service_usage(
+            &UiServiceUsageRequest {
+                bucket_size_s_opt: Some(0),
+                ..request.clone()
+            },
+            3333,
+        );
+        let odd_bucket = subject.compute_service_usage(
+            &UiServiceUsageRequest {
+                bucket_size_s_opt: Some(5_400),
+                ..request
+            },
+            4444,
+        );
+
+        assert_eq!(bad_wallet.opcode, "serviceUsage");
+        assert_eq!(bad_wallet.path, MessagePath::Conversation(1111));
+        let (code, message) = bad_wallet.payload.unwrap_err();
+        assert_eq!(code, SERVICE_USAGE_ERROR);
+        assert!(
+            message.starts_with("Wallet address is invalid: "),
+            "{}",
+            message
+        );
+        let error = |context_id, message: &str| MessageBody {
+            opcode: "serviceUsage".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((SERVICE_USAGE_ERROR, message.to_string())),
+        };
+        assert_eq!(
+            bad_range,
+            error(2222, "Age range must be low to high, not 500-400")
+        );
+        assert_eq!(
+            zero_bucket,
+            error(
+                3333,
+                "Bucket size must be a positive multiple of 3600 seconds, not 0"
+            )
+        );
+        assert_eq!(
+            odd_bucket,
+            error(
+                4444,
+                "Bucket size must be a positive multiple of 3600 seconds, not 5400"
+            )
+        );
+    }
+
+    #[test]
+    fn ban_list_request_produces_ban_list_response() {
+        let system = System::new("ban_list_request_produces_ban_list_response");
+        let banned_dao = BannedDaoMock::new().ban_list_result(vec![
+            BannedWallet {
+                wallet: make_wallet("delinquent"),
+                reason: BanReason::Delinquency,
+                note_opt: None,
+                timestamp_opt: None,
+            },
+            BannedWallet {
+                wallet: make_wallet("troublemaker"),
+                reason: BanReason::Manual,
+                note_opt: Some("Spams us".to_string()),
+                timestamp_opt: Some(from_time_t(1_650_000_000)),
+            },
+        ]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .banned_dao(banned_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiBanListRequest {}.tmb(2222),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        let (body, context_id) = UiBanListResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 2222);
+        assert_eq!(
+            body,
+            UiBanListResponse {
+                banned: vec![
+                    UiBannedWallet {
+                        wallet: make_wallet("delinquent").to_string(),
+                        reason: "delinquency".to_string(),
+                        note_opt: None,
+                        timestamp_opt: None,
+                    },
+                    UiBannedWallet {
+                        wallet: make_wallet("troublemaker").to_string(),
+                        reason: "manual".to_string(),
+                        note_opt: Some("Spams us".to_string()),
+                        timestamp_opt: Some(1_650_000_000),
+                    },
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn ban_request_bans_wallet_manually() {
+        init_test_logging();
+        let ban_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new("ban_request_bans_wallet_manually");
+        let banned_dao = BannedDaoMock::new()
+            .ban_parameters(&ban_params_arc)
+            .ban_result(true);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .banned_dao(banned_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let wallet = make_wallet("troublemaker");
+        let before = SystemTime::now();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiBanRequest {
+                    wallet: wallet.to_string(),
+                    note_opt: Some("Spams us".to_string()),
+                }
+                .tmb(3333),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let after = SystemTime::now();
+        let ban_params = ban_params_arc.lock().unwrap();
+        let (banned_wallet, reason, note_opt, timestamp) = &ban_params[0];
+        assert_eq!(banned_wallet, &wallet);
+        assert_eq!(reason, &BanReason::Manual);
+        assert_eq!(note_opt, &Some("Spams us".to_string()));
+        assert!(*timestamp >= before && *timestamp <= after);
+        assert_eq!(ban_params.len(), 1);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        assert_eq!(
+            response.body,
+            UiBanResponse { newly_banned: true }.tmb(3333)
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: Accountant: Wallet {} banned by request from UI",
+            wallet
+        ));
+    }
+
+    #[test]
+    fn ban_request_for_already_banned_wallet_reports_nothing_new() {
+        let banned_dao = BannedDaoMock::new().ban_result(false);
+        let subject = AccountantBuilder::default().banned_dao(banned_dao).build();
+
+        let result = subject.compute_ban(
+            &UiBanRequest {
+                wallet: make_wallet("troublemaker").to_string(),
+                note_opt: None,
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result,
+            UiBanResponse {
+                newly_banned: false
+            }
+            .tmb(4321)
+        );
+    }
+
+    #[test]
+    fn ban_request_with_bad_wallet_is_refused() {
+        let ban_params_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new().ban_parameters(&ban_params_arc);
+        let subject = AccountantBuilder::default().banned_dao(banned_dao).build();
+
+        let result = subject.compute_ban(
+            &UiBanRequest {
+                wallet: "booga".to_string(),
+                note_opt: None,
+            },
+            1111,
+        );
+
+        assert_eq!(result.opcode, "ban");
+        assert_eq!(result.path, MessagePath::Conversation(1111));
+        let (code, message) = result.payload.unwrap_err();
+        assert_eq!(code, BAN_LIST_ERROR);
+        assert!(
+            message.starts_with("Wallet address is invalid: "),
+            "{}",
+            message
+        );
+        assert!(ban_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn ban_request_for_our_own_wallets_is_refused() {
+        let ban_params_arc = Arc::new(Mutex::new(vec![]));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let earning_wallet = make_wallet("earning");
+        let banned_dao = BannedDaoMock::new().ban_parameters(&ban_params_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_wallets(
+                consuming_wallet.clone(),
+                earning_wallet.clone(),
+            ))
+            .banned_dao(banned_dao)
+            .build();
+
+        let consuming_result = subject.compute_ban(
+            &UiBanRequest {
+                wallet: consuming_wallet.to_string(),
+                note_opt: None,
+            },
+            1111,
+        );
+        let earning_result = subject.compute_ban(
+            &UiBanRequest {
+                wallet: earning_wallet.to_string(),
+                note_opt: None,
+            },
+            2222,
+        );
+
+        let error = |context_id, wallet: &Wallet| MessageBody {
+            opcode: "ban".to_string(),
+            path: MessagePath::Conversation(context_id),
+            payload: Err((
+                BAN_LIST_ERROR,
+                format!("Wallet {} belongs to this Node and can't be banned", wallet),
+            )),
+        };
+        assert_eq!(consuming_result, error(1111, &consuming_wallet));
+        assert_eq!(earning_result, error(2222, &earning_wallet));
+        assert!(ban_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unban_request_unbans_wallet() {
+        init_test_logging();
+        let unban_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new("unban_request_unbans_wallet");
+        let banned_dao = BannedDaoMock::new()
+            .unban_parameters(&unban_params_arc)
+            .unban_result(true);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .banned_dao(banned_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let wallet = make_wallet("reformed");
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiUnbanRequest {
+                    wallet: wallet.to_string(),
+                }
+                .tmb(4444),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let unban_params = unban_params_arc.lock().unwrap();
+        assert_eq!(*unban_params, vec![wallet.clone()]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        assert_eq!(
+            response.body,
+            UiUnbanResponse { was_banned: true }.tmb(4444)
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: Accountant: Wallet {} unbanned by request from UI",
+            wallet
+        ));
+    }
+
+    #[test]
+    fn unban_request_for_wallet_that_is_not_banned_reports_so() {
+        let banned_dao = BannedDaoMock::new().unban_result(false);
+        let subject = AccountantBuilder::default().banned_dao(banned_dao).build();
+
+        let result = subject.compute_unban(
+            &UiUnbanRequest {
+                wallet: make_wallet("innocent").to_string(),
+            },
+            4321,
+        );
+
+        assert_eq!(result, UiUnbanResponse { was_banned: false }.tmb(4321));
+    }
+
+    #[test]
+    fn unban_request_with_bad_wallet_is_refused() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = subject.compute_unban(
+            &UiUnbanRequest {
+                wallet: "booga".to_string(),
+            },
+            1111,
+        );
+
+        assert_eq!(result.opcode, "unban");
+        assert_eq!(result.path, MessagePath::Conversation(1111));
+        let (code, message) = result.payload.unwrap_err();
+        assert_eq!(code, BAN_LIST_ERROR);
+        assert!(
+            message.starts_with("Wallet address is invalid: "),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn receivable_scan_broadcasts_changes_to_ban_list() {
+        let newly_delinquent = make_receivable_account(1234, true);
+        let newly_paid_up = make_receivable_account(2345, false);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![newly_delinquent.clone()])
+            .paid_delinquencies_result(vec![newly_paid_up.clone()]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earning_wallet")))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, _) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("receivable_scan_broadcasts_changes_to_ban_list");
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiScanRequest {
+                    scan_type: ScanType::Receivables,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: AllClients,
+                body: UiBanListChangedBroadcast {
+                    banned: vec![newly_delinquent.wallet.to_string()],
+                    unbanned: vec![newly_paid_up.wallet.to_string()],
+                    warned: vec![],
+                }
+                .tmb(0),
+            }
+        );
+    }
+
+    #[test]
+    fn receivable_scan_broadcasts_warned_debtors_and_sends_them_notices_if_so_configured() {
+        let newly_delinquent = make_receivable_account(1234, true);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![newly_delinquent.clone()])
+            .paid_delinquencies_result(vec![]);
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        config.delinquency_policy = DelinquencyPolicy {
+            grace_period_sec: 3600,
+            notify_debtors: true,
+        };
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, _) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new(
+            "receivable_scan_broadcasts_warned_debtors_and_sends_them_notices_if_so_configured",
+        );
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .neighborhood(neighborhood)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let before = SystemTime::now();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiScanRequest {
+                    scan_type: ScanType::Receivables,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let after = SystemTime::now();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 1);
+        let notice = neighborhood_recording.get_record::<NoticeToDebtor>(0);
+        assert_eq!(notice.wallet, newly_delinquent.wallet);
+        assert_eq!(notice.balance_wei, newly_delinquent.balance_wei);
+        assert!(notice.ban_deadline >= before + Duration::from_secs(3600));
+        assert!(notice.ban_deadline <= after + Duration::from_secs(3600));
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: AllClients,
+                body: UiBanListChangedBroadcast {
+                    banned: vec![],
+                    unbanned: vec![],
+                    warned: vec![UiWarnedDebtor {
+                        wallet: newly_delinquent.wallet.to_string(),
+                        balance_wei: newly_delinquent.balance_wei.to_string(),
+                        ban_timestamp: to_time_t(notice.ban_deadline) as u64,
+                    }],
+                }
+                .tmb(0),
+            }
+        );
+    }
+
+    #[test]
+    fn receivable_scan_sends_no_notices_to_warned_debtors_unless_so_configured() {
+        let newly_delinquent = make_receivable_account(1234, true);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![newly_delinquent.clone()])
+            .paid_delinquencies_result(vec![]);
+        let mut config = bc_from_earning_wallet(make_wallet("earning_wallet"));
+        config.delinquency_policy = DelinquencyPolicy {
+            grace_period_sec: 3600,
+            notify_debtors: false,
+        };
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(config)
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, _) = make_recorder();
+        let subject_addr = subject.start();
+        let system =
+            System::new("receivable_scan_sends_no_notices_to_warned_debtors_unless_so_configured");
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .neighborhood(neighborhood)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiScanRequest {
+                    scan_type: ScanType::Receivables,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(neighborhood_recording.len(), 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let (broadcast, _) = UiBanListChangedBroadcast::fmb(
+            ui_gateway_recording
+                .get_record::<NodeToUiMessage>(0)
+                .body
+                .clone(),
+        )
+        .unwrap();
+        assert_eq!(broadcast.warned.len(), 1);
+        assert_eq!(
+            broadcast.warned[0].wallet,
+            newly_delinquent.wallet.to_string()
+        );
+    }
+
+    #[test]
+    fn forgive_debt_request_restarts_aging_withdraws_warning_and_lifts_delinquency_ban() {
+        init_test_logging();
+        let restart_aging_params_arc = Arc::new(Mutex::new(vec![]));
+        let unwarn_params_arc = Arc::new(Mutex::new(vec![]));
+        let unban_params_arc = Arc::new(Mutex::new(vec![]));
+        let system = System::new(
+            "forgive_debt_request_restarts_aging_withdraws_warning_and_lifts_delinquency_ban",
+        );
+        let wallet = make_wallet("debtor");
+        let receivable_dao = ReceivableDaoMock::new()
+            .restart_aging_parameters(&restart_aging_params_arc)
+            .restart_aging_result(Ok(Some(ReceivableAccount {
+                wallet: wallet.clone(),
+                balance_wei: 123_456_789,
+                last_received_timestamp: from_time_t(1_000),
+            })));
+        let banned_dao = BannedDaoMock::new()
+            .unwarn_parameters(&unwarn_params_arc)
+            .ban_list_result(vec![BannedWallet {
+                wallet: wallet.clone(),
+                reason: BanReason::Delinquency,
+                note_opt: None,
+                timestamp_opt: Some(from_time_t(2_000)),
+            }])
+            .unban_parameters(&unban_params_arc);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .banned_dao(banned_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let before = SystemTime::now();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiForgiveDebtRequest {
+                    wallet: wallet.to_string(),
+                    write_off: false,
+                }
+                .tmb(4444),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let after = SystemTime::now();
+        let restart_aging_params = restart_aging_params_arc.lock().unwrap();
+        assert_eq!(restart_aging_params.len(), 1);
+        assert_eq!(restart_aging_params[0].0, wallet);
+        assert!(before <= restart_aging_params[0].1 && restart_aging_params[0].1 <= after);
+        assert_eq!(*unwarn_params_arc.lock().unwrap(), vec![wallet.clone()]);
+        assert_eq!(*unban_params_arc.lock().unwrap(), vec![wallet.clone()]);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        assert_eq!(
+            response.body,
+            UiForgiveDebtResponse {
+                balance_wei: "123456789".to_string(),
+                unbanned: true,
+            }
+            .tmb(4444)
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: Accountant: Debt of 123456789 wei owed by wallet {} forgiven by request from UI; wallet unbanned",
+            wallet
+        ));
+    }
+
+    #[test]
+    fn forgive_debt_request_writes_off_debt_and_records_it_in_the_ledger() {
+        let write_off_params_arc = Arc::new(Mutex::new(vec![]));
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let unban_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet = make_wallet("debtor");
+        let receivable_dao = ReceivableDaoMock::new()
+            .write_off_parameters(&write_off_params_arc)
+            .write_off_result(Ok(Some(ReceivableAccount {
+                wallet: wallet.clone(),
+                balance_wei: 123_456_789,
+                last_received_timestamp: from_time_t(1_000),
+            })));
+        let ledger_dao = LedgerDaoMock::new().record_params(&record_params_arc);
+        let banned_dao = BannedDaoMock::new()
+            .ban_list_result(vec![BannedWallet {
+                wallet: wallet.clone(),
+                reason: BanReason::Manual,
+                note_opt: None,
+                timestamp_opt: None,
+            }])
+            .unban_parameters(&unban_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .ledger_dao(ledger_dao)
+            .banned_dao(banned_dao)
+            .build();
+
+        let result = subject.compute_forgive_debt(
+            &UiForgiveDebtRequest {
+                wallet: wallet.to_string(),
+                write_off: true,
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result,
+            UiForgiveDebtResponse {
+                balance_wei: "123456789".to_string(),
+                unbanned: false,
+            }
+            .tmb(4321)
+        );
+        assert_eq!(*write_off_params_arc.lock().unwrap(), vec![wallet.clone()]);
+        let record_params = record_params_arc.lock().unwrap();
+        assert_eq!(record_params.len(), 1);
+        assert_eq!(record_params[0].len(), 1);
+        let entry = &record_params[0][0];
+        assert_eq!(
+            *entry,
+            LedgerEntry::new(
+                entry.timestamp,
+                wallet,
+                LedgerEntryKind::DebtWrittenOff,
+                123_456_789
+            )
+        );
+        assert!(unban_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn forgive_debt_request_for_wallet_that_owes_nothing_is_refused() {
+        let receivable_dao = ReceivableDaoMock::new().restart_aging_result(Ok(None));
+        let unwarn_params_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new().unwarn_parameters(&unwarn_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .banned_dao(banned_dao)
+            .build();
+        let wallet = make_wallet("innocent");
+
+        let result = subject.compute_forgive_debt(
+            &UiForgiveDebtRequest {
+                wallet: wallet.to_string(),
+                write_off: false,
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "forgiveDebt".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    DEBT_FORGIVENESS_ERROR,
+                    format!("Wallet {} owes us nothing", wallet)
+                )),
+            }
+        );
+        assert!(unwarn_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn forgive_debt_request_reports_database_error() {
+        let receivable_dao = ReceivableDaoMock::new()
+            .write_off_result(Err(ReceivableDaoError::RusqliteError("Booga".to_string())));
+        let mut subject = AccountantBuilder::default()
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .build();
+
+        let result = subject.compute_forgive_debt(
+            &UiForgiveDebtRequest {
+                wallet: make_wallet("debtor").to_string(),
+                write_off: true,
+            },
+            4321,
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "forgiveDebt".to_string(),
+                path: MessagePath::Conversation(4321),
+                payload: Err((
+                    DEBT_FORGIVENESS_ERROR,
+                    "Couldn't forgive the debt: RusqliteError(\"Booga\")".to_string()
+                )),
+            }
+        );
+    }
+
+    #[test]
+    fn forgive_debt_request_with_bad_wallet_is_refused() {
+        let mut subject = AccountantBuilder::default().build();
+
+        let result = subject.compute_forgive_debt(
+            &UiForgiveDebtRequest {
+                wallet: "booga".to_string(),
+                write_off: false,
+            },
+            1111,
+        );
+
+        assert_eq!(result.opcode, "forgiveDebt");
+        assert_eq!(result.path, MessagePath::Conversation(1111));
+        let (code, message) = result.payload.unwrap_err();
+        assert_eq!(code, DEBT_FORGIVENESS_ERROR);
+        assert!(
+            message.starts_with("Wallet address is invalid: "),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn receivable_scan_broadcasts_nothing_if_ban_list_is_unchanged() {
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earning_wallet")))
+            .receivable_daos(vec![ForReceivableScanner(receivable_dao)])
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (blockchain_bridge, _, _) = make_recorder();
+        let subject_addr = subject.start();
+        let system = System::new("receivable_scan_broadcasts_nothing_if_ban_list_is_unchanged");
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .blockchain_bridge(blockchain_bridge)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiScanRequest {
+                    scan_type: ScanType::Receivables,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 0);
+    }
+
+    #[test]
+    fn financials_export_request_produces_financials_export_response() {
+        let payable_accounts_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_accounts_params_arc = Arc::new(Mutex::new(vec![]));
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let creditor = make_wallet("creditor");
+        let debtor = make_wallet("debtor");
+        let hash = make_tx_hash(456);
+        let payable_dao = PayableDaoMock::new()
+            .accounts_active_between_params(&payable_accounts_params_arc)
+            .accounts_active_between_result(vec![PayableAccount {
+                wallet: creditor.clone(),
+                balance_wei: 123_456_789_123_456_789_123,
+                last_paid_timestamp: from_time_t(1_650_000_000),
+                pending_payable_opt: Some(PendingPayableId { rowid: 5, hash }),
+            }]);
+        let receivable_dao = ReceivableDaoMock::new()
+            .accounts_active_between_params(&receivable_accounts_params_arc)
+            .accounts_active_between_result(vec![ReceivableAccount {
+                wallet: debtor.clone(),
+                balance_wei: -987_654_321,
+                last_received_timestamp: from_time_t(1_640_000_000),
+            }]);
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![LedgerEntry {
+                transaction_hash_opt: Some(hash),
+                nonce_opt: Some(7),
+                gas_price_gwei_opt: Some(60),
+                outcome_opt: Some(PaymentOutcome::Submitted),
+                ..LedgerEntry::new(
+                    from_time_t(1_650_000_000),
+                    creditor.clone(),
+                    LedgerEntryKind::PaymentSent,
+                    1_000_000_000_000_000_001,
+                )
+            }]);
+        let system = System::new("financials_export_request_produces_financials_export_response");
+        let subject = AccountantBuilder::default()
+            .bootstrapper_config(make_bc_with_defaults())
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .ledger_dao(ledger_dao)
+            .build();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiFinancialsExportRequest {
+                    from_timestamp_opt: Some(1_600_000_000),
+                    to_timestamp_opt: Some(1_700_000_000),
+                    offset_opt: None,
+                    limit_opt: None,
+                }
+                .tmb(3333),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let window = (from_time_t(1_600_000_000), from_time_t(1_700_000_000));
+        let payable_accounts_params = payable_accounts_params_arc.lock().unwrap();
+        assert_eq!(*payable_accounts_params, vec![window]);
+        let receivable_accounts_params = receivable_accounts_params_arc.lock().unwrap();
+        assert_eq!(*receivable_accounts_params, vec![window]);
+        let history_params = history_params_arc.lock().unwrap();
+        assert_eq!(
+            *history_params,
+            vec![(
+                None,
+                from_time_t(1_600_000_000),
+                from_time_t(1_700_000_000),
+                0,
+                LEDGER_PAGE_SIZE_LIMIT + 1
+            )]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let response = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(response.target, ClientId(1234));
+        let (body, context_id) = UiFinancialsExportResponse::fmb(response.body.clone()).unwrap();
+        assert_eq!(context_id, 3333);
+        assert_eq!(
+            body,
+            UiFinancialsExportResponse {
+                payables: vec![UiExportedAccount {
+                    wallet: creditor.to_string(),
+                    balance_wei: "123456789123456789123".to_string(),
+                    last_activity_timestamp: 1_650_000_000,
+                    pending_payable_hash_opt: Some(format!("{:?}", hash)),
+                }],
+                receivables: vec![UiExportedAccount {
+                    wallet: debtor.to_string(),
+                    balance_wei: "-987654321".to_string(),
+                    last_activity_timestamp: 1_640_000_000,
+                    pending_payable_hash_opt: None,
+                }],
+                transactions: vec![UiExportedTransaction {
+                    timestamp: 1_650_000_000,
+                    wallet: creditor.to_string(),
+                    kind: "payment_sent".to_string(),
+                    amount_wei: "1000000000000000001".to_string(),
+                    transaction_hash_opt: Some(format!("{:?}", hash)),
+                    nonce_opt: Some(7),
+                    gas_price_gwei_opt: Some(60),
+                    block_number_opt: None,
+                    outcome_opt: Some("submitted".to_string()),
+                }],
+                next_offset_opt: None,
+            }
+        )
+    }
+
+    #[test]
+    fn financials_export_without_time_window_reaches_from_the_beginning_until_now() {
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new().accounts_active_between_result(vec![]);
+        let receivable_dao = ReceivableDaoMock::new().accounts_active_between_result(vec![]);
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![]);
+        let subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .ledger_dao(ledger_dao)
+            .build();
+        let before = from_time_t(to_time_t(SystemTime::now()));
+
+        let result = subject.compute_financials_export(
+            &UiFinancialsExportRequest {
+                from_timestamp_opt: None,
+                to_timestamp_opt: None,
+                offset_opt: None,
+                limit_opt: None,
+            },
+            4444,
+        );
+
+        let after = SystemTime::now();
+        let (body, _) = UiFinancialsExportResponse::fmb(result).unwrap();
+        assert_eq!(
+            body,
+            UiFinancialsExportResponse {
+                payables: vec![],
+                receivables: vec![],
+                transactions: vec![],
+                next_offset_opt: None,
+            }
+        );
+        let history_params = history_params_arc.lock().unwrap();
+        let (_, from, to, _, _) = &history_params[0];
+        assert_eq!(*from, UNIX_EPOCH);
+        assert!(*to >= before && *to <= after);
+    }
+
+    #[test]
+    fn financials_export_request_with_inverted_time_window_is_refused() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = subject.compute_financials_export(
+            &UiFinancialsExportRequest {
+                from_timestamp_opt: Some(1_700_000_000),
+                to_timestamp_opt: Some(1_600_000_000),
+                offset_opt: None,
+                limit_opt: None,
+            },
+            5555,
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "financialsExport".to_string(),
+                path: MessagePath::Conversation(5555),
+                payload: Err((
+                    FINANCIALS_EXPORT_ERROR,
+                    "Time window must run from earlier to later, not 1700000000-1600000000"
+                        .to_string()
+                ))
+            }
+        );
+    }
+
+    #[test]
+    fn financials_export_pages_the_ledger_and_sends_accounts_with_the_first_page_only() {
+        let history_params_arc = Arc::new(Mutex::new(vec![]));
+        let entry = |timestamp: i64| {
+            LedgerEntry::new(
+                from_time_t(timestamp),
+                make_wallet("creditor"),
+                LedgerEntryKind::ServiceConsumed,
+                1,
+            )
+        };
+        let ledger_dao = LedgerDaoMock::new()
+            .history_params(&history_params_arc)
+            .history_result(vec![
+                entry(1_600_000_001),
+                entry(1_600_000_002),
+                entry(1_600_000_003),
+            ]);
+        let subject = AccountantBuilder::default().ledger_dao(ledger_dao).build();
+
+        let result = subject.compute_financials_export(
+            &UiFinancialsExportRequest {
+                from_timestamp_opt: Some(1_600_000_000),
+                to_timestamp_opt: Some(1_700_000_000),
+                offset_opt: Some(4),
+                limit_opt: Some(2),
+            },
+            4444,
+        );
+
+        let (body, _) = UiFinancialsExportResponse::fmb(result).unwrap();
+        assert_eq!(body.payables, vec![]);
+        assert_eq!(body.receivables, vec![]);
+        assert_eq!(
+            body.transactions
+                .iter()
+                .map(|transaction| transaction.timestamp)
+                .collect::<Vec<u64>>(),
+            vec![1_600_000_001, 1_600_000_002]
+        );
+        assert_eq!(body.next_offset_opt, Some(6));
+        let history_params = history_params_arc.lock().unwrap();
+        let (_, _, _, offset, limit) = &history_params[0];
+        assert_eq!((*offset, *limit), (4, 3));
+    }
+
+    #[test]
+    fn financials_export_request_with_oversized_page_is_refused() {
+        let subject = AccountantBuilder::default().build();
+
+        let result = subject.compute_financials_export(
+            &UiFinancialsExportRequest {
+                from_timestamp_opt: None,
+                to_timestamp_opt: None,
+                offset_opt: None,
+                limit_opt: Some(LEDGER_PAGE_SIZE_LIMIT + 1),
+            },
+            5555,
+        );
+
+        assert_eq!(
+            result,
+            MessageBody {
+                opcode: "financialsExport".to_string(),
+                path: MessagePath::Conversation(5555),
+                payload: Err((
+                    FINANCIALS_EXPORT_ERROR,
+                    format!(
+                        "Page size must be between 1 and {}, not {}",
+                        LEDGER_PAGE_SIZE_LIMIT,
+                        LEDGER_PAGE_SIZE_LIMIT + 1
+                    )
+                ))
+            }
+        );
+    }
+
+    #[test]
+    fn service_charges_are_recorded_in_ledger() {
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let now = SystemTime::now();
+        let payable_dao = PayableDaoMock::new().more_money_payable_result(Ok(()));
+        let receivable_dao = ReceivableDaoMock::new().more_money_receivable_result(Ok(()));
+        let ledger_dao = LedgerDaoMock::new().record_params(&record_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earner")))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .ledger_dao(ledger_dao)
+            .build();
+
+        subject.record_service_provided(
+            ServiceType::Routing,
+            42,
+            2,
+            now,
+            100,
+            &make_wallet("debtor"),
+        );
+        subject.record_service_consumed(
+            ServiceType::Exit,
+            24,
+            3,
+            now,
+            100,
+            &make_wallet("creditor"),
+        );
+
+        let record_params = record_params_arc.lock().unwrap();
+        assert_eq!(
+            *record_params,
+            vec![
+                vec![LedgerEntry::new(
+                    now,
+                    make_wallet("debtor"),
+                    LedgerEntryKind::ServiceProvided,
+                    242
+                )],
+                vec![LedgerEntry::new(
+                    now,
+                    make_wallet("creditor"),
+                    LedgerEntryKind::ServiceConsumed,
+                    324
+                )]
+            ]
+        );
+    }
+
+    #[test]
+    fn failure_to_record_in_ledger_is_only_logged() {
+        init_test_logging();
+        let test_name = "failure_to_record_in_ledger_is_only_logged";
+        let more_money_payable_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::new()
+            .more_money_payable_params(more_money_payable_params_arc.clone())
+            .more_money_payable_result(Ok(()));
+        let ledger_dao = LedgerDaoMock::new().record_result(Err(LedgerDaoError::InsertionFailed(
+            "disk full".to_string(),
+        )));
+        let mut subject = AccountantBuilder::default()
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .ledger_dao(ledger_dao)
+            .logger(Logger::new(test_name))
+            .build();
+
+        subject.record_service_consumed(
+            ServiceType::Exit,
+            24,
+            3,
+            SystemTime::now(),
+            100,
+            &make_wallet("creditor"),
+        );
+
+        assert_eq!(more_money_payable_params_arc.lock().unwrap().len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Failed to record 1 entries in the ledger: InsertionFailed(\"disk full\")",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn service_usage_is_recorded_by_service_type_and_direction() {
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let now = SystemTime::now();
+        let payable_dao = PayableDaoMock::new()
+            .more_money_payable_result(Ok(()))
+            .more_money_payable_result(Ok(()));
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_receivable_result(Ok(()))
+            .more_money_receivable_result(Ok(()));
+        let service_usage_dao = ServiceUsageDaoMock::new().record_params(&record_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earner")))
+            .payable_daos(vec![ForAccountantBody(payable_dao)])
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .service_usage_dao(service_usage_dao)
+            .build();
+
+        subject.handle_report_routing_service_provided_message(
+            ReportRoutingServiceProvidedMessage {
+                timestamp: now,
+                paying_wallet: make_wallet("debtor"),
+                payload_size: 100,
+                service_rate: 42,
+                byte_rate: 2,
+            },
+        );
+        subject.handle_report_exit_service_provided_message(ReportExitServiceProvidedMessage {
+            timestamp: now,
+            paying_wallet: make_wallet("debtor"),
+            payload_size: 200,
+            service_rate: 43,
+            byte_rate: 3,
+        });
+        subject.handle_report_services_consumed_message(ReportServicesConsumedMessage {
+            timestamp: now,
+            exit: ExitServiceConsumed {
+                earning_wallet: make_wallet("exit"),
+                payload_size: 300,
+                service_rate: 44,
+                byte_rate: 4,
+            },
+            routing_payload_size: 400,
+            routing: vec![RoutingServiceConsumed {
+                earning_wallet: make_wallet("router"),
+                service_rate: 45,
+                byte_rate: 5,
+            }],
+        });
+
+        let usage = |wallet: &str, service, direction, bytes, charge_wei| ServiceUsage {
+            timestamp: now,
+            wallet: make_wallet(wallet),
+            service,
+            direction,
+            bytes,
+            charge_wei,
+        };
+        assert_eq!(
+            *record_params_arc.lock().unwrap(),
+            vec![
+                usage(
+                    "debtor",
+                    ServiceType::Routing,
+                    ServiceDirection::Provided,
+                    100,
+                    242
+                ),
+                usage(
+                    "debtor",
+                    ServiceType::Exit,
+                    ServiceDirection::Provided,
+                    200,
+                    643
+                ),
+                usage(
+                    "exit",
+                    ServiceType::Exit,
+                    ServiceDirection::Consumed,
+                    300,
+                    1244
+                ),
+                usage(
+                    "router",
+                    ServiceType::Routing,
+                    ServiceDirection::Consumed,
+                    400,
+                    2045
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn failure_to_record_service_usage_is_only_logged() {
+        init_test_logging();
+        let test_name = "failure_to_record_service_usage_is_only_logged";
+        let receivable_dao = ReceivableDaoMock::new().more_money_receivable_result(Ok(()));
+        let service_usage_dao = ServiceUsageDaoMock::new().record_result(Err(
+            ServiceUsageDaoError::RecordingFailed("disk full".to_string()),
+        ));
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earner")))
+            .receivable_daos(vec![ForAccountantBody(receivable_dao)])
+            .service_usage_dao(service_usage_dao)
+            .logger(Logger::new(test_name))
+            .build();
+
+        subject.record_service_provided(
+            ServiceType::Exit,
+            42,
+            2,
+            SystemTime::now(),
+            100,
+            &make_wallet("debtor"),
+        );
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Failed to record provided exit service usage for {}: \
+             RecordingFailed(\"disk full\")",
+            test_name,
+            make_wallet("debtor")
+        ));
+    }
+
+    #[test]
+    fn service_usage_is_not_recorded_when_the_charge_is_not_booked() {
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let service_usage_dao = ServiceUsageDaoMock::new().record_params(&record_params_arc);
+        let mut subject = AccountantBuilder::default()
+            .bootstrapper_config(bc_from_earning_wallet(make_wallet("earner")))
+            .service_usage_dao(service_usage_dao)
+            .build();
+
+        subject.record_service_consumed(
+            ServiceType::Routing,
+            24,
+            3,
+            SystemTime::now(),
+            100,
+            &make_wallet("earner"),
+        );
+
+        assert!(record_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn received_payments_are_recorded_in_ledger_including_reverted_ones() {
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let now = SystemTime::now();
+        let received = BlockchainTransaction {
+            block_number: 7,
+            transaction_hash: make_tx_hash(3),
+            from: make_wallet("debtor_1"),
+            to: make_wallet("earning_wallet"),
+            wei_amount: 456,
+        };
+        let removed = BlockchainTransaction {
+            block_number: 5,
+            transaction_hash: make_tx_hash(4),
+            from: make_wallet("debtor_2"),
+            to: make_wallet("earning_wallet"),
+            wei_amount: 789,
+        };
+        let ledger_dao = LedgerDaoMock::new().record_params(&record_params_arc);
+        let mut subject = AccountantBuilder::default().ledger_dao(ledger_dao).build();
+
+        subject.record_received_payments_in_ledger(&ReceivedPayments {
+            timestamp: now,
+            payments: vec![received],
+            rechecked_payments_opt: None,
+            removed_payments: vec![removed],
+            catch_up_progress_opt: None,
+            response_skeleton_opt: None,
+        });
+        subject.record_received_payments_in_ledger(&ReceivedPayments {
+            timestamp: now,
+            payments: vec![],
+            rechecked_payments_opt: None,
+            removed_payments: vec![],
+            catch_up_progress_opt: None,
+            response_skeleton_opt: None,
+        });
+
+        let record_params = record_params_arc.lock().unwrap();
+        assert_eq!(
+            *record_params,
+            vec![vec![
+                LedgerEntry {
+                    transaction_hash_opt: Some(make_tx_hash(3)),
+                    block_number_opt: Some(7),
+                    outcome_opt: Some(PaymentOutcome::Confirmed),
+                    ..LedgerEntry::new(
+                        now,
+                        make_wallet("debtor_1"),
+                        LedgerEntryKind::PaymentReceived,
+                        456
+                    )
+                },
+                LedgerEntry {
+                    transaction_hash_opt: Some(make_tx_hash(4)),
+                    block_number_opt: Some(5),
+                    outcome_opt: Some(PaymentOutcome::Reverted),
+                    ..LedgerEntry::new(
+                        now,
+                        make_wallet("debtor_2"),
+                        LedgerEntryKind::PaymentReceived,
+                        789
+                    )
+                }
+            ]]
+        );
+    }
+
+    #[test]
+    fn payment_outcomes_from_transaction_receipts_are_recorded_in_ledger() {
+        init_test_logging();
+        let test_name = "payment_outcomes_from_transaction_receipts_are_recorded_in_ledger";
+        let record_payment_outcome_params_arc = Arc::new(Mutex::new(vec![]));
+        let ledger_dao = LedgerDaoMock::new()
+            .record_payment_outcome_params(&record_payment_outcome_params_arc)
+            .record_payment_outcome_result(Ok(()))
+            .record_payment_outcome_result(Err(LedgerDaoError::PaymentNotFound(make_tx_hash(222))));
+        let subject = AccountantBuilder::default()
+            .ledger_dao(ledger_dao)
+            .logger(Logger::new(test_name))
+            .build();
+        let fingerprint = |n: u32| PendingPayableFingerprint {
+            hash: make_tx_hash(n),
+            ..make_pending_payable_fingerprint()
+        };
+        let receipt = |status_opt: Option<u64>| {
+            let mut receipt = TransactionReceipt::default();
+            receipt.status = status_opt.map(U64::from);
+            receipt.block_number = Some(U64::from(4_444));
+            receipt
+        };
+        let before = SystemTime::now();
+
+        subject.record_payment_outcomes_in_ledger(&ReportTransactionReceipts {
+            fingerprints_with_receipts: vec![
+                (Some(receipt(Some(1))), fingerprint(111)),
+                (Some(receipt(Some(0))), fingerprint(222)),
+                (Some(receipt(None)), fingerprint(333)),
+                (None, fingerprint(444)),
+            ],
+            response_skeleton_opt: None,
+        });
+
+        let after = SystemTime::now();
+        let params = record_payment_outcome_params_arc.lock().unwrap();
+        assert_eq!(params.len(), 2);
+        assert_eq!(
+            (params[0].0, params[0].1, params[0].2),
+            (make_tx_hash(111), PaymentOutcome::Confirmed, Some(4_444))
+        );
+        assert_eq!(
+            (params[1].0, params[1].1, params[1].2),
+            (make_tx_hash(222), PaymentOutcome::Failed, Some(4_444))
+        );
+        assert!(params[0].3 >= before && params[0].3 <= after);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {}: Payment {:?} was sent before the ledger knew about it; its outcome is not recorded",
+            test_name,
+            make_tx_hash(222)
+        ));
+    }
+
     #[test]
     fn compute_financials_processes_defaulted_request() {
         let payable_dao = PayableDaoMock::new().total_result(u64::MAX as u128 + 123456);
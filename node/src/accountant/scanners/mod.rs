@@ -26,11 +26,12 @@ use crate::accountant::{
     ReportTransactionReceipts, RequestTransactionReceipts, ResponseSkeleton, ScanForPayables,
     ScanForPendingPayables, ScanForReceivables, SentPayables,
 };
-use crate::accountant::database_access_objects::banned_dao::BannedDao;
+use crate::accountant::database_access_objects::banned_dao::{BanReason, BannedDao};
 use crate::blockchain::blockchain_bridge::{PendingPayableFingerprint, RetrieveTransactions};
-use crate::blockchain::blockchain_interface::PayableTransactionError;
+use crate::blockchain::blockchain_interface::{BlockchainTransaction, PayableTransactionError};
 use crate::sub_lib::accountant::{
-    DaoFactories, FinancialStatistics, PaymentThresholds, ScanIntervals,
+    BanListChanges, DaoFactories, DelinquencyPolicy, EarningWallets, FinancialStatistics,
+    PaymentThresholds, ScanIntervals, WarnedDebtor,
 };
 use crate::sub_lib::blockchain_bridge::RequestBalancesToPayPayables;
 use crate::sub_lib::utils::{NotifyLaterHandle, NotifyLaterHandleReal};
@@ -39,7 +40,9 @@ use actix::{Context, Message, System};
 use itertools::Itertools;
 use masq_lib::logger::Logger;
 use masq_lib::logger::TIME_FORMATTING_STRING;
-use masq_lib::messages::{ScanType, ToMessageBody, UiScanResponse};
+use masq_lib::messages::{
+    ScanType, ToMessageBody, UiReceivablesCatchUpBroadcast, UiScanResponse,
+};
 use masq_lib::ui_gateway::{MessageTarget, NodeToUiMessage};
 use masq_lib::utils::ExpectValue;
 #[cfg(test)]
@@ -61,10 +64,12 @@ pub struct Scanners {
 impl Scanners {
     pub fn new(
         dao_factories: DaoFactories,
-        payment_thresholds: Rc<PaymentThresholds>,
-        earning_wallet: Rc<Wallet>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
+        earning_wallets: Rc<RefCell<EarningWallets>>,
         when_pending_too_long_sec: u64,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
+        ban_list_changes: Rc<RefCell<BanListChanges>>,
+        delinquency_policy: DelinquencyPolicy,
     ) -> Self {
         Scanners {
             payable: Box::new(PayableScanner::new(
@@ -83,8 +88,10 @@ impl Scanners {
                 dao_factories.receivable_dao_factory.make(),
                 dao_factories.banned_dao_factory.make(),
                 Rc::clone(&payment_thresholds),
-                earning_wallet,
+                earning_wallets,
                 financial_statistics,
+                ban_list_changes,
+                delinquency_policy,
             )),
         }
     }
@@ -110,11 +117,11 @@ where
 
 pub struct ScannerCommon {
     initiated_at_opt: Option<SystemTime>,
-    pub payment_thresholds: Rc<PaymentThresholds>,
+    pub payment_thresholds: Rc<RefCell<PaymentThresholds>>,
 }
 
 impl ScannerCommon {
-    fn new(payment_thresholds: Rc<PaymentThresholds>) -> Self {
+    fn new(payment_thresholds: Rc<RefCell<PaymentThresholds>>) -> Self {
         Self {
             initiated_at_opt: None,
             payment_thresholds,
@@ -245,7 +252,7 @@ impl PayableScanner {
     pub fn new(
         payable_dao: Box<dyn PayableDao>,
         pending_payable_dao: Box<dyn PendingPayableDao>,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
     ) -> Self {
         Self {
             common: ScannerCommon::new(payment_thresholds),
@@ -295,21 +302,29 @@ impl PayableScanner {
 
         if self.payable_threshold_gauge.is_innocent_age(
             debt_age,
-            self.common.payment_thresholds.maturity_threshold_sec,
+            self.common
+                .payment_thresholds
+                .borrow()
+                .maturity_threshold_sec,
         ) {
             return None;
         }
 
         if self.payable_threshold_gauge.is_innocent_balance(
             payable.balance_wei,
-            gwei_to_wei(self.common.payment_thresholds.permanent_debt_allowed_gwei),
+            gwei_to_wei(
+                self.common
+                    .payment_thresholds
+                    .borrow()
+                    .permanent_debt_allowed_gwei,
+            ),
         ) {
             return None;
         }
 
         let threshold = self
             .payable_threshold_gauge
-            .calculate_payout_threshold_in_gwei(&self.common.payment_thresholds, debt_age);
+            .calculate_payout_threshold_in_gwei(&self.common.payment_thresholds.borrow(), debt_age);
         if payable.balance_wei > threshold {
             Some(threshold)
         } else {
@@ -527,7 +542,7 @@ impl PendingPayableScanner {
     pub fn new(
         payable_dao: Box<dyn PayableDao>,
         pending_payable_dao: Box<dyn PendingPayableDao>,
-        payment_thresholds: Rc<PaymentThresholds>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
         when_pending_too_long_sec: u64,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
     ) -> Self {
@@ -667,7 +682,13 @@ impl PendingPayableScanner {
         logger: &Logger,
     ) {
         fn serialize_hashes(fingerprints: &[PendingPayableFingerprint]) -> String {
-            comma_joined_stringifiable(fingerprints, |fgp| format!("{:?}", fgp.hash))
+            // several fingerprints share a hash when their payables went out in one disperse transaction
+            let hashes = fingerprints
+                .iter()
+                .map(|fgp| fgp.hash)
+                .unique()
+                .collect_vec();
+            comma_joined_stringifiable(&hashes, |hash| format!("{:?}", hash))
         }
 
         if !fingerprints.is_empty() {
@@ -719,8 +740,10 @@ pub struct ReceivableScanner {
     pub common: ScannerCommon,
     pub receivable_dao: Box<dyn ReceivableDao>,
     pub banned_dao: Box<dyn BannedDao>,
-    pub earning_wallet: Rc<Wallet>,
+    pub earning_wallets: Rc<RefCell<EarningWallets>>,
     pub financial_statistics: Rc<RefCell<FinancialStatistics>>,
+    pub ban_list_changes: Rc<RefCell<BanListChanges>>,
+    pub delinquency_policy: DelinquencyPolicy,
 }
 
 impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
@@ -734,14 +757,16 @@ impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
             return Err(BeginScanError::ScanAlreadyRunning(timestamp));
         }
         self.mark_as_started(timestamp);
+        let recipients = self.watched_earning_wallets(timestamp, logger);
         info!(
             logger,
-            "Scanning for receivables to {}", self.earning_wallet
+            "Scanning for receivables to {}",
+            comma_joined_stringifiable(&recipients, |wallet| wallet.to_string())
         );
         self.scan_for_delinquencies(timestamp, logger);
 
         Ok(RetrieveTransactions {
-            recipient: self.earning_wallet.as_ref().clone(),
+            recipients,
             response_skeleton_opt,
         })
     }
@@ -768,6 +793,25 @@ impl Scanner<RetrieveTransactions, ReceivedPayments> for ReceivableScanner {
                 .borrow_mut()
                 .total_paid_receivable_wei += total_newly_paid_receivable;
         }
+        if !message.removed_payments.is_empty() {
+            self.reconcile_removed_payments(message.removed_payments, logger)
+        }
+        if let Some(progress) = message.catch_up_progress_opt {
+            info!(
+                logger,
+                "Catching up on receivables: scanned through block {} of {}",
+                progress.scanned_through_block,
+                progress.target_block
+            );
+            return Some(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiReceivablesCatchUpBroadcast {
+                    scanned_through_block: progress.scanned_through_block,
+                    target_block: progress.target_block,
+                }
+                .tmb(0),
+            });
+        }
 
         self.mark_as_ended(logger);
         message
@@ -787,19 +831,64 @@ impl ReceivableScanner {
     pub fn new(
         receivable_dao: Box<dyn ReceivableDao>,
         banned_dao: Box<dyn BannedDao>,
-        payment_thresholds: Rc<PaymentThresholds>,
-        earning_wallet: Rc<Wallet>,
+        payment_thresholds: Rc<RefCell<PaymentThresholds>>,
+        earning_wallets: Rc<RefCell<EarningWallets>>,
         financial_statistics: Rc<RefCell<FinancialStatistics>>,
+        ban_list_changes: Rc<RefCell<BanListChanges>>,
+        delinquency_policy: DelinquencyPolicy,
     ) -> Self {
         Self {
             common: ScannerCommon::new(payment_thresholds),
-            earning_wallet,
+            earning_wallets,
             receivable_dao,
             banned_dao,
             financial_statistics,
+            ban_list_changes,
+            delinquency_policy,
         }
     }
 
+    fn watched_earning_wallets(&self, timestamp: SystemTime, logger: &Logger) -> Vec<Wallet> {
+        let mut earning_wallets = self.earning_wallets.borrow_mut();
+        earning_wallets
+            .drop_expired(timestamp)
+            .into_iter()
+            .for_each(|wallet| {
+                info!(
+                    logger,
+                    "Stopped watching retired earning wallet {} for late payments", wallet
+                )
+            });
+        earning_wallets.watched()
+    }
+
+    fn reconcile_removed_payments(
+        &mut self,
+        removed_payments: Vec<BlockchainTransaction>,
+        logger: &Logger,
+    ) {
+        let total_removed = removed_payments
+            .iter()
+            .fold(0, |so_far, now| so_far + now.wei_amount);
+        warning!(
+            logger,
+            "Reverting {} received payment(s) totaling {} wei that disappeared in a blockchain reorganization: {}",
+            removed_payments.len(),
+            total_removed,
+            comma_joined_stringifiable(&removed_payments, |payment| format!(
+                "{} from {} (block {})",
+                payment.wei_amount, payment.from, payment.block_number
+            ))
+        );
+        self.receivable_dao
+            .as_mut()
+            .reverse_received_payments(removed_payments);
+        let mut financial_statistics = self.financial_statistics.borrow_mut();
+        financial_statistics.total_paid_receivable_wei = financial_statistics
+            .total_paid_receivable_wei
+            .saturating_sub(total_removed);
+    }
+
     pub fn scan_for_delinquencies(&self, timestamp: SystemTime, logger: &Logger) {
         info!(logger, "Scanning for delinquencies");
         self.find_and_ban_delinquents(timestamp, logger);
@@ -807,28 +896,87 @@ impl ReceivableScanner {
     }
 
     fn find_and_ban_delinquents(&self, timestamp: SystemTime, logger: &Logger) {
+        let grace_period = Duration::from_secs(self.delinquency_policy.grace_period_sec);
+        let mut warnings = self.banned_dao.warnings();
         self.receivable_dao
-            .new_delinquencies(timestamp, self.common.payment_thresholds.as_ref())
+            .new_delinquencies(timestamp, &self.common.payment_thresholds.borrow())
             .into_iter()
             .for_each(|account| {
-                self.banned_dao.ban(&account.wallet);
                 let (balance_str_wei, age) = balance_and_age(timestamp, &account);
+                match warnings.remove(&account.wallet) {
+                    None if grace_period > Duration::ZERO => {
+                        if self.banned_dao.warn(&account.wallet, timestamp) {
+                            self.ban_list_changes
+                                .borrow_mut()
+                                .warned
+                                .push(WarnedDebtor {
+                                    wallet: account.wallet.clone(),
+                                    balance_wei: account.balance_wei,
+                                    ban_deadline: timestamp + grace_period,
+                                });
+                            info!(
+                                logger,
+                                "Wallet {} (balance: {} gwei, age: {} sec) is delinquent: warned, and will be banned in {} sec unless it pays",
+                                account.wallet,
+                                balance_str_wei,
+                                age.as_secs(),
+                                grace_period.as_secs()
+                            )
+                        }
+                    }
+                    Some(warned_at) if warned_at + grace_period > timestamp => debug!(
+                        logger,
+                        "Wallet {} (balance: {} gwei, age: {} sec) is still in its delinquency grace period",
+                        account.wallet,
+                        balance_str_wei,
+                        age.as_secs()
+                    ),
+                    warning_opt => {
+                        if self
+                            .banned_dao
+                            .ban(&account.wallet, BanReason::Delinquency, None, timestamp)
+                        {
+                            self.ban_list_changes
+                                .borrow_mut()
+                                .banned
+                                .push(account.wallet.clone())
+                        }
+                        if warning_opt.is_some() {
+                            self.banned_dao.unwarn(&account.wallet);
+                        }
+                        info!(
+                            logger,
+                            "Wallet {} (balance: {} gwei, age: {} sec) banned for delinquency",
+                            account.wallet,
+                            balance_str_wei,
+                            age.as_secs()
+                        )
+                    }
+                }
+            });
+        // Whatever is left was warned but isn't an unbanned delinquent anymore
+        warnings.into_keys().for_each(|wallet| {
+            if self.banned_dao.unwarn(&wallet) {
                 info!(
                     logger,
-                    "Wallet {} (balance: {} gwei, age: {} sec) banned for delinquency",
-                    account.wallet,
-                    balance_str_wei,
-                    age.as_secs()
+                    "Delinquency warning for wallet {} withdrawn: it's no longer delinquent, or is already banned",
+                    wallet
                 )
-            });
+            }
+        });
     }
 
     fn find_and_unban_reformed_nodes(&self, timestamp: SystemTime, logger: &Logger) {
         self.receivable_dao
-            .paid_delinquencies(self.common.payment_thresholds.as_ref())
+            .paid_delinquencies(&self.common.payment_thresholds.borrow())
             .into_iter()
             .for_each(|account| {
-                self.banned_dao.unban(&account.wallet);
+                if self.banned_dao.unban(&account.wallet) {
+                    self.ban_list_changes
+                        .borrow_mut()
+                        .unbanned
+                        .push(account.wallet.clone())
+                }
                 let (balance_str_wei, age) = balance_and_age(timestamp, &account);
                 info!(
                     logger,
@@ -1075,6 +1223,7 @@ impl<T: Default> PeriodicalScanConfig<T> {
 
 #[cfg(test)]
 mod tests {
+    use crate::accountant::database_access_objects::banned_dao::BanReason;
     use crate::accountant::scanners::{
         BeginScanError, PayableScanner, PendingPayableScanner, ReceivableScanner, Scanner,
         ScannerCommon, Scanners,
@@ -1082,14 +1231,15 @@ mod tests {
     use crate::accountant::test_utils::{
         make_custom_payment_thresholds, make_payable_account, make_payables,
         make_pending_payable_fingerprint, make_receivable_account, BannedDaoFactoryMock,
-        BannedDaoMock, PayableDaoFactoryMock, PayableDaoMock, PayableScannerBuilder,
-        PayableThresholdsGaugeMock, PendingPayableDaoFactoryMock, PendingPayableDaoMock,
-        PendingPayableScannerBuilder, ReceivableDaoFactoryMock, ReceivableDaoMock,
-        ReceivableScannerBuilder,
+        BannedDaoMock, LedgerDaoFactoryMock, PayableDaoFactoryMock, PayableDaoMock,
+        PayableScannerBuilder, PayableThresholdsGaugeMock, PendingPayableDaoFactoryMock,
+        PendingPayableDaoMock, PendingPayableScannerBuilder, ReceivableDaoFactoryMock,
+        ReceivableDaoMock, ReceivableScannerBuilder, ServiceUsageDaoFactoryMock,
     };
     use crate::accountant::{
-        gwei_to_wei, PendingPayableId, ReceivedPayments, ReportTransactionReceipts,
-        RequestTransactionReceipts, SentPayables, DEFAULT_PENDING_TOO_LONG_SEC,
+        gwei_to_wei, PendingPayableId, ReceivablesCatchUpProgress, ReceivedPayments,
+        ReportTransactionReceipts, RequestTransactionReceipts, SentPayables,
+        DEFAULT_PENDING_TOO_LONG_SEC,
     };
     use crate::blockchain::blockchain_bridge::{PendingPayableFingerprint, RetrieveTransactions};
     use std::cell::RefCell;
@@ -1109,7 +1259,8 @@ mod tests {
     };
     use crate::blockchain::test_utils::make_tx_hash;
     use crate::sub_lib::accountant::{
-        DaoFactories, FinancialStatistics, PaymentThresholds, DEFAULT_PAYMENT_THRESHOLDS,
+        BanListChanges, DaoFactories, DelinquencyPolicy, EarningWallets, FinancialStatistics,
+        PaymentThresholds, RetiredEarningWallet, WarnedDebtor, DEFAULT_PAYMENT_THRESHOLDS,
     };
     use crate::sub_lib::blockchain_bridge::RequestBalancesToPayPayables;
     use crate::test_utils::make_wallet;
@@ -1119,6 +1270,7 @@ mod tests {
     use masq_lib::messages::ScanType;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
     use regex::Regex;
+    use std::collections::HashMap;
     use std::rc::Rc;
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, SystemTime};
@@ -1141,10 +1293,18 @@ mod tests {
             total_paid_payable_wei: 1,
             total_paid_receivable_wei: 2,
         };
-        let earning_wallet = make_wallet("unique_wallet");
+        let earning_wallets = Rc::new(RefCell::new(EarningWallets::new(
+            make_wallet("unique_wallet"),
+            vec![],
+        )));
         let payment_thresholds = make_custom_payment_thresholds();
-        let payment_thresholds_rc = Rc::new(payment_thresholds);
+        let payment_thresholds_rc = Rc::new(RefCell::new(payment_thresholds));
         let initial_rc_count = Rc::strong_count(&payment_thresholds_rc);
+        let ban_list_changes = Rc::new(RefCell::new(BanListChanges::default()));
+        let delinquency_policy = DelinquencyPolicy {
+            grace_period_sec: 3_600,
+            notify_debtors: true,
+        };
 
         let scanners = Scanners::new(
             DaoFactories {
@@ -1152,11 +1312,15 @@ mod tests {
                 pending_payable_dao_factory: Box::new(pending_payable_dao_factory),
                 receivable_dao_factory: Box::new(receivable_dao_factory),
                 banned_dao_factory: Box::new(banned_dao_factory),
+                ledger_dao_factory: Box::new(LedgerDaoFactoryMock::new()),
+                service_usage_dao_factory: Box::new(ServiceUsageDaoFactoryMock::new()),
             },
             Rc::clone(&payment_thresholds_rc),
-            Rc::new(earning_wallet.clone()),
+            Rc::clone(&earning_wallets),
             when_pending_too_long_sec,
             Rc::new(RefCell::new(financial_statistics.clone())),
+            Rc::clone(&ban_list_changes),
+            delinquency_policy,
         );
 
         let payable_scanner = scanners
@@ -1175,8 +1339,8 @@ mod tests {
             .downcast_ref::<ReceivableScanner>()
             .unwrap();
         assert_eq!(
-            payable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *payable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(payable_scanner.common.initiated_at_opt.is_some(), false);
         payable_scanner
@@ -1193,8 +1357,8 @@ mod tests {
             financial_statistics
         );
         assert_eq!(
-            pending_payable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *pending_payable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(
             pending_payable_scanner.common.initiated_at_opt.is_some(),
@@ -1204,15 +1368,20 @@ mod tests {
             *receivable_scanner.financial_statistics.borrow(),
             financial_statistics
         );
+        assert!(Rc::ptr_eq(
+            &receivable_scanner.earning_wallets,
+            &earning_wallets
+        ));
         assert_eq!(
-            receivable_scanner.earning_wallet.address(),
-            earning_wallet.address()
-        );
-        assert_eq!(
-            receivable_scanner.common.payment_thresholds.as_ref(),
-            &payment_thresholds
+            *receivable_scanner.common.payment_thresholds.borrow(),
+            payment_thresholds
         );
         assert_eq!(receivable_scanner.common.initiated_at_opt.is_some(), false);
+        assert!(Rc::ptr_eq(
+            &receivable_scanner.ban_list_changes,
+            &ban_list_changes
+        ));
+        assert_eq!(receivable_scanner.delinquency_policy, delinquency_policy);
         assert_eq!(
             Rc::strong_count(&payment_thresholds_rc),
             initial_rc_count + 3
@@ -2506,6 +2675,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn confirm_transactions_credits_every_payable_paid_by_a_shared_transaction() {
+        init_test_logging();
+        let test_name = "confirm_transactions_credits_every_payable_paid_by_a_shared_transaction";
+        let transactions_confirmed_params_arc = Arc::new(Mutex::new(vec![]));
+        let delete_fingerprints_params_arc = Arc::new(Mutex::new(vec![]));
+        let payable_dao = PayableDaoMock::default()
+            .transactions_confirmed_params(&transactions_confirmed_params_arc)
+            .transactions_confirmed_result(Ok(()));
+        let pending_payable_dao = PendingPayableDaoMock::default()
+            .delete_fingerprints_params(&delete_fingerprints_params_arc)
+            .delete_fingerprints_result(Ok(()));
+        let mut subject = PendingPayableScannerBuilder::new()
+            .payable_dao(payable_dao)
+            .pending_payable_dao(pending_payable_dao)
+            .build();
+        let shared_hash = make_tx_hash(0xd15e);
+        let make_fingerprint = |rowid: u64, amount: u128| PendingPayableFingerprint {
+            rowid,
+            timestamp: from_time_t(199_000_000),
+            hash: shared_hash,
+            attempt: 2,
+            amount,
+            process_error: None,
+        };
+        let fingerprints = vec![
+            make_fingerprint(3, 1_000),
+            make_fingerprint(4, 20_000),
+            make_fingerprint(5, 300_000),
+        ];
+
+        subject.confirm_transactions(fingerprints.clone(), &Logger::new(test_name));
+
+        let confirm_transactions_params = transactions_confirmed_params_arc.lock().unwrap();
+        assert_eq!(*confirm_transactions_params, vec![fingerprints]);
+        let delete_fingerprints_params = delete_fingerprints_params_arc.lock().unwrap();
+        assert_eq!(*delete_fingerprints_params, vec![vec![3, 4, 5]]);
+        let total_paid_payable = subject.financial_statistics.borrow().total_paid_payable_wei;
+        assert_eq!(total_paid_payable, 321_000);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {}: Transactions \
+             0x000000000000000000000000000000000000000000000000000000000000d15e \
+             completed their confirmation process succeeding",
+            test_name
+        ));
+    }
+
     #[test]
     #[should_panic(
         expected = "Unable to cast confirmed pending payables 0x0000000000000000000000000000000000000000000\
@@ -2678,7 +2894,7 @@ mod tests {
         assert_eq!(
             result,
             Ok(RetrieveTransactions {
-                recipient: earning_wallet.clone(),
+                recipients: vec![earning_wallet.clone()],
                 response_skeleton_opt: None
             })
         );
@@ -2687,6 +2903,55 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn receivable_scanner_watches_retired_earning_wallets_until_their_watch_period_expires() {
+        init_test_logging();
+        let test_name =
+            "receivable_scanner_watches_retired_earning_wallets_until_their_watch_period_expires";
+        let now = SystemTime::now();
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![])
+            .paid_delinquencies_result(vec![]);
+        let earning_wallet = make_wallet("earning");
+        let still_watched_wallet = make_wallet("still_watched");
+        let expired_wallet = make_wallet("expired");
+        let mut receivable_scanner = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .earning_wallet(earning_wallet.clone())
+            .retired_earning_wallets(vec![
+                RetiredEarningWallet {
+                    wallet: expired_wallet.clone(),
+                    watch_until: now.checked_sub(Duration::from_secs(1)).unwrap(),
+                },
+                RetiredEarningWallet {
+                    wallet: still_watched_wallet.clone(),
+                    watch_until: now.checked_add(Duration::from_secs(3600)).unwrap(),
+                },
+            ])
+            .build();
+
+        let result = receivable_scanner.begin_scan(now, None, &Logger::new(test_name));
+
+        assert_eq!(
+            result,
+            Ok(RetrieveTransactions {
+                recipients: vec![earning_wallet.clone(), still_watched_wallet.clone()],
+                response_skeleton_opt: None
+            })
+        );
+        assert_eq!(
+            receivable_scanner.earning_wallets.borrow().watched(),
+            vec![earning_wallet.clone(), still_watched_wallet.clone()]
+        );
+        let log_handler = TestLogHandler::new();
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Stopped watching retired earning wallet {expired_wallet} for late payments"
+        ));
+        log_handler.exists_log_containing(&format!(
+            "INFO: {test_name}: Scanning for receivables to {earning_wallet}, {still_watched_wallet}"
+        ));
+    }
+
     #[test]
     fn receivable_scanner_throws_error_in_case_scan_is_already_running() {
         let now = SystemTime::now();
@@ -2726,9 +2991,12 @@ mod tests {
         let payment_thresholds = make_custom_payment_thresholds();
         let earning_wallet = make_wallet("earning");
         let banned_dao = BannedDaoMock::new()
-            .ban_list_result(vec![])
             .ban_parameters(&ban_parameters_arc)
-            .unban_parameters(&unban_parameters_arc);
+            .ban_result(true)
+            .ban_result(false)
+            .unban_parameters(&unban_parameters_arc)
+            .unban_result(false)
+            .unban_result(true);
         let mut receivable_scanner = ReceivableScannerBuilder::new()
             .receivable_dao(receivable_dao)
             .banned_dao(banned_dao)
@@ -2743,7 +3011,7 @@ mod tests {
         assert_eq!(
             result,
             Ok(RetrieveTransactions {
-                recipient: earning_wallet,
+                recipients: vec![earning_wallet],
                 response_skeleton_opt: None
             })
         );
@@ -2756,13 +3024,40 @@ mod tests {
         assert_eq!(paid_delinquencies_parameters.len(), 1);
         assert_eq!(payment_thresholds, paid_delinquencies_parameters[0]);
         let ban_parameters = ban_parameters_arc.lock().unwrap();
-        assert!(ban_parameters.contains(&newly_banned_1.wallet));
-        assert!(ban_parameters.contains(&newly_banned_2.wallet));
-        assert_eq!(2, ban_parameters.len());
+        assert_eq!(
+            *ban_parameters,
+            vec![
+                (
+                    newly_banned_1.wallet.clone(),
+                    BanReason::Delinquency,
+                    None,
+                    now
+                ),
+                (
+                    newly_banned_2.wallet.clone(),
+                    BanReason::Delinquency,
+                    None,
+                    now
+                )
+            ]
+        );
         let unban_parameters = unban_parameters_arc.lock().unwrap();
-        assert!(unban_parameters.contains(&newly_unbanned_1.wallet));
-        assert!(unban_parameters.contains(&newly_unbanned_2.wallet));
-        assert_eq!(2, unban_parameters.len());
+        assert_eq!(
+            *unban_parameters,
+            vec![
+                newly_unbanned_1.wallet.clone(),
+                newly_unbanned_2.wallet.clone()
+            ]
+        );
+        // Only the bans and unbans that actually changed the list are to be broadcast
+        assert_eq!(
+            *receivable_scanner.ban_list_changes.borrow(),
+            BanListChanges {
+                banned: vec![newly_banned_1.wallet.clone()],
+                unbanned: vec![newly_unbanned_2.wallet.clone()],
+                warned: vec![],
+            }
+        );
         let tlh = TestLogHandler::new();
         tlh.exists_log_matching(
             "INFO: DELINQUENCY_TEST: Wallet 0x00000000000000000077616c6c65743132333464 \
@@ -2782,6 +3077,170 @@ mod tests {
         );
     }
 
+    #[test]
+    fn receivable_scanner_warns_delinquents_through_the_grace_period_before_banning_them() {
+        init_test_logging();
+        let test_name =
+            "receivable_scanner_warns_delinquents_through_the_grace_period_before_banning_them";
+        let now = SystemTime::now();
+        let grace_period = Duration::from_secs(3_600);
+        let newly_delinquent = make_receivable_account(1111, true);
+        let within_grace = make_receivable_account(2222, true);
+        let out_of_grace = make_receivable_account(3333, true);
+        let reformed_wallet = make_wallet("reformed");
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![
+                newly_delinquent.clone(),
+                within_grace.clone(),
+                out_of_grace.clone(),
+            ])
+            .paid_delinquencies_result(vec![]);
+        let warnings_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let warn_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let unwarn_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let ban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new()
+            .warnings_parameters(&warnings_parameters_arc)
+            .warnings_result(HashMap::from([
+                (
+                    within_grace.wallet.clone(),
+                    now - grace_period + Duration::from_secs(1),
+                ),
+                (out_of_grace.wallet.clone(), now - grace_period),
+                (reformed_wallet.clone(), now - Duration::from_secs(60)),
+            ]))
+            .warn_parameters(&warn_parameters_arc)
+            .warn_result(true)
+            .ban_parameters(&ban_parameters_arc)
+            .ban_result(true)
+            .unwarn_parameters(&unwarn_parameters_arc)
+            .unwarn_result(true)
+            .unwarn_result(true);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .banned_dao(banned_dao)
+            .delinquency_policy(DelinquencyPolicy {
+                grace_period_sec: grace_period.as_secs(),
+                notify_debtors: false,
+            })
+            .build();
+
+        let _ = subject.begin_scan(now, None, &Logger::new(test_name));
+
+        assert_eq!(*warnings_parameters_arc.lock().unwrap(), vec![()]);
+        assert_eq!(
+            *warn_parameters_arc.lock().unwrap(),
+            vec![(newly_delinquent.wallet.clone(), now)]
+        );
+        assert_eq!(
+            *ban_parameters_arc.lock().unwrap(),
+            vec![(
+                out_of_grace.wallet.clone(),
+                BanReason::Delinquency,
+                None,
+                now
+            )]
+        );
+        assert_eq!(
+            *unwarn_parameters_arc.lock().unwrap(),
+            vec![out_of_grace.wallet.clone(), reformed_wallet.clone()]
+        );
+        assert_eq!(
+            *subject.ban_list_changes.borrow(),
+            BanListChanges {
+                banned: vec![out_of_grace.wallet.clone()],
+                unbanned: vec![],
+                warned: vec![WarnedDebtor {
+                    wallet: newly_delinquent.wallet.clone(),
+                    balance_wei: newly_delinquent.balance_wei,
+                    ban_deadline: now + grace_period,
+                }],
+            }
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_matching(&format!(
+            "INFO: {test_name}: Wallet {} \\(balance: 1,111 gwei, age: \\d+ sec\\) is delinquent: \
+            warned, and will be banned in 3600 sec unless it pays",
+            newly_delinquent.wallet
+        ));
+        tlh.exists_log_matching(&format!(
+            "DEBUG: {test_name}: Wallet {} \\(balance: 2,222 gwei, age: \\d+ sec\\) is still in \
+            its delinquency grace period",
+            within_grace.wallet
+        ));
+        tlh.exists_log_matching(&format!(
+            "INFO: {test_name}: Wallet {} \\(balance: 3,333 gwei, age: \\d+ sec\\) banned for delinquency",
+            out_of_grace.wallet
+        ));
+        tlh.exists_log_containing(&format!(
+            "INFO: {test_name}: Delinquency warning for wallet {} withdrawn: it's no longer \
+            delinquent, or is already banned",
+            reformed_wallet
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_neither_records_nor_logs_warning_that_did_not_take() {
+        init_test_logging();
+        let test_name = "receivable_scanner_neither_records_nor_logs_warning_that_did_not_take";
+        let now = SystemTime::now();
+        let delinquent = make_receivable_account(1111, true);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![delinquent.clone()])
+            .paid_delinquencies_result(vec![]);
+        let banned_dao = BannedDaoMock::new()
+            .warnings_result(HashMap::new())
+            .warn_result(false);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .banned_dao(banned_dao)
+            .delinquency_policy(DelinquencyPolicy {
+                grace_period_sec: 3600,
+                notify_debtors: false,
+            })
+            .build();
+
+        let _ = subject.begin_scan(now, None, &Logger::new(test_name));
+
+        assert_eq!(subject.ban_list_changes.borrow().warned, vec![]);
+        TestLogHandler::new().exists_no_log_containing(&format!(
+            "INFO: {test_name}: Wallet {} (balance: 1,111 gwei",
+            delinquent.wallet
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_bans_delinquents_immediately_without_a_grace_period() {
+        let now = SystemTime::now();
+        let delinquent = make_receivable_account(1111, true);
+        let receivable_dao = ReceivableDaoMock::new()
+            .new_delinquencies_result(vec![delinquent.clone()])
+            .paid_delinquencies_result(vec![]);
+        let warn_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let unwarn_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let ban_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let banned_dao = BannedDaoMock::new()
+            .warnings_result(HashMap::new())
+            .warn_parameters(&warn_parameters_arc)
+            .unwarn_parameters(&unwarn_parameters_arc)
+            .ban_parameters(&ban_parameters_arc)
+            .ban_result(true);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .banned_dao(banned_dao)
+            .build();
+
+        let _ = subject.begin_scan(now, None, &Logger::new("test"));
+
+        assert!(warn_parameters_arc.lock().unwrap().is_empty());
+        assert!(unwarn_parameters_arc.lock().unwrap().is_empty());
+        assert_eq!(
+            *ban_parameters_arc.lock().unwrap(),
+            vec![(delinquent.wallet.clone(), BanReason::Delinquency, None, now)]
+        );
+        assert_eq!(subject.ban_list_changes.borrow().warned, vec![]);
+    }
+
     #[test]
     fn receivable_scanner_aborts_scan_if_no_payments_were_supplied() {
         init_test_logging();
@@ -2790,6 +3249,9 @@ mod tests {
         let msg = ReceivedPayments {
             timestamp: SystemTime::now(),
             payments: vec![],
+            rechecked_payments_opt: None,
+            removed_payments: vec![],
+            catch_up_progress_opt: None,
             response_skeleton_opt: None,
         };
 
@@ -2819,18 +3281,25 @@ mod tests {
         let receivables = vec![
             BlockchainTransaction {
                 block_number: 4578910,
+                transaction_hash: make_tx_hash(1),
                 from: make_wallet("wallet_1"),
+                to: make_wallet("earning_wallet"),
                 wei_amount: 45_780,
             },
             BlockchainTransaction {
                 block_number: 4569898,
+                transaction_hash: make_tx_hash(2),
                 from: make_wallet("wallet_2"),
+                to: make_wallet("earning_wallet"),
                 wei_amount: 3_333_345,
             },
         ];
         let msg = ReceivedPayments {
             timestamp: now,
             payments: receivables.clone(),
+            rechecked_payments_opt: None,
+            removed_payments: vec![],
+            catch_up_progress_opt: None,
             response_skeleton_opt: None,
         };
         subject.mark_as_started(SystemTime::now());
@@ -2851,12 +3320,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn receivable_scanner_broadcasts_catch_up_progress_without_ending_the_scan() {
+        init_test_logging();
+        let test_name = "receivable_scanner_broadcasts_catch_up_progress_without_ending_the_scan";
+        let now = SystemTime::now();
+        let more_money_received_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao = ReceivableDaoMock::new()
+            .more_money_received_parameters(&more_money_received_params_arc);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .build();
+        let receivables = vec![BlockchainTransaction {
+            block_number: 4578910,
+            transaction_hash: make_tx_hash(3),
+            from: make_wallet("wallet_1"),
+            to: make_wallet("earning_wallet"),
+            wei_amount: 45_780,
+        }];
+        let msg = ReceivedPayments {
+            timestamp: now,
+            payments: receivables.clone(),
+            rechecked_payments_opt: None,
+            removed_payments: vec![],
+            catch_up_progress_opt: Some(ReceivablesCatchUpProgress {
+                scanned_through_block: 4_600_000,
+                target_block: 5_000_000,
+            }),
+            response_skeleton_opt: None,
+        };
+        let started_at = SystemTime::now();
+        subject.mark_as_started(started_at);
+
+        let message_opt = subject.finish_scan(msg, &Logger::new(test_name));
+
+        assert_eq!(
+            message_opt,
+            Some(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiReceivablesCatchUpBroadcast {
+                    scanned_through_block: 4_600_000,
+                    target_block: 5_000_000,
+                }
+                .tmb(0),
+            })
+        );
+        assert_eq!(subject.scan_started_at(), Some(started_at));
+        let more_money_received_params = more_money_received_params_arc.lock().unwrap();
+        assert_eq!(*more_money_received_params, vec![(now, receivables)]);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Catching up on receivables: scanned through block 4600000 of 5000000"
+        ));
+    }
+
+    #[test]
+    fn receivable_scanner_reverts_payments_removed_by_reorganization() {
+        init_test_logging();
+        let test_name = "receivable_scanner_reverts_payments_removed_by_reorganization";
+        let reverse_received_payments_params_arc = Arc::new(Mutex::new(vec![]));
+        let receivable_dao = ReceivableDaoMock::new()
+            .reverse_received_payments_parameters(&reverse_received_payments_params_arc);
+        let mut subject = ReceivableScannerBuilder::new()
+            .receivable_dao(receivable_dao)
+            .build();
+        let mut financial_statistics = subject.financial_statistics.borrow().clone();
+        financial_statistics.total_paid_receivable_wei = 1_000_000;
+        subject.financial_statistics.replace(financial_statistics);
+        let removed_payments = vec![
+            BlockchainTransaction {
+                block_number: 4578910,
+                transaction_hash: make_tx_hash(4),
+                from: make_wallet("wallet_1"),
+                to: make_wallet("earning_wallet"),
+                wei_amount: 45_780,
+            },
+            BlockchainTransaction {
+                block_number: 4578911,
+                transaction_hash: make_tx_hash(5),
+                from: make_wallet("wallet_2"),
+                to: make_wallet("earning_wallet"),
+                wei_amount: 4_220,
+            },
+        ];
+        let msg = ReceivedPayments {
+            timestamp: SystemTime::now(),
+            payments: vec![],
+            rechecked_payments_opt: None,
+            removed_payments: removed_payments.clone(),
+            catch_up_progress_opt: None,
+            response_skeleton_opt: None,
+        };
+        subject.mark_as_started(SystemTime::now());
+
+        let message_opt = subject.finish_scan(msg, &Logger::new(test_name));
+
+        assert_eq!(message_opt, None);
+        let reverse_received_payments_params = reverse_received_payments_params_arc.lock().unwrap();
+        assert_eq!(*reverse_received_payments_params, vec![removed_payments]);
+        let total_paid_receivable = subject
+            .financial_statistics
+            .borrow()
+            .total_paid_receivable_wei;
+        assert_eq!(total_paid_receivable, 1_000_000 - 45_780 - 4_220);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {test_name}: Reverting 2 received payment(s) totaling 50000 wei that disappeared \
+            in a blockchain reorganization: 45780 from 0x00000000000000000000000077616c6c65745f31 (block 4578910), \
+            4220 from 0x00000000000000000000000077616c6c65745f32 (block 4578911)"
+        ));
+    }
+
     #[test]
     fn signal_scanner_completion_and_log_if_timestamp_is_correct() {
         init_test_logging();
         let test_name = "signal_scanner_completion_and_log_if_timestamp_is_correct";
         let logger = Logger::new(test_name);
-        let mut subject = ScannerCommon::new(Rc::new(make_custom_payment_thresholds()));
+        let mut subject =
+            ScannerCommon::new(Rc::new(RefCell::new(make_custom_payment_thresholds())));
         let start = from_time_t(1_000_000_000);
         let end = start.checked_add(Duration::from_millis(145)).unwrap();
         subject.initiated_at_opt = Some(start);
@@ -2873,7 +3452,8 @@ mod tests {
         init_test_logging();
         let test_name = "signal_scanner_completion_and_log_if_timestamp_is_not_found";
         let logger = Logger::new(test_name);
-        let mut subject = ScannerCommon::new(Rc::new(make_custom_payment_thresholds()));
+        let mut subject =
+            ScannerCommon::new(Rc::new(RefCell::new(make_custom_payment_thresholds())));
         subject.initiated_at_opt = None;
 
         subject.signal_scanner_completion(ScanType::Receivables, SystemTime::now(), &logger);
@@ -2,8 +2,13 @@
 
 #![cfg(test)]
 
-use crate::accountant::database_access_objects::banned_dao::{BannedDao, BannedDaoFactory};
+use crate::accountant::database_access_objects::banned_dao::{
+    BanReason, BannedDao, BannedDaoFactory, BannedWallet,
+};
 use crate::accountant::database_access_objects::dao_utils::{from_time_t, to_time_t, CustomQuery};
+use crate::accountant::database_access_objects::ledger_dao::{
+    LedgerDao, LedgerDaoError, LedgerDaoFactory, LedgerEntry, PaymentOutcome,
+};
 use crate::accountant::database_access_objects::payable_dao::{
     PayableAccount, PayableDao, PayableDaoError, PayableDaoFactory,
 };
@@ -13,6 +18,9 @@ use crate::accountant::database_access_objects::pending_payable_dao::{
 use crate::accountant::database_access_objects::receivable_dao::{
     ReceivableAccount, ReceivableDao, ReceivableDaoError, ReceivableDaoFactory,
 };
+use crate::accountant::database_access_objects::service_usage_dao::{
+    ServiceUsage, ServiceUsageBucket, ServiceUsageDao, ServiceUsageDaoError, ServiceUsageDaoFactory,
+};
 use crate::accountant::scanners::scanners_utils::payable_scanner_utils::PayableThresholdsGauge;
 use crate::accountant::scanners::{PayableScanner, PendingPayableScanner, ReceivableScanner};
 use crate::accountant::{gwei_to_wei, Accountant, DEFAULT_PENDING_TOO_LONG_SEC};
@@ -22,7 +30,10 @@ use crate::blockchain::test_utils::make_tx_hash;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::db_config::config_dao::{ConfigDao, ConfigDaoFactory};
 use crate::db_config::mocks::ConfigDaoMock;
-use crate::sub_lib::accountant::{DaoFactories, FinancialStatistics};
+use crate::sub_lib::accountant::{
+    BanListChanges, DaoFactories, DelinquencyPolicy, EarningWallets, FinancialStatistics,
+    RetiredEarningWallet,
+};
 use crate::sub_lib::accountant::{MessageIdGenerator, PaymentThresholds};
 use crate::sub_lib::wallet::Wallet;
 use crate::test_utils::make_wallet;
@@ -34,6 +45,7 @@ use masq_lib::utils::plus;
 use rusqlite::{Connection, Row};
 use std::any::type_name;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -82,6 +94,8 @@ pub struct AccountantBuilder {
     receivable_dao_factory: Option<ReceivableDaoFactoryMock>,
     pending_payable_dao_factory: Option<PendingPayableDaoFactoryMock>,
     banned_dao_factory: Option<BannedDaoFactoryMock>,
+    ledger_dao_factory: Option<LedgerDaoFactoryMock>,
+    service_usage_dao_factory: Option<ServiceUsageDaoFactoryMock>,
     config_dao_factory: Option<Box<dyn ConfigDaoFactory>>,
 }
 
@@ -94,6 +108,8 @@ impl Default for AccountantBuilder {
             receivable_dao_factory: None,
             pending_payable_dao_factory: None,
             banned_dao_factory: None,
+            ledger_dao_factory: None,
+            service_usage_dao_factory: None,
             config_dao_factory: None,
         }
     }
@@ -290,16 +306,24 @@ impl AccountantBuilder {
         )
     }
 
-    //TODO this method seems to be never used?
+    // For the Accountant body; the ReceivableScanner gets a default one
     pub fn banned_dao(mut self, banned_dao: BannedDaoMock) -> Self {
-        match self.banned_dao_factory {
-            None => {
-                self.banned_dao_factory = Some(BannedDaoFactoryMock::new().make_result(banned_dao))
-            }
-            Some(banned_dao_factory) => {
-                self.banned_dao_factory = Some(banned_dao_factory.make_result(banned_dao))
-            }
-        }
+        self.banned_dao_factory = Some(
+            BannedDaoFactoryMock::new()
+                .make_result(banned_dao)
+                .make_result(BannedDaoMock::new()),
+        );
+        self
+    }
+
+    pub fn ledger_dao(mut self, ledger_dao: LedgerDaoMock) -> Self {
+        self.ledger_dao_factory = Some(LedgerDaoFactoryMock::new().make_result(ledger_dao));
+        self
+    }
+
+    pub fn service_usage_dao(mut self, service_usage_dao: ServiceUsageDaoMock) -> Self {
+        self.service_usage_dao_factory =
+            Some(ServiceUsageDaoFactoryMock::new().make_result(service_usage_dao));
         self
     }
 
@@ -327,9 +351,17 @@ impl AccountantBuilder {
                 .make_result(PendingPayableDaoMock::new())
                 .make_result(PendingPayableDaoMock::new()),
         );
-        let banned_dao_factory = self
-            .banned_dao_factory
-            .unwrap_or(BannedDaoFactoryMock::new().make_result(BannedDaoMock::new()));
+        let banned_dao_factory = self.banned_dao_factory.unwrap_or(
+            BannedDaoFactoryMock::new()
+                .make_result(BannedDaoMock::new())
+                .make_result(BannedDaoMock::new()),
+        );
+        let ledger_dao_factory = self
+            .ledger_dao_factory
+            .unwrap_or(LedgerDaoFactoryMock::new().make_result(LedgerDaoMock::new()));
+        let service_usage_dao_factory = self
+            .service_usage_dao_factory
+            .unwrap_or(ServiceUsageDaoFactoryMock::new().make_result(ServiceUsageDaoMock::new()));
         let mut accountant = Accountant::new(
             config,
             DaoFactories {
@@ -337,6 +369,8 @@ impl AccountantBuilder {
                 pending_payable_dao_factory: Box::new(pending_payable_dao_factory),
                 receivable_dao_factory: Box::new(receivable_dao_factory),
                 banned_dao_factory: Box::new(banned_dao_factory),
+                ledger_dao_factory: Box::new(ledger_dao_factory),
+                service_usage_dao_factory: Box::new(service_usage_dao_factory),
             },
         );
         if let Some(logger) = self.logger {
@@ -453,6 +487,74 @@ impl BannedDaoFactoryMock {
     }
 }
 
+pub struct LedgerDaoFactoryMock {
+    make_params: Arc<Mutex<Vec<()>>>,
+    make_results: RefCell<Vec<Box<dyn LedgerDao>>>,
+}
+
+impl LedgerDaoFactory for LedgerDaoFactoryMock {
+    fn make(&self) -> Box<dyn LedgerDao> {
+        if self.make_results.borrow().len() == 0 {
+            panic!("LedgerDao Missing.")
+        };
+        self.make_params.lock().unwrap().push(());
+        self.make_results.borrow_mut().remove(0)
+    }
+}
+
+impl LedgerDaoFactoryMock {
+    pub fn new() -> Self {
+        Self {
+            make_params: Arc::new(Mutex::new(vec![])),
+            make_results: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.make_params = params.clone();
+        self
+    }
+
+    pub fn make_result(self, result: LedgerDaoMock) -> Self {
+        self.make_results.borrow_mut().push(Box::new(result));
+        self
+    }
+}
+
+pub struct ServiceUsageDaoFactoryMock {
+    make_params: Arc<Mutex<Vec<()>>>,
+    make_results: RefCell<Vec<Box<dyn ServiceUsageDao>>>,
+}
+
+impl ServiceUsageDaoFactory for ServiceUsageDaoFactoryMock {
+    fn make(&self) -> Box<dyn ServiceUsageDao> {
+        if self.make_results.borrow().len() == 0 {
+            panic!("ServiceUsageDao Missing.")
+        };
+        self.make_params.lock().unwrap().push(());
+        self.make_results.borrow_mut().remove(0)
+    }
+}
+
+impl ServiceUsageDaoFactoryMock {
+    pub fn new() -> Self {
+        Self {
+            make_params: Arc::new(Mutex::new(vec![])),
+            make_results: RefCell::new(vec![]),
+        }
+    }
+
+    pub fn make_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.make_params = params.clone();
+        self
+    }
+
+    pub fn make_result(self, result: ServiceUsageDaoMock) -> Self {
+        self.make_results.borrow_mut().push(Box::new(result));
+        self
+    }
+}
+
 pub struct ConfigDaoFactoryMock {
     called: Rc<RefCell<bool>>,
     mock: RefCell<Option<ConfigDaoMock>>,
@@ -491,6 +593,8 @@ pub struct PayableDaoMock {
     transactions_confirmed_results: RefCell<Vec<Result<(), PayableDaoError>>>,
     custom_query_params: Arc<Mutex<Vec<CustomQuery<u64>>>>,
     custom_query_result: RefCell<Vec<Option<Vec<PayableAccount>>>>,
+    accounts_active_between_params: Arc<Mutex<Vec<(SystemTime, SystemTime)>>>,
+    accounts_active_between_results: RefCell<Vec<Vec<PayableAccount>>>,
     total_results: RefCell<Vec<u128>>,
 }
 
@@ -547,6 +651,14 @@ impl PayableDao for PayableDaoMock {
         self.custom_query_result.borrow_mut().remove(0)
     }
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<PayableAccount> {
+        self.accounts_active_between_params
+            .lock()
+            .unwrap()
+            .push((from, to));
+        self.accounts_active_between_results.borrow_mut().remove(0)
+    }
+
     fn total(&self) -> u128 {
         self.total_results.borrow_mut().remove(0)
     }
@@ -625,6 +737,21 @@ impl PayableDaoMock {
         self
     }
 
+    pub fn accounts_active_between_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(SystemTime, SystemTime)>>>,
+    ) -> Self {
+        self.accounts_active_between_params = params.clone();
+        self
+    }
+
+    pub fn accounts_active_between_result(self, result: Vec<PayableAccount>) -> Self {
+        self.accounts_active_between_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn total_result(self, result: u128) -> Self {
         self.total_results.borrow_mut().push(result);
         self
@@ -637,12 +764,23 @@ pub struct ReceivableDaoMock {
     more_money_receivable_results: RefCell<Vec<Result<(), ReceivableDaoError>>>,
     more_money_received_parameters: Arc<Mutex<Vec<(SystemTime, Vec<BlockchainTransaction>)>>>,
     more_money_received_results: RefCell<Vec<Result<(), PayableDaoError>>>,
+    reverse_received_payments_parameters: Arc<Mutex<Vec<Vec<BlockchainTransaction>>>>,
+    received_payments_in_range_parameters: Arc<Mutex<Vec<(u64, u64, Vec<Wallet>)>>>,
+    received_payments_in_range_results: RefCell<Vec<Vec<BlockchainTransaction>>>,
+    forget_received_payments_before_parameters: Arc<Mutex<Vec<u64>>>,
+    forget_received_payments_before_results: RefCell<Vec<Result<(), ReceivableDaoError>>>,
     new_delinquencies_parameters: Arc<Mutex<Vec<(SystemTime, PaymentThresholds)>>>,
     new_delinquencies_results: RefCell<Vec<Vec<ReceivableAccount>>>,
     paid_delinquencies_parameters: Arc<Mutex<Vec<PaymentThresholds>>>,
     paid_delinquencies_results: RefCell<Vec<Vec<ReceivableAccount>>>,
+    restart_aging_parameters: Arc<Mutex<Vec<(Wallet, SystemTime)>>>,
+    restart_aging_results: RefCell<Vec<Result<Option<ReceivableAccount>, ReceivableDaoError>>>,
+    write_off_parameters: Arc<Mutex<Vec<Wallet>>>,
+    write_off_results: RefCell<Vec<Result<Option<ReceivableAccount>, ReceivableDaoError>>>,
     custom_query_params: Arc<Mutex<Vec<CustomQuery<i64>>>>,
     custom_query_result: RefCell<Vec<Option<Vec<ReceivableAccount>>>>,
+    accounts_active_between_params: Arc<Mutex<Vec<(SystemTime, SystemTime)>>>,
+    accounts_active_between_results: RefCell<Vec<Vec<ReceivableAccount>>>,
     total_results: RefCell<Vec<i128>>,
 }
 
@@ -667,6 +805,38 @@ impl ReceivableDao for ReceivableDaoMock {
             .push((now, transactions));
     }
 
+    fn reverse_received_payments(&mut self, transactions: Vec<BlockchainTransaction>) {
+        self.reverse_received_payments_parameters
+            .lock()
+            .unwrap()
+            .push(transactions);
+    }
+
+    fn received_payments_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        recipients: &[Wallet],
+    ) -> Vec<BlockchainTransaction> {
+        self.received_payments_in_range_parameters
+            .lock()
+            .unwrap()
+            .push((from_block, to_block, recipients.to_vec()));
+        self.received_payments_in_range_results
+            .borrow_mut()
+            .remove(0)
+    }
+
+    fn forget_received_payments_before(&self, block_number: u64) -> Result<(), ReceivableDaoError> {
+        self.forget_received_payments_before_parameters
+            .lock()
+            .unwrap()
+            .push(block_number);
+        self.forget_received_payments_before_results
+            .borrow_mut()
+            .remove(0)
+    }
+
     fn new_delinquencies(
         &self,
         now: SystemTime,
@@ -687,11 +857,39 @@ impl ReceivableDao for ReceivableDaoMock {
         self.paid_delinquencies_results.borrow_mut().remove(0)
     }
 
+    fn restart_aging(
+        &self,
+        wallet: &Wallet,
+        now: SystemTime,
+    ) -> Result<Option<ReceivableAccount>, ReceivableDaoError> {
+        self.restart_aging_parameters
+            .lock()
+            .unwrap()
+            .push((wallet.clone(), now));
+        self.restart_aging_results.borrow_mut().remove(0)
+    }
+
+    fn write_off(&self, wallet: &Wallet) -> Result<Option<ReceivableAccount>, ReceivableDaoError> {
+        self.write_off_parameters
+            .lock()
+            .unwrap()
+            .push(wallet.clone());
+        self.write_off_results.borrow_mut().remove(0)
+    }
+
     fn custom_query(&self, custom_query: CustomQuery<i64>) -> Option<Vec<ReceivableAccount>> {
         self.custom_query_params.lock().unwrap().push(custom_query);
         self.custom_query_result.borrow_mut().remove(0)
     }
 
+    fn accounts_active_between(&self, from: SystemTime, to: SystemTime) -> Vec<ReceivableAccount> {
+        self.accounts_active_between_params
+            .lock()
+            .unwrap()
+            .push((from, to));
+        self.accounts_active_between_results.borrow_mut().remove(0)
+    }
+
     fn total(&self) -> i128 {
         self.total_results.borrow_mut().remove(0)
     }
@@ -733,6 +931,47 @@ impl ReceivableDaoMock {
         self
     }
 
+    pub fn reverse_received_payments_parameters(
+        mut self,
+        parameters: &Arc<Mutex<Vec<Vec<BlockchainTransaction>>>>,
+    ) -> Self {
+        self.reverse_received_payments_parameters = parameters.clone();
+        self
+    }
+
+    pub fn received_payments_in_range_parameters(
+        mut self,
+        parameters: &Arc<Mutex<Vec<(u64, u64, Vec<Wallet>)>>>,
+    ) -> Self {
+        self.received_payments_in_range_parameters = parameters.clone();
+        self
+    }
+
+    pub fn received_payments_in_range_result(self, result: Vec<BlockchainTransaction>) -> Self {
+        self.received_payments_in_range_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn forget_received_payments_before_parameters(
+        mut self,
+        parameters: &Arc<Mutex<Vec<u64>>>,
+    ) -> Self {
+        self.forget_received_payments_before_parameters = parameters.clone();
+        self
+    }
+
+    pub fn forget_received_payments_before_result(
+        self,
+        result: Result<(), ReceivableDaoError>,
+    ) -> Self {
+        self.forget_received_payments_before_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn new_delinquencies_parameters(
         mut self,
         parameters: &Arc<Mutex<Vec<(SystemTime, PaymentThresholds)>>>,
@@ -759,6 +998,35 @@ impl ReceivableDaoMock {
         self
     }
 
+    pub fn restart_aging_parameters(
+        mut self,
+        parameters: &Arc<Mutex<Vec<(Wallet, SystemTime)>>>,
+    ) -> Self {
+        self.restart_aging_parameters = parameters.clone();
+        self
+    }
+
+    pub fn restart_aging_result(
+        self,
+        result: Result<Option<ReceivableAccount>, ReceivableDaoError>,
+    ) -> Self {
+        self.restart_aging_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn write_off_parameters(mut self, parameters: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.write_off_parameters = parameters.clone();
+        self
+    }
+
+    pub fn write_off_result(
+        self,
+        result: Result<Option<ReceivableAccount>, ReceivableDaoError>,
+    ) -> Self {
+        self.write_off_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn custom_query_params(mut self, params: &Arc<Mutex<Vec<CustomQuery<i64>>>>) -> Self {
         self.custom_query_params = params.clone();
         self
@@ -769,6 +1037,21 @@ impl ReceivableDaoMock {
         self
     }
 
+    pub fn accounts_active_between_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(SystemTime, SystemTime)>>>,
+    ) -> Self {
+        self.accounts_active_between_params = params.clone();
+        self
+    }
+
+    pub fn accounts_active_between_result(self, result: Vec<ReceivableAccount>) -> Self {
+        self.accounts_active_between_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn total_result(self, result: i128) -> Self {
         self.total_results.borrow_mut().push(result);
         self
@@ -776,25 +1059,85 @@ impl ReceivableDaoMock {
 }
 
 #[derive(Debug, Default)]
+// Bans and unbans that aren't given prepared results report a change to the ban list
 pub struct BannedDaoMock {
     ban_list_parameters: Arc<Mutex<Vec<()>>>,
-    ban_list_results: RefCell<Vec<Vec<Wallet>>>,
-    ban_parameters: Arc<Mutex<Vec<Wallet>>>,
+    ban_list_results: RefCell<Vec<Vec<BannedWallet>>>,
+    ban_parameters: Arc<Mutex<Vec<(Wallet, BanReason, Option<String>, SystemTime)>>>,
+    ban_results: RefCell<Vec<bool>>,
     unban_parameters: Arc<Mutex<Vec<Wallet>>>,
+    unban_results: RefCell<Vec<bool>>,
+    warnings_parameters: Arc<Mutex<Vec<()>>>,
+    warnings_results: RefCell<Vec<HashMap<Wallet, SystemTime>>>,
+    warn_parameters: Arc<Mutex<Vec<(Wallet, SystemTime)>>>,
+    warn_results: RefCell<Vec<bool>>,
+    unwarn_parameters: Arc<Mutex<Vec<Wallet>>>,
+    unwarn_results: RefCell<Vec<bool>>,
 }
 
 impl BannedDao for BannedDaoMock {
-    fn ban_list(&self) -> Vec<Wallet> {
+    fn ban_list(&self) -> Vec<BannedWallet> {
         self.ban_list_parameters.lock().unwrap().push(());
         self.ban_list_results.borrow_mut().remove(0)
     }
 
-    fn ban(&self, wallet: &Wallet) {
-        self.ban_parameters.lock().unwrap().push(wallet.clone());
+    fn ban(
+        &self,
+        wallet: &Wallet,
+        reason: BanReason,
+        note_opt: Option<&str>,
+        timestamp: SystemTime,
+    ) -> bool {
+        self.ban_parameters.lock().unwrap().push((
+            wallet.clone(),
+            reason,
+            note_opt.map(|note| note.to_string()),
+            timestamp,
+        ));
+        if self.ban_results.borrow().is_empty() {
+            true
+        } else {
+            self.ban_results.borrow_mut().remove(0)
+        }
     }
 
-    fn unban(&self, wallet: &Wallet) {
+    fn unban(&self, wallet: &Wallet) -> bool {
         self.unban_parameters.lock().unwrap().push(wallet.clone());
+        if self.unban_results.borrow().is_empty() {
+            true
+        } else {
+            self.unban_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn warnings(&self) -> HashMap<Wallet, SystemTime> {
+        self.warnings_parameters.lock().unwrap().push(());
+        if self.warnings_results.borrow().is_empty() {
+            HashMap::new()
+        } else {
+            self.warnings_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn warn(&self, wallet: &Wallet, timestamp: SystemTime) -> bool {
+        self.warn_parameters
+            .lock()
+            .unwrap()
+            .push((wallet.clone(), timestamp));
+        if self.warn_results.borrow().is_empty() {
+            true
+        } else {
+            self.warn_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn unwarn(&self, wallet: &Wallet) -> bool {
+        self.unwarn_parameters.lock().unwrap().push(wallet.clone());
+        if self.unwarn_results.borrow().is_empty() {
+            true
+        } else {
+            self.unwarn_results.borrow_mut().remove(0)
+        }
     }
 }
 
@@ -804,24 +1147,239 @@ impl BannedDaoMock {
             ban_list_parameters: Arc::new(Mutex::new(vec![])),
             ban_list_results: RefCell::new(vec![]),
             ban_parameters: Arc::new(Mutex::new(vec![])),
+            ban_results: RefCell::new(vec![]),
             unban_parameters: Arc::new(Mutex::new(vec![])),
+            unban_results: RefCell::new(vec![]),
+            warnings_parameters: Arc::new(Mutex::new(vec![])),
+            warnings_results: RefCell::new(vec![]),
+            warn_parameters: Arc::new(Mutex::new(vec![])),
+            warn_results: RefCell::new(vec![]),
+            unwarn_parameters: Arc::new(Mutex::new(vec![])),
+            unwarn_results: RefCell::new(vec![]),
         }
     }
 
-    pub fn ban_list_result(self, result: Vec<Wallet>) -> Self {
+    pub fn ban_list_result(self, result: Vec<BannedWallet>) -> Self {
         self.ban_list_results.borrow_mut().push(result);
         self
     }
 
-    pub fn ban_parameters(mut self, parameters: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+    pub fn ban_parameters(
+        mut self,
+        parameters: &Arc<Mutex<Vec<(Wallet, BanReason, Option<String>, SystemTime)>>>,
+    ) -> Self {
         self.ban_parameters = parameters.clone();
         self
     }
 
+    pub fn ban_result(self, result: bool) -> Self {
+        self.ban_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn unban_parameters(mut self, parameters: &Arc<Mutex<Vec<Wallet>>>) -> Self {
         self.unban_parameters = parameters.clone();
         self
     }
+
+    pub fn unban_result(self, result: bool) -> Self {
+        self.unban_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn warnings_parameters(mut self, parameters: &Arc<Mutex<Vec<()>>>) -> Self {
+        self.warnings_parameters = parameters.clone();
+        self
+    }
+
+    pub fn warnings_result(self, result: HashMap<Wallet, SystemTime>) -> Self {
+        self.warnings_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn warn_parameters(mut self, parameters: &Arc<Mutex<Vec<(Wallet, SystemTime)>>>) -> Self {
+        self.warn_parameters = parameters.clone();
+        self
+    }
+
+    pub fn warn_result(self, result: bool) -> Self {
+        self.warn_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn unwarn_parameters(mut self, parameters: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.unwarn_parameters = parameters.clone();
+        self
+    }
+
+    pub fn unwarn_result(self, result: bool) -> Self {
+        self.unwarn_results.borrow_mut().push(result);
+        self
+    }
+}
+
+// Bookkeeping into the ledger accompanies nearly every Accountant activity; tests that don't
+// prepare results for it get successful recordings
+#[derive(Default)]
+pub struct LedgerDaoMock {
+    record_params: Arc<Mutex<Vec<Vec<LedgerEntry>>>>,
+    record_results: RefCell<Vec<Result<(), LedgerDaoError>>>,
+    record_payment_outcome_params: Arc<Mutex<Vec<(H256, PaymentOutcome, Option<u64>, SystemTime)>>>,
+    record_payment_outcome_results: RefCell<Vec<Result<(), LedgerDaoError>>>,
+    history_params: Arc<Mutex<Vec<(Option<Wallet>, SystemTime, SystemTime, u64, u64)>>>,
+    history_results: RefCell<Vec<Vec<LedgerEntry>>>,
+}
+
+impl LedgerDao for LedgerDaoMock {
+    fn record(&mut self, entries: &[LedgerEntry]) -> Result<(), LedgerDaoError> {
+        self.record_params.lock().unwrap().push(entries.to_vec());
+        if self.record_results.borrow().is_empty() {
+            Ok(())
+        } else {
+            self.record_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn record_payment_outcome(
+        &self,
+        hash: H256,
+        outcome: PaymentOutcome,
+        block_number_opt: Option<u64>,
+        timestamp: SystemTime,
+    ) -> Result<(), LedgerDaoError> {
+        self.record_payment_outcome_params.lock().unwrap().push((
+            hash,
+            outcome,
+            block_number_opt,
+            timestamp,
+        ));
+        if self.record_payment_outcome_results.borrow().is_empty() {
+            Ok(())
+        } else {
+            self.record_payment_outcome_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn history(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+        offset: u64,
+        limit: u64,
+    ) -> Vec<LedgerEntry> {
+        self.history_params
+            .lock()
+            .unwrap()
+            .push((wallet_opt.cloned(), from, to, offset, limit));
+        self.history_results.borrow_mut().remove(0)
+    }
+}
+
+impl LedgerDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_params(mut self, params: &Arc<Mutex<Vec<Vec<LedgerEntry>>>>) -> Self {
+        self.record_params = params.clone();
+        self
+    }
+
+    pub fn record_result(self, result: Result<(), LedgerDaoError>) -> Self {
+        self.record_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn record_payment_outcome_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(H256, PaymentOutcome, Option<u64>, SystemTime)>>>,
+    ) -> Self {
+        self.record_payment_outcome_params = params.clone();
+        self
+    }
+
+    pub fn record_payment_outcome_result(self, result: Result<(), LedgerDaoError>) -> Self {
+        self.record_payment_outcome_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn history_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(Option<Wallet>, SystemTime, SystemTime, u64, u64)>>>,
+    ) -> Self {
+        self.history_params = params.clone();
+        self
+    }
+
+    pub fn history_result(self, result: Vec<LedgerEntry>) -> Self {
+        self.history_results.borrow_mut().push(result);
+        self
+    }
+}
+
+// Like the ledger, usage counters are kept for every service charge; unprepared recordings succeed
+#[derive(Default)]
+pub struct ServiceUsageDaoMock {
+    record_params: Arc<Mutex<Vec<ServiceUsage>>>,
+    record_results: RefCell<Vec<Result<(), ServiceUsageDaoError>>>,
+    usage_params: Arc<Mutex<Vec<(Option<Wallet>, SystemTime, SystemTime)>>>,
+    usage_results: RefCell<Vec<Vec<ServiceUsageBucket>>>,
+}
+
+impl ServiceUsageDao for ServiceUsageDaoMock {
+    fn record(&self, usage: &ServiceUsage) -> Result<(), ServiceUsageDaoError> {
+        self.record_params.lock().unwrap().push(usage.clone());
+        if self.record_results.borrow().is_empty() {
+            Ok(())
+        } else {
+            self.record_results.borrow_mut().remove(0)
+        }
+    }
+
+    fn usage(
+        &self,
+        wallet_opt: Option<&Wallet>,
+        from: SystemTime,
+        to: SystemTime,
+    ) -> Vec<ServiceUsageBucket> {
+        self.usage_params
+            .lock()
+            .unwrap()
+            .push((wallet_opt.cloned(), from, to));
+        self.usage_results.borrow_mut().remove(0)
+    }
+}
+
+impl ServiceUsageDaoMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_params(mut self, params: &Arc<Mutex<Vec<ServiceUsage>>>) -> Self {
+        self.record_params = params.clone();
+        self
+    }
+
+    pub fn record_result(self, result: Result<(), ServiceUsageDaoError>) -> Self {
+        self.record_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn usage_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(Option<Wallet>, SystemTime, SystemTime)>>>,
+    ) -> Self {
+        self.usage_params = params.clone();
+        self
+    }
+
+    pub fn usage_result(self, result: Vec<ServiceUsageBucket>) -> Self {
+        self.usage_results.borrow_mut().push(result);
+        self
+    }
 }
 
 pub fn bc_from_earning_wallet(earning_wallet: Wallet) -> BootstrapperConfig {
@@ -1073,7 +1631,7 @@ impl PayableScannerBuilder {
         PayableScanner::new(
             Box::new(self.payable_dao),
             Box::new(self.pending_payable_dao),
-            Rc::new(self.payment_thresholds),
+            Rc::new(RefCell::new(self.payment_thresholds)),
         )
     }
 }
@@ -1116,7 +1674,7 @@ impl PendingPayableScannerBuilder {
         PendingPayableScanner::new(
             Box::new(self.payable_dao),
             Box::new(self.pending_payable_dao),
-            Rc::new(self.payment_thresholds),
+            Rc::new(RefCell::new(self.payment_thresholds)),
             self.when_pending_too_long_sec,
             Rc::new(RefCell::new(self.financial_statistics)),
         )
@@ -1128,7 +1686,9 @@ pub struct ReceivableScannerBuilder {
     banned_dao: BannedDaoMock,
     payment_thresholds: PaymentThresholds,
     earning_wallet: Wallet,
+    retired_earning_wallets: Vec<RetiredEarningWallet>,
     financial_statistics: FinancialStatistics,
+    delinquency_policy: DelinquencyPolicy,
 }
 
 impl ReceivableScannerBuilder {
@@ -1138,7 +1698,9 @@ impl ReceivableScannerBuilder {
             banned_dao: BannedDaoMock::new(),
             payment_thresholds: PaymentThresholds::default(),
             earning_wallet: make_wallet("earning_default"),
+            retired_earning_wallets: vec![],
             financial_statistics: FinancialStatistics::default(),
+            delinquency_policy: DelinquencyPolicy::default(),
         }
     }
 
@@ -1162,13 +1724,31 @@ impl ReceivableScannerBuilder {
         self
     }
 
+    pub fn retired_earning_wallets(
+        mut self,
+        retired_earning_wallets: Vec<RetiredEarningWallet>,
+    ) -> Self {
+        self.retired_earning_wallets = retired_earning_wallets;
+        self
+    }
+
+    pub fn delinquency_policy(mut self, delinquency_policy: DelinquencyPolicy) -> Self {
+        self.delinquency_policy = delinquency_policy;
+        self
+    }
+
     pub fn build(self) -> ReceivableScanner {
         ReceivableScanner::new(
             Box::new(self.receivable_dao),
             Box::new(self.banned_dao),
-            Rc::new(self.payment_thresholds),
-            Rc::new(self.earning_wallet),
+            Rc::new(RefCell::new(self.payment_thresholds)),
+            Rc::new(RefCell::new(EarningWallets::new(
+                self.earning_wallet,
+                self.retired_earning_wallets,
+            ))),
             Rc::new(RefCell::new(self.financial_statistics)),
+            Rc::new(RefCell::new(BanListChanges::default())),
+            self.delinquency_policy,
         )
     }
 }
@@ -1,9 +1,14 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::daemon::restart_policy::{
+    DEFAULT_MAX_RESTARTS, DEFAULT_RESTART_BACKOFF_SECS, DEFAULT_RESTART_WINDOW_SECS,
+    MAX_RESTART_BACKOFF_SECS,
+};
 use clap::{crate_description, crate_version, App, AppSettings, Arg};
 use indoc::indoc;
 use lazy_static::lazy_static;
 use masq_lib::constants::{HIGHEST_USABLE_PORT, LOWEST_USABLE_INSECURE_PORT};
+use masq_lib::shared_schema::common_validators::validate_u64;
 use masq_lib::shared_schema::{
     chain_arg, data_directory_arg, db_password_arg, real_user_arg, shared_app, ui_port_arg,
     DB_PASSWORD_HELP,
@@ -31,6 +36,26 @@ pub fn app_daemon() -> App<'static, 'static> {
                 .help("Directs MASQ to start the Daemon that controls the Node, rather than the Node itself"),
         )
         .arg(ui_port_arg(&DAEMON_UI_PORT_HELP))
+        .arg(daemon_u64_arg("max-restarts", "MAX-RESTARTS", &MAX_RESTARTS_HELP))
+        .arg(daemon_u64_arg(
+            "restart-window",
+            "RESTART-WINDOW",
+            &RESTART_WINDOW_HELP,
+        ))
+        .arg(daemon_u64_arg(
+            "restart-backoff",
+            "RESTART-BACKOFF",
+            &RESTART_BACKOFF_HELP,
+        ))
+}
+
+fn daemon_u64_arg<'a>(name: &'a str, value_name: &'a str, help: &'a str) -> Arg<'a, 'a> {
+    Arg::with_name(name)
+        .long(name)
+        .value_name(value_name)
+        .takes_value(true)
+        .validator(validate_u64)
+        .help(help)
 }
 
 pub fn app_node() -> App<'static, 'static> {
@@ -59,6 +84,23 @@ lazy_static! {
         Best to accept the default unless you know what you're doing. Must be between {} and {}.",
         LOWEST_USABLE_INSECURE_PORT, HIGHEST_USABLE_PORT
     );
+    static ref MAX_RESTARTS_HELP: String = format!(
+        "The number of times the Daemon will restart a crashed Node within one restart window before \
+        it gives up and leaves the Node down. Zero turns automatic restarts off. Nodes that were shut \
+        down deliberately or that rejected their configuration are never restarted. Default is {}.",
+        DEFAULT_MAX_RESTARTS
+    );
+    static ref RESTART_WINDOW_HELP: String = format!(
+        "The number of seconds over which the Daemon counts restarts of a crashed Node against \
+        --max-restarts. Default is {}.",
+        DEFAULT_RESTART_WINDOW_SECS
+    );
+    static ref RESTART_BACKOFF_HELP: String = format!(
+        "The number of seconds the Daemon waits before restarting a crashed Node for the first time \
+        within a restart window. The wait doubles with every further restart in the window, up to {} \
+        seconds. Default is {}.",
+        MAX_RESTART_BACKOFF_SECS, DEFAULT_RESTART_BACKOFF_SECS
+    );
 }
 
 const DUMP_CONFIG_HELP: &str =
@@ -141,5 +183,22 @@ mod tests {
                 LOWEST_USABLE_INSECURE_PORT, HIGHEST_USABLE_PORT
             )
         );
+        assert_eq!(
+            MAX_RESTARTS_HELP.as_str(),
+            "The number of times the Daemon will restart a crashed Node within one restart window before \
+             it gives up and leaves the Node down. Zero turns automatic restarts off. Nodes that were shut \
+             down deliberately or that rejected their configuration are never restarted. Default is 5."
+        );
+        assert_eq!(
+            RESTART_WINDOW_HELP.as_str(),
+            "The number of seconds over which the Daemon counts restarts of a crashed Node against \
+             --max-restarts. Default is 3600."
+        );
+        assert_eq!(
+            RESTART_BACKOFF_HELP.as_str(),
+            "The number of seconds the Daemon waits before restarting a crashed Node for the first time \
+             within a restart window. The wait doubles with every further restart in the window, up to 300 \
+             seconds. Default is 1."
+        );
     }
 }
@@ -1,24 +1,36 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::accountant::database_access_objects::ledger_dao::{
+    LedgerDao, LedgerEntry, LedgerEntryKind, PaymentOutcome,
+};
+use crate::accountant::database_access_objects::payable_dao::PayableAccount;
 use crate::accountant::{
-    ConsumingWalletBalancesAndQualifiedPayables, ReceivedPayments, ResponseSkeleton, ScanError,
-    SentPayables, SkeletonOptHolder,
+    ConsumingWalletBalancesAndQualifiedPayables, ReceivablesCatchUpProgress, ReceivedPayments,
+    RecheckedPayments, ResponseSkeleton, ScanError, SentPayables, SkeletonOptHolder,
 };
 use crate::accountant::{ReportTransactionReceipts, RequestTransactionReceipts};
 use crate::blockchain::blockchain_interface::{
     BlockchainError, BlockchainInterface, BlockchainInterfaceClandestine,
     BlockchainInterfaceNonClandestine, PayableTransactionError, ProcessedPayableFallible,
 };
+use crate::blockchain::dev_chain::{DevChainServer, EMBEDDED_DEV_CHAIN_URL};
+use crate::blockchain::external_signer::ExternalSignerReal;
+use crate::blockchain::wallet_discovery::{
+    validate_discovery_range, WalletDiscoveryLauncher, WalletDiscoveryScan,
+    DEFAULT_DISCOVERY_ACCOUNT_COUNT, DEFAULT_DISCOVERY_INDEX_COUNT,
+};
 use crate::database::db_initializer::{DbInitializationConfig, DbInitializer, DbInitializerReal};
 use crate::db_config::config_dao::ConfigDaoReal;
 use crate::db_config::persistent_configuration::{
     PersistentConfiguration, PersistentConfigurationReal,
 };
+use crate::node_configurator::configurator::Configurator;
 use crate::sub_lib::blockchain_bridge::ConsumingWalletBalances;
 use crate::sub_lib::blockchain_bridge::{
-    BlockchainBridgeSubs, ReportAccountsPayable, RequestBalancesToPayPayables,
+    BlockchainBridgeSubs, LowBalanceThresholds, ReportAccountsPayable, RequestBalancesToPayPayables,
 };
 use crate::sub_lib::peer_actors::BindMessage;
+use crate::sub_lib::proxy_server::ConsumingServicesStatus;
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
 use crate::sub_lib::utils::{db_connection_launch_panic, handle_ui_crash_request};
 use crate::sub_lib::wallet::Wallet;
@@ -29,16 +41,25 @@ use actix::Message;
 use actix::{Addr, Recipient};
 use itertools::Itertools;
 use masq_lib::blockchains::chains::Chain;
+use masq_lib::constants::{WALLET_BALANCES_ERROR, WALLET_DISCOVERY_ERROR, WEIS_IN_GWEI};
 use masq_lib::logger::Logger;
-use masq_lib::messages::ScanType;
-use masq_lib::ui_gateway::NodeFromUiMessage;
+use masq_lib::messages::{
+    FromMessageBody, ScanType, ToMessageBody, UiDiscoverWalletsRequest, UiWalletBalanceBroadcast,
+    UiWalletBalancesRequest, UiWalletBalancesResponse,
+};
+use masq_lib::ui_gateway::{
+    MessageBody, MessagePath, MessageTarget, NodeFromUiMessage, NodeToUiMessage,
+};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 use web3::transports::Http;
-use web3::types::{TransactionReceipt, H256};
+use web3::types::{TransactionReceipt, H256, U256};
 use web3::Transport;
 
 pub const CRASH_KEY: &str = "BLOCKCHAINBRIDGE";
+pub const DEFAULT_MAX_BLOCK_COUNT: u64 = 100_000;
+pub const DEFAULT_BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
 
 pub struct BlockchainBridge<T: Transport = Http> {
     consuming_wallet_opt: Option<Wallet>,
@@ -51,7 +72,31 @@ pub struct BlockchainBridge<T: Transport = Http> {
     received_payments_subs_opt: Option<Recipient<ReceivedPayments>>,
     scan_error_subs_opt: Option<Recipient<ScanError>>,
     crashable: bool,
+    max_block_count: u64,
+    recheck_block_count: u64,
     pending_payable_confirmation: TransactionConfirmationTools,
+    balance_monitor: BalanceMonitorTools,
+    ledger_dao_opt: Option<Box<dyn LedgerDao>>,
+    wallet_discovery_launcher_opt: Option<Box<dyn WalletDiscoveryLauncher>>,
+    embedded_chain_opt: Option<DevChainServer>,
+}
+
+struct BalanceMonitorTools {
+    enabled: bool,
+    thresholds: LowBalanceThresholds,
+    check_interval: Duration,
+    consuming_services_suspended: bool,
+    consuming_services_status_sub_opt: Option<Recipient<ConsumingServicesStatus>>,
+    ui_message_sub_opt: Option<Recipient<NodeToUiMessage>>,
+}
+
+pub fn wei_to_saturated_gwei(wei: U256) -> u64 {
+    let gwei = wei / U256::from(WEIS_IN_GWEI as u64);
+    if gwei > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        gwei.as_u64()
+    }
 }
 
 struct TransactionConfirmationTools {
@@ -66,7 +111,11 @@ impl Actor for BlockchainBridge {
 impl Handler<BindMessage> for BlockchainBridge {
     type Result = ();
 
-    fn handle(&mut self, msg: BindMessage, _ctx: &mut Self::Context) -> Self::Result {
+    fn handle(&mut self, msg: BindMessage, ctx: &mut Self::Context) -> Self::Result {
+        self.balance_monitor.consuming_services_status_sub_opt =
+            Some(msg.peer_actors.proxy_server.consuming_services_status);
+        self.balance_monitor.ui_message_sub_opt =
+            Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
         self.set_consuming_wallet_subs_opt = Some(vec![
             msg.peer_actors.neighborhood.set_consuming_wallet_sub,
             msg.peer_actors.proxy_server.set_consuming_wallet_sub,
@@ -95,12 +144,27 @@ impl Handler<BindMessage> for BlockchainBridge {
                 "Received BindMessage; no consuming wallet address specified"
             ),
         }
+        if self.balance_monitor.enabled && self.consuming_wallet_opt.is_some() {
+            ctx.notify(CheckConsumingWalletBalances {})
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Message, Clone)]
+pub struct CheckConsumingWalletBalances {}
+
+impl Handler<CheckConsumingWalletBalances> for BlockchainBridge {
+    type Result = ();
+
+    fn handle(&mut self, msg: CheckConsumingWalletBalances, ctx: &mut Self::Context) {
+        self.handle_check_consuming_wallet_balances();
+        ctx.notify_later(msg, self.balance_monitor.check_interval);
     }
 }
 
 #[derive(Debug, PartialEq, Eq, Message, Clone)]
 pub struct RetrieveTransactions {
-    pub recipient: Wallet,
+    pub recipients: Vec<Wallet>,
     pub response_skeleton_opt: Option<ResponseSkeleton>,
 }
 
@@ -184,7 +248,13 @@ impl Handler<NodeFromUiMessage> for BlockchainBridge {
     type Result = ();
 
     fn handle(&mut self, msg: NodeFromUiMessage, _ctx: &mut Self::Context) -> Self::Result {
-        handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
+        if let Ok((_, context_id)) = UiWalletBalancesRequest::fmb(msg.body.clone()) {
+            self.handle_wallet_balances_request(msg.client_id, context_id)
+        } else if let Ok((body, context_id)) = UiDiscoverWalletsRequest::fmb(msg.body.clone()) {
+            self.handle_discover_wallets_request(msg.client_id, context_id, body)
+        } else {
+            handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
+        }
     }
 }
 
@@ -205,16 +275,63 @@ impl BlockchainBridge {
             received_payments_subs_opt: None,
             scan_error_subs_opt: None,
             crashable,
+            max_block_count: DEFAULT_MAX_BLOCK_COUNT,
+            recheck_block_count: 0,
             logger: Logger::new("BlockchainBridge"),
             pending_payable_confirmation: TransactionConfirmationTools {
                 new_pp_fingerprints_sub_opt: None,
                 report_transaction_receipts_sub_opt: None,
             },
+            balance_monitor: BalanceMonitorTools {
+                enabled: false,
+                thresholds: LowBalanceThresholds::default(),
+                check_interval: DEFAULT_BALANCE_CHECK_INTERVAL,
+                consuming_services_suspended: false,
+                consuming_services_status_sub_opt: None,
+                ui_message_sub_opt: None,
+            },
+            ledger_dao_opt: None,
+            wallet_discovery_launcher_opt: None,
+            embedded_chain_opt: None,
         }
     }
 
+    // Without a real blockchain to ask, balances would always read zero; so monitoring is opt-in
+    pub fn with_balance_monitoring(mut self, thresholds: LowBalanceThresholds) -> Self {
+        self.balance_monitor.enabled = true;
+        self.balance_monitor.thresholds = thresholds;
+        self
+    }
+
+    pub fn with_ledger(mut self, ledger_dao: Box<dyn LedgerDao>) -> Self {
+        self.ledger_dao_opt = Some(ledger_dao);
+        self
+    }
+
+    // Scanning for wallets is pointless without a real blockchain to ask
+    pub fn with_wallet_discovery(mut self, launcher: Box<dyn WalletDiscoveryLauncher>) -> Self {
+        self.wallet_discovery_launcher_opt = Some(launcher);
+        self
+    }
+
+    // The embedded chain serves the Node for as long as the Bridge lives, and shuts down with it
+    pub fn with_embedded_chain(mut self, embedded_chain_opt: Option<DevChainServer>) -> Self {
+        self.embedded_chain_opt = embedded_chain_opt;
+        self
+    }
+
+    // Each receivable scan takes a second look at this many blocks behind its start block, so that
+    // payments credited there can be reversed if a reorganization deeper than the confirmation
+    // depth has dropped them
+    pub fn with_payment_recheck(mut self, block_count: u64) -> Self {
+        self.recheck_block_count = block_count;
+        self
+    }
+
     pub fn make_connections(
         blockchain_service_url: Option<String>,
+        external_signer_url: Option<String>,
+        confirmation_depth: u64,
         data_directory: PathBuf,
         chain: Chain,
     ) -> (
@@ -224,9 +341,22 @@ impl BlockchainBridge {
         let blockchain_interface: Box<dyn BlockchainInterface> = {
             match blockchain_service_url {
                 Some(url) => match Http::new(&url) {
-                    Ok((event_loop_handle, transport)) => Box::new(
-                        BlockchainInterfaceNonClandestine::new(transport, event_loop_handle, chain),
-                    ),
+                    Ok((event_loop_handle, transport)) => {
+                        let interface = BlockchainInterfaceNonClandestine::new(
+                            transport,
+                            event_loop_handle,
+                            chain,
+                        )
+                        .with_confirmation_depth(confirmation_depth);
+                        match external_signer_url {
+                            Some(signer_url) => Box::new(interface.with_external_signer(Box::new(
+                                ExternalSignerReal::new(&signer_url).unwrap_or_else(|e| {
+                                    panic!("Invalid external signer URL: {:?}", e)
+                                }),
+                            ))),
+                            None => Box::new(interface),
+                        }
+                    }
                     Err(e) => panic!("Invalid blockchain node URL: {:?}", e),
                 },
                 None => Box::new(BlockchainInterfaceClandestine::new(chain)),
@@ -246,6 +376,23 @@ impl BlockchainBridge {
         )
     }
 
+    // The wallet discovery launcher talks to the same blockchain service as the interface does, so
+    // the embedded chain must be started once, before either is made
+    pub fn resolve_embedded_chain(url: String, chain: Chain) -> (String, Option<DevChainServer>) {
+        if url != EMBEDDED_DEV_CHAIN_URL {
+            return (url, None);
+        }
+        if chain != Chain::Dev {
+            panic!(
+                "The embedded blockchain can only be used with --chain {}",
+                Chain::Dev.rec().literal_identifier
+            )
+        }
+        let server = DevChainServer::start(chain)
+            .unwrap_or_else(|e| panic!("Couldn't start the embedded blockchain: {:?}", e));
+        (server.url(), Some(server))
+    }
+
     pub fn make_subs_from(addr: &Addr<BlockchainBridge>) -> BlockchainBridgeSubs {
         BlockchainBridgeSubs {
             bind: recipient!(addr, BindMessage),
@@ -271,6 +418,24 @@ impl BlockchainBridge {
                 )
             }
         };
+        let consuming_wallet_balances = self.fetch_consuming_wallet_balances(consuming_wallet)?;
+        self.balances_and_payables_sub_opt
+            .as_ref()
+            .expect("Accountant is unbound")
+            .try_send(ConsumingWalletBalancesAndQualifiedPayables {
+                qualified_payables: msg.accounts,
+                consuming_wallet_balances,
+                response_skeleton_opt: msg.response_skeleton_opt,
+            })
+            .expect("Accountant is dead");
+
+        Ok(())
+    }
+
+    fn fetch_consuming_wallet_balances(
+        &self,
+        consuming_wallet: &Wallet,
+    ) -> Result<ConsumingWalletBalances, String> {
         //TODO rewrite this into a batch call as soon as GH-629 gets into master
         let gas_balance = match self.blockchain_interface.get_gas_balance(consuming_wallet) {
             Ok(gas_balance) => gas_balance,
@@ -293,23 +458,184 @@ impl BlockchainBridge {
                 ))
             }
         };
-        let consuming_wallet_balances = {
-            ConsumingWalletBalances {
-                gas_currency: gas_balance,
-                masq_tokens: token_balance,
+        Ok(ConsumingWalletBalances {
+            gas_currency: gas_balance,
+            masq_tokens: token_balance,
+        })
+    }
+
+    fn handle_check_consuming_wallet_balances(&mut self) {
+        let consuming_wallet = match self.consuming_wallet_opt.as_ref() {
+            Some(wallet) => wallet,
+            None => return,
+        };
+        let balances = match self.fetch_consuming_wallet_balances(consuming_wallet) {
+            Ok(balances) => balances,
+            Err(e) => {
+                warning!(self.logger, "Periodic balance check failed: {}", e);
+                return;
             }
         };
-        self.balances_and_payables_sub_opt
-            .as_ref()
-            .expect("Accountant is unbound")
-            .try_send(ConsumingWalletBalancesAndQualifiedPayables {
-                qualified_payables: msg.accounts,
-                consuming_wallet_balances,
-                response_skeleton_opt: msg.response_skeleton_opt,
-            })
-            .expect("Accountant is dead");
+        let (gas_balance_gwei, masq_balance_gwei) = (
+            wei_to_saturated_gwei(balances.gas_currency),
+            wei_to_saturated_gwei(balances.masq_tokens),
+        );
+        let thresholds = self.balance_monitor.thresholds;
+        let suspended =
+            gas_balance_gwei < thresholds.gas_gwei || masq_balance_gwei < thresholds.masq_gwei;
+        if suspended != self.balance_monitor.consuming_services_suspended {
+            if suspended {
+                warning!(
+                    self.logger,
+                    "Consuming wallet balances ({} gwei of gas, {} gwei of MASQ) are below the thresholds \
+                    ({} gwei, {} gwei); consuming services are suspended until the wallet is replenished",
+                    gas_balance_gwei,
+                    masq_balance_gwei,
+                    thresholds.gas_gwei,
+                    thresholds.masq_gwei
+                )
+            } else {
+                info!(
+                    self.logger,
+                    "Consuming wallet balances ({} gwei of gas, {} gwei of MASQ) are back above the thresholds; \
+                    consuming services are resumed",
+                    gas_balance_gwei,
+                    masq_balance_gwei
+                )
+            }
+            self.balance_monitor.consuming_services_suspended = suspended;
+            self.balance_monitor
+                .consuming_services_status_sub_opt
+                .as_ref()
+                .expect("ProxyServer is unbound")
+                .try_send(ConsumingServicesStatus { suspended })
+                .expect("ProxyServer is dead");
+        }
+        let body = UiWalletBalanceBroadcast {
+            gas_balance_gwei,
+            masq_balance_gwei,
+            gas_threshold_gwei: thresholds.gas_gwei,
+            masq_threshold_gwei: thresholds.masq_gwei,
+            consuming_services_suspended: suspended,
+        }
+        .tmb(0);
+        self.send_to_ui(MessageTarget::AllClients, body);
+    }
 
-        Ok(())
+    fn handle_wallet_balances_request(&self, client_id: u64, context_id: u64) {
+        let body = match self.consuming_wallet_opt.as_ref() {
+            None => MessageBody {
+                opcode: "walletBalances".to_string(),
+                path: MessagePath::Conversation(context_id),
+                payload: Err((
+                    WALLET_BALANCES_ERROR,
+                    "No consuming wallet is configured".to_string(),
+                )),
+            },
+            Some(consuming_wallet) => {
+                match self.fetch_consuming_wallet_balances(consuming_wallet) {
+                    Err(e) => MessageBody {
+                        opcode: "walletBalances".to_string(),
+                        path: MessagePath::Conversation(context_id),
+                        payload: Err((WALLET_BALANCES_ERROR, e)),
+                    },
+                    Ok(balances) => UiWalletBalancesResponse {
+                        consuming_wallet_address: consuming_wallet.to_string(),
+                        gas_balance_gwei: wei_to_saturated_gwei(balances.gas_currency),
+                        masq_balance_gwei: wei_to_saturated_gwei(balances.masq_tokens),
+                        gas_threshold_gwei: self.balance_monitor.thresholds.gas_gwei,
+                        masq_threshold_gwei: self.balance_monitor.thresholds.masq_gwei,
+                        consuming_services_suspended: self
+                            .balance_monitor
+                            .consuming_services_suspended,
+                    }
+                    .tmb(context_id),
+                }
+            }
+        };
+        self.send_to_ui(MessageTarget::ClientId(client_id), body)
+    }
+
+    fn handle_discover_wallets_request(
+        &self,
+        client_id: u64,
+        context_id: u64,
+        msg: UiDiscoverWalletsRequest,
+    ) {
+        match self.prepare_wallet_discovery(client_id, context_id, &msg) {
+            Ok((launcher, scan)) => {
+                info!(
+                    self.logger,
+                    "Scanning {} accounts of {} indexes each for wallets derived from the supplied seed",
+                    scan.account_count,
+                    scan.index_count
+                );
+                launcher.launch(
+                    scan,
+                    self.balance_monitor
+                        .ui_message_sub_opt
+                        .clone()
+                        .expect("UiGateway is unbound"),
+                )
+            }
+            Err((code, e_msg)) => {
+                warning!(self.logger, "Wallet discovery failed: {}", e_msg);
+                let body = MessageBody {
+                    opcode: msg.opcode().to_string(),
+                    path: MessagePath::Conversation(context_id),
+                    payload: Err((code, e_msg)),
+                };
+                self.send_to_ui(MessageTarget::ClientId(client_id), body)
+            }
+        }
+    }
+
+    fn prepare_wallet_discovery(
+        &self,
+        client_id: u64,
+        context_id: u64,
+        msg: &UiDiscoverWalletsRequest,
+    ) -> Result<(&dyn WalletDiscoveryLauncher, WalletDiscoveryScan), (u64, String)> {
+        let seed = Configurator::make_seed(
+            &msg.seed_spec.mnemonic_passphrase_opt,
+            msg.seed_spec
+                .mnemonic_phrase_language_opt
+                .as_deref()
+                .unwrap_or("English"),
+            &msg.seed_spec.mnemonic_phrase,
+        )?;
+        let account_count = msg
+            .account_count_opt
+            .unwrap_or(DEFAULT_DISCOVERY_ACCOUNT_COUNT);
+        let index_count = msg.index_count_opt.unwrap_or(DEFAULT_DISCOVERY_INDEX_COUNT);
+        validate_discovery_range(account_count, index_count)
+            .map_err(|e| (WALLET_DISCOVERY_ERROR, e))?;
+        let launcher = self
+            .wallet_discovery_launcher_opt
+            .as_deref()
+            .ok_or_else(|| {
+                (
+                    WALLET_DISCOVERY_ERROR,
+                    "No blockchain service is configured to scan for wallets".to_string(),
+                )
+            })?;
+        let scan = WalletDiscoveryScan {
+            client_id,
+            context_id,
+            seed: seed.as_bytes().to_vec(),
+            account_count,
+            index_count,
+        };
+        Ok((launcher, scan))
+    }
+
+    fn send_to_ui(&self, target: MessageTarget, body: MessageBody) {
+        self.balance_monitor
+            .ui_message_sub_opt
+            .as_ref()
+            .expect("UiGateway is unbound")
+            .try_send(NodeToUiMessage { target, body })
+            .expect("UiGateway is dead")
     }
 
     fn handle_report_accounts_payable(&mut self, msg: ReportAccountsPayable) -> Result<(), String> {
@@ -334,40 +660,117 @@ impl BlockchainBridge {
     }
 
     fn handle_retrieve_transactions(&mut self, msg: RetrieveTransactions) -> Result<(), String> {
-        let start_block = match self.persistent_config.start_block() {
+        let mut start_block = match self.persistent_config.start_block() {
             Ok (sb) => sb,
             Err (e) => panic! ("Cannot retrieve start block from database; payments to you may not be processed: {:?}", e)
         };
-        let retrieved_transactions = self
-            .blockchain_interface
-            .retrieve_transactions(start_block, &msg.recipient);
-
-        match retrieved_transactions {
-            Ok(transactions) => {
-                if let Err(e) = self
-                    .persistent_config
-                    .set_start_block(transactions.new_start_block)
-                {
-                    panic! ("Cannot set start block in database; payments to you may not be processed: {:?}", e)
-                };
-                if transactions.transactions.is_empty() {
-                    debug!(self.logger, "No new receivable detected");
+        let mut rechecked_payments_opt = self.recheck_payments_behind(start_block, &msg.recipients);
+        loop {
+            let retrieved_transactions = self.blockchain_interface.retrieve_transactions(
+                start_block,
+                self.max_block_count,
+                &msg.recipients,
+            );
+            match retrieved_transactions {
+                Ok(transactions) => {
+                    if let Err(e) = self
+                        .persistent_config
+                        .set_start_block(transactions.new_start_block)
+                    {
+                        panic! ("Cannot set start block in database; payments to you may not be processed: {:?}", e)
+                    };
+                    if transactions.transactions.is_empty() {
+                        debug!(self.logger, "No new receivable detected");
+                    }
+                    let caught_up = transactions.new_start_block
+                        > transactions.last_confirmed_block
+                        || transactions.new_start_block == start_block;
+                    let catch_up_progress_opt = if caught_up {
+                        None
+                    } else {
+                        Some(ReceivablesCatchUpProgress {
+                            scanned_through_block: transactions.new_start_block - 1,
+                            target_block: transactions.last_confirmed_block,
+                        })
+                    };
+                    start_block = transactions.new_start_block;
+                    // a narrowed range is widened again page by page, so that one refused query
+                    // doesn't slow every later scan down
+                    self.max_block_count = self
+                        .max_block_count
+                        .saturating_mul(2)
+                        .min(DEFAULT_MAX_BLOCK_COUNT);
+                    self.received_payments_subs_opt
+                        .as_ref()
+                        .expect("Accountant is unbound")
+                        .try_send(ReceivedPayments {
+                            timestamp: SystemTime::now(),
+                            payments: transactions.transactions,
+                            rechecked_payments_opt: rechecked_payments_opt.take(),
+                            removed_payments: vec![],
+                            catch_up_progress_opt,
+                            response_skeleton_opt: if caught_up {
+                                msg.response_skeleton_opt
+                            } else {
+                                None
+                            },
+                        })
+                        .expect("Accountant is dead.");
+                    if caught_up {
+                        return Ok(());
+                    }
+                }
+                Err(BlockchainError::LogQueryTooBroad(e)) if self.max_block_count > 1 => {
+                    self.max_block_count /= 2;
+                    debug!(
+                        self.logger,
+                        "Log query starting at block {} was refused ({}); retrying with at most {} blocks",
+                        start_block,
+                        e,
+                        self.max_block_count
+                    );
+                }
+                Err(e) => {
+                    return Err(format!(
+                        "Tried to retrieve received payments but failed: {:?}",
+                        e
+                    ))
                 }
-                self.received_payments_subs_opt
-                    .as_ref()
-                    .expect("Accountant is unbound")
-                    .try_send(ReceivedPayments {
-                        timestamp: SystemTime::now(),
-                        payments: transactions.transactions,
-                        response_skeleton_opt: msg.response_skeleton_opt,
-                    })
-                    .expect("Accountant is dead.");
-                Ok(())
             }
-            Err(e) => Err(format!(
-                "Tried to retrieve received payments but failed: {:?}",
-                e
-            )),
+        }
+    }
+
+    fn recheck_payments_behind(
+        &self,
+        start_block: u64,
+        recipients: &[Wallet],
+    ) -> Option<RecheckedPayments> {
+        let from_block = start_block.saturating_sub(self.recheck_block_count);
+        if from_block == start_block {
+            return None;
+        }
+        match self.blockchain_interface.retrieve_transactions(
+            from_block,
+            start_block - from_block,
+            recipients,
+        ) {
+            Ok(retrieved) if retrieved.new_start_block > from_block => Some(RecheckedPayments {
+                from_block,
+                to_block: retrieved.new_start_block - 1,
+                recipients: recipients.to_vec(),
+                payments: retrieved.transactions,
+            }),
+            Ok(_) => None,
+            Err(e) => {
+                warning!(
+                    self.logger,
+                    "Could not recheck payments in blocks {} to {}: {:?}",
+                    from_block,
+                    start_block - 1,
+                    e
+                );
+                None
+            }
         }
     }
 
@@ -379,19 +782,27 @@ impl BlockchainBridge {
             Vec<Option<TransactionReceipt>>,
             Option<(BlockchainError, H256)>,
         ) = (vec![], None);
+        // Payables paid together through a disperse contract share one transaction; its receipt
+        // is fetched once and handed out to each of their fingerprints
+        let mut receipts_by_hash: HashMap<H256, Option<TransactionReceipt>> = HashMap::new();
         let (vector_of_results, error_opt) = msg.pending_payable.iter().fold(
             init,
             |(mut ok_receipts, err_opt), current_fingerprint| match err_opt {
-                None => match self
-                    .blockchain_interface
-                    .get_transaction_receipt(current_fingerprint.hash)
-                {
-                    Ok(receipt_opt) => {
-                        ok_receipts.push(receipt_opt);
-                        (ok_receipts, None)
+                None => {
+                    let hash = current_fingerprint.hash;
+                    let receipt_result = match receipts_by_hash.get(&hash) {
+                        Some(receipt_opt) => Ok(receipt_opt.clone()),
+                        None => self.blockchain_interface.get_transaction_receipt(hash),
+                    };
+                    match receipt_result {
+                        Ok(receipt_opt) => {
+                            receipts_by_hash.insert(hash, receipt_opt.clone());
+                            ok_receipts.push(receipt_opt);
+                            (ok_receipts, None)
+                        }
+                        Err(e) => (ok_receipts, Some((e, hash))),
                     }
-                    Err(e) => (ok_receipts, Some((e, current_fingerprint.hash))),
-                },
+                }
                 _ => (ok_receipts, err_opt),
             },
         );
@@ -442,7 +853,7 @@ impl BlockchainBridge {
     }
 
     fn process_payments(
-        &self,
+        &mut self,
         msg: &ReportAccountsPayable,
     ) -> Result<Vec<ProcessedPayableFallible>, PayableTransactionError> {
         let (consuming_wallet, gas_price) = match self.consuming_wallet_opt.as_ref() {
@@ -465,13 +876,73 @@ impl BlockchainBridge {
 
         let new_fingerprints_recipient = self.get_new_fingerprints_recipient();
 
-        self.blockchain_interface.send_payables_within_batch(
+        let result = self.blockchain_interface.send_payables_within_batch(
             consuming_wallet,
             gas_price,
             pending_nonce,
             new_fingerprints_recipient,
             &msg.accounts,
-        )
+        );
+        self.record_sent_payments_in_ledger(&result, &msg.accounts, gas_price, pending_nonce);
+        result
+    }
+
+    // Each payment in a batch takes the nonce following the one before
+    fn record_sent_payments_in_ledger(
+        &mut self,
+        result: &Result<Vec<ProcessedPayableFallible>, PayableTransactionError>,
+        accounts: &[PayableAccount],
+        gas_price: u64,
+        pending_nonce: U256,
+    ) {
+        let ledger_dao = match self.ledger_dao_opt.as_mut() {
+            Some(ledger_dao) => ledger_dao,
+            None => return,
+        };
+        let hashes_and_outcomes: Vec<(H256, PaymentOutcome)> = match result {
+            Ok(processed) => processed
+                .iter()
+                .map(|payable| match payable {
+                    ProcessedPayableFallible::Correct(pending_payable) => {
+                        (pending_payable.hash, PaymentOutcome::Submitted)
+                    }
+                    ProcessedPayableFallible::Failed(failure) => {
+                        (failure.hash, PaymentOutcome::Failed)
+                    }
+                })
+                .collect(),
+            Err(PayableTransactionError::Sending { hashes, .. }) => hashes
+                .iter()
+                .map(|hash| (*hash, PaymentOutcome::Failed))
+                .collect(),
+            Err(_) => return,
+        };
+        let now = SystemTime::now();
+        let entries = accounts
+            .iter()
+            .zip(hashes_and_outcomes.into_iter())
+            .enumerate()
+            .map(|(idx, (account, (hash, outcome)))| LedgerEntry {
+                transaction_hash_opt: Some(hash),
+                nonce_opt: Some((pending_nonce + U256::from(idx)).low_u64()),
+                gas_price_gwei_opt: Some(gas_price),
+                outcome_opt: Some(outcome),
+                ..LedgerEntry::new(
+                    now,
+                    account.wallet.clone(),
+                    LedgerEntryKind::PaymentSent,
+                    account.balance_wei,
+                )
+            })
+            .collect::<Vec<LedgerEntry>>();
+        if let Err(e) = ledger_dao.record(&entries) {
+            warning!(
+                self.logger,
+                "Failed to record {} sent payments in the ledger: {:?}",
+                entries.len(),
+                e
+            )
+        }
     }
 
     fn get_new_fingerprints_recipient(&self) -> &Recipient<PendingPayableFingerprintSeeds> {
@@ -493,14 +964,17 @@ mod tests {
     use super::*;
     use crate::accountant::database_access_objects::dao_utils::from_time_t;
     use crate::accountant::database_access_objects::payable_dao::{PayableAccount, PendingPayable};
-    use crate::accountant::test_utils::make_pending_payable_fingerprint;
+    use crate::accountant::test_utils::{make_pending_payable_fingerprint, LedgerDaoMock};
     use crate::accountant::ConsumingWalletBalancesAndQualifiedPayables;
     use crate::blockchain::bip32::Bip32ECKeyProvider;
     use crate::blockchain::blockchain_interface::ProcessedPayableFallible::Correct;
     use crate::blockchain::blockchain_interface::{
-        BlockchainError, BlockchainTransaction, RetrievedBlockchainTransactions,
+        BlockchainError, BlockchainTransaction, RetrievedBlockchainTransactions, RpcPayableFailure,
+    };
+    use crate::blockchain::test_utils::{
+        make_meaningless_phrase_words, make_tx_hash, BlockchainInterfaceMock,
+        WalletDiscoveryLauncherMock,
     };
-    use crate::blockchain::test_utils::{make_tx_hash, BlockchainInterfaceMock};
     use crate::db_config::persistent_configuration::PersistentConfigError;
     use crate::match_every_type_id;
     use crate::node_test_utils::check_timestamp;
@@ -517,21 +991,25 @@ mod tests {
     use actix::System;
     use ethereum_types::U64;
     use ethsign_crypto::Keccak256;
-    use masq_lib::constants::DEFAULT_CHAIN;
-    use masq_lib::messages::ScanType;
+    use masq_lib::constants::{DEFAULT_CHAIN, MNEMONIC_PHRASE_ERROR};
+    use masq_lib::messages::{ScanType, UiRecoverSeedSpec};
     use masq_lib::test_utils::logging::init_test_logging;
     use masq_lib::test_utils::logging::TestLogHandler;
     use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use masq_lib::utils::localhost;
     use rustc_hex::FromHex;
     use std::any::TypeId;
+    use std::net::{SocketAddr, TcpStream};
     use std::path::Path;
     use std::sync::{Arc, Mutex};
-    use std::time::{Duration, SystemTime};
+    use std::thread;
+    use std::time::{Duration, Instant, SystemTime};
     use web3::types::{TransactionReceipt, H160, H256, U256};
 
     #[test]
     fn constants_have_correct_values() {
         assert_eq!(CRASH_KEY, "BLOCKCHAINBRIDGE");
+        assert_eq!(DEFAULT_BALANCE_CHECK_INTERVAL, Duration::from_secs(300));
     }
 
     fn stub_bi() -> Box<dyn BlockchainInterface> {
@@ -598,6 +1076,23 @@ mod tests {
         let blockchain_service_url = Some("http://λ:8545".to_string());
         let _ = BlockchainBridge::make_connections(
             blockchain_service_url,
+            None,
+            12,
+            data_directory,
+            DEFAULT_CHAIN,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid external signer URL")]
+    fn invalid_external_signer_url_produces_panic() {
+        let data_directory = PathBuf::new(); //never reached
+        let blockchain_service_url = Some("http://127.0.0.1:8545".to_string());
+        let external_signer_url = Some("http://λ:8550".to_string());
+        let _ = BlockchainBridge::make_connections(
+            blockchain_service_url,
+            external_signer_url,
+            12,
             data_directory,
             DEFAULT_CHAIN,
         );
@@ -1032,7 +1527,7 @@ mod tests {
         let consuming_wallet = make_wallet("somewallet");
         let persistent_configuration_mock =
             PersistentConfigurationMock::new().gas_price_result(Ok(3u64));
-        let subject = BlockchainBridge::new(
+        let mut subject = BlockchainBridge::new(
             Box::new(blockchain_interface_mock),
             Box::new(persistent_configuration_mock),
             false,
@@ -1102,6 +1597,99 @@ mod tests {
         );
     }
 
+    #[test]
+    fn process_payments_records_sent_payments_in_ledger() {
+        let record_params_arc = Arc::new(Mutex::new(vec![]));
+        let wallet_1 = make_wallet("creditor_1");
+        let wallet_2 = make_wallet("creditor_2");
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .get_transaction_count_result(Ok(U256::from(6)))
+            .send_payables_within_batch_result(Ok(vec![
+                Correct(PendingPayable {
+                    recipient_wallet: wallet_1.clone(),
+                    hash: make_tx_hash(111),
+                }),
+                ProcessedPayableFallible::Failed(RpcPayableFailure {
+                    rpc_error: web3::Error::Unreachable,
+                    recipient_wallet: wallet_2.clone(),
+                    hash: make_tx_hash(222),
+                }),
+            ]));
+        let persistent_configuration_mock =
+            PersistentConfigurationMock::new().gas_price_result(Ok(77));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_configuration_mock),
+            false,
+            Some(make_wallet("consumer")),
+        )
+        .with_ledger(Box::new(
+            LedgerDaoMock::new().record_params(&record_params_arc),
+        ));
+        let (accountant, _, _) = make_recorder();
+        subject
+            .pending_payable_confirmation
+            .new_pp_fingerprints_sub_opt = Some(accountant.start().recipient());
+        let request = ReportAccountsPayable {
+            accounts: vec![
+                PayableAccount {
+                    wallet: wallet_1.clone(),
+                    balance_wei: 123_456,
+                    last_paid_timestamp: SystemTime::now(),
+                    pending_payable_opt: None,
+                },
+                PayableAccount {
+                    wallet: wallet_2.clone(),
+                    balance_wei: 654_321,
+                    last_paid_timestamp: SystemTime::now(),
+                    pending_payable_opt: None,
+                },
+            ],
+            response_skeleton_opt: None,
+        };
+        let before = SystemTime::now();
+
+        let _ = subject.process_payments(&request);
+
+        let after = SystemTime::now();
+        let mut record_params = record_params_arc.lock().unwrap();
+        let entries = record_params.remove(0);
+        assert!(record_params.is_empty());
+        let timestamp = entries[0].timestamp;
+        assert!(timestamp >= before && timestamp <= after);
+        let expected_entry = |wallet: &Wallet, amount, hash, nonce, outcome| LedgerEntry {
+            transaction_hash_opt: Some(hash),
+            nonce_opt: Some(nonce),
+            gas_price_gwei_opt: Some(77),
+            outcome_opt: Some(outcome),
+            ..LedgerEntry::new(
+                timestamp,
+                wallet.clone(),
+                LedgerEntryKind::PaymentSent,
+                amount,
+            )
+        };
+        assert_eq!(
+            entries,
+            vec![
+                expected_entry(
+                    &wallet_1,
+                    123_456,
+                    make_tx_hash(111),
+                    6,
+                    PaymentOutcome::Submitted
+                ),
+                expected_entry(
+                    &wallet_2,
+                    654_321,
+                    make_tx_hash(222),
+                    7,
+                    PaymentOutcome::Failed
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn handle_report_accounts_payable_manages_gas_price_error() {
         init_test_logging();
@@ -1234,6 +1822,72 @@ mod tests {
         assert_eq!(*get_transaction_receipt_params, vec![hash_1, hash_2])
     }
 
+    #[test]
+    fn blockchain_bridge_asks_once_for_the_receipt_of_a_transaction_shared_by_several_payables() {
+        let get_transaction_receipt_params_arc = Arc::new(Mutex::new(vec![]));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let shared_hash = make_tx_hash(0xd15e);
+        let other_hash = make_tx_hash(0xabc);
+        let make_fingerprint = |rowid: u64, hash: H256, amount: u128| PendingPayableFingerprint {
+            rowid,
+            timestamp: from_time_t(230_000_000),
+            hash,
+            attempt: 2,
+            amount,
+            process_error: None,
+        };
+        let fingerprint_1 = make_fingerprint(11, shared_hash, 1111);
+        let fingerprint_2 = make_fingerprint(12, other_hash, 2222);
+        let fingerprint_3 = make_fingerprint(13, shared_hash, 3333);
+        let mut receipt = TransactionReceipt::default();
+        receipt.status = Some(U64::from(1));
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .get_transaction_receipt_params(&get_transaction_receipt_params_arc)
+            .get_transaction_receipt_result(Ok(Some(receipt.clone())))
+            .get_transaction_receipt_result(Ok(None));
+        let subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(PersistentConfigurationMock::default()),
+            false,
+            None,
+        );
+        let addr = subject.start();
+        let subject_subs = BlockchainBridge::make_subs_from(&addr);
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        send_bind_message!(subject_subs, peer_actors);
+        let msg = RequestTransactionReceipts {
+            pending_payable: vec![
+                fingerprint_1.clone(),
+                fingerprint_2.clone(),
+                fingerprint_3.clone(),
+            ],
+            response_skeleton_opt: None,
+        };
+
+        let _ = addr.try_send(msg).unwrap();
+
+        let system = System::new("test");
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<ReportTransactionReceipts>(0),
+            &ReportTransactionReceipts {
+                fingerprints_with_receipts: vec![
+                    (Some(receipt.clone()), fingerprint_1),
+                    (None, fingerprint_2),
+                    (Some(receipt), fingerprint_3),
+                ],
+                response_skeleton_opt: None,
+            }
+        );
+        let get_transaction_receipt_params = get_transaction_receipt_params_arc.lock().unwrap();
+        assert_eq!(
+            *get_transaction_receipt_params,
+            vec![shared_hash, other_hash]
+        )
+    }
+
     #[test]
     fn blockchain_bridge_logs_error_from_retrieving_received_payments() {
         init_test_logging();
@@ -1254,7 +1908,7 @@ mod tests {
         );
         subject.scan_error_subs_opt = Some(scan_error_recipient);
         let msg = RetrieveTransactions {
-            recipient: make_wallet("blah"),
+            recipients: vec![make_wallet("blah")],
             response_skeleton_opt: None,
         };
         let subject_addr = subject.start();
@@ -1516,15 +2170,20 @@ mod tests {
         let amount2 = 55;
         let expected_transactions = RetrievedBlockchainTransactions {
             new_start_block: 1234,
+            last_confirmed_block: 1233,
             transactions: vec![
                 BlockchainTransaction {
                     block_number: 7,
+                    transaction_hash: make_tx_hash(1),
                     from: earning_wallet.clone(),
+                    to: make_wallet("earning_wallet"),
                     wei_amount: amount,
                 },
                 BlockchainTransaction {
                     block_number: 9,
+                    transaction_hash: make_tx_hash(2),
                     from: earning_wallet.clone(),
+                    to: make_wallet("earning_wallet"),
                     wei_amount: amount2,
                 },
             ],
@@ -1547,8 +2206,9 @@ mod tests {
         let subject_subs = BlockchainBridge::make_subs_from(&addr);
         let peer_actors = peer_actors_builder().accountant(accountant).build();
         send_bind_message!(subject_subs, peer_actors);
+        let retired_earning_wallet = make_wallet("retired");
         let retrieve_transactions = RetrieveTransactions {
-            recipient: earning_wallet.clone(),
+            recipients: vec![earning_wallet.clone(), retired_earning_wallet.clone()],
             response_skeleton_opt: Some(ResponseSkeleton {
                 client_id: 1234,
                 context_id: 4321,
@@ -1564,7 +2224,14 @@ mod tests {
         let set_start_block_params = set_start_block_params_arc.lock().unwrap();
         assert_eq!(*set_start_block_params, vec![1234]);
         let retrieve_transactions_params = retrieve_transactions_params_arc.lock().unwrap();
-        assert_eq!(*retrieve_transactions_params, vec![(6, earning_wallet)]);
+        assert_eq!(
+            *retrieve_transactions_params,
+            vec![(
+                6,
+                DEFAULT_MAX_BLOCK_COUNT,
+                vec![earning_wallet, retired_earning_wallet]
+            )]
+        );
         let accountant_received_payment = accountant_recording_arc.lock().unwrap();
         assert_eq!(accountant_received_payment.len(), 1);
         let received_payments = accountant_received_payment.get_record::<ReceivedPayments>(0);
@@ -1574,6 +2241,9 @@ mod tests {
             &ReceivedPayments {
                 timestamp: received_payments.timestamp,
                 payments: expected_transactions.transactions,
+                rechecked_payments_opt: None,
+                removed_payments: vec![],
+                catch_up_progress_opt: None,
                 response_skeleton_opt: Some(ResponseSkeleton {
                     client_id: 1234,
                     context_id: 4321
@@ -1588,6 +2258,7 @@ mod tests {
         let blockchain_interface_mock = BlockchainInterfaceMock::default()
             .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
                 new_start_block: 7,
+                last_confirmed_block: 6,
                 transactions: vec![],
             }));
         let set_start_block_params_arc = Arc::new(Mutex::new(vec![]));
@@ -1610,7 +2281,7 @@ mod tests {
         let peer_actors = peer_actors_builder().accountant(accountant).build();
         send_bind_message!(subject_subs, peer_actors);
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: Some(ResponseSkeleton {
                 client_id: 1234,
                 context_id: 4321,
@@ -1633,6 +2304,9 @@ mod tests {
             &ReceivedPayments {
                 timestamp: received_payments.timestamp,
                 payments: vec![],
+                rechecked_payments_opt: None,
+                removed_payments: vec![],
+                catch_up_progress_opt: None,
                 response_skeleton_opt: Some(ResponseSkeleton {
                     client_id: 1234,
                     context_id: 4321
@@ -1644,20 +2318,358 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(
-        expected = "Cannot retrieve start block from database; payments to you may not be processed: TransactionError"
-    )]
-    fn handle_retrieve_transactions_panics_if_start_block_cannot_be_read() {
+    fn handle_retrieve_transactions_pages_through_unconfirmed_backlog_and_persists_progress() {
+        let retrieve_transactions_params_arc = Arc::new(Mutex::new(vec![]));
+        let set_start_block_params_arc = Arc::new(Mutex::new(vec![]));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let earning_wallet = make_wallet("earning");
+        let first_page_transaction = BlockchainTransaction {
+            block_number: 50_000,
+            transaction_hash: make_tx_hash(3),
+            from: make_wallet("debtor"),
+            to: make_wallet("earning_wallet"),
+            wei_amount: 444,
+        };
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_params(&retrieve_transactions_params_arc)
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 100_006,
+                last_confirmed_block: 150_000,
+                transactions: vec![first_page_transaction.clone()],
+            }))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 150_001,
+                last_confirmed_block: 150_000,
+                transactions: vec![],
+            }));
         let persistent_config = PersistentConfigurationMock::new()
-            .start_block_result(Err(PersistentConfigError::TransactionError));
+            .start_block_result(Ok(6))
+            .set_start_block_params(&set_start_block_params_arc)
+            .set_start_block_result(Ok(()))
+            .set_start_block_result(Ok(()));
         let mut subject = BlockchainBridge::new(
-            Box::new(BlockchainInterfaceMock::default()),
+            Box::new(blockchain_interface_mock),
             Box::new(persistent_config),
             false,
-            None, //not needed in this test
+            None,
+        );
+        subject.received_payments_subs_opt = Some(accountant.start().recipient());
+        let response_skeleton = ResponseSkeleton {
+            client_id: 1234,
+            context_id: 4321,
+        };
+        let system = System::new(
+            "handle_retrieve_transactions_pages_through_unconfirmed_backlog_and_persists_progress",
+        );
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![earning_wallet.clone()],
+            response_skeleton_opt: Some(response_skeleton),
+        });
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, Ok(()));
+        let retrieve_transactions_params = retrieve_transactions_params_arc.lock().unwrap();
+        assert_eq!(
+            *retrieve_transactions_params,
+            vec![
+                (6, DEFAULT_MAX_BLOCK_COUNT, vec![earning_wallet.clone()]),
+                (100_006, DEFAULT_MAX_BLOCK_COUNT, vec![earning_wallet])
+            ]
+        );
+        let set_start_block_params = set_start_block_params_arc.lock().unwrap();
+        assert_eq!(*set_start_block_params, vec![100_006, 150_001]);
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 2);
+        let first_page = accountant_recording.get_record::<ReceivedPayments>(0);
+        assert_eq!(first_page.payments, vec![first_page_transaction]);
+        assert_eq!(
+            first_page.catch_up_progress_opt,
+            Some(ReceivablesCatchUpProgress {
+                scanned_through_block: 100_005,
+                target_block: 150_000
+            })
+        );
+        assert_eq!(first_page.response_skeleton_opt, None);
+        let last_page = accountant_recording.get_record::<ReceivedPayments>(1);
+        assert_eq!(last_page.payments, vec![]);
+        assert_eq!(last_page.catch_up_progress_opt, None);
+        assert_eq!(last_page.response_skeleton_opt, Some(response_skeleton));
+    }
+
+    #[test]
+    fn handle_retrieve_transactions_rechecks_blocks_behind_the_start_block_first() {
+        let retrieve_transactions_params_arc = Arc::new(Mutex::new(vec![]));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let earning_wallet = make_wallet("earning");
+        let rechecked_transaction = BlockchainTransaction {
+            block_number: 995,
+            transaction_hash: make_tx_hash(5),
+            from: make_wallet("debtor"),
+            to: earning_wallet.clone(),
+            wei_amount: 555,
+        };
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_params(&retrieve_transactions_params_arc)
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 1000,
+                last_confirmed_block: 1500,
+                transactions: vec![rechecked_transaction.clone()],
+            }))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 1501,
+                last_confirmed_block: 1500,
+                transactions: vec![],
+            }));
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(1000))
+            .set_start_block_result(Ok(()));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_config),
+            false,
+            None,
+        )
+        .with_payment_recheck(12);
+        subject.received_payments_subs_opt = Some(accountant.start().recipient());
+        let system = System::new(
+            "handle_retrieve_transactions_rechecks_blocks_behind_the_start_block_first",
+        );
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![earning_wallet.clone()],
+            response_skeleton_opt: None,
+        });
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, Ok(()));
+        let retrieve_transactions_params = retrieve_transactions_params_arc.lock().unwrap();
+        assert_eq!(
+            *retrieve_transactions_params,
+            vec![
+                (988, 12, vec![earning_wallet.clone()]),
+                (1000, DEFAULT_MAX_BLOCK_COUNT, vec![earning_wallet.clone()])
+            ]
+        );
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(accountant_recording.len(), 1);
+        let received_payments = accountant_recording.get_record::<ReceivedPayments>(0);
+        assert_eq!(
+            received_payments.rechecked_payments_opt,
+            Some(RecheckedPayments {
+                from_block: 988,
+                to_block: 999,
+                recipients: vec![earning_wallet],
+                payments: vec![rechecked_transaction]
+            })
+        );
+        assert_eq!(received_payments.removed_payments, vec![]);
+    }
+
+    #[test]
+    fn handle_retrieve_transactions_goes_on_without_recheck_if_it_fails() {
+        init_test_logging();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_result(Err(BlockchainError::QueryFailed("booga".to_string())))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 1501,
+                last_confirmed_block: 1500,
+                transactions: vec![],
+            }));
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(1000))
+            .set_start_block_result(Ok(()));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_config),
+            false,
+            None,
+        )
+        .with_payment_recheck(12);
+        subject.logger = Logger::new("recheck_fails");
+        subject.received_payments_subs_opt = Some(accountant.start().recipient());
+        let system =
+            System::new("handle_retrieve_transactions_goes_on_without_recheck_if_it_fails");
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![make_wallet("earning")],
+            response_skeleton_opt: None,
+        });
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, Ok(()));
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let received_payments = accountant_recording.get_record::<ReceivedPayments>(0);
+        assert_eq!(received_payments.rechecked_payments_opt, None);
+        TestLogHandler::new().exists_log_containing(
+            "WARN: recheck_fails: Could not recheck payments in blocks 988 to 999: QueryFailed(\"booga\")",
+        );
+    }
+
+    #[test]
+    fn handle_retrieve_transactions_shrinks_block_range_when_log_query_is_too_broad() {
+        init_test_logging();
+        let retrieve_transactions_params_arc = Arc::new(Mutex::new(vec![]));
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let earning_wallet = make_wallet("earning");
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_params(&retrieve_transactions_params_arc)
+            .retrieve_transactions_result(Err(BlockchainError::LogQueryTooBroad(
+                "query returned more than 10000 results".to_string(),
+            )))
+            .retrieve_transactions_result(Err(BlockchainError::LogQueryTooBroad(
+                "query returned more than 10000 results".to_string(),
+            )))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 25_006,
+                last_confirmed_block: 25_005,
+                transactions: vec![],
+            }));
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(6))
+            .set_start_block_result(Ok(()));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_config),
+            false,
+            None,
+        );
+        subject.logger = Logger::new("shrinks_block_range");
+        subject.received_payments_subs_opt = Some(accountant.start().recipient());
+        let system = System::new(
+            "handle_retrieve_transactions_shrinks_block_range_when_log_query_is_too_broad",
+        );
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![earning_wallet.clone()],
+            response_skeleton_opt: None,
+        });
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, Ok(()));
+        let retrieve_transactions_params = retrieve_transactions_params_arc.lock().unwrap();
+        assert_eq!(
+            *retrieve_transactions_params,
+            vec![
+                (6, DEFAULT_MAX_BLOCK_COUNT, vec![earning_wallet.clone()]),
+                (6, DEFAULT_MAX_BLOCK_COUNT / 2, vec![earning_wallet.clone()]),
+                (6, DEFAULT_MAX_BLOCK_COUNT / 4, vec![earning_wallet])
+            ]
+        );
+        assert_eq!(subject.max_block_count, DEFAULT_MAX_BLOCK_COUNT / 2);
+        assert_eq!(accountant_recording_arc.lock().unwrap().len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: shrinks_block_range: Log query starting at block 6 was refused (query returned \
+            more than 10000 results); retrying with at most {} blocks",
+            DEFAULT_MAX_BLOCK_COUNT / 4
+        ));
+    }
+
+    #[test]
+    fn handle_retrieve_transactions_widens_block_range_again_after_successful_pages() {
+        let retrieve_transactions_params_arc = Arc::new(Mutex::new(vec![]));
+        let (accountant, _, _) = make_recorder();
+        let earning_wallet = make_wallet("earning");
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_params(&retrieve_transactions_params_arc)
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 1006,
+                last_confirmed_block: 100_000,
+                transactions: vec![],
+            }))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 3006,
+                last_confirmed_block: 100_000,
+                transactions: vec![],
+            }))
+            .retrieve_transactions_result(Ok(RetrievedBlockchainTransactions {
+                new_start_block: 100_001,
+                last_confirmed_block: 100_000,
+                transactions: vec![],
+            }));
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Ok(6))
+            .set_start_block_result(Ok(()))
+            .set_start_block_result(Ok(()))
+            .set_start_block_result(Ok(()));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_config),
+            false,
+            None,
+        );
+        subject.max_block_count = 1000;
+        subject.received_payments_subs_opt = Some(accountant.start().recipient());
+        let system = System::new(
+            "handle_retrieve_transactions_widens_block_range_again_after_successful_pages",
+        );
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![earning_wallet.clone()],
+            response_skeleton_opt: None,
+        });
+
+        System::current().stop();
+        system.run();
+        assert_eq!(result, Ok(()));
+        let retrieve_transactions_params = retrieve_transactions_params_arc.lock().unwrap();
+        assert_eq!(
+            *retrieve_transactions_params,
+            vec![
+                (6, 1000, vec![earning_wallet.clone()]),
+                (1006, 2000, vec![earning_wallet.clone()]),
+                (3006, 4000, vec![earning_wallet])
+            ]
+        );
+        assert_eq!(subject.max_block_count, 8000);
+    }
+
+    #[test]
+    fn handle_retrieve_transactions_gives_up_when_single_block_log_query_is_too_broad() {
+        let blockchain_interface_mock = BlockchainInterfaceMock::default()
+            .retrieve_transactions_result(Err(BlockchainError::LogQueryTooBroad(
+                "response size exceeded".to_string(),
+            )));
+        let persistent_config = PersistentConfigurationMock::new().start_block_result(Ok(6));
+        let mut subject = BlockchainBridge::new(
+            Box::new(blockchain_interface_mock),
+            Box::new(persistent_config),
+            false,
+            None,
+        );
+        subject.max_block_count = 1;
+
+        let result = subject.handle_retrieve_transactions(RetrieveTransactions {
+            recipients: vec![make_wallet("earning")],
+            response_skeleton_opt: None,
+        });
+
+        assert_eq!(
+            result,
+            Err("Tried to retrieve received payments but failed: LogQueryTooBroad(\"response size exceeded\")".to_string())
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cannot retrieve start block from database; payments to you may not be processed: TransactionError"
+    )]
+    fn handle_retrieve_transactions_panics_if_start_block_cannot_be_read() {
+        let persistent_config = PersistentConfigurationMock::new()
+            .start_block_result(Err(PersistentConfigError::TransactionError));
+        let mut subject = BlockchainBridge::new(
+            Box::new(BlockchainInterfaceMock::default()),
+            Box::new(persistent_config),
+            false,
+            None, //not needed in this test
         );
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: None,
         };
 
@@ -1675,9 +2687,12 @@ mod tests {
         let blockchain_interface = BlockchainInterfaceMock::default().retrieve_transactions_result(
             Ok(RetrievedBlockchainTransactions {
                 new_start_block: 1234,
+                last_confirmed_block: 1233,
                 transactions: vec![BlockchainTransaction {
                     block_number: 1000,
+                    transaction_hash: make_tx_hash(4),
                     from: make_wallet("somewallet"),
+                    to: make_wallet("earning_wallet"),
                     wei_amount: 2345,
                 }],
             }),
@@ -1689,7 +2704,7 @@ mod tests {
             None, //not needed in this test
         );
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: None,
         };
 
@@ -1722,7 +2737,7 @@ mod tests {
         let system = System::new("test");
         subject.scan_error_subs_opt = Some(accountant.start().recipient());
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: Some(ResponseSkeleton {
                 client_id: 1234,
                 context_id: 4321,
@@ -1754,7 +2769,7 @@ mod tests {
         let system = System::new("test");
         subject.scan_error_subs_opt = Some(accountant.start().recipient());
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: None,
         };
 
@@ -1793,7 +2808,7 @@ mod tests {
         let system = System::new("test");
         subject.scan_error_subs_opt = Some(accountant.start().recipient());
         let retrieve_transactions = RetrieveTransactions {
-            recipient: make_wallet("somewallet"),
+            recipients: vec![make_wallet("somewallet")],
             response_skeleton_opt: Some(ResponseSkeleton {
                 client_id: 1234,
                 context_id: 4321,
@@ -1849,6 +2864,8 @@ mod tests {
         let act = |data_dir: &Path| {
             BlockchainBridge::make_connections(
                 Some("http://127.0.0.1".to_string()),
+                None,
+                12,
                 data_dir.to_path_buf(),
                 Chain::PolyMumbai,
             );
@@ -1856,4 +2873,508 @@ mod tests {
 
         assert_on_initialization_with_panic_on_migration(&data_dir, &act);
     }
+
+    #[test]
+    fn resolve_embedded_chain_leaves_ordinary_urls_alone() {
+        let (url, embedded_chain_opt) = BlockchainBridge::resolve_embedded_chain(
+            "https://polygon-mainnet.infura.io/v3/ID".to_string(),
+            Chain::PolyMainnet,
+        );
+
+        assert_eq!(url, "https://polygon-mainnet.infura.io/v3/ID".to_string());
+        assert!(embedded_chain_opt.is_none());
+    }
+
+    #[test]
+    fn resolve_embedded_chain_starts_a_local_blockchain_for_the_dev_chain() {
+        let (url, embedded_chain_opt) = BlockchainBridge::resolve_embedded_chain(
+            EMBEDDED_DEV_CHAIN_URL.to_string(),
+            Chain::Dev,
+        );
+
+        assert!(url.starts_with("http://127.0.0.1:"), "{}", url);
+        assert_eq!(
+            embedded_chain_opt.as_ref().map(|server| server.url()),
+            Some(url.clone())
+        );
+        let port = url.rsplit(':').next().unwrap().parse::<u16>().unwrap();
+        assert!(TcpStream::connect(SocketAddr::new(localhost(), port)).is_ok());
+    }
+
+    #[test]
+    fn embedded_chain_shuts_down_with_the_blockchain_bridge() {
+        let (url, embedded_chain_opt) = BlockchainBridge::resolve_embedded_chain(
+            EMBEDDED_DEV_CHAIN_URL.to_string(),
+            Chain::Dev,
+        );
+        let port = url.rsplit(':').next().unwrap().parse::<u16>().unwrap();
+        let subject = BlockchainBridge::new(
+            Box::new(BlockchainInterfaceMock::default()),
+            Box::new(PersistentConfigurationMock::new()),
+            false,
+            None,
+        )
+        .with_embedded_chain(embedded_chain_opt);
+        assert!(TcpStream::connect(SocketAddr::new(localhost(), port)).is_ok());
+
+        drop(subject);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while TcpStream::connect(SocketAddr::new(localhost(), port)).is_ok() {
+            assert!(
+                Instant::now() < deadline,
+                "Embedded chain still listening on port {}",
+                port
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "The embedded blockchain can only be used with --chain dev")]
+    fn resolve_embedded_chain_refuses_real_chains() {
+        let _ = BlockchainBridge::resolve_embedded_chain(
+            EMBEDDED_DEV_CHAIN_URL.to_string(),
+            Chain::EthMainnet,
+        );
+    }
+
+    fn gwei(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(WEIS_IN_GWEI as u64)
+    }
+
+    fn make_monitoring_subject(
+        blockchain_interface: BlockchainInterfaceMock,
+        consuming_wallet_opt: Option<Wallet>,
+    ) -> BlockchainBridge {
+        BlockchainBridge::new(
+            Box::new(blockchain_interface),
+            Box::new(PersistentConfigurationMock::default()),
+            false,
+            consuming_wallet_opt,
+        )
+        .with_balance_monitoring(LowBalanceThresholds {
+            gas_gwei: 1_000,
+            masq_gwei: 2_000,
+        })
+    }
+
+    #[test]
+    fn balance_monitoring_is_off_unless_asked_for() {
+        let subject = BlockchainBridge::new(
+            stub_bi(),
+            Box::new(PersistentConfigurationMock::default()),
+            false,
+            None,
+        );
+
+        assert_eq!(subject.balance_monitor.enabled, false);
+        assert_eq!(
+            subject.balance_monitor.thresholds,
+            LowBalanceThresholds::default()
+        );
+        assert_eq!(
+            subject.balance_monitor.check_interval,
+            DEFAULT_BALANCE_CHECK_INTERVAL
+        );
+    }
+
+    #[test]
+    fn low_balances_suspend_consuming_services_and_are_broadcast() {
+        init_test_logging();
+        let test_name = "low_balances_suspend_consuming_services_and_are_broadcast";
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let get_gas_balance_params_arc = Arc::new(Mutex::new(vec![]));
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_params(&get_gas_balance_params_arc)
+            .get_gas_balance_result(Ok(gwei(999)))
+            .get_token_balance_result(Ok(gwei(5_000)));
+        let mut subject =
+            make_monitoring_subject(blockchain_interface, Some(consuming_wallet.clone()));
+        subject.logger = Logger::new(test_name);
+        let system = System::new(test_name);
+        subject.balance_monitor.consuming_services_status_sub_opt =
+            Some(proxy_server.start().recipient());
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_check_consuming_wallet_balances();
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.balance_monitor.consuming_services_suspended, true);
+        let get_gas_balance_params = get_gas_balance_params_arc.lock().unwrap();
+        assert_eq!(*get_gas_balance_params, vec![consuming_wallet]);
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<ConsumingServicesStatus>(0),
+            &ConsumingServicesStatus { suspended: true }
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiWalletBalanceBroadcast {
+                    gas_balance_gwei: 999,
+                    masq_balance_gwei: 5_000,
+                    gas_threshold_gwei: 1_000,
+                    masq_threshold_gwei: 2_000,
+                    consuming_services_suspended: true
+                }
+                .tmb(0)
+            }
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Consuming wallet balances (999 gwei of gas, 5000 gwei of MASQ) are below \
+            the thresholds (1000 gwei, 2000 gwei); consuming services are suspended until the \
+            wallet is replenished",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn replenished_balances_resume_consuming_services() {
+        init_test_logging();
+        let test_name = "replenished_balances_resume_consuming_services";
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Ok(gwei(1_000)))
+            .get_token_balance_result(Ok(gwei(2_000)));
+        let mut subject =
+            make_monitoring_subject(blockchain_interface, Some(make_paying_wallet(b"consuming")));
+        subject.logger = Logger::new(test_name);
+        subject.balance_monitor.consuming_services_suspended = true;
+        let system = System::new(test_name);
+        subject.balance_monitor.consuming_services_status_sub_opt =
+            Some(proxy_server.start().recipient());
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_check_consuming_wallet_balances();
+
+        System::current().stop();
+        system.run();
+        assert_eq!(subject.balance_monitor.consuming_services_suspended, false);
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<ConsumingServicesStatus>(0),
+            &ConsumingServicesStatus { suspended: false }
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {}: Consuming wallet balances (1000 gwei of gas, 2000 gwei of MASQ) are back \
+            above the thresholds; consuming services are resumed",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn unchanged_balance_state_is_only_broadcast() {
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Ok(gwei(1_000_000)))
+            .get_token_balance_result(Ok(gwei(2_000_000)));
+        let mut subject =
+            make_monitoring_subject(blockchain_interface, Some(make_paying_wallet(b"consuming")));
+        let system = System::new("unchanged_balance_state_is_only_broadcast");
+        subject.balance_monitor.consuming_services_status_sub_opt =
+            Some(proxy_server.start().recipient());
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_check_consuming_wallet_balances();
+
+        System::current().stop();
+        system.run();
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(proxy_server_recording.len(), 0);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let message = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        let (broadcast, _) = UiWalletBalanceBroadcast::fmb(message.body.clone()).unwrap();
+        assert_eq!(broadcast.consuming_services_suspended, false);
+    }
+
+    #[test]
+    fn failed_balance_check_changes_nothing() {
+        init_test_logging();
+        let test_name = "failed_balance_check_changes_nothing";
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Err(BlockchainError::QueryFailed("booga".to_string())));
+        let mut subject =
+            make_monitoring_subject(blockchain_interface, Some(make_paying_wallet(b"consuming")));
+        subject.logger = Logger::new(test_name);
+
+        subject.handle_check_consuming_wallet_balances();
+
+        assert_eq!(subject.balance_monitor.consuming_services_suspended, false);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Periodic balance check failed: Did not find out gas balance of the \
+            consuming wallet: QueryFailed(\"booga\")",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn wallet_balances_request_is_answered() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Ok(gwei(1_234) + U256::from(567)))
+            .get_token_balance_result(Ok(gwei(8_765)));
+        let subject = BlockchainBridge::new(
+            Box::new(blockchain_interface),
+            Box::new(PersistentConfigurationMock::default()),
+            false,
+            Some(consuming_wallet.clone()),
+        );
+        let system = System::new("wallet_balances_request_is_answered");
+        let addr = subject.start();
+        addr.try_send(BindMessage {
+            peer_actors: peer_actors_builder().ui_gateway(ui_gateway).build(),
+        })
+        .unwrap();
+
+        addr.try_send(NodeFromUiMessage {
+            client_id: 1234,
+            body: UiWalletBalancesRequest {}.tmb(4321),
+        })
+        .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiWalletBalancesResponse {
+                    consuming_wallet_address: consuming_wallet.to_string(),
+                    gas_balance_gwei: 1_234,
+                    masq_balance_gwei: 8_765,
+                    gas_threshold_gwei: 0,
+                    masq_threshold_gwei: 0,
+                    consuming_services_suspended: false
+                }
+                .tmb(4321)
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+    }
+
+    #[test]
+    fn wallet_balances_request_without_consuming_wallet_is_refused() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = make_monitoring_subject(BlockchainInterfaceMock::default(), None);
+        let system = System::new("wallet_balances_request_without_consuming_wallet_is_refused");
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_wallet_balances_request(1234, 4321);
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: MessageBody {
+                    opcode: "walletBalances".to_string(),
+                    path: MessagePath::Conversation(4321),
+                    payload: Err((
+                        WALLET_BALANCES_ERROR,
+                        "No consuming wallet is configured".to_string()
+                    ))
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn discover_wallets_request_is_handed_to_launcher() {
+        init_test_logging();
+        let test_name = "discover_wallets_request_is_handed_to_launcher";
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let launch_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = BlockchainBridge::new(
+            Box::new(BlockchainInterfaceMock::default()),
+            Box::new(PersistentConfigurationMock::default()),
+            false,
+            None,
+        )
+        .with_wallet_discovery(Box::new(
+            WalletDiscoveryLauncherMock::default().launch_params(&launch_params_arc),
+        ));
+        subject.logger = Logger::new(test_name);
+        let system = System::new(test_name);
+        let addr = subject.start();
+        addr.try_send(BindMessage {
+            peer_actors: peer_actors_builder().ui_gateway(ui_gateway).build(),
+        })
+        .unwrap();
+
+        addr.try_send(NodeFromUiMessage {
+            client_id: 1234,
+            body: UiDiscoverWalletsRequest {
+                seed_spec: UiRecoverSeedSpec {
+                    mnemonic_phrase: make_meaningless_phrase_words(),
+                    mnemonic_phrase_language_opt: None,
+                    mnemonic_passphrase_opt: Some("passphrase".to_string()),
+                },
+                account_count_opt: None,
+                index_count_opt: Some(2),
+            }
+            .tmb(4321),
+        })
+        .unwrap();
+
+        System::current().stop();
+        system.run();
+        let seed = Configurator::make_seed(
+            &Some("passphrase".to_string()),
+            "English",
+            &make_meaningless_phrase_words(),
+        )
+        .unwrap();
+        let launch_params = launch_params_arc.lock().unwrap();
+        assert_eq!(
+            *launch_params,
+            vec![WalletDiscoveryScan {
+                client_id: 1234,
+                context_id: 4321,
+                seed: seed.as_bytes().to_vec(),
+                account_count: 1,
+                index_count: 2,
+            }]
+        );
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(ui_gateway_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {}: Scanning 1 accounts of 2 indexes each for wallets derived from the supplied seed",
+            test_name
+        ));
+    }
+
+    #[test]
+    fn discover_wallets_request_without_blockchain_service_is_refused() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = make_monitoring_subject(BlockchainInterfaceMock::default(), None);
+        let system = System::new("discover_wallets_request_without_blockchain_service_is_refused");
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_discover_wallets_request(
+            1234,
+            4321,
+            UiDiscoverWalletsRequest {
+                seed_spec: UiRecoverSeedSpec {
+                    mnemonic_phrase: make_meaningless_phrase_words(),
+                    mnemonic_phrase_language_opt: None,
+                    mnemonic_passphrase_opt: None,
+                },
+                account_count_opt: None,
+                index_count_opt: None,
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: MessageBody {
+                    opcode: "discoverWallets".to_string(),
+                    path: MessagePath::Conversation(4321),
+                    payload: Err((
+                        WALLET_DISCOVERY_ERROR,
+                        "No blockchain service is configured to scan for wallets".to_string()
+                    ))
+                }
+            }
+        );
+    }
+
+    #[test]
+    fn discover_wallets_request_with_bad_mnemonic_is_refused() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = make_monitoring_subject(BlockchainInterfaceMock::default(), None);
+        let system = System::new("discover_wallets_request_with_bad_mnemonic_is_refused");
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_discover_wallets_request(
+            1234,
+            4321,
+            UiDiscoverWalletsRequest {
+                seed_spec: UiRecoverSeedSpec {
+                    mnemonic_phrase: vec!["booga".to_string(); 12],
+                    mnemonic_phrase_language_opt: Some("English".to_string()),
+                    mnemonic_passphrase_opt: None,
+                },
+                account_count_opt: None,
+                index_count_opt: None,
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let message = ui_gateway_recording.get_record::<NodeToUiMessage>(0);
+        assert_eq!(message.target, MessageTarget::ClientId(1234));
+        assert_eq!(message.body.opcode, "discoverWallets".to_string());
+        assert_eq!(message.body.path, MessagePath::Conversation(4321));
+        match &message.body.payload {
+            Err((code, msg)) => {
+                assert_eq!(*code, MNEMONIC_PHRASE_ERROR);
+                assert!(
+                    msg.starts_with("Couldn't make a mnemonic out of the supplied phrase"),
+                    "{}",
+                    msg
+                );
+            }
+            x => panic!("Expected error, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn discover_wallets_request_with_oversized_range_is_refused() {
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = make_monitoring_subject(BlockchainInterfaceMock::default(), None);
+        let system = System::new("discover_wallets_request_with_oversized_range_is_refused");
+        subject.balance_monitor.ui_message_sub_opt = Some(ui_gateway.start().recipient());
+
+        subject.handle_discover_wallets_request(
+            1234,
+            4321,
+            UiDiscoverWalletsRequest {
+                seed_spec: UiRecoverSeedSpec {
+                    mnemonic_phrase: make_meaningless_phrase_words(),
+                    mnemonic_phrase_language_opt: None,
+                    mnemonic_passphrase_opt: None,
+                },
+                account_count_opt: Some(100),
+                index_count_opt: None,
+            },
+        );
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0).body.payload,
+            Err((
+                WALLET_DISCOVERY_ERROR,
+                "Scanning 100 accounts of 20 indexes each would exceed the limit of 500 derivation paths"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn wei_to_saturated_gwei_truncates_and_saturates() {
+        assert_eq!(wei_to_saturated_gwei(U256::from(1_999_999_999_u64)), 1);
+        assert_eq!(wei_to_saturated_gwei(U256::MAX), u64::MAX);
+    }
 }
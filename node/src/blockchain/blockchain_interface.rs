@@ -5,13 +5,14 @@ use crate::accountant::database_access_objects::payable_dao::{PayableAccount, Pe
 use crate::blockchain::batch_payable_tools::{BatchPayableTools, BatchPayableToolsReal};
 use crate::blockchain::blockchain_bridge::PendingPayableFingerprintSeeds;
 use crate::blockchain::blockchain_interface::BlockchainError::{
-    InvalidAddress, InvalidResponse, InvalidUrl, QueryFailed,
+    InvalidAddress, InvalidResponse, InvalidUrl, LogQueryTooBroad, QueryFailed,
 };
+use crate::blockchain::external_signer::{ExternalSigner, ExternallySignedTransaction};
 use crate::sub_lib::wallet::Wallet;
 use actix::{Message, Recipient};
 use futures::{future, Future};
 use itertools::Either::{Left, Right};
-use masq_lib::blockchains::chains::{Chain, ChainFamily};
+use masq_lib::blockchains::chains::Chain;
 use masq_lib::constants::DEFAULT_CHAIN;
 use masq_lib::logger::Logger;
 use masq_lib::utils::ExpectValue;
@@ -19,7 +20,7 @@ use serde_json::Value;
 use std::convert::{From, TryFrom, TryInto};
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
-use std::iter::once;
+use std::iter::{once, repeat};
 use thousands::Separable;
 use web3::contract::{Contract, Options};
 use web3::transports::{Batch, EventLoopHandle, Http};
@@ -31,7 +32,16 @@ use web3::{BatchTransport, Error, Transport, Web3};
 
 pub const REQUESTS_IN_PARALLEL: usize = 1;
 
-pub const CONTRACT_ABI: &str = r#"[{"constant":true,"inputs":[{"name":"owner","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"},{"constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transfer","outputs":[{"name":"","type":"bool"}],"payable":false,"stateMutability":"nonpayable","type":"function"}]"#;
+// Phrases RPC providers use when they refuse an eth_getLogs query for covering too many blocks or results.
+// Generic ones like "too many" or "limit exceeded" stay out: rate limiting (even under code -32005) uses them too
+const LOG_QUERY_TOO_BROAD_FRAGMENTS: [&str; 4] = [
+    "query returned more than",
+    "block range",
+    "response size exceeded",
+    "too many blocks",
+];
+
+pub const CONTRACT_ABI: &str = r#"[{"constant":true,"inputs":[{"name":"owner","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"},{"constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transfer","outputs":[{"name":"","type":"bool"}],"payable":false,"stateMutability":"nonpayable","type":"function"},{"constant":true,"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
 
 const TRANSACTION_LITERAL: H256 = H256([
     0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
@@ -40,10 +50,18 @@ const TRANSACTION_LITERAL: H256 = H256([
 
 const TRANSFER_METHOD_ID: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
 
+// disperseToken(address,address[],uint256[]) of the Disperse contract
+const DISPERSE_TOKEN_METHOD_ID: [u8; 4] = [0xc7, 0x3a, 0x2d, 0x60];
+
+// What every transaction costs before it does anything; a disperse transaction pays it only once
+const INTRINSIC_TRANSACTION_GAS: u64 = 21_000;
+
 #[derive(Clone, Debug, Eq, Message, PartialEq)]
 pub struct BlockchainTransaction {
     pub block_number: u64,
+    pub transaction_hash: H256,
     pub from: Wallet,
+    pub to: Wallet,
     pub wei_amount: u128,
 }
 
@@ -63,6 +81,7 @@ pub enum BlockchainError {
     InvalidAddress,
     InvalidResponse,
     QueryFailed(String),
+    LogQueryTooBroad(String),
 }
 
 impl Display for BlockchainError {
@@ -72,6 +91,7 @@ impl Display for BlockchainError {
             InvalidAddress => Left("Invalid address"),
             InvalidResponse => Left("Invalid response"),
             QueryFailed(msg) => Right(format!("Query failed: {}", msg)),
+            LogQueryTooBroad(msg) => Right(format!("Log query too broad: {}", msg)),
         };
         write!(f, "Blockchain error: {}", err_spec)
     }
@@ -126,6 +146,7 @@ impl Display for PayableTransactionError {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RetrievedBlockchainTransactions {
     pub new_start_block: u64,
+    pub last_confirmed_block: u64,
     pub transactions: Vec<BlockchainTransaction>,
 }
 
@@ -135,7 +156,8 @@ pub trait BlockchainInterface<T: Transport = Http> {
     fn retrieve_transactions(
         &self,
         start_block: u64,
-        recipient: &Wallet,
+        max_block_count: u64,
+        recipients: &[Wallet],
     ) -> Result<RetrievedBlockchainTransactions, BlockchainError>;
 
     fn send_payables_within_batch(
@@ -153,6 +175,9 @@ pub trait BlockchainInterface<T: Transport = Http> {
 
     fn get_transaction_count(&self, address: &Wallet) -> ResultForNonce;
 
+    // Number of token Transfer logs naming the wallet as recipient since the contract was created
+    fn get_received_transfer_count(&self, address: &Wallet) -> BlockchainResult<u64>;
+
     fn get_transaction_receipt(&self, hash: H256) -> ResultForReceipt;
 }
 
@@ -185,7 +210,8 @@ impl BlockchainInterface for BlockchainInterfaceClandestine {
     fn retrieve_transactions(
         &self,
         _start_block: u64,
-        _recipient: &Wallet,
+        _max_block_count: u64,
+        _recipients: &[Wallet],
     ) -> Result<RetrievedBlockchainTransactions, BlockchainError> {
         let msg = "Can't retrieve transactions clandestinely yet".to_string();
         error!(self.logger, "{}", &msg);
@@ -222,6 +248,14 @@ impl BlockchainInterface for BlockchainInterfaceClandestine {
         Ok(0.into())
     }
 
+    fn get_received_transfer_count(&self, _address: &Wallet) -> BlockchainResult<u64> {
+        error!(
+            self.logger,
+            "Can't get received transfers clandestinely yet",
+        );
+        Ok(0)
+    }
+
     fn get_transaction_receipt(&self, _hash: H256) -> ResultForReceipt {
         error!(
             self.logger,
@@ -240,6 +274,9 @@ pub struct BlockchainInterfaceNonClandestine<T: BatchTransport + Debug> {
     batch_web3: Web3<Batch<T>>,
     batch_payable_tools: Box<dyn BatchPayableTools<T>>,
     contract: Contract<T>,
+    external_signer_opt: Option<Box<dyn ExternalSigner>>,
+    disperse_contract_opt: Option<Address>,
+    confirmation_depth: u64,
 }
 
 const GWEI: U256 = U256([1_000_000_000u64, 0, 0, 0]);
@@ -260,24 +297,49 @@ where
     fn retrieve_transactions(
         &self,
         start_block: u64,
-        recipient: &Wallet,
+        max_block_count: u64,
+        recipients: &[Wallet],
     ) -> Result<RetrievedBlockchainTransactions, BlockchainError> {
         debug!(
             self.logger,
-            "Retrieving transactions from start block: {} for: {} chain_id: {} contract: {:#x}",
+            "Retrieving transactions from start block: {} (at most {} blocks) for: {} chain_id: {} contract: {:#x}",
             start_block,
-            recipient,
+            max_block_count,
+            comma_joined_stringifiable(recipients, |recipient| recipient.to_string()),
             self.chain.rec().num_chain_id,
             self.contract_address()
         );
+        let recipient_topics = recipients
+            .iter()
+            .map(|recipient| recipient.address().into())
+            .collect::<Vec<H256>>();
+        let last_confirmed_block_opt = self.last_confirmed_block()?;
+        let last_confirmed_block = match last_confirmed_block_opt {
+            Some(block) if block >= start_block => block,
+            _ => {
+                debug!(
+                    self.logger,
+                    "No block at or after {} is {} confirmations deep yet; nothing to retrieve",
+                    start_block,
+                    self.confirmation_depth
+                );
+                return Ok(RetrievedBlockchainTransactions {
+                    new_start_block: start_block,
+                    last_confirmed_block: last_confirmed_block_opt.unwrap_or(0),
+                    transactions: vec![],
+                });
+            }
+        };
+        let end_block =
+            last_confirmed_block.min(start_block.saturating_add(max_block_count.max(1) - 1));
         let filter = FilterBuilder::default()
             .address(vec![self.contract_address()])
             .from_block(BlockNumber::Number(ethereum_types::U64::from(start_block)))
-            .to_block(BlockNumber::Latest)
+            .to_block(BlockNumber::Number(ethereum_types::U64::from(end_block)))
             .topics(
                 Some(vec![TRANSACTION_LITERAL]),
                 None,
-                Some(vec![recipient.address().into()]),
+                Some(recipient_topics),
                 None,
             )
             .build();
@@ -300,42 +362,20 @@ where
                             );
                             Err(BlockchainError::InvalidResponse)
                         } else {
-                            let transactions: Vec<BlockchainTransaction> = logs
+                            let valid_logs = logs
                                 .iter()
-                                .filter_map(|log: &Log| match log.block_number {
-                                    Some(block_number) => {
-                                        let amount: U256 = U256::from(log.data.0.as_slice());
-                                        let wei_amount_result = u128::try_from(amount);
-                                        wei_amount_result.ok().map(|wei_amount| {
-                                            BlockchainTransaction {
-                                                block_number: u64::try_from(block_number)
-                                                    .expect("Internal Error"),
-                                                from: Wallet::from(log.topics[1]),
-                                                wei_amount,
-                                            }
-                                        })
-                                    }
-                                    None => None,
-                                })
-                                .collect();
+                                .filter(|log| log.removed != Some(true))
+                                .collect::<Vec<&Log>>();
+                            let transactions = Self::transactions_from_logs(&valid_logs);
                             debug!(logger, "Retrieved transactions: {:?}", transactions);
-                            // Get the largest transaction block number, unless there are no
-                            // transactions, in which case use start_block.
-                            let last_transaction_block =
-                                transactions.iter().fold(start_block, |so_far, elem| {
-                                    if elem.block_number > so_far {
-                                        elem.block_number
-                                    } else {
-                                        so_far
-                                    }
-                                });
                             Ok(RetrievedBlockchainTransactions {
-                                new_start_block: last_transaction_block + 1,
+                                new_start_block: end_block + 1,
+                                last_confirmed_block,
                                 transactions,
                             })
                         }
                     }
-                    Err(e) => Err(BlockchainError::QueryFailed(e.to_string())),
+                    Err(e) => Err(Self::log_query_error(e)),
                 })
             })
             .wait()
@@ -358,12 +398,27 @@ where
             gas_price
         );
 
-        let hashes_and_paid_amounts = self.sign_and_append_multiple_payments(
-            consuming_wallet,
-            gas_price,
-            pending_nonce,
-            accounts,
-        )?;
+        let disperse_contract_opt = self
+            .disperse_contract_opt
+            .filter(|_| accounts.len() > 1)
+            .filter(|disperse_contract| {
+                self.disperse_contract_may_pay(consuming_wallet, *disperse_contract, accounts)
+            });
+        let hashes_and_paid_amounts = match disperse_contract_opt {
+            Some(disperse_contract) => self.sign_and_append_dispersed_payments(
+                consuming_wallet,
+                gas_price,
+                pending_nonce,
+                disperse_contract,
+                accounts,
+            )?,
+            None => self.sign_and_append_multiple_payments(
+                consuming_wallet,
+                gas_price,
+                pending_nonce,
+                accounts,
+            )?,
+        };
         let timestamp = self.batch_payable_tools.batch_wide_timestamp();
         self.batch_payable_tools
             .send_new_payable_fingerprints_seeds(
@@ -380,7 +435,7 @@ where
 
         match self.batch_payable_tools.submit_batch(&self.batch_web3) {
             Ok(responses) => Ok(Self::merged_output_data(
-                responses,
+                Self::response_per_payable(responses, disperse_contract_opt.is_some(), accounts),
                 hashes_and_paid_amounts,
                 accounts,
             )),
@@ -417,6 +472,28 @@ where
             .wait()
     }
 
+    fn get_received_transfer_count(&self, wallet: &Wallet) -> BlockchainResult<u64> {
+        let filter = FilterBuilder::default()
+            .address(vec![self.contract_address()])
+            .from_block(BlockNumber::Number(ethereum_types::U64::from(
+                self.chain.rec().contract_creation_block,
+            )))
+            .to_block(BlockNumber::Latest)
+            .topics(
+                Some(vec![TRANSACTION_LITERAL]),
+                None,
+                Some(vec![wallet.address().into()]),
+                None,
+            )
+            .build();
+        self.web3
+            .eth()
+            .logs(filter)
+            .map_err(Self::log_query_error)
+            .wait()
+            .map(|logs| logs.iter().filter(|log| log.removed != Some(true)).count() as u64)
+    }
+
     fn get_transaction_receipt(&self, hash: H256) -> ResultForReceipt {
         self.web3
             .eth()
@@ -461,9 +538,27 @@ where
             batch_web3,
             batch_payable_tools,
             contract,
+            external_signer_opt: None,
+            disperse_contract_opt: chain.rec().disperse_contract_opt,
+            confirmation_depth: chain.rec().confirmation_depth,
         }
     }
 
+    pub fn with_external_signer(mut self, external_signer: Box<dyn ExternalSigner>) -> Self {
+        self.external_signer_opt = Some(external_signer);
+        self
+    }
+
+    pub fn with_disperse_contract(mut self, disperse_contract: Address) -> Self {
+        self.disperse_contract_opt = Some(disperse_contract);
+        self
+    }
+
+    pub fn with_confirmation_depth(mut self, confirmation_depth: u64) -> Self {
+        self.confirmation_depth = confirmation_depth;
+        self
+    }
+
     fn sign_and_append_multiple_payments(
         &self,
         consuming_wallet: &Wallet,
@@ -547,12 +642,143 @@ where
         }
     }
 
+    // The disperse contract moves the MASQ with transferFrom, so without enough allowance its
+    // transaction would fail for every creditor at once
+    fn disperse_contract_may_pay(
+        &self,
+        consuming_wallet: &Wallet,
+        disperse_contract: Address,
+        accounts: &[PayableAccount],
+    ) -> bool {
+        let total_wei = accounts.iter().fold(U256::zero(), |total, account| {
+            total + U256::try_from(account.balance_wei).expect("shouldn't overflow")
+        });
+        let allowance_result: Result<U256, web3::contract::Error> = self
+            .contract
+            .query(
+                "allowance",
+                (consuming_wallet.address(), disperse_contract),
+                None,
+                Options::default(),
+                None,
+            )
+            .wait();
+        match allowance_result {
+            Ok(allowance) if allowance >= total_wei => true,
+            Ok(allowance) => {
+                warning!(
+                    self.logger,
+                    "Disperse contract {:?} may spend only {} wei of {}, but {} wei is due; paying creditors one by one",
+                    disperse_contract,
+                    allowance,
+                    consuming_wallet,
+                    total_wei
+                );
+                false
+            }
+            Err(e) => {
+                warning!(
+                    self.logger,
+                    "Couldn't check the allowance of disperse contract {:?} for {}: {}; paying creditors one by one",
+                    disperse_contract,
+                    consuming_wallet,
+                    e
+                );
+                false
+            }
+        }
+    }
+
+    fn sign_and_append_dispersed_payments(
+        &self,
+        consuming_wallet: &Wallet,
+        gas_price: u64,
+        nonce: U256,
+        disperse_contract: Address,
+        accounts: &[PayableAccount],
+    ) -> HashAndAmountResult {
+        debug!(
+            self.logger,
+            "Preparing payments to {} creditors through disperse contract {:?} with nonce {}",
+            accounts.len(),
+            disperse_contract,
+            nonce
+        );
+        let transaction_parameters =
+            self.disperse_transaction_parameters(disperse_contract, accounts, nonce, gas_price);
+        let hash = self.sign_and_append_transaction(transaction_parameters, consuming_wallet)?;
+        Ok(accounts
+            .iter()
+            .map(|account| (hash, account.balance_wei))
+            .collect())
+    }
+
+    fn last_confirmed_block(&self) -> Result<Option<u64>, BlockchainError> {
+        let head = self
+            .web3
+            .eth()
+            .block_number()
+            .map_err(|e| BlockchainError::QueryFailed(e.to_string()))
+            .wait()?;
+        Ok(head.as_u64().checked_sub(self.confirmation_depth))
+    }
+
+    fn log_query_error(error: Error) -> BlockchainError {
+        let msg = error.to_string();
+        let lowercase_msg = msg.to_lowercase();
+        if LOG_QUERY_TOO_BROAD_FRAGMENTS
+            .iter()
+            .any(|fragment| lowercase_msg.contains(fragment))
+        {
+            BlockchainError::LogQueryTooBroad(msg)
+        } else {
+            BlockchainError::QueryFailed(msg)
+        }
+    }
+
+    fn transactions_from_logs(logs: &[&Log]) -> Vec<BlockchainTransaction> {
+        logs.iter()
+            .filter_map(|log| match (log.block_number, log.transaction_hash) {
+                (Some(block_number), Some(transaction_hash)) => {
+                    let amount: U256 = U256::from(log.data.0.as_slice());
+                    let wei_amount_result = u128::try_from(amount);
+                    wei_amount_result
+                        .ok()
+                        .map(|wei_amount| BlockchainTransaction {
+                            block_number: u64::try_from(block_number).expect("Internal Error"),
+                            transaction_hash,
+                            from: Wallet::from(log.topics[1]),
+                            to: Wallet::from(log.topics[2]),
+                            wei_amount,
+                        })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
     fn advance_used_nonce(current_nonce: U256) -> U256 {
         current_nonce
             .checked_add(U256::one())
             .expect("unexpected limits")
     }
 
+    fn response_per_payable(
+        responses: Vec<web3::transports::Result<Value>>,
+        dispersed: bool,
+        accounts: &[PayableAccount],
+    ) -> Vec<web3::transports::Result<Value>> {
+        if dispersed {
+            responses
+                .into_iter()
+                .take(1)
+                .flat_map(|response| repeat(response).take(accounts.len()))
+                .collect()
+        } else {
+            responses
+        }
+    }
+
     fn merged_output_data(
         responses: Vec<web3::transports::Result<Value>>,
         hashes_and_paid_amounts: Vec<(H256, u128)>,
@@ -599,28 +825,106 @@ where
         nonce: U256,
         gas_price: u64,
     ) -> Result<H256, PayableTransactionError> {
-        let signed_tx =
-            self.sign_transaction(recipient, consuming_wallet, amount, nonce, gas_price)?;
+        let transaction_parameters =
+            self.transaction_parameters(recipient, amount, nonce, gas_price);
+        self.sign_and_append_transaction(transaction_parameters, consuming_wallet)
+    }
+
+    fn sign_and_append_transaction(
+        &self,
+        transaction_parameters: TransactionParameters,
+        consuming_wallet: &Wallet,
+    ) -> Result<H256, PayableTransactionError> {
+        let (raw_transaction, transaction_hash) = match &self.external_signer_opt {
+            Some(external_signer) => {
+                let signed_tx = Self::sign_transaction_externally(
+                    external_signer.as_ref(),
+                    transaction_parameters,
+                    consuming_wallet,
+                )?;
+                (signed_tx.raw_transaction, signed_tx.transaction_hash)
+            }
+            None => {
+                let signed_tx = self.sign_transaction(transaction_parameters, consuming_wallet)?;
+                (signed_tx.raw_transaction, signed_tx.transaction_hash)
+            }
+        };
         self.batch_payable_tools
-            .append_transaction_to_batch(signed_tx.raw_transaction, &self.batch_web3);
-        Ok(signed_tx.transaction_hash)
+            .append_transaction_to_batch(raw_transaction, &self.batch_web3);
+        Ok(transaction_hash)
+    }
+
+    fn sign_transaction_externally(
+        external_signer: &dyn ExternalSigner,
+        transaction_parameters: TransactionParameters,
+        consuming_wallet: &Wallet,
+    ) -> Result<ExternallySignedTransaction, PayableTransactionError> {
+        external_signer
+            .sign_transaction(consuming_wallet.address(), transaction_parameters)
+            .map_err(|e| PayableTransactionError::Signing(e.to_string()))
     }
 
-    fn sign_transaction<'a>(
+    fn sign_transaction(
         &self,
-        recipient: &'a Wallet,
-        consuming_wallet: &'a Wallet,
+        transaction_parameters: TransactionParameters,
+        consuming_wallet: &Wallet,
+    ) -> Result<SignedTransaction, PayableTransactionError> {
+        let key = match consuming_wallet.prepare_secp256k1_secret() {
+            Ok(secret) => secret,
+            Err(e) => return Err(PayableTransactionError::UnusableWallet(e.to_string())),
+        };
+
+        self.batch_payable_tools
+            .sign_transaction(transaction_parameters, &self.batch_web3, &key)
+            .map_err(|e| PayableTransactionError::Signing(e.to_string()))
+    }
+
+    fn transaction_parameters(
+        &self,
+        recipient: &Wallet,
         amount: u128,
         nonce: U256,
         gas_price: u64,
-    ) -> Result<SignedTransaction, PayableTransactionError> {
+    ) -> TransactionParameters {
         let mut data = [0u8; 4 + 32 + 32];
         data[0..4].copy_from_slice(&TRANSFER_METHOD_ID);
         data[16..36].copy_from_slice(&recipient.address().0[..]);
         U256::try_from(amount)
             .expect("shouldn't overflow")
             .to_big_endian(&mut data[36..68]);
-        let base_gas_limit = Self::base_gas_limit(self.chain);
+        self.assemble_transaction_parameters(
+            H160(self.contract_address().0),
+            data.to_vec(),
+            Self::base_gas_limit(self.chain),
+            nonce,
+            gas_price,
+        )
+    }
+
+    fn disperse_transaction_parameters(
+        &self,
+        disperse_contract: Address,
+        accounts: &[PayableAccount],
+        nonce: U256,
+        gas_price: u64,
+    ) -> TransactionParameters {
+        self.assemble_transaction_parameters(
+            disperse_contract,
+            Self::disperse_token_data(self.contract_address(), accounts),
+            Self::dispersed_base_gas_limit(self.chain, accounts.len()),
+            nonce,
+            gas_price,
+        )
+    }
+
+    fn assemble_transaction_parameters(
+        &self,
+        to: Address,
+        data: Vec<u8>,
+        base_gas_limit: u64,
+        nonce: U256,
+        gas_price: u64,
+    ) -> TransactionParameters {
         let gas_limit =
             ethereum_types::U256::try_from(data.iter().fold(base_gas_limit, |acc, v| {
                 acc + if v == &0u8 { 4 } else { 68 }
@@ -635,24 +939,51 @@ where
         )
         .expect("Internal error");
 
-        let transaction_parameters = TransactionParameters {
+        TransactionParameters {
             nonce: Some(converted_nonce),
-            to: Some(H160(self.contract_address().0)),
+            to: Some(to),
             gas: gas_limit,
             gas_price: Some(gas_price),
             value: ethereum_types::U256::zero(),
-            data: Bytes(data.to_vec()),
+            data: Bytes(data),
             chain_id: Some(self.chain.rec().num_chain_id),
-        };
+        }
+    }
 
-        let key = match consuming_wallet.prepare_secp256k1_secret() {
-            Ok(secret) => secret,
-            Err(e) => return Err(PayableTransactionError::UnusableWallet(e.to_string())),
-        };
+    fn disperse_token_data(token: Address, accounts: &[PayableAccount]) -> Vec<u8> {
+        let payment_count = accounts.len();
+        let head = [
+            U256::from(&token.0[..]),
+            // offsets of the recipients array and the values array
+            U256::from(3 * 32),
+            U256::from((4 + payment_count) * 32),
+        ];
+        let recipients = accounts
+            .iter()
+            .map(|account| U256::from(&account.wallet.address().0[..]));
+        let values = accounts
+            .iter()
+            .map(|account| U256::try_from(account.balance_wei).expect("shouldn't overflow"));
+        let mut data = DISPERSE_TOKEN_METHOD_ID.to_vec();
+        head.iter()
+            .copied()
+            .chain(once(U256::from(payment_count)))
+            .chain(recipients)
+            .chain(once(U256::from(payment_count)))
+            .chain(values)
+            .for_each(|word| {
+                let mut bytes = [0u8; 32];
+                word.to_big_endian(&mut bytes);
+                data.extend_from_slice(&bytes)
+            });
+        data
+    }
 
-        self.batch_payable_tools
-            .sign_transaction(transaction_parameters, &self.batch_web3, &key)
-            .map_err(|e| PayableTransactionError::Signing(e.to_string()))
+    // Every transfer after the first costs what a lone transfer would, minus the intrinsic gas
+    fn dispersed_base_gas_limit(chain: Chain, payment_count: usize) -> u64 {
+        let base_gas_limit = Self::base_gas_limit(chain);
+        let extra_payment_count = payment_count.saturating_sub(1) as u64;
+        base_gas_limit + extra_payment_count * (base_gas_limit - INTRINSIC_TRANSACTION_GAS)
     }
 
     fn transmission_log(&self, accounts: &[PayableAccount], gas_price: u64) -> String {
@@ -686,11 +1017,7 @@ where
     }
 
     fn base_gas_limit(chain: Chain) -> u64 {
-        match chain.rec().chain_family {
-            ChainFamily::Polygon => 70_000,
-            ChainFamily::Eth => 55_000,
-            ChainFamily::Dev => 55_000,
-        }
+        chain.rec().gas_limit_base
     }
 
     #[cfg(test)]
@@ -709,9 +1036,10 @@ mod tests {
     };
     use crate::blockchain::bip32::Bip32ECKeyProvider;
     use crate::blockchain::blockchain_interface::ProcessedPayableFallible::{Correct, Failed};
+    use crate::blockchain::external_signer::ExternalSignerError;
     use crate::blockchain::test_utils::{
         make_default_signed_transaction, make_fake_event_loop_handle, make_tx_hash,
-        BatchPayableToolsMock, TestTransport,
+        BatchPayableToolsMock, ExternalSignerMock, TestTransport,
     };
     use crate::sub_lib::wallet::Wallet;
     use crate::test_utils::make_paying_wallet;
@@ -724,6 +1052,7 @@ mod tests {
     use ethsign_crypto::Keccak256;
     use jsonrpc_core::Version::V2;
     use jsonrpc_core::{Call, Error, ErrorCode, Id, MethodCall, Params};
+    use masq_lib::blockchains::chains::ChainFamily;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use masq_lib::utils::{find_free_port, slice_of_strs_to_vec_of_strings};
@@ -744,7 +1073,7 @@ mod tests {
 
     #[test]
     fn constants_have_correct_values() {
-        let contract_abi_expected: &str = r#"[{"constant":true,"inputs":[{"name":"owner","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"},{"constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transfer","outputs":[{"name":"","type":"bool"}],"payable":false,"stateMutability":"nonpayable","type":"function"}]"#;
+        let contract_abi_expected: &str = r#"[{"constant":true,"inputs":[{"name":"owner","type":"address"}],"name":"balanceOf","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"},{"constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transfer","outputs":[{"name":"","type":"bool"}],"payable":false,"stateMutability":"nonpayable","type":"function"},{"constant":true,"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"","type":"uint256"}],"payable":false,"stateMutability":"view","type":"function"}]"#;
         let transaction_literal_expected: H256 = H256 {
             0: [
                 0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37,
@@ -828,7 +1157,10 @@ mod tests {
         let port = find_free_port();
         let test_server = TestServer::start(
             port,
-            vec![br#"{"jsonrpc":"2.0","id":3,"result":[]}"#.to_vec()],
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"result":"0x3a"}"#.to_vec(),
+                br#"{"jsonrpc":"2.0","id":3,"result":[]}"#.to_vec(),
+            ],
         );
 
         let (event_loop_handle, transport) = Http::with_max_parallel(
@@ -845,7 +1177,8 @@ mod tests {
         let result = subject
             .retrieve_transactions(
                 42,
-                &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+                100_000,
+                &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
             )
             .unwrap();
 
@@ -854,25 +1187,73 @@ mod tests {
             .into_iter()
             .map(|request| serde_json::from_slice(&request.body()).unwrap())
             .collect();
+        assert_eq!(bodies[0]["method"].to_string(), "\"eth_blockNumber\"");
         assert_eq!(
             format!("\"0x000000000000000000000000{}\"", &to[2..]),
-            bodies[0]["params"][0]["topics"][2].to_string(),
+            bodies[1]["params"][0]["topics"][2].to_string(),
         );
+        assert_eq!(bodies[1]["params"][0]["fromBlock"].to_string(), "\"0x2a\"");
+        assert_eq!(bodies[1]["params"][0]["toBlock"].to_string(), "\"0x2e\"");
         assert_eq!(
             result,
             RetrievedBlockchainTransactions {
-                new_start_block: 42 + 1,
-                transactions: vec![]
+                new_start_block: 0x2e + 1,
+                last_confirmed_block: 0x2e,
+                transactions: vec![],
             }
         )
     }
 
+    #[test]
+    fn blockchain_interface_non_clandestine_asks_for_transactions_to_every_recipient() {
+        let port = find_free_port();
+        let test_server = TestServer::start(
+            port,
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"result":"0x3a"}"#.to_vec(),
+                br#"{"jsonrpc":"2.0","id":3,"result":[]}"#.to_vec(),
+            ],
+        );
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let _ = subject
+            .retrieve_transactions(
+                42,
+                100_000,
+                &[
+                    Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+                    Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092").unwrap(),
+                ],
+            )
+            .unwrap();
+
+        let requests = test_server.requests_so_far();
+        let body: Value = serde_json::from_slice(&requests[1].body()).unwrap();
+        assert_eq!(
+            body["params"][0]["topics"][2],
+            json!([
+                "0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc",
+                "0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"
+            ])
+        );
+    }
+
     #[test]
     fn blockchain_interface_non_clandestine_retrieves_transactions() {
         let to = "0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc";
         let port = find_free_port();
         #[rustfmt::skip]
         let test_server = TestServer::start (port, vec![
+            br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
             br#"{
                 "jsonrpc":"2.0",
                 "id":3,
@@ -925,7 +1306,8 @@ mod tests {
         let result = subject
             .retrieve_transactions(
                 42,
-                &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+                100_000,
+                &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
             )
             .unwrap();
 
@@ -936,26 +1318,41 @@ mod tests {
             .collect();
         assert_eq!(
             format!("\"0x000000000000000000000000{}\"", &to[2..]),
-            bodies[0]["params"][0]["topics"][2].to_string(),
+            bodies[1]["params"][0]["topics"][2].to_string(),
+        );
+        assert_eq!(
+            bodies[1]["params"][0]["toBlock"].to_string(),
+            "\"0x4be667\""
         );
         assert_eq!(
             result,
             RetrievedBlockchainTransactions {
-                new_start_block: 0x4be663 + 1,
+                new_start_block: 0x4be667 + 1,
+                last_confirmed_block: 0x4be667,
                 transactions: vec![
                     BlockchainTransaction {
                         block_number: 0x4be663,
+                        transaction_hash: H256::from_str(
+                            "955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681"
+                        )
+                        .unwrap(),
                         from: Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182")
                             .unwrap(),
+                        to: Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092").unwrap(),
                         wei_amount: 4_503_599_627_370_496,
                     },
                     BlockchainTransaction {
                         block_number: 0x4be662,
+                        transaction_hash: H256::from_str(
+                            "955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0680"
+                        )
+                        .unwrap(),
                         from: Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc")
                             .unwrap(),
+                        to: Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092").unwrap(),
                         wei_amount: 4_503_599_627_370_496,
                     },
-                ]
+                ],
             }
         )
     }
@@ -976,8 +1373,11 @@ mod tests {
             TEST_DEFAULT_CHAIN,
         );
 
-        let result = subject
-            .retrieve_transactions(42, &Wallet::new("0x3f69f9efd4f2592fd70beecd9dce71c472fc"));
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::new("0x3f69f9efd4f2592fd70beecd9dce71c472fc")],
+        );
 
         assert_eq!(
             result.expect_err("Expected an Err, got Ok"),
@@ -990,6 +1390,7 @@ mod tests {
     ) {
         let port = find_free_port();
         let _test_server = TestServer::start (port, vec![
+            br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
             br#"{"jsonrpc":"2.0","id":3,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","blockNumber":"0x4be663","data":"0x0000000000000000000000000000000000000000000000056bc75e2d63100000","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"}]}"#.to_vec()
         ]);
         let (event_loop_handle, transport) = Http::with_max_parallel(
@@ -1005,7 +1406,8 @@ mod tests {
 
         let result = subject.retrieve_transactions(
             42,
-            &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
         );
 
         assert_eq!(
@@ -1019,6 +1421,7 @@ mod tests {
     ) {
         let port = find_free_port();
         let _test_server = TestServer::start(port, vec![
+            br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
             br#"{"jsonrpc":"2.0","id":3,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","blockNumber":"0x4be663","data":"0x0000000000000000000000000000000000000000000000056bc75e2d6310000001","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc","0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"}]}"#.to_vec()
         ]);
 
@@ -1036,7 +1439,8 @@ mod tests {
 
         let result = subject.retrieve_transactions(
             42,
-            &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
         );
 
         assert_eq!(result, Err(BlockchainError::InvalidResponse));
@@ -1047,6 +1451,7 @@ mod tests {
     ) {
         let port = find_free_port();
         let _test_server = TestServer::start (port, vec![
+            br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
             br#"{"jsonrpc":"2.0","id":3,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","data":"0x0000000000000000000000000000000000000000000000000010000000000000","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc","0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"}]}"#.to_vec()
         ]);
 
@@ -1064,52 +1469,71 @@ mod tests {
 
         let result = subject.retrieve_transactions(
             42,
-            &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
         );
 
         assert_eq!(
             result,
             Ok(RetrievedBlockchainTransactions {
-                new_start_block: 43,
-                transactions: vec![]
+                new_start_block: 0x4be667 + 1,
+                last_confirmed_block: 0x4be667,
+                transactions: vec![],
             })
         );
     }
 
     #[test]
-    fn blockchain_interface_non_clandestine_can_retrieve_eth_balance_of_a_wallet() {
+    fn blockchain_interface_non_clandestine_retrieve_transactions_ignores_removed_logs() {
         let port = find_free_port();
-        let _test_server = TestServer::start(
-            port,
-            vec![br#"{"jsonrpc":"2.0","id":0,"result":"0xFFFF"}"#.to_vec()],
-        );
-
+        let _test_server = TestServer::start (port, vec![
+            br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
+            br#"{"jsonrpc":"2.0","id":3,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","blockNumber":"0x4be663","data":"0x0000000000000000000000000000000000000000000000000010000000000000","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182","0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"},{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732b","blockNumber":"0x4be662","data":"0x0000000000000000000000000000000000000000000000000020000000000000","logIndex":"0x0","removed":true,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc","0x000000000000000000000000adc1853c7859369639eb414b6342b36288fe6092"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0680","transactionIndex":"0x0"}]}"#.to_vec()
+        ]);
         let (event_loop_handle, transport) = Http::with_max_parallel(
             &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
             REQUESTS_IN_PARALLEL,
         )
         .unwrap();
-
         let subject = BlockchainInterfaceNonClandestine::new(
             transport,
             event_loop_handle,
             TEST_DEFAULT_CHAIN,
         );
 
-        let result = subject
-            .get_gas_balance(
-                &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
-            )
-            .unwrap();
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
 
-        assert_eq!(result, U256::from(65_535));
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: 0x4be667 + 1,
+                last_confirmed_block: 0x4be667,
+                transactions: vec![BlockchainTransaction {
+                    block_number: 0x4be663,
+                    transaction_hash: H256::from_str(
+                        "955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681"
+                    )
+                    .unwrap(),
+                    from: Wallet::from_str("0x3ab28ecedea6cdb6feed398e93ae8c7b316b1182").unwrap(),
+                    to: Wallet::from_str("0xadc1853c7859369639eb414b6342b36288fe6092").unwrap(),
+                    wei_amount: 4_503_599_627_370_496,
+                }],
+            })
+        );
     }
 
     #[test]
-    #[should_panic(expected = "No address for an uninitialized wallet!")]
-    fn blockchain_interface_non_clandestine_returns_an_error_when_requesting_eth_balance_of_an_invalid_wallet(
+    fn blockchain_interface_non_clandestine_retrieve_transactions_does_not_query_logs_until_start_block_is_confirmed(
     ) {
-        let port = 8545;
+        let port = find_free_port();
+        let test_server = TestServer::start(
+            port,
+            vec![br#"{"jsonrpc":"2.0","id":2,"result":"0x30"}"#.to_vec()],
+        );
         let (event_loop_handle, transport) = Http::with_max_parallel(
             &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
             REQUESTS_IN_PARALLEL,
@@ -1121,26 +1545,283 @@ mod tests {
             TEST_DEFAULT_CHAIN,
         );
 
-        let result =
-            subject.get_gas_balance(&Wallet::new("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fQ"));
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
 
-        assert_eq!(result, Err(BlockchainError::InvalidAddress));
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: 42,
+                last_confirmed_block: 0x24,
+                transactions: vec![],
+            })
+        );
+        let requests = test_server.requests_so_far();
+        assert_eq!(requests.len(), 1);
     }
 
     #[test]
-    fn blockchain_interface_non_clandestine_returns_an_error_for_unintelligible_response_to_requesting_eth_balance(
+    fn blockchain_interface_non_clandestine_retrieve_transactions_honors_configured_confirmation_depth(
     ) {
         let port = find_free_port();
-        let _test_server = TestServer::start(
+        let test_server = TestServer::start(
             port,
-            vec![br#"{"jsonrpc":"2.0","id":0,"result":"0xFFFQ"}"#.to_vec()],
+            vec![br#"{"jsonrpc":"2.0","id":2,"result":"0x30"}"#.to_vec()],
         );
-
-        let (event_loop_handle, transport) = Http::new(&format!(
-            "http://{}:{}",
-            &Ipv4Addr::LOCALHOST.to_string(),
-            port
-        ))
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        )
+        .with_confirmation_depth(40);
+
+        let result = subject.retrieve_transactions(
+            9,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
+
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: 9,
+                last_confirmed_block: 8,
+                transactions: vec![],
+            })
+        );
+        let requests = test_server.requests_so_far();
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_retrieve_transactions_returns_an_error_if_block_number_query_fails(
+    ) {
+        let port = find_free_port();
+        let _test_server = TestServer::start(
+            port,
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"head unknown"}}"#
+                    .to_vec(),
+            ],
+        );
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
+
+        match result {
+            Err(BlockchainError::QueryFailed(msg)) => {
+                assert!(msg.contains("head unknown"), "{}", msg)
+            }
+            x => panic!("Expected QueryFailed, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_retrieve_transactions_limits_the_block_range_queried() {
+        let port = find_free_port();
+        let test_server = TestServer::start(
+            port,
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
+                br#"{"jsonrpc":"2.0","id":3,"result":[]}"#.to_vec(),
+            ],
+        );
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject.retrieve_transactions(
+            42,
+            1000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
+
+        let requests = test_server.requests_so_far();
+        let bodies: Vec<Value> = requests
+            .into_iter()
+            .map(|request| serde_json::from_slice(&request.body()).unwrap())
+            .collect();
+        assert_eq!(bodies[1]["params"][0]["fromBlock"].to_string(), "\"0x2a\"");
+        assert_eq!(bodies[1]["params"][0]["toBlock"].to_string(), "\"0x411\"");
+        assert_eq!(
+            result,
+            Ok(RetrievedBlockchainTransactions {
+                new_start_block: 42 + 1000,
+                last_confirmed_block: 0x4be667,
+                transactions: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_retrieve_transactions_recognizes_too_broad_log_queries()
+    {
+        let port = find_free_port();
+        let _test_server = TestServer::start(
+            port,
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
+                br#"{"jsonrpc":"2.0","id":3,"error":{"code":-32005,"message":"query returned more than 10000 results"}}"#.to_vec(),
+            ],
+        );
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
+
+        match result {
+            Err(BlockchainError::LogQueryTooBroad(msg)) => {
+                assert!(
+                    msg.contains("query returned more than 10000 results"),
+                    "{}",
+                    msg
+                )
+            }
+            x => panic!("Expected LogQueryTooBroad, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_retrieve_transactions_does_not_take_rate_limiting_for_too_broad_log_queries(
+    ) {
+        let port = find_free_port();
+        let _test_server = TestServer::start(
+            port,
+            vec![
+                br#"{"jsonrpc":"2.0","id":2,"result":"0x4be673"}"#.to_vec(),
+                br#"{"jsonrpc":"2.0","id":3,"error":{"code":-32005,"message":"daily request limit exceeded: too many requests"}}"#.to_vec(),
+            ],
+        );
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject.retrieve_transactions(
+            42,
+            100_000,
+            &[Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap()],
+        );
+
+        match result {
+            Err(BlockchainError::QueryFailed(msg)) => {
+                assert!(msg.contains("daily request limit exceeded"), "{}", msg)
+            }
+            x => panic!("Expected QueryFailed, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_can_retrieve_eth_balance_of_a_wallet() {
+        let port = find_free_port();
+        let _test_server = TestServer::start(
+            port,
+            vec![br#"{"jsonrpc":"2.0","id":0,"result":"0xFFFF"}"#.to_vec()],
+        );
+
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject
+            .get_gas_balance(
+                &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(result, U256::from(65_535));
+    }
+
+    #[test]
+    #[should_panic(expected = "No address for an uninitialized wallet!")]
+    fn blockchain_interface_non_clandestine_returns_an_error_when_requesting_eth_balance_of_an_invalid_wallet(
+    ) {
+        let port = 8545;
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result =
+            subject.get_gas_balance(&Wallet::new("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fQ"));
+
+        assert_eq!(result, Err(BlockchainError::InvalidAddress));
+    }
+
+    #[test]
+    fn blockchain_interface_non_clandestine_returns_an_error_for_unintelligible_response_to_requesting_eth_balance(
+    ) {
+        let port = find_free_port();
+        let _test_server = TestServer::start(
+            port,
+            vec![br#"{"jsonrpc":"2.0","id":0,"result":"0xFFFQ"}"#.to_vec()],
+        );
+
+        let (event_loop_handle, transport) = Http::new(&format!(
+            "http://{}:{}",
+            &Ipv4Addr::LOCALHOST.to_string(),
+            port
+        ))
         .unwrap();
         let subject = BlockchainInterfaceNonClandestine::new(
             transport,
@@ -1197,6 +1878,30 @@ mod tests {
         assert_eq!(result, U256::from(65_535));
     }
 
+    #[test]
+    fn blockchain_interface_non_clandestine_counts_received_transfers_ignoring_removed_logs() {
+        let port = find_free_port();
+        let _test_server = TestServer::start (port, vec![
+            br#"{"jsonrpc":"2.0","id":0,"result":[{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732a","blockNumber":"0x4be663","data":"0x0000000000000000000000000000000000000000000000000010000000000000","logIndex":"0x0","removed":false,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182","0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0681","transactionIndex":"0x0"},{"address":"0xcd6c588e005032dd882cd43bf53a32129be81302","blockHash":"0x1a24b9169cbaec3f6effa1f600b70c7ab9e8e86db44062b49132a4415d26732b","blockNumber":"0x4be662","data":"0x0000000000000000000000000000000000000000000000000020000000000000","logIndex":"0x0","removed":true,"topics":["0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef","0x0000000000000000000000003ab28ecedea6cdb6feed398e93ae8c7b316b1182","0x0000000000000000000000003f69f9efd4f2592fd70be8c32ecd9dce71c472fc"],"transactionHash":"0x955cec6ac4f832911ab894ce16aa22c3003f46deff3f7165b32700d2f5ff0680","transactionIndex":"0x0"}]}"#.to_vec()
+        ]);
+        let (event_loop_handle, transport) = Http::with_max_parallel(
+            &format!("http://{}:{}", &Ipv4Addr::LOCALHOST.to_string(), port),
+            REQUESTS_IN_PARALLEL,
+        )
+        .unwrap();
+        let subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            event_loop_handle,
+            TEST_DEFAULT_CHAIN,
+        );
+
+        let result = subject.get_received_transfer_count(
+            &Wallet::from_str("0x3f69f9efd4f2592fd70be8c32ecd9dce71c472fc").unwrap(),
+        );
+
+        assert_eq!(result, Ok(1));
+    }
+
     #[test]
     #[should_panic(expected = "No address for an uninitialized wallet!")]
     fn blockchain_interface_non_clandestine_returns_an_error_when_requesting_token_balance_of_an_invalid_wallet(
@@ -1489,6 +2194,7 @@ mod tests {
         let chain = Chain::EthMainnet;
         let mut subject =
             BlockchainInterfaceNonClandestine::new(transport, make_fake_event_loop_handle(), chain);
+        subject.disperse_contract_opt = None;
         let first_tx_parameters = TransactionParameters {
             nonce: Some(U256::from(4)),
             to: Some(subject.contract_address()),
@@ -1655,6 +2361,438 @@ mod tests {
         assert_eq!(accountant_recording.len(), 1)
     }
 
+    #[test]
+    fn send_payables_within_batch_pays_several_creditors_in_one_disperse_transaction() {
+        let prepare_params_arc = Arc::new(Mutex::new(vec![]));
+        let sign_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let new_payable_fingerprint_params_arc = Arc::new(Mutex::new(vec![]));
+        let append_transaction_to_batch_params_arc = Arc::new(Mutex::new(vec![]));
+        let batch_wide_timestamp = SystemTime::now();
+        let hash = make_tx_hash(0xd15e);
+        let mut signed_transaction = make_default_signed_transaction();
+        signed_transaction.transaction_hash = hash;
+        signed_transaction.raw_transaction = Bytes(vec![1, 2, 3, 4]);
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .sign_transaction_params(&sign_transaction_params_arc)
+            .sign_transaction_result(Ok(signed_transaction.clone()))
+            .batch_wide_timestamp_result(batch_wide_timestamp)
+            .send_new_payable_fingerprint_credentials_params(&new_payable_fingerprint_params_arc)
+            .append_transaction_to_batch_params(&append_transaction_to_batch_params_arc)
+            .submit_batch_result(Ok(vec![Ok(Value::String("blah".to_string()))]));
+        let transport = TestTransport::default()
+            .prepare_params(&prepare_params_arc)
+            .send_result(allowance_response(666_000_000));
+        let disperse_contract = H160::from_low_u64_be(0xd15e);
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            transport,
+            make_fake_event_loop_handle(),
+            Chain::EthMainnet,
+        )
+        .with_disperse_contract(disperse_contract);
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        let accounts = vec![
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                make_wallet("creditor1"),
+                111_000_000,
+                None,
+            ),
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                make_wallet("creditor2"),
+                222_000_000,
+                None,
+            ),
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                make_wallet("creditor3"),
+                333_000_000,
+                None,
+            ),
+        ];
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let unimportant_recipient = Recorder::new().start().recipient();
+
+        let result = subject.send_payables_within_batch(
+            &consuming_wallet,
+            123,
+            U256::from(7),
+            &unimportant_recipient,
+            &accounts,
+        );
+
+        assert_eq!(
+            result,
+            Ok(accounts
+                .iter()
+                .map(|account| Correct(PendingPayable {
+                    recipient_wallet: account.wallet.clone(),
+                    hash
+                }))
+                .collect())
+        );
+        let prepare_params = prepare_params_arc.lock().unwrap();
+        assert_eq!(prepare_params.len(), 1);
+        assert_eq!(prepare_params[0].0, "eth_call");
+        assert_eq!(
+            prepare_params[0].1[0]["to"],
+            json!(subject.contract_address())
+        );
+        assert_eq!(
+            prepare_params[0].1[0]["data"],
+            json!(format!(
+                "0xdd62ed3e{}{}",
+                address_word(consuming_wallet.address()),
+                address_word(disperse_contract)
+            ))
+        );
+        let mut sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
+        let (transaction_params, _, _) = sign_transaction_params.remove(0);
+        assert!(sign_transaction_params.is_empty());
+        assert_eq!(transaction_params.to, Some(disperse_contract));
+        assert_eq!(transaction_params.nonce, Some(U256::from(7)));
+        assert_eq!(
+            transaction_params.data,
+            Bytes(
+                BlockchainInterfaceNonClandestine::<TestTransport>::disperse_token_data(
+                    subject.contract_address(),
+                    &accounts
+                )
+            )
+        );
+        assert!(transaction_params.gas > U256::from(55_000 + 2 * 34_000));
+        assert!(transaction_params.gas < U256::from(3 * 55_000));
+        let new_payable_fingerprint_params = new_payable_fingerprint_params_arc.lock().unwrap();
+        assert_eq!(
+            new_payable_fingerprint_params[0].2,
+            vec![
+                (hash, 111_000_000),
+                (hash, 222_000_000),
+                (hash, 333_000_000)
+            ]
+        );
+        let append_transaction_to_batch_params =
+            append_transaction_to_batch_params_arc.lock().unwrap();
+        assert_eq!(append_transaction_to_batch_params.len(), 1);
+        assert_eq!(
+            append_transaction_to_batch_params[0].0,
+            signed_transaction.raw_transaction
+        );
+    }
+
+    #[test]
+    fn send_payables_within_batch_reports_a_failed_disperse_transaction_for_every_creditor() {
+        let hash = make_tx_hash(0xd15e);
+        let mut signed_transaction = make_default_signed_transaction();
+        signed_transaction.transaction_hash = hash;
+        let rpc_error = web3::Error::Rpc(Error {
+            code: ErrorCode::InternalError,
+            message: "insufficient allowance".to_string(),
+            data: None,
+        });
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .sign_transaction_result(Ok(signed_transaction))
+            .batch_wide_timestamp_result(SystemTime::now())
+            .submit_batch_result(Ok(vec![Err(rpc_error.clone())]));
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default().send_result(allowance_response(333_000_000)),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_disperse_contract(H160::from_low_u64_be(0xd15e));
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        let accounts = vec![
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                make_wallet("creditor1"),
+                111_000_000,
+                None,
+            ),
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                make_wallet("creditor2"),
+                222_000_000,
+                None,
+            ),
+        ];
+        let unimportant_recipient = Recorder::new().start().recipient();
+
+        let result = subject.send_payables_within_batch(
+            &make_paying_wallet(b"consuming"),
+            123,
+            U256::from(7),
+            &unimportant_recipient,
+            &accounts,
+        );
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                Failed(RpcPayableFailure {
+                    rpc_error: rpc_error.clone(),
+                    recipient_wallet: make_wallet("creditor1"),
+                    hash
+                }),
+                Failed(RpcPayableFailure {
+                    rpc_error,
+                    recipient_wallet: make_wallet("creditor2"),
+                    hash
+                }),
+            ])
+        );
+    }
+
+    #[test]
+    fn send_payables_within_batch_pays_creditors_one_by_one_if_the_allowance_falls_short() {
+        init_test_logging();
+        let test_name =
+            "send_payables_within_batch_pays_creditors_one_by_one_if_the_allowance_falls_short";
+        let sign_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .sign_transaction_params(&sign_transaction_params_arc)
+            .sign_transaction_result(Ok(make_default_signed_transaction()))
+            .sign_transaction_result(Ok(make_default_signed_transaction()))
+            .batch_wide_timestamp_result(SystemTime::now())
+            .submit_batch_result(Ok(vec![
+                Ok(Value::String("blah".to_string())),
+                Ok(Value::String("blah".to_string())),
+            ]));
+        let disperse_contract = H160::from_low_u64_be(0xd15e);
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default().send_result(allowance_response(332_999_999)),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_disperse_contract(disperse_contract);
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        subject.logger = Logger::new(test_name);
+        let first_recipient = make_wallet("creditor1");
+        let second_recipient = make_wallet("creditor2");
+        let accounts = vec![
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                first_recipient.clone(),
+                111_000_000,
+                None,
+            ),
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                second_recipient.clone(),
+                222_000_000,
+                None,
+            ),
+        ];
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let unimportant_recipient = Recorder::new().start().recipient();
+
+        let _ = subject.send_payables_within_batch(
+            &consuming_wallet,
+            123,
+            U256::from(7),
+            &unimportant_recipient,
+            &accounts,
+        );
+
+        let sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
+        assert_eq!(
+            sign_transaction_params
+                .iter()
+                .map(|(transaction_params, _, _)| transaction_params.clone())
+                .collect::<Vec<TransactionParameters>>(),
+            vec![
+                subject.transaction_parameters(&first_recipient, 111_000_000, U256::from(7), 123),
+                subject.transaction_parameters(&second_recipient, 222_000_000, U256::from(8), 123)
+            ]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Disperse contract {:?} may spend only 332999999 wei of {}, but 333000000 wei \
+             is due; paying creditors one by one",
+            test_name, disperse_contract, consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn send_payables_within_batch_pays_creditors_one_by_one_if_the_allowance_is_unknown() {
+        init_test_logging();
+        let test_name =
+            "send_payables_within_batch_pays_creditors_one_by_one_if_the_allowance_is_unknown";
+        let sign_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .sign_transaction_params(&sign_transaction_params_arc)
+            .sign_transaction_result(Ok(make_default_signed_transaction()))
+            .sign_transaction_result(Ok(make_default_signed_transaction()))
+            .batch_wide_timestamp_result(SystemTime::now())
+            .submit_batch_result(Ok(vec![
+                Ok(Value::String("blah".to_string())),
+                Ok(Value::String("blah".to_string())),
+            ]));
+        let disperse_contract = H160::from_low_u64_be(0xd15e);
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default(),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_disperse_contract(disperse_contract);
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        subject.logger = Logger::new(test_name);
+        let accounts = vec![make_payable_account(5555), make_payable_account(6666)];
+        let consuming_wallet = make_paying_wallet(b"consuming");
+        let unimportant_recipient = Recorder::new().start().recipient();
+
+        let _ = subject.send_payables_within_batch(
+            &consuming_wallet,
+            123,
+            U256::from(7),
+            &unimportant_recipient,
+            &accounts,
+        );
+
+        let sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
+        assert_eq!(sign_transaction_params.len(), 2);
+        assert!(sign_transaction_params
+            .iter()
+            .all(|(transaction_params, _, _)| transaction_params.to
+                == Some(subject.contract_address())));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Couldn't check the allowance of disperse contract {:?} for {}: ",
+            test_name, disperse_contract, consuming_wallet
+        ));
+    }
+
+    #[test]
+    fn mainnets_come_with_a_disperse_contract() {
+        [Chain::EthMainnet, Chain::PolyMainnet, Chain::PolyMumbai]
+            .into_iter()
+            .for_each(|chain| {
+                let subject = BlockchainInterfaceNonClandestine::new(
+                    TestTransport::default(),
+                    make_fake_event_loop_handle(),
+                    chain,
+                );
+
+                assert_eq!(
+                    subject.disperse_contract_opt,
+                    chain.rec().disperse_contract_opt
+                );
+                assert_eq!(subject.disperse_contract_opt.is_some(), chain.is_mainnet())
+            })
+    }
+
+    fn allowance_response(wei: u64) -> Value {
+        json!(format!("0x{:064x}", wei))
+    }
+
+    fn address_word(address: Address) -> String {
+        format!(
+            "{:0>64}",
+            json!(address).as_str().unwrap().trim_start_matches("0x")
+        )
+    }
+
+    #[test]
+    fn send_payables_within_batch_pays_a_lone_creditor_directly_despite_disperse_contract() {
+        let sign_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .sign_transaction_params(&sign_transaction_params_arc)
+            .sign_transaction_result(Ok(make_default_signed_transaction()))
+            .batch_wide_timestamp_result(SystemTime::now())
+            .submit_batch_result(Ok(vec![Ok(Value::String("blah".to_string()))]));
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default(),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_disperse_contract(H160::from_low_u64_be(0xd15e));
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        let recipient = make_wallet("creditor1");
+        let account = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            recipient.clone(),
+            111_000_000,
+            None,
+        );
+        let unimportant_recipient = Recorder::new().start().recipient();
+
+        let _ = subject.send_payables_within_batch(
+            &make_paying_wallet(b"consuming"),
+            123,
+            U256::from(7),
+            &unimportant_recipient,
+            &[account],
+        );
+
+        let sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
+        assert_eq!(
+            sign_transaction_params[0].0,
+            subject.transaction_parameters(&recipient, 111_000_000, U256::from(7), 123)
+        );
+    }
+
+    #[test]
+    fn disperse_token_data_encodes_a_disperse_token_call() {
+        fn word(value: u64) -> Vec<u8> {
+            let mut bytes = [0u8; 32];
+            U256::from(value).to_big_endian(&mut bytes);
+            bytes.to_vec()
+        }
+        fn address_word(address: Address) -> Vec<u8> {
+            let mut bytes = vec![0u8; 12];
+            bytes.extend_from_slice(&address.0);
+            bytes
+        }
+        let token = H160::from_low_u64_be(0x70c3);
+        let first_wallet = make_wallet("creditor1");
+        let second_wallet = make_wallet("creditor2");
+        let accounts = vec![
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                first_wallet.clone(),
+                1000,
+                None,
+            ),
+            make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+                second_wallet.clone(),
+                2000,
+                None,
+            ),
+        ];
+
+        let result = BlockchainInterfaceNonClandestine::<TestTransport>::disperse_token_data(
+            token, &accounts,
+        );
+
+        let expected = vec![
+            vec![0xc7, 0x3a, 0x2d, 0x60],
+            address_word(token),
+            word(0x60),
+            word(0xc0),
+            word(2),
+            address_word(first_wallet.address()),
+            address_word(second_wallet.address()),
+            word(2),
+            word(1000),
+            word(2000),
+        ]
+        .concat();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn dispersed_base_gas_limit_charges_intrinsic_gas_only_once() {
+        assert_eq!(
+            BlockchainInterfaceNonClandestine::<Http>::dispersed_base_gas_limit(
+                Chain::EthMainnet,
+                1
+            ),
+            55_000
+        );
+        assert_eq!(
+            BlockchainInterfaceNonClandestine::<Http>::dispersed_base_gas_limit(
+                Chain::EthMainnet,
+                3
+            ),
+            123_000
+        );
+        assert_eq!(
+            BlockchainInterfaceNonClandestine::<Http>::dispersed_base_gas_limit(
+                Chain::PolyMainnet,
+                2
+            ),
+            119_000
+        );
+    }
+
     #[test]
     fn non_clandestine_base_gas_limit_is_properly_set() {
         assert_eq!(
@@ -1730,14 +2868,15 @@ mod tests {
         let gas_price = 123;
         let nonce = U256::from(5);
 
-        let _ = subject.sign_transaction(
+        let transaction_parameters = subject.transaction_parameters(
             &make_wallet("wallet1"),
-            &consuming_wallet,
             1_000_000_000,
             nonce,
             gas_price,
         );
 
+        let _ = subject.sign_transaction(transaction_parameters, &consuming_wallet);
+
         let mut sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
         let (transaction_params, _, secret) = sign_transaction_params.remove(0);
         assert!(sign_transaction_params.is_empty());
@@ -1892,8 +3031,10 @@ mod tests {
         let gas_price = 123;
         let nonce = U256::from(1);
 
-        let result =
-            subject.sign_transaction(&recipient, &consuming_wallet, 444444, nonce, gas_price);
+        let transaction_parameters =
+            subject.transaction_parameters(&recipient, 444444, nonce, gas_price);
+
+        let result = subject.sign_transaction(transaction_parameters, &consuming_wallet);
 
         assert_eq!(
             result,
@@ -1903,6 +3044,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handle_new_transaction_delegates_signing_to_external_signer_if_there_is_one() {
+        let sign_transaction_params_arc = Arc::new(Mutex::new(vec![]));
+        let append_transaction_to_batch_params_arc = Arc::new(Mutex::new(vec![]));
+        let signed_transaction = ExternallySignedTransaction::new(Bytes(vec![1, 2, 3, 4]));
+        let external_signer = ExternalSignerMock::default()
+            .sign_transaction_params(&sign_transaction_params_arc)
+            .sign_transaction_result(Ok(signed_transaction.clone()));
+        let batch_payable_tools = BatchPayableToolsMock::<TestTransport>::default()
+            .append_transaction_to_batch_params(&append_transaction_to_batch_params_arc);
+        let mut subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default(),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_external_signer(Box::new(external_signer));
+        subject.batch_payable_tools = Box::new(batch_payable_tools);
+        let recipient = make_wallet("recipient");
+        let address_only_consuming_wallet = make_wallet("consuming");
+        let nonce = U256::from(5);
+
+        let result = subject.handle_new_transaction(
+            &recipient,
+            &address_only_consuming_wallet,
+            444444,
+            nonce,
+            123,
+        );
+
+        assert_eq!(result, Ok(signed_transaction.transaction_hash));
+        let expected_parameters = subject.transaction_parameters(&recipient, 444444, nonce, 123);
+        let mut sign_transaction_params = sign_transaction_params_arc.lock().unwrap();
+        let (from, transaction) = sign_transaction_params.remove(0);
+        assert_eq!(from, address_only_consuming_wallet.address());
+        assert_eq!(transaction.nonce, expected_parameters.nonce);
+        assert_eq!(transaction.to, expected_parameters.to);
+        assert_eq!(transaction.gas, expected_parameters.gas);
+        assert_eq!(transaction.gas_price, expected_parameters.gas_price);
+        assert_eq!(transaction.data, expected_parameters.data);
+        assert_eq!(transaction.chain_id, expected_parameters.chain_id);
+        assert!(sign_transaction_params.is_empty());
+        let append_transaction_to_batch_params =
+            append_transaction_to_batch_params_arc.lock().unwrap();
+        assert_eq!(
+            append_transaction_to_batch_params[0].0,
+            signed_transaction.raw_transaction
+        );
+    }
+
+    #[test]
+    fn handle_new_transaction_reports_refusal_of_external_signer() {
+        let external_signer = ExternalSignerMock::default().sign_transaction_result(Err(
+            ExternalSignerError::Refused("Request denied".to_string()),
+        ));
+        let subject = BlockchainInterfaceNonClandestine::new(
+            TestTransport::default(),
+            make_fake_event_loop_handle(),
+            Chain::PolyMumbai,
+        )
+        .with_external_signer(Box::new(external_signer));
+
+        let result = subject.handle_new_transaction(
+            &make_wallet("recipient"),
+            &make_wallet("consuming"),
+            444444,
+            U256::from(5),
+            123,
+        );
+
+        assert_eq!(
+            result,
+            Err(PayableTransactionError::Signing(
+                "External signer refused to sign: Request denied".to_string()
+            ))
+        );
+    }
+
     fn test_consuming_wallet_with_secret() -> Wallet {
         let key_pair = Bip32ECKeyProvider::from_raw_secret(
             &decode_hex("97923d8fd8de4a00f912bfb77ef483141dec551bd73ea59343ef5c4aac965d04")
@@ -2367,18 +3585,20 @@ mod tests {
             BlockchainError::QueryFailed(
                 "Don't query so often, it gives me a headache".to_string(),
             ),
+            BlockchainError::LogQueryTooBroad("Too many results, narrow it down".to_string()),
         ];
         let pretty_print_closure = |err_to_resolve: &BlockchainError| match err_to_resolve {
             BlockchainError::InvalidUrl => (err_to_resolve.to_string(), 11),
             BlockchainError::InvalidAddress => (err_to_resolve.to_string(), 22),
             BlockchainError::InvalidResponse => (err_to_resolve.to_string(), 33),
             BlockchainError::QueryFailed(..) => (err_to_resolve.to_string(), 44),
+            BlockchainError::LogQueryTooBroad(..) => (err_to_resolve.to_string(), 55),
         };
 
         let actual_error_msgs = collect_match_displayable_error_variants_in_exhaustive_mode(
             original_errors.as_slice(),
             pretty_print_closure,
-            vec![11, 22, 33, 44],
+            vec![11, 22, 33, 44, 55],
         );
 
         assert_eq!(
@@ -2388,6 +3608,7 @@ mod tests {
                 "Blockchain error: Invalid address",
                 "Blockchain error: Invalid response",
                 "Blockchain error: Query failed: Don't query so often, it gives me a headache",
+                "Blockchain error: Log query too broad: Too many results, narrow it down",
             ])
         )
     }
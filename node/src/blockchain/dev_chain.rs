@@ -0,0 +1,882 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use ethereum_types::{Address, H256, U256};
+use ethsign::Signature;
+use ethsign_crypto::Keccak256;
+use masq_lib::blockchains::chains::Chain;
+use masq_lib::logger::Logger;
+use masq_lib::utils::localhost;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use web3::types::Bytes;
+
+// Value of --blockchain-service-url that makes a Node on --chain dev run its own blockchain
+pub const EMBEDDED_DEV_CHAIN_URL: &str = "embedded";
+
+pub const DEV_CHAIN_STARTING_GAS_BALANCE_WEI: u128 = 100_000_000_000_000_000_000;
+pub const DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI: u128 = 1_000_000_000_000_000_000_000_000;
+
+const TRANSFER_EVENT_TOPIC: [u8; 32] = [
+    0xdd, 0xf2, 0x52, 0xad, 0x1b, 0xe2, 0xc8, 0x9b, 0x69, 0xc2, 0xb0, 0x68, 0xfc, 0x37, 0x8d, 0xaa,
+    0x95, 0x2b, 0xa7, 0xf1, 0x63, 0xc4, 0xa1, 0x16, 0x28, 0xf5, 0x5a, 0x4d, 0xf5, 0x23, 0xb3, 0xef,
+];
+const TRANSFER_METHOD_ID: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const BALANCE_OF_METHOD_ID: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+// Stand-in for Ganache: a tiny in-process JSON-RPC server that speaks the subset of the Ethereum
+// API BlockchainInterfaceNonClandestine uses, and models the MASQ token as a plain ERC-20.
+// Every accepted transaction is mined immediately in a block of its own.
+pub struct DevChainServer {
+    port: u16,
+    state: Arc<Mutex<DevChainState>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Drop for DevChainServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        // wake up the accepting thread so that it can notice
+        let _ = TcpStream::connect(SocketAddr::new(localhost(), self.port));
+    }
+}
+
+impl DevChainServer {
+    pub fn start(chain: Chain) -> io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddr::new(localhost(), 0))?;
+        let port = listener.local_addr()?.port();
+        let state = Arc::new(Mutex::new(DevChainState::new(chain)));
+        let running = Arc::new(AtomicBool::new(true));
+        let logger = Logger::new("DevChainServer");
+        info!(
+            logger,
+            "Embedded {} blockchain listening on port {}",
+            chain.rec().literal_identifier,
+            port
+        );
+        let thread_state = state.clone();
+        let thread_running = running.clone();
+        thread::spawn(move || {
+            for stream_result in listener.incoming() {
+                if !thread_running.load(Ordering::Relaxed) {
+                    break;
+                }
+                match stream_result {
+                    Ok(stream) => {
+                        let state = thread_state.clone();
+                        let logger = logger.clone();
+                        thread::spawn(move || Self::serve_connection(stream, state, logger));
+                    }
+                    Err(e) => warning!(logger, "Couldn't accept connection: {}", e),
+                }
+            }
+        });
+        Ok(Self {
+            port,
+            state,
+            running,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}:{}", localhost(), self.port)
+    }
+
+    pub fn state(&self) -> Arc<Mutex<DevChainState>> {
+        self.state.clone()
+    }
+
+    fn serve_connection(stream: TcpStream, state: Arc<Mutex<DevChainState>>, logger: Logger) {
+        let mut writer = match stream.try_clone() {
+            Ok(writer) => writer,
+            Err(_) => return,
+        };
+        let mut reader = BufReader::new(stream);
+        while let Some(body) = Self::read_request_body(&mut reader) {
+            let response = match serde_json::from_slice::<Value>(&body) {
+                Ok(Value::Array(calls)) => Value::Array(
+                    calls
+                        .into_iter()
+                        .map(|call| Self::respond(&state, call))
+                        .collect(),
+                ),
+                Ok(call) => Self::respond(&state, call),
+                Err(e) => json!({"jsonrpc": "2.0", "id": null,
+                    "error": {"code": -32700, "message": format!("Parse error: {}", e)}}),
+            };
+            debug!(logger, "Responding with {}", response);
+            let payload = response.to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                payload.len()
+            );
+            if writer.write_all(head.as_bytes()).is_err()
+                || writer.write_all(payload.as_bytes()).is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn read_request_body(reader: &mut BufReader<TcpStream>) -> Option<Vec<u8>> {
+        let mut content_length = 0usize;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).ok()? == 0 {
+                return None;
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().ok()?;
+                }
+            }
+        }
+        let mut body = vec![0u8; content_length];
+        reader.read_exact(&mut body).ok()?;
+        Some(body)
+    }
+
+    fn respond(state: &Arc<Mutex<DevChainState>>, call: Value) -> Value {
+        let id = call.get("id").cloned().unwrap_or(Value::Null);
+        let method = call.get("method").and_then(|m| m.as_str()).unwrap_or("");
+        let params = match call.get("params") {
+            Some(Value::Array(params)) => params.clone(),
+            _ => vec![],
+        };
+        let result = state
+            .lock()
+            .expect("Dev chain state poisoned")
+            .handle_call(method, &params);
+        match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => {
+                json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DevChainLog {
+    pub block_number: u64,
+    pub transaction_hash: H256,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DevChainReceipt {
+    block_number: u64,
+    from: Address,
+    to: Address,
+    gas_used: U256,
+    gas_price: U256,
+    success: bool,
+    log_opt: Option<DevChainLog>,
+}
+
+pub struct DevChainState {
+    chain_id: u64,
+    contract: Address,
+    block_number: u64,
+    gas_balances: HashMap<Address, U256>,
+    token_balances: HashMap<Address, U256>,
+    nonces: HashMap<Address, U256>,
+    logs: Vec<DevChainLog>,
+    receipts: HashMap<H256, DevChainReceipt>,
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+impl DevChainState {
+    pub fn new(chain: Chain) -> Self {
+        Self {
+            chain_id: chain.rec().num_chain_id,
+            contract: chain.rec().contract,
+            block_number: 0,
+            gas_balances: HashMap::new(),
+            token_balances: HashMap::new(),
+            nonces: HashMap::new(),
+            logs: vec![],
+            receipts: HashMap::new(),
+        }
+    }
+
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    pub fn mine_blocks(&mut self, count: u64) {
+        self.block_number += count
+    }
+
+    // Unknown addresses start out funded, the way Ganache funds its accounts
+    pub fn gas_balance(&self, address: Address) -> U256 {
+        self.gas_balances
+            .get(&address)
+            .copied()
+            .unwrap_or_else(|| U256::from(DEV_CHAIN_STARTING_GAS_BALANCE_WEI))
+    }
+
+    pub fn set_gas_balance(&mut self, address: Address, balance: U256) {
+        self.gas_balances.insert(address, balance);
+    }
+
+    pub fn token_balance(&self, address: Address) -> U256 {
+        self.token_balances
+            .get(&address)
+            .copied()
+            .unwrap_or_else(|| U256::from(DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI))
+    }
+
+    pub fn set_token_balance(&mut self, address: Address, balance: U256) {
+        self.token_balances.insert(address, balance);
+    }
+
+    pub fn logs(&self) -> &[DevChainLog] {
+        &self.logs
+    }
+
+    // Mines a token transfer as if it had been sent from outside the Node, e.g. to pay its earning wallet
+    pub fn transfer_tokens(&mut self, from: Address, to: Address, amount: U256) -> H256 {
+        let nonce = self.nonce(from);
+        self.nonces.insert(from, nonce + 1);
+        let mut seed = from.0.to_vec();
+        seed.extend_from_slice(&to_word(nonce));
+        let transaction_hash = H256(seed.keccak256());
+        self.mine_transfer(transaction_hash, from, to, amount, U256::zero());
+        transaction_hash
+    }
+
+    fn handle_call(&mut self, method: &str, params: &[Value]) -> RpcResult {
+        match method {
+            "eth_blockNumber" => Ok(json!(U256::from(self.block_number))),
+            "eth_chainId" | "net_version" => Ok(json!(U256::from(self.chain_id))),
+            "eth_gasPrice" => Ok(json!(U256::from(1_000_000_000u64))),
+            "eth_getBalance" => Ok(json!(self.gas_balance(param(params, 0)?))),
+            "eth_getTransactionCount" => Ok(json!(self.nonce(param(params, 0)?))),
+            "eth_call" => self.call(params),
+            "eth_getLogs" => self.get_logs(params),
+            "eth_sendRawTransaction" => {
+                let raw: Bytes = param(params, 0)?;
+                self.send_raw_transaction(&raw.0)
+            }
+            "eth_getTransactionReceipt" => {
+                let hash: H256 = param(params, 0)?;
+                Ok(self
+                    .receipts
+                    .get(&hash)
+                    .map(|receipt| self.receipt_json(hash, receipt))
+                    .unwrap_or(Value::Null))
+            }
+            _ => Err((-32601, format!("Method {} not found", method))),
+        }
+    }
+
+    fn nonce(&self, address: Address) -> U256 {
+        self.nonces.get(&address).copied().unwrap_or_default()
+    }
+
+    fn call(&self, params: &[Value]) -> RpcResult {
+        let call = params
+            .get(0)
+            .ok_or_else(|| (-32602, "Missing call object".to_string()))?;
+        let to: Address = field(call, "to")?;
+        let data: Bytes = field(call, "data")?;
+        if to != self.contract || data.0.len() != 36 || data.0[0..4] != BALANCE_OF_METHOD_ID {
+            return Err((-32000, "execution reverted".to_string()));
+        }
+        let balance = self.token_balance(Address::from_slice(&data.0[16..36]));
+        Ok(json!(Bytes(to_word(balance))))
+    }
+
+    fn get_logs(&self, params: &[Value]) -> RpcResult {
+        let filter = params
+            .get(0)
+            .ok_or_else(|| (-32602, "Missing filter object".to_string()))?;
+        let from_block = self.block_param(filter.get("fromBlock"), 0)?;
+        let to_block = self.block_param(filter.get("toBlock"), self.block_number)?;
+        let addresses: Vec<Address> = match filter.get("address") {
+            None | Some(Value::Null) => vec![],
+            Some(Value::Array(addresses)) => addresses
+                .iter()
+                .map(|a| from_json(a.clone()))
+                .collect::<Result<_, _>>()?,
+            Some(address) => vec![from_json(address.clone())?],
+        };
+        let topics: Vec<Vec<H256>> = match filter.get("topics") {
+            Some(Value::Array(positions)) => positions
+                .iter()
+                .map(|position| match position {
+                    Value::Null => Ok(vec![]),
+                    Value::Array(alternatives) => alternatives
+                        .iter()
+                        .map(|t| from_json(t.clone()))
+                        .collect::<Result<Vec<H256>, _>>(),
+                    single => Ok(vec![from_json(single.clone())?]),
+                })
+                .collect::<Result<_, _>>()?,
+            _ => vec![],
+        };
+        if !addresses.is_empty() && !addresses.contains(&self.contract) {
+            return Ok(json!([]));
+        }
+        let logs = self
+            .logs
+            .iter()
+            .filter(|log| log.block_number >= from_block && log.block_number <= to_block)
+            .filter(|log| {
+                Self::log_topics(log)
+                    .iter()
+                    .zip(topics.iter())
+                    .all(|(topic, wanted)| wanted.is_empty() || wanted.contains(topic))
+            })
+            .map(|log| self.log_json(log))
+            .collect::<Vec<Value>>();
+        Ok(Value::Array(logs))
+    }
+
+    fn block_param(&self, value_opt: Option<&Value>, default: u64) -> Result<u64, (i64, String)> {
+        match value_opt {
+            None | Some(Value::Null) => Ok(default),
+            Some(Value::String(tag)) if tag == "latest" || tag == "pending" => {
+                Ok(self.block_number)
+            }
+            Some(Value::String(tag)) if tag == "earliest" => Ok(0),
+            Some(value) => from_json::<U256>(value.clone()).map(|n| n.low_u64()),
+        }
+    }
+
+    fn send_raw_transaction(&mut self, raw: &[u8]) -> RpcResult {
+        let transaction = DecodedTransaction::decode(raw, self.chain_id)
+            .map_err(|e| (-32602, format!("invalid transaction: {}", e)))?;
+        let expected_nonce = self.nonce(transaction.from);
+        if transaction.nonce < expected_nonce {
+            return Err((-32000, "nonce too low".to_string()));
+        }
+        if transaction.nonce > expected_nonce {
+            return Err((-32000, "nonce too high".to_string()));
+        }
+        let fee = transaction.gas * transaction.gas_price;
+        let gas_balance = self.gas_balance(transaction.from);
+        if gas_balance < fee + transaction.value {
+            return Err((
+                -32000,
+                "insufficient funds for gas * price + value".to_string(),
+            ));
+        }
+        self.nonces.insert(transaction.from, expected_nonce + 1);
+        self.gas_balances
+            .insert(transaction.from, gas_balance - fee - transaction.value);
+        let transaction_hash = H256(raw.keccak256());
+        let token_transfer_opt = if transaction.to == self.contract
+            && transaction.data.len() == 68
+            && transaction.data[0..4] == TRANSFER_METHOD_ID
+        {
+            Some((
+                Address::from_slice(&transaction.data[16..36]),
+                U256::from_big_endian(&transaction.data[36..68]),
+            ))
+        } else {
+            None
+        };
+        match token_transfer_opt {
+            Some((recipient, amount)) => {
+                self.mine_transfer(
+                    transaction_hash,
+                    transaction.from,
+                    recipient,
+                    amount,
+                    transaction.gas_price,
+                );
+                if let Some(receipt) = self.receipts.get_mut(&transaction_hash) {
+                    receipt.to = transaction.to;
+                    receipt.gas_used = transaction.gas;
+                }
+            }
+            None => {
+                let recipient_balance = self.gas_balance(transaction.to);
+                self.gas_balances
+                    .insert(transaction.to, recipient_balance + transaction.value);
+                self.block_number += 1;
+                self.receipts.insert(
+                    transaction_hash,
+                    DevChainReceipt {
+                        block_number: self.block_number,
+                        from: transaction.from,
+                        to: transaction.to,
+                        gas_used: transaction.gas,
+                        gas_price: transaction.gas_price,
+                        success: true,
+                        log_opt: None,
+                    },
+                );
+            }
+        }
+        Ok(json!(transaction_hash))
+    }
+
+    fn mine_transfer(
+        &mut self,
+        transaction_hash: H256,
+        from: Address,
+        to: Address,
+        amount: U256,
+        gas_price: U256,
+    ) {
+        self.block_number += 1;
+        let sender_balance = self.token_balance(from);
+        let log_opt = if sender_balance >= amount {
+            let recipient_balance = self.token_balance(to);
+            self.token_balances.insert(from, sender_balance - amount);
+            self.token_balances.insert(to, recipient_balance + amount);
+            let log = DevChainLog {
+                block_number: self.block_number,
+                transaction_hash,
+                from,
+                to,
+                amount,
+            };
+            self.logs.push(log.clone());
+            Some(log)
+        } else {
+            None
+        };
+        self.receipts.insert(
+            transaction_hash,
+            DevChainReceipt {
+                block_number: self.block_number,
+                from,
+                to: self.contract,
+                gas_used: U256::zero(),
+                gas_price,
+                success: log_opt.is_some(),
+                log_opt,
+            },
+        );
+    }
+
+    fn log_topics(log: &DevChainLog) -> Vec<H256> {
+        vec![
+            H256(TRANSFER_EVENT_TOPIC),
+            H256::from(log.from),
+            H256::from(log.to),
+        ]
+    }
+
+    fn block_hash(block_number: u64) -> H256 {
+        H256(block_number.to_be_bytes().keccak256())
+    }
+
+    fn log_json(&self, log: &DevChainLog) -> Value {
+        json!({
+            "address": self.contract,
+            "blockHash": Self::block_hash(log.block_number),
+            "blockNumber": U256::from(log.block_number),
+            "data": Bytes(to_word(log.amount)),
+            "logIndex": "0x0",
+            "removed": false,
+            "topics": Self::log_topics(log),
+            "transactionHash": log.transaction_hash,
+            "transactionIndex": "0x0"
+        })
+    }
+
+    fn receipt_json(&self, hash: H256, receipt: &DevChainReceipt) -> Value {
+        json!({
+            "transactionHash": hash,
+            "blockHash": Self::block_hash(receipt.block_number),
+            "blockNumber": U256::from(receipt.block_number),
+            "contractAddress": null,
+            "cumulativeGasUsed": receipt.gas_used,
+            "effectiveGasPrice": receipt.gas_price,
+            "from": receipt.from,
+            "gasUsed": receipt.gas_used,
+            "logs": receipt.log_opt.iter().map(|log| self.log_json(log)).collect::<Vec<Value>>(),
+            "logsBloom": Bytes(vec![0u8; 256]),
+            "status": if receipt.success { "0x1" } else { "0x0" },
+            "to": receipt.to,
+            "transactionIndex": "0x0",
+            "type": "0x0"
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct DecodedTransaction {
+    from: Address,
+    nonce: U256,
+    gas_price: U256,
+    gas: U256,
+    to: Address,
+    value: U256,
+    data: Vec<u8>,
+}
+
+impl DecodedTransaction {
+    // Legacy transactions signed per EIP-155, which is what web3 produces for us
+    fn decode(raw: &[u8], chain_id: u64) -> Result<Self, String> {
+        let rlp = rlp::Rlp::new(raw);
+        let item = |index: usize| Self::item(&rlp, index);
+        if rlp.item_count().map_err(|e| format!("{:?}", e))? != 9 {
+            return Err("expected nine fields".to_string());
+        }
+        if item(3)?.len() != 20 {
+            return Err("contract creation is not supported".to_string());
+        }
+        let v = U256::from_big_endian(item(6)?).low_u64();
+        let recovery_id = v
+            .checked_sub(chain_id * 2 + 35)
+            .filter(|id| *id <= 1)
+            .ok_or_else(|| format!("v of {} doesn't match chain id {}", v, chain_id))?;
+        let mut unsigned = rlp::RlpStream::new_list(9);
+        for index in 0..6 {
+            unsigned.append_raw(rlp.at(index).map_err(|e| format!("{:?}", e))?.as_raw(), 1);
+        }
+        unsigned.append(&chain_id).append(&0u8).append(&0u8);
+        let signature = Signature {
+            v: recovery_id as u8,
+            r: Self::word(item(7)?),
+            s: Self::word(item(8)?),
+        };
+        let public_key = signature
+            .recover(&unsigned.out().keccak256())
+            .map_err(|e| format!("{:?}", e))?;
+        Ok(Self {
+            from: Address::from_slice(public_key.address()),
+            nonce: U256::from_big_endian(item(0)?),
+            gas_price: U256::from_big_endian(item(1)?),
+            gas: U256::from_big_endian(item(2)?),
+            to: Address::from_slice(item(3)?),
+            value: U256::from_big_endian(item(4)?),
+            data: item(5)?.to_vec(),
+        })
+    }
+
+    fn item<'a>(rlp: &rlp::Rlp<'a>, index: usize) -> Result<&'a [u8], String> {
+        rlp.at(index)
+            .and_then(|item| item.data())
+            .map_err(|e| format!("{:?}", e))
+    }
+
+    fn word(bytes: &[u8]) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        word[32 - bytes.len().min(32)..].copy_from_slice(&bytes[bytes.len().saturating_sub(32)..]);
+        word
+    }
+}
+
+fn to_word(number: U256) -> Vec<u8> {
+    let mut word = vec![0u8; 32];
+    number.to_big_endian(&mut word);
+    word
+}
+
+fn param<T: serde::de::DeserializeOwned>(
+    params: &[Value],
+    index: usize,
+) -> Result<T, (i64, String)> {
+    from_json(params.get(index).cloned().unwrap_or(Value::Null))
+}
+
+fn field<T: serde::de::DeserializeOwned>(object: &Value, name: &str) -> Result<T, (i64, String)> {
+    from_json(object.get(name).cloned().unwrap_or(Value::Null))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: Value) -> Result<T, (i64, String)> {
+    serde_json::from_value(value).map_err(|e| (-32602, format!("Invalid params: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::test_utils::make_payable_account_with_wallet_and_balance_and_timestamp_opt;
+    use crate::blockchain::blockchain_interface::{
+        BlockchainInterface, BlockchainInterfaceNonClandestine, BlockchainTransaction,
+        ProcessedPayableFallible, REQUESTS_IN_PARALLEL,
+    };
+    use crate::test_utils::recorder::make_recorder;
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use actix::{Actor, System};
+    use web3::transports::Http;
+
+    fn make_interface(server: &DevChainServer) -> BlockchainInterfaceNonClandestine<Http> {
+        let (event_loop_handle, transport) =
+            Http::with_max_parallel(&server.url(), REQUESTS_IN_PARALLEL).unwrap();
+        BlockchainInterfaceNonClandestine::new(transport, event_loop_handle, Chain::Dev)
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(EMBEDDED_DEV_CHAIN_URL, "embedded");
+        assert_eq!(
+            DEV_CHAIN_STARTING_GAS_BALANCE_WEI,
+            100_000_000_000_000_000_000
+        );
+        assert_eq!(
+            DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI,
+            1_000_000_000_000_000_000_000_000
+        );
+        assert_eq!(
+            H256(TRANSFER_EVENT_TOPIC),
+            H256("Transfer(address,address,uint256)".keccak256())
+        );
+        assert_eq!(
+            TRANSFER_METHOD_ID,
+            "transfer(address,uint256)".keccak256()[0..4]
+        );
+        assert_eq!(BALANCE_OF_METHOD_ID, "balanceOf(address)".keccak256()[0..4]);
+    }
+
+    #[test]
+    fn balances_and_nonces_are_served_to_the_interface() {
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let wallet = make_wallet("dev chain wallet");
+        {
+            let state_arc = server.state();
+            let mut state = state_arc.lock().unwrap();
+            state.set_gas_balance(wallet.address(), U256::from(1234));
+            state.set_token_balance(wallet.address(), U256::from(5678));
+        }
+        let subject = make_interface(&server);
+
+        assert_eq!(subject.get_gas_balance(&wallet), Ok(U256::from(1234)));
+        assert_eq!(subject.get_token_balance(&wallet), Ok(U256::from(5678)));
+        assert_eq!(subject.get_transaction_count(&wallet), Ok(U256::zero()));
+    }
+
+    #[test]
+    fn incoming_token_transfers_are_retrievable_as_received_payments() {
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let payer = make_wallet("payer");
+        let earning_wallet = make_wallet("earning wallet");
+        let bystander = make_wallet("bystander");
+        let transaction_hash = {
+            let state_arc = server.state();
+            let mut state = state_arc.lock().unwrap();
+            state.mine_blocks(10);
+            let transaction_hash =
+                state.transfer_tokens(payer.address(), earning_wallet.address(), U256::from(4321));
+            state.transfer_tokens(payer.address(), bystander.address(), U256::from(1111));
+            transaction_hash
+        };
+        let subject = make_interface(&server);
+
+        let result = subject
+            .retrieve_transactions(5, 100_000, &[earning_wallet.clone()])
+            .unwrap();
+
+        assert_eq!(
+            result.transactions,
+            vec![BlockchainTransaction {
+                block_number: 11,
+                transaction_hash,
+                from: payer,
+                to: earning_wallet,
+                wei_amount: 4321,
+            }]
+        );
+        assert_eq!(result.new_start_block, 13);
+        assert_eq!(result.last_confirmed_block, 12);
+    }
+
+    #[test]
+    fn incoming_token_transfers_to_several_earning_wallets_are_retrieved_together() {
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let payer = make_wallet("payer");
+        let late_payer = make_wallet("late payer");
+        let earning_wallet = make_wallet("earning wallet");
+        let retired_earning_wallet = make_wallet("retired earning wallet");
+        let bystander = make_wallet("bystander");
+        let (first_hash, second_hash) = {
+            let state_arc = server.state();
+            let mut state = state_arc.lock().unwrap();
+            state.mine_blocks(10);
+            let first_hash =
+                state.transfer_tokens(payer.address(), earning_wallet.address(), U256::from(4321));
+            let second_hash = state.transfer_tokens(
+                late_payer.address(),
+                retired_earning_wallet.address(),
+                U256::from(1234),
+            );
+            state.transfer_tokens(payer.address(), bystander.address(), U256::from(1111));
+            (first_hash, second_hash)
+        };
+        let subject = make_interface(&server);
+
+        let result = subject
+            .retrieve_transactions(
+                5,
+                100_000,
+                &[earning_wallet.clone(), retired_earning_wallet.clone()],
+            )
+            .unwrap();
+
+        assert_eq!(
+            result.transactions,
+            vec![
+                BlockchainTransaction {
+                    block_number: 11,
+                    transaction_hash: first_hash,
+                    from: payer,
+                    to: earning_wallet,
+                    wei_amount: 4321,
+                },
+                BlockchainTransaction {
+                    block_number: 12,
+                    transaction_hash: second_hash,
+                    from: late_payer,
+                    to: retired_earning_wallet,
+                    wei_amount: 1234,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn payables_are_mined_and_move_tokens_end_to_end() {
+        let system = System::new("payables_are_mined_and_move_tokens_end_to_end");
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let consuming_wallet = make_paying_wallet(b"dev chain consuming wallet");
+        let creditor = make_wallet("creditor");
+        let (accountant, _, _) = make_recorder();
+        let fingerprints_recipient = accountant.start().recipient();
+        let account = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            creditor.clone(),
+            3_000_000,
+            None,
+        );
+        let subject = make_interface(&server);
+
+        let result = subject
+            .send_payables_within_batch(
+                &consuming_wallet,
+                1,
+                U256::zero(),
+                &fingerprints_recipient,
+                &[account],
+            )
+            .unwrap();
+
+        let hash = match &result[0] {
+            ProcessedPayableFallible::Correct(pending_payable) => pending_payable.hash,
+            other => panic!("Expected a successful payment, got {:?}", other),
+        };
+        let receipt = subject.get_transaction_receipt(hash).unwrap().unwrap();
+        assert_eq!(receipt.status, Some(1.into()));
+        assert_eq!(
+            subject.get_token_balance(&creditor),
+            Ok(U256::from(DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI) + U256::from(3_000_000))
+        );
+        assert_eq!(
+            subject.get_transaction_count(&consuming_wallet),
+            Ok(U256::one())
+        );
+        assert!(
+            subject.get_gas_balance(&consuming_wallet).unwrap()
+                < U256::from(DEV_CHAIN_STARTING_GAS_BALANCE_WEI)
+        );
+        let received = subject
+            .retrieve_transactions(0, 100_000, &[creditor.clone()])
+            .unwrap();
+        assert_eq!(
+            received.transactions,
+            vec![BlockchainTransaction {
+                block_number: 1,
+                transaction_hash: hash,
+                from: consuming_wallet.as_address_wallet(),
+                to: creditor,
+                wei_amount: 3_000_000,
+            }]
+        );
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn raw_transaction_with_stale_nonce_is_rejected() {
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let consuming_wallet = make_paying_wallet(b"stale nonce wallet");
+        {
+            let state_arc = server.state();
+            let mut state = state_arc.lock().unwrap();
+            state.transfer_tokens(
+                consuming_wallet.address(),
+                make_wallet("x").address(),
+                1.into(),
+            );
+        }
+        let (accountant, _, _) = make_recorder();
+        let system = System::new("raw_transaction_with_stale_nonce_is_rejected");
+        let fingerprints_recipient = accountant.start().recipient();
+        let account = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            make_wallet("creditor"),
+            1000,
+            None,
+        );
+        let subject = make_interface(&server);
+
+        let result = subject
+            .send_payables_within_batch(
+                &consuming_wallet,
+                1,
+                U256::zero(),
+                &fingerprints_recipient,
+                &[account],
+            )
+            .unwrap();
+
+        match &result[0] {
+            ProcessedPayableFallible::Failed(failure) => {
+                assert!(
+                    failure.rpc_error.to_string().contains("nonce too low"),
+                    "{:?}",
+                    failure
+                )
+            }
+            other => panic!("Expected a failure, got {:?}", other),
+        }
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn unknown_methods_are_reported_as_such() {
+        let mut subject = DevChainState::new(Chain::Dev);
+
+        let result = subject.handle_call("eth_mining", &[]);
+
+        assert_eq!(
+            result,
+            Err((-32601, "Method eth_mining not found".to_string()))
+        );
+    }
+
+    #[test]
+    fn unfunded_transfer_is_mined_as_a_failure_without_a_log() {
+        let mut subject = DevChainState::new(Chain::Dev);
+        let poor = make_wallet("poor").address();
+        subject.set_token_balance(poor, U256::from(5));
+
+        let hash = subject.transfer_tokens(poor, make_wallet("rich").address(), U256::from(6));
+
+        assert_eq!(subject.logs(), &[]);
+        assert_eq!(subject.block_number(), 1);
+        let receipt = subject
+            .handle_call("eth_getTransactionReceipt", &[json!(hash)])
+            .unwrap();
+        assert_eq!(receipt["status"], json!("0x0"));
+        assert_eq!(subject.token_balance(poor), U256::from(5));
+    }
+}
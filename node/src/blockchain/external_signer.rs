@@ -0,0 +1,308 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use ethsign_crypto::Keccak256;
+use futures::Future;
+use serde_json::{json, Value};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use web3::transports::{EventLoopHandle, Http};
+use web3::types::{Address, Bytes, TransactionParameters, H256};
+use web3::{Error, Transport};
+
+// Clef's method for signing a transaction without sending it
+pub const SIGN_TRANSACTION_METHOD: &str = "account_signTransaction";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternallySignedTransaction {
+    pub raw_transaction: Bytes,
+    pub transaction_hash: H256,
+}
+
+impl ExternallySignedTransaction {
+    pub fn new(raw_transaction: Bytes) -> Self {
+        let transaction_hash = H256(raw_transaction.0.keccak256());
+        Self {
+            raw_transaction,
+            transaction_hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExternalSignerError {
+    Unreachable(String),
+    Refused(String),
+    InvalidResponse(String),
+}
+
+impl Display for ExternalSignerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreachable(msg) => write!(f, "External signer is unreachable: {}", msg),
+            Self::Refused(msg) => write!(f, "External signer refused to sign: {}", msg),
+            Self::InvalidResponse(msg) => {
+                write!(f, "External signer gave an invalid response: {}", msg)
+            }
+        }
+    }
+}
+
+// Signs payable transactions for a consuming wallet whose private key the Node never sees
+pub trait ExternalSigner {
+    fn sign_transaction(
+        &self,
+        from: Address,
+        transaction: TransactionParameters,
+    ) -> Result<ExternallySignedTransaction, ExternalSignerError>;
+}
+
+// Talks to a Clef-style signer over HTTP JSON-RPC
+pub struct ExternalSignerReal {
+    // This must not be dropped for requests to be completed
+    _event_loop_handle: EventLoopHandle,
+    transport: Http,
+}
+
+impl ExternalSigner for ExternalSignerReal {
+    fn sign_transaction(
+        &self,
+        from: Address,
+        transaction: TransactionParameters,
+    ) -> Result<ExternallySignedTransaction, ExternalSignerError> {
+        let request = Self::request_json(from, transaction);
+        let response = self
+            .transport
+            .execute(SIGN_TRANSACTION_METHOD, vec![request])
+            .wait()
+            .map_err(Self::convert_error)?;
+        match response.get("raw") {
+            Some(raw) => serde_json::from_value::<Bytes>(raw.clone())
+                .map(ExternallySignedTransaction::new)
+                .map_err(|e| ExternalSignerError::InvalidResponse(e.to_string())),
+            None => Err(ExternalSignerError::InvalidResponse(format!(
+                "No raw transaction in {}",
+                response
+            ))),
+        }
+    }
+}
+
+impl ExternalSignerReal {
+    pub fn new(url: &str) -> Result<Self, ExternalSignerError> {
+        match Http::new(url) {
+            Ok((event_loop_handle, transport)) => Ok(Self {
+                _event_loop_handle: event_loop_handle,
+                transport,
+            }),
+            Err(e) => Err(ExternalSignerError::Unreachable(format!("{:?}", e))),
+        }
+    }
+
+    fn request_json(from: Address, transaction: TransactionParameters) -> Value {
+        json!({
+            "from": from,
+            "to": transaction.to,
+            "gas": transaction.gas,
+            "gasPrice": transaction.gas_price,
+            "value": transaction.value,
+            "nonce": transaction.nonce,
+            "data": transaction.data,
+            "chainId": transaction.chain_id.map(|chain_id| format!("{:#x}", chain_id)),
+        })
+    }
+
+    fn convert_error(error: Error) -> ExternalSignerError {
+        match error {
+            Error::Rpc(rpc_error) => ExternalSignerError::Refused(rpc_error.message),
+            unreachable @ (Error::Unreachable | Error::Transport(_)) => {
+                ExternalSignerError::Unreachable(unreachable.to_string())
+            }
+            other => ExternalSignerError::InvalidResponse(other.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accountant::test_utils::make_payable_account_with_wallet_and_balance_and_timestamp_opt;
+    use crate::blockchain::blockchain_interface::{
+        BlockchainInterface, BlockchainInterfaceNonClandestine, ProcessedPayableFallible,
+        REQUESTS_IN_PARALLEL,
+    };
+    use crate::blockchain::dev_chain::{DevChainServer, DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI};
+    use crate::blockchain::test_utils::ExternalSignerStandIn;
+    use crate::sub_lib::wallet::Wallet;
+    use crate::test_utils::recorder::make_recorder;
+    use crate::test_utils::{make_paying_wallet, make_wallet};
+    use actix::{Actor, System};
+    use masq_lib::blockchains::chains::Chain;
+    use masq_lib::utils::find_free_port;
+    use web3::types::U256;
+
+    fn make_transaction_parameters(nonce: u64) -> TransactionParameters {
+        TransactionParameters {
+            nonce: Some(nonce.into()),
+            to: Some(make_wallet("contract").address()),
+            gas: 55_000u64.into(),
+            gas_price: Some(1_000_000_000u64.into()),
+            value: 0u64.into(),
+            data: Bytes(vec![1, 2, 3, 4]),
+            chain_id: Some(2_000),
+        }
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(SIGN_TRANSACTION_METHOD, "account_signTransaction");
+    }
+
+    #[test]
+    fn external_signer_error_displays_properly() {
+        assert_eq!(
+            ExternalSignerError::Unreachable("booga".to_string()).to_string(),
+            "External signer is unreachable: booga"
+        );
+        assert_eq!(
+            ExternalSignerError::Refused("booga".to_string()).to_string(),
+            "External signer refused to sign: booga"
+        );
+        assert_eq!(
+            ExternalSignerError::InvalidResponse("booga".to_string()).to_string(),
+            "External signer gave an invalid response: booga"
+        );
+    }
+
+    #[test]
+    fn externally_signed_transaction_is_hashed_like_any_other() {
+        let raw_transaction = Bytes(vec![0xf8, 0x01, 0x02, 0x03]);
+
+        let result = ExternallySignedTransaction::new(raw_transaction.clone());
+
+        assert_eq!(
+            result,
+            ExternallySignedTransaction {
+                raw_transaction: raw_transaction.clone(),
+                transaction_hash: H256(raw_transaction.0.keccak256()),
+            }
+        );
+    }
+
+    #[test]
+    fn external_signer_signs_the_same_way_a_local_key_would() {
+        let wallet = make_paying_wallet(b"externally held key");
+        let stand_in = ExternalSignerStandIn::start(vec![wallet.clone()]).unwrap();
+        let subject = ExternalSignerReal::new(&stand_in.url()).unwrap();
+        let expected = ExternalSignerStandIn::sign_locally(&wallet, make_transaction_parameters(7));
+
+        let result = subject
+            .sign_transaction(wallet.address(), make_transaction_parameters(7))
+            .unwrap();
+
+        assert_eq!(result, expected);
+        let requests = stand_in.requests();
+        assert_eq!(requests.len(), 1);
+        let request = &requests[0];
+        assert_eq!(request["method"], json!(SIGN_TRANSACTION_METHOD));
+        let transaction = &request["params"][0];
+        assert_eq!(transaction["from"], json!(wallet.address()));
+        assert_eq!(transaction["nonce"], json!("0x7"));
+        assert_eq!(transaction["gas"], json!("0xd6d8"));
+        assert_eq!(transaction["gasPrice"], json!("0x3b9aca00"));
+        assert_eq!(transaction["data"], json!("0x01020304"));
+        assert_eq!(transaction["chainId"], json!("0x7d0"));
+    }
+
+    #[test]
+    fn external_signer_reports_refusal() {
+        let stand_in = ExternalSignerStandIn::start(vec![]).unwrap();
+        let subject = ExternalSignerReal::new(&stand_in.url()).unwrap();
+
+        let result = subject.sign_transaction(
+            make_wallet("unknown account").address(),
+            make_transaction_parameters(0),
+        );
+
+        assert_eq!(
+            result,
+            Err(ExternalSignerError::Refused("Request denied".to_string()))
+        );
+    }
+
+    #[test]
+    fn external_signer_reports_unreachability() {
+        let port = find_free_port();
+        let subject = ExternalSignerReal::new(&format!("http://127.0.0.1:{}", port)).unwrap();
+
+        let result = subject.sign_transaction(
+            make_wallet("any account").address(),
+            make_transaction_parameters(0),
+        );
+
+        match result {
+            Err(ExternalSignerError::Unreachable(_)) => (),
+            other => panic!("Expected Unreachable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn external_signer_rejects_malformed_url() {
+        let result = ExternalSignerReal::new("booga booga");
+
+        match result {
+            Err(ExternalSignerError::Unreachable(_)) => (),
+            Err(e) => panic!("Expected Unreachable, got {:?}", e),
+            Ok(_) => panic!("Expected Unreachable, got a signer"),
+        }
+    }
+
+    #[test]
+    fn payables_are_paid_from_an_address_only_consuming_wallet_via_external_signer() {
+        let system = System::new(
+            "payables_are_paid_from_an_address_only_consuming_wallet_via_external_signer",
+        );
+        let server = DevChainServer::start(Chain::Dev).unwrap();
+        let key_holder = make_paying_wallet(b"key held by the external signer");
+        let stand_in = ExternalSignerStandIn::start(vec![key_holder.clone()]).unwrap();
+        let consuming_wallet = Wallet::from(key_holder.address());
+        let creditor = make_wallet("creditor");
+        let (accountant, _, _) = make_recorder();
+        let fingerprints_recipient = accountant.start().recipient();
+        let account = make_payable_account_with_wallet_and_balance_and_timestamp_opt(
+            creditor.clone(),
+            2_500_000,
+            None,
+        );
+        let (event_loop_handle, transport) =
+            web3::transports::Http::with_max_parallel(&server.url(), REQUESTS_IN_PARALLEL).unwrap();
+        let subject =
+            BlockchainInterfaceNonClandestine::new(transport, event_loop_handle, Chain::Dev)
+                .with_external_signer(Box::new(ExternalSignerReal::new(&stand_in.url()).unwrap()));
+
+        let result = subject
+            .send_payables_within_batch(
+                &consuming_wallet,
+                1,
+                U256::zero(),
+                &fingerprints_recipient,
+                &[account],
+            )
+            .unwrap();
+
+        match &result[0] {
+            ProcessedPayableFallible::Correct(_) => (),
+            other => panic!("Expected a successful payment, got {:?}", other),
+        };
+        assert_eq!(
+            subject.get_token_balance(&creditor),
+            Ok(U256::from(DEV_CHAIN_STARTING_TOKEN_BALANCE_WEI) + U256::from(2_500_000))
+        );
+        assert_eq!(
+            subject.get_transaction_count(&consuming_wallet),
+            Ok(U256::one())
+        );
+        assert_eq!(stand_in.requests().len(), 1);
+        System::current().stop();
+        system.run();
+    }
+}
@@ -4,8 +4,11 @@ pub mod bip32;
 pub mod bip39;
 pub mod blockchain_bridge;
 pub mod blockchain_interface;
+pub mod dev_chain;
+pub mod external_signer;
 pub mod payer;
 pub mod signature;
+pub mod wallet_discovery;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -8,6 +8,7 @@ use crate::blockchain::blockchain_interface::{
     ProcessedPayableFallible, ResultForBalance, ResultForNonce, ResultForReceipt,
     REQUESTS_IN_PARALLEL,
 };
+use crate::blockchain::wallet_discovery::{WalletDiscoveryLauncher, WalletDiscoveryScan};
 use crate::sub_lib::wallet::Wallet;
 use actix::Recipient;
 use bip39::{Language, Mnemonic, Seed};
@@ -22,6 +23,19 @@ use std::time::SystemTime;
 
 use crate::accountant::database_access_objects::payable_dao::PayableAccount;
 use crate::blockchain::batch_payable_tools::BatchPayableTools;
+use crate::blockchain::dev_chain::DevChainServer;
+use crate::blockchain::external_signer::{
+    ExternalSigner, ExternalSignerError, ExternallySignedTransaction, SIGN_TRANSACTION_METHOD,
+};
+use futures::Future;
+use masq_lib::ui_gateway::NodeToUiMessage;
+use masq_lib::utils::localhost;
+use serde_json::{json, Value};
+use std::io;
+use std::io::{BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
 use web3::transports::{Batch, EventLoopHandle, Http};
 use web3::types::{Address, Bytes, SignedTransaction, TransactionParameters, U256};
 use web3::{BatchTransport, Error as Web3Error, Web3};
@@ -55,7 +69,7 @@ pub fn make_meaningless_seed() -> Seed {
 
 #[derive(Default)]
 pub struct BlockchainInterfaceMock {
-    retrieve_transactions_parameters: Arc<Mutex<Vec<(u64, Wallet)>>>,
+    retrieve_transactions_parameters: Arc<Mutex<Vec<(u64, u64, Vec<Wallet>)>>>,
     retrieve_transactions_results:
         RefCell<Vec<Result<RetrievedBlockchainTransactions, BlockchainError>>>,
     send_payables_within_batch_params: Arc<
@@ -80,6 +94,8 @@ pub struct BlockchainInterfaceMock {
     contract_address_results: RefCell<Vec<Address>>,
     get_transaction_count_parameters: Arc<Mutex<Vec<Wallet>>>,
     get_transaction_count_results: RefCell<Vec<BlockchainResult<U256>>>,
+    get_received_transfer_count_params: Arc<Mutex<Vec<Wallet>>>,
+    get_received_transfer_count_results: RefCell<Vec<BlockchainResult<u64>>>,
 }
 
 impl BlockchainInterface for BlockchainInterfaceMock {
@@ -90,12 +106,14 @@ impl BlockchainInterface for BlockchainInterfaceMock {
     fn retrieve_transactions(
         &self,
         start_block: u64,
-        recipient: &Wallet,
+        max_block_count: u64,
+        recipients: &[Wallet],
     ) -> Result<RetrievedBlockchainTransactions, BlockchainError> {
-        self.retrieve_transactions_parameters
-            .lock()
-            .unwrap()
-            .push((start_block, recipient.clone()));
+        self.retrieve_transactions_parameters.lock().unwrap().push((
+            start_block,
+            max_block_count,
+            recipients.to_vec(),
+        ));
         self.retrieve_transactions_results.borrow_mut().remove(0)
     }
 
@@ -146,6 +164,16 @@ impl BlockchainInterface for BlockchainInterfaceMock {
         self.get_transaction_count_results.borrow_mut().remove(0)
     }
 
+    fn get_received_transfer_count(&self, wallet: &Wallet) -> BlockchainResult<u64> {
+        self.get_received_transfer_count_params
+            .lock()
+            .unwrap()
+            .push(wallet.clone());
+        self.get_received_transfer_count_results
+            .borrow_mut()
+            .remove(0)
+    }
+
     fn get_transaction_receipt(&self, hash: H256) -> ResultForReceipt {
         self.get_transaction_receipt_params
             .lock()
@@ -156,7 +184,10 @@ impl BlockchainInterface for BlockchainInterfaceMock {
 }
 
 impl BlockchainInterfaceMock {
-    pub fn retrieve_transactions_params(mut self, params: &Arc<Mutex<Vec<(u64, Wallet)>>>) -> Self {
+    pub fn retrieve_transactions_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(u64, u64, Vec<Wallet>)>>>,
+    ) -> Self {
         self.retrieve_transactions_parameters = params.clone();
         self
     }
@@ -232,6 +263,18 @@ impl BlockchainInterfaceMock {
         self
     }
 
+    pub fn get_received_transfer_count_params(mut self, params: &Arc<Mutex<Vec<Wallet>>>) -> Self {
+        self.get_received_transfer_count_params = params.clone();
+        self
+    }
+
+    pub fn get_received_transfer_count_result(self, result: BlockchainResult<u64>) -> Self {
+        self.get_received_transfer_count_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
     pub fn get_transaction_receipt_params(mut self, params: &Arc<Mutex<Vec<H256>>>) -> Self {
         self.get_transaction_receipt_params = params.clone();
         self
@@ -516,3 +559,179 @@ pub fn make_default_signed_transaction() -> SignedTransaction {
 pub fn make_tx_hash(base: u32) -> H256 {
     H256::from_uint(&U256::from(base))
 }
+
+#[derive(Default)]
+pub struct ExternalSignerMock {
+    sign_transaction_params: Arc<Mutex<Vec<(Address, TransactionParameters)>>>,
+    sign_transaction_results:
+        RefCell<Vec<Result<ExternallySignedTransaction, ExternalSignerError>>>,
+}
+
+impl ExternalSigner for ExternalSignerMock {
+    fn sign_transaction(
+        &self,
+        from: Address,
+        transaction: TransactionParameters,
+    ) -> Result<ExternallySignedTransaction, ExternalSignerError> {
+        self.sign_transaction_params
+            .lock()
+            .unwrap()
+            .push((from, transaction));
+        self.sign_transaction_results.borrow_mut().remove(0)
+    }
+}
+
+impl ExternalSignerMock {
+    pub fn sign_transaction_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(Address, TransactionParameters)>>>,
+    ) -> Self {
+        self.sign_transaction_params = params.clone();
+        self
+    }
+
+    pub fn sign_transaction_result(
+        self,
+        result: Result<ExternallySignedTransaction, ExternalSignerError>,
+    ) -> Self {
+        self.sign_transaction_results.borrow_mut().push(result);
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct WalletDiscoveryLauncherMock {
+    launch_params: Arc<Mutex<Vec<WalletDiscoveryScan>>>,
+}
+
+impl WalletDiscoveryLauncher for WalletDiscoveryLauncherMock {
+    fn launch(&self, scan: WalletDiscoveryScan, _ui_sub: Recipient<NodeToUiMessage>) {
+        self.launch_params.lock().unwrap().push(scan);
+    }
+}
+
+impl WalletDiscoveryLauncherMock {
+    pub fn launch_params(mut self, params: &Arc<Mutex<Vec<WalletDiscoveryScan>>>) -> Self {
+        self.launch_params = params.clone();
+        self
+    }
+}
+
+// Stand-in for Clef: a local JSON-RPC server that signs transactions for the accounts whose keys
+// it holds and denies every other request, as Clef does when its operator says no.
+pub struct ExternalSignerStandIn {
+    port: u16,
+    requests: Arc<Mutex<Vec<Value>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl Drop for ExternalSignerStandIn {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        let _ = TcpStream::connect(SocketAddr::new(localhost(), self.port));
+    }
+}
+
+impl ExternalSignerStandIn {
+    pub fn start(accounts: Vec<Wallet>) -> io::Result<Self> {
+        let listener = TcpListener::bind(SocketAddr::new(localhost(), 0))?;
+        let port = listener.local_addr()?.port();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_requests = requests.clone();
+        let thread_running = running.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if !thread_running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let accounts = accounts.clone();
+                let requests = thread_requests.clone();
+                thread::spawn(move || Self::serve_connection(stream, accounts, requests));
+            }
+        });
+        Ok(Self {
+            port,
+            requests,
+            running,
+        })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}:{}", localhost(), self.port)
+    }
+
+    pub fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn sign_locally(
+        wallet: &Wallet,
+        transaction: TransactionParameters,
+    ) -> ExternallySignedTransaction {
+        let key = wallet.prepare_secp256k1_secret().unwrap();
+        let signed = Web3::new(TestTransport::default())
+            .accounts()
+            .sign_transaction(transaction, &key)
+            .wait()
+            .unwrap();
+        ExternallySignedTransaction::new(signed.raw_transaction)
+    }
+
+    fn serve_connection(
+        stream: TcpStream,
+        accounts: Vec<Wallet>,
+        requests: Arc<Mutex<Vec<Value>>>,
+    ) {
+        let mut writer = match stream.try_clone() {
+            Ok(writer) => writer,
+            Err(_) => return,
+        };
+        let mut reader = BufReader::new(stream);
+        while let Some(body) = DevChainServer::read_request_body(&mut reader) {
+            let call: Value = serde_json::from_slice(&body).unwrap();
+            requests.lock().unwrap().push(call.clone());
+            let response = match Self::respond(&accounts, &call) {
+                Ok(result) => json!({"jsonrpc": "2.0", "id": call["id"], "result": result}),
+                Err(message) => json!({"jsonrpc": "2.0", "id": call["id"],
+                    "error": {"code": -32000, "message": message}}),
+            };
+            let payload = response.to_string();
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+                payload.len()
+            );
+            if writer.write_all(head.as_bytes()).is_err()
+                || writer.write_all(payload.as_bytes()).is_err()
+            {
+                return;
+            }
+        }
+    }
+
+    fn respond(accounts: &[Wallet], call: &Value) -> Result<Value, String> {
+        if call["method"] != json!(SIGN_TRANSACTION_METHOD) {
+            return Err(format!("Method {} not supported", call["method"]));
+        }
+        let request = &call["params"][0];
+        let from: Address = serde_json::from_value(request["from"].clone()).unwrap();
+        let wallet = match accounts.iter().find(|wallet| wallet.address() == from) {
+            Some(wallet) => wallet,
+            None => return Err("Request denied".to_string()),
+        };
+        let chain_id = request["chainId"]
+            .as_str()
+            .map(|hex| u64::from_str_radix(hex.trim_start_matches("0x"), 16).unwrap());
+        let transaction = TransactionParameters {
+            nonce: serde_json::from_value(request["nonce"].clone()).unwrap(),
+            to: serde_json::from_value(request["to"].clone()).unwrap(),
+            gas: serde_json::from_value(request["gas"].clone()).unwrap(),
+            gas_price: serde_json::from_value(request["gasPrice"].clone()).unwrap(),
+            value: serde_json::from_value(request["value"].clone()).unwrap(),
+            data: serde_json::from_value(request["data"].clone()).unwrap(),
+            chain_id,
+        };
+        let signed = Self::sign_locally(wallet, transaction);
+        Ok(json!({"raw": signed.raw_transaction, "tx": request}))
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::bip32::Bip32ECKeyProvider;
+use crate::blockchain::blockchain_bridge::wei_to_saturated_gwei;
+use crate::blockchain::blockchain_interface::{
+    BlockchainError, BlockchainInterface, BlockchainInterfaceNonClandestine,
+};
+use crate::sub_lib::wallet::Wallet;
+use actix::Recipient;
+use masq_lib::blockchains::chains::Chain;
+use masq_lib::constants::WALLET_DISCOVERY_ERROR;
+use masq_lib::logger::Logger;
+use masq_lib::messages::{
+    ToMessageBody, UiDiscoverWalletsResponse, UiWalletCandidate, UiWalletDiscoveryProgressBroadcast,
+};
+use masq_lib::ui_gateway::{MessageBody, MessagePath, MessageTarget, NodeToUiMessage};
+use std::convert::TryFrom;
+use std::thread;
+use web3::transports::Http;
+use web3::types::U256;
+use web3::Transport;
+
+pub const DEFAULT_DISCOVERY_ACCOUNT_COUNT: u32 = 1;
+pub const DEFAULT_DISCOVERY_INDEX_COUNT: u32 = 20;
+pub const MAX_DISCOVERY_PATH_COUNT: u32 = 500;
+pub const DISCOVERY_PROGRESS_BATCH_SIZE: usize = 20;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct WalletDiscoveryScan {
+    pub client_id: u64,
+    pub context_id: u64,
+    pub seed: Vec<u8>,
+    pub account_count: u32,
+    pub index_count: u32,
+}
+
+// A scan makes several blocking calls to the blockchain service for every derivation path, so it
+// runs on a thread of its own rather than holding up the BlockchainBridge.
+pub trait WalletDiscoveryLauncher {
+    fn launch(&self, scan: WalletDiscoveryScan, ui_sub: Recipient<NodeToUiMessage>);
+}
+
+pub struct WalletDiscoveryLauncherReal {
+    blockchain_service_url: String,
+    chain: Chain,
+}
+
+impl WalletDiscoveryLauncher for WalletDiscoveryLauncherReal {
+    fn launch(&self, scan: WalletDiscoveryScan, ui_sub: Recipient<NodeToUiMessage>) {
+        let blockchain_service_url = self.blockchain_service_url.clone();
+        let chain = self.chain;
+        thread::spawn(move || {
+            let logger = Logger::new("WalletDiscovery");
+            let result = match Http::new(&blockchain_service_url) {
+                Ok((event_loop_handle, transport)) => {
+                    let blockchain_interface =
+                        BlockchainInterfaceNonClandestine::new(transport, event_loop_handle, chain);
+                    run_scan(&blockchain_interface, &scan, &ui_sub)
+                }
+                Err(e) => Err(format!("Couldn't reach the blockchain service: {:?}", e)),
+            };
+            let body = match result {
+                Ok(response) => response.tmb(scan.context_id),
+                Err(e_msg) => {
+                    warning!(logger, "Wallet discovery failed: {}", e_msg);
+                    MessageBody {
+                        opcode: "discoverWallets".to_string(),
+                        path: MessagePath::Conversation(scan.context_id),
+                        payload: Err((WALLET_DISCOVERY_ERROR, e_msg)),
+                    }
+                }
+            };
+            ui_sub
+                .try_send(NodeToUiMessage {
+                    target: MessageTarget::ClientId(scan.client_id),
+                    body,
+                })
+                .expect("UiGateway is dead")
+        });
+    }
+}
+
+impl WalletDiscoveryLauncherReal {
+    pub fn new(blockchain_service_url: String, chain: Chain) -> Self {
+        Self {
+            blockchain_service_url,
+            chain,
+        }
+    }
+}
+
+fn run_scan<T: Transport>(
+    blockchain_interface: &dyn BlockchainInterface<T>,
+    scan: &WalletDiscoveryScan,
+    ui_sub: &Recipient<NodeToUiMessage>,
+) -> Result<UiDiscoverWalletsResponse, String> {
+    discover_wallets(
+        blockchain_interface,
+        &scan.seed,
+        scan.account_count,
+        scan.index_count,
+        &mut |progress| {
+            ui_sub
+                .try_send(NodeToUiMessage {
+                    target: MessageTarget::ClientId(scan.client_id),
+                    body: progress.tmb(0),
+                })
+                .expect("UiGateway is dead")
+        },
+    )
+}
+
+// Standard BIP-44 paths for Ethereum-compatible chains: m/44'/60'/<account>'/0/<index>. Most
+// wallets vary the index (m/44'/60'/0'/0/n); some, like Ledger Live, vary the account instead.
+pub fn discovery_derivation_path(account: u32, index: u32) -> String {
+    format!("m/44'/60'/{}'/0/{}", account, index)
+}
+
+pub fn discovery_derivation_paths(account_count: u32, index_count: u32) -> Vec<String> {
+    (0..account_count)
+        .flat_map(|account| {
+            (0..index_count).map(move |index| discovery_derivation_path(account, index))
+        })
+        .collect()
+}
+
+pub fn validate_discovery_range(account_count: u32, index_count: u32) -> Result<(), String> {
+    if account_count == 0 || index_count == 0 {
+        return Err("Account count and index count must both be at least 1".to_string());
+    }
+    match account_count.checked_mul(index_count) {
+        Some(path_count) if path_count <= MAX_DISCOVERY_PATH_COUNT => Ok(()),
+        _ => Err(format!(
+            "Scanning {} accounts of {} indexes each would exceed the limit of {} derivation paths",
+            account_count, index_count, MAX_DISCOVERY_PATH_COUNT
+        )),
+    }
+}
+
+// A derivation path is a candidate if its wallet holds gas or MASQ, has ever sent a transaction
+// or has ever received MASQ. Wallets that only ever received gas and were later emptied won't
+// show up. Progress is reported after every batch of paths, with the candidates found in it.
+pub fn discover_wallets<T: Transport>(
+    blockchain_interface: &dyn BlockchainInterface<T>,
+    seed: &[u8],
+    account_count: u32,
+    index_count: u32,
+    report_progress: &mut dyn FnMut(UiWalletDiscoveryProgressBroadcast),
+) -> Result<UiDiscoverWalletsResponse, String> {
+    validate_discovery_range(account_count, index_count)?;
+    let paths = discovery_derivation_paths(account_count, index_count);
+    let mut candidates = vec![];
+    let mut scanned_path_count = 0;
+    for batch in paths.chunks(DISCOVERY_PROGRESS_BATCH_SIZE) {
+        let batch_candidates = batch
+            .iter()
+            .map(|path| examine_path(blockchain_interface, seed, path))
+            .collect::<Result<Vec<Option<UiWalletCandidate>>, String>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<UiWalletCandidate>>();
+        scanned_path_count += batch.len() as u32;
+        report_progress(UiWalletDiscoveryProgressBroadcast {
+            scanned_path_count,
+            total_path_count: paths.len() as u32,
+            candidates: batch_candidates.clone(),
+        });
+        candidates.extend(batch_candidates);
+    }
+    Ok(UiDiscoverWalletsResponse {
+        scanned_path_count,
+        candidates,
+    })
+}
+
+fn examine_path<T: Transport>(
+    blockchain_interface: &dyn BlockchainInterface<T>,
+    seed: &[u8],
+    path: &str,
+) -> Result<Option<UiWalletCandidate>, String> {
+    let wallet = Wallet::from(
+        Bip32ECKeyProvider::try_from((seed, path))
+            .map_err(|e| format!("Couldn't derive a key for {}: {}", path, e))?,
+    );
+    let describe = |what: &str, e: BlockchainError| {
+        format!("Couldn't get {} of {} ({}): {}", what, wallet, path, e)
+    };
+    let gas_balance = blockchain_interface
+        .get_gas_balance(&wallet)
+        .map_err(|e| describe("gas balance", e))?;
+    let masq_balance = blockchain_interface
+        .get_token_balance(&wallet)
+        .map_err(|e| describe("MASQ balance", e))?;
+    let transaction_count = blockchain_interface
+        .get_transaction_count(&wallet)
+        .map_err(|e| describe("transaction count", e))?;
+    let received_transfer_count = blockchain_interface
+        .get_received_transfer_count(&wallet)
+        .map_err(|e| describe("received transfers", e))?;
+    if gas_balance.is_zero()
+        && masq_balance.is_zero()
+        && transaction_count.is_zero()
+        && received_transfer_count == 0
+    {
+        return Ok(None);
+    }
+    Ok(Some(UiWalletCandidate {
+        derivation_path: path.to_string(),
+        address: wallet.to_string(),
+        gas_balance_gwei: wei_to_saturated_gwei(gas_balance),
+        masq_balance_gwei: wei_to_saturated_gwei(masq_balance),
+        transaction_count: saturated_u64(transaction_count),
+        received_transfer_count,
+    }))
+}
+
+fn saturated_u64(value: U256) -> u64 {
+    if value > U256::from(u64::MAX) {
+        u64::MAX
+    } else {
+        value.as_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::test_utils::{make_meaningless_seed, BlockchainInterfaceMock};
+    use std::sync::{Arc, Mutex};
+
+    fn wallet_at(path: &str) -> Wallet {
+        Wallet::from(
+            Bip32ECKeyProvider::try_from((make_meaningless_seed().as_bytes(), path)).unwrap(),
+        )
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DEFAULT_DISCOVERY_ACCOUNT_COUNT, 1);
+        assert_eq!(DEFAULT_DISCOVERY_INDEX_COUNT, 20);
+        assert_eq!(MAX_DISCOVERY_PATH_COUNT, 500);
+        assert_eq!(DISCOVERY_PROGRESS_BATCH_SIZE, 20);
+    }
+
+    #[test]
+    fn discovery_derivation_paths_cover_accounts_and_indexes() {
+        let result = discovery_derivation_paths(2, 3);
+
+        assert_eq!(
+            result,
+            vec![
+                "m/44'/60'/0'/0/0",
+                "m/44'/60'/0'/0/1",
+                "m/44'/60'/0'/0/2",
+                "m/44'/60'/1'/0/0",
+                "m/44'/60'/1'/0/1",
+                "m/44'/60'/1'/0/2",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_discovery_range_rejects_empty_and_oversized_ranges() {
+        assert_eq!(
+            validate_discovery_range(0, 20),
+            Err("Account count and index count must both be at least 1".to_string())
+        );
+        assert_eq!(
+            validate_discovery_range(3, 0),
+            Err("Account count and index count must both be at least 1".to_string())
+        );
+        assert_eq!(
+            validate_discovery_range(26, 20),
+            Err("Scanning 26 accounts of 20 indexes each would exceed the limit of 500 derivation paths".to_string())
+        );
+        assert_eq!(
+            validate_discovery_range(u32::MAX, u32::MAX),
+            Err(format!(
+                "Scanning {} accounts of {} indexes each would exceed the limit of 500 derivation paths",
+                u32::MAX,
+                u32::MAX
+            ))
+        );
+        assert_eq!(validate_discovery_range(25, 20), Ok(()));
+    }
+
+    #[test]
+    fn discover_wallets_reports_only_wallets_with_activity() {
+        let get_gas_balance_params_arc = Arc::new(Mutex::new(vec![]));
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_params(&get_gas_balance_params_arc)
+            .get_gas_balance_result(Ok(U256::zero()))
+            .get_token_balance_result(Ok(U256::zero()))
+            .get_transaction_count_result(Ok(U256::zero()))
+            .get_received_transfer_count_result(Ok(0))
+            .get_gas_balance_result(Ok(U256::from(3_000_000_000u64)))
+            .get_token_balance_result(Ok(U256::zero()))
+            .get_transaction_count_result(Ok(U256::zero()))
+            .get_received_transfer_count_result(Ok(0))
+            .get_gas_balance_result(Ok(U256::zero()))
+            .get_token_balance_result(Ok(U256::zero()))
+            .get_transaction_count_result(Ok(U256::from(7)))
+            .get_received_transfer_count_result(Ok(0))
+            .get_gas_balance_result(Ok(U256::zero()))
+            .get_token_balance_result(Ok(U256::from(5_000_000_000u64)))
+            .get_transaction_count_result(Ok(U256::zero()))
+            .get_received_transfer_count_result(Ok(0));
+        let seed = make_meaningless_seed();
+
+        let result = discover_wallets(&blockchain_interface, seed.as_bytes(), 2, 2, &mut |_| ());
+
+        assert_eq!(
+            result,
+            Ok(UiDiscoverWalletsResponse {
+                scanned_path_count: 4,
+                candidates: vec![
+                    UiWalletCandidate {
+                        derivation_path: "m/44'/60'/0'/0/1".to_string(),
+                        address: wallet_at("m/44'/60'/0'/0/1").to_string(),
+                        gas_balance_gwei: 3,
+                        masq_balance_gwei: 0,
+                        transaction_count: 0,
+                        received_transfer_count: 0,
+                    },
+                    UiWalletCandidate {
+                        derivation_path: "m/44'/60'/1'/0/0".to_string(),
+                        address: wallet_at("m/44'/60'/1'/0/0").to_string(),
+                        gas_balance_gwei: 0,
+                        masq_balance_gwei: 0,
+                        transaction_count: 7,
+                        received_transfer_count: 0,
+                    },
+                    UiWalletCandidate {
+                        derivation_path: "m/44'/60'/1'/0/1".to_string(),
+                        address: wallet_at("m/44'/60'/1'/0/1").to_string(),
+                        gas_balance_gwei: 0,
+                        masq_balance_gwei: 5,
+                        transaction_count: 0,
+                        received_transfer_count: 0,
+                    },
+                ]
+            })
+        );
+        let get_gas_balance_params = get_gas_balance_params_arc.lock().unwrap();
+        assert_eq!(
+            *get_gas_balance_params,
+            vec![
+                wallet_at("m/44'/60'/0'/0/0"),
+                wallet_at("m/44'/60'/0'/0/1"),
+                wallet_at("m/44'/60'/1'/0/0"),
+                wallet_at("m/44'/60'/1'/0/1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn discover_wallets_finds_wallet_that_only_received_masq() {
+        let get_received_transfer_count_params_arc = Arc::new(Mutex::new(vec![]));
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Ok(U256::zero()))
+            .get_token_balance_result(Ok(U256::zero()))
+            .get_transaction_count_result(Ok(U256::zero()))
+            .get_received_transfer_count_params(&get_received_transfer_count_params_arc)
+            .get_received_transfer_count_result(Ok(2));
+        let seed = make_meaningless_seed();
+
+        let result = discover_wallets(&blockchain_interface, seed.as_bytes(), 1, 1, &mut |_| ());
+
+        assert_eq!(
+            result,
+            Ok(UiDiscoverWalletsResponse {
+                scanned_path_count: 1,
+                candidates: vec![UiWalletCandidate {
+                    derivation_path: "m/44'/60'/0'/0/0".to_string(),
+                    address: wallet_at("m/44'/60'/0'/0/0").to_string(),
+                    gas_balance_gwei: 0,
+                    masq_balance_gwei: 0,
+                    transaction_count: 0,
+                    received_transfer_count: 2,
+                }]
+            })
+        );
+        let get_received_transfer_count_params =
+            get_received_transfer_count_params_arc.lock().unwrap();
+        assert_eq!(
+            *get_received_transfer_count_params,
+            vec![wallet_at("m/44'/60'/0'/0/0")]
+        );
+    }
+
+    #[test]
+    fn discover_wallets_reports_progress_batch_by_batch() {
+        let path_count = DISCOVERY_PROGRESS_BATCH_SIZE + 1;
+        let blockchain_interface =
+            (0..path_count).fold(BlockchainInterfaceMock::default(), |interface, idx| {
+                interface
+                    .get_gas_balance_result(Ok(U256::zero()))
+                    .get_token_balance_result(Ok(U256::zero()))
+                    .get_transaction_count_result(Ok(U256::from(if idx == 1 { 1 } else { 0 })))
+                    .get_received_transfer_count_result(Ok(if idx == path_count - 1 {
+                        1
+                    } else {
+                        0
+                    }))
+            });
+        let seed = make_meaningless_seed();
+        let mut progress_reports = vec![];
+
+        let result = discover_wallets(
+            &blockchain_interface,
+            seed.as_bytes(),
+            1,
+            path_count as u32,
+            &mut |progress| progress_reports.push(progress),
+        );
+
+        let response = result.unwrap();
+        assert_eq!(response.scanned_path_count, path_count as u32);
+        assert_eq!(response.candidates.len(), 2);
+        assert_eq!(
+            progress_reports,
+            vec![
+                UiWalletDiscoveryProgressBroadcast {
+                    scanned_path_count: DISCOVERY_PROGRESS_BATCH_SIZE as u32,
+                    total_path_count: path_count as u32,
+                    candidates: vec![response.candidates[0].clone()],
+                },
+                UiWalletDiscoveryProgressBroadcast {
+                    scanned_path_count: path_count as u32,
+                    total_path_count: path_count as u32,
+                    candidates: vec![response.candidates[1].clone()],
+                },
+            ]
+        );
+        assert_eq!(
+            response.candidates[1].derivation_path,
+            discovery_derivation_path(0, DISCOVERY_PROGRESS_BATCH_SIZE as u32)
+        );
+    }
+
+    #[test]
+    fn discover_wallets_stops_at_first_blockchain_error() {
+        let blockchain_interface = BlockchainInterfaceMock::default()
+            .get_gas_balance_result(Ok(U256::zero()))
+            .get_token_balance_result(Err(BlockchainError::QueryFailed("booga".to_string())));
+        let seed = make_meaningless_seed();
+
+        let result = discover_wallets(&blockchain_interface, seed.as_bytes(), 1, 5, &mut |_| ());
+
+        assert_eq!(
+            result,
+            Err(format!(
+                "Couldn't get MASQ balance of {} (m/44'/60'/0'/0/0): Blockchain error: Query failed: booga",
+                wallet_at("m/44'/60'/0'/0/0")
+            ))
+        );
+    }
+
+    #[test]
+    fn discover_wallets_rejects_bad_range_before_touching_blockchain() {
+        let blockchain_interface = BlockchainInterfaceMock::default();
+        let seed = make_meaningless_seed();
+
+        let result = discover_wallets(&blockchain_interface, seed.as_bytes(), 1, 501, &mut |_| ());
+
+        assert_eq!(
+            result,
+            Err("Scanning 1 accounts of 501 indexes each would exceed the limit of 500 derivation paths".to_string())
+        );
+    }
+
+    #[test]
+    fn saturated_u64_caps_at_u64_max() {
+        assert_eq!(saturated_u64(U256::from(u64::MAX) + 1), u64::MAX);
+        assert_eq!(saturated_u64(U256::from(1234)), 1234);
+    }
+}
@@ -24,7 +24,9 @@ use crate::privilege_drop::{IdWrapper, IdWrapperReal};
 use crate::server_initializer::LoggerInitializerWrapper;
 use crate::stream_handler_pool::StreamHandlerPoolSubs;
 use crate::sub_lib::accountant;
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
+use crate::sub_lib::accountant::{
+    DelinquencyPolicy, PaymentThresholds, RetiredEarningWallet, ScanIntervals,
+};
 use crate::sub_lib::blockchain_bridge::BlockchainBridgeConfig;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde_null::CryptDENull;
@@ -349,12 +351,14 @@ pub struct BootstrapperConfig {
     pub mapping_protocol_opt: Option<AutomapProtocol>,
     pub real_user: RealUser,
     pub payment_thresholds_opt: Option<PaymentThresholds>,
+    pub delinquency_policy: DelinquencyPolicy,
 
     // These fields must be set without privilege: otherwise the database will be created as root
     pub db_password_opt: Option<String>,
     pub clandestine_port_opt: Option<u16>,
     pub consuming_wallet_opt: Option<Wallet>,
     pub earning_wallet: Wallet,
+    pub retired_earning_wallets: Vec<RetiredEarningWallet>,
     pub neighborhood_config: NeighborhoodConfig,
 }
 
@@ -380,7 +384,10 @@ impl BootstrapperConfig {
             blockchain_bridge_config: BlockchainBridgeConfig {
                 blockchain_service_url_opt: None,
                 chain: TEST_DEFAULT_CHAIN,
+                confirmation_depth_opt: None,
+                external_signer_url_opt: None,
                 gas_price: 1,
+                low_balance_thresholds_opt: None,
             },
             port_configurations: HashMap::new(),
             data_directory: PathBuf::new(),
@@ -390,11 +397,13 @@ impl BootstrapperConfig {
             mapping_protocol_opt: None,
             real_user: RealUser::new(None, None, None),
             payment_thresholds_opt: Default::default(),
+            delinquency_policy: DelinquencyPolicy::default(),
 
             // These fields must be set without privilege: otherwise the database will be created as root
             db_password_opt: None,
             clandestine_port_opt: None,
             earning_wallet: accountant::DEFAULT_EARNING_WALLET.clone(),
+            retired_earning_wallets: vec![],
             consuming_wallet_opt: None,
             neighborhood_config: NeighborhoodConfig {
                 mode: NeighborhoodMode::ZeroHop,
@@ -409,15 +418,25 @@ impl BootstrapperConfig {
         self.blockchain_bridge_config.blockchain_service_url_opt = unprivileged
             .blockchain_bridge_config
             .blockchain_service_url_opt;
+        self.blockchain_bridge_config.external_signer_url_opt = unprivileged
+            .blockchain_bridge_config
+            .external_signer_url_opt;
+        self.blockchain_bridge_config.confirmation_depth_opt =
+            unprivileged.blockchain_bridge_config.confirmation_depth_opt;
+        self.blockchain_bridge_config.low_balance_thresholds_opt = unprivileged
+            .blockchain_bridge_config
+            .low_balance_thresholds_opt;
         self.clandestine_port_opt = unprivileged.clandestine_port_opt;
         self.neighborhood_config = unprivileged.neighborhood_config;
         self.earning_wallet = unprivileged.earning_wallet;
+        self.retired_earning_wallets = unprivileged.retired_earning_wallets;
         self.consuming_wallet_opt = unprivileged.consuming_wallet_opt;
         self.db_password_opt = unprivileged.db_password_opt;
         self.scan_intervals_opt = unprivileged.scan_intervals_opt;
         self.suppress_initial_scans = unprivileged.suppress_initial_scans;
         self.payment_thresholds_opt = unprivileged.payment_thresholds_opt;
         self.when_pending_too_long_sec = unprivileged.when_pending_too_long_sec;
+        self.delinquency_policy = unprivileged.delinquency_policy;
     }
 
     pub fn exit_service_rate(&self) -> u64 {
@@ -731,7 +750,8 @@ mod tests {
     use crate::server_initializer::LoggerInitializerWrapper;
     use crate::stream_handler_pool::StreamHandlerPoolSubs;
     use crate::stream_messages::AddStreamMsg;
-    use crate::sub_lib::accountant::ScanIntervals;
+    use crate::sub_lib::accountant::{RetiredEarningWallet, ScanIntervals};
+    use crate::sub_lib::blockchain_bridge::LowBalanceThresholds;
     use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::cryptde::{CryptDE, PlainData};
     use crate::sub_lib::cryptde_null::CryptDENull;
@@ -778,6 +798,7 @@ mod tests {
     use std::str::FromStr;
     use std::sync::{Arc, Mutex};
     use std::thread;
+    use std::time::SystemTime;
     use tokio;
     use tokio::executor::current_thread::CurrentThread;
     use tokio::prelude::stream::FuturesUnordered;
@@ -1231,26 +1252,51 @@ mod tests {
         //values from unprivileged config
         let gas_price = 123;
         let blockchain_url_opt = Some("some.service@earth.abc".to_string());
+        let external_signer_url_opt = Some("http://127.0.0.1:8550".to_string());
+        let confirmation_depth_opt = Some(64);
         let clandestine_port_opt = Some(44444);
         let neighborhood_config = NeighborhoodConfig {
             mode: NeighborhoodMode::OriginateOnly(vec![], rate_pack(9)),
             min_hops_count: MIN_HOPS_COUNT_FOR_TEST,
         };
         let earning_wallet = make_wallet("earning wallet");
+        let retired_earning_wallets = vec![RetiredEarningWallet {
+            wallet: make_wallet("retired earning wallet"),
+            watch_until: SystemTime::now(),
+        }];
         let consuming_wallet_opt = Some(make_wallet("consuming wallet"));
         let db_password_opt = Some("password".to_string());
+        let low_balance_thresholds_opt = Some(LowBalanceThresholds {
+            gas_gwei: 3_000,
+            masq_gwei: 4_000,
+        });
+        let delinquency_policy = DelinquencyPolicy {
+            grace_period_sec: 3_600,
+            notify_debtors: true,
+        };
         unprivileged_config.blockchain_bridge_config.gas_price = gas_price;
         unprivileged_config
             .blockchain_bridge_config
             .blockchain_service_url_opt = blockchain_url_opt.clone();
+        unprivileged_config
+            .blockchain_bridge_config
+            .external_signer_url_opt = external_signer_url_opt.clone();
+        unprivileged_config
+            .blockchain_bridge_config
+            .confirmation_depth_opt = confirmation_depth_opt;
+        unprivileged_config
+            .blockchain_bridge_config
+            .low_balance_thresholds_opt = low_balance_thresholds_opt;
         unprivileged_config.clandestine_port_opt = clandestine_port_opt;
         unprivileged_config.neighborhood_config = neighborhood_config.clone();
         unprivileged_config.earning_wallet = earning_wallet.clone();
+        unprivileged_config.retired_earning_wallets = retired_earning_wallets.clone();
         unprivileged_config.consuming_wallet_opt = consuming_wallet_opt.clone();
         unprivileged_config.db_password_opt = db_password_opt.clone();
         unprivileged_config.scan_intervals_opt = Some(ScanIntervals::default());
         unprivileged_config.suppress_initial_scans = false;
         unprivileged_config.when_pending_too_long_sec = DEFAULT_PENDING_TOO_LONG_SEC;
+        unprivileged_config.delinquency_policy = delinquency_policy;
 
         privileged_config.merge_unprivileged(unprivileged_config);
 
@@ -1265,9 +1311,31 @@ mod tests {
                 .blockchain_service_url_opt,
             blockchain_url_opt
         );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .external_signer_url_opt,
+            external_signer_url_opt
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .confirmation_depth_opt,
+            confirmation_depth_opt
+        );
+        assert_eq!(
+            privileged_config
+                .blockchain_bridge_config
+                .low_balance_thresholds_opt,
+            low_balance_thresholds_opt
+        );
         assert_eq!(privileged_config.clandestine_port_opt, clandestine_port_opt);
         assert_eq!(privileged_config.neighborhood_config, neighborhood_config);
         assert_eq!(privileged_config.earning_wallet, earning_wallet);
+        assert_eq!(
+            privileged_config.retired_earning_wallets,
+            retired_earning_wallets
+        );
         assert_eq!(privileged_config.consuming_wallet_opt, consuming_wallet_opt);
         assert_eq!(privileged_config.db_password_opt, db_password_opt);
         assert_eq!(
@@ -1279,6 +1347,7 @@ mod tests {
             privileged_config.when_pending_too_long_sec,
             DEFAULT_PENDING_TOO_LONG_SEC
         );
+        assert_eq!(privileged_config.delinquency_policy, delinquency_policy);
         //some values from the privileged config
         assert_eq!(privileged_config.log_level, Off);
         assert_eq!(
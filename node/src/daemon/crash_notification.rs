@@ -8,6 +8,7 @@ lazy_static! {
     static ref RECOGNIZERS: Vec<Box<dyn Recognizer>> = vec![
         Box::new(ChildWaitFailureRecognizer {}),
         Box::new(NoInformationRecognizer {}),
+        Box::new(ConfigurationErrorRecognizer {}),
         Box::new(UnrecognizedRecognizer {}),
     ];
 }
@@ -79,6 +80,26 @@ impl Recognizer for NoInformationRecognizer {
     }
 }
 
+struct ConfigurationErrorRecognizer {}
+
+const CONFIGURATION_ERROR_PREFIX: &str = "Configuration error";
+
+impl Recognizer for ConfigurationErrorRecognizer {
+    fn try_convert(
+        &self,
+        _exit_code_opt: Option<i32>,
+        stderr_opt: &Option<String>,
+    ) -> Option<CrashReason> {
+        match stderr_opt {
+            Some(stderr) if stderr.starts_with(CONFIGURATION_ERROR_PREFIX) => {
+                let err_msg = stderr.trim_start_matches(CONFIGURATION_ERROR_PREFIX);
+                Some(CrashReason::ConfigurationError(err_msg.trim().to_string()))
+            }
+            _ => None,
+        }
+    }
+}
+
 struct UnrecognizedRecognizer {}
 
 impl Recognizer for UnrecognizedRecognizer {
@@ -97,6 +118,7 @@ mod tests {
     #[test]
     fn constants_have_correct_values() {
         assert_eq!(CHILD_WAIT_FAILURE_PREFIX, "Child wait failure: ");
+        assert_eq!(CONFIGURATION_ERROR_PREFIX, "Configuration error");
     }
 
     #[test]
@@ -137,6 +159,22 @@ mod tests {
             })
     }
 
+    #[test]
+    fn recognizes_configuration_error() {
+        let subject = CrashNotification {
+            process_id: 0,
+            exit_code: Some(1),
+            stderr: Some("Configuration error\nchain - Booga\n".to_string()),
+        };
+
+        let result = subject.analyze();
+
+        assert_eq!(
+            result,
+            CrashReason::ConfigurationError("chain - Booga".to_string())
+        )
+    }
+
     #[test]
     fn eventually_gives_up() {
         let subject = CrashNotification {
@@ -2,6 +2,7 @@
 
 use crate::bootstrapper::RealUser;
 use crate::daemon::launcher::LauncherReal;
+use crate::daemon::restart_policy::RestartPolicy;
 use crate::daemon::{
     ChannelFactory, ChannelFactoryReal, Daemon, DaemonBindMessage, Launcher, Recipients,
 };
@@ -26,16 +27,26 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 pub trait RecipientsFactory {
-    fn make(&self, launcher: Box<dyn Launcher>, ui_port: u16) -> Recipients;
+    fn make(
+        &self,
+        launcher: Box<dyn Launcher>,
+        ui_port: u16,
+        restart_policy: RestartPolicy,
+    ) -> Recipients;
 }
 
 #[derive(Default)]
 pub struct RecipientsFactoryReal {}
 
 impl RecipientsFactory for RecipientsFactoryReal {
-    fn make(&self, launcher: Box<dyn Launcher>, ui_port: u16) -> Recipients {
+    fn make(
+        &self,
+        launcher: Box<dyn Launcher>,
+        ui_port: u16,
+        restart_policy: RestartPolicy,
+    ) -> Recipients {
         let ui_gateway_addr = UiGateway::new(&UiGatewayConfig { ui_port }, false).start();
-        let daemon_addr = Daemon::new(launcher).start();
+        let daemon_addr = Daemon::new(launcher).restart_policy(restart_policy).start();
         Recipients {
             ui_gateway_from_sub: ui_gateway_addr.clone().recipient(),
             ui_gateway_to_sub: ui_gateway_addr.clone().recipient(),
@@ -154,9 +165,11 @@ impl DaemonInitializerReal {
 
     fn bind(&mut self, sender: Sender<HashMap<String, String>>) {
         let launcher = LauncherReal::new(sender);
-        let recipients = self
-            .recipients_factory
-            .make(Box::new(launcher), self.config.ui_port);
+        let recipients = self.recipients_factory.make(
+            Box::new(launcher),
+            self.config.ui_port,
+            self.config.restart_policy.clone(),
+        );
         let bind_message = DaemonBindMessage {
             to_ui_message_recipient: recipients.ui_gateway_to_sub,
             from_ui_message_recipient: recipients.ui_gateway_from_sub,
@@ -207,15 +220,24 @@ mod tests {
     use std::ptr::addr_of;
     use std::str::FromStr;
     use std::sync::{Arc, Mutex};
+    use std::time::Duration;
 
     struct RecipientsFactoryMock {
-        make_params: Arc<Mutex<Vec<(Box<dyn Launcher>, u16)>>>,
+        make_params: Arc<Mutex<Vec<(Box<dyn Launcher>, u16, RestartPolicy)>>>,
         make_results: RefCell<Vec<Recipients>>,
     }
 
     impl RecipientsFactory for RecipientsFactoryMock {
-        fn make(&self, launcher: Box<dyn Launcher>, ui_port: u16) -> Recipients {
-            self.make_params.lock().unwrap().push((launcher, ui_port));
+        fn make(
+            &self,
+            launcher: Box<dyn Launcher>,
+            ui_port: u16,
+            restart_policy: RestartPolicy,
+        ) -> Recipients {
+            self.make_params
+                .lock()
+                .unwrap()
+                .push((launcher, ui_port, restart_policy));
             self.make_results.borrow_mut().remove(0)
         }
     }
@@ -228,6 +250,14 @@ mod tests {
             }
         }
 
+        fn make_params(
+            mut self,
+            params: &Arc<Mutex<Vec<(Box<dyn Launcher>, u16, RestartPolicy)>>>,
+        ) -> Self {
+            self.make_params = params.clone();
+            self
+        }
+
         fn make_result(self, result: Recipients) -> Self {
             self.make_results.borrow_mut().push(result);
             self
@@ -330,9 +360,20 @@ mod tests {
             .data_dir_result(Some(home_dir.join("data")));
         let logger_initializer_wrapper = LoggerInitializerWrapperMock::new();
         let port = find_free_port();
-        let config = InitializationConfig { ui_port: port };
+        let restart_policy = RestartPolicy {
+            max_restarts: 2,
+            window: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(3),
+        };
+        let config = InitializationConfig {
+            ui_port: port,
+            restart_policy: restart_policy.clone(),
+        };
         let channel_factory = ChannelFactoryMock::new();
-        let addr_factory = RecipientsFactoryMock::new().make_result(recipients);
+        let make_params_arc = Arc::new(Mutex::new(vec![]));
+        let addr_factory = RecipientsFactoryMock::new()
+            .make_params(&make_params_arc)
+            .make_result(recipients);
         let rerunner = RerunnerMock::new();
         let clustered_params = DIClusteredParams {
             dirs_wrapper: Box::new(dirs_wrapper),
@@ -353,6 +394,9 @@ mod tests {
         let _ = daemon_recording.get_record::<DaemonBindMessage>(0);
         assert_eq!(ui_gateway_recording.len(), 1);
         assert_eq!(daemon_recording.len(), 1);
+        let make_params = make_params_arc.lock().unwrap();
+        assert_eq!(make_params[0].1, port);
+        assert_eq!(make_params[0].2, restart_policy);
     }
 
     #[test]
@@ -368,7 +412,10 @@ mod tests {
             .data_dir_result(Some(home_dir.join("data")));
         let logger_initializer_wrapper = LoggerInitializerWrapperMock::new();
         let port = find_free_port();
-        let config = InitializationConfig { ui_port: port };
+        let config = InitializationConfig {
+            ui_port: port,
+            restart_policy: RestartPolicy::default(),
+        };
         let (sender, receiver) = unbounded();
         let channel_factory = ChannelFactoryMock::new();
         let addr_factory = RecipientsFactoryMock::new();
@@ -423,8 +470,13 @@ mod tests {
             recipients_factory: Box::new(RecipientsFactoryMock::new()),
             rerunner: Box::new(RerunnerMock::new()),
         };
-        let mut subject =
-            DaemonInitializerReal::new(InitializationConfig { ui_port: port }, clustered_params);
+        let mut subject = DaemonInitializerReal::new(
+            InitializationConfig {
+                ui_port: port,
+                restart_policy: RestartPolicy::default(),
+            },
+            clustered_params,
+        );
         let mut holder = FakeStreamHolder::new();
 
         let result = subject.go(&mut holder.streams(), &[]);
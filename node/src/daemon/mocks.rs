@@ -5,6 +5,7 @@
 use crate::daemon::launch_verifier::{
     ClientBuilderWrapper, ClientWrapper, LaunchVerification, LaunchVerifier, VerifierTools,
 };
+use crate::daemon::setup_profiles::{SetupProfile, SetupProfileStore};
 use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
 use websocket::client::ParseError;
@@ -205,3 +206,62 @@ impl ClientBuilderWrapperMock {
         self
     }
 }
+
+#[derive(Default)]
+pub struct SetupProfileStoreMock {
+    save_params: Arc<Mutex<Vec<(String, SetupProfile)>>>,
+    save_results: RefCell<Vec<Result<(), String>>>,
+    load_params: Arc<Mutex<Vec<String>>>,
+    load_results: RefCell<Vec<Result<SetupProfile, String>>>,
+    list_results: RefCell<Vec<Result<Vec<String>, String>>>,
+}
+
+impl SetupProfileStore for SetupProfileStoreMock {
+    fn save(&self, name: &str, profile: &SetupProfile) -> Result<(), String> {
+        self.save_params
+            .lock()
+            .unwrap()
+            .push((name.to_string(), profile.clone()));
+        self.save_results.borrow_mut().remove(0)
+    }
+
+    fn load(&self, name: &str) -> Result<SetupProfile, String> {
+        self.load_params.lock().unwrap().push(name.to_string());
+        self.load_results.borrow_mut().remove(0)
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        self.list_results.borrow_mut().remove(0)
+    }
+}
+
+impl SetupProfileStoreMock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn save_params(mut self, params: &Arc<Mutex<Vec<(String, SetupProfile)>>>) -> Self {
+        self.save_params = params.clone();
+        self
+    }
+
+    pub fn save_result(self, result: Result<(), String>) -> Self {
+        self.save_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn load_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+        self.load_params = params.clone();
+        self
+    }
+
+    pub fn load_result(self, result: Result<SetupProfile, String>) -> Self {
+        self.load_results.borrow_mut().push(result);
+        self
+    }
+
+    pub fn list_result(self, result: Result<Vec<String>, String>) -> Self {
+        self.list_results.borrow_mut().push(result);
+        self
+    }
+}
@@ -0,0 +1,229 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use masq_lib::messages::CrashReason;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_MAX_RESTARTS: u64 = 5;
+pub const DEFAULT_RESTART_WINDOW_SECS: u64 = 3600;
+pub const DEFAULT_RESTART_BACKOFF_SECS: u64 = 1;
+pub const MAX_RESTART_BACKOFF_SECS: u64 = 300;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RestartPolicy {
+    pub max_restarts: u64,
+    pub window: Duration,
+    pub initial_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            window: Duration::from_secs(DEFAULT_RESTART_WINDOW_SECS),
+            initial_backoff: Duration::from_secs(DEFAULT_RESTART_BACKOFF_SECS),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RestartPlan {
+    pub attempt: u64,
+    pub delay: Duration,
+}
+
+pub struct RestartSupervisor {
+    policy: RestartPolicy,
+    restart_times: VecDeque<Instant>,
+}
+
+impl RestartSupervisor {
+    pub fn new(policy: RestartPolicy) -> Self {
+        Self {
+            policy,
+            restart_times: VecDeque::new(),
+        }
+    }
+
+    // Decides whether a Node that just terminated should be started again, and if so, how long
+    // to wait first. The wait doubles with every restart still inside the window.
+    pub fn plan_restart(
+        &mut self,
+        exit_code_opt: Option<i32>,
+        crash_reason: &CrashReason,
+        now: Instant,
+    ) -> Result<RestartPlan, String> {
+        if self.policy.max_restarts == 0 {
+            return Err("automatic restarts are disabled".to_string());
+        }
+        if exit_code_opt == Some(0) {
+            return Err("it shut down deliberately".to_string());
+        }
+        if let CrashReason::ConfigurationError(_) = crash_reason {
+            return Err("its configuration was rejected".to_string());
+        }
+        let window = self.policy.window;
+        self.restart_times
+            .retain(|restart_time| now.saturating_duration_since(*restart_time) < window);
+        let previous_restarts = self.restart_times.len() as u64;
+        if previous_restarts >= self.policy.max_restarts {
+            return Err(format!(
+                "it has already been restarted {} times in the last {} seconds",
+                previous_restarts,
+                window.as_secs()
+            ));
+        }
+        self.restart_times.push_back(now);
+        Ok(RestartPlan {
+            attempt: previous_restarts + 1,
+            delay: self.backoff(previous_restarts),
+        })
+    }
+
+    fn backoff(&self, previous_restarts: u64) -> Duration {
+        let max_backoff = Duration::from_secs(MAX_RESTART_BACKOFF_SECS);
+        u32::try_from(previous_restarts)
+            .ok()
+            .and_then(|exponent| 2u32.checked_pow(exponent))
+            .and_then(|factor| self.policy.initial_backoff.checked_mul(factor))
+            .map(|backoff| backoff.min(max_backoff))
+            .unwrap_or(max_backoff)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_subject(max_restarts: u64) -> RestartSupervisor {
+        RestartSupervisor::new(RestartPolicy {
+            max_restarts,
+            window: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(2),
+        })
+    }
+
+    fn crash() -> CrashReason {
+        CrashReason::Unrecognized("thread 'main' panicked".to_string())
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(DEFAULT_MAX_RESTARTS, 5);
+        assert_eq!(DEFAULT_RESTART_WINDOW_SECS, 3600);
+        assert_eq!(DEFAULT_RESTART_BACKOFF_SECS, 1);
+        assert_eq!(MAX_RESTART_BACKOFF_SECS, 300);
+    }
+
+    #[test]
+    fn default_policy_uses_default_constants() {
+        let result = RestartPolicy::default();
+
+        assert_eq!(
+            result,
+            RestartPolicy {
+                max_restarts: DEFAULT_MAX_RESTARTS,
+                window: Duration::from_secs(DEFAULT_RESTART_WINDOW_SECS),
+                initial_backoff: Duration::from_secs(DEFAULT_RESTART_BACKOFF_SECS),
+            }
+        );
+    }
+
+    #[test]
+    fn restarts_are_not_planned_when_disabled() {
+        let mut subject = make_subject(0);
+
+        let result = subject.plan_restart(Some(101), &crash(), Instant::now());
+
+        assert_eq!(result, Err("automatic restarts are disabled".to_string()));
+    }
+
+    #[test]
+    fn deliberate_shutdown_is_not_restarted() {
+        let mut subject = make_subject(5);
+
+        let result = subject.plan_restart(
+            Some(0),
+            &CrashReason::Unrecognized("Received shutdown order from client 1".to_string()),
+            Instant::now(),
+        );
+
+        assert_eq!(result, Err("it shut down deliberately".to_string()));
+    }
+
+    #[test]
+    fn configuration_error_is_not_restarted() {
+        let mut subject = make_subject(5);
+
+        let result = subject.plan_restart(
+            Some(1),
+            &CrashReason::ConfigurationError("chain - booga".to_string()),
+            Instant::now(),
+        );
+
+        assert_eq!(result, Err("its configuration was rejected".to_string()));
+    }
+
+    #[test]
+    fn backoff_doubles_with_every_restart_until_the_limit_is_reached() {
+        let mut subject = make_subject(3);
+        let now = Instant::now();
+
+        let results = (0..4)
+            .map(|n| subject.plan_restart(None, &crash(), now + Duration::from_secs(n)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(RestartPlan {
+                    attempt: 1,
+                    delay: Duration::from_secs(2)
+                }),
+                Ok(RestartPlan {
+                    attempt: 2,
+                    delay: Duration::from_secs(4)
+                }),
+                Ok(RestartPlan {
+                    attempt: 3,
+                    delay: Duration::from_secs(8)
+                }),
+                Err("it has already been restarted 3 times in the last 60 seconds".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn restarts_older_than_the_window_are_forgotten() {
+        let mut subject = make_subject(2);
+        let now = Instant::now();
+        let _ = subject.plan_restart(None, &crash(), now);
+        let _ = subject.plan_restart(None, &crash(), now + Duration::from_secs(30));
+
+        let result = subject.plan_restart(None, &crash(), now + Duration::from_secs(60));
+
+        assert_eq!(
+            result,
+            Ok(RestartPlan {
+                attempt: 2,
+                delay: Duration::from_secs(4)
+            })
+        );
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let subject = make_subject(100);
+
+        assert_eq!(subject.backoff(7), Duration::from_secs(256));
+        assert_eq!(
+            subject.backoff(8),
+            Duration::from_secs(MAX_RESTART_BACKOFF_SECS)
+        );
+        assert_eq!(
+            subject.backoff(99),
+            Duration::from_secs(MAX_RESTART_BACKOFF_SECS)
+        );
+    }
+}
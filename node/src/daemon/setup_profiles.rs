@@ -0,0 +1,309 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::bootstrapper::RealUser;
+use crate::node_configurator::{data_directory_from_context, DirsWrapper};
+use masq_lib::constants::DEFAULT_CHAIN;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+pub const SETUP_PROFILE_DIRECTORY: &str = "setup-profiles";
+pub const SETUP_PROFILE_EXTENSION: &str = "json";
+const MAX_PROFILE_NAME_LENGTH: usize = 64;
+
+// Parameter name -> value, as it would be given to the 'setup' command
+pub type SetupProfile = BTreeMap<String, String>;
+
+pub trait SetupProfileStore {
+    fn save(&self, name: &str, profile: &SetupProfile) -> Result<(), String>;
+    fn load(&self, name: &str) -> Result<SetupProfile, String>;
+    fn list(&self) -> Result<Vec<String>, String>;
+}
+
+pub struct SetupProfileStoreReal {
+    dirs_wrapper: Box<dyn DirsWrapper>,
+}
+
+impl SetupProfileStore for SetupProfileStoreReal {
+    fn save(&self, name: &str, profile: &SetupProfile) -> Result<(), String> {
+        validate_profile_name(name)?;
+        let directory = self.directory();
+        fs::create_dir_all(&directory).map_err(|e| {
+            format!(
+                "Couldn't create setup profile directory {}: {}",
+                directory.display(),
+                e
+            )
+        })?;
+        let json = serde_json::to_string_pretty(profile).expect("Serialization failed");
+        let path = Self::profile_path(&directory, name);
+        fs::write(&path, json)
+            .map_err(|e| format!("Couldn't write setup profile {}: {}", path.display(), e))
+    }
+
+    fn load(&self, name: &str) -> Result<SetupProfile, String> {
+        validate_profile_name(name)?;
+        let path = Self::profile_path(&self.directory(), name);
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(format!("There is no setup profile named '{}'", name))
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Couldn't read setup profile {}: {}",
+                    path.display(),
+                    e
+                ))
+            }
+        };
+        serde_json::from_str(&json)
+            .map_err(|e| format!("Setup profile {} is corrupt: {}", path.display(), e))
+    }
+
+    fn list(&self) -> Result<Vec<String>, String> {
+        let directory = self.directory();
+        let entries = match fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => {
+                return Err(format!(
+                    "Couldn't read setup profile directory {}: {}",
+                    directory.display(),
+                    e
+                ))
+            }
+        };
+        let mut names = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension().and_then(|extension| extension.to_str())
+                    == Some(SETUP_PROFILE_EXTENSION)
+            })
+            .flat_map(|path| {
+                path.file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(|stem| stem.to_string())
+            })
+            .filter(|name| validate_profile_name(name).is_ok())
+            .collect::<Vec<String>>();
+        names.sort();
+        Ok(names)
+    }
+}
+
+impl SetupProfileStoreReal {
+    pub fn new(dirs_wrapper: Box<dyn DirsWrapper>) -> Self {
+        Self { dirs_wrapper }
+    }
+
+    // Next to the chain-specific data directories, so that one profile can switch chains
+    fn directory(&self) -> PathBuf {
+        let real_user = RealUser::new(None, None, None).populate(self.dirs_wrapper.as_ref());
+        let chain_directory = data_directory_from_context(
+            self.dirs_wrapper.as_ref(),
+            &real_user,
+            &None,
+            DEFAULT_CHAIN,
+        );
+        chain_directory
+            .parent()
+            .expect("Default data directory has no parent")
+            .join(SETUP_PROFILE_DIRECTORY)
+    }
+
+    fn profile_path(directory: &Path, name: &str) -> PathBuf {
+        directory.join(format!("{}.{}", name, SETUP_PROFILE_EXTENSION))
+    }
+}
+
+// Profile names become file names, so nothing that could climb out of the directory is allowed
+pub fn validate_profile_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.len() > MAX_PROFILE_NAME_LENGTH
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Err(format!(
+            "Setup profile names must be 1 to {} letters, digits, '-' or '_', not '{}'",
+            MAX_PROFILE_NAME_LENGTH, name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_test_utils::DirsWrapperMock;
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    fn make_subject(home_dir: &Path) -> SetupProfileStoreReal {
+        SetupProfileStoreReal::new(Box::new(
+            DirsWrapperMock::new()
+                .home_dir_result(Some(home_dir.to_path_buf()))
+                .data_dir_result(Some(home_dir.join("data"))),
+        ))
+    }
+
+    fn make_profile(pairs: Vec<(&str, &str)>) -> SetupProfile {
+        pairs
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(SETUP_PROFILE_DIRECTORY, "setup-profiles");
+        assert_eq!(SETUP_PROFILE_EXTENSION, "json");
+        assert_eq!(MAX_PROFILE_NAME_LENGTH, 64);
+    }
+
+    #[test]
+    fn profiles_are_saved_next_to_the_chain_data_directories() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "profiles_are_saved_next_to_the_chain_data_directories",
+        );
+        let subject = make_subject(&home_dir);
+        let profile = make_profile(vec![
+            ("chain", "polygon-mainnet"),
+            ("neighborhood-mode", "consume-only"),
+        ]);
+
+        let result = subject.save("polygon-consume-only", &profile);
+
+        assert_eq!(result, Ok(()));
+        let json = fs::read_to_string(
+            home_dir
+                .join("data")
+                .join("MASQ")
+                .join("setup-profiles")
+                .join("polygon-consume-only.json"),
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::from_str::<SetupProfile>(&json).unwrap(),
+            profile
+        );
+    }
+
+    #[test]
+    fn saved_profiles_can_be_loaded_and_listed() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "saved_profiles_can_be_loaded_and_listed",
+        );
+        let subject = make_subject(&home_dir);
+        let dev_standard = make_profile(vec![("chain", "dev"), ("log-level", "debug")]);
+        let consume_only = make_profile(vec![("neighborhood-mode", "consume-only")]);
+        subject.save("dev-standard", &dev_standard).unwrap();
+        subject.save("consume_only", &consume_only).unwrap();
+        subject.save("dev-standard", &consume_only).unwrap();
+        fs::write(
+            home_dir
+                .join("data")
+                .join("MASQ")
+                .join("setup-profiles")
+                .join("notes.txt"),
+            "not a profile",
+        )
+        .unwrap();
+
+        let list_result = subject.list();
+        let load_result = subject.load("dev-standard");
+
+        assert_eq!(
+            list_result,
+            Ok(vec!["consume_only".to_string(), "dev-standard".to_string()])
+        );
+        assert_eq!(load_result, Ok(consume_only));
+    }
+
+    #[test]
+    fn list_is_empty_before_anything_is_saved() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "list_is_empty_before_anything_is_saved",
+        );
+        let subject = make_subject(&home_dir);
+
+        let result = subject.list();
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[test]
+    fn load_complains_about_missing_profile() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "load_complains_about_missing_profile",
+        );
+        let subject = make_subject(&home_dir);
+
+        let result = subject.load("nonexistent");
+
+        assert_eq!(
+            result,
+            Err("There is no setup profile named 'nonexistent'".to_string())
+        );
+    }
+
+    #[test]
+    fn load_complains_about_corrupt_profile() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "load_complains_about_corrupt_profile",
+        );
+        let directory = home_dir.join("data").join("MASQ").join("setup-profiles");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("broken.json"), "{\"chain\": ").unwrap();
+        let subject = make_subject(&home_dir);
+
+        let result = subject.load("broken");
+
+        let message = result.unwrap_err();
+        assert!(
+            message.starts_with(&format!(
+                "Setup profile {} is corrupt: ",
+                directory.join("broken.json").display()
+            )),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn profile_names_that_are_not_plain_file_names_are_rejected() {
+        let home_dir = ensure_node_home_directory_exists(
+            "setup_profiles",
+            "profile_names_that_are_not_plain_file_names_are_rejected",
+        );
+        let subject = make_subject(&home_dir);
+        let too_long = "x".repeat(MAX_PROFILE_NAME_LENGTH + 1);
+
+        let results = vec!["", "../escape", "with space", "dot.ted", too_long.as_str()]
+            .into_iter()
+            .map(|name| subject.save(name, &SetupProfile::new()))
+            .collect::<Vec<_>>();
+
+        results.into_iter().for_each(|result| {
+            let message = result.unwrap_err();
+            assert!(
+                message
+                    .starts_with("Setup profile names must be 1 to 64 letters, digits, '-' or '_'"),
+                "{}",
+                message
+            )
+        });
+        assert_eq!(
+            validate_profile_name(&"x".repeat(MAX_PROFILE_NAME_LENGTH)),
+            Ok(())
+        );
+    }
+}
@@ -27,7 +27,7 @@ use crate::sub_lib::utils::make_new_multi_config;
 use crate::test_utils::main_cryptde;
 use clap::value_t;
 use itertools::Itertools;
-use masq_lib::blockchains::chains::Chain as BlockChain;
+use masq_lib::blockchains::chains::{chain_from_chain_identifier, Chain as BlockChain};
 use masq_lib::constants::DEFAULT_CHAIN;
 use masq_lib::logger::Logger;
 use masq_lib::messages::UiSetupResponseValueStatus::{Blank, Configured, Default, Required, Set};
@@ -297,11 +297,9 @@ impl SetupReporterReal {
             (Some(ddir_str), Some(_)) => Some(PathBuf::from(&ddir_str)),
             _ => None,
         };
-        Ok((
-            real_user_opt,
-            data_directory_opt,
-            BlockChain::from(chain_name.as_str()),
-        ))
+        let chain = chain_from_chain_identifier(&chain_name)
+            .map_err(|e| ConfiguratorError::required("chain", &e))?;
+        Ok((real_user_opt, data_directory_opt, chain))
     }
 
     fn calculate_configured_setup(
@@ -602,6 +600,13 @@ impl ValueRetriever for ConfigFile {
     }
 }
 
+struct ConfirmationDepth {}
+impl ValueRetriever for ConfirmationDepth {
+    fn value_name(&self) -> &'static str {
+        "confirmation-depth"
+    }
+}
+
 struct ConsumingPrivateKey {}
 impl ValueRetriever for ConsumingPrivateKey {
     fn value_name(&self) -> &'static str {
@@ -674,6 +679,49 @@ impl ValueRetriever for DbPassword {
     }
 }
 
+struct DelinquencyGracePeriod {}
+impl ValueRetriever for DelinquencyGracePeriod {
+    fn value_name(&self) -> &'static str {
+        "delinquency-grace-period"
+    }
+
+    fn computed_default(
+        &self,
+        bootstrapper_config: &BootstrapperConfig,
+        _persistent_config: &dyn PersistentConfiguration,
+        _db_password_opt: &Option<String>,
+    ) -> Option<(String, UiSetupResponseValueStatus)> {
+        Some((
+            bootstrapper_config
+                .delinquency_policy
+                .grace_period_sec
+                .to_string(),
+            Default,
+        ))
+    }
+}
+
+struct DelinquencyNotices {}
+impl ValueRetriever for DelinquencyNotices {
+    fn value_name(&self) -> &'static str {
+        "delinquency-notices"
+    }
+
+    fn computed_default(
+        &self,
+        bootstrapper_config: &BootstrapperConfig,
+        _persistent_config: &dyn PersistentConfiguration,
+        _db_password_opt: &Option<String>,
+    ) -> Option<(String, UiSetupResponseValueStatus)> {
+        let value = if bootstrapper_config.delinquency_policy.notify_debtors {
+            "on"
+        } else {
+            "off"
+        };
+        Some((value.to_string(), Default))
+    }
+}
+
 struct DnsServers {
     factory: Box<dyn DnsInspectorFactory>,
     logger: Logger,
@@ -731,6 +779,20 @@ impl ValueRetriever for EarningWallet {
     }
 }
 
+struct ExternalSignerAddress {}
+impl ValueRetriever for ExternalSignerAddress {
+    fn value_name(&self) -> &'static str {
+        "external-signer-address"
+    }
+}
+
+struct ExternalSignerUrl {}
+impl ValueRetriever for ExternalSignerUrl {
+    fn value_name(&self) -> &'static str {
+        "external-signer-url"
+    }
+}
+
 struct GasPrice {}
 impl ValueRetriever for GasPrice {
     fn value_name(&self) -> &'static str {
@@ -809,6 +871,13 @@ impl ValueRetriever for LogLevel {
     }
 }
 
+struct LowBalanceThresholds {}
+impl ValueRetriever for LowBalanceThresholds {
+    fn value_name(&self) -> &'static str {
+        "low-balance-thresholds"
+    }
+}
+
 struct MappingProtocol {}
 impl ValueRetriever for MappingProtocol {
     fn value_name(&self) -> &'static str {
@@ -1053,15 +1122,21 @@ fn value_retrievers(dirs_wrapper: &dyn DirsWrapper) -> Vec<Box<dyn ValueRetrieve
         Box::new(Chain {}),
         Box::new(ClandestinePort {}),
         Box::new(ConfigFile {}),
+        Box::new(ConfirmationDepth {}),
         Box::new(ConsumingPrivateKey {}),
         Box::new(CrashPoint {}),
         Box::new(DataDirectory::new(dirs_wrapper)),
         Box::new(DbPassword {}),
+        Box::new(DelinquencyGracePeriod {}),
+        Box::new(DelinquencyNotices {}),
         Box::new(DnsServers::new()),
         Box::new(EarningWallet {}),
+        Box::new(ExternalSignerAddress {}),
+        Box::new(ExternalSignerUrl {}),
         Box::new(GasPrice {}),
         Box::new(Ip {}),
         Box::new(LogLevel {}),
+        Box::new(LowBalanceThresholds {}),
         Box::new(MappingProtocol {}),
         Box::new(MinHops {}),
         Box::new(NeighborhoodMode {}),
@@ -1273,15 +1348,21 @@ mod tests {
             ("chain", DEFAULT_CHAIN.rec().literal_identifier, Default),
             ("clandestine-port", "1234", Configured),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "", Blank),
             ("crash-point", "", Blank),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("delinquency-grace-period", "0", Default),
+            ("delinquency-notices", "off", Default),
             ("dns-servers", &dns_servers_str, dns_servers_status),
             ("earning-wallet", "", Blank),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "1234567890", Default),
             ("ip", "4.3.2.1", Set),
             ("log-level", "warn", Default),
+            ("low-balance-thresholds", "", Blank),
             ("mapping-protocol", "", Blank),
             ("min-hops", "3", Configured), // TODO: GH-698: This should be changed to Default after this card is played
             ("neighborhood-mode", "standard", Default),
@@ -1338,15 +1419,21 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Set),
             ("clandestine-port", "1234", Set),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "0011223344556677001122334455667700112233445566770011223344556677", Set),
             ("crash-point", "Message", Set),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("delinquency-grace-period", "3600", Set),
+            ("delinquency-notices", "on", Set),
             ("dns-servers", "8.8.8.8", Set),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Set),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "50", Set),
             ("ip", "4.3.2.1", Set),
             ("log-level", "error", Set),
+            ("low-balance-thresholds", "1000|2000", Set),
             ("mapping-protocol", "pmp", Set),
             ("min-hops", "2", Set),
             ("neighborhood-mode", "originate-only", Set),
@@ -1368,15 +1455,21 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Set),
             ("clandestine-port", "1234", Set),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "0011223344556677001122334455667700112233445566770011223344556677", Set),
             ("crash-point", "Message", Set),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("delinquency-grace-period", "3600", Set),
+            ("delinquency-notices", "on", Set),
             ("dns-servers", "8.8.8.8", Set),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Set),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "50", Set),
             ("ip", "4.3.2.1", Set),
             ("log-level", "error", Set),
+            ("low-balance-thresholds", "1000|2000", Set),
             ("mapping-protocol", "pmp", Set),
             ("min-hops", "2", Set),
             ("neighborhood-mode", "originate-only", Set),
@@ -1412,11 +1505,14 @@ mod tests {
             ("crash-point", "Message"),
             ("data-directory", home_dir.to_str().unwrap()),
             ("db-password", "password"),
+            ("delinquency-grace-period", "3600"),
+            ("delinquency-notices", "on"),
             ("dns-servers", "8.8.8.8"),
             ("earning-wallet", "0x0123456789012345678901234567890123456789"),
             ("gas-price", "50"),
             ("ip", "4.3.2.1"),
             ("log-level", "error"),
+            ("low-balance-thresholds", "1000|2000"),
             ("mapping-protocol", "igdp"),
             ("min-hops", "2"),
             ("neighborhood-mode", "originate-only"),
@@ -1442,15 +1538,21 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Set),
             ("clandestine-port", "1234", Set),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "0011223344556677001122334455667700112233445566770011223344556677", Set),
             ("crash-point", "Message", Set),
             ("data-directory", home_dir.to_str().unwrap(), Set),
             ("db-password", "password", Set),
+            ("delinquency-grace-period", "3600", Set),
+            ("delinquency-notices", "on", Set),
             ("dns-servers", "8.8.8.8", Set),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Set),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "50", Set),
             ("ip", "4.3.2.1", Set),
             ("log-level", "error", Set),
+            ("low-balance-thresholds", "1000|2000", Set),
             ("mapping-protocol", "igdp", Set),
             ("min-hops", "2", Set),
             ("neighborhood-mode", "originate-only", Set),
@@ -1487,11 +1589,14 @@ mod tests {
             ("MASQ_CRASH_POINT", "Error"),
             ("MASQ_DATA_DIRECTORY", home_dir.to_str().unwrap()),
             ("MASQ_DB_PASSWORD", "password"),
+            ("MASQ_DELINQUENCY_GRACE_PERIOD", "3600"),
+            ("MASQ_DELINQUENCY_NOTICES", "on"),
             ("MASQ_DNS_SERVERS", "8.8.8.8"),
             ("MASQ_EARNING_WALLET", "0x0123456789012345678901234567890123456789"),
             ("MASQ_GAS_PRICE", "50"),
             ("MASQ_IP", "4.3.2.1"),
             ("MASQ_LOG_LEVEL", "error"),
+            ("MASQ_LOW_BALANCE_THRESHOLDS", "1000|2000"),
             ("MASQ_MAPPING_PROTOCOL", "pmp"),
             ("MASQ_MIN_HOPS", "2"),
             ("MASQ_NEIGHBORHOOD_MODE", "originate-only"),
@@ -1515,15 +1620,21 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Configured),
             ("clandestine-port", "1234", Configured),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "0011223344556677001122334455667700112233445566770011223344556677", Configured),
             ("crash-point", "Error", Configured),
             ("data-directory", home_dir.to_str().unwrap(), Configured),
             ("db-password", "password", Configured),
+            ("delinquency-grace-period", "3600", Configured),
+            ("delinquency-notices", "on", Configured),
             ("dns-servers", "8.8.8.8", Configured),
             ("earning-wallet", "0x0123456789012345678901234567890123456789", Configured),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "50", Configured),
             ("ip", "4.3.2.1", Configured),
             ("log-level", "error", Configured),
+            ("low-balance-thresholds", "1000|2000", Configured),
             ("mapping-protocol", "pmp", Configured),
             ("min-hops", "2", Configured),
             ("neighborhood-mode", "originate-only", Configured),
@@ -1665,6 +1776,7 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Set),
             ("clandestine-port", "8877", Configured),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             (
                 "consuming-private-key",
                 "FFEEDDCCBBAA99887766554433221100FFEEDDCCBBAA99887766554433221100",
@@ -1677,15 +1789,20 @@ mod tests {
                 Default,
             ),
             ("db-password", "ropstenPassword", Configured),
+            ("delinquency-grace-period", "0", Default),
+            ("delinquency-notices", "off", Default),
             ("dns-servers", "8.7.6.5", Configured),
             (
                 "earning-wallet",
                 "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
                 Configured,
             ),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "88", Configured),
             ("ip", "", Blank),
             ("log-level", "debug", Configured),
+            ("low-balance-thresholds", "", Blank),
             ("mapping-protocol", "pmp", Configured),
             ("min-hops", "2", Configured),
             ("neighborhood-mode", "zero-hop", Configured),
@@ -1735,10 +1852,13 @@ mod tests {
             ("MASQ_CONSUMING_PRIVATE_KEY", "0011223344556677001122334455667700112233445566770011223344556677"),
             ("MASQ_CRASH_POINT", "Panic"),
             ("MASQ_DATA_DIRECTORY", home_dir.to_str().unwrap()),
+            ("MASQ_DELINQUENCY_GRACE_PERIOD", "3600"),
+            ("MASQ_DELINQUENCY_NOTICES", "on"),
             ("MASQ_DNS_SERVERS", "8.8.8.8"),
             ("MASQ_EARNING_WALLET", "0x0123456789012345678901234567890123456789"),
             ("MASQ_GAS_PRICE", "50"),
             ("MASQ_LOG_LEVEL", "error"),
+            ("MASQ_LOW_BALANCE_THRESHOLDS", "1000|2000"),
             ("MASQ_MAPPING_PROTOCOL", "pcp"),
             ("MASQ_MIN_HOPS", "2"),
             ("MASQ_NEIGHBORHOOD_MODE", "originate-only"),
@@ -1759,11 +1879,14 @@ mod tests {
             "crash-point",
             "data-directory",
             "db-password",
+            "delinquency-grace-period",
+            "delinquency-notices",
             "dns-servers",
             "earning-wallet",
             "gas-price",
             "ip",
             "log-level",
+            "low-balance-thresholds",
             "mapping-protocol",
             "min-hops",
             "neighborhood-mode",
@@ -1782,6 +1905,7 @@ mod tests {
             setup_cluster_from(vec![
             ("blockchain-service-url", "https://booga.com", Set),
             ("clandestine-port", "4321", Set),
+            ("confirmation-depth", "", Blank),
             (
                 "consuming-private-key",
                 "7766554433221100776655443322110077665544332211007766554433221100",
@@ -1790,15 +1914,20 @@ mod tests {
             ("crash-point", "Message", Set),
             ("data-directory", "booga", Set),
             ("db-password", "drowssap", Set),
+            ("delinquency-grace-period", "3600", Set),
+            ("delinquency-notices", "on", Set),
             ("dns-servers", "4.4.4.4", Set),
             (
                 "earning-wallet",
                 "0x9876543210987654321098765432109876543210",
                 Set,
             ),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "5", Set),
             ("ip", "1.2.3.4", Set),
             ("log-level", "error", Set),
+            ("low-balance-thresholds", "3000|4000", Set),
             ("mapping-protocol", "pcp", Set),
             ("min-hops", "4", Set),
             ("neighborhood-mode", "consume-only", Set),
@@ -1824,19 +1953,25 @@ mod tests {
             ("chain", TEST_DEFAULT_CHAIN.rec().literal_identifier, Configured),
             ("clandestine-port", "1234", Configured),
             ("config-file", "config.toml", Default),
+            ("confirmation-depth", "", Blank),
             ("consuming-private-key", "0011223344556677001122334455667700112233445566770011223344556677", Configured),
             ("crash-point", "Panic", Configured),
             ("data-directory", home_dir.to_str().unwrap(), Configured),
             ("db-password", "",Required),
+            ("delinquency-grace-period", "3600", Configured),
+            ("delinquency-notices", "on", Configured),
             ("dns-servers", "8.8.8.8", Configured),
             (
                 "earning-wallet",
                 "0x0123456789012345678901234567890123456789",
                 Configured,
             ),
+            ("external-signer-address", "", Blank),
+            ("external-signer-url", "", Blank),
             ("gas-price", "50", Configured),
             ("ip","", Blank),
             ("log-level", "error", Configured),
+            ("low-balance-thresholds", "1000|2000", Configured),
             ("mapping-protocol", "pcp", Configured),
             ("min-hops", "2", Configured),
             ("neighborhood-mode", "originate-only", Configured),
@@ -2342,6 +2477,22 @@ mod tests {
         assert_eq!(chain, DEFAULT_CHAIN);
     }
 
+    #[test]
+    fn calculate_fundamentals_reports_an_unknown_chain_instead_of_panicking() {
+        let _guard = EnvironmentGuard::new();
+        let setup = setup_cluster_from(vec![("chain", "unloaded-net", Set)]);
+
+        let result = SetupReporterReal::calculate_fundamentals(&DirsWrapperReal {}, &setup);
+
+        assert_eq!(
+            result.err().unwrap(),
+            ConfiguratorError::required(
+                "chain",
+                "Chain 'unloaded-net' is unknown; if it's a custom chain, MASQ_CHAINS_FILE must name the file that defines it"
+            )
+        );
+    }
+
     #[test]
     fn blanking_a_parameter_with_a_default_produces_that_default() {
         let _guard = EnvironmentGuard::new();
@@ -3342,11 +3493,17 @@ mod tests {
         assert_eq!(ConsumingPrivateKey {}.is_required(&params), false);
         assert_eq!(DataDirectory::default().is_required(&params), true);
         assert_eq!(DbPassword {}.is_required(&params), true);
+        assert_eq!(DelinquencyGracePeriod {}.is_required(&params), false);
+        assert_eq!(DelinquencyNotices {}.is_required(&params), false);
         assert_eq!(DnsServers::new().is_required(&params), true);
         assert_eq!(EarningWallet {}.is_required(&params), false);
         assert_eq!(GasPrice {}.is_required(&params), true);
         assert_eq!(Ip {}.is_required(&params), false);
         assert_eq!(LogLevel {}.is_required(&params), true);
+        assert_eq!(
+            setup_reporter::LowBalanceThresholds {}.is_required(&params),
+            false
+        );
         assert_eq!(MappingProtocol {}.is_required(&params), false);
         assert_eq!(MinHops {}.is_required(&params), false);
         assert_eq!(NeighborhoodMode {}.is_required(&params), true);
@@ -3372,14 +3529,29 @@ mod tests {
         assert_eq!(Chain {}.value_name(), "chain");
         assert_eq!(ClandestinePort {}.value_name(), "clandestine-port");
         assert_eq!(ConfigFile {}.value_name(), "config-file");
+        assert_eq!(ConfirmationDepth {}.value_name(), "confirmation-depth");
         assert_eq!(ConsumingPrivateKey {}.value_name(), "consuming-private-key");
         assert_eq!(DataDirectory::default().value_name(), "data-directory");
         assert_eq!(DbPassword {}.value_name(), "db-password");
+        assert_eq!(
+            DelinquencyGracePeriod {}.value_name(),
+            "delinquency-grace-period"
+        );
+        assert_eq!(DelinquencyNotices {}.value_name(), "delinquency-notices");
         assert_eq!(DnsServers::new().value_name(), "dns-servers");
         assert_eq!(EarningWallet {}.value_name(), "earning-wallet");
+        assert_eq!(
+            ExternalSignerAddress {}.value_name(),
+            "external-signer-address"
+        );
+        assert_eq!(ExternalSignerUrl {}.value_name(), "external-signer-url");
         assert_eq!(GasPrice {}.value_name(), "gas-price");
         assert_eq!(Ip {}.value_name(), "ip");
         assert_eq!(LogLevel {}.value_name(), "log-level");
+        assert_eq!(
+            setup_reporter::LowBalanceThresholds {}.value_name(),
+            "low-balance-thresholds"
+        );
         assert_eq!(MappingProtocol {}.value_name(), "mapping-protocol");
         assert_eq!(MinHops {}.value_name(), "min-hops");
         assert_eq!(NeighborhoodMode {}.value_name(), "neighborhood-mode");
@@ -24,6 +24,8 @@ use std::{fs, vec};
 use tokio::net::TcpListener;
 
 pub const DATABASE_FILE: &str = "node-data.db";
+// The database as the very first Node versions left it; every migration starts from here
+pub const VERSION_0_SCHEMA: &str = include_str!("database_version_0_sql.txt");
 
 #[derive(Debug, PartialEq)]
 pub enum InitializationError {
@@ -93,6 +95,21 @@ impl DbInitializer for DbInitializerReal {
                 db_file_path,
                 OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_READ_WRITE,
             ) {
+                Ok(conn) if target_version < CURRENT_SCHEMA_VERSION => {
+                    eprintln!(
+                        "Created new database at {:?} for schema version {}",
+                        db_file_path, target_version
+                    );
+                    Self::extra_configuration(&conn, &init_config)?;
+                    Self::create_version_0_tables(&conn)?;
+                    self.check_migrations_and_return_connection(
+                        conn,
+                        init_config,
+                        db_file_path,
+                        target_version,
+                        OpenFlags::SQLITE_OPEN_READ_WRITE,
+                    )
+                }
                 Ok(conn) => {
                     eprintln!("Created new database at {:?}", db_file_path);
                     Self::extra_configuration(&conn, &init_config)?;
@@ -130,6 +147,15 @@ impl DbInitializerReal {
         }
     }
 
+    // Older schema versions can't be created directly; they are migrated up to from version 0
+    fn create_version_0_tables(conn: &Connection) -> Result<(), InitializationError> {
+        VERSION_0_SCHEMA
+            .lines()
+            .filter(|statement| !statement.trim().is_empty())
+            .try_for_each(|statement| conn.execute(statement, []).map(|_| ()))
+            .map_err(InitializationError::SqliteError)
+    }
+
     fn create_database_tables(&self, conn: &Connection, external_params: ExternalData) {
         self.create_config_table(conn);
         self.initialize_config(conn, external_params);
@@ -137,6 +163,10 @@ impl DbInitializerReal {
         self.create_pending_payable_table(conn);
         self.create_receivable_table(conn);
         self.create_banned_table(conn);
+        self.create_delinquency_warning_table(conn);
+        self.create_received_payment_table(conn);
+        self.create_ledger_table(conn);
+        self.create_service_usage_table(conn);
     }
 
     fn create_config_table(&self, conn: &Connection) {
@@ -253,6 +283,13 @@ impl DbInitializerReal {
             false,
             "scan intervals",
         );
+        Self::set_config_value(
+            conn,
+            "retired_earning_wallets",
+            None,
+            false,
+            "earning wallets still watched for late payments after rotation",
+        );
     }
 
     fn create_pending_payable_table(&self, conn: &Connection) {
@@ -270,7 +307,7 @@ impl DbInitializerReal {
         )
         .expect("Can't create pending_payable table");
         conn.execute(
-            "CREATE UNIQUE INDEX pending_payable_hash_idx ON pending_payable (transaction_hash)",
+            "CREATE INDEX pending_payable_hash_idx ON pending_payable (transaction_hash)",
             [],
         )
         .expect("Can't create transaction hash index in pending payments");
@@ -305,12 +342,100 @@ impl DbInitializerReal {
 
     fn create_banned_table(&self, conn: &Connection) {
         conn.execute(
-            "create table banned ( wallet_address text primary key )",
+            "create table banned (
+                    wallet_address text primary key,
+                    reason text not null default 'delinquency',
+                    note text null,
+                    timestamp integer null
+            )",
             [],
         )
         .expect("Can't create banned table");
     }
 
+    fn create_delinquency_warning_table(&self, conn: &Connection) {
+        conn.execute(
+            "create table delinquency_warning (
+                    wallet_address text primary key,
+                    timestamp integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create delinquency_warning table");
+    }
+
+    fn create_received_payment_table(&self, conn: &Connection) {
+        conn.execute(
+            "create table received_payment (
+                    transaction_hash text not null,
+                    block_number integer not null,
+                    wallet_address text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null,
+                    recipient_address text not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create received_payment table");
+        conn.execute(
+            "create index received_payment_block_number_idx on received_payment (block_number)",
+            [],
+        )
+        .expect("Can't create block number index in received_payment");
+    }
+
+    fn create_ledger_table(&self, conn: &Connection) {
+        conn.execute(
+            "create table if not exists ledger (
+                    rowid integer primary key,
+                    timestamp integer not null,
+                    wallet_address text not null,
+                    kind text not null,
+                    amount_high_b integer not null,
+                    amount_low_b integer not null,
+                    transaction_hash text null,
+                    nonce integer null,
+                    gas_price_gwei integer null,
+                    block_number integer null,
+                    outcome text null
+            ) strict",
+            [],
+        )
+        .expect("Can't create ledger table");
+        conn.execute(
+            "create index ledger_wallet_address_idx on ledger (wallet_address)",
+            [],
+        )
+        .expect("Can't create wallet address index in ledger");
+        conn.execute(
+            "create index ledger_timestamp_idx on ledger (timestamp)",
+            [],
+        )
+        .expect("Can't create timestamp index in ledger");
+    }
+
+    fn create_service_usage_table(&self, conn: &Connection) {
+        conn.execute(
+            "create table if not exists service_usage (
+                    wallet_address text not null,
+                    bucket_start integer not null,
+                    service text not null,
+                    direction text not null,
+                    service_count integer not null,
+                    bytes integer not null,
+                    charge_high_b integer not null,
+                    charge_low_b integer not null
+            ) strict",
+            [],
+        )
+        .expect("Can't create service_usage table");
+        conn.execute(
+            "create unique index service_usage_key_idx on service_usage (bucket_start, wallet_address, service, direction)",
+            [],
+        )
+        .expect("Can't create key index in service_usage");
+    }
+
     fn extra_configuration(
         conn: &Connection,
         init_config: &DbInitializationConfig,
@@ -729,8 +854,9 @@ mod tests {
     use crate::test_utils::database_utils::{
         assert_create_table_stm_contains_all_parts,
         assert_index_stm_is_coupled_with_right_parameter, assert_no_index_exists_for_table,
-        assert_table_created_as_strict, bring_db_0_back_to_life_and_return_connection,
-        make_external_data, retrieve_config_row, DbMigratorMock,
+        assert_table_created_as_strict, assert_table_does_not_exist,
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+        DbMigratorMock,
     };
     use itertools::Either::{Left, Right};
     use itertools::{Either, Itertools};
@@ -757,7 +883,28 @@ mod tests {
     #[test]
     fn constants_have_correct_values() {
         assert_eq!(DATABASE_FILE, "node-data.db");
-        assert_eq!(CURRENT_SCHEMA_VERSION, 7);
+        assert_eq!(CURRENT_SCHEMA_VERSION, 15);
+    }
+
+    #[test]
+    fn initialize_to_version_creates_older_schema_by_migrating_up_from_version_0() {
+        let home_dir = ensure_node_home_directory_exists(
+            "db_initializer",
+            "initialize_to_version_creates_older_schema_by_migrating_up_from_version_0",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize_to_version(
+                &home_dir,
+                11,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("11".to_string()));
+        assert_table_does_not_exist(conn.as_ref(), "received_payment");
     }
 
     #[test]
@@ -815,7 +962,19 @@ mod tests {
             conn.as_ref(),
             "pending_payable_hash_idx",
             expected_key_words,
-        )
+        );
+        // Creditors paid through a disperse contract share a transaction hash
+        let insert_fingerprint = "insert into pending_payable \
+            (transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error) \
+            values ('0x1234', 0, 5000, 1600000000, 1, null)";
+        conn.prepare(insert_fingerprint)
+            .unwrap()
+            .execute([])
+            .unwrap();
+        conn.prepare(insert_fingerprint)
+            .unwrap()
+            .execute([])
+            .unwrap();
     }
 
     #[test]
@@ -886,14 +1045,182 @@ mod tests {
             .initialize(&home_dir, DbInitializationConfig::test_default())
             .unwrap();
 
-        let mut stmt = conn.prepare("select wallet_address from banned").unwrap();
+        let mut stmt = conn
+            .prepare("select wallet_address, reason, note, timestamp from banned")
+            .unwrap();
         let mut banned_contents = stmt.query_map([], |_| Ok(42)).unwrap();
         assert!(banned_contents.next().is_none());
-        let expected_key_words: &[&[&str]] = &[&["wallet_address", "text", "primary", "key"]];
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "primary", "key"],
+            &["reason", "text", "not", "null", "default", "'delinquency'"],
+            &["note", "text", "null"],
+            &["timestamp", "integer", "null"],
+        ];
         assert_create_table_stm_contains_all_parts(conn.as_ref(), "banned", expected_key_words);
         assert_no_index_exists_for_table(conn.as_ref(), "banned")
     }
 
+    #[test]
+    fn db_initialize_creates_delinquency_warning_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_delinquency_warning_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select wallet_address, timestamp from delinquency_warning")
+            .unwrap();
+        let mut warning_contents = stmt.query_map([], |_| Ok(())).unwrap();
+        assert!(warning_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "delinquency_warning");
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "primary", "key"],
+            &["timestamp", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "delinquency_warning",
+            expected_key_words,
+        );
+        assert_no_index_exists_for_table(conn.as_ref(), "delinquency_warning")
+    }
+
+    #[test]
+    fn db_initialize_creates_received_payment_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_received_payment_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select transaction_hash, block_number, wallet_address, amount_high_b, amount_low_b, recipient_address from received_payment")
+            .unwrap();
+        let mut received_payment_contents = stmt.query_map([], |_| Ok(())).unwrap();
+        assert!(received_payment_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "received_payment");
+        let expected_key_words: &[&[&str]] = &[
+            &["transaction_hash", "text", "not", "null"],
+            &["block_number", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+            &["recipient_address", "text", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "received_payment",
+            expected_key_words,
+        );
+        let expected_key_words: &[&[&str]] = &[&["block_number"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "received_payment_block_number_idx",
+            expected_key_words,
+        )
+    }
+
+    #[test]
+    fn db_initialize_creates_ledger_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_ledger_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select rowid, timestamp, wallet_address, kind, amount_high_b, amount_low_b, transaction_hash, nonce, gas_price_gwei, block_number, outcome from ledger")
+            .unwrap();
+        let mut ledger_contents = stmt.query_map([], |_| Ok(())).unwrap();
+        assert!(ledger_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "ledger");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["timestamp", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["kind", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+            &["transaction_hash", "text", "null"],
+            &["nonce", "integer", "null"],
+            &["gas_price_gwei", "integer", "null"],
+            &["block_number", "integer", "null"],
+            &["outcome", "text", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(conn.as_ref(), "ledger", expected_key_words);
+        let expected_key_words: &[&[&str]] = &[&["wallet_address"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "ledger_wallet_address_idx",
+            expected_key_words,
+        );
+        let expected_key_words: &[&[&str]] = &[&["timestamp"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "ledger_timestamp_idx",
+            expected_key_words,
+        )
+    }
+
+    #[test]
+    fn db_initialize_creates_service_usage_table() {
+        let home_dir = ensure_node_home_directory_does_not_exist(
+            "db_initializer",
+            "db_initialize_creates_service_usage_table",
+        );
+        let subject = DbInitializerReal::default();
+
+        let conn = subject
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+
+        let mut stmt = conn
+            .prepare("select wallet_address, bucket_start, service, direction, service_count, bytes, charge_high_b, charge_low_b from service_usage")
+            .unwrap();
+        let mut usage_contents = stmt.query_map([], |_| Ok(())).unwrap();
+        assert!(usage_contents.next().is_none());
+        assert_table_created_as_strict(&*conn, "service_usage");
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "not", "null"],
+            &["bucket_start", "integer", "not", "null"],
+            &["service", "text", "not", "null"],
+            &["direction", "text", "not", "null"],
+            &["service_count", "integer", "not", "null"],
+            &["bytes", "integer", "not", "null"],
+            &["charge_high_b", "integer", "not", "null"],
+            &["charge_low_b", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "service_usage",
+            expected_key_words,
+        );
+        let expected_key_words: &[&[&str]] = &[
+            &["bucket_start"],
+            &["wallet_address"],
+            &["service"],
+            &["direction"],
+        ];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "service_usage_key_idx",
+            expected_key_words,
+        )
+    }
+
     #[test]
     #[should_panic(expected = "The database undoubtedly exists, but: unable to open database file")]
     fn double_check_the_result_of_db_migration_panics_if_cannot_reestablish_the_connection_to_the_database(
@@ -1052,6 +1379,7 @@ mod tests {
             Some(&DEFAULT_RATE_PACK.to_string()),
             false,
         );
+        verify(&mut config_vec, "retired_earning_wallets", None, false);
         verify(
             &mut config_vec,
             "scan_intervals",
@@ -3,12 +3,20 @@
 use crate::database::connection_wrapper::ConnectionWrapper;
 use crate::database::db_initializer::ExternalData;
 use crate::database::db_migrations::migrations::migration_0_to_1::Migrate_0_to_1;
+use crate::database::db_migrations::migrations::migration_10_to_11::Migrate_10_to_11;
+use crate::database::db_migrations::migrations::migration_11_to_12::Migrate_11_to_12;
+use crate::database::db_migrations::migrations::migration_12_to_13::Migrate_12_to_13;
+use crate::database::db_migrations::migrations::migration_13_to_14::Migrate_13_to_14;
+use crate::database::db_migrations::migrations::migration_14_to_15::Migrate_14_to_15;
 use crate::database::db_migrations::migrations::migration_1_to_2::Migrate_1_to_2;
 use crate::database::db_migrations::migrations::migration_2_to_3::Migrate_2_to_3;
 use crate::database::db_migrations::migrations::migration_3_to_4::Migrate_3_to_4;
 use crate::database::db_migrations::migrations::migration_4_to_5::Migrate_4_to_5;
 use crate::database::db_migrations::migrations::migration_5_to_6::Migrate_5_to_6;
 use crate::database::db_migrations::migrations::migration_6_to_7::Migrate_6_to_7;
+use crate::database::db_migrations::migrations::migration_7_to_8::Migrate_7_to_8;
+use crate::database::db_migrations::migrations::migration_8_to_9::Migrate_8_to_9;
+use crate::database::db_migrations::migrations::migration_9_to_10::Migrate_9_to_10;
 use crate::database::db_migrations::migrator_utils::{
     DBMigDeclarator, DBMigrationUtilities, DBMigrationUtilitiesReal, DBMigratorInnerConfiguration,
 };
@@ -75,6 +83,14 @@ impl DbMigratorReal {
             &Migrate_4_to_5,
             &Migrate_5_to_6,
             &Migrate_6_to_7,
+            &Migrate_7_to_8,
+            &Migrate_8_to_9,
+            &Migrate_9_to_10,
+            &Migrate_10_to_11,
+            &Migrate_11_to_12,
+            &Migrate_12_to_13,
+            &Migrate_13_to_14,
+            &Migrate_14_to_15,
         ]
     }
 
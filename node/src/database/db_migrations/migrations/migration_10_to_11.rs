@@ -0,0 +1,73 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_10_to_11;
+
+impl DatabaseMigration for Migrate_10_to_11 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        let statement_1 = "create table delinquency_warning (
+                wallet_address text primary key,
+                timestamp integer not null
+            ) strict";
+        utils.execute_upon_transaction(&[&statement_1])
+    }
+
+    fn old_version(&self) -> usize {
+        10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_create_table_stm_contains_all_parts, assert_table_created_as_strict,
+        assert_table_does_not_exist, bring_db_0_back_to_life_and_return_connection,
+        make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    #[test]
+    fn migration_from_10_to_11_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_10_to_11_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    10,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            assert_table_does_not_exist(conn.as_ref(), "delinquency_warning");
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            11,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        assert_table_created_as_strict(conn.as_ref(), "delinquency_warning");
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "primary", "key"],
+            &["timestamp", "integer", "not", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(
+            conn.as_ref(),
+            "delinquency_warning",
+            expected_key_words,
+        );
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("11".to_string()));
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_11_to_12;
+
+impl DatabaseMigration for Migrate_11_to_12 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        let statement_1 = "INSERT INTO config (name, value, encrypted) \
+            VALUES ('retired_earning_wallets', null, 0)";
+        utils.execute_upon_transaction(&[&statement_1])
+    }
+
+    fn old_version(&self) -> usize {
+        11
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::Error;
+
+    #[test]
+    fn migration_from_11_to_12_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_11_to_12_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    11,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            let result = conn
+                .prepare("select value from config where name = ?")
+                .unwrap()
+                .query_row(["retired_earning_wallets"], |row| {
+                    row.get::<usize, Option<String>>(0)
+                });
+            assert_eq!(result, Err(Error::QueryReturnedNoRows));
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            12,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        let (retired_earning_wallets, encrypted) =
+            retrieve_config_row(conn.as_ref(), "retired_earning_wallets");
+        assert_eq!(retired_earning_wallets, None);
+        assert_eq!(encrypted, false);
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("12".to_string()));
+    }
+}
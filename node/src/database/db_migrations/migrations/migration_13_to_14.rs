@@ -0,0 +1,91 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+// Creditors paid together through a disperse contract share one transaction hash, each with a
+// fingerprint of its own, so the hash index can no longer be unique
+#[allow(non_camel_case_types)]
+pub struct Migrate_13_to_14;
+
+impl DatabaseMigration for Migrate_13_to_14 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        let statement_1 = "drop index pending_payable_hash_idx";
+        let statement_2 =
+            "create index pending_payable_hash_idx on pending_payable (transaction_hash)";
+        utils.execute_upon_transaction(&[&statement_1, &statement_2])
+    }
+
+    fn old_version(&self) -> usize {
+        13
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_index_stm_is_coupled_with_right_parameter,
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    const INSERT_FINGERPRINT: &str = "insert into pending_payable \
+        (transaction_hash, amount_high_b, amount_low_b, payable_timestamp, attempt, process_error) \
+        values ('0x1234', 0, 5000, 1600000000, 1, null)";
+
+    #[test]
+    fn migration_from_13_to_14_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_13_to_14_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    13,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            conn.prepare(INSERT_FINGERPRINT)
+                .unwrap()
+                .execute([])
+                .unwrap();
+            assert!(conn
+                .prepare(INSERT_FINGERPRINT)
+                .unwrap()
+                .execute([])
+                .is_err());
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            14,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        let expected_key_words: &[&[&str]] = &[&["transaction_hash"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "pending_payable_hash_idx",
+            expected_key_words,
+        );
+        conn.prepare(INSERT_FINGERPRINT)
+            .unwrap()
+            .execute([])
+            .unwrap();
+        let fingerprint_count: i64 = conn
+            .prepare("select count(*) from pending_payable where transaction_hash = '0x1234'")
+            .unwrap()
+            .query_row([], |row| row.get(0))
+            .unwrap();
+        assert_eq!(fingerprint_count, 2);
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("14".to_string()));
+    }
+}
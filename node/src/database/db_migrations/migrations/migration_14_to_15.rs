@@ -0,0 +1,83 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+// A credited payment remembers which of our wallets it went to, so that a recheck covering only
+// some of them leaves the others alone. Payments credited before this migration get an empty
+// recipient: no recheck asks about them, so none of them can be taken for a reorganized one
+#[allow(non_camel_case_types)]
+pub struct Migrate_14_to_15;
+
+impl DatabaseMigration for Migrate_14_to_15 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        let statement_1 =
+            "alter table received_payment add column recipient_address text not null default ''";
+        utils.execute_upon_transaction(&[&statement_1])
+    }
+
+    fn old_version(&self) -> usize {
+        14
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    #[test]
+    fn migration_from_14_to_15_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_14_to_15_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    14,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            conn.prepare(
+                "insert into received_payment (transaction_hash, block_number, wallet_address, \
+                amount_high_b, amount_low_b) values ('0x1234', 42, '0xaaaa', 0, 5000)",
+            )
+            .unwrap()
+            .execute([])
+            .unwrap();
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            15,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        conn.prepare(
+            "insert into received_payment (transaction_hash, block_number, wallet_address, \
+            amount_high_b, amount_low_b, recipient_address) values ('0x5678', 43, '0xbbbb', 0, 6000, '0xcccc')",
+        )
+        .unwrap()
+        .execute([])
+        .unwrap();
+        let recipients = conn
+            .prepare("select recipient_address from received_payment order by block_number")
+            .unwrap()
+            .query_map([], |row| row.get::<usize, String>(0))
+            .unwrap()
+            .map(|recipient| recipient.unwrap())
+            .collect::<Vec<String>>();
+        assert_eq!(recipients, vec!["".to_string(), "0xcccc".to_string()]);
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("15".to_string()));
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_7_to_8;
+
+impl DatabaseMigration for Migrate_7_to_8 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        let statement_1 = "create table ledger (
+                rowid integer primary key,
+                timestamp integer not null,
+                wallet_address text not null,
+                kind text not null,
+                amount_high_b integer not null,
+                amount_low_b integer not null,
+                transaction_hash text null,
+                nonce integer null,
+                gas_price_gwei integer null,
+                block_number integer null,
+                outcome text null
+            ) strict";
+        let statement_2 = "create index ledger_wallet_address_idx on ledger (wallet_address)";
+        let statement_3 = "create index ledger_timestamp_idx on ledger (timestamp)";
+        utils.execute_upon_transaction(&[&statement_1, &statement_2, &statement_3])
+    }
+
+    fn old_version(&self) -> usize {
+        7
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_create_table_stm_contains_all_parts,
+        assert_index_stm_is_coupled_with_right_parameter, assert_table_created_as_strict,
+        assert_table_does_not_exist, bring_db_0_back_to_life_and_return_connection,
+        make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+
+    #[test]
+    fn migration_from_7_to_8_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_7_to_8_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    7,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            assert_table_does_not_exist(conn.as_ref(), "ledger");
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            8,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        assert_table_created_as_strict(conn.as_ref(), "ledger");
+        let expected_key_words: &[&[&str]] = &[
+            &["rowid", "integer", "primary", "key"],
+            &["timestamp", "integer", "not", "null"],
+            &["wallet_address", "text", "not", "null"],
+            &["kind", "text", "not", "null"],
+            &["amount_high_b", "integer", "not", "null"],
+            &["amount_low_b", "integer", "not", "null"],
+            &["transaction_hash", "text", "null"],
+            &["nonce", "integer", "null"],
+            &["gas_price_gwei", "integer", "null"],
+            &["block_number", "integer", "null"],
+            &["outcome", "text", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(conn.as_ref(), "ledger", expected_key_words);
+        let expected_key_words: &[&[&str]] = &[&["wallet_address"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "ledger_wallet_address_idx",
+            expected_key_words,
+        );
+        let expected_key_words: &[&[&str]] = &[&["timestamp"]];
+        assert_index_stm_is_coupled_with_right_parameter(
+            conn.as_ref(),
+            "ledger_timestamp_idx",
+            expected_key_words,
+        );
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("8".to_string()));
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::database::db_migrations::db_migrator::DatabaseMigration;
+use crate::database::db_migrations::migrator_utils::DBMigDeclarator;
+
+#[allow(non_camel_case_types)]
+pub struct Migrate_9_to_10;
+
+impl DatabaseMigration for Migrate_9_to_10 {
+    fn migrate<'a>(&self, utils: Box<dyn DBMigDeclarator + 'a>) -> rusqlite::Result<()> {
+        // Until now only the delinquency scan banned wallets, and it never noted when
+        let statement_1 =
+            "alter table banned add column reason text not null default 'delinquency'";
+        let statement_2 = "alter table banned add column note text null";
+        let statement_3 = "alter table banned add column timestamp integer null";
+        utils.execute_upon_transaction(&[&statement_1, &statement_2, &statement_3])
+    }
+
+    fn old_version(&self) -> usize {
+        9
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::database::db_initializer::{
+        DbInitializationConfig, DbInitializer, DbInitializerReal, DATABASE_FILE,
+    };
+    use crate::test_utils::database_utils::{
+        assert_create_table_stm_contains_all_parts, bring_db_0_back_to_life_and_return_connection,
+        make_external_data, retrieve_config_row,
+    };
+    use masq_lib::test_utils::utils::ensure_node_home_directory_exists;
+    use rusqlite::ToSql;
+
+    #[test]
+    fn migration_from_9_to_10_works() {
+        let dir_path =
+            ensure_node_home_directory_exists("db_migrations", "migration_from_9_to_10_works");
+        let db_path = dir_path.join(DATABASE_FILE);
+        let _ = bring_db_0_back_to_life_and_return_connection(&db_path);
+        let subject = DbInitializerReal::default();
+        {
+            let conn = subject
+                .initialize_to_version(
+                    &dir_path,
+                    9,
+                    DbInitializationConfig::create_or_migrate(make_external_data()),
+                )
+                .unwrap();
+            let params: &[&dyn ToSql] = &[&"0x0000000000000000000000000000000000616263"];
+            conn.prepare("insert into banned (wallet_address) values (?)")
+                .unwrap()
+                .execute(params)
+                .unwrap();
+        }
+
+        let result = subject.initialize_to_version(
+            &dir_path,
+            10,
+            DbInitializationConfig::create_or_migrate(make_external_data()),
+        );
+
+        let conn = result.unwrap();
+        let expected_key_words: &[&[&str]] = &[
+            &["wallet_address", "text", "primary", "key"],
+            &["reason", "text", "not", "null", "default", "'delinquency'"],
+            &["note", "text", "null"],
+            &["timestamp", "integer", "null"],
+        ];
+        assert_create_table_stm_contains_all_parts(conn.as_ref(), "banned", expected_key_words);
+        let (reason, note_opt, timestamp_opt) = conn
+            .prepare("select reason, note, timestamp from banned")
+            .unwrap()
+            .query_row([], |row| {
+                Ok((
+                    row.get::<usize, String>(0).unwrap(),
+                    row.get::<usize, Option<String>>(1).unwrap(),
+                    row.get::<usize, Option<i64>>(2).unwrap(),
+                ))
+            })
+            .unwrap();
+        assert_eq!(reason, "delinquency".to_string());
+        assert_eq!(note_opt, None);
+        assert_eq!(timestamp_opt, None);
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some("10".to_string()));
+    }
+}
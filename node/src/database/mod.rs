@@ -4,3 +4,4 @@ pub mod config_dumper;
 pub mod connection_wrapper;
 pub mod db_initializer;
 pub mod db_migrations;
+pub mod node_state_archive;
@@ -0,0 +1,765 @@
+// Copyright (c) 2023, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::blockchain::bip39::Bip39;
+use crate::database::connection_wrapper::ConnectionWrapper;
+use crate::database::db_initializer::{
+    DbInitializationConfig, DbInitializer, DbInitializerReal, ExternalData, DATABASE_FILE,
+};
+use masq_lib::blockchains::chains::{chain_from_chain_identifier, Chain};
+use masq_lib::constants::CURRENT_SCHEMA_VERSION;
+use masq_lib::utils::NeighborhoodModeLight;
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::{params_from_iter, Connection};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// Tables whose rows travel in an archive. Wallets and past neighbors live in the config table.
+pub const ARCHIVED_TABLES: [&str; 6] = [
+    "config",
+    "payable",
+    "receivable",
+    "pending_payable",
+    "banned",
+    "delinquency_warning",
+];
+pub const RESTORE_DIRECTORY: &str = "restore-in-progress";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ArchiveError {
+    DatabaseError(String),
+    EncryptionFailure(String),
+    UndecryptableArchive(String),
+    CorruptArchive(String),
+    NewerSchema { archive: usize, current: usize },
+    ChainMismatch { archive: String, node: String },
+    MigrationFailure(String),
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
+            ArchiveError::EncryptionFailure(msg) => {
+                write!(f, "Archive could not be encrypted: {}", msg)
+            }
+            ArchiveError::UndecryptableArchive(msg) => write!(
+                f,
+                "Archive could not be decrypted; wrong password or damaged archive: {}",
+                msg
+            ),
+            ArchiveError::CorruptArchive(msg) => write!(f, "Archive is corrupt: {}", msg),
+            ArchiveError::NewerSchema { archive, current } => write!(
+                f,
+                "Archive has database schema version {}, but this Node only understands versions up to {}",
+                archive, current
+            ),
+            ArchiveError::ChainMismatch { archive, node } => write!(
+                f,
+                "Archive was made on chain {}, but this Node runs on chain {}",
+                archive, node
+            ),
+            ArchiveError::MigrationFailure(msg) => {
+                write!(f, "Archive could not be migrated: {}", msg)
+            }
+        }
+    }
+}
+
+impl From<rusqlite::Error> for ArchiveError {
+    fn from(e: rusqlite::Error) -> Self {
+        ArchiveError::DatabaseError(format!("{}", e))
+    }
+}
+
+pub trait NodeStateArchiver {
+    fn backup(&self, db_password: &str) -> Result<String, ArchiveError>;
+    fn restore(&self, archive: &str, db_password: &str) -> Result<usize, ArchiveError>;
+}
+
+pub struct NodeStateArchiverReal {
+    data_directory: PathBuf,
+}
+
+impl NodeStateArchiver for NodeStateArchiverReal {
+    fn backup(&self, db_password: &str) -> Result<String, ArchiveError> {
+        let conn = self.live_connection()?;
+        let config = read_table(conn.as_ref(), "config")?;
+        let archive = NodeStateArchive {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            chain_name: config.value_of("chain_name").ok_or_else(|| {
+                ArchiveError::DatabaseError("No chain_name in config".to_string())
+            })?,
+            tables: ARCHIVED_TABLES
+                .iter()
+                .map(|name| read_table(conn.as_ref(), name))
+                .collect::<Result<Vec<ArchivedTable>, ArchiveError>>()?,
+        };
+        let serialized = serde_cbor::to_vec(&archive)
+            .map_err(|e| ArchiveError::EncryptionFailure(format!("{}", e)))?;
+        Bip39::encrypt_bytes(&serialized, db_password)
+            .map_err(|e| ArchiveError::EncryptionFailure(format!("{:?}", e)))
+    }
+
+    fn restore(&self, archive: &str, db_password: &str) -> Result<usize, ArchiveError> {
+        let archive = Self::open_archive(archive, db_password)?;
+        if archive.schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(ArchiveError::NewerSchema {
+                archive: archive.schema_version,
+                current: CURRENT_SCHEMA_VERSION,
+            });
+        }
+        let mut live_conn = self.live_connection()?;
+        let node_chain_name = read_table(live_conn.as_ref(), "config")?
+            .value_of("chain_name")
+            .ok_or_else(|| ArchiveError::DatabaseError("No chain_name in config".to_string()))?;
+        if archive.chain_name != node_chain_name {
+            return Err(ArchiveError::ChainMismatch {
+                archive: archive.chain_name,
+                node: node_chain_name,
+            });
+        }
+        let chain =
+            chain_from_chain_identifier(&node_chain_name).map_err(ArchiveError::DatabaseError)?;
+        let restore_directory = self.data_directory.join(RESTORE_DIRECTORY);
+        let result = Self::stage_archive(&restore_directory, &archive, chain, db_password)
+            .and_then(|staged_tables| Self::replace_live_tables(live_conn.as_mut(), staged_tables));
+        let _ = fs::remove_dir_all(&restore_directory);
+        result.map(|_| archive.schema_version)
+    }
+}
+
+impl NodeStateArchiverReal {
+    pub fn new(data_directory: &Path) -> Self {
+        Self {
+            data_directory: data_directory.to_path_buf(),
+        }
+    }
+
+    fn live_connection(&self) -> Result<Box<dyn ConnectionWrapper>, ArchiveError> {
+        DbInitializerReal::default()
+            .initialize(
+                &self.data_directory,
+                DbInitializationConfig::panic_on_migration(),
+            )
+            .map_err(|e| ArchiveError::DatabaseError(format!("{:?}", e)))
+    }
+
+    fn open_archive(archive: &str, db_password: &str) -> Result<NodeStateArchive, ArchiveError> {
+        let plain = Bip39::decrypt_bytes(archive.trim(), db_password)
+            .map_err(|e| ArchiveError::UndecryptableArchive(format!("{:?}", e)))?;
+        serde_cbor::from_slice::<NodeStateArchive>(plain.as_slice())
+            .map_err(|e| ArchiveError::CorruptArchive(format!("{}", e)))
+    }
+
+    // Rebuilds the archived database in a scratch directory, so that an archive made at an older
+    // schema version can be brought up to date by the regular migrations before it touches the
+    // live database. The schema is always our own for the archive's version; the archive supplies
+    // only rows, and only for the tables and columns that schema has.
+    fn stage_archive(
+        restore_directory: &Path,
+        archive: &NodeStateArchive,
+        chain: Chain,
+        db_password: &str,
+    ) -> Result<Vec<ArchivedTable>, ArchiveError> {
+        let _ = fs::remove_dir_all(restore_directory);
+        let archived_config = archive
+            .tables
+            .iter()
+            .find(|table| table.name == "config")
+            .ok_or_else(|| ArchiveError::CorruptArchive("No config table".to_string()))?;
+        let neighborhood_mode = archived_config
+            .value_of("neighborhood_mode")
+            .and_then(|mode| NeighborhoodModeLight::from_str(&mode).ok())
+            .unwrap_or(NeighborhoodModeLight::Standard);
+        let init_config = || {
+            DbInitializationConfig::create_or_migrate(ExternalData::new(
+                chain,
+                neighborhood_mode,
+                Some(db_password.to_string()),
+            ))
+        };
+        {
+            let mut staged_conn = DbInitializerReal::default()
+                .initialize_to_version(restore_directory, archive.schema_version, init_config())
+                .map_err(|e| ArchiveError::MigrationFailure(format!("{:?}", e)))?;
+            let tx = staged_conn.transaction()?;
+            for table in &archive.tables {
+                let staged_table = Self::staged_table_for(&tx, table)?;
+                tx.execute(&format!("delete from {}", staged_table.name), [])?;
+                write_rows(&tx, &staged_table)
+                    .map_err(|e| ArchiveError::CorruptArchive(format!("{}", e)))?;
+            }
+            tx.commit()?;
+        }
+        let staged_conn = DbInitializerReal::default()
+            .initialize(restore_directory, init_config())
+            .map_err(|e| ArchiveError::MigrationFailure(format!("{:?}", e)))?;
+        ARCHIVED_TABLES
+            .iter()
+            .map(|name| read_table(staged_conn.as_ref(), name))
+            .collect()
+    }
+
+    // Names from the archive never reach SQL: the staged table carries the names our own schema
+    // uses, and an archived table or column that schema lacks makes the archive corrupt
+    fn staged_table_for(
+        conn: &Connection,
+        table: &ArchivedTable,
+    ) -> Result<ArchivedTable, ArchiveError> {
+        let name = ARCHIVED_TABLES
+            .iter()
+            .find(|name| **name == table.name)
+            .ok_or_else(|| {
+                ArchiveError::CorruptArchive(format!("Unexpected table {}", table.name))
+            })?;
+        let staged_columns = conn
+            .prepare(&format!("select * from {}", name))
+            .map_err(|e| ArchiveError::CorruptArchive(format!("{}: {}", name, e)))?
+            .column_names()
+            .into_iter()
+            .map(|column| column.to_string())
+            .collect::<Vec<String>>();
+        let columns = table
+            .columns
+            .iter()
+            .map(|column| {
+                staged_columns
+                    .iter()
+                    .find(|staged| *staged == column)
+                    .cloned()
+                    .ok_or_else(|| {
+                        ArchiveError::CorruptArchive(format!(
+                            "Unexpected column {} in table {}",
+                            column, name
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<String>, ArchiveError>>()?;
+        if table.rows.iter().any(|row| row.len() != columns.len()) {
+            return Err(ArchiveError::CorruptArchive(format!(
+                "Row of the wrong width in table {}",
+                name
+            )));
+        }
+        Ok(ArchivedTable {
+            name: name.to_string(),
+            columns,
+            rows: table.rows.clone(),
+        })
+    }
+
+    // The staged tables were read back from our own schema, so their names are safe to use
+    fn replace_live_tables(
+        live_conn: &mut dyn ConnectionWrapper,
+        tables: Vec<ArchivedTable>,
+    ) -> Result<(), ArchiveError> {
+        let tx = live_conn.transaction()?;
+        for table in &tables {
+            tx.execute(&format!("delete from {}", table.name), [])?;
+            write_rows(&tx, table)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+struct NodeStateArchive {
+    schema_version: usize,
+    chain_name: String,
+    tables: Vec<ArchivedTable>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+struct ArchivedTable {
+    name: String,
+    columns: Vec<String>,
+    rows: Vec<Vec<ArchivedValue>>,
+}
+
+impl ArchivedTable {
+    // Only meaningful for the config table, whose rows are (name, value, encrypted)
+    fn value_of(&self, name: &str) -> Option<String> {
+        let name_idx = self.columns.iter().position(|c| c == "name")?;
+        let value_idx = self.columns.iter().position(|c| c == "value")?;
+        self.rows
+            .iter()
+            .find(|row| row[name_idx] == ArchivedValue::Text(name.to_string()))
+            .and_then(|row| match &row[value_idx] {
+                ArchivedValue::Text(value) => Some(value.clone()),
+                _ => None,
+            })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+enum ArchivedValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl From<ValueRef<'_>> for ArchivedValue {
+    fn from(value: ValueRef<'_>) -> Self {
+        match value {
+            ValueRef::Null => ArchivedValue::Null,
+            ValueRef::Integer(i) => ArchivedValue::Integer(i),
+            ValueRef::Real(r) => ArchivedValue::Real(r),
+            ValueRef::Text(t) => ArchivedValue::Text(String::from_utf8_lossy(t).to_string()),
+            ValueRef::Blob(b) => ArchivedValue::Blob(b.to_vec()),
+        }
+    }
+}
+
+impl From<&ArchivedValue> for Value {
+    fn from(value: &ArchivedValue) -> Self {
+        match value {
+            ArchivedValue::Null => Value::Null,
+            ArchivedValue::Integer(i) => Value::Integer(*i),
+            ArchivedValue::Real(r) => Value::Real(*r),
+            ArchivedValue::Text(t) => Value::Text(t.clone()),
+            ArchivedValue::Blob(b) => Value::Blob(b.clone()),
+        }
+    }
+}
+
+fn read_table(conn: &dyn ConnectionWrapper, name: &str) -> Result<ArchivedTable, ArchiveError> {
+    let mut stmt = conn.prepare(&format!("select * from {}", name))?;
+    let columns = stmt
+        .column_names()
+        .into_iter()
+        .map(|column| column.to_string())
+        .collect::<Vec<String>>();
+    let column_count = columns.len();
+    let rows = stmt
+        .query_map([], |row| {
+            (0..column_count)
+                .map(|idx| row.get_ref(idx).map(ArchivedValue::from))
+                .collect::<Result<Vec<ArchivedValue>, rusqlite::Error>>()
+        })?
+        .collect::<Result<Vec<Vec<ArchivedValue>>, rusqlite::Error>>()?;
+    Ok(ArchivedTable {
+        name: name.to_string(),
+        columns,
+        rows,
+    })
+}
+
+fn write_rows(conn: &Connection, table: &ArchivedTable) -> Result<(), rusqlite::Error> {
+    let sql = format!(
+        "insert into {} ({}) values ({})",
+        table.name,
+        table.columns.join(", "),
+        vec!["?"; table.columns.len()].join(", ")
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    for row in &table.rows {
+        stmt.execute(params_from_iter(row.iter().map(Value::from)))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_config::config_dao::ConfigDaoReal;
+    use crate::db_config::persistent_configuration::{
+        PersistentConfiguration, PersistentConfigurationReal,
+    };
+    use crate::test_utils::database_utils::{
+        bring_db_0_back_to_life_and_return_connection, make_external_data, retrieve_config_row,
+    };
+    use crate::test_utils::make_wallet;
+    use masq_lib::test_utils::utils::{ensure_node_home_directory_exists, TEST_DEFAULT_CHAIN};
+
+    fn make_live_database(test_name: &str, side: &str) -> PathBuf {
+        let data_directory = ensure_node_home_directory_exists(
+            "node_state_archive",
+            &format!("{}/{}", test_name, side),
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&data_directory, DbInitializationConfig::test_default())
+            .unwrap();
+        let mut persistent_config =
+            PersistentConfigurationReal::new(Box::new(ConfigDaoReal::new(conn)));
+        persistent_config
+            .change_password(None, &format!("{} password", side))
+            .unwrap();
+        data_directory
+    }
+
+    fn insert_payable(data_directory: &Path, wallet: &str, balance_low_b: i64) {
+        let conn = DbInitializerReal::default()
+            .initialize(data_directory, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        conn.prepare(
+            "insert into payable (wallet_address, balance_high_b, balance_low_b, last_paid_timestamp) \
+             values (?, 0, ?, 1234)",
+        )
+        .unwrap()
+        .execute(rusqlite::params![wallet, balance_low_b])
+        .unwrap();
+    }
+
+    fn read_payables(data_directory: &Path) -> Vec<(String, i64)> {
+        let conn = DbInitializerReal::default()
+            .initialize(data_directory, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        let mut stmt = conn
+            .prepare("select wallet_address, balance_low_b from payable order by wallet_address")
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect()
+    }
+
+    fn insert_delinquency_warning(data_directory: &Path, wallet: &str, timestamp: i64) {
+        let conn = DbInitializerReal::default()
+            .initialize(data_directory, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        conn.prepare("insert into delinquency_warning (wallet_address, timestamp) values (?, ?)")
+            .unwrap()
+            .execute(rusqlite::params![wallet, timestamp])
+            .unwrap();
+    }
+
+    fn read_delinquency_warnings(data_directory: &Path) -> Vec<(String, i64)> {
+        let conn = DbInitializerReal::default()
+            .initialize(data_directory, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        let mut stmt = conn
+            .prepare(
+                "select wallet_address, timestamp from delinquency_warning order by wallet_address",
+            )
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|row| row.unwrap())
+            .collect()
+    }
+
+    fn archive_of(data_directory: &Path) -> NodeStateArchive {
+        let conn = DbInitializerReal::default()
+            .initialize(data_directory, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        NodeStateArchive {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            chain_name: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string(),
+            tables: ARCHIVED_TABLES
+                .iter()
+                .map(|name| read_table(conn.as_ref(), name).unwrap())
+                .collect(),
+        }
+    }
+
+    fn encrypt_archive(archive: &NodeStateArchive, db_password: &str) -> String {
+        Bip39::encrypt_bytes(&serde_cbor::to_vec(archive).unwrap(), db_password).unwrap()
+    }
+
+    #[test]
+    fn constants_have_correct_values() {
+        assert_eq!(
+            ARCHIVED_TABLES,
+            [
+                "config",
+                "payable",
+                "receivable",
+                "pending_payable",
+                "banned",
+                "delinquency_warning"
+            ]
+        );
+        assert_eq!(RESTORE_DIRECTORY, "restore-in-progress");
+    }
+
+    #[test]
+    fn backup_can_be_restored_on_another_node() {
+        let test_name = "backup_can_be_restored_on_another_node";
+        let old_node = make_live_database(test_name, "old");
+        let new_node = make_live_database(test_name, "new");
+        let wallet = make_wallet("creditor").to_string();
+        insert_payable(&old_node, &wallet, 4567);
+        insert_payable(&new_node, &make_wallet("stranger").to_string(), 1);
+        let debtor = make_wallet("debtor").to_string();
+        insert_delinquency_warning(&old_node, &debtor, 2345);
+        insert_delinquency_warning(&new_node, &make_wallet("forgiven").to_string(), 3456);
+        let archive = NodeStateArchiverReal::new(&old_node)
+            .backup("old password")
+            .unwrap();
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&archive, "old password");
+
+        assert_eq!(result, Ok(CURRENT_SCHEMA_VERSION));
+        assert_eq!(read_payables(&new_node), vec![(wallet, 4567)]);
+        assert_eq!(read_delinquency_warnings(&new_node), vec![(debtor, 2345)]);
+        let conn = DbInitializerReal::default()
+            .initialize(&new_node, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        let persistent_config =
+            PersistentConfigurationReal::new(Box::new(ConfigDaoReal::new(conn)));
+        assert_eq!(
+            persistent_config.check_password(Some("old password".to_string())),
+            Ok(true)
+        );
+        assert_eq!(new_node.join(RESTORE_DIRECTORY).exists(), false);
+    }
+
+    #[test]
+    fn restore_rejects_wrong_password() {
+        let test_name = "restore_rejects_wrong_password";
+        let old_node = make_live_database(test_name, "old");
+        let new_node = make_live_database(test_name, "new");
+        let archive = NodeStateArchiverReal::new(&old_node)
+            .backup("old password")
+            .unwrap();
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&archive, "new password");
+
+        match result {
+            Err(ArchiveError::UndecryptableArchive(_)) => (),
+            x => panic!("Expected UndecryptableArchive, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn restore_rejects_archive_from_newer_schema() {
+        let new_node = make_live_database("restore_rejects_archive_from_newer_schema", "new");
+        let archive = NodeStateArchive {
+            schema_version: CURRENT_SCHEMA_VERSION + 1,
+            chain_name: "dev".to_string(),
+            tables: vec![],
+        };
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&encrypt_archive(&archive, "password"), "password");
+
+        assert_eq!(
+            result,
+            Err(ArchiveError::NewerSchema {
+                archive: CURRENT_SCHEMA_VERSION + 1,
+                current: CURRENT_SCHEMA_VERSION
+            })
+        );
+    }
+
+    #[test]
+    fn restore_rejects_archive_from_another_chain() {
+        let new_node = make_live_database("restore_rejects_archive_from_another_chain", "new");
+        let archive = NodeStateArchive {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            chain_name: "polygon-mainnet".to_string(),
+            tables: vec![],
+        };
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&encrypt_archive(&archive, "password"), "password");
+
+        assert_eq!(
+            result,
+            Err(ArchiveError::ChainMismatch {
+                archive: "polygon-mainnet".to_string(),
+                node: TEST_DEFAULT_CHAIN.rec().literal_identifier.to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn restore_migrates_archive_from_older_schema() {
+        let test_name = "restore_migrates_archive_from_older_schema";
+        let old_node =
+            ensure_node_home_directory_exists("node_state_archive", &format!("{}/old", test_name));
+        let _ = bring_db_0_back_to_life_and_return_connection(&old_node.join(DATABASE_FILE));
+        let old_conn = DbInitializerReal::default()
+            .initialize_to_version(
+                &old_node,
+                11,
+                DbInitializationConfig::create_or_migrate(make_external_data()),
+            )
+            .unwrap();
+        let archive = NodeStateArchive {
+            schema_version: 11,
+            chain_name: read_table(old_conn.as_ref(), "config")
+                .unwrap()
+                .value_of("chain_name")
+                .unwrap(),
+            tables: ARCHIVED_TABLES
+                .iter()
+                .map(|name| read_table(old_conn.as_ref(), name).unwrap())
+                .collect(),
+        };
+        let new_node = make_live_database(test_name, "new");
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&encrypt_archive(&archive, "password"), "password");
+
+        assert_eq!(result, Ok(11));
+        let conn = DbInitializerReal::default()
+            .initialize(&new_node, DbInitializationConfig::panic_on_migration())
+            .unwrap();
+        let (schema_version, _) = retrieve_config_row(conn.as_ref(), "schema_version");
+        assert_eq!(schema_version, Some(CURRENT_SCHEMA_VERSION.to_string()));
+        let (retired_earning_wallets, _) =
+            retrieve_config_row(conn.as_ref(), "retired_earning_wallets");
+        assert_eq!(retired_earning_wallets, None);
+    }
+
+    #[test]
+    fn restore_rejects_archive_with_table_that_is_not_archived() {
+        let test_name = "restore_rejects_archive_with_table_that_is_not_archived";
+        let old_node = make_live_database(test_name, "old");
+        let new_node = make_live_database(test_name, "new");
+        insert_payable(&new_node, &make_wallet("creditor").to_string(), 1234);
+        let mut archive = archive_of(&old_node);
+        archive.tables.push(ArchivedTable {
+            name: "payable; drop table payable".to_string(),
+            columns: vec![],
+            rows: vec![],
+        });
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&encrypt_archive(&archive, "password"), "password");
+
+        assert_eq!(
+            result,
+            Err(ArchiveError::CorruptArchive(
+                "Unexpected table payable; drop table payable".to_string()
+            ))
+        );
+        assert_eq!(
+            read_payables(&new_node),
+            vec![(make_wallet("creditor").to_string(), 1234)]
+        );
+        assert_eq!(new_node.join(RESTORE_DIRECTORY).exists(), false);
+    }
+
+    #[test]
+    fn restore_rejects_archive_with_column_the_schema_does_not_have() {
+        let test_name = "restore_rejects_archive_with_column_the_schema_does_not_have";
+        let old_node = make_live_database(test_name, "old");
+        let new_node = make_live_database(test_name, "new");
+        let mut archive = archive_of(&old_node);
+        let banned = archive
+            .tables
+            .iter_mut()
+            .find(|table| table.name == "banned")
+            .unwrap();
+        banned
+            .columns
+            .push("wallet_address) select 1; --".to_string());
+        let subject = NodeStateArchiverReal::new(&new_node);
+
+        let result = subject.restore(&encrypt_archive(&archive, "password"), "password");
+
+        assert_eq!(
+            result,
+            Err(ArchiveError::CorruptArchive(
+                "Unexpected column wallet_address) select 1; -- in table banned".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn restore_rejects_garbage() {
+        let new_node = make_live_database("restore_rejects_garbage", "new");
+        let subject = NodeStateArchiverReal::new(&new_node);
+        let archive = Bip39::encrypt_bytes(b"not an archive", "password").unwrap();
+
+        let result = subject.restore(&archive, "password");
+
+        match result {
+            Err(ArchiveError::CorruptArchive(_)) => (),
+            x => panic!("Expected CorruptArchive, got {:?}", x),
+        }
+    }
+
+    #[test]
+    fn archive_errors_are_displayed_helpfully() {
+        assert_eq!(
+            ArchiveError::NewerSchema {
+                archive: 13,
+                current: 12
+            }
+            .to_string(),
+            "Archive has database schema version 13, but this Node only understands versions up to 12"
+        );
+        assert_eq!(
+            ArchiveError::ChainMismatch {
+                archive: "eth-mainnet".to_string(),
+                node: "polygon-mainnet".to_string()
+            }
+            .to_string(),
+            "Archive was made on chain eth-mainnet, but this Node runs on chain polygon-mainnet"
+        );
+        assert_eq!(
+            ArchiveError::UndecryptableArchive("booga".to_string()).to_string(),
+            "Archive could not be decrypted; wrong password or damaged archive: booga"
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use crate::database::node_state_archive::{ArchiveError, NodeStateArchiver};
+    use std::cell::RefCell;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    pub struct NodeStateArchiverMock {
+        backup_params: Arc<Mutex<Vec<String>>>,
+        backup_results: RefCell<Vec<Result<String, ArchiveError>>>,
+        restore_params: Arc<Mutex<Vec<(String, String)>>>,
+        restore_results: RefCell<Vec<Result<usize, ArchiveError>>>,
+    }
+
+    impl NodeStateArchiver for NodeStateArchiverMock {
+        fn backup(&self, db_password: &str) -> Result<String, ArchiveError> {
+            self.backup_params
+                .lock()
+                .unwrap()
+                .push(db_password.to_string());
+            self.backup_results.borrow_mut().remove(0)
+        }
+
+        fn restore(&self, archive: &str, db_password: &str) -> Result<usize, ArchiveError> {
+            self.restore_params
+                .lock()
+                .unwrap()
+                .push((archive.to_string(), db_password.to_string()));
+            self.restore_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl NodeStateArchiverMock {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn backup_params(mut self, params: &Arc<Mutex<Vec<String>>>) -> Self {
+            self.backup_params = params.clone();
+            self
+        }
+
+        pub fn backup_result(self, result: Result<String, ArchiveError>) -> Self {
+            self.backup_results.borrow_mut().push(result);
+            self
+        }
+
+        pub fn restore_params(mut self, params: &Arc<Mutex<Vec<(String, String)>>>) -> Self {
+            self.restore_params = params.clone();
+            self
+        }
+
+        pub fn restore_result(self, result: Result<usize, ArchiveError>) -> Self {
+            self.restore_results.borrow_mut().push(result);
+            self
+        }
+    }
+}
@@ -40,6 +40,8 @@ pub trait ConfigDao {
     fn get_all(&self) -> Result<Vec<ConfigDaoRecord>, ConfigDaoError>;
     fn get(&self, name: &str) -> Result<ConfigDaoRecord, ConfigDaoError>;
     fn set(&self, name: &str, value: Option<String>) -> Result<(), ConfigDaoError>;
+    // Either all of the values are set or none of them is
+    fn set_together(&mut self, values: Vec<(&str, Option<String>)>) -> Result<(), ConfigDaoError>;
 }
 
 pub struct ConfigDaoReal {
@@ -75,6 +77,26 @@ impl ConfigDao for ConfigDaoReal {
         let params: &[&dyn ToSql] = &[&value, &name];
         handle_update_execution(stmt.execute(params))
     }
+
+    fn set_together(&mut self, values: Vec<(&str, Option<String>)>) -> Result<(), ConfigDaoError> {
+        let transaction = self
+            .conn
+            .transaction()
+            .map_err(|_| ConfigDaoError::TransactionError)?;
+        {
+            let mut stmt = transaction
+                .prepare("update config set value = ? where name = ?")
+                .map_err(|e| ConfigDaoError::DatabaseError(format!("{}", e)))?;
+            for (name, value) in values {
+                let params: &[&dyn ToSql] = &[&value, &name];
+                handle_update_execution(stmt.execute(params))?;
+            }
+        }
+        // Dropping the transaction without committing it rolls back whatever got set
+        transaction
+            .commit()
+            .map_err(|_| ConfigDaoError::TransactionError)
+    }
 }
 
 impl ConfigDaoReal {
@@ -234,6 +256,55 @@ mod tests {
         assert_eq!(subject_get, modified_value);
     }
 
+    #[test]
+    fn set_together_sets_all_values() {
+        let home_dir =
+            ensure_node_home_directory_exists("config_dao", "set_together_sets_all_values");
+        let mut subject = ConfigDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+
+        subject
+            .set_together(vec![
+                ("gas_price", Some("1234".to_string())),
+                ("earning_wallet_address", Some("0x1234".to_string())),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            subject.get("gas_price").unwrap(),
+            ConfigDaoRecord::new("gas_price", Some("1234"), false)
+        );
+        assert_eq!(
+            subject.get("earning_wallet_address").unwrap(),
+            ConfigDaoRecord::new("earning_wallet_address", Some("0x1234"), false)
+        );
+    }
+
+    #[test]
+    fn set_together_sets_nothing_if_any_value_fails() {
+        let home_dir = ensure_node_home_directory_exists(
+            "config_dao",
+            "set_together_sets_nothing_if_any_value_fails",
+        );
+        let mut subject = ConfigDaoReal::new(
+            DbInitializerReal::default()
+                .initialize(&home_dir, DbInitializationConfig::test_default())
+                .unwrap(),
+        );
+        let gas_price_before = subject.get("gas_price").unwrap();
+
+        let result = subject.set_together(vec![
+            ("gas_price", Some("1234".to_string())),
+            ("booga", Some("bigglesworth".to_string())),
+        ]);
+
+        assert_eq!(result, Err(ConfigDaoError::NotPresent));
+        assert_eq!(subject.get("gas_price").unwrap(), gas_price_before);
+    }
+
     #[test]
     fn setting_nonexistent_value_returns_not_present() {
         let home_dir = ensure_node_home_directory_exists(
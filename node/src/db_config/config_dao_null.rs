@@ -69,6 +69,10 @@ impl ConfigDao for ConfigDaoNull {
     fn set(&self, _name: &str, _value: Option<String>) -> Result<(), ConfigDaoError> {
         Ok(())
     }
+
+    fn set_together(&mut self, _values: Vec<(&str, Option<String>)>) -> Result<(), ConfigDaoError> {
+        Ok(())
+    }
 }
 
 impl Default for ConfigDaoNull {
@@ -109,6 +113,7 @@ impl Default for ConfigDaoNull {
         data.insert("past_neighbors".to_string(), (None, true));
         data.insert("mapping_protocol".to_string(), (None, false));
         data.insert("earning_wallet_address".to_string(), (None, false));
+        data.insert("retired_earning_wallets".to_string(), (None, false));
         data.insert(
             "schema_version".to_string(),
             (Some(format!("{}", CURRENT_SCHEMA_VERSION)), false),
@@ -259,6 +264,7 @@ mod tests {
             ("past_neighbors", None),
             ("mapping_protocol", None),
             ("earning_wallet_address", None),
+            ("retired_earning_wallets", None),
             (
                 "schema_version",
                 Some(format!("{}", CURRENT_SCHEMA_VERSION).as_str()),
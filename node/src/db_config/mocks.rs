@@ -11,6 +11,8 @@ pub struct ConfigDaoMock {
     get_results: RefCell<Vec<Result<ConfigDaoRecord, ConfigDaoError>>>,
     set_params: Arc<Mutex<Vec<(String, Option<String>)>>>,
     set_results: RefCell<Vec<Result<(), ConfigDaoError>>>,
+    set_together_params: Arc<Mutex<Vec<Vec<(String, Option<String>)>>>>,
+    set_together_results: RefCell<Vec<Result<(), ConfigDaoError>>>,
 }
 
 impl ConfigDao for ConfigDaoMock {
@@ -30,6 +32,16 @@ impl ConfigDao for ConfigDaoMock {
             .push((name.to_string(), value.map(|x| x.to_string())));
         self.set_results.borrow_mut().remove(0)
     }
+
+    fn set_together(&mut self, values: Vec<(&str, Option<String>)>) -> Result<(), ConfigDaoError> {
+        self.set_together_params.lock().unwrap().push(
+            values
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        );
+        self.set_together_results.borrow_mut().remove(0)
+    }
 }
 
 impl ConfigDaoMock {
@@ -40,6 +52,8 @@ impl ConfigDaoMock {
             get_results: RefCell::new(vec![]),
             set_params: Arc::new(Mutex::new(vec![])),
             set_results: RefCell::new(vec![]),
+            set_together_params: Arc::new(Mutex::new(vec![])),
+            set_together_results: RefCell::new(vec![]),
         }
     }
 
@@ -67,4 +81,17 @@ impl ConfigDaoMock {
         self.set_results.borrow_mut().push(result);
         self
     }
+
+    pub fn set_together_params(
+        mut self,
+        params: &Arc<Mutex<Vec<Vec<(String, Option<String>)>>>>,
+    ) -> Self {
+        self.set_together_params = params.clone();
+        self
+    }
+
+    pub fn set_together_result(self, result: Result<(), ConfigDaoError>) -> Self {
+        self.set_together_results.borrow_mut().push(result);
+        self
+    }
 }
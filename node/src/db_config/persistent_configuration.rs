@@ -1,5 +1,6 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::accountant::database_access_objects::dao_utils::{from_time_t, to_time_t};
 #[cfg(test)]
 use crate::arbitrary_id_stamp_in_trait;
 use crate::blockchain::bip32::Bip32ECKeyProvider;
@@ -11,7 +12,9 @@ use crate::db_config::typed_config_layer::{
     decode_bytes, decode_combined_params, decode_u64, encode_bytes, encode_u64,
     TypedConfigLayerError,
 };
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
+use crate::sub_lib::accountant::{
+    EarningWallets, PaymentThresholds, RetiredEarningWallet, ScanIntervals,
+};
 use crate::sub_lib::cryptde::PlainData;
 use crate::sub_lib::neighborhood::{NodeDescriptor, RatePack};
 use crate::sub_lib::wallet::Wallet;
@@ -25,6 +28,7 @@ use rustc_hex::{FromHex, ToHex};
 use std::fmt::Display;
 use std::net::{Ipv4Addr, SocketAddrV4, TcpListener};
 use std::str::FromStr;
+use std::time::SystemTime;
 use websocket::url::Url;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -111,6 +115,13 @@ pub trait PersistentConfiguration {
     fn earning_wallet(&self) -> Result<Option<Wallet>, PersistentConfigError>;
     // WARNING: Actors should get earning-wallet information from their startup config, not from here
     fn earning_wallet_address(&self) -> Result<Option<String>, PersistentConfigError>;
+    // WARNING: Actors should get retired-earning-wallet information from their startup config, not from here
+    fn retired_earning_wallets(&self) -> Result<Vec<RetiredEarningWallet>, PersistentConfigError>;
+    fn rotate_earning_wallet(
+        &mut self,
+        new_earning_wallet_address: &str,
+        watch_until: SystemTime,
+    ) -> Result<RetiredEarningWallet, PersistentConfigError>;
     fn gas_price(&self) -> Result<u64, PersistentConfigError>;
     fn set_gas_price(&mut self, gas_price: u64) -> Result<(), PersistentConfigError>;
     fn mapping_protocol(&self) -> Result<Option<AutomapProtocol>, PersistentConfigError>;
@@ -302,6 +313,51 @@ impl PersistentConfiguration for PersistentConfigurationReal {
         Ok(self.get("earning_wallet_address")?)
     }
 
+    fn retired_earning_wallets(&self) -> Result<Vec<RetiredEarningWallet>, PersistentConfigError> {
+        match self.get("retired_earning_wallets")? {
+            None => Ok(vec![]),
+            Some(encoded) => Self::decode_retired_earning_wallets(&encoded),
+        }
+    }
+
+    fn rotate_earning_wallet(
+        &mut self,
+        new_earning_wallet_address: &str,
+        watch_until: SystemTime,
+    ) -> Result<RetiredEarningWallet, PersistentConfigError> {
+        let new_earning_wallet = Wallet::from_str(new_earning_wallet_address).map_err(|_| {
+            PersistentConfigError::BadAddressFormat(new_earning_wallet_address.to_string())
+        })?;
+        let current_earning_wallet = match self.earning_wallet()? {
+            Some(wallet) => wallet,
+            None => return Err(PersistentConfigError::NotPresent),
+        };
+        if new_earning_wallet == current_earning_wallet {
+            return Err(PersistentConfigError::Collision(
+                "New earning wallet address is the same as the current one".to_string(),
+            ));
+        }
+        let retired = RetiredEarningWallet {
+            wallet: current_earning_wallet.clone(),
+            watch_until,
+        };
+        let mut earning_wallets =
+            EarningWallets::new(current_earning_wallet, self.retired_earning_wallets()?);
+        earning_wallets.drop_expired(SystemTime::now());
+        earning_wallets.rotate(new_earning_wallet, retired.clone());
+        self.dao.set_together(vec![
+            (
+                "retired_earning_wallets",
+                Self::encode_retired_earning_wallets(&earning_wallets.retired),
+            ),
+            (
+                "earning_wallet_address",
+                Some(new_earning_wallet_address.to_string()),
+            ),
+        ])?;
+        Ok(retired)
+    }
+
     fn gas_price(&self) -> Result<u64, PersistentConfigError> {
         match decode_u64(self.get("gas_price")?) {
             Ok(val) => {
@@ -509,6 +565,43 @@ impl PersistentConfigurationReal {
         Wallet::from_str(address).is_ok()
     }
 
+    // Stored as comma-separated "<address>@<watch-until time_t>" pairs
+    fn encode_retired_earning_wallets(retired: &[RetiredEarningWallet]) -> Option<String> {
+        if retired.is_empty() {
+            return None;
+        }
+        Some(
+            retired
+                .iter()
+                .map(|retired| format!("{}@{}", retired.wallet, to_time_t(retired.watch_until)))
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+    }
+
+    fn decode_retired_earning_wallets(
+        encoded: &str,
+    ) -> Result<Vec<RetiredEarningWallet>, PersistentConfigError> {
+        encoded
+            .split(',')
+            .map(|pair| {
+                let uninterpretable = || {
+                    PersistentConfigError::UninterpretableValue(format!(
+                        "Retired earning wallet '{}' is not <address>@<time_t>",
+                        pair
+                    ))
+                };
+                let (address, watch_until) = pair.split_once('@').ok_or_else(uninterpretable)?;
+                let wallet = Wallet::from_str(address).map_err(|_| uninterpretable())?;
+                let watch_until = watch_until.parse::<i64>().map_err(|_| uninterpretable())?;
+                Ok(RetiredEarningWallet {
+                    wallet,
+                    watch_until: from_time_t(watch_until),
+                })
+            })
+            .collect()
+    }
+
     fn get(&self, name: &str) -> Result<Option<String>, ConfigDaoError> {
         self.get_record(name).map(|record| record.value_opt)
     }
@@ -1156,6 +1249,248 @@ mod tests {
         assert_eq!(*get_params, vec!["earning_wallet_address".to_string()]);
     }
 
+    #[test]
+    fn retired_earning_wallets_when_there_are_none() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "retired_earning_wallets",
+            None,
+            false,
+        )));
+        let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.retired_earning_wallets();
+
+        assert_eq!(result, Ok(vec![]));
+    }
+
+    #[test]
+    fn retired_earning_wallets_when_there_are_some() {
+        let get_params_arc = Arc::new(Mutex::new(vec![]));
+        let config_dao = ConfigDaoMock::new()
+            .get_params(&get_params_arc)
+            .get_result(Ok(ConfigDaoRecord::new(
+                "retired_earning_wallets",
+                Some(
+                    "0x7d6dabd6b5c75291a3258c29b418f5805792a875@1700000000,\
+                     0x0000000000000000000000000000000000000001@1800000000",
+                ),
+                false,
+            )));
+        let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.retired_earning_wallets();
+
+        assert_eq!(
+            result,
+            Ok(vec![
+                RetiredEarningWallet {
+                    wallet: Wallet::from_str("0x7d6dabd6b5c75291a3258c29b418f5805792a875").unwrap(),
+                    watch_until: from_time_t(1_700_000_000),
+                },
+                RetiredEarningWallet {
+                    wallet: Wallet::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+                    watch_until: from_time_t(1_800_000_000),
+                }
+            ])
+        );
+        let get_params = get_params_arc.lock().unwrap();
+        assert_eq!(*get_params, vec!["retired_earning_wallets".to_string()]);
+    }
+
+    #[test]
+    fn retired_earning_wallets_complains_about_uninterpretable_values() {
+        vec![
+            "booga",
+            "booga@1700000000",
+            "0x7d6dabd6b5c75291a3258c29b418f5805792a875@booga",
+        ]
+        .into_iter()
+        .for_each(|value| {
+            let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+                "retired_earning_wallets",
+                Some(value),
+                false,
+            )));
+            let subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+            let result = subject.retired_earning_wallets();
+
+            assert_eq!(
+                result,
+                Err(PersistentConfigError::UninterpretableValue(format!(
+                    "Retired earning wallet '{}' is not <address>@<time_t>",
+                    value
+                )))
+            );
+        })
+    }
+
+    #[test]
+    fn rotate_earning_wallet_retires_the_current_one_and_forgets_expired_ones() {
+        let set_together_params_arc = Arc::new(Mutex::new(vec![]));
+        let later = to_time_t(SystemTime::now()) + 1000;
+        let config_dao = ConfigDaoMock::new()
+            .get_result(Ok(ConfigDaoRecord::new(
+                "earning_wallet_address",
+                Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875"),
+                false,
+            )))
+            .get_result(Ok(ConfigDaoRecord::new(
+                "retired_earning_wallets",
+                Some(&format!(
+                    "0x0000000000000000000000000000000000000001@1000000000,\
+                     0x0000000000000000000000000000000000000002@{}",
+                    later
+                )),
+                false,
+            )))
+            .set_together_params(&set_together_params_arc)
+            .set_together_result(Ok(()));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.rotate_earning_wallet(
+            "0x0000000000000000000000000000000000000003",
+            from_time_t(later + 1000),
+        );
+
+        assert_eq!(
+            result,
+            Ok(RetiredEarningWallet {
+                wallet: Wallet::from_str("0x7d6dabd6b5c75291a3258c29b418f5805792a875").unwrap(),
+                watch_until: from_time_t(later + 1000),
+            })
+        );
+        let set_together_params = set_together_params_arc.lock().unwrap();
+        assert_eq!(
+            *set_together_params,
+            vec![vec![
+                (
+                    "retired_earning_wallets".to_string(),
+                    Some(format!(
+                        "0x0000000000000000000000000000000000000002@{},\
+                         0x7d6dabd6b5c75291a3258c29b418f5805792a875@{}",
+                        later,
+                        later + 1000
+                    ))
+                ),
+                (
+                    "earning_wallet_address".to_string(),
+                    Some("0x0000000000000000000000000000000000000003".to_string())
+                )
+            ]]
+        )
+    }
+
+    #[test]
+    fn rotate_earning_wallet_reports_failure_to_store_the_change() {
+        let config_dao = ConfigDaoMock::new()
+            .get_result(Ok(ConfigDaoRecord::new(
+                "earning_wallet_address",
+                Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875"),
+                false,
+            )))
+            .get_result(Ok(ConfigDaoRecord::new(
+                "retired_earning_wallets",
+                None,
+                false,
+            )))
+            .set_together_result(Err(ConfigDaoError::DatabaseError("Booga".to_string())));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.rotate_earning_wallet(
+            "0x0000000000000000000000000000000000000003",
+            SystemTime::now(),
+        );
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::DatabaseError("Booga".to_string()))
+        );
+    }
+
+    #[test]
+    fn rotate_earning_wallet_complains_about_bad_address() {
+        let mut subject = PersistentConfigurationReal::new(Box::new(ConfigDaoMock::new()));
+
+        let result = subject.rotate_earning_wallet("booga", SystemTime::now());
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::BadAddressFormat("booga".to_string()))
+        );
+    }
+
+    #[test]
+    fn rotate_earning_wallet_complains_if_there_is_no_earning_wallet_yet() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_address",
+            None,
+            false,
+        )));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.rotate_earning_wallet(
+            "0x0000000000000000000000000000000000000003",
+            SystemTime::now(),
+        );
+
+        assert_eq!(result, Err(PersistentConfigError::NotPresent));
+    }
+
+    #[test]
+    fn rotate_earning_wallet_complains_if_the_new_wallet_is_the_current_one() {
+        let config_dao = ConfigDaoMock::new().get_result(Ok(ConfigDaoRecord::new(
+            "earning_wallet_address",
+            Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875"),
+            false,
+        )));
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+
+        let result = subject.rotate_earning_wallet(
+            "0x7D6DABD6B5C75291A3258C29B418F5805792A875",
+            SystemTime::now(),
+        );
+
+        assert_eq!(
+            result,
+            Err(PersistentConfigError::Collision(
+                "New earning wallet address is the same as the current one".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn rotate_earning_wallet_works_against_the_database() {
+        let home_dir = ensure_node_home_directory_exists(
+            "persistent_configuration",
+            "rotate_earning_wallet_works_against_the_database",
+        );
+        let conn = DbInitializerReal::default()
+            .initialize(&home_dir, DbInitializationConfig::test_default())
+            .unwrap();
+        let config_dao = ConfigDaoReal::new(conn);
+        config_dao
+            .set(
+                "earning_wallet_address",
+                Some("0x7d6dabd6b5c75291a3258c29b418f5805792a875".to_string()),
+            )
+            .unwrap();
+        let mut subject = PersistentConfigurationReal::new(Box::new(config_dao));
+        let watch_until = from_time_t(to_time_t(SystemTime::now()) + 3600);
+
+        let retired = subject
+            .rotate_earning_wallet("0x0000000000000000000000000000000000000003", watch_until)
+            .unwrap();
+
+        assert_eq!(
+            subject.earning_wallet_address(),
+            Ok(Some(
+                "0x0000000000000000000000000000000000000003".to_string()
+            ))
+        );
+        assert_eq!(subject.retired_earning_wallets(), Ok(vec![retired]));
+    }
+
     fn make_seed_info(db_password: &str) -> (PlainData, String) {
         let mnemonic = Bip39::mnemonic(MnemonicType::Words12, Language::English);
         let mnemonic_seed = Bip39::seed(&mnemonic, "");
@@ -6,6 +6,7 @@ mod routing_service;
 
 use crate::bootstrapper::CryptDEPair;
 use crate::hopper::routing_service::RoutingServiceSubs;
+use crate::sub_lib::configurator::{ConfigurationChange, ConfigurationChangeMessage};
 use crate::sub_lib::dispatcher::InboundClientData;
 use crate::sub_lib::hopper::HopperSubs;
 use crate::sub_lib::hopper::IncipientCoresPackage;
@@ -114,6 +115,18 @@ impl Handler<NodeFromUiMessage> for Hopper {
     }
 }
 
+impl Handler<ConfigurationChangeMessage> for Hopper {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ConfigurationChangeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_configuration_change(msg.change)
+    }
+}
+
 impl Hopper {
     pub fn new(config: HopperConfig) -> Hopper {
         Hopper {
@@ -135,6 +148,24 @@ impl Hopper {
             from_hopper_client_no_lookup: recipient!(addr, NoLookupIncipientCoresPackage),
             from_dispatcher: recipient!(addr, InboundClientData),
             node_from_ui: recipient!(addr, NodeFromUiMessage),
+            configuration_change: recipient!(addr, ConfigurationChangeMessage),
+        }
+    }
+
+    fn handle_configuration_change(&mut self, change: ConfigurationChange) {
+        if let ConfigurationChange::RatePack(rate_pack) = change {
+            self.per_routing_service = rate_pack.routing_service_rate;
+            self.per_routing_byte = rate_pack.routing_byte_rate;
+            if let Some(routing_service) = self.routing_service.as_mut() {
+                routing_service
+                    .update_routing_rates(self.per_routing_service, self.per_routing_byte);
+            }
+            info!(
+                self.logger,
+                "Routing rates changed to {} per service and {} per byte",
+                self.per_routing_service,
+                self.per_routing_byte
+            );
         }
     }
 }
@@ -143,12 +174,16 @@ impl Hopper {
 mod tests {
     use super::live_cores_package::LiveCoresPackage;
     use super::*;
+    use crate::accountant::database_access_objects::banned_dao::BAN_CACHE;
+    use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
     use crate::sub_lib::cryptde::PlainData;
     use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::dispatcher::Component;
     use crate::sub_lib::hopper::IncipientCoresPackage;
+    use crate::sub_lib::neighborhood::RatePack;
     use crate::sub_lib::route::Route;
     use crate::sub_lib::route::RouteSegment;
+    use crate::test_utils::recorder::{make_recorder, peer_actors_builder};
     use crate::test_utils::unshared_test_utils::prove_that_crash_request_handler_is_hooked_up;
     use crate::test_utils::{
         alias_cryptde, main_cryptde, make_cryptde_pair, make_meaningless_message_type,
@@ -156,6 +191,8 @@ mod tests {
     };
     use actix::Actor;
     use actix::System;
+    use masq_lib::test_utils::environment_guard::EnvironmentGuard;
+    use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
     use std::net::SocketAddr;
     use std::str::FromStr;
@@ -273,4 +310,74 @@ mod tests {
 
         prove_that_crash_request_handler_is_hooked_up(hopper, CRASH_KEY);
     }
+
+    #[test]
+    fn rate_pack_change_updates_the_rates_charged_for_routing() {
+        init_test_logging();
+        let _eg = EnvironmentGuard::new();
+        BAN_CACHE.clear();
+        let main_cryptde = main_cryptde();
+        let paying_wallet = make_paying_wallet(b"wallet");
+        let next_key = PublicKey::new(&[65, 65, 65]);
+        let route = Route::one_way(
+            RouteSegment::new(
+                vec![&main_cryptde.public_key(), &next_key],
+                Component::Neighborhood,
+            ),
+            main_cryptde,
+            Some(paying_wallet.clone()),
+            Some(TEST_DEFAULT_CHAIN.rec().contract),
+        )
+        .unwrap();
+        let payload = PlainData::new(&b"abcd"[..]);
+        let lcp = LiveCoresPackage::new(route, main_cryptde.encode(&next_key, &payload).unwrap());
+        let data_ser = PlainData::new(&serde_cbor::ser::to_vec(&lcp).unwrap()[..]);
+        let data_enc = main_cryptde
+            .encode(&main_cryptde.public_key(), &data_ser)
+            .unwrap();
+        let inbound_client_data = InboundClientData {
+            timestamp: SystemTime::now(),
+            peer_addr: SocketAddr::from_str("1.2.3.4:5678").unwrap(),
+            reception_port: None,
+            last_data: true,
+            is_clandestine: true,
+            sequence_number: None,
+            data: data_enc.into(),
+        };
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let system = System::new("rate_pack_change_updates_the_rates_charged_for_routing");
+        let mut subject = Hopper::new(HopperConfig {
+            cryptdes: make_cryptde_pair(),
+            per_routing_service: 100,
+            per_routing_byte: 200,
+            is_decentralized: false,
+            crashable: false,
+        });
+        subject.logger = Logger::new("rate_pack_change_updates_the_rates_charged_for_routing");
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().accountant(accountant).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ConfigurationChangeMessage {
+                change: ConfigurationChange::RatePack(RatePack {
+                    routing_byte_rate: 1,
+                    routing_service_rate: 2,
+                    exit_byte_rate: 3,
+                    exit_service_rate: 4,
+                }),
+            })
+            .unwrap();
+        subject_addr.try_send(inbound_client_data).unwrap();
+
+        System::current().stop();
+        system.run();
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        let message = accountant_recording.get_record::<ReportRoutingServiceProvidedMessage>(0);
+        assert_eq!(message.service_rate, 2);
+        assert_eq!(message.byte_rate, 1);
+        TestLogHandler::new().exists_log_containing(
+            "INFO: rate_pack_change_updates_the_rates_charged_for_routing: Routing rates changed to 2 per service and 1 per byte",
+        );
+    }
 }
@@ -8,7 +8,9 @@ use crate::sub_lib::cryptde::{decodex, encodex, CryptData, CryptdecError};
 use crate::sub_lib::dispatcher::{Component, Endpoint, InboundClientData};
 use crate::sub_lib::hop::LiveHop;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, HopperSubs, MessageType};
-use crate::sub_lib::neighborhood::{GossipFailure_0v1, NeighborhoodSubs};
+use crate::sub_lib::neighborhood::{
+    DelinquencyNotice_0v1, GossipFailure_0v1, NeighborhoodSubs, RoutingPayerSighting,
+};
 use crate::sub_lib::proxy_client::{
     ClientResponsePayload_0v1, DnsResolveFailure_0v1, ProxyClientSubs,
 };
@@ -57,6 +59,11 @@ impl RoutingService {
         }
     }
 
+    pub fn update_routing_rates(&mut self, per_routing_service: u64, per_routing_byte: u64) {
+        self.per_routing_service = per_routing_service;
+        self.per_routing_byte = per_routing_byte;
+    }
+
     pub fn route(&self, ibcd: InboundClientData) {
         let data_size = ibcd.data.len();
         debug!(
@@ -115,7 +122,7 @@ impl RoutingService {
                 live_package.payload.len(),
                 next_hop.public_key
             );
-            self.route_data_externally(live_package, next_hop.payer, last_data);
+            self.route_data_externally(live_package, sender_addr, next_hop.payer, last_data);
         } else {
             debug!(
                 self.logger,
@@ -387,6 +394,29 @@ impl RoutingService {
                     ))
                     .expect("Neighborhood is dead")
             }
+            (Component::Neighborhood, MessageType::DelinquencyNotice(vd)) => {
+                let notice = match DelinquencyNotice_0v1::try_from(vd) {
+                    Ok(n) => n,
+                    Err(e) => {
+                        error!(
+                            self.logger,
+                            "Received unmigratable DelinquencyNotice: {:?}", e
+                        );
+                        return;
+                    }
+                };
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .delinquency_notice
+                    .try_send(ExpiredCoresPackage::new(
+                        expired_package.immediate_neighbor,
+                        expired_package.paying_wallet,
+                        expired_package.remaining_route,
+                        notice,
+                        expired_package.payload_len,
+                    ))
+                    .expect("Neighborhood is dead")
+            }
             (destination, payload) => error!(
                 self.logger,
                 "Attempt to send invalid combination {:?} to {:?}", payload, destination
@@ -397,6 +427,7 @@ impl RoutingService {
     fn route_data_externally(
         &self,
         live_package: LiveCoresPackage,
+        sender_addr: SocketAddr,
         payer: Option<Payer>,
         last_data: bool,
     ) {
@@ -418,6 +449,14 @@ impl RoutingService {
                     );
                     return;
                 }
+                self.routing_service_subs
+                    .neighborhood_subs
+                    .routing_payer_sighting_sub
+                    .try_send(RoutingPayerSighting {
+                        paying_wallet: payer.wallet.clone(),
+                        sender_ip: sender_addr.ip(),
+                    })
+                    .expect("Neighborhood is dead");
                 match self.routing_service_subs.to_accountant_routing.try_send(
                     ReportRoutingServiceProvidedMessage {
                         timestamp: SystemTime::now(),
@@ -509,7 +548,6 @@ mod tests {
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::cryptde_real::CryptDEReal;
     use crate::sub_lib::hopper::{IncipientCoresPackage, MessageType, MessageType::ClientRequest};
-    use crate::sub_lib::neighborhood::GossipFailure_0v1;
     use crate::sub_lib::peer_actors::PeerActors;
     use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
     use crate::sub_lib::proxy_server::{ClientRequestPayload_0v1, ProxyProtocol};
@@ -529,7 +567,7 @@ mod tests {
     use masq_lib::test_utils::environment_guard::EnvironmentGuard;
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
     use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
-    use std::net::SocketAddr;
+    use std::net::{IpAddr, SocketAddr};
     use std::str::FromStr;
     use std::time::SystemTime;
 
@@ -1093,6 +1131,87 @@ mod tests {
         assert_eq!(record.payload_len, expected_ecp.payload_len);
     }
 
+    #[test]
+    fn converts_live_delinquency_notice_to_expired_for_neighborhood() {
+        let _eg = EnvironmentGuard::new();
+        BAN_CACHE.clear();
+        let cryptde = main_cryptde();
+        let (component, _, component_recording_arc) = make_recorder();
+        let mut route = Route::one_way(
+            RouteSegment::new(
+                vec![&cryptde.public_key(), &cryptde.public_key()],
+                Component::Neighborhood,
+            ),
+            cryptde,
+            None,
+            None,
+        )
+        .unwrap();
+        route.shift(cryptde).unwrap();
+        let notice = DelinquencyNotice_0v1 {
+            balance_gwei: 123_456,
+            ban_timestamp: 1_700_000_000,
+        };
+        let payload = MessageType::DelinquencyNotice(VersionedData::new(
+            &crate::sub_lib::migrations::delinquency_notice::MIGRATIONS,
+            &notice,
+        ));
+        let lcp = LiveCoresPackage::new(
+            route,
+            encodex::<MessageType>(cryptde, &cryptde.public_key(), &payload).unwrap(),
+        );
+        let data_enc = encodex(cryptde, &cryptde.public_key(), &lcp).unwrap();
+        let inbound_client_data = InboundClientData {
+            timestamp: SystemTime::now(),
+            peer_addr: SocketAddr::from_str("1.3.2.4:5678").unwrap(),
+            reception_port: None,
+            last_data: false,
+            is_clandestine: true,
+            sequence_number: None,
+            data: data_enc.into(),
+        };
+
+        let system = System::new("converts_live_delinquency_notice_to_expired_for_neighborhood");
+        let peer_actors = peer_actors_builder().neighborhood(component).build();
+        let subject = RoutingService::new(
+            CryptDEPair {
+                main: cryptde,
+                alias: alias_cryptde(),
+            },
+            RoutingServiceSubs {
+                proxy_client_subs_opt: peer_actors.proxy_client_opt,
+                proxy_server_subs: peer_actors.proxy_server,
+                neighborhood_subs: peer_actors.neighborhood,
+                hopper_subs: peer_actors.hopper,
+                to_dispatcher: peer_actors.dispatcher.from_dispatcher_client,
+                to_accountant_routing: peer_actors.accountant.report_routing_service_provided,
+            },
+            0,
+            0,
+            true,
+        );
+
+        subject.route(inbound_client_data);
+
+        System::current().stop();
+        system.run();
+        let component_recording = component_recording_arc.lock().unwrap();
+        let record =
+            component_recording.get_record::<ExpiredCoresPackage<DelinquencyNotice_0v1>>(0);
+        let expected_ecp = lcp
+            .to_expired(
+                SocketAddr::from_str("1.3.2.4:5678").unwrap(),
+                cryptde,
+                cryptde,
+            )
+            .unwrap();
+        assert_eq!(record.immediate_neighbor, expected_ecp.immediate_neighbor);
+        assert_eq!(record.paying_wallet, expected_ecp.paying_wallet);
+        assert_eq!(record.remaining_route, expected_ecp.remaining_route);
+        assert_eq!(record.payload, notice);
+        assert_eq!(record.payload_len, expected_ecp.payload_len);
+    }
+
     #[test]
     fn passes_on_inbound_client_data_not_meant_for_this_node() {
         let _eg = EnvironmentGuard::new();
@@ -1103,6 +1222,7 @@ mod tests {
         let address_paying_wallet = Wallet::from(paying_wallet.address());
         let (dispatcher, _, dispatcher_recording_arc) = make_recorder();
         let (accountant, _, accountant_recording_arc) = make_recorder();
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
         let next_key = PublicKey::new(&[65, 65, 65]);
         let contract_address = TEST_DEFAULT_CHAIN.rec().contract;
         let route = Route::one_way(
@@ -1136,6 +1256,7 @@ mod tests {
         let peer_actors = peer_actors_builder()
             .dispatcher(dispatcher)
             .accountant(accountant)
+            .neighborhood(neighborhood)
             .build();
         let subject = RoutingService::new(
             CryptDEPair {
@@ -1183,12 +1304,21 @@ mod tests {
             *message,
             ReportRoutingServiceProvidedMessage {
                 timestamp: message.timestamp,
-                paying_wallet: address_paying_wallet,
+                paying_wallet: address_paying_wallet.clone(),
                 payload_size: lcp.payload.len(),
                 service_rate: rate_pack_routing(103),
                 byte_rate: rate_pack_routing_byte(103),
             }
-        )
+        );
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<RoutingPayerSighting>(0),
+            &RoutingPayerSighting {
+                paying_wallet: address_paying_wallet,
+                sender_ip: IpAddr::from_str("1.2.3.4").unwrap(),
+            }
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
     }
 
     #[test]
@@ -1547,6 +1677,7 @@ mod tests {
 
         subject.route_data_externally(
             lcp,
+            SocketAddr::from_str("1.2.3.4:5678").unwrap(),
             paying_wallet.map(|w| w.as_payer(&PublicKey::new(b"can't pay"), &contract_address)),
             true,
         );
@@ -8,10 +8,11 @@ pub mod neighborhood_database;
 pub mod node_record;
 pub mod overall_connection_status;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
 
 use actix::Context;
 use actix::Handler;
@@ -23,42 +24,55 @@ use itertools::Itertools;
 use masq_lib::messages::{
     FromMessageBody, ToMessageBody, UiConnectionStage, UiConnectionStatusRequest,
 };
+use masq_lib::messages::{ShutdownStage, UiGracefulShutdownRequest};
 use masq_lib::messages::{UiConnectionStatusResponse, UiShutdownRequest};
+use masq_lib::messages::{UiGracefulShutdownResponse, UiShutdownProgressBroadcast};
+use masq_lib::messages::{UiNeighbor, UiNeighborhoodStatusRequest, UiNeighborhoodStatusResponse};
 use masq_lib::ui_gateway::{MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::utils::{exit_process, ExpectValue, NeighborhoodModeLight};
 
+use crate::accountant::wei_to_gwei;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::database::db_initializer::DbInitializationConfig;
 use crate::database::db_initializer::{DbInitializer, DbInitializerReal};
 use crate::db_config::persistent_configuration::{
     PersistentConfigError, PersistentConfiguration, PersistentConfigurationReal,
 };
-use crate::neighborhood::gossip::{DotGossipEndpoint, GossipNodeRecord, Gossip_0v1};
+use crate::neighborhood::gossip::{DotGossipEndpoint, GossipBuilder, GossipNodeRecord, Gossip_0v1};
 use crate::neighborhood::gossip_acceptor::GossipAcceptanceResult;
 use crate::neighborhood::node_record::NodeRecordInner_0v1;
 use crate::neighborhood::overall_connection_status::{
     OverallConnectionStage, OverallConnectionStatus,
 };
 use crate::stream_messages::RemovedStreamType;
-use crate::sub_lib::configurator::NewPasswordMessage;
+use crate::sub_lib::accountant::SettleBeforeShutdown;
+use crate::sub_lib::configurator::{
+    ConfigurationChange, ConfigurationChangeMessage, NewEarningWalletMessage, NewPasswordMessage,
+};
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::cryptde::{CryptDE, CryptData, PlainData};
 use crate::sub_lib::dispatcher::{Component, StreamShutdownMsg};
 use crate::sub_lib::hopper::{ExpiredCoresPackage, NoLookupIncipientCoresPackage};
 use crate::sub_lib::hopper::{IncipientCoresPackage, MessageType};
 use crate::sub_lib::neighborhood::NodeRecordMetadataMessage;
+use crate::sub_lib::neighborhood::RatePack;
 use crate::sub_lib::neighborhood::RemoveNeighborMessage;
 use crate::sub_lib::neighborhood::RouteQueryMessage;
 use crate::sub_lib::neighborhood::RouteQueryResponse;
 use crate::sub_lib::neighborhood::{AskAboutDebutGossipMessage, NodeDescriptor};
 use crate::sub_lib::neighborhood::{ConnectionProgressEvent, ExpectedServices};
 use crate::sub_lib::neighborhood::{ConnectionProgressMessage, ExpectedService};
+use crate::sub_lib::neighborhood::{
+    DelinquencyNotice_0v1, NoticeToDebtor, PayingWalletSighting, RoutingPayerSighting,
+};
 use crate::sub_lib::neighborhood::{DispatcherNodeQueryMessage, GossipFailure_0v1};
+use crate::sub_lib::neighborhood::{FinishGracefulShutdown, ShutdownStageComplete};
 use crate::sub_lib::neighborhood::{Hops, NeighborhoodMetadata, NodeQueryResponseMetadata};
 use crate::sub_lib::neighborhood::{NRMetadataChange, NodeQueryMessage};
 use crate::sub_lib::neighborhood::{NeighborhoodSubs, NeighborhoodTools};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::peer_actors::{BindMessage, NewPublicIp, StartMessage};
+use crate::sub_lib::proxy_server::DrainClientStreams;
 use crate::sub_lib::route::Route;
 use crate::sub_lib::route::RouteSegment;
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
@@ -89,10 +103,14 @@ pub struct Neighborhood {
     hopper_no_lookup_opt: Option<Recipient<NoLookupIncipientCoresPackage>>,
     connected_signal_opt: Option<Recipient<StartMessage>>,
     node_to_ui_recipient_opt: Option<Recipient<NodeToUiMessage>>,
+    drain_client_streams_sub_opt: Option<Recipient<DrainClientStreams>>,
+    settle_before_shutdown_sub_opt: Option<Recipient<SettleBeforeShutdown>>,
+    graceful_shutdown_opt: Option<UiGracefulShutdownRequest>,
     gossip_acceptor: Box<dyn GossipAcceptor>,
     gossip_producer: Box<dyn GossipProducer>,
     neighborhood_database: NeighborhoodDatabase,
     consuming_wallet_opt: Option<Wallet>,
+    paying_wallet_owners: HashMap<Wallet, PublicKey>,
     mode: NeighborhoodModeLight,
     min_hops_count: Hops,
     next_return_route_id: u32,
@@ -119,6 +137,9 @@ impl Handler<BindMessage> for Neighborhood {
         self.hopper_no_lookup_opt = Some(msg.peer_actors.hopper.from_hopper_client_no_lookup);
         self.connected_signal_opt = Some(msg.peer_actors.accountant.start);
         self.node_to_ui_recipient_opt = Some(msg.peer_actors.ui_gateway.node_to_ui_message_sub);
+        self.drain_client_streams_sub_opt = Some(msg.peer_actors.proxy_server.drain_client_streams);
+        self.settle_before_shutdown_sub_opt =
+            Some(msg.peer_actors.accountant.settle_before_shutdown_sub);
     }
 }
 
@@ -220,6 +241,42 @@ impl Handler<ExpiredCoresPackage<GossipFailure_0v1>> for Neighborhood {
     }
 }
 
+impl Handler<ExpiredCoresPackage<DelinquencyNotice_0v1>> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ExpiredCoresPackage<DelinquencyNotice_0v1>,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_delinquency_notice(msg.immediate_neighbor, msg.payload);
+    }
+}
+
+impl Handler<NoticeToDebtor> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: NoticeToDebtor, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_notice_to_debtor(msg);
+    }
+}
+
+impl Handler<PayingWalletSighting> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: PayingWalletSighting, _ctx: &mut Self::Context) -> Self::Result {
+        self.record_paying_wallet_owner(msg.paying_wallet, msg.public_key);
+    }
+}
+
+impl Handler<RoutingPayerSighting> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: RoutingPayerSighting, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_routing_payer_sighting(msg);
+    }
+}
+
 impl Handler<RemoveNeighborMessage> for Neighborhood {
     type Result = ();
 
@@ -353,14 +410,34 @@ impl Handler<NodeFromUiMessage> for Neighborhood {
         let client_id = msg.client_id;
         if let Ok((_, context_id)) = UiConnectionStatusRequest::fmb(msg.body.clone()) {
             self.handle_connection_status_message(client_id, context_id);
+        } else if let Ok((_, context_id)) = UiNeighborhoodStatusRequest::fmb(msg.body.clone()) {
+            self.handle_neighborhood_status_message(client_id, context_id);
         } else if let Ok((body, _)) = UiShutdownRequest::fmb(msg.body.clone()) {
             self.handle_shutdown_order(client_id, body);
+        } else if let Ok((body, context_id)) = UiGracefulShutdownRequest::fmb(msg.body.clone()) {
+            self.handle_graceful_shutdown_order(client_id, context_id, body);
         } else {
             handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
         }
     }
 }
 
+impl Handler<ShutdownStageComplete> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: ShutdownStageComplete, ctx: &mut Self::Context) -> Self::Result {
+        self.handle_shutdown_stage_complete(msg, ctx);
+    }
+}
+
+impl Handler<FinishGracefulShutdown> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, _msg: FinishGracefulShutdown, _ctx: &mut Self::Context) -> Self::Result {
+        self.finish_graceful_shutdown();
+    }
+}
+
 impl Handler<NewPasswordMessage> for Neighborhood {
     type Result = ();
 
@@ -369,6 +446,26 @@ impl Handler<NewPasswordMessage> for Neighborhood {
     }
 }
 
+impl Handler<NewEarningWalletMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(&mut self, msg: NewEarningWalletMessage, _ctx: &mut Self::Context) -> Self::Result {
+        self.handle_new_earning_wallet(msg.new_earning_wallet);
+    }
+}
+
+impl Handler<ConfigurationChangeMessage> for Neighborhood {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ConfigurationChangeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_configuration_change(msg.change);
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct AccessibleGossipRecord {
     pub signed_gossip: PlainData,
@@ -449,10 +546,14 @@ impl Neighborhood {
             hopper_no_lookup_opt: None,
             connected_signal_opt: None,
             node_to_ui_recipient_opt: None,
+            drain_client_streams_sub_opt: None,
+            settle_before_shutdown_sub_opt: None,
+            graceful_shutdown_opt: None,
             gossip_acceptor: Box::new(GossipAcceptorReal::new(cryptde)),
             gossip_producer: Box::new(GossipProducerReal::new()),
             neighborhood_database,
             consuming_wallet_opt: config.consuming_wallet_opt.clone(),
+            paying_wallet_owners: HashMap::new(),
             mode,
             min_hops_count,
             next_return_route_id: 0,
@@ -478,13 +579,22 @@ impl Neighborhood {
             gossip_failure: addr
                 .clone()
                 .recipient::<ExpiredCoresPackage<GossipFailure_0v1>>(),
+            delinquency_notice: addr
+                .clone()
+                .recipient::<ExpiredCoresPackage<DelinquencyNotice_0v1>>(),
             dispatcher_node_query: addr.clone().recipient::<DispatcherNodeQueryMessage>(),
             remove_neighbor: addr.clone().recipient::<RemoveNeighborMessage>(),
             stream_shutdown_sub: addr.clone().recipient::<StreamShutdownMsg>(),
             set_consuming_wallet_sub: addr.clone().recipient::<SetConsumingWalletMessage>(),
             from_ui_message_sub: addr.clone().recipient::<NodeFromUiMessage>(),
             new_password_sub: addr.clone().recipient::<NewPasswordMessage>(),
+            new_earning_wallet_sub: addr.clone().recipient::<NewEarningWalletMessage>(),
             connection_progress_sub: addr.clone().recipient::<ConnectionProgressMessage>(),
+            shutdown_stage_complete_sub: addr.clone().recipient::<ShutdownStageComplete>(),
+            configuration_change_sub: addr.clone().recipient::<ConfigurationChangeMessage>(),
+            notice_to_debtor_sub: addr.clone().recipient::<NoticeToDebtor>(),
+            paying_wallet_sighting_sub: addr.clone().recipient::<PayingWalletSighting>(),
+            routing_payer_sighting_sub: addr.clone().recipient::<RoutingPayerSighting>(),
         }
     }
 
@@ -985,8 +1095,17 @@ impl Neighborhood {
         }
 
         let has_long_segment = segments.iter().any(|segment| segment.keys.len() > 2);
-        if self.consuming_wallet_opt.is_none() && has_long_segment {
-            return Err("Cannot make multi-hop route segment without consuming wallet".to_string());
+        // A consuming wallet whose key an external signer holds can't sign the payer proofs
+        let paying_wallet_opt = self
+            .consuming_wallet_opt
+            .clone()
+            .filter(|wallet| wallet.can_sign());
+        if has_long_segment && paying_wallet_opt.is_none() {
+            return Err(match self.consuming_wallet_opt {
+                Some(_) => "Cannot make multi-hop route segment with address-only consuming wallet",
+                None => "Cannot make multi-hop route segment without consuming wallet",
+            }
+            .to_string());
         }
 
         let expected_request_services = match self.make_expected_services(&over) {
@@ -1005,7 +1124,7 @@ impl Neighborhood {
                 over,
                 back,
                 self.cryptde,
-                self.consuming_wallet_opt.clone(),
+                paying_wallet_opt,
                 return_route_id,
                 Some(self.chain.rec().contract),
             )
@@ -1414,6 +1533,88 @@ impl Neighborhood {
         trace!(self.logger, "Sent GossipFailure_0v1: {}", gossip_failure);
     }
 
+    // A Node pays from one consuming wallet at a time, and only Nodes in the database can be sent
+    // notices; so older wallets of the same Node, and Nodes that have dropped out, are forgotten
+    fn record_paying_wallet_owner(&mut self, paying_wallet: Wallet, public_key: PublicKey) {
+        let database = &self.neighborhood_database;
+        self.paying_wallet_owners
+            .retain(|_, key| key != &public_key && database.node_by_key(key).is_some());
+        self.paying_wallet_owners.insert(paying_wallet, public_key);
+    }
+
+    // The sender of a routed package may be just another relay, so this never overrides an owner
+    // already known for the wallet, and is dropped if no other Node in the database has the sender's IP
+    fn handle_routing_payer_sighting(&mut self, msg: RoutingPayerSighting) {
+        if self.paying_wallet_owners.contains_key(&msg.paying_wallet) {
+            return;
+        }
+        let root_key = self.neighborhood_database.root().public_key();
+        let sender_key_opt = self
+            .neighborhood_database
+            .node_by_ip(&msg.sender_ip)
+            .map(|node| node.public_key())
+            .filter(|key| *key != root_key)
+            .cloned();
+        if let Some(sender_key) = sender_key_opt {
+            self.record_paying_wallet_owner(msg.paying_wallet, sender_key)
+        }
+    }
+
+    // Receivables are keyed by the wallet the debtor pays from, which Gossip doesn't carry
+    fn handle_notice_to_debtor(&self, msg: NoticeToDebtor) {
+        let root_key = self.neighborhood_database.root().public_key();
+        let debtor_opt = self
+            .paying_wallet_owners
+            .get(&msg.wallet)
+            .filter(|key| *key != root_key)
+            .and_then(|key| self.neighborhood_database.node_by_key(key))
+            .filter(|node| node.node_addr_opt().is_some());
+        let debtor = match debtor_opt {
+            Some(node) => node,
+            None => {
+                debug!(
+                    self.logger,
+                    "Can't send delinquency notice to {}: no reachable Node in the Neighborhood is known to pay from that wallet",
+                    msg.wallet
+                );
+                return;
+            }
+        };
+        let notice = DelinquencyNotice_0v1 {
+            balance_gwei: wei_to_gwei(msg.balance_wei.max(0)),
+            ban_timestamp: msg
+                .ban_deadline
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+        self.send_no_lookup_package(
+            MessageType::DelinquencyNotice(VersionedData::new(
+                &crate::sub_lib::migrations::delinquency_notice::MIGRATIONS,
+                &notice,
+            )),
+            debtor.public_key(),
+            &debtor.node_addr_opt().expectv("NodeAddr"),
+        );
+        debug!(
+            self.logger,
+            "Sent DelinquencyNotice_0v1 to {} for wallet {}: {:?}",
+            debtor.public_key(),
+            msg.wallet,
+            notice
+        );
+    }
+
+    fn handle_delinquency_notice(&self, source: SocketAddr, notice: DelinquencyNotice_0v1) {
+        warning!(
+            self.logger,
+            "Node at {} reports that our wallet owes it {} gwei and will be banned at timestamp {} unless the debt is paid",
+            source.ip(),
+            notice.balance_gwei,
+            notice.ban_timestamp
+        );
+    }
+
     fn handle_gossip_ignored(&self, _ignored_node_name: String, _gossip_record_count: usize) {
         // Maybe something here eventually for keeping statistics
     }
@@ -1488,6 +1689,40 @@ impl Neighborhood {
             .expect("UiGateway is dead");
     }
 
+    fn handle_neighborhood_status_message(&self, client_id: u64, context_id: u64) {
+        let db = &self.neighborhood_database;
+        let full_neighbor_keys = db.root().full_neighbor_keys(db);
+        let neighbors = db
+            .root()
+            .half_neighbor_keys()
+            .into_iter()
+            .map(|key| UiNeighbor {
+                public_key: key.to_string(),
+                node_addr_opt: db
+                    .node_by_key(key)
+                    .and_then(|node| node.node_addr_opt())
+                    .map(|node_addr| node_addr.to_string()),
+                full_neighbor: full_neighbor_keys.contains(key),
+            })
+            .sorted_by(|a, b| a.public_key.cmp(&b.public_key))
+            .collect();
+        let message = NodeToUiMessage {
+            target: MessageTarget::ClientId(client_id),
+            body: UiNeighborhoodStatusResponse {
+                stage: self.overall_connection_status.stage.into(),
+                neighbors,
+                known_nodes: (db.keys().len() - 1) as u64,
+            }
+            .tmb(context_id),
+        };
+
+        self.node_to_ui_recipient_opt
+            .as_ref()
+            .expect("UI Gateway is unbound")
+            .try_send(message)
+            .expect("UiGateway is dead");
+    }
+
     fn remove_neighbor(&mut self, neighbor_key: &PublicKey, peer_addr: &SocketAddr) {
         match self.neighborhood_database.remove_neighbor(neighbor_key) {
             Err(e) => panic!("Node suddenly disappeared: {:?}", e),
@@ -1521,9 +1756,220 @@ impl Neighborhood {
         );
     }
 
+    fn handle_graceful_shutdown_order(
+        &mut self,
+        client_id: u64,
+        context_id: u64,
+        msg: UiGracefulShutdownRequest,
+    ) {
+        self.node_to_ui_recipient_opt
+            .as_ref()
+            .expect("UI Gateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: UiGracefulShutdownResponse {}.tmb(context_id),
+            })
+            .expect("UiGateway is dead");
+        if self.graceful_shutdown_opt.is_some() {
+            warning!(
+                self.logger,
+                "Received graceful shutdown order from client {}, but a graceful shutdown is already under way - ignoring",
+                client_id
+            );
+            return;
+        }
+        info!(
+            self.logger,
+            "Received graceful shutdown order from client {}: shutting down gracefully", client_id
+        );
+        let drain_timeout = Duration::from_secs(msg.drain_timeout_sec);
+        self.broadcast_shutdown_progress(
+            ShutdownStage::Draining,
+            format!(
+                "Refusing new client streams; waiting up to {}s for open ones to finish",
+                msg.drain_timeout_sec
+            ),
+        );
+        self.graceful_shutdown_opt = Some(msg);
+        self.drain_client_streams_sub_opt
+            .as_ref()
+            .expect("ProxyServer is unbound")
+            .try_send(DrainClientStreams {
+                timeout: drain_timeout,
+            })
+            .expect("ProxyServer is dead");
+    }
+
+    fn handle_shutdown_stage_complete(
+        &mut self,
+        msg: ShutdownStageComplete,
+        ctx: &mut Context<Neighborhood>,
+    ) {
+        let final_scan = match &self.graceful_shutdown_opt {
+            Some(graceful_shutdown) => graceful_shutdown.final_scan,
+            None => {
+                warning!(
+                    self.logger,
+                    "Received {:?}, but no graceful shutdown is under way - ignoring",
+                    msg
+                );
+                return;
+            }
+        };
+        match msg {
+            ShutdownStageComplete::ClientStreamsDrained { abandoned_streams } => {
+                let neighbor_count = self.send_farewell_gossip();
+                self.broadcast_shutdown_progress(
+                    ShutdownStage::Farewell,
+                    format!(
+                        "Client streams drained ({} abandoned); sent farewell Gossip to {} neighbor(s)",
+                        abandoned_streams, neighbor_count
+                    ),
+                );
+                if final_scan {
+                    self.broadcast_shutdown_progress(
+                        ShutdownStage::Settling,
+                        "Running a final payable scan".to_string(),
+                    );
+                    self.settle_before_shutdown_sub_opt
+                        .as_ref()
+                        .expect("Accountant is unbound")
+                        .try_send(SettleBeforeShutdown {})
+                        .expect("Accountant is dead");
+                } else {
+                    self.schedule_graceful_exit(ctx);
+                }
+            }
+            ShutdownStageComplete::DebtsSettled => self.schedule_graceful_exit(ctx),
+        }
+    }
+
+    // Drops all our neighborships and tells the former neighbors about it, so that they stop
+    // routing through us right away instead of waiting for our streams to time out.
+    fn send_farewell_gossip(&mut self) -> usize {
+        let neighbors = self
+            .neighborhood_database
+            .root()
+            .half_neighbor_keys()
+            .into_iter()
+            .cloned()
+            .collect_vec();
+        let root = self.neighborhood_database.root_mut();
+        root.clear_half_neighbors();
+        root.increment_version();
+        root.regenerate_signed_gossip(self.cryptde);
+        let gossip = GossipBuilder::new(&self.neighborhood_database)
+            .node(self.cryptde.public_key(), true)
+            .build();
+        neighbors
+            .iter()
+            .for_each(|neighbor| self.gossip_to_neighbor(neighbor, gossip.clone()));
+        neighbors.len()
+    }
+
+    fn schedule_graceful_exit(&mut self, ctx: &mut Context<Neighborhood>) {
+        self.broadcast_shutdown_progress(ShutdownStage::Exiting, "Shutting down".to_string());
+        // the delay gives the UI Gateway a chance to deliver the broadcasts above
+        let _ = self
+            .tools
+            .notify_later_finish_graceful_shutdown
+            .notify_later(
+                FinishGracefulShutdown {},
+                self.tools.finish_graceful_shutdown_delay,
+                ctx,
+            );
+    }
+
+    fn finish_graceful_shutdown(&self) {
+        exit_process(0, "Graceful shutdown complete");
+    }
+
+    fn broadcast_shutdown_progress(&self, stage: ShutdownStage, message: String) {
+        info!(self.logger, "Graceful shutdown: {}", message);
+        self.node_to_ui_recipient_opt
+            .as_ref()
+            .expect("UI Gateway is unbound")
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast { stage, message }.tmb(0),
+            })
+            .expect("UiGateway is dead");
+    }
+
     fn handle_new_password(&mut self, new_password: String) {
         self.db_password_opt = Some(new_password);
     }
+
+    fn handle_new_earning_wallet(&mut self, new_earning_wallet: Wallet) {
+        let root = self.neighborhood_database.root_mut();
+        if root.set_earning_wallet(new_earning_wallet.clone()) {
+            root.increment_version();
+            info!(
+                self.logger,
+                "Earning wallet changed to {}; gossiping it to neighbors", new_earning_wallet
+            );
+            self.gossip_to_neighbors();
+        }
+    }
+
+    fn handle_configuration_change(&mut self, change: ConfigurationChange) {
+        match change {
+            ConfigurationChange::MinHops(min_hops_count) => {
+                self.min_hops_count = min_hops_count;
+                info!(
+                    self.logger,
+                    "Minimum hop count changed to {}; new routes will honor it",
+                    min_hops_count as usize
+                );
+            }
+            ConfigurationChange::Neighbors(neighbors) => self.handle_new_neighbors(neighbors),
+            ConfigurationChange::RatePack(rate_pack) => self.handle_new_rate_pack(rate_pack),
+            _ => (),
+        }
+    }
+
+    fn handle_new_rate_pack(&mut self, rate_pack: RatePack) {
+        let root = self.neighborhood_database.root_mut();
+        if root.set_rate_pack(rate_pack) {
+            root.increment_version();
+            info!(
+                self.logger,
+                "Rate pack changed to {}; gossiping it to neighbors", rate_pack
+            );
+            self.gossip_to_neighbors();
+        }
+    }
+
+    fn handle_new_neighbors(&mut self, neighbors: Vec<NodeDescriptor>) {
+        let known_neighbors = self
+            .overall_connection_status
+            .iter_initial_node_descriptors()
+            .cloned()
+            .collect::<HashSet<NodeDescriptor>>();
+        let new_neighbors = neighbors
+            .into_iter()
+            .filter(|node_descriptor| !known_neighbors.contains(node_descriptor))
+            .collect_vec();
+        if new_neighbors.is_empty() {
+            info!(
+                self.logger,
+                "No new neighbors supplied; nothing to connect to"
+            );
+            return;
+        }
+        info!(
+            self.logger,
+            "Connecting to {} new neighbor(s)",
+            new_neighbors.len()
+        );
+        let gossip = self
+            .gossip_producer
+            .produce_debut(&self.neighborhood_database);
+        new_neighbors.into_iter().for_each(|node_descriptor| {
+            self.send_debut_gossip_to_descriptor(&gossip, &node_descriptor);
+            self.overall_connection_status.add(node_descriptor);
+        });
+    }
 }
 
 pub fn regenerate_signed_gossip(
@@ -1574,8 +2020,8 @@ mod tests {
     use std::str::FromStr;
     use std::sync::{Arc, Mutex};
     use std::thread;
-    use std::time::Duration;
     use std::time::Instant;
+    use std::time::{Duration, SystemTime};
     use tokio::prelude::Future;
 
     use masq_lib::constants::{DEFAULT_CHAIN, TLS_PORT};
@@ -1591,6 +2037,7 @@ mod tests {
     use crate::neighborhood::gossip::Gossip_0v1;
     use crate::neighborhood::node_record::NodeRecordInner_0v1;
     use crate::stream_messages::{NonClandestineAttributes, RemovedStreamType};
+    use crate::sub_lib::accountant::RetiredEarningWallet;
     use crate::sub_lib::cryptde::{decodex, encodex, CryptData};
     use crate::sub_lib::cryptde_null::CryptDENull;
     use crate::sub_lib::dispatcher::Endpoint;
@@ -2728,6 +3175,63 @@ mod tests {
         assert_eq!(result, expected_response);
     }
 
+    #[test]
+    fn route_query_works_in_zero_hop_mode_with_address_only_consuming_wallet() {
+        let system =
+            System::new("route_query_works_in_zero_hop_mode_with_address_only_consuming_wallet");
+        let mut subject = make_standard_subject();
+        subject.mode = NeighborhoodModeLight::ZeroHop;
+        subject.consuming_wallet_opt =
+            Some(Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap());
+        let addr: Addr<Neighborhood> = subject.start();
+        let sub: Recipient<RouteQueryMessage> = addr.recipient::<RouteQueryMessage>();
+
+        let future = sub.send(RouteQueryMessage::data_indefinite_route_request(
+            None, 12345,
+        ));
+
+        System::current().stop_with_code(0);
+        system.run();
+        let result = future.wait().unwrap().unwrap();
+        assert_eq!(
+            result.expected_services,
+            ExpectedServices::RoundTrip(
+                vec![ExpectedService::Nothing, ExpectedService::Nothing],
+                vec![ExpectedService::Nothing, ExpectedService::Nothing],
+                0,
+            )
+        );
+    }
+
+    #[test]
+    fn multi_hop_route_cannot_be_made_with_address_only_consuming_wallet() {
+        let mut subject = make_standard_subject();
+        subject.consuming_wallet_opt =
+            Some(Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap());
+        let root_key = subject.neighborhood_database.root().public_key().clone();
+        let relay_key = PublicKey::new(&[1, 2, 3, 4]);
+        let exit_key = PublicKey::new(&[2, 3, 4, 5]);
+
+        let result = subject.compose_route_query_response(
+            RouteSegment::new(
+                vec![&root_key, &relay_key, &exit_key],
+                Component::ProxyClient,
+            ),
+            RouteSegment::new(
+                vec![&exit_key, &relay_key, &root_key],
+                Component::ProxyServer,
+            ),
+        );
+
+        assert_eq!(
+            result,
+            Err(
+                "Cannot make multi-hop route segment with address-only consuming wallet"
+                    .to_string()
+            )
+        );
+    }
+
     #[test]
     fn zero_hop_routing_handles_return_route_id_properly() {
         let mut subject = make_standard_subject();
@@ -3602,15 +4106,278 @@ mod tests {
         );
     }
 
-    struct DatabaseReplacementGossipAcceptor {
-        pub replacement_database: NeighborhoodDatabase,
-    }
+    #[test]
+    fn neighborhood_transmits_notice_to_debtor_found_by_paying_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let system = System::new("neighborhood_transmits_notice_to_debtor_found_by_paying_wallet");
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        let paying_wallet = make_wallet("neighbor consuming");
+        subject
+            .paying_wallet_owners
+            .insert(paying_wallet.clone(), neighbor.public_key().clone());
+        let ban_deadline = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+
+        subject.handle_notice_to_debtor(NoticeToDebtor {
+            wallet: paying_wallet,
+            balance_wei: 123_456_789_000_000_000,
+            ban_deadline,
+        });
 
-    impl GossipAcceptor for DatabaseReplacementGossipAcceptor {
-        fn handle(
-            &self,
-            database: &mut NeighborhoodDatabase,
-            _agrs: Vec<AccessibleGossipRecord>,
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(1, hopper_recording.len());
+        assert_eq!(package.public_key, neighbor.public_key().clone());
+        assert_eq!(package.node_addr, neighbor.node_addr_opt().unwrap());
+        let payload = decodex::<MessageType>(
+            &CryptDENull::from(neighbor.public_key(), TEST_DEFAULT_CHAIN),
+            &package.payload,
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            MessageType::DelinquencyNotice(VersionedData::new(
+                &crate::sub_lib::migrations::delinquency_notice::MIGRATIONS,
+                &DelinquencyNotice_0v1 {
+                    balance_gwei: 123_456_789_000,
+                    ban_timestamp: 1_700_000_000,
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn neighborhood_does_not_send_notice_to_debtor_it_cannot_find() {
+        init_test_logging();
+        let test_name = "neighborhood_does_not_send_notice_to_debtor_it_cannot_find";
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let system = System::new(test_name);
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+        subject.logger = Logger::new(test_name);
+        let stranger = make_wallet("stranger");
+        subject
+            .paying_wallet_owners
+            .insert(neighbor.earning_wallet(), neighbor.public_key().clone());
+
+        subject.handle_notice_to_debtor(NoticeToDebtor {
+            wallet: stranger.clone(),
+            balance_wei: 123_456_789_000_000_000,
+            ban_deadline: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        });
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 0);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "DEBUG: {}: Can't send delinquency notice to {}: no reachable Node in the Neighborhood is known to pay from that wallet",
+            test_name, stranger
+        ));
+    }
+
+    #[test]
+    fn neighborhood_does_not_send_notice_to_debtor_by_its_earning_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let system =
+            System::new("neighborhood_does_not_send_notice_to_debtor_by_its_earning_wallet");
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+
+        subject.handle_notice_to_debtor(NoticeToDebtor {
+            wallet: neighbor.earning_wallet(),
+            balance_wei: 123_456_789_000_000_000,
+            ban_deadline: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        });
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 0);
+    }
+
+    #[test]
+    fn neighborhood_remembers_who_pays_from_sighted_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let subject = neighborhood_from_nodes(&subject_node, None);
+        let paying_wallet = make_wallet("paying");
+        let public_key = PublicKey::new(&[1, 2, 3, 4]);
+        let system = System::new("neighborhood_remembers_who_pays_from_sighted_wallet");
+        let addr = subject.start();
+
+        addr.try_send(PayingWalletSighting {
+            paying_wallet: paying_wallet.clone(),
+            public_key: public_key.clone(),
+        })
+        .unwrap();
+
+        let assertion_msg = AssertionsMessage {
+            assertions: Box::new(move |neighborhood: &mut Neighborhood| {
+                assert_eq!(
+                    neighborhood.paying_wallet_owners.get(&paying_wallet),
+                    Some(&public_key)
+                )
+            }),
+        };
+        addr.try_send(assertion_msg).unwrap();
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+    }
+
+    #[test]
+    fn neighborhood_forgets_older_wallets_and_vanished_nodes_when_it_sights_a_paying_wallet() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        let vanished_key = PublicKey::new(&[9, 9, 9, 9]);
+        subject.paying_wallet_owners.insert(
+            make_wallet("old neighbor consuming"),
+            neighbor.public_key().clone(),
+        );
+        subject
+            .paying_wallet_owners
+            .insert(make_wallet("vanished consuming"), vanished_key);
+
+        subject.record_paying_wallet_owner(
+            make_wallet("new neighbor consuming"),
+            neighbor.public_key().clone(),
+        );
+
+        assert_eq!(
+            subject.paying_wallet_owners,
+            vec![(
+                make_wallet("new neighbor consuming"),
+                neighbor.public_key().clone()
+            )]
+            .into_iter()
+            .collect::<HashMap<Wallet, PublicKey>>()
+        );
+    }
+
+    #[test]
+    fn neighborhood_remembers_routing_payer_as_the_node_that_sent_the_package() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        let paying_wallet = make_wallet("neighbor consuming");
+        let neighbor_key = neighbor.public_key().clone();
+        let system =
+            System::new("neighborhood_remembers_routing_payer_as_the_node_that_sent_the_package");
+        let addr = subject.start();
+
+        addr.try_send(RoutingPayerSighting {
+            paying_wallet: paying_wallet.clone(),
+            sender_ip: neighbor.node_addr_opt().unwrap().ip_addr(),
+        })
+        .unwrap();
+
+        let assertion_msg = AssertionsMessage {
+            assertions: Box::new(move |neighborhood: &mut Neighborhood| {
+                assert_eq!(
+                    neighborhood.paying_wallet_owners.get(&paying_wallet),
+                    Some(&neighbor_key)
+                )
+            }),
+        };
+        addr.try_send(assertion_msg).unwrap();
+        System::current().stop();
+        assert_eq!(system.run(), 0);
+    }
+
+    #[test]
+    fn routing_payer_sighting_does_not_override_a_known_owner() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        let paying_wallet = make_wallet("originator consuming");
+        let originator_key = PublicKey::new(&[1, 2, 3, 4]);
+        subject
+            .paying_wallet_owners
+            .insert(paying_wallet.clone(), originator_key.clone());
+
+        subject.handle_routing_payer_sighting(RoutingPayerSighting {
+            paying_wallet: paying_wallet.clone(),
+            sender_ip: neighbor.node_addr_opt().unwrap().ip_addr(),
+        });
+
+        assert_eq!(
+            subject.paying_wallet_owners.get(&paying_wallet),
+            Some(&originator_key)
+        );
+    }
+
+    #[test]
+    fn routing_payer_sighting_from_an_unknown_ip_address_is_ignored() {
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let neighbor = make_node_record(1111, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, Some(&neighbor));
+
+        subject.handle_routing_payer_sighting(RoutingPayerSighting {
+            paying_wallet: make_wallet("stranger consuming"),
+            sender_ip: IpAddr::from_str("9.8.7.6").unwrap(),
+        });
+
+        assert!(subject.paying_wallet_owners.is_empty());
+    }
+
+    #[test]
+    fn neighborhood_logs_delinquency_notice_from_creditor() {
+        init_test_logging();
+        let test_name = "neighborhood_logs_delinquency_notice_from_creditor";
+        let subject_node = make_global_cryptde_node_record(5555, true);
+        let mut subject: Neighborhood = neighborhood_from_nodes(&subject_node, None);
+        subject.logger = Logger::new(test_name);
+
+        subject.handle_delinquency_notice(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            DelinquencyNotice_0v1 {
+                balance_gwei: 123_456,
+                ban_timestamp: 1_700_000_000,
+            },
+        );
+
+        TestLogHandler::new().exists_log_containing(&format!(
+            "WARN: {}: Node at 1.2.3.4 reports that our wallet owes it 123456 gwei and will be banned at timestamp 1700000000 unless the debt is paid",
+            test_name
+        ));
+    }
+
+    struct DatabaseReplacementGossipAcceptor {
+        pub replacement_database: NeighborhoodDatabase,
+    }
+
+    impl GossipAcceptor for DatabaseReplacementGossipAcceptor {
+        fn handle(
+            &self,
+            database: &mut NeighborhoodDatabase,
+            _agrs: Vec<AccessibleGossipRecord>,
             _gossip_source: SocketAddr,
             _neighborhood_metadata: NeighborhoodMetadata,
         ) -> GossipAcceptanceResult {
@@ -5474,71 +6241,412 @@ mod tests {
             .exists_log_containing("INFO: Neighborhood: Received shutdown order from client 1234");
     }
 
-    #[test]
-    fn connection_status_message_is_handled_properly_for_not_connected() {
-        let stage = OverallConnectionStage::NotConnected;
-        let client_id = 1234;
-        let context_id = 4321;
+    fn make_zero_hop_subject(test_name: &str) -> Neighborhood {
+        Neighborhood::new(
+            main_cryptde(),
+            &bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ZeroHop,
+                    min_hops_count: MIN_HOPS_COUNT_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                test_name,
+            ),
+        )
+    }
 
-        let message_opt = connection_status_message_received_by_ui(
-            stage,
-            client_id,
-            context_id,
-            "connection_status_message_is_handled_properly_for_not_connected",
+    #[test]
+    fn graceful_shutdown_order_responds_and_starts_draining_client_streams() {
+        let system = System::new("test");
+        let subject = make_zero_hop_subject(
+            "graceful_shutdown_order_responds_and_starts_draining_client_streams",
         );
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .proxy_server(proxy_server)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
 
-        assert_eq!(
-            message_opt,
-            Some(NodeToUiMessage {
-                target: MessageTarget::ClientId(client_id),
-                body: UiConnectionStatusResponse {
-                    stage: stage.into()
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiGracefulShutdownRequest {
+                    drain_timeout_sec: 30,
+                    final_scan: true,
                 }
-                .tmb(context_id),
+                .tmb(4321),
             })
-        )
-    }
-
-    #[test]
-    fn connection_status_message_is_handled_properly_for_connected_to_neighbor() {
-        let stage = OverallConnectionStage::ConnectedToNeighbor;
-        let client_id = 1235;
-        let context_id = 4322;
+            .unwrap();
 
-        let message_opt = connection_status_message_received_by_ui(
-            stage,
-            client_id,
-            context_id,
-            "connection_status_message_is_handled_properly_for_connected_to_neighbor",
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiGracefulShutdownResponse {}.tmb(4321),
+            }
         );
-
         assert_eq!(
-            message_opt,
-            Some(NodeToUiMessage {
-                target: MessageTarget::ClientId(client_id),
-                body: UiConnectionStatusResponse {
-                    stage: stage.into()
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast {
+                    stage: ShutdownStage::Draining,
+                    message:
+                        "Refusing new client streams; waiting up to 30s for open ones to finish"
+                            .to_string(),
                 }
-                .tmb(context_id),
-            })
-        )
+                .tmb(0),
+            }
+        );
+        let proxy_server_recording = proxy_server_recording_arc.lock().unwrap();
+        assert_eq!(
+            proxy_server_recording.get_record::<DrainClientStreams>(0),
+            &DrainClientStreams {
+                timeout: Duration::from_secs(30)
+            }
+        );
     }
 
     #[test]
-    fn connection_status_message_is_handled_properly_for_three_hops_route_found() {
-        let stage = OverallConnectionStage::RouteFound;
-        let client_id = 1236;
-        let context_id = 4323;
-
-        let message_opt = connection_status_message_received_by_ui(
-            stage,
-            client_id,
-            context_id,
-            "connection_status_message_is_handled_properly_for_three_hops_route_found",
-        );
+    fn repeated_graceful_shutdown_order_does_not_drain_again() {
+        init_test_logging();
+        let system = System::new("test");
+        let mut subject =
+            make_zero_hop_subject("repeated_graceful_shutdown_order_does_not_drain_again");
+        subject.graceful_shutdown_opt = Some(UiGracefulShutdownRequest {
+            drain_timeout_sec: 30,
+            final_scan: false,
+        });
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (proxy_server, _, proxy_server_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .proxy_server(proxy_server)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
 
-        assert_eq!(
-            message_opt,
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiGracefulShutdownRequest {
+                    drain_timeout_sec: 10,
+                    final_scan: true,
+                }
+                .tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiGracefulShutdownResponse {}.tmb(4321),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 1);
+        assert!(proxy_server_recording_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(
+            "WARN: Neighborhood: Received graceful shutdown order from client 1234, but a graceful shutdown is already under way - ignoring",
+        );
+    }
+
+    #[test]
+    fn drained_client_streams_lead_to_farewell_gossip_and_final_settlement() {
+        let system = System::new("test");
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let subject_node = make_global_cryptde_node_record(1345, true);
+        let neighbor = make_node_record(2456, true);
+        let mut subject = neighborhood_from_nodes(&subject_node, None);
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_arbitrary_full_neighbor(subject_node.public_key(), neighbor.public_key());
+        subject.graceful_shutdown_opt = Some(UiGracefulShutdownRequest {
+            drain_timeout_sec: 30,
+            final_scan: true,
+        });
+        let root_version = subject.neighborhood_database.root().version();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .hopper(hopper)
+            .ui_gateway(ui_gateway)
+            .accountant(accountant)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ShutdownStageComplete::ClientStreamsDrained {
+                abandoned_streams: 2,
+            })
+            .unwrap();
+
+        let subject_key = subject_node.public_key().clone();
+        subject_addr
+            .try_send(AssertionsMessage {
+                assertions: Box::new(move |neighborhood: &mut Neighborhood| {
+                    let root = neighborhood.neighborhood_database.root();
+                    assert_eq!(root.public_key(), &subject_key);
+                    assert!(root.half_neighbor_keys().is_empty());
+                }),
+            })
+            .unwrap();
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        let package = hopper_recording.get_record::<IncipientCoresPackage>(0);
+        let neighbor_cryptde = CryptDENull::from(neighbor.public_key(), TEST_DEFAULT_CHAIN);
+        let gossip = match decodex(&neighbor_cryptde, &package.payload).unwrap() {
+            MessageType::Gossip(vd) => Gossip_0v1::try_from(vd).unwrap(),
+            x => panic!("Expected MessageType::Gossip, got {:?}", x),
+        };
+        assert_eq!(gossip.node_records.len(), 1);
+        let inner = NodeRecordInner_0v1::try_from(gossip.node_records[0].clone()).unwrap();
+        assert_eq!(&inner.public_key, subject_node.public_key());
+        assert!(inner.neighbors.is_empty());
+        assert_eq!(inner.version, root_version + 1);
+        assert_eq!(hopper_recording.len(), 1);
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast {
+                    stage: ShutdownStage::Farewell,
+                    message:
+                        "Client streams drained (2 abandoned); sent farewell Gossip to 1 neighbor(s)"
+                            .to_string(),
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast {
+                    stage: ShutdownStage::Settling,
+                    message: "Running a final payable scan".to_string(),
+                }
+                .tmb(0),
+            }
+        );
+        let accountant_recording = accountant_recording_arc.lock().unwrap();
+        assert_eq!(
+            accountant_recording.get_record::<SettleBeforeShutdown>(0),
+            &SettleBeforeShutdown {}
+        );
+    }
+
+    #[test]
+    fn drained_client_streams_without_final_scan_schedule_exit() {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let (accountant, _, accountant_recording_arc) = make_recorder();
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject =
+            make_zero_hop_subject("drained_client_streams_without_final_scan_schedule_exit");
+        subject.tools.notify_later_finish_graceful_shutdown = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        subject.graceful_shutdown_opt = Some(UiGracefulShutdownRequest {
+            drain_timeout_sec: 30,
+            final_scan: false,
+        });
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder()
+            .ui_gateway(ui_gateway)
+            .accountant(accountant)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ShutdownStageComplete::ClientStreamsDrained {
+                abandoned_streams: 0,
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(1),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast {
+                    stage: ShutdownStage::Exiting,
+                    message: "Shutting down".to_string(),
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 2);
+        assert!(accountant_recording_arc.lock().unwrap().is_empty());
+        assert_eq!(
+            *notify_later_params_arc.lock().unwrap(),
+            vec![(FinishGracefulShutdown {}, Duration::from_millis(500))]
+        );
+    }
+
+    #[test]
+    fn settled_debts_schedule_exit() {
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = make_zero_hop_subject("settled_debts_schedule_exit");
+        subject.tools.notify_later_finish_graceful_shutdown = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        subject.tools.finish_graceful_shutdown_delay = Duration::from_millis(5);
+        subject.graceful_shutdown_opt = Some(UiGracefulShutdownRequest {
+            drain_timeout_sec: 30,
+            final_scan: true,
+        });
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ShutdownStageComplete::DebtsSettled)
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::AllClients,
+                body: UiShutdownProgressBroadcast {
+                    stage: ShutdownStage::Exiting,
+                    message: "Shutting down".to_string(),
+                }
+                .tmb(0),
+            }
+        );
+        assert_eq!(
+            *notify_later_params_arc.lock().unwrap(),
+            vec![(FinishGracefulShutdown {}, Duration::from_millis(5))]
+        );
+    }
+
+    #[test]
+    fn shutdown_stage_complete_is_ignored_without_graceful_shutdown() {
+        init_test_logging();
+        let system = System::new("test");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject =
+            make_zero_hop_subject("shutdown_stage_complete_is_ignored_without_graceful_shutdown");
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ShutdownStageComplete::DebtsSettled)
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        assert!(ui_gateway_recording_arc.lock().unwrap().is_empty());
+        TestLogHandler::new().exists_log_containing(
+            "WARN: Neighborhood: Received DebtsSettled, but no graceful shutdown is under way - ignoring",
+        );
+    }
+
+    #[should_panic(expected = "0: Graceful shutdown complete")]
+    #[test]
+    fn finish_graceful_shutdown_exits_process() {
+        running_test();
+        let system = System::new("test");
+        let subject = make_zero_hop_subject("finish_graceful_shutdown_exits_process");
+        let subject_addr = subject.start();
+
+        subject_addr.try_send(FinishGracefulShutdown {}).unwrap();
+
+        System::current().stop();
+        system.run();
+    }
+
+    #[test]
+    fn connection_status_message_is_handled_properly_for_not_connected() {
+        let stage = OverallConnectionStage::NotConnected;
+        let client_id = 1234;
+        let context_id = 4321;
+
+        let message_opt = connection_status_message_received_by_ui(
+            stage,
+            client_id,
+            context_id,
+            "connection_status_message_is_handled_properly_for_not_connected",
+        );
+
+        assert_eq!(
+            message_opt,
+            Some(NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: UiConnectionStatusResponse {
+                    stage: stage.into()
+                }
+                .tmb(context_id),
+            })
+        )
+    }
+
+    #[test]
+    fn connection_status_message_is_handled_properly_for_connected_to_neighbor() {
+        let stage = OverallConnectionStage::ConnectedToNeighbor;
+        let client_id = 1235;
+        let context_id = 4322;
+
+        let message_opt = connection_status_message_received_by_ui(
+            stage,
+            client_id,
+            context_id,
+            "connection_status_message_is_handled_properly_for_connected_to_neighbor",
+        );
+
+        assert_eq!(
+            message_opt,
+            Some(NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: UiConnectionStatusResponse {
+                    stage: stage.into()
+                }
+                .tmb(context_id),
+            })
+        )
+    }
+
+    #[test]
+    fn connection_status_message_is_handled_properly_for_three_hops_route_found() {
+        let stage = OverallConnectionStage::RouteFound;
+        let client_id = 1236;
+        let context_id = 4323;
+
+        let message_opt = connection_status_message_received_by_ui(
+            stage,
+            client_id,
+            context_id,
+            "connection_status_message_is_handled_properly_for_three_hops_route_found",
+        );
+
+        assert_eq!(
+            message_opt,
             Some(NodeToUiMessage {
                 target: MessageTarget::ClientId(client_id),
                 body: UiConnectionStatusResponse {
@@ -5549,6 +6657,275 @@ mod tests {
         )
     }
 
+    #[test]
+    fn neighborhood_status_message_reports_stage_and_neighbors() {
+        let system = System::new("test");
+        let mut subject = Neighborhood::new(
+            main_cryptde(),
+            &bc_from_nc_plus(
+                NeighborhoodConfig {
+                    mode: NeighborhoodMode::ConsumeOnly(vec![make_node_descriptor(make_ip(1))]),
+                    min_hops_count: MIN_HOPS_COUNT_FOR_TEST,
+                },
+                make_wallet("earning"),
+                None,
+                "neighborhood_status_message_reports_stage_and_neighbors",
+            ),
+        );
+        subject.overall_connection_status.stage = OverallConnectionStage::ConnectedToNeighbor;
+        let full_neighbor = make_node_record(2345, true);
+        let half_neighbor = make_node_record(3456, false);
+        let distant_node = make_node_record(4567, true);
+        let root_key = subject.neighborhood_database.root().public_key().clone();
+        {
+            let db = &mut subject.neighborhood_database;
+            db.add_node(full_neighbor.clone()).unwrap();
+            db.add_node(half_neighbor.clone()).unwrap();
+            db.add_node(distant_node.clone()).unwrap();
+            db.add_arbitrary_full_neighbor(&root_key, full_neighbor.public_key());
+            db.add_arbitrary_half_neighbor(&root_key, half_neighbor.public_key());
+            db.add_arbitrary_full_neighbor(full_neighbor.public_key(), distant_node.public_key());
+        }
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiNeighborhoodStatusRequest {}.tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        let mut expected_neighbors = vec![
+            UiNeighbor {
+                public_key: full_neighbor.public_key().to_string(),
+                node_addr_opt: Some(full_neighbor.node_addr_opt().unwrap().to_string()),
+                full_neighbor: true,
+            },
+            UiNeighbor {
+                public_key: half_neighbor.public_key().to_string(),
+                node_addr_opt: None,
+                full_neighbor: false,
+            },
+        ];
+        expected_neighbors.sort_by(|a, b| a.public_key.cmp(&b.public_key));
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiNeighborhoodStatusResponse {
+                    stage: UiConnectionStage::ConnectedToNeighbor,
+                    neighbors: expected_neighbors,
+                    known_nodes: 3,
+                }
+                .tmb(4321),
+            }
+        );
+    }
+
+    #[test]
+    fn new_earning_wallet_message_changes_root_earning_wallet_and_gossips_it() {
+        init_test_logging();
+        let system =
+            System::new("new_earning_wallet_message_changes_root_earning_wallet_and_gossips_it");
+        let hopper = Recorder::new();
+        let hopper_recording_arc = hopper.get_recording();
+        let mut subject = make_standard_subject();
+        subject.logger =
+            Logger::new("new_earning_wallet_message_changes_root_earning_wallet_and_gossips_it");
+        let root_node_record = subject.neighborhood_database.root().clone();
+        let neighbor = make_node_record(3456, true);
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_arbitrary_full_neighbor(root_node_record.public_key(), neighbor.public_key());
+        let new_earning_wallet = make_wallet("new earning wallet");
+        let addr = subject.start();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        addr.try_send(NewEarningWalletMessage {
+            new_earning_wallet: new_earning_wallet.clone(),
+            retired: RetiredEarningWallet {
+                wallet: root_node_record.earning_wallet(),
+                watch_until: SystemTime::now(),
+            },
+        })
+        .unwrap();
+
+        let expected_earning_wallet = new_earning_wallet.clone();
+        let expected_version = root_node_record.version() + 1;
+        addr.try_send(AssertionsMessage {
+            assertions: Box::new(move |neighborhood: &mut Neighborhood| {
+                let root = neighborhood.neighborhood_database.root();
+                assert_eq!(root.earning_wallet(), expected_earning_wallet);
+                assert_eq!(root.version(), expected_version);
+            }),
+        })
+        .unwrap();
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: new_earning_wallet_message_changes_root_earning_wallet_and_gossips_it: Earning wallet changed to {}; gossiping it to neighbors",
+            new_earning_wallet
+        ));
+    }
+
+    #[test]
+    fn new_earning_wallet_message_is_ignored_if_the_earning_wallet_does_not_change() {
+        let mut subject = make_standard_subject();
+        let root_node_record = subject.neighborhood_database.root().clone();
+
+        subject.handle_new_earning_wallet(root_node_record.earning_wallet());
+
+        assert_eq!(subject.neighborhood_database.root(), &root_node_record);
+    }
+
+    #[test]
+    fn rate_pack_change_is_applied_to_root_and_gossiped() {
+        init_test_logging();
+        let test_name = "rate_pack_change_is_applied_to_root_and_gossiped";
+        let system = System::new(test_name);
+        let hopper = Recorder::new();
+        let hopper_recording_arc = hopper.get_recording();
+        let mut subject = make_standard_subject();
+        subject.logger = Logger::new(test_name);
+        let root_node_record = subject.neighborhood_database.root().clone();
+        let neighbor = make_node_record(3456, true);
+        subject
+            .neighborhood_database
+            .add_node(neighbor.clone())
+            .unwrap();
+        subject
+            .neighborhood_database
+            .add_arbitrary_full_neighbor(root_node_record.public_key(), neighbor.public_key());
+        let new_rate_pack = rate_pack(200);
+        let addr = subject.start();
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        addr.try_send(ConfigurationChangeMessage {
+            change: ConfigurationChange::RatePack(new_rate_pack),
+        })
+        .unwrap();
+
+        let expected_version = root_node_record.version() + 1;
+        addr.try_send(AssertionsMessage {
+            assertions: Box::new(move |neighborhood: &mut Neighborhood| {
+                let root = neighborhood.neighborhood_database.root();
+                assert_eq!(root.rate_pack(), &new_rate_pack);
+                assert_eq!(root.version(), expected_version);
+            }),
+        })
+        .unwrap();
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 1);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {}: Rate pack changed to {}; gossiping it to neighbors",
+            test_name, new_rate_pack
+        ));
+    }
+
+    #[test]
+    fn rate_pack_change_is_ignored_if_the_rate_pack_does_not_change() {
+        let mut subject = make_standard_subject();
+        let root_node_record = subject.neighborhood_database.root().clone();
+
+        subject.handle_configuration_change(ConfigurationChange::RatePack(
+            *root_node_record.rate_pack(),
+        ));
+
+        assert_eq!(subject.neighborhood_database.root(), &root_node_record);
+    }
+
+    #[test]
+    fn min_hops_change_is_applied() {
+        init_test_logging();
+        let test_name = "min_hops_change_is_applied";
+        let mut subject = make_standard_subject();
+        subject.logger = Logger::new(test_name);
+
+        subject.handle_configuration_change(ConfigurationChange::MinHops(Hops::FiveHops));
+
+        assert_eq!(subject.min_hops_count, Hops::FiveHops);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Minimum hop count changed to 5; new routes will honor it"
+        ));
+    }
+
+    #[test]
+    fn new_neighbors_receive_debut_gossip_and_join_the_connection_status() {
+        init_test_logging();
+        let test_name = "new_neighbors_receive_debut_gossip_and_join_the_connection_status";
+        let system = System::new(test_name);
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let mut subject = make_standard_subject();
+        subject.logger = Logger::new(test_name);
+        let known_neighbor = subject
+            .overall_connection_status
+            .iter_initial_node_descriptors()
+            .next()
+            .unwrap()
+            .clone();
+        let new_neighbor = make_node_descriptor(make_ip(4));
+        let peer_actors = peer_actors_builder().hopper(hopper).build();
+        subject.hopper_no_lookup_opt = Some(peer_actors.hopper.from_hopper_client_no_lookup);
+
+        subject.handle_configuration_change(ConfigurationChange::Neighbors(vec![
+            known_neighbor.clone(),
+            new_neighbor.clone(),
+        ]));
+
+        System::current().stop();
+        system.run();
+        let hopper_recording = hopper_recording_arc.lock().unwrap();
+        assert_eq!(hopper_recording.len(), 1);
+        let package = hopper_recording.get_record::<NoLookupIncipientCoresPackage>(0);
+        assert_eq!(package.public_key, new_neighbor.encryption_public_key);
+        assert_eq!(
+            subject
+                .overall_connection_status
+                .iter_initial_node_descriptors()
+                .collect_vec(),
+            vec![&known_neighbor, &new_neighbor]
+        );
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Connecting to 1 new neighbor(s)"
+        ));
+    }
+
+    #[test]
+    fn neighbors_change_without_new_neighbors_does_nothing() {
+        init_test_logging();
+        let test_name = "neighbors_change_without_new_neighbors_does_nothing";
+        let mut subject = make_standard_subject();
+        subject.logger = Logger::new(test_name);
+        let initial_ocs = subject.overall_connection_status.clone();
+        let known_neighbors = initial_ocs
+            .iter_initial_node_descriptors()
+            .cloned()
+            .collect_vec();
+
+        subject.handle_configuration_change(ConfigurationChange::Neighbors(known_neighbors));
+
+        assert_eq!(subject.overall_connection_status, initial_ocs);
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: No new neighbors supplied; nothing to connect to"
+        ));
+    }
+
     #[test]
     fn new_password_message_works() {
         let system = System::new("test");
@@ -244,6 +244,15 @@ impl NodeRecord {
         &self.inner.rate_pack
     }
 
+    pub fn set_rate_pack(&mut self, rate_pack: RatePack) -> bool {
+        if self.inner.rate_pack == rate_pack {
+            false
+        } else {
+            self.inner.rate_pack = rate_pack;
+            true
+        }
+    }
+
     pub fn update(&mut self, agr: AccessibleGossipRecord) -> Result<(), String> {
         if &agr.inner.public_key != self.public_key() {
             return Err(format!(
@@ -252,9 +261,9 @@ impl NodeRecord {
                 agr.inner.public_key
             ));
         }
-        if &agr.inner.rate_pack != self.rate_pack() {
+        if &agr.inner.rate_pack != self.rate_pack() && agr.inner.version <= self.version() {
             return Err(format!(
-                "Updating a NodeRecord must not change its rate pack: {} -> {}",
+                "Updating a NodeRecord must not change its rate pack without a new version: {} -> {}",
                 self.rate_pack(),
                 agr.inner.rate_pack
             ));
@@ -902,7 +911,7 @@ mod tests {
     }
 
     #[test]
-    fn update_complains_when_rate_pack_tries_to_change() {
+    fn update_complains_when_rate_pack_tries_to_change_without_a_new_version() {
         let mut subject = make_node_record(1234, true);
         let mut modified = subject.clone();
         modified.inner.rate_pack = ZERO_RATE_PACK.clone();
@@ -913,10 +922,38 @@ mod tests {
 
         assert_eq!(
             result,
-            Err("Updating a NodeRecord must not change its rate pack: 1235|1434|1237|1634 -> 0|0|0|0".to_string()),
+            Err("Updating a NodeRecord must not change its rate pack without a new version: 1235|1434|1237|1634 -> 0|0|0|0".to_string()),
         )
     }
 
+    #[test]
+    fn update_accepts_rate_pack_change_with_a_new_version() {
+        let mut subject = make_node_record(1234, true);
+        let mut modified = subject.clone();
+        modified.inner.rate_pack = ZERO_RATE_PACK;
+        modified.inner.version = subject.version() + 1;
+        modified.resign();
+        let agr = AccessibleGossipRecord::from(&modified);
+
+        let result = subject.update(agr);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(subject.rate_pack(), &ZERO_RATE_PACK);
+    }
+
+    #[test]
+    fn set_rate_pack_reports_whether_the_rate_pack_changed() {
+        let mut subject = make_node_record(1234, true);
+        let original_rate_pack = *subject.rate_pack();
+
+        let unchanged = subject.set_rate_pack(original_rate_pack);
+        let changed = subject.set_rate_pack(ZERO_RATE_PACK);
+
+        assert!(!unchanged);
+        assert!(changed);
+        assert_eq!(subject.rate_pack(), &ZERO_RATE_PACK);
+    }
+
     #[test]
     fn from_gnr_to_nri_when_gossip_is_corrupt() {
         let corrupt_gnr = GossipNodeRecord {
@@ -307,6 +307,10 @@ impl OverallConnectionStatus {
         self.progress.is_empty()
     }
 
+    pub fn add(&mut self, node_descriptor: NodeDescriptor) {
+        self.progress.push(ConnectionProgress::new(node_descriptor));
+    }
+
     pub fn remove(&mut self, index: usize) -> NodeDescriptor {
         let removed_connection_progress = self.progress.remove(index);
         removed_connection_progress.initial_node_descriptor
@@ -550,6 +554,24 @@ mod tests {
         assert_eq!(result.next(), None);
     }
 
+    #[test]
+    fn add_appends_descriptor_at_stage_zero() {
+        let node_desc_1 = make_node_descriptor(make_ip(1));
+        let node_desc_2 = make_node_descriptor(make_ip(2));
+        let mut subject = OverallConnectionStatus::new(vec![node_desc_1.clone()]);
+
+        subject.add(node_desc_2.clone());
+
+        assert_eq!(
+            subject.iter_initial_node_descriptors().collect::<Vec<_>>(),
+            vec![&node_desc_1, &node_desc_2]
+        );
+        assert_eq!(
+            subject.progress[1].connection_stage,
+            ConnectionStage::StageZero
+        );
+    }
+
     #[test]
     fn remove_deletes_descriptor_s_progress_and_returns_node_descriptor() {
         let node_desc_1 = make_node_descriptor(make_ip(1));
@@ -1,6 +1,7 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
 use crate::apps::app_daemon;
+use crate::daemon::restart_policy::RestartPolicy;
 use crate::node_configurator::NodeConfigurator;
 use crate::sub_lib::utils::make_new_multi_config;
 use masq_lib::multi_config::{CommandLineVcl, MultiConfig};
@@ -9,6 +10,7 @@ use masq_lib::shared_schema::ConfiguratorError;
 #[derive(Default, Clone, PartialEq, Eq, Debug)]
 pub struct InitializationConfig {
     pub ui_port: u16,
+    pub restart_policy: RestartPolicy,
 }
 
 pub struct NodeConfiguratorInitializationReal;
@@ -37,12 +39,28 @@ impl NodeConfigurator<InitializationConfig> for NodeConfiguratorInitializationRe
 
 mod initialization {
     use super::*;
+    use crate::daemon::restart_policy::{
+        DEFAULT_MAX_RESTARTS, DEFAULT_RESTART_BACKOFF_SECS, DEFAULT_RESTART_WINDOW_SECS,
+    };
     use clap::value_t;
     use masq_lib::constants::DEFAULT_UI_PORT;
     use masq_lib::multi_config::MultiConfig;
+    use std::time::Duration;
 
     pub fn parse_args(multi_config: &MultiConfig, config: &mut InitializationConfig) {
         config.ui_port = value_m!(multi_config, "ui-port", u16).unwrap_or(DEFAULT_UI_PORT);
+        config.restart_policy = RestartPolicy {
+            max_restarts: value_m!(multi_config, "max-restarts", u64)
+                .unwrap_or(DEFAULT_MAX_RESTARTS),
+            window: Duration::from_secs(
+                value_m!(multi_config, "restart-window", u64)
+                    .unwrap_or(DEFAULT_RESTART_WINDOW_SECS),
+            ),
+            initial_backoff: Duration::from_secs(
+                value_m!(multi_config, "restart-backoff", u64)
+                    .unwrap_or(DEFAULT_RESTART_BACKOFF_SECS),
+            ),
+        };
     }
 }
 
@@ -52,6 +70,7 @@ mod tests {
     use crate::test_utils::ArgsBuilder;
     use masq_lib::constants::DEFAULT_UI_PORT;
     use masq_lib::multi_config::{CommandLineVcl, VirtualCommandLine};
+    use std::time::Duration;
 
     #[test]
     fn parse_args_creates_configuration_with_defaults() {
@@ -64,13 +83,17 @@ mod tests {
         initialization::parse_args(&multi_config, &mut config);
 
         assert_eq!(config.ui_port, DEFAULT_UI_PORT);
+        assert_eq!(config.restart_policy, RestartPolicy::default());
     }
 
     #[test]
     fn parse_args_creates_configuration_with_values() {
         let args = ArgsBuilder::new()
             .opt("--initialization")
-            .param("--ui-port", "4321");
+            .param("--ui-port", "4321")
+            .param("--max-restarts", "3")
+            .param("--restart-window", "600")
+            .param("--restart-backoff", "10");
         let mut config = InitializationConfig::default();
         let vcls: Vec<Box<dyn VirtualCommandLine>> =
             vec![Box::new(CommandLineVcl::new(args.into()))];
@@ -79,5 +102,13 @@ mod tests {
         initialization::parse_args(&multi_config, &mut config);
 
         assert_eq!(config.ui_port, 4321);
+        assert_eq!(
+            config.restart_policy,
+            RestartPolicy {
+                max_restarts: 3,
+                window: Duration::from_secs(600),
+                initial_backoff: Duration::from_secs(10),
+            }
+        );
     }
 }
@@ -4,7 +4,10 @@ use crate::accountant::DEFAULT_PENDING_TOO_LONG_SEC;
 use crate::blockchain::bip32::Bip32ECKeyProvider;
 use crate::bootstrapper::BootstrapperConfig;
 use crate::db_config::persistent_configuration::{PersistentConfigError, PersistentConfiguration};
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals, DEFAULT_EARNING_WALLET};
+use crate::sub_lib::accountant::{
+    DelinquencyPolicy, PaymentThresholds, ScanIntervals, DEFAULT_EARNING_WALLET,
+};
+use crate::sub_lib::blockchain_bridge::LowBalanceThresholds;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde_null::CryptDENull;
 use crate::sub_lib::cryptde_real::CryptDEReal;
@@ -47,6 +50,12 @@ pub trait UnprivilegedParseArgsConfiguration {
                     Err(pce) => return Err(pce.into_configurator_error("gas-price")),
                 }
             };
+        unprivileged_config
+            .blockchain_bridge_config
+            .external_signer_url_opt = value_m!(multi_config, "external-signer-url", String);
+        unprivileged_config
+            .blockchain_bridge_config
+            .confirmation_depth_opt = value_m!(multi_config, "confirmation-depth", u64);
         unprivileged_config.clandestine_port_opt = value_m!(multi_config, "clandestine-port", u16);
         unprivileged_config.blockchain_bridge_config.gas_price =
             if is_user_specified(multi_config, "gas-price") {
@@ -57,6 +66,20 @@ pub trait UnprivilegedParseArgsConfiguration {
                     Err(pce) => return Err(pce.into_configurator_error("gas-price")),
                 }
             };
+        if let Some(thresholds) = value_m!(multi_config, "low-balance-thresholds", String) {
+            unprivileged_config
+                .blockchain_bridge_config
+                .low_balance_thresholds_opt = Some(
+                LowBalanceThresholds::from_str(&thresholds)
+                    .map_err(|e| ConfiguratorError::required("low-balance-thresholds", &e))?,
+            );
+        }
+        unprivileged_config.delinquency_policy = DelinquencyPolicy {
+            grace_period_sec: value_m!(multi_config, "delinquency-grace-period", u64).unwrap_or(0),
+            notify_debtors: value_m!(multi_config, "delinquency-notices", String)
+                .unwrap_or_else(|| "off".to_string())
+                == *"on",
+        };
         unprivileged_config.db_password_opt = value_m!(multi_config, "db-password", String);
         configure_accountant_config(multi_config, unprivileged_config, persistent_config)?;
         unprivileged_config.mapping_protocol_opt =
@@ -127,6 +150,7 @@ pub fn get_wallets(
 ) -> Result<(), ConfiguratorError> {
     let mc_consuming_opt = value_m!(multi_config, "consuming-private-key", String);
     let mc_earning_opt = value_m!(multi_config, "earning-wallet", String);
+    let external_signer_wallet_opt = get_external_signer_wallet(multi_config, &mc_consuming_opt)?;
     let pc_consuming_opt = if let Some(db_password) = &config.db_password_opt {
         match persistent_config.consuming_wallet_private_key(db_password.as_str()) {
             Ok(pco) => pco,
@@ -151,10 +175,21 @@ pub fn get_wallets(
             ))
         }
     };
+    let retired_earning_wallets = match persistent_config.retired_earning_wallets() {
+        Ok(retired) => retired,
+        Err(e) => return Err(e.into_configurator_error("earning-wallet")),
+    };
+    // A wallet rotated away from since it was set up stays in the setup; the database knows better
+    let is_retired = |address: &str| {
+        retired_earning_wallets
+            .iter()
+            .any(|retired| wallet_params_are_equal(address, &retired.wallet.to_string()))
+    };
     let earning_opt = match (&mc_earning_opt, &pc_earning_opt) {
         (None, _) => pc_earning_opt,
         (Some(_), None) => mc_earning_opt,
         (Some(m), Some(c)) if wallet_params_are_equal(m, c) => pc_earning_opt,
+        (Some(m), Some(_)) if is_retired(m) => pc_earning_opt,
         (Some(m), Some(c)) => {
             return Err(ConfiguratorError::required(
                 "earning-wallet",
@@ -191,11 +226,48 @@ pub fn get_wallets(
             )
         })
     });
-    config.consuming_wallet_opt = consuming_wallet_opt;
+    config.consuming_wallet_opt = external_signer_wallet_opt.or(consuming_wallet_opt);
     config.earning_wallet = earning_wallet_opt.unwrap_or_else(|| DEFAULT_EARNING_WALLET.clone());
+    config.retired_earning_wallets = retired_earning_wallets;
     Ok(())
 }
 
+// With an external signer the Node knows only the consuming wallet's address; the signer holds the key.
+// Routes through other Nodes need payer proofs signed with that key for every hop, so only a Node that
+// never routes through others can do without it
+fn get_external_signer_wallet(
+    multi_config: &MultiConfig,
+    mc_consuming_opt: &Option<String>,
+) -> Result<Option<Wallet>, ConfiguratorError> {
+    if value_m!(multi_config, "external-signer-url", String).is_none() {
+        return Ok(None);
+    }
+    if value_m!(multi_config, "neighborhood-mode", String).as_deref() != Some("zero-hop") {
+        return Err(ConfiguratorError::required(
+            "external-signer-url",
+            "Can only be used with --neighborhood-mode zero-hop",
+        ));
+    }
+    if mc_consuming_opt.is_some() {
+        return Err(ConfiguratorError::required(
+            "consuming-private-key",
+            "Cannot be combined with --external-signer-url",
+        ));
+    }
+    match value_m!(multi_config, "external-signer-address", String) {
+        Some(address) => Ok(Some(Wallet::from_str(&address).unwrap_or_else(|_| {
+            panic!(
+                "Validation failure: bad external signer address: {}",
+                address
+            )
+        }))),
+        None => Err(ConfiguratorError::required(
+            "external-signer-address",
+            "Required with --external-signer-url",
+        )),
+    }
+}
+
 fn wallet_params_are_equal(a: &str, b: &str) -> bool {
     a.to_uppercase() == b.to_uppercase()
 }
@@ -627,7 +699,7 @@ mod tests {
     use crate::db_config::config_dao::{ConfigDao, ConfigDaoReal};
     use crate::db_config::persistent_configuration::PersistentConfigError::NotPresent;
     use crate::db_config::persistent_configuration::PersistentConfigurationReal;
-    use crate::sub_lib::accountant::DEFAULT_PAYMENT_THRESHOLDS;
+    use crate::sub_lib::accountant::{RetiredEarningWallet, DEFAULT_PAYMENT_THRESHOLDS};
     use crate::sub_lib::cryptde::{PlainData, PublicKey};
     use crate::sub_lib::neighborhood::{Hops, DEFAULT_RATE_PACK};
     use crate::sub_lib::utils::make_new_multi_config;
@@ -638,7 +710,7 @@ mod tests {
         make_persistent_config_real_with_config_dao_null, make_simplified_multi_config,
         ACCOUNTANT_CONFIG_PARAMS, MAPPING_PROTOCOL, RATE_PACK, ZERO,
     };
-    use crate::test_utils::{main_cryptde, ArgsBuilder};
+    use crate::test_utils::{main_cryptde, make_wallet, ArgsBuilder};
     use masq_lib::constants::DEFAULT_GAS_PRICE;
     use masq_lib::multi_config::{CommandLineVcl, NameValueVclArg, VclArg, VirtualCommandLine};
     use masq_lib::test_utils::logging::{init_test_logging, TestLogHandler};
@@ -647,7 +719,7 @@ mod tests {
     use std::path::PathBuf;
     use std::str::FromStr;
     use std::sync::{Arc, Mutex};
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     fn convert_ci_configs_handles_blockchain_mismatch() {
@@ -1605,6 +1677,7 @@ mod tests {
         assert_eq!(config.earning_wallet, DEFAULT_EARNING_WALLET.clone(),);
         assert_eq!(config.consuming_wallet_opt, None);
         assert_eq!(config.mapping_protocol_opt, None);
+        assert_eq!(config.blockchain_bridge_config.confirmation_depth_opt, None);
     }
 
     #[test]
@@ -1702,6 +1775,175 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unprivileged_parse_args_with_confirmation_depth_on_command_line() {
+        running_test();
+        let args = ArgsBuilder::new()
+            .param("--neighborhood-mode", "zero-hop")
+            .param("--confirmation-depth", "64");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_multi_config(&app_node(), vcls).unwrap();
+        let mut persistent_configuration = {
+            let config = make_persistent_config(None, None, None, None, None, None)
+                .blockchain_service_url_result(Ok(None));
+            default_persistent_config_just_accountant_config(config)
+        };
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+
+        subject
+            .unprivileged_parse_args(
+                &multi_config,
+                &mut config,
+                &mut persistent_configuration,
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            config.blockchain_bridge_config.confirmation_depth_opt,
+            Some(64)
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_with_low_balance_thresholds_on_command_line() {
+        running_test();
+        let args = ArgsBuilder::new()
+            .param("--neighborhood-mode", "zero-hop")
+            .param("--low-balance-thresholds", "1234|5678");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_multi_config(&app_node(), vcls).unwrap();
+        let mut persistent_configuration = {
+            let config = make_persistent_config(None, None, None, None, None, None)
+                .blockchain_service_url_result(Ok(None));
+            default_persistent_config_just_accountant_config(config)
+        };
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+
+        subject
+            .unprivileged_parse_args(
+                &multi_config,
+                &mut config,
+                &mut persistent_configuration,
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            config.blockchain_bridge_config.low_balance_thresholds_opt,
+            Some(LowBalanceThresholds {
+                gas_gwei: 1234,
+                masq_gwei: 5678
+            })
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_rejects_the_wrong_number_of_low_balance_thresholds() {
+        running_test();
+        let args = ArgsBuilder::new()
+            .param("--neighborhood-mode", "zero-hop")
+            .param("--low-balance-thresholds", "1234");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_multi_config(&app_node(), vcls).unwrap();
+        let mut persistent_configuration = {
+            let config = make_persistent_config(None, None, None, None, None, None)
+                .blockchain_service_url_result(Ok(None));
+            default_persistent_config_just_accountant_config(config)
+        };
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+
+        let result = subject.unprivileged_parse_args(
+            &multi_config,
+            &mut config,
+            &mut persistent_configuration,
+            &Logger::new("test"),
+        );
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "low-balance-thresholds",
+                "Supply exactly two thresholds, gas and MASQ, like 10000000|1000000000, not '1234'"
+            ))
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_with_delinquency_policy_on_command_line() {
+        running_test();
+        let args = ArgsBuilder::new()
+            .param("--neighborhood-mode", "zero-hop")
+            .param("--delinquency-grace-period", "3600")
+            .param("--delinquency-notices", "on");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_multi_config(&app_node(), vcls).unwrap();
+        let mut persistent_configuration = {
+            let config = make_persistent_config(None, None, None, None, None, None)
+                .blockchain_service_url_result(Ok(None));
+            default_persistent_config_just_accountant_config(config)
+        };
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+
+        subject
+            .unprivileged_parse_args(
+                &multi_config,
+                &mut config,
+                &mut persistent_configuration,
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            config.delinquency_policy,
+            DelinquencyPolicy {
+                grace_period_sec: 3600,
+                notify_debtors: true
+            }
+        );
+    }
+
+    #[test]
+    fn unprivileged_parse_args_defaults_delinquency_policy_to_immediate_silent_bans() {
+        running_test();
+        let args = ArgsBuilder::new().param("--neighborhood-mode", "zero-hop");
+        let mut config = BootstrapperConfig::new();
+        let vcls: Vec<Box<dyn VirtualCommandLine>> =
+            vec![Box::new(CommandLineVcl::new(args.into()))];
+        let multi_config = make_new_multi_config(&app_node(), vcls).unwrap();
+        let mut persistent_configuration = {
+            let config = make_persistent_config(None, None, None, None, None, None)
+                .blockchain_service_url_result(Ok(None));
+            default_persistent_config_just_accountant_config(config)
+        };
+        let subject = UnprivilegedParseArgsConfigurationDaoReal {};
+
+        subject
+            .unprivileged_parse_args(
+                &multi_config,
+                &mut config,
+                &mut persistent_configuration,
+                &Logger::new("test"),
+            )
+            .unwrap();
+
+        assert_eq!(
+            config.delinquency_policy,
+            DelinquencyPolicy {
+                grace_period_sec: 0,
+                notify_debtors: false
+            }
+        );
+    }
+
     #[test]
     fn unprivileged_parse_args_with_mapping_protocol_both_on_command_line_and_in_database() {
         running_test();
@@ -2328,6 +2570,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn earning_wallet_address_rotated_away_from_gives_way_to_database() {
+        running_test();
+        let args = [
+            "--earning-wallet",
+            "0x0123456789012345678901234567890123456789",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let retired = vec![RetiredEarningWallet {
+            wallet: Wallet::from_str("0x0123456789012345678901234567890123456789").unwrap(),
+            watch_until: SystemTime::now() + Duration::from_secs(3600),
+        }];
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .earning_wallet_address_result(Ok(Some(
+                "0x9876543210987654321098765432109876543210".to_string(),
+            )))
+            .retired_earning_wallets_result(Ok(retired.clone()));
+        let mut config = BootstrapperConfig::new();
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        assert_eq!(
+            config.earning_wallet,
+            Wallet::from_str("0x9876543210987654321098765432109876543210").unwrap()
+        );
+        assert_eq!(config.retired_earning_wallets, retired);
+    }
+
+    #[test]
+    fn get_wallets_loads_retired_earning_wallets() {
+        running_test();
+        let multi_config = make_simplified_multi_config([]);
+        let retired = vec![RetiredEarningWallet {
+            wallet: make_wallet("retired"),
+            watch_until: SystemTime::now() + Duration::from_secs(3600),
+        }];
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .earning_wallet_address_result(Ok(Some(
+                "0xcafedeadbeefbabefacecafedeadbeefbabeface".to_string(),
+            )))
+            .retired_earning_wallets_result(Ok(retired.clone()));
+        let mut config = BootstrapperConfig::new();
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        assert_eq!(
+            config.earning_wallet,
+            Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap()
+        );
+        assert_eq!(config.retired_earning_wallets, retired);
+    }
+
+    #[test]
+    fn get_wallets_handles_failure_of_retired_earning_wallets() {
+        running_test();
+        let multi_config = make_simplified_multi_config([]);
+        let mut persistent_config = PersistentConfigurationMock::new()
+            .earning_wallet_address_result(Ok(None))
+            .retired_earning_wallets_result(Err(PersistentConfigError::UninterpretableValue(
+                "booga".to_string(),
+            )));
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config);
+
+        assert_eq!(
+            result,
+            Err(
+                PersistentConfigError::UninterpretableValue("booga".to_string())
+                    .into_configurator_error("earning-wallet")
+            )
+        );
+    }
+
     #[test]
     fn consuming_wallet_private_key_different_from_database() {
         running_test();
@@ -2381,6 +2697,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_wallets_makes_address_only_consuming_wallet_for_external_signer() {
+        running_test();
+        let args = [
+            "--external-signer-url",
+            "http://127.0.0.1:8550",
+            "--neighborhood-mode",
+            "zero-hop",
+            "--external-signer-address",
+            "0xcafedeadbeefbabefacecafedeadbeefbabeface",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config = make_persistent_config(
+            Some("password"),
+            Some("0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF0123456789ABCDEF"),
+            None,
+            None,
+            None,
+            None,
+        );
+        let mut config = BootstrapperConfig::new();
+        config.db_password_opt = Some("password".to_string());
+
+        get_wallets(&multi_config, &mut persistent_config, &mut config).unwrap();
+
+        assert_eq!(
+            config.consuming_wallet_opt,
+            Some(Wallet::from_str("0xcafedeadbeefbabefacecafedeadbeefbabeface").unwrap())
+        );
+    }
+
+    #[test]
+    fn get_wallets_requires_external_signer_address_with_external_signer_url() {
+        running_test();
+        let args = [
+            "--external-signer-url",
+            "http://127.0.0.1:8550",
+            "--neighborhood-mode",
+            "zero-hop",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config = make_persistent_config(None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config);
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "external-signer-address",
+                "Required with --external-signer-url"
+            ))
+        );
+    }
+
+    #[test]
+    fn get_wallets_rejects_consuming_private_key_with_external_signer_url() {
+        running_test();
+        let args = [
+            "--external-signer-url",
+            "http://127.0.0.1:8550",
+            "--neighborhood-mode",
+            "zero-hop",
+            "--external-signer-address",
+            "0xcafedeadbeefbabefacecafedeadbeefbabeface",
+            "--consuming-private-key",
+            "ABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCDABCD",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config = make_persistent_config(None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config);
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "consuming-private-key",
+                "Cannot be combined with --external-signer-url"
+            ))
+        );
+    }
+
+    #[test]
+    fn get_wallets_refuses_external_signer_url_outside_zero_hop_mode() {
+        running_test();
+        let args = [
+            "--external-signer-url",
+            "http://127.0.0.1:8550",
+            "--external-signer-address",
+            "0xcafedeadbeefbabefacecafedeadbeefbabeface",
+            "--neighborhood-mode",
+            "consume-only",
+        ];
+        let multi_config = make_simplified_multi_config(args);
+        let mut persistent_config = make_persistent_config(None, None, None, None, None, None);
+        let mut config = BootstrapperConfig::new();
+
+        let result = get_wallets(&multi_config, &mut persistent_config, &mut config);
+
+        assert_eq!(
+            result,
+            Err(ConfiguratorError::required(
+                "external-signer-url",
+                "Can only be used with --neighborhood-mode zero-hop"
+            ))
+        );
+    }
+
     #[test]
     fn configure_rate_pack_command_line_absent_config_dao_null_so_all_defaults() {
         running_test();
@@ -2621,6 +3046,7 @@ mod tests {
                 Ok(earning_wallet_address_opt.map(|ewa| ewa.to_string())),
             )
             .earning_wallet_result(Ok(earning_wallet_opt))
+            .retired_earning_wallets_result(Ok(vec![]))
             .gas_price_result(Ok(gas_price))
             .past_neighbors_result(past_neighbors_result)
             .mapping_protocol_result(Ok(Some(AutomapProtocol::Pcp)))
@@ -8,16 +8,19 @@ mod stream_handler_pool;
 mod stream_reader;
 mod stream_writer;
 
+use crate::proxy_client::resolver_wrapper::ResolverWrapper;
 use crate::proxy_client::resolver_wrapper::ResolverWrapperFactory;
 use crate::proxy_client::resolver_wrapper::ResolverWrapperFactoryReal;
 use crate::proxy_client::stream_handler_pool::StreamHandlerPool;
 use crate::proxy_client::stream_handler_pool::StreamHandlerPoolFactory;
 use crate::proxy_client::stream_handler_pool::StreamHandlerPoolFactoryReal;
 use crate::sub_lib::accountant::ReportExitServiceProvidedMessage;
+use crate::sub_lib::configurator::{ConfigurationChange, ConfigurationChangeMessage};
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::hopper::MessageType;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, IncipientCoresPackage};
+use crate::sub_lib::neighborhood::PayingWalletSighting;
 use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::InboundServerData;
 use crate::sub_lib::proxy_client::ProxyClientConfig;
@@ -55,6 +58,7 @@ pub struct ProxyClient {
     cryptde: &'static dyn CryptDE,
     to_hopper: Option<Recipient<IncipientCoresPackage>>,
     to_accountant: Option<Recipient<ReportExitServiceProvidedMessage>>,
+    to_neighborhood: Option<Recipient<PayingWalletSighting>>,
     pool: Option<Box<dyn StreamHandlerPool>>,
     stream_contexts: HashMap<StreamKey, StreamContext>,
     exit_service_rate: u64,
@@ -76,17 +80,8 @@ impl Handler<BindMessage> for ProxyClient {
         ctx.set_mailbox_capacity(NODE_MAILBOX_CAPACITY);
         self.to_hopper = Some(msg.peer_actors.hopper.from_hopper_client);
         self.to_accountant = Some(msg.peer_actors.accountant.report_exit_service_provided);
-        let mut config = ResolverConfig::new();
-        for dns_server_ref in &self.dns_servers {
-            info!(self.logger, "Adding DNS server: {}", dns_server_ref.ip());
-            config.add_name_server(NameServerConfig {
-                socket_addr: *dns_server_ref,
-                protocol: Protocol::Udp,
-                tls_dns_name: None,
-            })
-        }
-        let opts = ResolverOpts::default();
-        let resolver = self.resolver_wrapper_factory.make(config, opts);
+        self.to_neighborhood = Some(msg.peer_actors.neighborhood.paying_wallet_sighting_sub);
+        let resolver = self.make_resolver();
         self.pool = Some(self.stream_handler_pool_factory.make(
             resolver,
             self.cryptde,
@@ -123,8 +118,21 @@ impl Handler<ExpiredCoresPackage<ClientRequestPayload_0v1>> for ProxyClient {
                 payload.sequenced_packet.sequence_number,
                 payload.sequenced_packet.data.len()
             );
-            self.stream_contexts
-                .insert(payload.stream_key, latest_stream_context);
+            let new_stream = self
+                .stream_contexts
+                .insert(payload.stream_key, latest_stream_context)
+                .is_none();
+            // The Neighborhood needs to know whom to warn when this wallet's debts go unpaid
+            if let (true, Some(wallet)) = (new_stream, paying_wallet.as_ref()) {
+                self.to_neighborhood
+                    .as_ref()
+                    .expect("Neighborhood unbound")
+                    .try_send(PayingWalletSighting {
+                        paying_wallet: wallet.clone(),
+                        public_key: payload.originator_public_key.clone(),
+                    })
+                    .expect("Neighborhood is dead");
+            }
             pool.process_package(payload, paying_wallet);
         } else {
             warning!(self.logger, "Refusing to provide exit services for CORES package with {}-byte payload without paying wallet", payload.sequenced_packet.data.len());
@@ -208,6 +216,18 @@ impl Handler<DnsResolveFailure_0v1> for ProxyClient {
     }
 }
 
+impl Handler<ConfigurationChangeMessage> for ProxyClient {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ConfigurationChangeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_configuration_change(msg.change)
+    }
+}
+
 impl Handler<NodeFromUiMessage> for ProxyClient {
     type Result = ();
 
@@ -228,6 +248,7 @@ impl ProxyClient {
             cryptde: config.cryptde,
             to_hopper: None,
             to_accountant: None,
+            to_neighborhood: None,
             pool: None,
             stream_contexts: HashMap::new(),
             exit_service_rate: config.exit_service_rate,
@@ -245,6 +266,55 @@ impl ProxyClient {
             inbound_server_data: recipient!(addr, InboundServerData),
             dns_resolve_failed: recipient!(addr, DnsResolveFailure_0v1),
             node_from_ui: recipient!(addr, NodeFromUiMessage),
+            configuration_change: recipient!(addr, ConfigurationChangeMessage),
+        }
+    }
+
+    fn make_resolver(&self) -> Box<dyn ResolverWrapper> {
+        let mut config = ResolverConfig::new();
+        for dns_server_ref in &self.dns_servers {
+            info!(self.logger, "Adding DNS server: {}", dns_server_ref.ip());
+            config.add_name_server(NameServerConfig {
+                socket_addr: *dns_server_ref,
+                protocol: Protocol::Udp,
+                tls_dns_name: None,
+            })
+        }
+        let opts = ResolverOpts::default();
+        self.resolver_wrapper_factory.make(config, opts)
+    }
+
+    fn handle_configuration_change(&mut self, change: ConfigurationChange) {
+        match change {
+            ConfigurationChange::RatePack(rate_pack) => {
+                self.exit_service_rate = rate_pack.exit_service_rate;
+                self.exit_byte_rate = rate_pack.exit_byte_rate;
+                if let Some(pool) = self.pool.as_ref() {
+                    pool.update_exit_rates(self.exit_service_rate, self.exit_byte_rate);
+                }
+                info!(
+                    self.logger,
+                    "Exit rates changed to {} per service and {} per byte",
+                    self.exit_service_rate,
+                    self.exit_byte_rate
+                );
+            }
+            ConfigurationChange::DnsServers(dns_servers) => {
+                self.dns_servers = dns_servers;
+                if let Some(pool) = self.pool.as_ref() {
+                    pool.update_resolver(self.make_resolver());
+                }
+                info!(
+                    self.logger,
+                    "DNS servers changed; new streams will resolve through {}",
+                    self.dns_servers
+                        .iter()
+                        .map(|addr| addr.ip().to_string())
+                        .collect::<Vec<String>>()
+                        .join(",")
+                );
+            }
+            _ => (),
         }
     }
 
@@ -342,6 +412,7 @@ mod tests {
     use crate::sub_lib::cryptde::PublicKey;
     use crate::sub_lib::dispatcher::Component;
     use crate::sub_lib::hopper::MessageType;
+    use crate::sub_lib::neighborhood::RatePack;
     use crate::sub_lib::proxy_client::ClientResponsePayload_0v1;
     use crate::sub_lib::proxy_server::ClientRequestPayload_0v1;
     use crate::sub_lib::proxy_server::ProxyProtocol;
@@ -378,6 +449,8 @@ mod tests {
 
     pub struct StreamHandlerPoolMock {
         process_package_parameters: Arc<Mutex<Vec<(ClientRequestPayload_0v1, Option<Wallet>)>>>,
+        update_exit_rates_parameters: Arc<Mutex<Vec<(u64, u64)>>>,
+        update_resolver_parameters: Arc<Mutex<Vec<Box<dyn ResolverWrapper>>>>,
     }
 
     impl StreamHandlerPool for StreamHandlerPoolMock {
@@ -391,12 +464,28 @@ mod tests {
                 .unwrap()
                 .push((payload, paying_wallet));
         }
+
+        fn update_exit_rates(&self, exit_service_rate: u64, exit_byte_rate: u64) {
+            self.update_exit_rates_parameters
+                .lock()
+                .unwrap()
+                .push((exit_service_rate, exit_byte_rate));
+        }
+
+        fn update_resolver(&self, resolver: Box<dyn ResolverWrapper>) {
+            self.update_resolver_parameters
+                .lock()
+                .unwrap()
+                .push(resolver);
+        }
     }
 
     impl StreamHandlerPoolMock {
         pub fn new() -> StreamHandlerPoolMock {
             StreamHandlerPoolMock {
                 process_package_parameters: Arc::new(Mutex::new(vec![])),
+                update_exit_rates_parameters: Arc::new(Mutex::new(vec![])),
+                update_resolver_parameters: Arc::new(Mutex::new(vec![])),
             }
         }
 
@@ -407,6 +496,22 @@ mod tests {
             *parameters = self.process_package_parameters.clone();
             self
         }
+
+        pub fn update_exit_rates_parameters(
+            self,
+            parameters: &mut Arc<Mutex<Vec<(u64, u64)>>>,
+        ) -> StreamHandlerPoolMock {
+            *parameters = self.update_exit_rates_parameters.clone();
+            self
+        }
+
+        pub fn update_resolver_parameters(
+            self,
+            parameters: &mut Arc<Mutex<Vec<Box<dyn ResolverWrapper>>>>,
+        ) -> StreamHandlerPoolMock {
+            *parameters = self.update_resolver_parameters.clone();
+            self
+        }
     }
 
     pub struct StreamHandlerPoolFactoryMock {
@@ -597,6 +702,82 @@ mod tests {
         assert_eq!(resolver_wrapper_new_parameters.is_empty(), true);
     }
 
+    #[test]
+    fn rate_pack_change_updates_exit_rates_in_proxy_client_and_pool() {
+        init_test_logging();
+        let mut update_exit_rates_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let pool = StreamHandlerPoolMock::new()
+            .update_exit_rates_parameters(&mut update_exit_rates_parameters_arc);
+        let mut subject = ProxyClient::new(ProxyClientConfig {
+            cryptde: main_cryptde(),
+            dns_servers: dnss(),
+            exit_service_rate: 100,
+            exit_byte_rate: 200,
+            is_decentralized: true,
+            crashable: false,
+        });
+        subject.pool = Some(Box::new(pool));
+        subject.logger = Logger::new("rate_pack_change_updates_exit_rates");
+
+        subject.handle_configuration_change(ConfigurationChange::RatePack(RatePack {
+            routing_byte_rate: 1,
+            routing_service_rate: 2,
+            exit_byte_rate: 3,
+            exit_service_rate: 4,
+        }));
+
+        assert_eq!(subject.exit_service_rate, 4);
+        assert_eq!(subject.exit_byte_rate, 3);
+        let update_exit_rates_parameters = update_exit_rates_parameters_arc.lock().unwrap();
+        assert_eq!(*update_exit_rates_parameters, vec![(4, 3)]);
+        TestLogHandler::new().exists_log_containing(
+            "INFO: rate_pack_change_updates_exit_rates: Exit rates changed to 4 per service and 3 per byte",
+        );
+    }
+
+    #[test]
+    fn dns_servers_change_gives_the_pool_a_new_resolver() {
+        let mut resolver_wrapper_new_parameters_arc: Arc<
+            Mutex<Vec<(ResolverConfig, ResolverOpts)>>,
+        > = Arc::new(Mutex::new(vec![]));
+        let resolver_wrapper_factory = ResolverWrapperFactoryMock::new()
+            .new_parameters(&mut resolver_wrapper_new_parameters_arc)
+            .new_result(Box::new(ResolverWrapperMock::new()));
+        let mut update_resolver_parameters_arc = Arc::new(Mutex::new(vec![]));
+        let pool = StreamHandlerPoolMock::new()
+            .update_resolver_parameters(&mut update_resolver_parameters_arc);
+        let mut subject = ProxyClient::new(ProxyClientConfig {
+            cryptde: main_cryptde(),
+            dns_servers: dnss(),
+            exit_service_rate: 100,
+            exit_byte_rate: 200,
+            is_decentralized: true,
+            crashable: false,
+        });
+        subject.resolver_wrapper_factory = Box::new(resolver_wrapper_factory);
+        subject.pool = Some(Box::new(pool));
+        let new_dns_servers = vec![
+            SocketAddr::from_str("9.9.9.9:53").unwrap(),
+            SocketAddr::from_str("1.0.0.1:53").unwrap(),
+        ];
+
+        subject
+            .handle_configuration_change(ConfigurationChange::DnsServers(new_dns_servers.clone()));
+
+        assert_eq!(subject.dns_servers, new_dns_servers);
+        let resolver_wrapper_new_parameters = resolver_wrapper_new_parameters_arc.lock().unwrap();
+        let (config, _) = &resolver_wrapper_new_parameters[0];
+        assert_eq!(
+            config
+                .name_servers()
+                .iter()
+                .map(|ns| ns.socket_addr)
+                .collect::<Vec<SocketAddr>>(),
+            new_dns_servers
+        );
+        assert_eq!(update_resolver_parameters_arc.lock().unwrap().len(), 1);
+    }
+
     #[test]
     #[should_panic(expected = "StreamHandlerPool unbound")]
     fn panics_if_unbound() {
@@ -799,6 +980,67 @@ mod tests {
         assert_eq!(parameter, (request, Some(make_wallet("consuming")),));
     }
 
+    #[test]
+    fn paying_originator_of_new_stream_is_reported_to_neighborhood() {
+        let request = ClientRequestPayload_0v1 {
+            stream_key: make_meaningless_stream_key(),
+            sequenced_packet: SequencedPacket {
+                data: b"inbound data".to_vec(),
+                sequence_number: 0,
+                last_data: false,
+            },
+            target_hostname: None,
+            target_port: 0,
+            protocol: ProxyProtocol::HTTP,
+            originator_public_key: PublicKey::new(&b"originator"[..]),
+        };
+        let key1 = make_meaningless_public_key();
+        let key2 = make_meaningless_public_key();
+        let route = make_one_way_route_to_proxy_client(vec![&key1, &key2]);
+        let package = ExpiredCoresPackage::new(
+            SocketAddr::from_str("1.2.3.4:1234").unwrap(),
+            Some(make_wallet("consuming")),
+            route,
+            request.into(),
+            0,
+        );
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let system = System::new("paying_originator_of_new_stream_is_reported_to_neighborhood");
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        let pool = Box::new(StreamHandlerPoolMock::new());
+        let pool_factory = StreamHandlerPoolFactoryMock::new().make_result(pool);
+        let resolver = ResolverWrapperMock::new()
+            .lookup_ip_success(vec![IpAddr::from_str("4.3.2.1").unwrap()]);
+        let resolver_factory = ResolverWrapperFactoryMock::new().new_result(Box::new(resolver));
+        let mut subject = ProxyClient::new(ProxyClientConfig {
+            cryptde: main_cryptde(),
+            dns_servers: dnss(),
+            exit_service_rate: 100,
+            exit_byte_rate: 200,
+            is_decentralized: true,
+            crashable: false,
+        });
+        subject.resolver_wrapper_factory = Box::new(resolver_factory);
+        subject.stream_handler_pool_factory = Box::new(pool_factory);
+        let subject_addr: Addr<ProxyClient> = subject.start();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr.try_send(package.clone()).unwrap();
+        subject_addr.try_send(package).unwrap();
+
+        System::current().stop_with_code(0);
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<PayingWalletSighting>(0),
+            &PayingWalletSighting {
+                paying_wallet: make_wallet("consuming"),
+                public_key: PublicKey::new(&b"originator"[..]),
+            }
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
     #[test]
     fn refuse_to_provide_exit_services_with_no_paying_wallet() {
         init_test_logging();
@@ -31,6 +31,8 @@ use trust_dns_resolver::lookup_ip::LookupIp;
 
 pub trait StreamHandlerPool {
     fn process_package(&self, payload: ClientRequestPayload_0v1, paying_wallet_opt: Option<Wallet>);
+    fn update_exit_rates(&self, exit_service_rate: u64, exit_byte_rate: u64);
+    fn update_resolver(&self, resolver: Box<dyn ResolverWrapper>);
 }
 
 pub struct StreamHandlerPoolReal {
@@ -59,6 +61,19 @@ impl StreamHandlerPool for StreamHandlerPoolReal {
         self.do_housekeeping();
         Self::process_package(payload, paying_wallet_opt, self.inner.clone())
     }
+
+    fn update_exit_rates(&self, exit_service_rate: u64, exit_byte_rate: u64) {
+        let mut inner = self.inner.lock().expect("Stream handler pool is poisoned");
+        inner.exit_service_rate = exit_service_rate;
+        inner.exit_byte_rate = exit_byte_rate;
+    }
+
+    fn update_resolver(&self, resolver: Box<dyn ResolverWrapper>) {
+        self.inner
+            .lock()
+            .expect("Stream handler pool is poisoned")
+            .resolver = resolver;
+    }
 }
 
 type StreamEstablisherResult =
@@ -1606,4 +1621,24 @@ mod tests {
         let proxy_client_recording = proxy_client_recording_arc.lock().unwrap();
         assert_eq!(proxy_client_recording.len(), 0);
     }
+
+    #[test]
+    fn update_exit_rates_replaces_the_rates_charged_for_new_exits() {
+        let (proxy_client, _, _) = make_recorder();
+        let peer_actors = peer_actors_builder().proxy_client(proxy_client).build();
+        let subject = StreamHandlerPoolReal::new(
+            Box::new(ResolverWrapperMock::new()),
+            main_cryptde(),
+            peer_actors.accountant.report_exit_service_provided,
+            peer_actors.proxy_client_opt.unwrap(),
+            100,
+            200,
+        );
+
+        subject.update_exit_rates(123, 456);
+
+        let inner = subject.inner.lock().unwrap();
+        assert_eq!(inner.exit_service_rate, 123);
+        assert_eq!(inner.exit_byte_rate, 456);
+    }
 }
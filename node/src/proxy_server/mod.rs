@@ -18,12 +18,14 @@ use crate::stream_messages::RemovedStreamType;
 use crate::sub_lib::accountant::RoutingServiceConsumed;
 use crate::sub_lib::accountant::{ExitServiceConsumed, ReportServicesConsumedMessage};
 use crate::sub_lib::bidi_hashmap::BidiHashMap;
+use crate::sub_lib::configurator::{ConfigurationChange, ConfigurationChangeMessage};
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::dispatcher::InboundClientData;
 use crate::sub_lib::dispatcher::{Endpoint, StreamShutdownMsg};
 use crate::sub_lib::hopper::{ExpiredCoresPackage, IncipientCoresPackage};
 use crate::sub_lib::neighborhood::RouteQueryResponse;
+use crate::sub_lib::neighborhood::ShutdownStageComplete;
 use crate::sub_lib::neighborhood::{ExpectedService, NodeRecordMetadataMessage};
 use crate::sub_lib::neighborhood::{ExpectedServices, RatePack};
 use crate::sub_lib::neighborhood::{NRMetadataChange, RouteQueryMessage};
@@ -31,21 +33,29 @@ use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
 use crate::sub_lib::proxy_server::ClientRequestPayload_0v1;
 use crate::sub_lib::proxy_server::ProxyServerSubs;
-use crate::sub_lib::proxy_server::{AddReturnRouteMessage, AddRouteMessage};
+use crate::sub_lib::proxy_server::{
+    AddReturnRouteMessage, AddRouteMessage, ConsumingServicesStatus, DrainClientStreams,
+};
 use crate::sub_lib::route::Route;
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
 use crate::sub_lib::stream_handler_pool::TransmitDataMsg;
 use crate::sub_lib::stream_key::StreamKey;
 use crate::sub_lib::ttl_hashmap::TtlHashMap;
-use crate::sub_lib::utils::{handle_ui_crash_request, NODE_MAILBOX_CAPACITY};
+use crate::sub_lib::utils::{
+    handle_ui_crash_request, NotifyLaterHandle, NotifyLaterHandleReal, NODE_MAILBOX_CAPACITY,
+};
 use crate::sub_lib::wallet::Wallet;
 use actix::Addr;
 use actix::Context;
 use actix::Handler;
+use actix::Message;
 use actix::Recipient;
 use actix::{Actor, MailboxError};
 use masq_lib::logger::Logger;
-use masq_lib::ui_gateway::NodeFromUiMessage;
+use masq_lib::messages::{
+    FromMessageBody, ToMessageBody, UiStreamStatusRequest, UiStreamStatusResponse,
+};
+use masq_lib::ui_gateway::{MessageTarget, NodeFromUiMessage, NodeToUiMessage};
 use masq_lib::utils::MutabilityConflictHelper;
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -65,6 +75,18 @@ struct ProxyServerOutSubs {
     add_return_route: Recipient<AddReturnRouteMessage>,
     add_route: Recipient<AddRouteMessage>,
     stream_shutdown_sub: Recipient<StreamShutdownMsg>,
+    shutdown_stage_complete: Recipient<ShutdownStageComplete>,
+    node_to_ui: Recipient<NodeToUiMessage>,
+}
+
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrainDeadline {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DrainState {
+    Accepting,
+    Draining,
+    Drained,
 }
 
 pub struct ProxyServer {
@@ -76,6 +98,7 @@ pub struct ProxyServer {
     stream_key_routes: HashMap<StreamKey, RouteQueryResponse>,
     is_decentralized: bool,
     consuming_wallet_balance: Option<i64>,
+    consuming_services_suspended: bool,
     main_cryptde: &'static dyn CryptDE,
     alias_cryptde: &'static dyn CryptDE,
     crashable: bool,
@@ -83,6 +106,8 @@ pub struct ProxyServer {
     route_ids_to_return_routes: TtlHashMap<u32, AddReturnRouteMessage>,
     browser_proxy_sequence_offset: bool,
     inbound_client_data_helper_opt: Option<Box<dyn IBCDHelper>>,
+    drain_state: DrainState,
+    notify_later_drain_deadline: Box<dyn NotifyLaterHandle<DrainDeadline, ProxyServer>>,
 }
 
 impl Actor for ProxyServer {
@@ -103,6 +128,8 @@ impl Handler<BindMessage> for ProxyServer {
             add_return_route: msg.peer_actors.proxy_server.add_return_route,
             add_route: msg.peer_actors.proxy_server.add_route,
             stream_shutdown_sub: msg.peer_actors.proxy_server.stream_shutdown_sub,
+            shutdown_stage_complete: msg.peer_actors.neighborhood.shutdown_stage_complete_sub,
+            node_to_ui: msg.peer_actors.ui_gateway.node_to_ui_message_sub,
         };
         self.subs = Some(subs);
     }
@@ -123,11 +150,36 @@ impl Handler<SetConsumingWalletMessage> for ProxyServer {
     }
 }
 
+impl Handler<ConsumingServicesStatus> for ProxyServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: ConsumingServicesStatus, _ctx: &mut Self::Context) -> Self::Result {
+        if msg.suspended != self.consuming_services_suspended {
+            if msg.suspended {
+                warning!(
+                    self.logger,
+                    "Consuming wallet is low on funds; refusing browser requests until it's replenished"
+                )
+            } else {
+                info!(
+                    self.logger,
+                    "Consuming wallet has been replenished; accepting browser requests again"
+                )
+            }
+        }
+        self.consuming_services_suspended = msg.suspended;
+    }
+}
+
 impl Handler<InboundClientData> for ProxyServer {
     type Result = ();
 
     fn handle(&mut self, msg: InboundClientData, _ctx: &mut Self::Context) -> Self::Result {
-        if msg.is_connect() {
+        if self.drain_state != DrainState::Accepting
+            && self.keys_and_addrs.b_to_a(&msg.peer_addr).is_none()
+        {
+            self.refuse_new_stream(msg.peer_addr)
+        } else if msg.is_connect() {
             self.tls_connect(&msg);
             self.browser_proxy_sequence_offset = true;
         } else if let Err(e) =
@@ -138,6 +190,42 @@ impl Handler<InboundClientData> for ProxyServer {
     }
 }
 
+impl Handler<DrainClientStreams> for ProxyServer {
+    type Result = ();
+
+    fn handle(&mut self, msg: DrainClientStreams, ctx: &mut Self::Context) -> Self::Result {
+        self.handle_drain_client_streams(msg, ctx)
+    }
+}
+
+impl Handler<ConfigurationChangeMessage> for ProxyServer {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: ConfigurationChangeMessage,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        self.handle_configuration_change(msg.change)
+    }
+}
+
+impl Handler<DrainDeadline> for ProxyServer {
+    type Result = ();
+
+    fn handle(&mut self, _msg: DrainDeadline, _ctx: &mut Self::Context) -> Self::Result {
+        if self.drain_state == DrainState::Draining {
+            let abandoned_streams = self.keys_and_addrs.len();
+            warning!(
+                self.logger,
+                "Drain timeout expired; abandoning {} open client stream(s)",
+                abandoned_streams
+            );
+            self.report_streams_drained(abandoned_streams)
+        }
+    }
+}
+
 impl Handler<AddReturnRouteMessage> for ProxyServer {
     type Result = ();
 
@@ -207,7 +295,11 @@ impl Handler<NodeFromUiMessage> for ProxyServer {
     type Result = ();
 
     fn handle(&mut self, msg: NodeFromUiMessage, _ctx: &mut Self::Context) -> Self::Result {
-        handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
+        if let Ok((_, context_id)) = UiStreamStatusRequest::fmb(msg.body.clone()) {
+            self.handle_stream_status_request(msg.client_id, context_id)
+        } else {
+            handle_ui_crash_request(msg, &self.logger, self.crashable, CRASH_KEY)
+        }
     }
 }
 
@@ -228,6 +320,7 @@ impl ProxyServer {
             stream_key_routes: HashMap::new(),
             is_decentralized,
             consuming_wallet_balance,
+            consuming_services_suspended: false,
             main_cryptde,
             alias_cryptde,
             crashable,
@@ -235,6 +328,8 @@ impl ProxyServer {
             route_ids_to_return_routes: TtlHashMap::new(RETURN_ROUTE_TTL),
             browser_proxy_sequence_offset: false,
             inbound_client_data_helper_opt: Some(Box::new(IBCDHelperReal {})),
+            drain_state: DrainState::Accepting,
+            notify_later_drain_deadline: Box::new(NotifyLaterHandleReal::new()),
         }
     }
 
@@ -248,6 +343,9 @@ impl ProxyServer {
             add_route: recipient!(addr, AddRouteMessage),
             stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
             set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
+            consuming_services_status: recipient!(addr, ConsumingServicesStatus),
+            drain_client_streams: recipient!(addr, DrainClientStreams),
+            configuration_change: recipient!(addr, ConfigurationChangeMessage),
             node_from_ui: recipient!(addr, NodeFromUiMessage),
         }
     }
@@ -435,6 +533,20 @@ impl ProxyServer {
             .unwrap_or_else(|| panic!("{} unbound in ProxyServer", actor_name))
     }
 
+    fn handle_stream_status_request(&self, client_id: u64, context_id: u64) {
+        let response = UiStreamStatusResponse {
+            active_streams: self.keys_and_addrs.len() as u64,
+            tunneled_streams: self.tunneled_hosts.len() as u64,
+        };
+        self.out_subs("UiGateway")
+            .node_to_ui
+            .try_send(NodeToUiMessage {
+                target: MessageTarget::ClientId(client_id),
+                body: response.tmb(context_id),
+            })
+            .expect("UiGateway is dead");
+    }
+
     fn handle_stream_shutdown_msg(&mut self, msg: StreamShutdownMsg) {
         let nca = match msg.stream_type {
             RemovedStreamType::Clandestine => {
@@ -510,6 +622,103 @@ impl ProxyServer {
         let _ = self.keys_and_addrs.remove_a(stream_key);
         let _ = self.stream_key_routes.remove(stream_key);
         let _ = self.tunneled_hosts.remove(stream_key);
+        if self.drain_state == DrainState::Draining && self.keys_and_addrs.is_empty() {
+            info!(self.logger, "All client streams have finished");
+            self.report_streams_drained(0)
+        }
+    }
+
+    fn handle_configuration_change(&mut self, change: ConfigurationChange) {
+        if let ConfigurationChange::MinHops(min_hops_count) = change {
+            self.forget_routes_shorter_than(min_hops_count as usize)
+        }
+    }
+
+    // Open streams whose routes are now too short get a fresh route with their next request
+    fn forget_routes_shorter_than(&mut self, min_hops: usize) {
+        if !self.is_decentralized {
+            return;
+        }
+        let short_route_stream_keys = self
+            .stream_key_routes
+            .iter()
+            .filter(|(_, route_query_response)| {
+                Self::route_hop_count(route_query_response) < min_hops
+            })
+            .map(|(stream_key, _)| *stream_key)
+            .collect::<Vec<StreamKey>>();
+        if short_route_stream_keys.is_empty() {
+            return;
+        }
+        short_route_stream_keys.iter().for_each(|stream_key| {
+            let _ = self.stream_key_routes.remove(stream_key);
+        });
+        info!(
+            self.logger,
+            "Minimum hop count changed to {}; rerouting {} open stream(s) with their next request",
+            min_hops,
+            short_route_stream_keys.len()
+        );
+    }
+
+    fn route_hop_count(route_query_response: &RouteQueryResponse) -> usize {
+        let over = match &route_query_response.expected_services {
+            ExpectedServices::OneWay(services) => services,
+            ExpectedServices::RoundTrip(over, _, _) => over,
+        };
+        over.iter()
+            .filter(|service| !matches!(service, ExpectedService::Nothing))
+            .count()
+    }
+
+    fn handle_drain_client_streams(&mut self, msg: DrainClientStreams, ctx: &mut Context<Self>) {
+        if self.drain_state != DrainState::Accepting {
+            warning!(
+                self.logger,
+                "Already draining client streams; ignoring repeated request"
+            );
+            return;
+        }
+        self.drain_state = DrainState::Draining;
+        let open_streams = self.keys_and_addrs.len();
+        if open_streams == 0 {
+            info!(self.logger, "No client streams open; refusing new ones");
+            self.report_streams_drained(0)
+        } else {
+            info!(
+                self.logger,
+                "Draining {} open client stream(s) for up to {}s; refusing new ones",
+                open_streams,
+                msg.timeout.as_secs()
+            );
+            let _ =
+                self.notify_later_drain_deadline
+                    .notify_later(DrainDeadline {}, msg.timeout, ctx);
+        }
+    }
+
+    fn report_streams_drained(&mut self, abandoned_streams: usize) {
+        self.drain_state = DrainState::Drained;
+        self.out_subs("Neighborhood")
+            .shutdown_stage_complete
+            .try_send(ShutdownStageComplete::ClientStreamsDrained { abandoned_streams })
+            .expect("Neighborhood is dead")
+    }
+
+    fn refuse_new_stream(&self, peer_addr: SocketAddr) {
+        debug!(
+            self.logger,
+            "Refusing new client stream from {}: Node is shutting down", peer_addr
+        );
+        self.out_subs("Dispatcher")
+            .dispatcher
+            .try_send(TransmitDataMsg {
+                endpoint: Endpoint::Socket(peer_addr),
+                last_data: true,
+                sequence_number: Some(0),
+                data: vec![],
+            })
+            .expect("Dispatcher is dead")
     }
 
     fn make_payload(
@@ -863,14 +1072,28 @@ impl IBCDHelper for IBCDHelperReal {
         retire_stream_key: bool,
     ) -> Result<(), String> {
         let source_addr = msg.peer_addr;
-        if proxy.consuming_wallet_balance.is_none() && proxy.is_decentralized {
+        if proxy.is_decentralized
+            && (proxy.consuming_wallet_balance.is_none() || proxy.consuming_services_suspended)
+        {
             let protocol_pack = match from_ibcd(&msg) {
                 Err(e) => return Err(e),
                 Ok(pp) => pp,
             };
-            let data = protocol_pack
-                .server_impersonator()
-                .consuming_wallet_absent();
+            let (data, reason) = if proxy.consuming_wallet_balance.is_none() {
+                (
+                    protocol_pack
+                        .server_impersonator()
+                        .consuming_wallet_absent(),
+                    "missing consuming wallet",
+                )
+            } else {
+                (
+                    protocol_pack
+                        .server_impersonator()
+                        .consuming_wallet_insufficient_funds(),
+                    "consuming wallet low on funds",
+                )
+            };
             let msg = TransmitDataMsg {
                 endpoint: Endpoint::Socket(source_addr),
                 last_data: true,
@@ -882,7 +1105,7 @@ impl IBCDHelper for IBCDHelperReal {
                 .dispatcher
                 .try_send(msg)
                 .expect("Dispatcher is dead");
-            return Err("Browser request rejected due to missing consuming wallet".to_string());
+            return Err(format!("Browser request rejected due to {}", reason));
         }
         let stream_key = proxy.make_stream_key(&msg);
         let timestamp = msg.timestamp;
@@ -1035,6 +1258,7 @@ mod tests {
     use crate::sub_lib::hop::LiveHop;
     use crate::sub_lib::hopper::MessageType;
     use crate::sub_lib::neighborhood::ExpectedServices;
+    use crate::sub_lib::neighborhood::Hops;
     use crate::sub_lib::neighborhood::{ExpectedService, DEFAULT_RATE_PACK};
     use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
     use crate::sub_lib::proxy_server::ClientRequestPayload_0v1;
@@ -1049,6 +1273,7 @@ mod tests {
     use crate::test_utils::recorder::make_recorder;
     use crate::test_utils::recorder::peer_actors_builder;
     use crate::test_utils::recorder::Recorder;
+    use crate::test_utils::unshared_test_utils::notify_handlers::NotifyLaterHandleMock;
     use crate::test_utils::unshared_test_utils::prove_that_crash_request_handler_is_hooked_up;
     use crate::test_utils::zero_hop_route_response;
     use crate::test_utils::{alias_cryptde, rate_pack};
@@ -1087,6 +1312,8 @@ mod tests {
             add_return_route: recipient!(addr, AddReturnRouteMessage),
             add_route: recipient!(addr, AddRouteMessage),
             stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
+            shutdown_stage_complete: recipient!(addr, ShutdownStageComplete),
+            node_to_ui: recipient!(addr, NodeToUiMessage),
         }
     }
 
@@ -1754,6 +1981,264 @@ mod tests {
         );
     }
 
+    #[test]
+    fn proxy_server_refuses_http_request_while_consuming_services_are_suspended() {
+        init_test_logging();
+        let cryptde = main_cryptde();
+        let http_request = b"GET /index.html HTTP/1.1\r\nHost: nowhere.com\r\n\r\n";
+        let (hopper, _, hopper_log_arc) = make_recorder();
+        let (neighborhood, _, neighborhood_log_arc) = make_recorder();
+        let (dispatcher, _, dispatcher_log_arc) = make_recorder();
+        let socket_addr = SocketAddr::from_str("1.2.3.4:5678").unwrap();
+        let msg_from_dispatcher = InboundClientData {
+            timestamp: SystemTime::now(),
+            peer_addr: socket_addr.clone(),
+            reception_port: Some(HTTP_PORT),
+            sequence_number: Some(0),
+            last_data: true,
+            is_clandestine: false,
+            data: http_request.to_vec(),
+        };
+        let stream_key_factory = StreamKeyFactoryMock::new(); // can't make any stream keys; shouldn't have to
+        let system =
+            System::new("proxy_server_refuses_http_request_while_consuming_services_are_suspended");
+        let mut subject = ProxyServer::new(cryptde, alias_cryptde(), true, Some(0), false);
+        subject.stream_key_factory = Box::new(stream_key_factory);
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let mut peer_actors = peer_actors_builder()
+            .dispatcher(dispatcher)
+            .hopper(hopper)
+            .neighborhood(neighborhood)
+            .build();
+        peer_actors.proxy_server = ProxyServer::make_subs_from(&subject_addr);
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(ConsumingServicesStatus { suspended: true })
+            .unwrap();
+        subject_addr.try_send(msg_from_dispatcher).unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_log_arc.lock().unwrap();
+        assert!(neighborhood_recording.is_empty());
+        let hopper_recording = hopper_log_arc.lock().unwrap();
+        assert!(hopper_recording.is_empty());
+        let dispatcher_recording = dispatcher_log_arc.lock().unwrap();
+        let record = dispatcher_recording.get_record::<TransmitDataMsg>(0);
+        let server_impersonator = ServerImpersonatorHttp {};
+        assert_eq!(
+            record,
+            &TransmitDataMsg {
+                endpoint: Endpoint::Socket(socket_addr),
+                last_data: true,
+                sequence_number: Some(0),
+                data: server_impersonator.consuming_wallet_insufficient_funds(),
+            }
+        );
+        let tlh = TestLogHandler::new();
+        tlh.exists_log_containing(
+            "WARN: ProxyServer: Consuming wallet is low on funds; refusing browser requests until it's replenished",
+        );
+        tlh.exists_log_containing(
+            "ERROR: ProxyServer: Browser request rejected due to consuming wallet low on funds",
+        );
+    }
+
+    #[test]
+    fn consuming_services_status_lifts_suspension() {
+        init_test_logging();
+        let system = System::new("consuming_services_status_lifts_suspension");
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, Some(0), false);
+        subject.consuming_services_suspended = true;
+        subject.logger = Logger::new("consuming_services_status_lifts_suspension");
+        let subject_addr: Addr<ProxyServer> = subject.start();
+
+        subject_addr
+            .try_send(ConsumingServicesStatus { suspended: false })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        TestLogHandler::new().exists_log_containing(
+            "INFO: consuming_services_status_lifts_suspension: Consuming wallet has been replenished; accepting browser requests again",
+        );
+    }
+
+    #[test]
+    fn drain_client_streams_with_no_open_streams_reports_drained_immediately() {
+        let system =
+            System::new("drain_client_streams_with_no_open_streams_reports_drained_immediately");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, Some(0), false);
+        subject.notify_later_drain_deadline = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(DrainClientStreams {
+                timeout: Duration::from_secs(30),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::ClientStreamsDrained {
+                abandoned_streams: 0
+            }
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
+        assert!(notify_later_params_arc.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn drain_client_streams_schedules_deadline_and_refuses_new_streams() {
+        init_test_logging();
+        let system = System::new("drain_client_streams_schedules_deadline_and_refuses_new_streams");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let (dispatcher, _, dispatcher_recording_arc) = make_recorder();
+        let (hopper, _, hopper_recording_arc) = make_recorder();
+        let notify_later_params_arc = Arc::new(Mutex::new(vec![]));
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, Some(0), false);
+        subject.logger = Logger::new("drain_client_streams_schedules_deadline");
+        subject.notify_later_drain_deadline = Box::new(
+            NotifyLaterHandleMock::default().notify_later_params(&notify_later_params_arc),
+        );
+        let open_socket_addr = SocketAddr::from_str("2.3.4.5:6789").unwrap();
+        let open_stream_key = StreamKey::new(main_cryptde().public_key().clone(), open_socket_addr);
+        subject
+            .keys_and_addrs
+            .insert(open_stream_key, open_socket_addr);
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let peer_actors = peer_actors_builder()
+            .neighborhood(neighborhood)
+            .dispatcher(dispatcher)
+            .hopper(hopper)
+            .build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+        let new_socket_addr = SocketAddr::from_str("1.2.3.4:5678").unwrap();
+
+        subject_addr
+            .try_send(DrainClientStreams {
+                timeout: Duration::from_secs(30),
+            })
+            .unwrap();
+        subject_addr
+            .try_send(InboundClientData {
+                timestamp: SystemTime::now(),
+                peer_addr: new_socket_addr,
+                reception_port: Some(HTTP_PORT),
+                sequence_number: Some(0),
+                last_data: false,
+                is_clandestine: false,
+                data: b"GET /index.html HTTP/1.1\r\nHost: nowhere.com\r\n\r\n".to_vec(),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let dispatcher_recording = dispatcher_recording_arc.lock().unwrap();
+        assert_eq!(
+            dispatcher_recording.get_record::<TransmitDataMsg>(0),
+            &TransmitDataMsg {
+                endpoint: Endpoint::Socket(new_socket_addr),
+                last_data: true,
+                sequence_number: Some(0),
+                data: vec![],
+            }
+        );
+        assert!(hopper_recording_arc.lock().unwrap().is_empty());
+        assert!(neighborhood_recording_arc.lock().unwrap().is_empty());
+        assert_eq!(
+            *notify_later_params_arc.lock().unwrap(),
+            vec![(DrainDeadline {}, Duration::from_secs(30))]
+        );
+        TestLogHandler::new().exists_log_containing(
+            "INFO: drain_client_streams_schedules_deadline: Draining 1 open client stream(s) for up to 30s; refusing new ones",
+        );
+    }
+
+    #[test]
+    fn purging_last_stream_while_draining_reports_streams_drained() {
+        let system = System::new("purging_last_stream_while_draining_reports_streams_drained");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, Some(0), false);
+        subject.drain_state = DrainState::Draining;
+        let socket_addr = SocketAddr::from_str("2.3.4.5:6789").unwrap();
+        let stream_key = StreamKey::new(main_cryptde().public_key().clone(), socket_addr);
+        subject.keys_and_addrs.insert(stream_key, socket_addr);
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(StreamShutdownMsg {
+                peer_addr: socket_addr,
+                stream_type: RemovedStreamType::NonClandestine(NonClandestineAttributes {
+                    reception_port: HTTP_PORT,
+                    sequence_number: 1234,
+                }),
+                report_to_counterpart: false,
+            })
+            .unwrap();
+        subject_addr.try_send(DrainDeadline {}).unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::ClientStreamsDrained {
+                abandoned_streams: 0
+            }
+        );
+        assert_eq!(neighborhood_recording.len(), 1);
+    }
+
+    #[test]
+    fn drain_deadline_reports_abandoned_streams() {
+        init_test_logging();
+        let system = System::new("drain_deadline_reports_abandoned_streams");
+        let (neighborhood, _, neighborhood_recording_arc) = make_recorder();
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, Some(0), false);
+        subject.logger = Logger::new("drain_deadline_reports_abandoned_streams");
+        subject.drain_state = DrainState::Draining;
+        vec!["2.3.4.5:6789", "3.4.5.6:7890"]
+            .into_iter()
+            .map(|addr| SocketAddr::from_str(addr).unwrap())
+            .for_each(|socket_addr| {
+                subject.keys_and_addrs.insert(
+                    StreamKey::new(main_cryptde().public_key().clone(), socket_addr),
+                    socket_addr,
+                )
+            });
+        let subject_addr: Addr<ProxyServer> = subject.start();
+        let peer_actors = peer_actors_builder().neighborhood(neighborhood).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr.try_send(DrainDeadline {}).unwrap();
+
+        System::current().stop();
+        system.run();
+        let neighborhood_recording = neighborhood_recording_arc.lock().unwrap();
+        assert_eq!(
+            neighborhood_recording.get_record::<ShutdownStageComplete>(0),
+            &ShutdownStageComplete::ClientStreamsDrained {
+                abandoned_streams: 2
+            }
+        );
+        TestLogHandler::new().exists_log_containing(
+            "WARN: drain_deadline_reports_abandoned_streams: Drain timeout expired; abandoning 2 open client stream(s)",
+        );
+    }
+
     #[test]
     fn proxy_server_receives_http_request_with_no_consuming_wallet_in_zero_hop_mode_and_handles_normally(
     ) {
@@ -4749,6 +5234,55 @@ mod tests {
         assert!(!subject.tunneled_hosts.contains_key(&affected_stream_key));
     }
 
+    #[test]
+    fn min_hops_change_forgets_routes_that_are_too_short() {
+        init_test_logging();
+        let test_name = "min_hops_change_forgets_routes_that_are_too_short";
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, None, false);
+        subject.logger = Logger::new(test_name);
+        let make_route = |relays: usize| {
+            let mut over = vec![ExpectedService::Nothing];
+            (0..relays).for_each(|index| {
+                over.push(ExpectedService::Routing(
+                    PublicKey::new(&[index as u8]),
+                    make_wallet("relay"),
+                    rate_pack(101),
+                ))
+            });
+            over.push(ExpectedService::Exit(
+                PublicKey::new(&[9]),
+                make_wallet("exit"),
+                rate_pack(102),
+            ));
+            RouteQueryResponse {
+                route: Route { hops: vec![] },
+                expected_services: ExpectedServices::RoundTrip(over, vec![], 1234),
+            }
+        };
+        let short_stream_key = StreamKey::new(
+            main_cryptde().public_key().clone(),
+            SocketAddr::from_str("2.3.4.5:6789").unwrap(),
+        );
+        let long_stream_key = StreamKey::new(
+            main_cryptde().public_key().clone(),
+            SocketAddr::from_str("3.4.5.6:7890").unwrap(),
+        );
+        subject
+            .stream_key_routes
+            .insert(short_stream_key, make_route(1));
+        subject
+            .stream_key_routes
+            .insert(long_stream_key, make_route(2));
+
+        subject.handle_configuration_change(ConfigurationChange::MinHops(Hops::ThreeHops));
+
+        assert!(!subject.stream_key_routes.contains_key(&short_stream_key));
+        assert!(subject.stream_key_routes.contains_key(&long_stream_key));
+        TestLogHandler::new().exists_log_containing(&format!(
+            "INFO: {test_name}: Minimum hop count changed to 3; rerouting 1 open stream(s) with their next request"
+        ));
+    }
+
     #[test]
     fn handle_stream_shutdown_msg_logs_errors_from_handling_normal_client_data() {
         init_test_logging();
@@ -4955,6 +5489,47 @@ mod tests {
         system.run();
     }
 
+    #[test]
+    fn stream_status_request_reports_open_streams() {
+        let system = System::new("stream_status_request_reports_open_streams");
+        let (ui_gateway, _, ui_gateway_recording_arc) = make_recorder();
+        let mut subject = ProxyServer::new(main_cryptde(), alias_cryptde(), true, None, false);
+        let first_addr = SocketAddr::from_str("1.2.3.4:5678").unwrap();
+        let second_addr = SocketAddr::from_str("2.3.4.5:6789").unwrap();
+        let first_key = StreamKey::new(main_cryptde().public_key().clone(), first_addr);
+        let second_key = StreamKey::new(main_cryptde().public_key().clone(), second_addr);
+        subject.keys_and_addrs.insert(first_key, first_addr);
+        subject.keys_and_addrs.insert(second_key, second_addr);
+        subject
+            .tunneled_hosts
+            .insert(first_key, "booga.com".to_string());
+        let subject_addr = subject.start();
+        let peer_actors = peer_actors_builder().ui_gateway(ui_gateway).build();
+        subject_addr.try_send(BindMessage { peer_actors }).unwrap();
+
+        subject_addr
+            .try_send(NodeFromUiMessage {
+                client_id: 1234,
+                body: UiStreamStatusRequest {}.tmb(4321),
+            })
+            .unwrap();
+
+        System::current().stop();
+        system.run();
+        let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeToUiMessage>(0),
+            &NodeToUiMessage {
+                target: MessageTarget::ClientId(1234),
+                body: UiStreamStatusResponse {
+                    active_streams: 2,
+                    tunneled_streams: 1,
+                }
+                .tmb(4321),
+            }
+        );
+    }
+
     #[test]
     #[should_panic(
         expected = "panic message (processed with: node_lib::sub_lib::utils::crash_request_analyzer)"
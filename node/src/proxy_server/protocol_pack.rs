@@ -64,4 +64,5 @@ pub trait ServerImpersonator {
         server_name_opt: Option<String>,
     ) -> Vec<u8>;
     fn consuming_wallet_absent(&self) -> Vec<u8>;
+    fn consuming_wallet_insufficient_funds(&self) -> Vec<u8>;
 }
@@ -53,6 +53,18 @@ impl ServerImpersonator for ServerImpersonatorHttp {
             Set up a funded consuming wallet and try again.",
         )
     }
+
+    fn consuming_wallet_insufficient_funds(&self) -> Vec<u8> {
+        ServerImpersonatorHttp::make_error_response(
+            402,
+            "Consuming Wallet Low On Funds",
+            "Can't consume without funds to pay with",
+            "You're trying to consume routing and exit services from other Nodes, but your consuming \
+            wallet has dropped below its low-balance thresholds, and your Node might not be able to pay \
+            the bills you're about to incur. Add gas currency or MASQ tokens to your consuming wallet; \
+            your Node will notice within a few minutes and start serving you again.",
+        )
+    }
 }
 
 impl ServerImpersonatorHttp {
@@ -256,4 +268,22 @@ mod tests {
         );
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn consuming_wallet_insufficient_funds_response_produces_expected_error_page() {
+        let subject = ServerImpersonatorHttp {};
+
+        let result = subject.consuming_wallet_insufficient_funds();
+
+        let expected = ServerImpersonatorHttp::make_error_response(
+            402,
+            "Consuming Wallet Low On Funds",
+            "Can't consume without funds to pay with",
+            "You're trying to consume routing and exit services from other Nodes, but your consuming \
+            wallet has dropped below its low-balance thresholds, and your Node might not be able to pay \
+            the bills you're about to incur. Add gas currency or MASQ tokens to your consuming wallet; \
+            your Node will notice within a few minutes and start serving you again.",
+        );
+        assert_eq!(expected, result);
+    }
 }
@@ -20,6 +20,10 @@ impl ServerImpersonator for ServerImpersonatorTls {
     fn consuming_wallet_absent(&self) -> Vec<u8> {
         Vec::from(&TLS_INTERNAL_ERROR_ALERT[..])
     }
+
+    fn consuming_wallet_insufficient_funds(&self) -> Vec<u8> {
+        Vec::from(&TLS_INTERNAL_ERROR_ALERT[..])
+    }
 }
 
 const TLS_INTERNAL_ERROR_ALERT: [u8; 7] = [
@@ -94,4 +98,13 @@ mod tests {
 
         assert_eq!(Vec::from(&TLS_INTERNAL_ERROR_ALERT[..]), result);
     }
+
+    #[test]
+    fn consuming_wallet_insufficient_funds_produces_internal_error_alert() {
+        let subject = ServerImpersonatorTls {};
+
+        let result = subject.consuming_wallet_insufficient_funds();
+
+        assert_eq!(Vec::from(&TLS_INTERNAL_ERROR_ALERT[..]), result);
+    }
 }
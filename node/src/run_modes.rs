@@ -10,6 +10,7 @@ use crate::run_modes_factories::{
 use actix::System;
 use clap::Error;
 use futures::future::Future;
+use masq_lib::blockchains::custom_chains::load_custom_chains_from_environment;
 use masq_lib::command::StdStreams;
 use masq_lib::multi_config::MultiConfig;
 use masq_lib::shared_schema::{ConfiguratorError, ParamError};
@@ -42,6 +43,10 @@ impl RunModes {
     }
 
     pub fn go(&self, args: &[String], streams: &mut StdStreams<'_>) -> i32 {
+        if let Err(msg) = load_custom_chains_from_environment() {
+            short_writeln!(streams.stderr, "{}", msg);
+            return 1;
+        }
         let mode = match self.entrance_interview(args, streams) {
             Enter(mode) => mode,
             Leave(exit_code) => return exit_code,
@@ -9,7 +9,8 @@ use crate::run_modes_factories::{RunModeResult, ServerInitializer};
 use crate::sub_lib::socket_server::ConfiguredByPrivilege;
 use backtrace::Backtrace;
 use flexi_logger::{
-    Cleanup, Criterion, DeferredNow, Duplicate, LevelFilter, LogSpecBuilder, Logger, Naming, Record,
+    Cleanup, Criterion, DeferredNow, Duplicate, LevelFilter, LogSpecBuilder, LogSpecification,
+    Logger, Naming, ReconfigurationHandle, Record,
 };
 use futures::try_ready;
 use lazy_static::lazy_static;
@@ -136,6 +137,7 @@ impl<'a> GatheredParams<'a> {
 
 lazy_static! {
     pub static ref LOGFILE_NAME: Mutex<PathBuf> = Mutex::new(PathBuf::from("uninitialized"));
+    static ref LOG_RECONFIGURATION_HANDLE: Mutex<Option<ReconfigurationHandle>> = Mutex::new(None);
 }
 
 pub trait LoggerInitializerWrapper {
@@ -158,28 +160,25 @@ impl LoggerInitializerWrapper for LoggerInitializerWrapperReal {
         log_level: LevelFilter,
         discriminant_opt: Option<&str>,
     ) {
-        let mut logger = Logger::with(
-            LogSpecBuilder::new()
-                .default(log_level)
-                .module("tokio", LevelFilter::Off)
-                .module("mio", LevelFilter::Off)
-                .build(),
-        )
-        .log_to_file()
-        .directory(file_path.clone())
-        .print_message()
-        .duplicate_to_stderr(Duplicate::Info)
-        .suppress_timestamp()
-        .format(format_function)
-        .rotate(
-            Criterion::Size(100_000_000),
-            Naming::Numbers,
-            Cleanup::KeepZipFiles(50),
-        );
+        let mut logger = Logger::with(log_specification(log_level))
+            .log_to_file()
+            .directory(file_path.clone())
+            .print_message()
+            .duplicate_to_stderr(Duplicate::Info)
+            .suppress_timestamp()
+            .format(format_function)
+            .rotate(
+                Criterion::Size(100_000_000),
+                Naming::Numbers,
+                Cleanup::KeepZipFiles(50),
+            );
         if let Some(discriminant) = discriminant_opt {
             logger = logger.discriminant(discriminant);
         }
-        logger.start().expect("Logging subsystem failed to start");
+        let handle = logger.start().expect("Logging subsystem failed to start");
+        *(LOG_RECONFIGURATION_HANDLE
+            .lock()
+            .expect("Log reconfiguration handle is poisoned")) = Some(handle);
         let privilege_dropper = PrivilegeDropperReal::new();
         let logfile_name = file_path.join(format!(
             "MASQNode_{}rCURRENT.log",
@@ -223,6 +222,36 @@ impl LoggerInitializerWrapperReal {
     }
 }
 
+pub trait LogLevelSetter {
+    fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String>;
+}
+
+// Changes the level of the running logger without restarting the Node
+pub struct LogLevelSetterReal;
+
+impl LogLevelSetter for LogLevelSetterReal {
+    fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String> {
+        let mut handle_opt = LOG_RECONFIGURATION_HANDLE
+            .lock()
+            .expect("Log reconfiguration handle is poisoned");
+        match handle_opt.as_mut() {
+            Some(handle) => {
+                handle.set_new_spec(log_specification(log_level));
+                Ok(())
+            }
+            None => Err("Logging subsystem is not running".to_string()),
+        }
+    }
+}
+
+fn log_specification(log_level: LevelFilter) -> LogSpecification {
+    LogSpecBuilder::new()
+        .default(log_level)
+        .module("tokio", LevelFilter::Off)
+        .module("mio", LevelFilter::Off)
+        .build()
+}
+
 struct AltLocation {
     file: String,
     line: u32,
@@ -287,7 +316,7 @@ fn format_function(
 pub mod test_utils {
     use crate::bootstrapper::RealUser;
     use crate::privilege_drop::PrivilegeDropper;
-    use crate::server_initializer::LoggerInitializerWrapper;
+    use crate::server_initializer::{LogLevelSetter, LoggerInitializerWrapper};
     use log::LevelFilter;
     use std::cell::RefCell;
     use std::path::{Path, PathBuf};
@@ -394,6 +423,31 @@ pub mod test_utils {
             self
         }
     }
+
+    #[derive(Default)]
+    pub struct LogLevelSetterMock {
+        set_log_level_params: Arc<Mutex<Vec<LevelFilter>>>,
+        set_log_level_results: RefCell<Vec<Result<(), String>>>,
+    }
+
+    impl LogLevelSetter for LogLevelSetterMock {
+        fn set_log_level(&self, log_level: LevelFilter) -> Result<(), String> {
+            self.set_log_level_params.lock().unwrap().push(log_level);
+            self.set_log_level_results.borrow_mut().remove(0)
+        }
+    }
+
+    impl LogLevelSetterMock {
+        pub fn set_log_level_params(mut self, params: &Arc<Mutex<Vec<LevelFilter>>>) -> Self {
+            self.set_log_level_params = params.clone();
+            self
+        }
+
+        pub fn set_log_level_result(self, result: Result<(), String>) -> Self {
+            self.set_log_level_results.borrow_mut().push(result);
+            self
+        }
+    }
 }
 
 #[cfg(test)]
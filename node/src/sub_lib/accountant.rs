@@ -1,13 +1,16 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 use crate::accountant::database_access_objects::banned_dao::BannedDaoFactory;
+use crate::accountant::database_access_objects::ledger_dao::LedgerDaoFactory;
 use crate::accountant::database_access_objects::payable_dao::PayableDaoFactory;
 use crate::accountant::database_access_objects::pending_payable_dao::PendingPayableDaoFactory;
 use crate::accountant::database_access_objects::receivable_dao::ReceivableDaoFactory;
+use crate::accountant::database_access_objects::service_usage_dao::ServiceUsageDaoFactory;
 use crate::accountant::{
     checked_conversion, Accountant, ConsumingWalletBalancesAndQualifiedPayables, ReceivedPayments,
     ReportTransactionReceipts, ScanError, SentPayables,
 };
 use crate::blockchain::blockchain_bridge::PendingPayableFingerprintSeeds;
+use crate::sub_lib::configurator::{ConfigurationChangeMessage, NewEarningWalletMessage};
 use crate::sub_lib::peer_actors::{BindMessage, StartMessage};
 use crate::sub_lib::wallet::Wallet;
 use actix::Recipient;
@@ -72,6 +75,8 @@ pub struct DaoFactories {
     pub pending_payable_dao_factory: Box<dyn PendingPayableDaoFactory>,
     pub receivable_dao_factory: Box<dyn ReceivableDaoFactory>,
     pub banned_dao_factory: Box<dyn BannedDaoFactory>,
+    pub ledger_dao_factory: Box<dyn LedgerDaoFactory>,
+    pub service_usage_dao_factory: Box<dyn ServiceUsageDaoFactory>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -102,6 +107,9 @@ pub struct AccountantSubs {
     pub report_sent_payments: Recipient<SentPayables>,
     pub scan_errors: Recipient<ScanError>,
     pub ui_message_sub: Recipient<NodeFromUiMessage>,
+    pub new_earning_wallet_sub: Recipient<NewEarningWalletMessage>,
+    pub settle_before_shutdown_sub: Recipient<SettleBeforeShutdown>,
+    pub configuration_change_sub: Recipient<ConfigurationChangeMessage>,
 }
 
 impl Debug for AccountantSubs {
@@ -149,6 +157,10 @@ pub struct ReportServicesConsumedMessage {
     pub routing: Vec<RoutingServiceConsumed>,
 }
 
+// Sent by the Neighborhood during a graceful shutdown that asked for a final payable scan
+#[derive(Clone, PartialEq, Eq, Debug, Message)]
+pub struct SettleBeforeShutdown {}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct RoutingServiceConsumed {
     pub earning_wallet: Wallet,
@@ -170,6 +182,71 @@ pub struct FinancialStatistics {
     pub total_paid_receivable_wei: u128,
 }
 
+// How the delinquency scan treats a debtor whose debt has crossed the threshold curve
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub struct DelinquencyPolicy {
+    pub grace_period_sec: u64,
+    pub notify_debtors: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WarnedDebtor {
+    pub wallet: Wallet,
+    pub balance_wei: i128,
+    pub ban_deadline: SystemTime,
+}
+
+// Changes the delinquency scan has made to the ban list and the UIs haven't been told about yet
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct BanListChanges {
+    pub banned: Vec<Wallet>,
+    pub unbanned: Vec<Wallet>,
+    pub warned: Vec<WarnedDebtor>,
+}
+
+// An earning wallet that has been rotated out but is still watched for late payments
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RetiredEarningWallet {
+    pub wallet: Wallet,
+    pub watch_until: SystemTime,
+}
+
+// The wallets that receivable scans look for incoming payments to
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EarningWallets {
+    pub current: Wallet,
+    pub retired: Vec<RetiredEarningWallet>,
+}
+
+impl EarningWallets {
+    pub fn new(current: Wallet, retired: Vec<RetiredEarningWallet>) -> Self {
+        Self { current, retired }
+    }
+
+    pub fn rotate(&mut self, new_earning_wallet: Wallet, retired: RetiredEarningWallet) {
+        self.retired.retain(|existing| {
+            existing.wallet != new_earning_wallet && existing.wallet != retired.wallet
+        });
+        self.retired.push(retired);
+        self.current = new_earning_wallet;
+    }
+
+    pub fn drop_expired(&mut self, now: SystemTime) -> Vec<Wallet> {
+        let (expired, still_watched): (Vec<_>, Vec<_>) = self
+            .retired
+            .drain(..)
+            .partition(|retired| retired.watch_until <= now);
+        self.retired = still_watched;
+        expired.into_iter().map(|retired| retired.wallet).collect()
+    }
+
+    pub fn watched(&self) -> Vec<Wallet> {
+        let mut wallets = vec![self.current.clone()];
+        wallets.extend(self.retired.iter().map(|retired| retired.wallet.clone()));
+        wallets
+    }
+}
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum SignConversionError {
     U64(String),
@@ -197,18 +274,19 @@ mod tests {
     use crate::accountant::test_utils::AccountantBuilder;
     use crate::accountant::{checked_conversion, Accountant};
     use crate::sub_lib::accountant::{
-        AccountantSubsFactory, AccountantSubsFactoryReal, MessageIdGenerator,
-        MessageIdGeneratorReal, PaymentThresholds, ScanIntervals, DEFAULT_EARNING_WALLET,
-        DEFAULT_PAYMENT_THRESHOLDS, DEFAULT_SCAN_INTERVALS, MSG_ID_INCREMENTER,
-        TEMPORARY_CONSUMING_WALLET,
+        AccountantSubsFactory, AccountantSubsFactoryReal, EarningWallets, MessageIdGenerator,
+        MessageIdGeneratorReal, PaymentThresholds, RetiredEarningWallet, ScanIntervals,
+        DEFAULT_EARNING_WALLET, DEFAULT_PAYMENT_THRESHOLDS, DEFAULT_SCAN_INTERVALS,
+        MSG_ID_INCREMENTER, TEMPORARY_CONSUMING_WALLET,
     };
     use crate::sub_lib::wallet::Wallet;
+    use crate::test_utils::make_wallet;
     use crate::test_utils::recorder::{make_accountant_subs_from_recorder, Recorder};
     use actix::Actor;
     use std::str::FromStr;
     use std::sync::atomic::Ordering;
     use std::sync::Mutex;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime};
 
     static MSG_ID_GENERATOR_TEST_GUARD: Mutex<()> = Mutex::new(());
 
@@ -266,6 +344,88 @@ mod tests {
         assert_eq!(subs, Accountant::make_subs_from(&addr))
     }
 
+    #[test]
+    fn earning_wallets_rotation_retires_the_current_wallet() {
+        let now = SystemTime::now();
+        let older = RetiredEarningWallet {
+            wallet: make_wallet("older"),
+            watch_until: now + Duration::from_secs(100),
+        };
+        let mut subject = EarningWallets::new(make_wallet("old"), vec![older.clone()]);
+        let retired = RetiredEarningWallet {
+            wallet: make_wallet("old"),
+            watch_until: now + Duration::from_secs(1000),
+        };
+
+        subject.rotate(make_wallet("new"), retired.clone());
+
+        assert_eq!(
+            subject,
+            EarningWallets::new(make_wallet("new"), vec![older, retired])
+        );
+        assert_eq!(
+            subject.watched(),
+            vec![make_wallet("new"), make_wallet("older"), make_wallet("old")]
+        );
+    }
+
+    #[test]
+    fn earning_wallets_rotation_back_to_a_retired_wallet_stops_watching_it_as_retired() {
+        let now = SystemTime::now();
+        let mut subject = EarningWallets::new(
+            make_wallet("new"),
+            vec![RetiredEarningWallet {
+                wallet: make_wallet("old"),
+                watch_until: now + Duration::from_secs(100),
+            }],
+        );
+        let retired = RetiredEarningWallet {
+            wallet: make_wallet("new"),
+            watch_until: now + Duration::from_secs(1000),
+        };
+
+        subject.rotate(make_wallet("old"), retired.clone());
+
+        assert_eq!(
+            subject,
+            EarningWallets::new(make_wallet("old"), vec![retired])
+        );
+    }
+
+    #[test]
+    fn earning_wallets_drop_expired_retired_wallets() {
+        let now = SystemTime::now();
+        let still_watched = RetiredEarningWallet {
+            wallet: make_wallet("still watched"),
+            watch_until: now + Duration::from_secs(1),
+        };
+        let mut subject = EarningWallets::new(
+            make_wallet("current"),
+            vec![
+                RetiredEarningWallet {
+                    wallet: make_wallet("expired"),
+                    watch_until: now - Duration::from_secs(1),
+                },
+                still_watched.clone(),
+                RetiredEarningWallet {
+                    wallet: make_wallet("just expired"),
+                    watch_until: now,
+                },
+            ],
+        );
+
+        let result = subject.drop_expired(now);
+
+        assert_eq!(
+            result,
+            vec![make_wallet("expired"), make_wallet("just expired")]
+        );
+        assert_eq!(
+            subject,
+            EarningWallets::new(make_wallet("current"), vec![still_watched])
+        );
+    }
+
     #[test]
     fn msg_id_generator_increments_by_one_with_every_call() {
         let _guard = MSG_ID_GENERATOR_TEST_GUARD.lock().unwrap();
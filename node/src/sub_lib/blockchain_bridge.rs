@@ -10,13 +10,48 @@ use masq_lib::blockchains::chains::Chain;
 use masq_lib::ui_gateway::NodeFromUiMessage;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::str::FromStr;
 use web3::types::U256;
 
 #[derive(Clone, PartialEq, Eq, Debug, Default)]
 pub struct BlockchainBridgeConfig {
     pub blockchain_service_url_opt: Option<String>,
     pub chain: Chain,
+    // None means the chain's own confirmation depth
+    pub confirmation_depth_opt: Option<u64>,
+    pub external_signer_url_opt: Option<String>,
     pub gas_price: u64,
+    // None means the consuming wallet's balances aren't monitored
+    pub low_balance_thresholds_opt: Option<LowBalanceThresholds>,
+}
+
+// Below either of these balances, the consuming wallet is considered too poor to keep buying services
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct LowBalanceThresholds {
+    pub gas_gwei: u64,
+    pub masq_gwei: u64,
+}
+
+impl FromStr for LowBalanceThresholds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split('|')
+            .map(|segment| segment.parse::<u64>())
+            .collect::<Result<Vec<u64>, _>>()
+            .map_err(|_| format!("Thresholds must be numeric, not '{}'", s))?;
+        match values.as_slice() {
+            [gas_gwei, masq_gwei] => Ok(Self {
+                gas_gwei: *gas_gwei,
+                masq_gwei: *masq_gwei,
+            }),
+            _ => Err(format!(
+                "Supply exactly two thresholds, gas and MASQ, like 10000000|1000000000, not '{}'",
+                s
+            )),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Eq)]
@@ -67,9 +102,48 @@ pub struct ConsumingWalletBalances {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::test_utils::recorder::{make_blockchain_bridge_subs_from, Recorder};
     use actix::Actor;
 
+    #[test]
+    fn low_balance_thresholds_default() {
+        assert_eq!(
+            LowBalanceThresholds::default(),
+            LowBalanceThresholds {
+                gas_gwei: 0,
+                masq_gwei: 0
+            }
+        )
+    }
+
+    #[test]
+    fn balances_are_not_monitored_unless_thresholds_are_configured() {
+        assert_eq!(
+            BlockchainBridgeConfig::default().low_balance_thresholds_opt,
+            None
+        )
+    }
+
+    #[test]
+    fn low_balance_thresholds_can_be_parsed() {
+        assert_eq!(
+            LowBalanceThresholds::from_str("123|456"),
+            Ok(LowBalanceThresholds {
+                gas_gwei: 123,
+                masq_gwei: 456
+            })
+        );
+        assert_eq!(
+            LowBalanceThresholds::from_str("123|booga"),
+            Err("Thresholds must be numeric, not '123|booga'".to_string())
+        );
+        assert_eq!(
+            LowBalanceThresholds::from_str("123|456|789"),
+            Err("Supply exactly two thresholds, gas and MASQ, like 10000000|1000000000, not '123|456|789'".to_string())
+        );
+    }
+
     #[test]
     fn blockchain_bridge_subs_debug() {
         let recorder = Recorder::new().start();
@@ -1,16 +1,43 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 
+use crate::sub_lib::accountant::{PaymentThresholds, RetiredEarningWallet, ScanIntervals};
+use crate::sub_lib::neighborhood::{Hops, NodeDescriptor, RatePack};
 use crate::sub_lib::peer_actors::BindMessage;
+use crate::sub_lib::wallet::Wallet;
 use actix::Recipient;
 use masq_lib::ui_gateway::NodeFromUiMessage;
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::net::SocketAddr;
 
 #[derive(Debug, actix::Message, Clone, PartialEq, Eq)]
 pub struct NewPasswordMessage {
     pub new_password: String,
 }
 
+#[derive(Debug, actix::Message, Clone, PartialEq, Eq)]
+pub struct NewEarningWalletMessage {
+    pub new_earning_wallet: Wallet,
+    pub retired: RetiredEarningWallet,
+}
+
+// A runtime configuration change, sent to every actor that may own the parameter;
+// each actor applies the changes it cares about and ignores the rest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigurationChange {
+    DnsServers(Vec<SocketAddr>),
+    MinHops(Hops),
+    Neighbors(Vec<NodeDescriptor>),
+    PaymentThresholds(PaymentThresholds),
+    RatePack(RatePack),
+    ScanIntervals(ScanIntervals),
+}
+
+#[derive(Debug, actix::Message, Clone, PartialEq, Eq)]
+pub struct ConfigurationChangeMessage {
+    pub change: ConfigurationChange,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct ConfiguratorSubs {
     pub bind: Recipient<BindMessage>,
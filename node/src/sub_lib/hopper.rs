@@ -1,12 +1,13 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
 use crate::bootstrapper::CryptDEPair;
 use crate::neighborhood::gossip::Gossip_0v1;
+use crate::sub_lib::configurator::ConfigurationChangeMessage;
 use crate::sub_lib::cryptde::encodex;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::cryptde::CryptData;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::dispatcher::InboundClientData;
-use crate::sub_lib::neighborhood::GossipFailure_0v1;
+use crate::sub_lib::neighborhood::{DelinquencyNotice_0v1, GossipFailure_0v1};
 use crate::sub_lib::node_addr::NodeAddr;
 use crate::sub_lib::peer_actors::BindMessage;
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, DnsResolveFailure_0v1};
@@ -71,6 +72,7 @@ pub enum MessageType {
     ClientResponse(VersionedData<ClientResponsePayload_0v1>),
     Gossip(VersionedData<Gossip_0v1>),
     GossipFailure(VersionedData<GossipFailure_0v1>),
+    DelinquencyNotice(VersionedData<DelinquencyNotice_0v1>),
     DnsResolveFailed(VersionedData<DnsResolveFailure_0v1>),
 }
 
@@ -137,6 +139,7 @@ pub struct HopperSubs {
     pub from_hopper_client_no_lookup: Recipient<NoLookupIncipientCoresPackage>,
     pub from_dispatcher: Recipient<InboundClientData>,
     pub node_from_ui: Recipient<NodeFromUiMessage>,
+    pub configuration_change: Recipient<ConfigurationChangeMessage>,
 }
 
 impl Debug for HopperSubs {
@@ -168,6 +171,7 @@ mod tests {
             from_hopper_client_no_lookup: recipient!(recorder, NoLookupIncipientCoresPackage),
             from_dispatcher: recipient!(recorder, InboundClientData),
             node_from_ui: recipient!(recorder, NodeFromUiMessage),
+            configuration_change: recipient!(recorder, ConfigurationChangeMessage),
         };
 
         assert_eq!(format!("{:?}", subject), "HopperSubs");
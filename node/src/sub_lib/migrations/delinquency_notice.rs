@@ -0,0 +1,163 @@
+// Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+
+use crate::sub_lib::migrations::utils::value_to_type;
+use crate::sub_lib::neighborhood::DelinquencyNotice_0v1;
+use crate::sub_lib::versioned_data::Migrations;
+use crate::sub_lib::versioned_data::{MigrationError, StepError, VersionedData};
+use lazy_static::lazy_static;
+use serde_cbor::Value;
+use std::convert::TryFrom;
+
+lazy_static! {
+    pub static ref MIGRATIONS: Migrations = {
+        let current_version = masq_lib::constants::DELINQUENCY_NOTICE_CURRENT_VERSION;
+        let mut migrations = Migrations::new(current_version);
+
+        migrate_value!(dv!(0, 1), DelinquencyNotice_0v1, DelinquencyNoticeMF_0v1, {|value: serde_cbor::Value| {
+            DelinquencyNotice_0v1::try_from (&value)
+        }});
+        migrations.add_step (masq_lib::data_version::FUTURE_VERSION, dv!(0, 1), Box::new (DelinquencyNoticeMF_0v1{}));
+
+        // add more steps here
+
+        migrations
+    };
+}
+
+impl From<DelinquencyNotice_0v1> for VersionedData<DelinquencyNotice_0v1> {
+    fn from(data: DelinquencyNotice_0v1) -> Self {
+        VersionedData::new(&MIGRATIONS, &data)
+    }
+}
+
+impl TryFrom<VersionedData<DelinquencyNotice_0v1>> for DelinquencyNotice_0v1 {
+    type Error = MigrationError;
+
+    fn try_from(vd: VersionedData<DelinquencyNotice_0v1>) -> Result<Self, Self::Error> {
+        vd.extract(&MIGRATIONS)
+    }
+}
+
+impl TryFrom<&Value> for DelinquencyNotice_0v1 {
+    type Error = StepError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Map(map) => {
+                let mut balance_gwei_opt: Option<u64> = None;
+                let mut ban_timestamp_opt: Option<u64> = None;
+                map.keys().for_each(|k| {
+                    let v = map.get(k).expect("Disappeared");
+                    if let Value::Text(field_name) = k {
+                        match field_name.as_str() {
+                            "balance_gwei" => balance_gwei_opt = value_to_type::<u64>(v),
+                            "ban_timestamp" => ban_timestamp_opt = value_to_type::<u64>(v),
+                            _ => (),
+                        }
+                    }
+                });
+                let mut missing_fields: Vec<&str> = vec![];
+                fn check_field<'a, T>(
+                    missing_fields: &mut Vec<&'a str>,
+                    name: &'a str,
+                    field: &Option<T>,
+                ) {
+                    if field.is_none() {
+                        missing_fields.push(name)
+                    }
+                }
+                check_field(&mut missing_fields, "balance_gwei", &balance_gwei_opt);
+                check_field(&mut missing_fields, "ban_timestamp", &ban_timestamp_opt);
+                if !missing_fields.is_empty() {
+                    // Unlike our own payloads, a notice comes from a Node we may know nothing about
+                    return Err(StepError::SemanticError(format!(
+                        "DelinquencyNotice is missing fields: {:?}",
+                        missing_fields
+                    )));
+                }
+                Ok(DelinquencyNotice_0v1 {
+                    balance_gwei: balance_gwei_opt.expect("balance_gwei disappeared"),
+                    ban_timestamp: ban_timestamp_opt.expect("ban_timestamp disappeared"),
+                })
+            }
+            _ => Err(StepError::SemanticError(format!(
+                "Expected Value::Map; found {:?}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use masq_lib::data_version::DataVersion;
+    use serde_derive::{Deserialize, Serialize};
+
+    #[test]
+    fn can_migrate_from_the_future() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFutureDN {
+            pub balance_gwei: u64,
+            pub ban_timestamp: u64,
+            pub another_field: String,
+        }
+        let expected_notice = DelinquencyNotice_0v1 {
+            balance_gwei: 123_456,
+            ban_timestamp: 1_700_000_000,
+        };
+        let future_notice = ExampleFutureDN {
+            balance_gwei: expected_notice.balance_gwei,
+            ban_timestamp: expected_notice.ban_timestamp,
+            another_field: "Pay up".to_string(),
+        };
+        let future_migrations = Migrations::new(DataVersion::new(4095, 4095));
+        let serialized =
+            serde_cbor::ser::to_vec(&VersionedData::new(&future_migrations, &future_notice))
+                .unwrap();
+        let future_vd =
+            serde_cbor::de::from_slice::<VersionedData<DelinquencyNotice_0v1>>(&serialized)
+                .unwrap();
+
+        let actual_notice = DelinquencyNotice_0v1::try_from(future_vd).unwrap();
+
+        assert_eq!(actual_notice, expected_notice);
+    }
+
+    #[test]
+    fn cannot_migrate_from_the_future_without_the_fields_we_need() {
+        #[derive(Serialize, Deserialize)]
+        struct ExampleFutureDN {
+            pub balance_gwei: u64,
+            pub another_field: String,
+        }
+        let future_notice = ExampleFutureDN {
+            balance_gwei: 123_456,
+            another_field: "Pay up".to_string(),
+        };
+        let value = serde_cbor::value::to_value(&future_notice).unwrap();
+
+        let result = DelinquencyNotice_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "DelinquencyNotice is missing fields: [\"ban_timestamp\"]".to_string()
+            ))
+        )
+    }
+
+    #[test]
+    fn cannot_migrate_from_value_other_than_map() {
+        let value = Value::Bool(true);
+
+        let result = DelinquencyNotice_0v1::try_from(&value);
+
+        assert_eq!(
+            result,
+            Err(StepError::SemanticError(
+                "Expected Value::Map; found Bool(true)".to_string()
+            ))
+        )
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod client_request_payload;
 pub mod client_response_payload;
+pub mod delinquency_notice;
 pub mod dns_resolve_failure;
 pub mod gossip;
 pub mod gossip_failure;
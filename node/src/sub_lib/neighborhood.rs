@@ -4,7 +4,9 @@ use crate::neighborhood::gossip::Gossip_0v1;
 use crate::neighborhood::node_record::NodeRecord;
 use crate::neighborhood::overall_connection_status::ConnectionProgress;
 use crate::neighborhood::Neighborhood;
-use crate::sub_lib::configurator::NewPasswordMessage;
+use crate::sub_lib::configurator::{
+    ConfigurationChangeMessage, NewEarningWalletMessage, NewPasswordMessage,
+};
 use crate::sub_lib::cryptde::{CryptDE, PublicKey};
 use crate::sub_lib::cryptde_real::CryptDEReal;
 use crate::sub_lib::dispatcher::{Component, StreamShutdownMsg};
@@ -32,9 +34,10 @@ use std::convert::TryFrom;
 use std::fmt::{Debug, Display, Formatter};
 use std::net::IpAddr;
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 const ASK_ABOUT_GOSSIP_INTERVAL: Duration = Duration::from_secs(10);
+const FINISH_GRACEFUL_SHUTDOWN_DELAY: Duration = Duration::from_millis(500);
 
 pub const DEFAULT_RATE_PACK: RatePack = RatePack {
     routing_byte_rate: 172_300_000,
@@ -415,13 +418,20 @@ pub struct NeighborhoodSubs {
     pub update_node_record_metadata: Recipient<NodeRecordMetadataMessage>,
     pub from_hopper: Recipient<ExpiredCoresPackage<Gossip_0v1>>,
     pub gossip_failure: Recipient<ExpiredCoresPackage<GossipFailure_0v1>>,
+    pub delinquency_notice: Recipient<ExpiredCoresPackage<DelinquencyNotice_0v1>>,
+    pub notice_to_debtor_sub: Recipient<NoticeToDebtor>,
+    pub paying_wallet_sighting_sub: Recipient<PayingWalletSighting>,
+    pub routing_payer_sighting_sub: Recipient<RoutingPayerSighting>,
     pub dispatcher_node_query: Recipient<DispatcherNodeQueryMessage>,
     pub remove_neighbor: Recipient<RemoveNeighborMessage>,
     pub stream_shutdown_sub: Recipient<StreamShutdownMsg>,
     pub set_consuming_wallet_sub: Recipient<SetConsumingWalletMessage>,
     pub from_ui_message_sub: Recipient<NodeFromUiMessage>,
     pub new_password_sub: Recipient<NewPasswordMessage>,
+    pub new_earning_wallet_sub: Recipient<NewEarningWalletMessage>,
     pub connection_progress_sub: Recipient<ConnectionProgressMessage>,
+    pub shutdown_stage_complete_sub: Recipient<ShutdownStageComplete>,
+    pub configuration_change_sub: Recipient<ConfigurationChangeMessage>,
 }
 
 impl Debug for NeighborhoodSubs {
@@ -538,6 +548,16 @@ pub struct AskAboutDebutGossipMessage {
     pub prev_connection_progress: ConnectionProgress,
 }
 
+// Reported to the Neighborhood by the actors doing the work of a graceful shutdown
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub enum ShutdownStageComplete {
+    ClientStreamsDrained { abandoned_streams: usize },
+    DebtsSettled,
+}
+
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct FinishGracefulShutdown {}
+
 #[derive(Clone, Debug, Message, PartialEq, Eq)]
 pub struct NodeRecordMetadataMessage {
     pub public_key: PublicKey,
@@ -572,6 +592,38 @@ impl fmt::Display for GossipFailure_0v1 {
     }
 }
 
+// Sent by a creditor Node to a debtor Node whose debt it has found delinquent, ahead of the ban
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(non_camel_case_types)]
+pub struct DelinquencyNotice_0v1 {
+    pub balance_gwei: u64,
+    pub ban_timestamp: u64,
+}
+
+// From the Accountant: tell the Node that owns this wallet that it's been warned of a ban
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct NoticeToDebtor {
+    pub wallet: Wallet,
+    pub balance_wei: i128,
+    pub ban_deadline: SystemTime,
+}
+
+// From the ProxyClient: the Node with this public key pays for our exit services from this wallet
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct PayingWalletSighting {
+    pub paying_wallet: Wallet,
+    pub public_key: PublicKey,
+}
+
+// From the RoutingService: the Node at this IP address handed us a package to route that is paid
+// for from this wallet. The sender only owns the wallet if it originated the route, so this is a
+// weaker hint than a PayingWalletSighting
+#[derive(Clone, Debug, Message, PartialEq, Eq)]
+pub struct RoutingPayerSighting {
+    pub paying_wallet: Wallet,
+    pub sender_ip: IpAddr,
+}
+
 // This metadata is only passed from Neighborhood to GossipHandler
 pub struct NeighborhoodMetadata {
     pub connection_progress_peers: Vec<IpAddr>,
@@ -583,6 +635,9 @@ pub struct NeighborhoodTools {
     pub notify_later_ask_about_gossip:
         Box<dyn NotifyLaterHandle<AskAboutDebutGossipMessage, Neighborhood>>,
     pub ask_about_gossip_interval: Duration,
+    pub notify_later_finish_graceful_shutdown:
+        Box<dyn NotifyLaterHandle<FinishGracefulShutdown, Neighborhood>>,
+    pub finish_graceful_shutdown_delay: Duration,
 }
 
 impl Default for NeighborhoodTools {
@@ -590,6 +645,8 @@ impl Default for NeighborhoodTools {
         Self {
             notify_later_ask_about_gossip: Box::new(NotifyLaterHandleReal::new()),
             ask_about_gossip_interval: ASK_ABOUT_GOSSIP_INTERVAL,
+            notify_later_finish_graceful_shutdown: Box::new(NotifyLaterHandleReal::new()),
+            finish_graceful_shutdown_delay: FINISH_GRACEFUL_SHUTDOWN_DELAY,
         }
     }
 }
@@ -628,6 +685,7 @@ mod tests {
             }
         );
         assert_eq!(ASK_ABOUT_GOSSIP_INTERVAL, Duration::from_secs(10));
+        assert_eq!(FINISH_GRACEFUL_SHUTDOWN_DELAY, Duration::from_millis(500));
     }
 
     pub fn rate_pack(base_rate: u64) -> RatePack {
@@ -651,13 +709,20 @@ mod tests {
             update_node_record_metadata: recipient!(recorder, NodeRecordMetadataMessage),
             from_hopper: recipient!(recorder, ExpiredCoresPackage<Gossip_0v1>),
             gossip_failure: recipient!(recorder, ExpiredCoresPackage<GossipFailure_0v1>),
+            delinquency_notice: recipient!(recorder, ExpiredCoresPackage<DelinquencyNotice_0v1>),
+            notice_to_debtor_sub: recipient!(recorder, NoticeToDebtor),
+            paying_wallet_sighting_sub: recipient!(recorder, PayingWalletSighting),
+            routing_payer_sighting_sub: recipient!(recorder, RoutingPayerSighting),
             dispatcher_node_query: recipient!(recorder, DispatcherNodeQueryMessage),
             remove_neighbor: recipient!(recorder, RemoveNeighborMessage),
             stream_shutdown_sub: recipient!(recorder, StreamShutdownMsg),
             set_consuming_wallet_sub: recipient!(recorder, SetConsumingWalletMessage),
             from_ui_message_sub: recipient!(recorder, NodeFromUiMessage),
             new_password_sub: recipient!(recorder, NewPasswordMessage),
+            new_earning_wallet_sub: recipient!(recorder, NewEarningWalletMessage),
             connection_progress_sub: recipient!(recorder, ConnectionProgressMessage),
+            shutdown_stage_complete_sub: recipient!(recorder, ShutdownStageComplete),
+            configuration_change_sub: recipient!(recorder, ConfigurationChangeMessage),
         };
 
         assert_eq!(format!("{:?}", subject), "NeighborhoodSubs");
@@ -1245,6 +1310,15 @@ mod tests {
             .downcast_ref::<NotifyLaterHandleReal<AskAboutDebutGossipMessage>>()
             .unwrap();
         assert_eq!(subject.ask_about_gossip_interval, Duration::from_secs(10));
+        subject
+            .notify_later_finish_graceful_shutdown
+            .as_any()
+            .downcast_ref::<NotifyLaterHandleReal<FinishGracefulShutdown>>()
+            .unwrap();
+        assert_eq!(
+            subject.finish_graceful_shutdown_delay,
+            Duration::from_millis(500)
+        );
     }
 
     #[test]
@@ -1,4 +1,5 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::sub_lib::configurator::ConfigurationChangeMessage;
 use crate::sub_lib::cryptde::CryptDE;
 use crate::sub_lib::hopper::{ExpiredCoresPackage, MessageType};
 use crate::sub_lib::peer_actors::BindMessage;
@@ -72,6 +73,7 @@ pub struct ProxyClientSubs {
     pub from_hopper: Recipient<ExpiredCoresPackage<ClientRequestPayload_0v1>>,
     pub inbound_server_data: Recipient<InboundServerData>,
     pub dns_resolve_failed: Recipient<DnsResolveFailure_0v1>,
+    pub configuration_change: Recipient<ConfigurationChangeMessage>,
     pub node_from_ui: Recipient<NodeFromUiMessage>,
 }
 
@@ -139,6 +141,7 @@ mod tests {
             from_hopper: recipient!(recorder, ExpiredCoresPackage<ClientRequestPayload_0v1>),
             inbound_server_data: recipient!(recorder, InboundServerData),
             dns_resolve_failed: recipient!(recorder, DnsResolveFailure_0v1),
+            configuration_change: recipient!(recorder, ConfigurationChangeMessage),
             node_from_ui: recipient!(recorder, NodeFromUiMessage),
         };
 
@@ -1,4 +1,5 @@
 // Copyright (c) 2019, MASQ (https://masq.ai) and/or its affiliates. All rights reserved.
+use crate::sub_lib::configurator::ConfigurationChangeMessage;
 use crate::sub_lib::cryptde::PublicKey;
 use crate::sub_lib::data_version::DataVersion;
 use crate::sub_lib::dispatcher::InboundClientData;
@@ -16,6 +17,7 @@ use actix::Recipient;
 use masq_lib::ui_gateway::NodeFromUiMessage;
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::time::Duration;
 
 pub const DEFAULT_MINIMUM_HOP_COUNT: usize = 3;
 
@@ -69,6 +71,19 @@ pub struct AddRouteMessage {
     pub route: RouteQueryResponse,
 }
 
+// Sent when the consuming wallet drops below, or climbs back above, its low-balance thresholds
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConsumingServicesStatus {
+    pub suspended: bool,
+}
+
+// Sent by the Neighborhood at the start of a graceful shutdown: stop accepting new client
+// streams and report back once the open ones have finished or the timeout has expired
+#[derive(Message, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrainClientStreams {
+    pub timeout: Duration,
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct ProxyServerSubs {
     // ProxyServer will handle these messages:
@@ -80,6 +95,9 @@ pub struct ProxyServerSubs {
     pub add_route: Recipient<AddRouteMessage>,
     pub stream_shutdown_sub: Recipient<StreamShutdownMsg>,
     pub set_consuming_wallet_sub: Recipient<SetConsumingWalletMessage>,
+    pub consuming_services_status: Recipient<ConsumingServicesStatus>,
+    pub drain_client_streams: Recipient<DrainClientStreams>,
+    pub configuration_change: Recipient<ConfigurationChangeMessage>,
     pub node_from_ui: Recipient<NodeFromUiMessage>,
 }
 
@@ -112,6 +130,9 @@ mod tests {
             add_route: recipient!(recorder, AddRouteMessage),
             stream_shutdown_sub: recipient!(recorder, StreamShutdownMsg),
             set_consuming_wallet_sub: recipient!(recorder, SetConsumingWalletMessage),
+            consuming_services_status: recipient!(recorder, ConsumingServicesStatus),
+            drain_client_streams: recipient!(recorder, DrainClientStreams),
+            configuration_change: recipient!(recorder, ConfigurationChangeMessage),
             node_from_ui: recipient!(recorder, NodeFromUiMessage),
         };
 
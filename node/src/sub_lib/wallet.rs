@@ -136,6 +136,10 @@ impl Wallet {
         format!("{:#x}", self.address())
     }
 
+    pub fn can_sign(&self) -> bool {
+        matches!(self.kind, WalletKind::SecretKey(_))
+    }
+
     pub fn sign(&self, msg: &dyn AsRef<[u8]>) -> Result<Signature, WalletError> {
         match self.kind {
             WalletKind::SecretKey(ref key_provider) => key_provider
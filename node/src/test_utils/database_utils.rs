@@ -4,7 +4,7 @@
 
 use crate::accountant::database_access_objects::dao_utils::VigilantRusqliteFlatten;
 use crate::database::connection_wrapper::ConnectionWrapper;
-use crate::database::db_initializer::ExternalData;
+use crate::database::db_initializer::{ExternalData, VERSION_0_SCHEMA};
 
 use crate::database::db_migrations::db_migrator::DbMigrator;
 use masq_lib::logger::Logger;
@@ -12,9 +12,7 @@ use masq_lib::test_utils::utils::TEST_DEFAULT_CHAIN;
 use masq_lib::utils::NeighborhoodModeLight;
 use rusqlite::{Connection, Error};
 use std::cell::RefCell;
-use std::env::current_dir;
-use std::fs::{remove_file, File};
-use std::io::Read;
+use std::fs::remove_file;
 use std::iter::once;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
@@ -26,15 +24,7 @@ pub fn bring_db_0_back_to_life_and_return_connection(db_path: &Path) -> Connecti
         _ => (),
     };
     let conn = Connection::open(&db_path).unwrap();
-    let file_path = current_dir()
-        .unwrap()
-        .join("src")
-        .join("test_utils")
-        .join("database_version_0_sql.txt");
-    let mut file = File::open(file_path).unwrap();
-    let mut buffer = String::new();
-    file.read_to_string(&mut buffer).unwrap();
-    buffer.lines().for_each(|stm| {
+    VERSION_0_SCHEMA.lines().for_each(|stm| {
         conn.execute(stm, []).unwrap();
     });
     conn
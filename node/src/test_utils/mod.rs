@@ -676,6 +676,7 @@ pub mod unshared_test_utils {
         persistent_config_mock
             .earning_wallet_address_result(Ok(None))
             .earning_wallet_result(Ok(None))
+            .retired_earning_wallets_result(Ok(vec![]))
             .consuming_wallet_private_key_result(Ok(None))
             .consuming_wallet_result(Ok(None))
             .past_neighbors_result(Ok(None))
@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use crate::db_config::persistent_configuration::{PersistentConfigError, PersistentConfiguration};
-use crate::sub_lib::accountant::{PaymentThresholds, ScanIntervals};
+use crate::sub_lib::accountant::{PaymentThresholds, RetiredEarningWallet, ScanIntervals};
 use crate::sub_lib::neighborhood::{NodeDescriptor, RatePack};
 use crate::sub_lib::wallet::Wallet;
 use crate::test_utils::unshared_test_utils::arbitrary_id_stamp::ArbitraryIdStamp;
@@ -12,6 +12,7 @@ use masq_lib::utils::AutomapProtocol;
 use masq_lib::utils::NeighborhoodModeLight;
 use std::cell::RefCell;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 #[allow(clippy::type_complexity)]
 #[derive(Clone, Default)]
@@ -39,6 +40,11 @@ pub struct PersistentConfigurationMock {
         RefCell<Vec<Result<Option<String>, PersistentConfigError>>>,
     earning_wallet_results: RefCell<Vec<Result<Option<Wallet>, PersistentConfigError>>>,
     earning_wallet_address_results: RefCell<Vec<Result<Option<String>, PersistentConfigError>>>,
+    retired_earning_wallets_results:
+        RefCell<Vec<Result<Vec<RetiredEarningWallet>, PersistentConfigError>>>,
+    rotate_earning_wallet_params: Arc<Mutex<Vec<(String, SystemTime)>>>,
+    rotate_earning_wallet_results:
+        RefCell<Vec<Result<RetiredEarningWallet, PersistentConfigError>>>,
     set_wallet_info_params: Arc<Mutex<Vec<(String, String, String)>>>,
     set_wallet_info_results: RefCell<Vec<Result<(), PersistentConfigError>>>,
     mapping_protocol_results: RefCell<Vec<Result<Option<AutomapProtocol>, PersistentConfigError>>>,
@@ -151,6 +157,22 @@ impl PersistentConfiguration for PersistentConfigurationMock {
         Self::result_from(&self.earning_wallet_address_results)
     }
 
+    fn retired_earning_wallets(&self) -> Result<Vec<RetiredEarningWallet>, PersistentConfigError> {
+        Self::result_from(&self.retired_earning_wallets_results)
+    }
+
+    fn rotate_earning_wallet(
+        &mut self,
+        new_earning_wallet_address: &str,
+        watch_until: SystemTime,
+    ) -> Result<RetiredEarningWallet, PersistentConfigError> {
+        self.rotate_earning_wallet_params
+            .lock()
+            .unwrap()
+            .push((new_earning_wallet_address.to_string(), watch_until));
+        self.rotate_earning_wallet_results.borrow_mut().remove(0)
+    }
+
     fn gas_price(&self) -> Result<u64, PersistentConfigError> {
         Self::result_from(&self.gas_price_results)
     }
@@ -515,6 +537,32 @@ impl PersistentConfigurationMock {
         self
     }
 
+    pub fn retired_earning_wallets_result(
+        self,
+        result: Result<Vec<RetiredEarningWallet>, PersistentConfigError>,
+    ) -> PersistentConfigurationMock {
+        self.retired_earning_wallets_results
+            .borrow_mut()
+            .push(result);
+        self
+    }
+
+    pub fn rotate_earning_wallet_params(
+        mut self,
+        params: &Arc<Mutex<Vec<(String, SystemTime)>>>,
+    ) -> PersistentConfigurationMock {
+        self.rotate_earning_wallet_params = params.clone();
+        self
+    }
+
+    pub fn rotate_earning_wallet_result(
+        self,
+        result: Result<RetiredEarningWallet, PersistentConfigError>,
+    ) -> PersistentConfigurationMock {
+        self.rotate_earning_wallet_results.borrow_mut().push(result);
+        self
+    }
+
     pub fn start_block_params(mut self, params: &Arc<Mutex<Vec<()>>>) -> Self {
         self.start_block_params = params.clone();
         self
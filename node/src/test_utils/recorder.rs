@@ -15,9 +15,12 @@ use crate::sub_lib::accountant::AccountantSubs;
 use crate::sub_lib::accountant::ReportExitServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportRoutingServiceProvidedMessage;
 use crate::sub_lib::accountant::ReportServicesConsumedMessage;
+use crate::sub_lib::accountant::SettleBeforeShutdown;
 use crate::sub_lib::blockchain_bridge::ReportAccountsPayable;
 use crate::sub_lib::blockchain_bridge::{BlockchainBridgeSubs, RequestBalancesToPayPayables};
-use crate::sub_lib::configurator::{ConfiguratorSubs, NewPasswordMessage};
+use crate::sub_lib::configurator::{
+    ConfigurationChangeMessage, ConfiguratorSubs, NewEarningWalletMessage, NewPasswordMessage,
+};
 use crate::sub_lib::dispatcher::InboundClientData;
 use crate::sub_lib::dispatcher::{DispatcherSubs, StreamShutdownMsg};
 use crate::sub_lib::hopper::IncipientCoresPackage;
@@ -31,14 +34,19 @@ use crate::sub_lib::neighborhood::NodeRecordMetadataMessage;
 use crate::sub_lib::neighborhood::RemoveNeighborMessage;
 use crate::sub_lib::neighborhood::RouteQueryMessage;
 use crate::sub_lib::neighborhood::RouteQueryResponse;
-use crate::sub_lib::neighborhood::{DispatcherNodeQueryMessage, GossipFailure_0v1};
+use crate::sub_lib::neighborhood::ShutdownStageComplete;
+use crate::sub_lib::neighborhood::{
+    DelinquencyNotice_0v1, DispatcherNodeQueryMessage, GossipFailure_0v1, NoticeToDebtor,
+    PayingWalletSighting, RoutingPayerSighting,
+};
 use crate::sub_lib::peer_actors::PeerActors;
 use crate::sub_lib::peer_actors::{BindMessage, NewPublicIp, StartMessage};
 use crate::sub_lib::proxy_client::{ClientResponsePayload_0v1, InboundServerData};
 use crate::sub_lib::proxy_client::{DnsResolveFailure_0v1, ProxyClientSubs};
 use crate::sub_lib::proxy_server::ProxyServerSubs;
 use crate::sub_lib::proxy_server::{
-    AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1,
+    AddReturnRouteMessage, AddRouteMessage, ClientRequestPayload_0v1, ConsumingServicesStatus,
+    DrainClientStreams,
 };
 use crate::sub_lib::set_consuming_wallet_message::SetConsumingWalletMessage;
 use crate::sub_lib::stream_handler_pool::DispatcherNodeQueryResponse;
@@ -98,13 +106,16 @@ recorder_message_handler!(AddReturnRouteMessage);
 recorder_message_handler!(AddRouteMessage);
 recorder_message_handler!(AddStreamMsg);
 recorder_message_handler!(BindMessage);
+recorder_message_handler!(ConfigurationChangeMessage);
 recorder_message_handler!(CrashNotification);
 recorder_message_handler!(DaemonBindMessage);
 recorder_message_handler!(DispatcherNodeQueryMessage);
 recorder_message_handler!(DispatcherNodeQueryResponse);
 recorder_message_handler!(DnsResolveFailure_0v1);
+recorder_message_handler!(DrainClientStreams);
 recorder_message_handler!(ExpiredCoresPackage<ClientRequestPayload_0v1>);
 recorder_message_handler!(ExpiredCoresPackage<ClientResponsePayload_0v1>);
+recorder_message_handler!(ExpiredCoresPackage<DelinquencyNotice_0v1>);
 recorder_message_handler!(ExpiredCoresPackage<DnsResolveFailure_0v1>);
 recorder_message_handler!(ExpiredCoresPackage<Gossip_0v1>);
 recorder_message_handler!(ExpiredCoresPackage<GossipFailure_0v1>);
@@ -112,23 +123,30 @@ recorder_message_handler!(ExpiredCoresPackage<MessageType>);
 recorder_message_handler!(InboundClientData);
 recorder_message_handler!(InboundServerData);
 recorder_message_handler!(IncipientCoresPackage);
+recorder_message_handler!(NewEarningWalletMessage);
 recorder_message_handler!(NewPasswordMessage);
 recorder_message_handler!(NewPublicIp);
 recorder_message_handler!(NodeFromUiMessage);
 recorder_message_handler!(NodeToUiMessage);
 recorder_message_handler!(NodeRecordMetadataMessage);
 recorder_message_handler!(NoLookupIncipientCoresPackage);
+recorder_message_handler!(NoticeToDebtor);
+recorder_message_handler!(PayingWalletSighting);
 recorder_message_handler!(PoolBindMessage);
 recorder_message_handler!(ReceivedPayments);
 recorder_message_handler!(RemoveNeighborMessage);
 recorder_message_handler!(RemoveStreamMsg);
+recorder_message_handler!(RoutingPayerSighting);
 recorder_message_handler!(ReportServicesConsumedMessage);
 recorder_message_handler!(ReportExitServiceProvidedMessage);
 recorder_message_handler!(ReportRoutingServiceProvidedMessage);
 recorder_message_handler!(ScanError);
+recorder_message_handler!(ConsumingServicesStatus);
 recorder_message_handler!(ConsumingWalletBalancesAndQualifiedPayables);
 recorder_message_handler!(SentPayables);
 recorder_message_handler!(SetConsumingWalletMessage);
+recorder_message_handler!(SettleBeforeShutdown);
+recorder_message_handler!(ShutdownStageComplete);
 recorder_message_handler!(RequestBalancesToPayPayables);
 recorder_message_handler!(StartMessage);
 recorder_message_handler!(StreamShutdownMsg);
@@ -345,6 +363,9 @@ pub fn make_proxy_server_subs_from(addr: &Addr<Recorder>) -> ProxyServerSubs {
         add_route: recipient!(addr, AddRouteMessage),
         stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
         set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
+        consuming_services_status: recipient!(addr, ConsumingServicesStatus),
+        drain_client_streams: recipient!(addr, DrainClientStreams),
+        configuration_change: recipient!(addr, ConfigurationChangeMessage),
         node_from_ui: recipient!(addr, NodeFromUiMessage),
     }
 }
@@ -367,6 +388,7 @@ pub fn make_hopper_subs_from(addr: &Addr<Recorder>) -> HopperSubs {
         from_hopper_client_no_lookup: recipient!(addr, NoLookupIncipientCoresPackage),
         from_dispatcher: recipient!(addr, InboundClientData),
         node_from_ui: recipient!(addr, NodeFromUiMessage),
+        configuration_change: recipient!(addr, ConfigurationChangeMessage),
     }
 }
 
@@ -376,6 +398,7 @@ pub fn make_proxy_client_subs_from(addr: &Addr<Recorder>) -> ProxyClientSubs {
         from_hopper: recipient!(addr, ExpiredCoresPackage<ClientRequestPayload_0v1>),
         inbound_server_data: recipient!(addr, InboundServerData),
         dns_resolve_failed: recipient!(addr, DnsResolveFailure_0v1),
+        configuration_change: recipient!(addr, ConfigurationChangeMessage),
         node_from_ui: recipient!(addr, NodeFromUiMessage),
     }
 }
@@ -389,13 +412,20 @@ pub fn make_neighborhood_subs_from(addr: &Addr<Recorder>) -> NeighborhoodSubs {
         update_node_record_metadata: recipient!(addr, NodeRecordMetadataMessage),
         from_hopper: recipient!(addr, ExpiredCoresPackage<Gossip_0v1>),
         gossip_failure: recipient!(addr, ExpiredCoresPackage<GossipFailure_0v1>),
+        delinquency_notice: recipient!(addr, ExpiredCoresPackage<DelinquencyNotice_0v1>),
+        notice_to_debtor_sub: recipient!(addr, NoticeToDebtor),
+        paying_wallet_sighting_sub: recipient!(addr, PayingWalletSighting),
+        routing_payer_sighting_sub: recipient!(addr, RoutingPayerSighting),
         dispatcher_node_query: recipient!(addr, DispatcherNodeQueryMessage),
         remove_neighbor: recipient!(addr, RemoveNeighborMessage),
         stream_shutdown_sub: recipient!(addr, StreamShutdownMsg),
         set_consuming_wallet_sub: recipient!(addr, SetConsumingWalletMessage),
         from_ui_message_sub: recipient!(addr, NodeFromUiMessage),
         new_password_sub: recipient!(addr, NewPasswordMessage),
+        new_earning_wallet_sub: recipient!(addr, NewEarningWalletMessage),
         connection_progress_sub: recipient!(addr, ConnectionProgressMessage),
+        shutdown_stage_complete_sub: recipient!(addr, ShutdownStageComplete),
+        configuration_change_sub: recipient!(addr, ConfigurationChangeMessage),
     }
 }
 
@@ -416,6 +446,9 @@ pub fn make_accountant_subs_from_recorder(addr: &Addr<Recorder>) -> AccountantSu
         report_sent_payments: recipient!(addr, SentPayables),
         scan_errors: recipient!(addr, ScanError),
         ui_message_sub: recipient!(addr, NodeFromUiMessage),
+        new_earning_wallet_sub: recipient!(addr, NewEarningWalletMessage),
+        settle_before_shutdown_sub: recipient!(addr, SettleBeforeShutdown),
+        configuration_change_sub: recipient!(addr, ConfigurationChangeMessage),
     }
 }
 
@@ -9,8 +9,8 @@ use futures::Future;
 use futures::Sink;
 use futures::Stream;
 use masq_lib::constants::UNMARSHAL_ERROR;
-use masq_lib::logger::Logger;
-use masq_lib::messages::{ToMessageBody, UiUnmarshalError, NODE_UI_PROTOCOL};
+use masq_lib::logger::{unsubscribe_from_log_stream, Logger};
+use masq_lib::messages::{ToMessageBody, UiClientDisconnected, UiUnmarshalError, NODE_UI_PROTOCOL};
 use masq_lib::ui_gateway::MessagePath::Conversation;
 use masq_lib::ui_gateway::MessageTarget::ClientId;
 use masq_lib::ui_gateway::{MessageBody, MessageTarget, NodeFromUiMessage, NodeToUiMessage};
@@ -143,6 +143,8 @@ impl WebSocketSupervisorReal {
                 if !clients.is_empty() {
                     clients
                 } else {
+                    // otherwise a log stream to a vanished client would feed on its own warnings
+                    unsubscribe_from_log_stream(n);
                     Self::log_absent_client(n);
                     return;
                 }
@@ -409,6 +411,13 @@ impl WebSocketSupervisorReal {
             locked_inner.port
         );
         Self::close_connection(&mut locked_inner, client_id, socket_addr, logger);
+        locked_inner
+            .from_ui_message_sub
+            .try_send(NodeFromUiMessage {
+                client_id,
+                body: UiClientDisconnected {}.tmb(0),
+            })
+            .expect("UiGateway is dead");
 
         err::<(), ()>(()) // end the stream
     }
@@ -500,6 +509,7 @@ impl WebSocketSupervisorReal {
             .client_id_by_socket_addr
             .remove(&socket_addr)
             .expectv("client id");
+        unsubscribe_from_log_stream(client_id);
     }
 
     fn handle_websocket_errors<I>(
@@ -528,6 +538,7 @@ impl WebSocketSupervisorReal {
         logger: &Logger,
     ) {
         let _ = locked_inner.socket_addr_by_client_id.remove(&client_id);
+        unsubscribe_from_log_stream(client_id);
         let mut client = match locked_inner.client_by_id.remove(&client_id) {
             Some(client) => client,
             None => panic!("WebSocketSupervisor got a disconnect from a client that has disappeared from the stable!"),
@@ -595,7 +606,11 @@ mod tests {
     use actix::{Actor, Addr};
     use crossbeam_channel::bounded;
     use futures::lazy;
+    use log::Level;
     use masq_lib::constants::UNMARSHAL_ERROR;
+    use masq_lib::logger::{
+        log_stream_subscription, subscribe_to_log_stream, LogStreamSubscription,
+    };
     use masq_lib::messages::{
         FromMessageBody, UiDescriptorResponse, UiShutdownRequest, UiStartOrder, UiUnmarshalError,
         NODE_UI_PROTOCOL,
@@ -965,9 +980,9 @@ mod tests {
             .unwrap();
         let another_close_msg = another_client.recv_message().unwrap();
 
-        ui_gateway_awaiter.await_message_count(3);
+        ui_gateway_awaiter.await_message_count(5);
         let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
-        let messages = (0..=2)
+        let messages = (0..=4)
             .map(|i| {
                 ui_gateway_recording
                     .get_record::<NodeFromUiMessage>(i)
@@ -1007,6 +1022,20 @@ mod tests {
                 },
             },
         );
+        assert_contains(
+            &messages,
+            &NodeFromUiMessage {
+                client_id: 0,
+                body: UiClientDisconnected {}.tmb(0),
+            },
+        );
+        assert_contains(
+            &messages,
+            &NodeFromUiMessage {
+                client_id: 1,
+                body: UiClientDisconnected {}.tmb(0),
+            },
+        );
         assert_eq!(one_close_msg, OwnedMessage::Close(None));
         assert_eq!(another_close_msg, OwnedMessage::Close(None));
     }
@@ -1325,7 +1354,7 @@ mod tests {
         client.send(UiStartOrder {});
 
         client.shutdown();
-        ui_gateway_awaiter.await_message_count(1);
+        ui_gateway_awaiter.await_message_count(2);
         thread::sleep(Duration::from_millis(500)); // make sure there's not another message sent
         let ui_gateway_recording = ui_gateway_recording_arc.lock().unwrap();
         assert_eq!(
@@ -1335,7 +1364,14 @@ mod tests {
                 body: UiShutdownRequest {}.tmb(0),
             }
         );
-        assert_eq!(ui_gateway_recording.len(), 1);
+        assert_eq!(
+            ui_gateway_recording.get_record::<NodeFromUiMessage>(1),
+            &NodeFromUiMessage {
+                client_id: 0,
+                body: UiClientDisconnected {}.tmb(0),
+            }
+        );
+        assert_eq!(ui_gateway_recording.len(), 2);
         let mail = rx.try_recv().unwrap();
         let inner_clone = mail.lock().unwrap();
         assert!(inner_clone.client_by_id.is_empty());
@@ -1550,6 +1586,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn send_msg_to_an_absent_client_drops_its_log_stream_subscription() {
+        let _system = System::new("send_msg_to_an_absent_client_drops_its_log_stream_subscription");
+        let client_id = 0x5ab5_e27;
+        subscribe_to_log_stream(LogStreamSubscription {
+            client_id,
+            min_level: Level::Warn,
+            modules: vec![],
+        });
+        let inner_arc = Arc::new(Mutex::new(make_ordinary_inner()));
+        let msg = NodeToUiMessage {
+            target: MessageTarget::ClientId(client_id),
+            body: MessageBody {
+                opcode: "logStream".to_string(),
+                path: FireAndForget,
+                payload: Ok("{}".to_string()),
+            },
+        };
+
+        WebSocketSupervisorReal::send_msg(&inner_arc, msg);
+
+        assert_eq!(log_stream_subscription(client_id), None);
+    }
+
     #[test]
     fn send_msg_fails_to_look_up_client_to_send_to() {
         init_test_logging();